@@ -58,6 +58,13 @@ main() {
   create_secret_client "opendut-cleo-client" "$OPENDUT_CLEO_NETWORK_OIDC_CLIENT_SECRET" "$REALM_OPENDUT"
   create_secret_client "opendut-edgar-client" "$OPENDUT_EDGAR_NETWORK_OIDC_CLIENT_SECRET" "$REALM_OPENDUT"
 
+  # Create openDuT roles, which CARL maps to its permissions (see `network.oidc.authorization` in the CARL configuration)
+  create_realm_role opendut-viewer "$REALM_OPENDUT"
+  create_realm_role opendut-operator "$REALM_OPENDUT"
+  create_realm_role opendut-admin "$REALM_OPENDUT"
+  kcadm add-roles -r "$REALM_OPENDUT" --uusername opendut --rolename opendut-admin
+  kcadm add-roles -r "$REALM_OPENDUT" --uusername service-account-opendut-cleo-client --rolename opendut-admin
+
   # Create keycloak client privileges for openDuT-CARL
   create_realm_role carl-admin "$REALM_OPENDUT"
   # Add role carl-admin to client opendut-carl-client
@@ -62,6 +62,15 @@ main() {
   create_secret_client "opendut-cleo-client" "918642e0-4ec4-4ef5-8ae0-ba92de7da3f9" "$REALM_OPENDUT"
   create_secret_client "opendut-edgar-client" "c7d6ace0-b90f-471a-bb62-a4ecac4150f8" "$REALM_OPENDUT"
 
+  # Create openDuT roles, which CARL maps to its permissions (see `network.oidc.authorization` in the CARL configuration)
+  create_realm_role opendut-viewer "$REALM_OPENDUT"
+  create_realm_role opendut-operator "$REALM_OPENDUT"
+  create_realm_role opendut-admin "$REALM_OPENDUT"
+  kcadm add-roles -r "$REALM_OPENDUT" --uusername opendut --rolename opendut-admin
+  kcadm add-roles -r "$REALM_OPENDUT" --uusername manager --rolename opendut-admin
+  kcadm add-roles -r "$REALM_OPENDUT" --uusername testuser --rolename opendut-operator
+  kcadm add-roles -r "$REALM_OPENDUT" --uusername service-account-opendut-cleo-client --rolename opendut-admin
+
   # Create keycloak client privileges for openDuT-CARL
   create_realm_role carl-admin "$REALM_OPENDUT"
   # Add role carl-admin to client opendut-carl-client
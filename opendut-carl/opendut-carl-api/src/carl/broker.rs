@@ -9,6 +9,14 @@ pub mod error {
     #[derive(thiserror::Error, Debug)]
     #[error("{message}")]
     pub struct OpenStream { pub message: String }
+
+    #[derive(thiserror::Error, Debug)]
+    #[error("{message}")]
+    pub struct StreamExecutorLogs { pub message: String }
+
+    #[derive(thiserror::Error, Debug)]
+    #[error("{message}")]
+    pub struct ExecuteExecutorCommand { pub message: String }
 }
 
 #[cfg(any(feature = "client", feature = "wasm-client"))]
@@ -16,6 +24,8 @@ mod client {
     use cfg_if::cfg_if;
     use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
 
+    use opendut_types::peer::executor::ExecutorCommand;
+    use opendut_types::peer::executor::run::ExecutorLogLine;
     use opendut_types::peer::PeerId;
     use opendut_types::proto::ConversionError;
 
@@ -80,6 +90,58 @@ mod client {
                 },
             }
         }
+
+        /// Streams the log of the latest run of the executor with the given name.
+        /// With `follow`, the stream stays open and yields new lines until the run finishes.
+        pub async fn stream_executor_logs(&mut self, peer_id: PeerId, executor_name: String, follow: bool) -> Result<ExecutorLogReceiver, error::StreamExecutorLogs> {
+            let request = tonic::Request::new(peer_messaging_broker::StreamExecutorLogsRequest {
+                peer_id: Some(peer_id.into()),
+                executor_name,
+                follow,
+            });
+
+            match self.inner.stream_executor_logs(request).await {
+                Ok(response) => Ok(ExecutorLogReceiver { inner: response.into_inner() }),
+                Err(status) => Err(error::StreamExecutorLogs { message: format!("gRPC failure: {}", status.message()) }),
+            }
+        }
+
+        /// Sends a command to the executor with the given name on a connected peer.
+        pub async fn execute_executor_command(&mut self, peer_id: PeerId, executor_name: String, command: ExecutorCommand) -> Result<(), error::ExecuteExecutorCommand> {
+            let request = tonic::Request::new(peer_messaging_broker::ExecuteExecutorCommandRequest {
+                peer_id: Some(peer_id.into()),
+                executor_name,
+                command: Some(command.into()),
+            });
+
+            match self.inner.execute_executor_command(request).await {
+                Ok(_) => Ok(()),
+                Err(status) => Err(error::ExecuteExecutorCommand { message: format!("gRPC failure: {}", status.message()) }),
+            }
+        }
+    }
+
+    pub struct ExecutorLogReceiver {
+        inner: tonic::Streaming<peer_messaging_broker::StreamExecutorLogsResponse>,
+    }
+
+    impl ExecutorLogReceiver {
+        /// Returns the next batch of log lines or `None`, when the stream has ended.
+        pub async fn next(&mut self) -> Result<Option<Vec<ExecutorLogLine>>, error::StreamExecutorLogs> {
+            let response = self.inner.message().await
+                .map_err(|status| error::StreamExecutorLogs { message: format!("gRPC failure: {}", status.message()) })?;
+
+            match response {
+                None => Ok(None),
+                Some(response) => {
+                    let lines = response.lines.into_iter()
+                        .map(ExecutorLogLine::try_from)
+                        .collect::<Result<Vec<_>, ConversionError>>()
+                        .map_err(|cause| error::StreamExecutorLogs { message: cause.to_string() })?;
+                    Ok(Some(lines))
+                }
+            }
+        }
     }
 
     cfg_if! {
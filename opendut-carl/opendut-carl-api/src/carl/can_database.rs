@@ -0,0 +1,170 @@
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+pub use client::*;
+use opendut_types::can::{CanDatabaseId, CanDatabaseName, CanDatabaseVersion};
+use opendut_types::peer::PeerId;
+
+#[derive(thiserror::Error, Debug)]
+pub enum StoreCanDatabaseError {
+    #[error("CAN database '{database_name}' <{database_id}> could not be stored, because version '{database_version}' is already registered as CAN database <{other_id}>!")]
+    AlreadyExists {
+        database_id: CanDatabaseId,
+        database_name: CanDatabaseName,
+        database_version: CanDatabaseVersion,
+        other_id: CanDatabaseId,
+    },
+    #[error("CAN database '{database_name}' <{database_id}> could not be stored, because its content is invalid:\n  {cause}")]
+    IllegalContent {
+        database_id: CanDatabaseId,
+        database_name: CanDatabaseName,
+        cause: String
+    },
+    #[error("CAN database '{database_name}' <{database_id}> could not be stored, due to internal errors:\n  {cause}")]
+    Internal {
+        database_id: CanDatabaseId,
+        database_name: CanDatabaseName,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeleteCanDatabaseError {
+    #[error("CAN database <{database_id}> could not be deleted, because a CAN database with that id does not exist!")]
+    CanDatabaseNotFound {
+        database_id: CanDatabaseId
+    },
+    #[error("CAN database '{database_name}' <{database_id}> cannot be deleted, because it is still referenced by the executors of the peers: {}", peer_ids.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    CanDatabaseInUse {
+        database_id: CanDatabaseId,
+        database_name: CanDatabaseName,
+        peer_ids: Vec<PeerId>,
+    },
+    #[error("CAN database <{database_id}> deleted with internal errors:\n  {cause}")]
+    Internal {
+        database_id: CanDatabaseId,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListCanDatabasesError {
+    #[error("An internal error occurred computing the list of CAN databases:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+mod client {
+    use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
+
+    use opendut_types::can::{CanDatabase, CanDatabaseDescriptor, CanDatabaseId};
+
+    use crate::carl::{ClientError, extract};
+    use crate::carl::can_database::{DeleteCanDatabaseError, ListCanDatabasesError, StoreCanDatabaseError};
+    use crate::proto::services::can_database_manager;
+    use crate::proto::services::can_database_manager::can_database_manager_client::CanDatabaseManagerClient;
+
+    #[derive(Clone, Debug)]
+    pub struct CanDatabaseManager<T> {
+        inner: CanDatabaseManagerClient<T>,
+    }
+
+    impl<T> CanDatabaseManager<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: CanDatabaseManagerClient<T>) -> CanDatabaseManager<T> {
+            CanDatabaseManager {
+                inner
+            }
+        }
+
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> CanDatabaseManager<InterceptedService<T, F>>
+            where
+                F: tonic::service::Interceptor,
+                T::ResponseBody: Default,
+                T: tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                    Response = http::Response<
+                        <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                    >,
+                >,
+                <T as tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                >>::Error: Into<StdError> + Send + Sync,
+        {
+            let inner_client = CanDatabaseManagerClient::new(InterceptedService::new(inner, interceptor));
+            CanDatabaseManager {
+                inner: inner_client
+            }
+        }
+
+        pub async fn store_can_database(&mut self, database: CanDatabase) -> Result<CanDatabaseId, ClientError<StoreCanDatabaseError>> {
+
+            let request = tonic::Request::new(can_database_manager::StoreCanDatabaseRequest {
+                database: Some(database.into()),
+            });
+
+            let response = self.inner.store_can_database(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                can_database_manager::store_can_database_response::Reply::Failure(failure) => {
+                    let error = StoreCanDatabaseError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                can_database_manager::store_can_database_response::Reply::Success(success) => {
+                    let database_id = extract!(success.database_id)?;
+                    Ok(database_id)
+                }
+            }
+        }
+
+        pub async fn delete_can_database(&mut self, database_id: CanDatabaseId) -> Result<CanDatabaseDescriptor, ClientError<DeleteCanDatabaseError>> {
+
+            let request = tonic::Request::new(can_database_manager::DeleteCanDatabaseRequest {
+                database_id: Some(database_id.into()),
+            });
+
+            let response = self.inner.delete_can_database(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                can_database_manager::delete_can_database_response::Reply::Failure(failure) => {
+                    let error = DeleteCanDatabaseError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                can_database_manager::delete_can_database_response::Reply::Success(success) => {
+                    let descriptor = extract!(success.descriptor)?;
+                    Ok(descriptor)
+                }
+            }
+        }
+
+        pub async fn list_can_databases(&mut self) -> Result<Vec<CanDatabaseDescriptor>, ClientError<ListCanDatabasesError>> {
+
+            let request = tonic::Request::new(can_database_manager::ListCanDatabasesRequest {});
+
+            let response = self.inner.list_can_databases(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                can_database_manager::list_can_databases_response::Reply::Failure(failure) => {
+                    let error = ListCanDatabasesError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                can_database_manager::list_can_databases_response::Reply::Success(success) => {
+                    Ok(success.descriptors.into_iter()
+                        .map(CanDatabaseDescriptor::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+    }
+}
@@ -1,8 +1,10 @@
 #[cfg(any(feature = "client", feature = "wasm-client"))]
 pub use client::*;
-use opendut_types::cluster::{ClusterId, ClusterName};
+use opendut_types::cluster::{CanFaultInjectionRuleId, ClusterId, ClusterName};
 use opendut_types::cluster::state::ClusterState;
+use opendut_types::peer::PeerId;
 use opendut_types::ShortName;
+use opendut_types::topology::DeviceId;
 
 #[derive(thiserror::Error, Debug)]
 pub enum CreateClusterConfigurationError {
@@ -13,6 +15,18 @@ pub enum CreateClusterConfigurationError {
         other_id: ClusterId,
         other_name: ClusterName
     },
+    #[error("ClusterConfigration '{cluster_name}' <{cluster_id}> could not be created, because its leader <{leader_id}> does not exist!")]
+    LeaderNotFound {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        leader_id: PeerId,
+    },
+    #[error("ClusterConfigration '{cluster_name}' <{cluster_id}> could not be created, because its device <{device_id}> does not exist!")]
+    DeviceNotFound {
+        cluster_id: ClusterId,
+        cluster_name: ClusterName,
+        device_id: DeviceId,
+    },
     #[error("ClusterConfigration '{cluster_name}' <{cluster_id}> could not be created, due to internal errors:\n  {cause}")]
     Internal {
         cluster_id: ClusterId,
@@ -54,8 +68,20 @@ pub struct ListClusterConfigurationsError {
     message: String,
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum TransferClusterConfigurationOwnershipError {
+    #[error("Ownership of ClusterConfiguration <{cluster_id}> could not be transferred, because a ClusterConfiguration with that id does not exist!")]
+    ClusterConfigurationNotFound {
+        cluster_id: ClusterId
+    },
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum StoreClusterDeploymentError {
+    #[error("ClusterDeployment for cluster <{cluster_id}> could not be stored, because a ClusterConfiguration with that id does not exist!")]
+    ClusterConfigurationNotFound {
+        cluster_id: ClusterId
+    },
     #[error("ClusterDeployment for cluster '{cluster_name}' <{cluster_id}> cannot be changed when cluster is in state '{}'! A cluster can be updated when: {}", actual_state.short_name(), ClusterState::short_names_joined(required_states))]
     IllegalClusterState {
         cluster_id: ClusterId,
@@ -98,12 +124,47 @@ pub struct ListClusterDeploymentsError {
     message: String,
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum StoreCanFaultInjectionRuleError {
+    #[error("CAN fault injection rule <{rule_id}> could not be stored, because a ClusterConfiguration with id <{cluster_id}> does not exist!")]
+    ClusterConfigurationNotFound {
+        rule_id: CanFaultInjectionRuleId,
+        cluster_id: ClusterId,
+    },
+    #[error("CAN fault injection rule <{rule_id}> could not be stored, due to internal errors:\n  {cause}")]
+    Internal {
+        rule_id: CanFaultInjectionRuleId,
+        cause: String,
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeleteCanFaultInjectionRuleError {
+    #[error("CAN fault injection rule <{rule_id}> could not be deleted, because a rule with that id does not exist!")]
+    RuleNotFound {
+        rule_id: CanFaultInjectionRuleId,
+    },
+    #[error("CAN fault injection rule <{rule_id}> deleted with internal errors:\n  {cause}")]
+    Internal {
+        rule_id: CanFaultInjectionRuleId,
+        cause: String,
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListCanFaultInjectionRulesError {
+    #[error("An internal error occurred computing the list of CAN fault injection rules:\n  {cause}")]
+    Internal {
+        cause: String,
+    }
+}
+
 
 #[cfg(any(feature = "client", feature = "wasm-client"))]
 mod client {
     use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
 
-    use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+    use opendut_types::cluster::{CanFaultInjectionRule, ClusterConfiguration, ClusterDeployment, ClusterId};
 
     use crate::carl::{ClientError, extract};
     use crate::proto::services::cluster_manager;
@@ -245,6 +306,29 @@ mod client {
             }
         }
 
+        pub async fn transfer_cluster_configuration_ownership(&mut self, cluster_id: ClusterId, owner: String, group: Option<String>) -> Result<ClusterId, ClientError<TransferClusterConfigurationOwnershipError>> {
+
+            let request = tonic::Request::new(cluster_manager::TransferClusterConfigurationOwnershipRequest {
+                cluster_id: Some(cluster_id.into()),
+                owner,
+                group,
+            });
+
+            let response = self.inner.transfer_cluster_configuration_ownership(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                cluster_manager::transfer_cluster_configuration_ownership_response::Reply::Failure(failure) => {
+                    let error = TransferClusterConfigurationOwnershipError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                cluster_manager::transfer_cluster_configuration_ownership_response::Reply::Success(success) => {
+                    let cluster_id = extract!(success.cluster_id)?;
+                    Ok(cluster_id)
+                }
+            }
+        }
+
         pub async fn store_cluster_deployment(&mut self, deployment: ClusterDeployment) -> Result<ClusterId, ClientError<StoreClusterDeploymentError>> {
 
             let request = tonic::Request::new(cluster_manager::StoreClusterDeploymentRequest {
@@ -311,5 +395,70 @@ mod client {
                 }
             }
         }
+
+        pub async fn store_can_fault_injection_rule(&mut self, rule: CanFaultInjectionRule) -> Result<CanFaultInjectionRuleId, ClientError<StoreCanFaultInjectionRuleError>> {
+
+            let request = tonic::Request::new(cluster_manager::StoreCanFaultInjectionRuleRequest {
+                rule: Some(rule.into()),
+            });
+
+            let response = self.inner.store_can_fault_injection_rule(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                cluster_manager::store_can_fault_injection_rule_response::Reply::Failure(failure) => {
+                    let error = StoreCanFaultInjectionRuleError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                cluster_manager::store_can_fault_injection_rule_response::Reply::Success(success) => {
+                    let rule_id = extract!(success.rule_id)?;
+                    Ok(rule_id)
+                }
+            }
+        }
+
+        pub async fn delete_can_fault_injection_rule(&mut self, rule_id: CanFaultInjectionRuleId) -> Result<CanFaultInjectionRule, ClientError<DeleteCanFaultInjectionRuleError>> {
+
+            let request = tonic::Request::new(cluster_manager::DeleteCanFaultInjectionRuleRequest {
+                rule_id: Some(rule_id.into()),
+            });
+
+            let response = self.inner.delete_can_fault_injection_rule(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                cluster_manager::delete_can_fault_injection_rule_response::Reply::Failure(failure) => {
+                    let error = DeleteCanFaultInjectionRuleError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                cluster_manager::delete_can_fault_injection_rule_response::Reply::Success(success) => {
+                    let rule = extract!(success.rule)?;
+                    Ok(rule)
+                }
+            }
+        }
+
+        pub async fn list_can_fault_injection_rules(&mut self, cluster_id: Option<ClusterId>) -> Result<Vec<CanFaultInjectionRule>, ClientError<ListCanFaultInjectionRulesError>> {
+
+            let request = tonic::Request::new(cluster_manager::ListCanFaultInjectionRulesRequest {
+                cluster_id: cluster_id.map(Into::into),
+            });
+
+            let response = self.inner.list_can_fault_injection_rules(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                cluster_manager::list_can_fault_injection_rules_response::Reply::Failure(failure) => {
+                    let error = ListCanFaultInjectionRulesError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                cluster_manager::list_can_fault_injection_rules_response::Reply::Success(success) => {
+                    Ok(success.rules.into_iter()
+                        .map(CanFaultInjectionRule::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
     }
 }
@@ -1,9 +1,13 @@
 use cfg_if::cfg_if;
 
+pub mod audit;
 pub mod broker;
+pub mod can_database;
 pub mod cluster;
 pub mod metadata;
 pub mod peer;
+pub mod secret;
+pub mod service_account;
 
 cfg_if! {
     if #[cfg(any(feature = "client", feature = "wasm-client"))] {
@@ -22,6 +26,8 @@ cfg_if! {
             InvalidRequest(String),
             #[error("{0}")]
             InvalidResponse(String),
+            #[error("Permission denied: {0}")]
+            PermissionDenied(String),
             #[error("{0}")]
             UsageError(A),
         }
@@ -35,6 +41,9 @@ cfg_if! {
                     tonic::Code::InvalidArgument => {
                         Self::InvalidRequest(status.message().to_owned())
                     }
+                    tonic::Code::PermissionDenied => {
+                        Self::PermissionDenied(status.message().to_owned())
+                    }
                     _ => {
                         Self::TransportError(status.message().to_owned())
                     }
@@ -109,24 +118,36 @@ cfg_if! {
         use opendut_auth::confidential::client::ConfidentialClient;
         use opendut_auth::confidential::tonic_service::TonicAuthenticationService;
 
+        use crate::carl::audit::AuditLog;
+        use crate::carl::can_database::CanDatabaseManager;
         use crate::carl::cluster::ClusterManager;
         use crate::carl::metadata::MetadataProvider;
         use crate::carl::peer::PeersRegistrar;
         use crate::carl::broker::PeerMessagingBroker;
+        use crate::carl::secret::SecretManager;
+        use crate::carl::service_account::ServiceAccountManager;
 
+        use crate::proto::services::audit_log::audit_log_client::AuditLogClient;
+        use crate::proto::services::can_database_manager::can_database_manager_client::CanDatabaseManagerClient;
         use crate::proto::services::cluster_manager::cluster_manager_client::ClusterManagerClient;
         use crate::proto::services::metadata_provider::metadata_provider_client::MetadataProviderClient;
         use crate::proto::services::peer_manager::peer_manager_client::PeerManagerClient;
         use crate::proto::services::peer_messaging_broker::peer_messaging_broker_client::PeerMessagingBrokerClient;
+        use crate::proto::services::secret_manager::secret_manager_client::SecretManagerClient;
+        use crate::proto::services::service_account_manager::service_account_manager_client::ServiceAccountManagerClient;
 
         use tower::ServiceBuilder;
 
         #[derive(Debug, Clone)]
         pub struct CarlClient {
+            pub audit: AuditLog<TonicAuthenticationService>,
             pub broker: PeerMessagingBroker<TonicAuthenticationService>,
+            pub can_databases: CanDatabaseManager<TonicAuthenticationService>,
             pub cluster: ClusterManager<TonicAuthenticationService>,
             pub metadata: MetadataProvider<TonicAuthenticationService>,
             pub peers: PeersRegistrar<TonicAuthenticationService>,
+            pub secrets: SecretManager<TonicAuthenticationService>,
+            pub service_accounts: ServiceAccountManager<TonicAuthenticationService>,
         }
 
         pub enum CaCertInfo {
@@ -166,6 +187,17 @@ cfg_if! {
                         debug!("Using override for verified domain name of '{domain_name_override}'.");
                         config = config.domain_name(domain_name_override);
                     }
+
+                    let client_certificate = settings.get_string("network.tls.client.certificate").ok().filter(|path| !path.is_empty());
+                    let client_key = settings.get_string("network.tls.client.key").ok().filter(|path| !path.is_empty());
+                    if let (Some(certificate_path), Some(key_path)) = (client_certificate, client_key) {
+                        debug!("Authenticating with TLS client certificate: {certificate_path}");
+                        let certificate = std::fs::read_to_string(&certificate_path)
+                            .map_err(|cause| InitializationError::TlsConfiguration { message: format!("Failed to read client certificate from path '{certificate_path}'"), cause: cause.into() })?;
+                        let key = std::fs::read_to_string(&key_path)
+                            .map_err(|cause| InitializationError::TlsConfiguration { message: format!("Failed to read client key from path '{key_path}'"), cause: cause.into() })?;
+                        config = config.identity(tonic::transport::Identity::from_pem(certificate, key));
+                    }
                     config
                 };
 
@@ -177,15 +209,23 @@ cfg_if! {
                     .tls_config(tls_config)
                     .map_err(|cause| InitializationError::TlsConfiguration { message: String::from("Failed to initialize secure channel with specified TLS configuration"), cause: cause.into() })?;
 
-                let oidc_client = ConfidentialClient::from_settings(settings).await
-                    .map_err(|cause| InitializationError::OidcConfiguration { message: String::from("Failed to initialize OIDC authentication manager"), cause: cause.into() })?;
-                match oidc_client {
-                    None => {}
-                    Some(ref client) => {
-                        client.check_login().await
-                        .map_err(|cause| InitializationError::ConnectError { address: address.clone(), cause: cause.into() })?;
+                let api_token = settings.get_string("network.api.token").ok().filter(|token| !token.is_empty());
+
+                let oidc_client = if api_token.is_some() {
+                    debug!("Authenticating with API token of a service account instead of OIDC.");
+                    None
+                } else {
+                    let oidc_client = ConfidentialClient::from_settings(settings).await
+                        .map_err(|cause| InitializationError::OidcConfiguration { message: String::from("Failed to initialize OIDC authentication manager"), cause: cause.into() })?;
+                    match oidc_client {
+                        None => {}
+                        Some(ref client) => {
+                            client.check_login().await
+                            .map_err(|cause| InitializationError::ConnectError { address: address.clone(), cause: cause.into() })?;
+                        }
                     }
-                }
+                    oidc_client
+                };
 
                 debug!("Set up endpoint for connection to CARL at '{address}'.");
                 let channel = endpoint.connect().await
@@ -193,14 +233,21 @@ cfg_if! {
                 info!("Connected to CARL at '{address}'.");
 
                 let auth_svc = ServiceBuilder::new()
-                    .layer_fn(|channel| TonicAuthenticationService::new(channel, oidc_client.clone()))
+                    .layer_fn(|channel| match &api_token {
+                        Some(api_token) => TonicAuthenticationService::with_api_token(channel, api_token.as_str()),
+                        None => TonicAuthenticationService::new(channel, oidc_client.clone()),
+                    })
                     .service(channel);
 
                 Ok(CarlClient {
+                    audit: AuditLog::new(AuditLogClient::new(Clone::clone(&auth_svc))),
                     broker: PeerMessagingBroker::new(PeerMessagingBrokerClient::new(Clone::clone(&auth_svc))),
+                    can_databases: CanDatabaseManager::new(CanDatabaseManagerClient::new(Clone::clone(&auth_svc))),
                     cluster: ClusterManager::new(ClusterManagerClient::new(Clone::clone(&auth_svc))),
                     metadata: MetadataProvider::new(MetadataProviderClient::new(Clone::clone(&auth_svc))),
                     peers: PeersRegistrar::new(PeerManagerClient::new(Clone::clone(&auth_svc))),
+                    secrets: SecretManager::new(SecretManagerClient::new(Clone::clone(&auth_svc))),
+                    service_accounts: ServiceAccountManager::new(ServiceAccountManagerClient::new(Clone::clone(&auth_svc))),
                 })
             }
         }
@@ -214,18 +261,26 @@ pub mod wasm {
 
     use opendut_auth::public::{Auth, AuthInterceptor, OptionalAuthData};
 
+    use crate::carl::audit::AuditLog;
     use crate::carl::broker::PeerMessagingBroker;
+    use crate::carl::can_database::CanDatabaseManager;
     use crate::carl::cluster::ClusterManager;
     use crate::carl::InitializationError;
     use crate::carl::metadata::MetadataProvider;
     use crate::carl::peer::PeersRegistrar;
+    use crate::carl::secret::SecretManager;
+    use crate::carl::service_account::ServiceAccountManager;
 
     #[derive(Debug, Clone)]
     pub struct CarlClient {
+        pub audit: AuditLog<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
         pub broker: PeerMessagingBroker<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
+        pub can_databases: CanDatabaseManager<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
         pub cluster: ClusterManager<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
         pub metadata: MetadataProvider<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
         pub peers: PeersRegistrar<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
+        pub secrets: SecretManager<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
+        pub service_accounts: ServiceAccountManager<InterceptedService<tonic_web_wasm_client::Client, AuthInterceptor>>,
     }
 
     impl CarlClient {
@@ -247,10 +302,14 @@ pub mod wasm {
             let auth_interceptor = AuthInterceptor::new(auth);
 
             Ok(CarlClient {
+                audit: AuditLog::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
                 broker: PeerMessagingBroker::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
+                can_databases: CanDatabaseManager::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
                 cluster: ClusterManager::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
                 metadata: MetadataProvider::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
                 peers: PeersRegistrar::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
+                secrets: SecretManager::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
+                service_accounts: ServiceAccountManager::with_interceptor(Clone::clone(&client), Clone::clone(&auth_interceptor)),
             })
         }
     }
@@ -1,7 +1,13 @@
 #[cfg(any(feature = "client", feature = "wasm-client"))]
 pub use client::*;
+use std::fmt;
+
+use opendut_types::can::CanDatabaseId;
+use opendut_types::cleo::CleoId;
 use opendut_types::peer::{PeerId, PeerName};
+use opendut_types::peer::executor::container::ContainerRegistryCredentialName;
 use opendut_types::peer::state::PeerState;
+use opendut_types::secret::SecretName;
 use opendut_types::ShortName;
 use opendut_types::topology::DeviceId;
 
@@ -20,6 +26,30 @@ pub enum StorePeerDescriptorError {
         peer_name: PeerName,
         error: IllegalDevicesError
     },
+    #[error("Peer '{peer_name}' <{peer_id}> could not be stored, because its executors reference CAN database <{database_id}>, which does not exist!")]
+    CanDatabaseNotFound {
+        peer_id: PeerId,
+        peer_name: PeerName,
+        database_id: CanDatabaseId,
+    },
+    #[error("Peer '{peer_name}' <{peer_id}> could not be stored, because its executors reference registry credential '{credential_name}', which is not configured in CARL!")]
+    RegistryCredentialNotFound {
+        peer_id: PeerId,
+        peer_name: PeerName,
+        credential_name: ContainerRegistryCredentialName,
+    },
+    #[error("Peer '{peer_name}' <{peer_id}> could not be stored, because its executors reference secret '{secret_name}', which does not exist in CARL!")]
+    SecretNotFound {
+        peer_id: PeerId,
+        peer_name: PeerName,
+        secret_name: SecretName,
+    },
+    #[error("Peer '{peer_name}' <{peer_id}> could not be stored, because its executors contain an illegal reference to a secret:\n  {cause}")]
+    IllegalSecretReference {
+        peer_id: PeerId,
+        peer_name: PeerName,
+        cause: String,
+    },
     #[error("Peer '{peer_name}' <{peer_id}> could not be created, due to internal errors:\n  {cause}")]
     Internal {
         peer_id: PeerId,
@@ -70,6 +100,60 @@ pub enum ListPeerDescriptorsError {
     }
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum TransferPeerOwnershipError {
+    #[error("Ownership of peer <{peer_id}> could not be transferred, because a peer with that id does not exist!")]
+    PeerNotFound {
+        peer_id: PeerId
+    },
+}
+
+/// Peer or CLEO instance, which CARL registered an OIDC client for, when generating its setup string.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CredentialsHolder {
+    Peer(PeerId),
+    Cleo(CleoId),
+}
+
+impl fmt::Display for CredentialsHolder {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CredentialsHolder::Peer(peer_id) => write!(f, "peer <{peer_id}>"),
+            CredentialsHolder::Cleo(cleo_id) => write!(f, "CLEO <{cleo_id}>"),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RotateClientSecretError {
+    #[error("Client secret of {holder} could not be rotated, because it has no OIDC client!")]
+    ClientNotFound {
+        holder: CredentialsHolder,
+    },
+    #[error("Client secret of peer <{peer_id}> was not rotated, because the peer is not connected and could not receive the new secret!")]
+    PeerNotConnected {
+        peer_id: PeerId,
+    },
+    #[error("An internal error occurred while rotating the client secret of {holder}:\n  {cause}")]
+    Internal {
+        holder: CredentialsHolder,
+        cause: String,
+    },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RevokeClientError {
+    #[error("Client of {holder} could not be revoked, because it has no OIDC client!")]
+    ClientNotFound {
+        holder: CredentialsHolder,
+    },
+    #[error("An internal error occurred while revoking the client of {holder}:\n  {cause}")]
+    Internal {
+        holder: CredentialsHolder,
+        cause: String,
+    },
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum ListDevicesError {
     #[error("An internal error occurred computing the list of devices:\n  {cause}")]
@@ -78,6 +162,22 @@ pub enum ListDevicesError {
     }
 }
 
+#[derive(thiserror::Error, Debug)]
+pub enum ListExecutorRunsError {
+    #[error("An internal error occurred computing the list of executor runs:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListResultArtifactsError {
+    #[error("An internal error occurred computing the list of result artifacts:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 #[error("{message}")]
 pub struct  CreateSetupError {
@@ -99,10 +199,14 @@ mod client {
     use opendut_types::cleo::CleoSetup;
 
     use opendut_types::peer::{PeerDescriptor, PeerId, PeerSetup};
+    use opendut_types::cluster::ClusterId;
+    use opendut_types::peer::executor::run::{ExecutorRun, ExecutorRunId, ResultArtifact};
     use opendut_types::topology::DeviceDescriptor;
 
+    use opendut_types::util::net::{ClientCredentials, ClientId};
+
     use crate::carl::{ClientError, extract};
-    use crate::carl::peer::{CreateSetupError, DeletePeerDescriptorError, GetPeerDescriptorError, ListDevicesError, ListPeerDescriptorsError, StorePeerDescriptorError};
+    use crate::carl::peer::{CreateSetupError, CredentialsHolder, DeletePeerDescriptorError, GetPeerDescriptorError, ListDevicesError, ListExecutorRunsError, ListPeerDescriptorsError, ListResultArtifactsError, RevokeClientError, RotateClientSecretError, StorePeerDescriptorError, TransferPeerOwnershipError};
     use crate::proto::services::peer_manager;
     use crate::proto::services::peer_manager::peer_manager_client::PeerManagerClient;
 
@@ -230,6 +334,127 @@ mod client {
             }
         }
 
+        pub async fn transfer_peer_ownership(&mut self, peer_id: PeerId, owner: String, group: Option<String>) -> Result<PeerId, ClientError<TransferPeerOwnershipError>> {
+
+            let request = tonic::Request::new(peer_manager::TransferPeerOwnershipRequest {
+                peer_id: Some(peer_id.into()),
+                owner,
+                group,
+            });
+
+            let response = self.inner.transfer_peer_ownership(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::transfer_peer_ownership_response::Reply::Failure(failure) => {
+                    let error = TransferPeerOwnershipError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::transfer_peer_ownership_response::Reply::Success(success) => {
+                    let peer_id = extract!(success.peer_id)?;
+                    Ok(peer_id)
+                }
+            }
+        }
+
+        /// Rotates the client secret of a peer or CLEO. Returns the new credentials of CLEO, whereas peers receive theirs from CARL directly.
+        pub async fn rotate_client_secret(&mut self, holder: CredentialsHolder) -> Result<Option<ClientCredentials>, ClientError<RotateClientSecretError>> {
+
+            let request = tonic::Request::new(peer_manager::RotateClientSecretRequest {
+                holder: Some(holder.into()),
+            });
+
+            let response = self.inner.rotate_client_secret(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::rotate_client_secret_response::Reply::Failure(failure) => {
+                    let error = RotateClientSecretError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::rotate_client_secret_response::Reply::Success(success) => {
+                    match (success.client_id, success.client_secret) {
+                        (Some(client_id), Some(client_secret)) => Ok(Some(ClientCredentials {
+                            client_id: client_id.try_into()?,
+                            client_secret: client_secret.try_into()?,
+                        })),
+                        _ => Ok(None),
+                    }
+                }
+            }
+        }
+
+        pub async fn revoke_client(&mut self, holder: CredentialsHolder) -> Result<Vec<ClientId>, ClientError<RevokeClientError>> {
+
+            let request = tonic::Request::new(peer_manager::RevokeClientRequest {
+                holder: Some(holder.into()),
+            });
+
+            let response = self.inner.revoke_client(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::revoke_client_response::Reply::Failure(failure) => {
+                    let error = RevokeClientError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::revoke_client_response::Reply::Success(success) => {
+                    let client_ids = success.client_ids.into_iter()
+                        .map(ClientId::try_from)
+                        .collect::<Result<Vec<_>, _>>()?;
+                    Ok(client_ids)
+                }
+            }
+        }
+
+        pub async fn list_executor_runs(&mut self, peer_id: Option<PeerId>) -> Result<Vec<ExecutorRun>, ClientError<ListExecutorRunsError>> {
+
+            let request = tonic::Request::new(peer_manager::ListExecutorRunsRequest {
+                peer_id: peer_id.map(Into::into),
+            });
+
+            let response = self.inner.list_executor_runs(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::list_executor_runs_response::Reply::Failure(failure) => {
+                    let error = ListExecutorRunsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::list_executor_runs_response::Reply::Success(success) => {
+                    Ok(success.runs.into_iter()
+                        .map(ExecutorRun::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
+        pub async fn list_result_artifacts(&mut self, peer_id: Option<PeerId>, cluster_id: Option<ClusterId>, run_id: Option<ExecutorRunId>) -> Result<Vec<ResultArtifact>, ClientError<ListResultArtifactsError>> {
+
+            let request = tonic::Request::new(peer_manager::ListResultArtifactsRequest {
+                peer_id: peer_id.map(Into::into),
+                cluster_id: cluster_id.map(Into::into),
+                run_id: run_id.map(Into::into),
+            });
+
+            let response = self.inner.list_result_artifacts(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                peer_manager::list_result_artifacts_response::Reply::Failure(failure) => {
+                    let error = ListResultArtifactsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                peer_manager::list_result_artifacts_response::Reply::Success(success) => {
+                    Ok(success.artifacts.into_iter()
+                        .map(ResultArtifact::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
         pub async fn create_peer_setup(&mut self, peer_id: PeerId, user_id: String) -> Result<PeerSetup, CreateSetupError> {
             let request = tonic::Request::new(
                 peer_manager::GeneratePeerSetupRequest {
@@ -0,0 +1,159 @@
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+pub use client::*;
+use opendut_types::peer::PeerId;
+use opendut_types::secret::SecretName;
+
+#[derive(thiserror::Error, Debug)]
+pub enum StoreSecretError {
+    #[error("Secret '{secret_name}' could not be stored, due to internal errors:\n  {cause}")]
+    Internal {
+        secret_name: SecretName,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeleteSecretError {
+    #[error("Secret '{secret_name}' could not be deleted, because a secret with that name does not exist!")]
+    SecretNotFound {
+        secret_name: SecretName
+    },
+    #[error("Secret '{secret_name}' cannot be deleted, because it is still referenced by the executors of the peers: {}", peer_ids.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "))]
+    SecretInUse {
+        secret_name: SecretName,
+        peer_ids: Vec<PeerId>,
+    },
+    #[error("Secret '{secret_name}' deleted with internal errors:\n  {cause}")]
+    Internal {
+        secret_name: SecretName,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListSecretsError {
+    #[error("An internal error occurred computing the list of secrets:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+mod client {
+    use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
+
+    use opendut_types::secret::{Secret, SecretName};
+
+    use crate::carl::{ClientError, extract};
+    use crate::carl::secret::{DeleteSecretError, ListSecretsError, StoreSecretError};
+    use crate::proto::services::secret_manager;
+    use crate::proto::services::secret_manager::secret_manager_client::SecretManagerClient;
+
+    #[derive(Clone, Debug)]
+    pub struct SecretManager<T> {
+        inner: SecretManagerClient<T>,
+    }
+
+    impl<T> SecretManager<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: SecretManagerClient<T>) -> SecretManager<T> {
+            SecretManager {
+                inner
+            }
+        }
+
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> SecretManager<InterceptedService<T, F>>
+            where
+                F: tonic::service::Interceptor,
+                T::ResponseBody: Default,
+                T: tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                    Response = http::Response<
+                        <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                    >,
+                >,
+                <T as tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                >>::Error: Into<StdError> + Send + Sync,
+        {
+            let inner_client = SecretManagerClient::new(InterceptedService::new(inner, interceptor));
+            SecretManager {
+                inner: inner_client
+            }
+        }
+
+        /// Stores the secret, replacing the value of an existing secret with the same name.
+        pub async fn store_secret(&mut self, secret: Secret, owner: Option<String>, group: Option<String>) -> Result<SecretName, ClientError<StoreSecretError>> {
+
+            let request = tonic::Request::new(secret_manager::StoreSecretRequest {
+                secret: Some(secret.into()),
+                owner,
+                group,
+            });
+
+            let response = self.inner.store_secret(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                secret_manager::store_secret_response::Reply::Failure(failure) => {
+                    let error = StoreSecretError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                secret_manager::store_secret_response::Reply::Success(success) => {
+                    let secret_name = extract!(success.secret_name)?;
+                    Ok(secret_name)
+                }
+            }
+        }
+
+        pub async fn delete_secret(&mut self, secret_name: SecretName) -> Result<SecretName, ClientError<DeleteSecretError>> {
+
+            let request = tonic::Request::new(secret_manager::DeleteSecretRequest {
+                secret_name: Some(secret_name.into()),
+            });
+
+            let response = self.inner.delete_secret(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                secret_manager::delete_secret_response::Reply::Failure(failure) => {
+                    let error = DeleteSecretError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                secret_manager::delete_secret_response::Reply::Success(success) => {
+                    let secret_name = extract!(success.secret_name)?;
+                    Ok(secret_name)
+                }
+            }
+        }
+
+        /// Lists the names of all secrets. Their values are never returned.
+        pub async fn list_secrets(&mut self) -> Result<Vec<SecretName>, ClientError<ListSecretsError>> {
+
+            let request = tonic::Request::new(secret_manager::ListSecretsRequest {});
+
+            let response = self.inner.list_secrets(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                secret_manager::list_secrets_response::Reply::Failure(failure) => {
+                    let error = ListSecretsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                secret_manager::list_secrets_response::Reply::Success(success) => {
+                    Ok(success.secret_names.into_iter()
+                        .map(SecretName::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,263 @@
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+pub use client::*;
+use opendut_types::service_account::{ApiTokenId, ServiceAccountId, ServiceAccountName};
+
+#[derive(thiserror::Error, Debug)]
+pub enum CreateServiceAccountError {
+    #[error("Service account '{name}' could not be created, because the name is already used by service account <{other_id}>!")]
+    AlreadyExists {
+        name: ServiceAccountName,
+        other_id: ServiceAccountId,
+    },
+    #[error("Service account '{name}' could not be created, due to internal errors:\n  {cause}")]
+    Internal {
+        name: ServiceAccountName,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum DeleteServiceAccountError {
+    #[error("Service account <{service_account_id}> could not be deleted, because a service account with that id does not exist!")]
+    ServiceAccountNotFound {
+        service_account_id: ServiceAccountId
+    },
+    #[error("Service account <{service_account_id}> deleted with internal errors:\n  {cause}")]
+    Internal {
+        service_account_id: ServiceAccountId,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListServiceAccountsError {
+    #[error("An internal error occurred computing the list of service accounts:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum CreateApiTokenError {
+    #[error("API token could not be created, because a service account with id <{service_account_id}> does not exist!")]
+    ServiceAccountNotFound {
+        service_account_id: ServiceAccountId
+    },
+    #[error("API token for service account <{service_account_id}> could not be created, due to internal errors:\n  {cause}")]
+    Internal {
+        service_account_id: ServiceAccountId,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RevokeApiTokenError {
+    #[error("API token <{token_id}> could not be revoked, because a token with that id does not exist!")]
+    ApiTokenNotFound {
+        token_id: ApiTokenId
+    },
+    #[error("API token <{token_id}> revoked with internal errors:\n  {cause}")]
+    Internal {
+        token_id: ApiTokenId,
+        cause: String
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ListApiTokensError {
+    #[error("An internal error occurred computing the list of API tokens:\n  {cause}")]
+    Internal {
+        cause: String
+    }
+}
+
+#[cfg(any(feature = "client", feature = "wasm-client"))]
+mod client {
+    use std::time::Duration;
+
+    use tonic::codegen::{Body, Bytes, http, InterceptedService, StdError};
+
+    use opendut_types::service_account::{ApiToken, ApiTokenDescriptor, ApiTokenId, ServiceAccountDescriptor, ServiceAccountId, ServiceAccountName, ServiceAccountRole};
+
+    use crate::carl::{ClientError, extract};
+    use crate::carl::service_account::{CreateApiTokenError, CreateServiceAccountError, DeleteServiceAccountError, ListApiTokensError, ListServiceAccountsError, RevokeApiTokenError};
+    use crate::proto::services::service_account_manager;
+    use crate::proto::services::service_account_manager::service_account_manager_client::ServiceAccountManagerClient;
+
+    #[derive(Clone, Debug)]
+    pub struct ServiceAccountManager<T> {
+        inner: ServiceAccountManagerClient<T>,
+    }
+
+    impl<T> ServiceAccountManager<T>
+    where T: tonic::client::GrpcService<tonic::body::BoxBody>,
+          T::Error: Into<StdError>,
+          T::ResponseBody: Body<Data=Bytes> + Send + 'static,
+          <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: ServiceAccountManagerClient<T>) -> ServiceAccountManager<T> {
+            ServiceAccountManager {
+                inner
+            }
+        }
+
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> ServiceAccountManager<InterceptedService<T, F>>
+            where
+                F: tonic::service::Interceptor,
+                T::ResponseBody: Default,
+                T: tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                    Response = http::Response<
+                        <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                    >,
+                >,
+                <T as tonic::codegen::Service<
+                    http::Request<tonic::body::BoxBody>,
+                >>::Error: Into<StdError> + Send + Sync,
+        {
+            let inner_client = ServiceAccountManagerClient::new(InterceptedService::new(inner, interceptor));
+            ServiceAccountManager {
+                inner: inner_client
+            }
+        }
+
+        pub async fn create_service_account(&mut self, name: ServiceAccountName, role: ServiceAccountRole, group: Option<String>) -> Result<ServiceAccountDescriptor, ClientError<CreateServiceAccountError>> {
+
+            let request = tonic::Request::new(service_account_manager::CreateServiceAccountRequest {
+                name: Some(name.into()),
+                role: Some(role.into()),
+                group,
+            });
+
+            let response = self.inner.create_service_account(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                service_account_manager::create_service_account_response::Reply::Failure(failure) => {
+                    let error = CreateServiceAccountError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                service_account_manager::create_service_account_response::Reply::Success(success) => {
+                    let descriptor = extract!(success.descriptor)?;
+                    Ok(descriptor)
+                }
+            }
+        }
+
+        /// Deletes the service account and all of its API tokens.
+        pub async fn delete_service_account(&mut self, service_account_id: ServiceAccountId) -> Result<ServiceAccountDescriptor, ClientError<DeleteServiceAccountError>> {
+
+            let request = tonic::Request::new(service_account_manager::DeleteServiceAccountRequest {
+                service_account_id: Some(service_account_id.into()),
+            });
+
+            let response = self.inner.delete_service_account(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                service_account_manager::delete_service_account_response::Reply::Failure(failure) => {
+                    let error = DeleteServiceAccountError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                service_account_manager::delete_service_account_response::Reply::Success(success) => {
+                    let descriptor = extract!(success.descriptor)?;
+                    Ok(descriptor)
+                }
+            }
+        }
+
+        pub async fn list_service_accounts(&mut self) -> Result<Vec<ServiceAccountDescriptor>, ClientError<ListServiceAccountsError>> {
+
+            let request = tonic::Request::new(service_account_manager::ListServiceAccountsRequest {});
+
+            let response = self.inner.list_service_accounts(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                service_account_manager::list_service_accounts_response::Reply::Failure(failure) => {
+                    let error = ListServiceAccountsError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                service_account_manager::list_service_accounts_response::Reply::Success(success) => {
+                    Ok(success.service_accounts.into_iter()
+                        .map(ServiceAccountDescriptor::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+
+        /// Creates an API token, which expires after the given lifetime.
+        /// The token itself is only returned by this call, CARL does not store it.
+        pub async fn create_api_token(&mut self, service_account_id: ServiceAccountId, lifetime: Duration) -> Result<(ApiTokenDescriptor, ApiToken), ClientError<CreateApiTokenError>> {
+
+            let request = tonic::Request::new(service_account_manager::CreateApiTokenRequest {
+                service_account_id: Some(service_account_id.into()),
+                lifetime_seconds: lifetime.as_secs(),
+            });
+
+            let response = self.inner.create_api_token(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                service_account_manager::create_api_token_response::Reply::Failure(failure) => {
+                    let error = CreateApiTokenError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                service_account_manager::create_api_token_response::Reply::Success(success) => {
+                    let descriptor = extract!(success.descriptor)?;
+                    let token = extract!(success.token)?;
+                    Ok((descriptor, token))
+                }
+            }
+        }
+
+        pub async fn revoke_api_token(&mut self, token_id: ApiTokenId) -> Result<ApiTokenDescriptor, ClientError<RevokeApiTokenError>> {
+
+            let request = tonic::Request::new(service_account_manager::RevokeApiTokenRequest {
+                token_id: Some(token_id.into()),
+            });
+
+            let response = self.inner.revoke_api_token(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                service_account_manager::revoke_api_token_response::Reply::Failure(failure) => {
+                    let error = RevokeApiTokenError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                service_account_manager::revoke_api_token_response::Reply::Success(success) => {
+                    let descriptor = extract!(success.descriptor)?;
+                    Ok(descriptor)
+                }
+            }
+        }
+
+        /// Lists the API tokens of all service accounts or only of the given one.
+        pub async fn list_api_tokens(&mut self, service_account_id: Option<ServiceAccountId>) -> Result<Vec<ApiTokenDescriptor>, ClientError<ListApiTokensError>> {
+
+            let request = tonic::Request::new(service_account_manager::ListApiTokensRequest {
+                service_account_id: service_account_id.map(Into::into),
+            });
+
+            let response = self.inner.list_api_tokens(request).await?
+                .into_inner();
+
+            match extract!(response.reply)? {
+                service_account_manager::list_api_tokens_response::Reply::Failure(failure) => {
+                    let error = ListApiTokensError::try_from(failure)?;
+                    Err(ClientError::UsageError(error))
+                }
+                service_account_manager::list_api_tokens_response::Reply::Success(success) => {
+                    Ok(success.tokens.into_iter()
+                        .map(ApiTokenDescriptor::try_from)
+                        .collect::<Result<Vec<_>, _>>()?
+                    )
+                }
+            }
+        }
+    }
+}
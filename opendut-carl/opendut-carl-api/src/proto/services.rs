@@ -1,10 +1,279 @@
+pub mod audit_log {
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+
+    use crate::carl::audit::ListAuditEventsError;
+
+    tonic::include_proto!("opendut.carl.services.audit_log");
+
+    impl From<ListAuditEventsError> for ListAuditEventsFailure {
+        fn from(error: ListAuditEventsError) -> Self {
+            let proto_error = match error {
+                ListAuditEventsError::Internal { cause } => {
+                    list_audit_events_failure::Error::Internal(ListAuditEventsFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ListAuditEventsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListAuditEventsFailure> for ListAuditEventsError {
+        type Error = ConversionError;
+        fn try_from(failure: ListAuditEventsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListAuditEventsFailure, ListAuditEventsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_audit_events_failure::Error::Internal(failure) => {
+                    ListAuditEventsError::Internal { cause: failure.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+}
+
+pub mod can_database_manager {
+    use opendut_types::can::{CanDatabaseId, CanDatabaseName, CanDatabaseVersion};
+    use opendut_types::proto;
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+
+    use crate::carl::can_database::{DeleteCanDatabaseError, ListCanDatabasesError, StoreCanDatabaseError};
+
+    tonic::include_proto!("opendut.carl.services.can_database_manager");
+
+    impl From<StoreCanDatabaseError> for StoreCanDatabaseFailure {
+        fn from(error: StoreCanDatabaseError) -> Self {
+            let proto_error = match error {
+                StoreCanDatabaseError::AlreadyExists { database_id, database_name, database_version, other_id } => {
+                    store_can_database_failure::Error::AlreadyExists(StoreCanDatabaseFailureAlreadyExists {
+                        database_id: Some(database_id.into()),
+                        database_name: Some(database_name.into()),
+                        database_version: Some(database_version.into()),
+                        other_id: Some(other_id.into()),
+                    })
+                }
+                StoreCanDatabaseError::IllegalContent { database_id, database_name, cause } => {
+                    store_can_database_failure::Error::IllegalContent(StoreCanDatabaseFailureIllegalContent {
+                        database_id: Some(database_id.into()),
+                        database_name: Some(database_name.into()),
+                        cause
+                    })
+                }
+                StoreCanDatabaseError::Internal { database_id, database_name, cause } => {
+                    store_can_database_failure::Error::Internal(StoreCanDatabaseFailureInternal {
+                        database_id: Some(database_id.into()),
+                        database_name: Some(database_name.into()),
+                        cause
+                    })
+                }
+            };
+            StoreCanDatabaseFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<StoreCanDatabaseFailure> for StoreCanDatabaseError {
+        type Error = ConversionError;
+        fn try_from(failure: StoreCanDatabaseFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StoreCanDatabaseFailure, StoreCanDatabaseError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                store_can_database_failure::Error::AlreadyExists(error) => {
+                    error.try_into()?
+                }
+                store_can_database_failure::Error::IllegalContent(error) => {
+                    error.try_into()?
+                }
+                store_can_database_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<StoreCanDatabaseFailureAlreadyExists> for StoreCanDatabaseError {
+        type Error = ConversionError;
+        fn try_from(failure: StoreCanDatabaseFailureAlreadyExists) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StoreCanDatabaseFailureAlreadyExists, StoreCanDatabaseError>;
+            let database_id: CanDatabaseId = failure.database_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("database_id"))?
+                .try_into()?;
+            let database_name: CanDatabaseName = failure.database_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("database_name"))?
+                .try_into()?;
+            let database_version: CanDatabaseVersion = failure.database_version
+                .ok_or_else(|| ErrorBuilder::field_not_set("database_version"))?
+                .try_into()?;
+            let other_id: CanDatabaseId = failure.other_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("other_id"))?
+                .try_into()?;
+            Ok(StoreCanDatabaseError::AlreadyExists { database_id, database_name, database_version, other_id })
+        }
+    }
+
+    impl TryFrom<StoreCanDatabaseFailureIllegalContent> for StoreCanDatabaseError {
+        type Error = ConversionError;
+        fn try_from(failure: StoreCanDatabaseFailureIllegalContent) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StoreCanDatabaseFailureIllegalContent, StoreCanDatabaseError>;
+            let database_id: CanDatabaseId = failure.database_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("database_id"))?
+                .try_into()?;
+            let database_name: CanDatabaseName = failure.database_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("database_name"))?
+                .try_into()?;
+            Ok(StoreCanDatabaseError::IllegalContent { database_id, database_name, cause: failure.cause })
+        }
+    }
+
+    impl TryFrom<StoreCanDatabaseFailureInternal> for StoreCanDatabaseError {
+        type Error = ConversionError;
+        fn try_from(failure: StoreCanDatabaseFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StoreCanDatabaseFailureInternal, StoreCanDatabaseError>;
+            let database_id: CanDatabaseId = failure.database_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("database_id"))?
+                .try_into()?;
+            let database_name: CanDatabaseName = failure.database_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("database_name"))?
+                .try_into()?;
+            Ok(StoreCanDatabaseError::Internal { database_id, database_name, cause: failure.cause })
+        }
+    }
+
+    impl From<DeleteCanDatabaseError> for DeleteCanDatabaseFailure {
+        fn from(error: DeleteCanDatabaseError) -> Self {
+            let proto_error = match error {
+                DeleteCanDatabaseError::CanDatabaseNotFound { database_id } => {
+                    delete_can_database_failure::Error::NotFound(DeleteCanDatabaseFailureNotFound {
+                        database_id: Some(database_id.into()),
+                    })
+                }
+                DeleteCanDatabaseError::CanDatabaseInUse { database_id, database_name, peer_ids } => {
+                    delete_can_database_failure::Error::InUse(DeleteCanDatabaseFailureInUse {
+                        database_id: Some(database_id.into()),
+                        database_name: Some(database_name.into()),
+                        peer_ids: peer_ids.into_iter().map(Into::into).collect(),
+                    })
+                }
+                DeleteCanDatabaseError::Internal { database_id, cause } => {
+                    delete_can_database_failure::Error::Internal(DeleteCanDatabaseFailureInternal {
+                        database_id: Some(database_id.into()),
+                        cause
+                    })
+                }
+            };
+            DeleteCanDatabaseFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DeleteCanDatabaseFailure> for DeleteCanDatabaseError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteCanDatabaseFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteCanDatabaseFailure, DeleteCanDatabaseError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                delete_can_database_failure::Error::NotFound(error) => {
+                    error.try_into()?
+                }
+                delete_can_database_failure::Error::InUse(error) => {
+                    error.try_into()?
+                }
+                delete_can_database_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<DeleteCanDatabaseFailureNotFound> for DeleteCanDatabaseError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteCanDatabaseFailureNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteCanDatabaseFailureNotFound, DeleteCanDatabaseError>;
+            let database_id: CanDatabaseId = failure.database_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("database_id"))?
+                .try_into()?;
+            Ok(DeleteCanDatabaseError::CanDatabaseNotFound { database_id })
+        }
+    }
+
+    impl TryFrom<DeleteCanDatabaseFailureInUse> for DeleteCanDatabaseError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteCanDatabaseFailureInUse) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteCanDatabaseFailureInUse, DeleteCanDatabaseError>;
+            let database_id: CanDatabaseId = failure.database_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("database_id"))?
+                .try_into()?;
+            let database_name: CanDatabaseName = failure.database_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("database_name"))?
+                .try_into()?;
+            let peer_ids = failure.peer_ids.into_iter()
+                .map(proto::peer::PeerId::try_into)
+                .collect::<Result<_, _>>()?;
+            Ok(DeleteCanDatabaseError::CanDatabaseInUse { database_id, database_name, peer_ids })
+        }
+    }
+
+    impl TryFrom<DeleteCanDatabaseFailureInternal> for DeleteCanDatabaseError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteCanDatabaseFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteCanDatabaseFailureInternal, DeleteCanDatabaseError>;
+            let database_id: CanDatabaseId = failure.database_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("database_id"))?
+                .try_into()?;
+            Ok(DeleteCanDatabaseError::Internal { database_id, cause: failure.cause })
+        }
+    }
+
+    impl From<ListCanDatabasesError> for ListCanDatabasesFailure {
+        fn from(error: ListCanDatabasesError) -> Self {
+            let proto_error = match error {
+                ListCanDatabasesError::Internal { cause } => {
+                    list_can_databases_failure::Error::Internal(ListCanDatabasesFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ListCanDatabasesFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListCanDatabasesFailure> for ListCanDatabasesError {
+        type Error = ConversionError;
+        fn try_from(failure: ListCanDatabasesFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListCanDatabasesFailure, ListCanDatabasesError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_can_databases_failure::Error::Internal(failure) => {
+                    ListCanDatabasesError::Internal { cause: failure.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+}
+
 pub mod cluster_manager {
-    use opendut_types::cluster::{ClusterId, ClusterName};
+    use opendut_types::cluster::{CanFaultInjectionRuleId, ClusterId, ClusterName};
     use opendut_types::cluster::state::ClusterState;
+    use opendut_types::peer::PeerId;
+    use opendut_types::topology::DeviceId;
     use opendut_types::proto;
     use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
 
-    use crate::carl::cluster::{CreateClusterConfigurationError, DeleteClusterConfigurationError, DeleteClusterDeploymentError, StoreClusterDeploymentError};
+    use crate::carl::cluster::{CreateClusterConfigurationError, DeleteCanFaultInjectionRuleError, DeleteClusterConfigurationError, DeleteClusterDeploymentError, ListCanFaultInjectionRulesError, StoreCanFaultInjectionRuleError, StoreClusterDeploymentError, TransferClusterConfigurationOwnershipError};
 
     tonic::include_proto!("opendut.carl.services.cluster_manager");
 
@@ -19,6 +288,20 @@ pub mod cluster_manager {
                         other_name: Some(other_name.into()),
                     })
                 }
+                CreateClusterConfigurationError::LeaderNotFound { cluster_id, cluster_name, leader_id } => {
+                    create_cluster_configuration_failure::Error::LeaderNotFound(CreateClusterConfigurationFailureLeaderNotFound {
+                        cluster_id: Some(cluster_id.into()),
+                        cluster_name: Some(cluster_name.into()),
+                        leader_id: Some(leader_id.into()),
+                    })
+                }
+                CreateClusterConfigurationError::DeviceNotFound { cluster_id, cluster_name, device_id } => {
+                    create_cluster_configuration_failure::Error::DeviceNotFound(CreateClusterConfigurationFailureDeviceNotFound {
+                        cluster_id: Some(cluster_id.into()),
+                        cluster_name: Some(cluster_name.into()),
+                        device_id: Some(device_id.into()),
+                    })
+                }
                 CreateClusterConfigurationError::Internal { cluster_id, cluster_name, cause } => {
                     create_cluster_configuration_failure::Error::Internal(CreateClusterConfigurationFailureInternal {
                         cluster_id: Some(cluster_id.into()),
@@ -46,6 +329,12 @@ pub mod cluster_manager {
                 create_cluster_configuration_failure::Error::Internal(error) => {
                     error.try_into()?
                 }
+                create_cluster_configuration_failure::Error::LeaderNotFound(error) => {
+                    error.try_into()?
+                }
+                create_cluster_configuration_failure::Error::DeviceNotFound(error) => {
+                    error.try_into()?
+                }
             };
             Ok(error)
         }
@@ -71,6 +360,40 @@ pub mod cluster_manager {
         }
     }
 
+    impl TryFrom<CreateClusterConfigurationFailureLeaderNotFound> for CreateClusterConfigurationError {
+        type Error = ConversionError;
+        fn try_from(failure: CreateClusterConfigurationFailureLeaderNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreateClusterConfigurationFailureLeaderNotFound, CreateClusterConfigurationError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            let cluster_name: ClusterName = failure.cluster_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+                .try_into()?;
+            let leader_id: PeerId = failure.leader_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("leader_id"))?
+                .try_into()?;
+            Ok(CreateClusterConfigurationError::LeaderNotFound { cluster_id, cluster_name, leader_id })
+        }
+    }
+
+    impl TryFrom<CreateClusterConfigurationFailureDeviceNotFound> for CreateClusterConfigurationError {
+        type Error = ConversionError;
+        fn try_from(failure: CreateClusterConfigurationFailureDeviceNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreateClusterConfigurationFailureDeviceNotFound, CreateClusterConfigurationError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            let cluster_name: ClusterName = failure.cluster_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_name"))?
+                .try_into()?;
+            let device_id: DeviceId = failure.device_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("device_id"))?
+                .try_into()?;
+            Ok(CreateClusterConfigurationError::DeviceNotFound { cluster_id, cluster_name, device_id })
+        }
+    }
+
     impl TryFrom<CreateClusterConfigurationFailureInternal> for CreateClusterConfigurationError {
         type Error = ConversionError;
         fn try_from(failure: CreateClusterConfigurationFailureInternal) -> Result<Self, Self::Error> {
@@ -181,9 +504,55 @@ pub mod cluster_manager {
         }
     }
 
+    impl From<TransferClusterConfigurationOwnershipError> for TransferClusterConfigurationOwnershipFailure {
+        fn from(error: TransferClusterConfigurationOwnershipError) -> Self {
+            let proto_error = match error {
+                TransferClusterConfigurationOwnershipError::ClusterConfigurationNotFound { cluster_id } => {
+                    transfer_cluster_configuration_ownership_failure::Error::ClusterConfigurationNotFound(TransferClusterConfigurationOwnershipFailureClusterConfigurationNotFound {
+                        cluster_id: Some(cluster_id.into())
+                    })
+                }
+            };
+            TransferClusterConfigurationOwnershipFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<TransferClusterConfigurationOwnershipFailure> for TransferClusterConfigurationOwnershipError {
+        type Error = ConversionError;
+        fn try_from(failure: TransferClusterConfigurationOwnershipFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<TransferClusterConfigurationOwnershipFailure, TransferClusterConfigurationOwnershipError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                transfer_cluster_configuration_ownership_failure::Error::ClusterConfigurationNotFound(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<TransferClusterConfigurationOwnershipFailureClusterConfigurationNotFound> for TransferClusterConfigurationOwnershipError {
+        type Error = ConversionError;
+        fn try_from(failure: TransferClusterConfigurationOwnershipFailureClusterConfigurationNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<TransferClusterConfigurationOwnershipFailureClusterConfigurationNotFound, TransferClusterConfigurationOwnershipError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            Ok(TransferClusterConfigurationOwnershipError::ClusterConfigurationNotFound { cluster_id })
+        }
+    }
+
     impl From<StoreClusterDeploymentError> for StoreClusterDeploymentFailure {
         fn from(error: StoreClusterDeploymentError) -> Self {
             let proto_error = match error {
+                StoreClusterDeploymentError::ClusterConfigurationNotFound { cluster_id } => {
+                    store_cluster_deployment_failure::Error::ClusterConfigurationNotFound(StoreClusterDeploymentFailureClusterConfigurationNotFound {
+                        cluster_id: Some(cluster_id.into()),
+                    })
+                }
                 StoreClusterDeploymentError::IllegalClusterState { cluster_id, cluster_name, actual_state, required_states } => {
                     store_cluster_deployment_failure::Error::IllegalClusterState(StoreClusterDeploymentFailureIllegalClusterState {
                         cluster_id: Some(cluster_id.into()),
@@ -213,6 +582,9 @@ pub mod cluster_manager {
             let error = failure.error
                 .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
             let error = match error {
+                store_cluster_deployment_failure::Error::ClusterConfigurationNotFound(error) => {
+                    error.try_into()?
+                }
                 store_cluster_deployment_failure::Error::IllegalClusterState(error) => {
                     error.try_into()?
                 }
@@ -224,6 +596,17 @@ pub mod cluster_manager {
         }
     }
 
+    impl TryFrom<StoreClusterDeploymentFailureClusterConfigurationNotFound> for StoreClusterDeploymentError {
+        type Error = ConversionError;
+        fn try_from(failure: StoreClusterDeploymentFailureClusterConfigurationNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StoreClusterDeploymentFailureClusterConfigurationNotFound, StoreClusterDeploymentError>;
+            let cluster_id: ClusterId = failure.cluster_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                .try_into()?;
+            Ok(StoreClusterDeploymentError::ClusterConfigurationNotFound { cluster_id })
+        }
+    }
+
     impl TryFrom<StoreClusterDeploymentFailureIllegalClusterState> for StoreClusterDeploymentError {
         type Error = ConversionError;
         fn try_from(failure: StoreClusterDeploymentFailureIllegalClusterState) -> Result<Self, Self::Error> {
@@ -354,40 +737,195 @@ pub mod cluster_manager {
         }
     }
 
-}
-
-pub mod metadata_provider {
-    tonic::include_proto!("opendut.carl.services.metadata_provider");
-}
-
-#[allow(clippy::large_enum_variant)]
-pub mod peer_manager {
-    use opendut_types::peer::{PeerId, PeerName};
-    use opendut_types::peer::state::PeerState;
-    use opendut_types::proto;
-    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
-    use opendut_types::topology::DeviceId;
-
-    use crate::carl::peer::{StorePeerDescriptorError, DeletePeerDescriptorError, GetPeerDescriptorError, ListPeerDescriptorsError};
-
-    tonic::include_proto!("opendut.carl.services.peer_manager");
 
-    impl From<StorePeerDescriptorError> for StorePeerDescriptorFailure {
-        fn from(error: StorePeerDescriptorError) -> Self {
+    impl From<StoreCanFaultInjectionRuleError> for StoreCanFaultInjectionRuleFailure {
+        fn from(error: StoreCanFaultInjectionRuleError) -> Self {
             let proto_error = match error {
-                StorePeerDescriptorError::IllegalPeerState { peer_id, peer_name, actual_state, required_states } => {
-                    store_peer_descriptor_failure::Error::IllegalPeerState(StorePeerDescriptorFailureIllegalPeerState {
-                        peer_id: Some(peer_id.into()),
-                        peer_name: Some(peer_name.into()),
-                        actual_state: Some(actual_state.into()),
-                        required_states: required_states.into_iter().map(Into::into).collect(),
+                StoreCanFaultInjectionRuleError::ClusterConfigurationNotFound { rule_id, cluster_id } => {
+                    store_can_fault_injection_rule_failure::Error::ClusterConfigurationNotFound(StoreCanFaultInjectionRuleFailureClusterConfigurationNotFound {
+                        rule_id: Some(rule_id.into()),
+                        cluster_id: Some(cluster_id.into()),
                     })
                 }
-                StorePeerDescriptorError::IllegalDevices { peer_id, peer_name, error } => {
-                    store_peer_descriptor_failure::Error::IllegalDevices(StorePeerDescriptorFailureIllegalDevices {
-                        peer_id: Some(peer_id.into()),
-                        peer_name: Some(peer_name.into()),
-                        error: Some(error.into()),
+                StoreCanFaultInjectionRuleError::Internal { rule_id, cause } => {
+                    store_can_fault_injection_rule_failure::Error::Internal(StoreCanFaultInjectionRuleFailureInternal {
+                        rule_id: Some(rule_id.into()),
+                        cause
+                    })
+                }
+            };
+            StoreCanFaultInjectionRuleFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<StoreCanFaultInjectionRuleFailure> for StoreCanFaultInjectionRuleError {
+        type Error = ConversionError;
+        fn try_from(failure: StoreCanFaultInjectionRuleFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StoreCanFaultInjectionRuleFailure, StoreCanFaultInjectionRuleError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                store_can_fault_injection_rule_failure::Error::ClusterConfigurationNotFound(failure) => {
+                    let rule_id: CanFaultInjectionRuleId = failure.rule_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("rule_id"))?
+                        .try_into()?;
+                    let cluster_id: ClusterId = failure.cluster_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("cluster_id"))?
+                        .try_into()?;
+                    StoreCanFaultInjectionRuleError::ClusterConfigurationNotFound { rule_id, cluster_id }
+                }
+                store_can_fault_injection_rule_failure::Error::Internal(failure) => {
+                    let rule_id: CanFaultInjectionRuleId = failure.rule_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("rule_id"))?
+                        .try_into()?;
+                    StoreCanFaultInjectionRuleError::Internal { rule_id, cause: failure.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<DeleteCanFaultInjectionRuleError> for DeleteCanFaultInjectionRuleFailure {
+        fn from(error: DeleteCanFaultInjectionRuleError) -> Self {
+            let proto_error = match error {
+                DeleteCanFaultInjectionRuleError::RuleNotFound { rule_id } => {
+                    delete_can_fault_injection_rule_failure::Error::RuleNotFound(DeleteCanFaultInjectionRuleFailureRuleNotFound {
+                        rule_id: Some(rule_id.into()),
+                    })
+                }
+                DeleteCanFaultInjectionRuleError::Internal { rule_id, cause } => {
+                    delete_can_fault_injection_rule_failure::Error::Internal(DeleteCanFaultInjectionRuleFailureInternal {
+                        rule_id: Some(rule_id.into()),
+                        cause
+                    })
+                }
+            };
+            DeleteCanFaultInjectionRuleFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DeleteCanFaultInjectionRuleFailure> for DeleteCanFaultInjectionRuleError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteCanFaultInjectionRuleFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteCanFaultInjectionRuleFailure, DeleteCanFaultInjectionRuleError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                delete_can_fault_injection_rule_failure::Error::RuleNotFound(failure) => {
+                    let rule_id: CanFaultInjectionRuleId = failure.rule_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("rule_id"))?
+                        .try_into()?;
+                    DeleteCanFaultInjectionRuleError::RuleNotFound { rule_id }
+                }
+                delete_can_fault_injection_rule_failure::Error::Internal(failure) => {
+                    let rule_id: CanFaultInjectionRuleId = failure.rule_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("rule_id"))?
+                        .try_into()?;
+                    DeleteCanFaultInjectionRuleError::Internal { rule_id, cause: failure.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<ListCanFaultInjectionRulesError> for ListCanFaultInjectionRulesFailure {
+        fn from(error: ListCanFaultInjectionRulesError) -> Self {
+            let proto_error = match error {
+                ListCanFaultInjectionRulesError::Internal { cause } => {
+                    list_can_fault_injection_rules_failure::Error::Internal(ListCanFaultInjectionRulesFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ListCanFaultInjectionRulesFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListCanFaultInjectionRulesFailure> for ListCanFaultInjectionRulesError {
+        type Error = ConversionError;
+        fn try_from(failure: ListCanFaultInjectionRulesFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListCanFaultInjectionRulesFailure, ListCanFaultInjectionRulesError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_can_fault_injection_rules_failure::Error::Internal(failure) => {
+                    ListCanFaultInjectionRulesError::Internal { cause: failure.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+}
+
+pub mod metadata_provider {
+    tonic::include_proto!("opendut.carl.services.metadata_provider");
+}
+
+#[allow(clippy::large_enum_variant)]
+pub mod peer_manager {
+    use opendut_types::can::CanDatabaseId;
+    use opendut_types::peer::{PeerId, PeerName};
+    use opendut_types::peer::executor::container::ContainerRegistryCredentialName;
+    use opendut_types::peer::state::PeerState;
+    use opendut_types::proto;
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+    use opendut_types::secret::SecretName;
+    use opendut_types::topology::DeviceId;
+
+    use crate::carl::peer::{StorePeerDescriptorError, DeletePeerDescriptorError, GetPeerDescriptorError, ListExecutorRunsError, ListPeerDescriptorsError, ListResultArtifactsError, RevokeClientError, RotateClientSecretError, TransferPeerOwnershipError};
+
+    tonic::include_proto!("opendut.carl.services.peer_manager");
+
+    impl From<StorePeerDescriptorError> for StorePeerDescriptorFailure {
+        fn from(error: StorePeerDescriptorError) -> Self {
+            let proto_error = match error {
+                StorePeerDescriptorError::IllegalPeerState { peer_id, peer_name, actual_state, required_states } => {
+                    store_peer_descriptor_failure::Error::IllegalPeerState(StorePeerDescriptorFailureIllegalPeerState {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        actual_state: Some(actual_state.into()),
+                        required_states: required_states.into_iter().map(Into::into).collect(),
+                    })
+                }
+                StorePeerDescriptorError::IllegalDevices { peer_id, peer_name, error } => {
+                    store_peer_descriptor_failure::Error::IllegalDevices(StorePeerDescriptorFailureIllegalDevices {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        error: Some(error.into()),
+                    })
+                }
+                StorePeerDescriptorError::CanDatabaseNotFound { peer_id, peer_name, database_id } => {
+                    store_peer_descriptor_failure::Error::CanDatabaseNotFound(StorePeerDescriptorFailureCanDatabaseNotFound {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        database_id: Some(database_id.into()),
+                    })
+                }
+                StorePeerDescriptorError::RegistryCredentialNotFound { peer_id, peer_name, credential_name } => {
+                    store_peer_descriptor_failure::Error::RegistryCredentialNotFound(StorePeerDescriptorFailureRegistryCredentialNotFound {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        credential_name: Some(credential_name.into()),
+                    })
+                }
+                StorePeerDescriptorError::SecretNotFound { peer_id, peer_name, secret_name } => {
+                    store_peer_descriptor_failure::Error::SecretNotFound(StorePeerDescriptorFailureSecretNotFound {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        secret_name: Some(secret_name.into()),
+                    })
+                }
+                StorePeerDescriptorError::IllegalSecretReference { peer_id, peer_name, cause } => {
+                    store_peer_descriptor_failure::Error::IllegalSecretReference(StorePeerDescriptorFailureIllegalSecretReference {
+                        peer_id: Some(peer_id.into()),
+                        peer_name: Some(peer_name.into()),
+                        cause,
                     })
                 }
                 StorePeerDescriptorError::Internal { peer_id, peer_name, cause } => {
@@ -417,6 +955,18 @@ pub mod peer_manager {
                 store_peer_descriptor_failure::Error::IllegalDevices(error) => {
                     error.try_into()?
                 }
+                store_peer_descriptor_failure::Error::CanDatabaseNotFound(error) => {
+                    error.try_into()?
+                }
+                store_peer_descriptor_failure::Error::RegistryCredentialNotFound(error) => {
+                    error.try_into()?
+                }
+                store_peer_descriptor_failure::Error::SecretNotFound(error) => {
+                    error.try_into()?
+                }
+                store_peer_descriptor_failure::Error::IllegalSecretReference(error) => {
+                    error.try_into()?
+                }
                 store_peer_descriptor_failure::Error::Internal(error) => {
                     error.try_into()?
                 }
@@ -462,6 +1012,71 @@ pub mod peer_manager {
         }
     }
 
+    impl TryFrom<StorePeerDescriptorFailureCanDatabaseNotFound> for StorePeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: StorePeerDescriptorFailureCanDatabaseNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StorePeerDescriptorFailureCanDatabaseNotFound, StorePeerDescriptorError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            let database_id: CanDatabaseId = failure.database_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("database_id"))?
+                .try_into()?;
+            Ok(StorePeerDescriptorError::CanDatabaseNotFound { peer_id, peer_name, database_id })
+        }
+    }
+
+    impl TryFrom<StorePeerDescriptorFailureRegistryCredentialNotFound> for StorePeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: StorePeerDescriptorFailureRegistryCredentialNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StorePeerDescriptorFailureRegistryCredentialNotFound, StorePeerDescriptorError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            let credential_name: ContainerRegistryCredentialName = failure.credential_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("credential_name"))?
+                .try_into()?;
+            Ok(StorePeerDescriptorError::RegistryCredentialNotFound { peer_id, peer_name, credential_name })
+        }
+    }
+
+    impl TryFrom<StorePeerDescriptorFailureSecretNotFound> for StorePeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: StorePeerDescriptorFailureSecretNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StorePeerDescriptorFailureSecretNotFound, StorePeerDescriptorError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            let secret_name: SecretName = failure.secret_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("secret_name"))?
+                .try_into()?;
+            Ok(StorePeerDescriptorError::SecretNotFound { peer_id, peer_name, secret_name })
+        }
+    }
+
+    impl TryFrom<StorePeerDescriptorFailureIllegalSecretReference> for StorePeerDescriptorError {
+        type Error = ConversionError;
+        fn try_from(failure: StorePeerDescriptorFailureIllegalSecretReference) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StorePeerDescriptorFailureIllegalSecretReference, StorePeerDescriptorError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            let peer_name: PeerName = failure.peer_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_name"))?
+                .try_into()?;
+            Ok(StorePeerDescriptorError::IllegalSecretReference { peer_id, peer_name, cause: failure.cause })
+        }
+    }
+
     impl TryFrom<StorePeerDescriptorFailureInternal> for StorePeerDescriptorError {
         type Error = ConversionError;
         fn try_from(failure: StorePeerDescriptorFailureInternal) -> Result<Self, Self::Error> {
@@ -709,8 +1324,654 @@ pub mod peer_manager {
             Ok(ListPeerDescriptorsError::Internal{ cause: failure.cause})
         }
     }
-}
 
-pub mod peer_messaging_broker {
-    tonic::include_proto!("opendut.carl.services.peer_messaging_broker");
+    impl From<TransferPeerOwnershipError> for TransferPeerOwnershipFailure {
+        fn from(error: TransferPeerOwnershipError) -> Self {
+            let proto_error = match error {
+                TransferPeerOwnershipError::PeerNotFound { peer_id } => {
+                    transfer_peer_ownership_failure::Error::PeerNotFound(TransferPeerOwnershipFailurePeerNotFound {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+            };
+            TransferPeerOwnershipFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<TransferPeerOwnershipFailure> for TransferPeerOwnershipError {
+        type Error = ConversionError;
+        fn try_from(failure: TransferPeerOwnershipFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<TransferPeerOwnershipFailure, TransferPeerOwnershipError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                transfer_peer_ownership_failure::Error::PeerNotFound(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<TransferPeerOwnershipFailurePeerNotFound> for TransferPeerOwnershipError {
+        type Error = ConversionError;
+        fn try_from(failure: TransferPeerOwnershipFailurePeerNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<TransferPeerOwnershipFailurePeerNotFound, TransferPeerOwnershipError>;
+            let peer_id: PeerId = failure.peer_id
+                .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                .try_into()?;
+            Ok(TransferPeerOwnershipError::PeerNotFound { peer_id })
+        }
+    }
+
+    impl From<crate::carl::peer::CredentialsHolder> for CredentialsHolder {
+        fn from(holder: crate::carl::peer::CredentialsHolder) -> Self {
+            let holder = match holder {
+                crate::carl::peer::CredentialsHolder::Peer(peer_id) => credentials_holder::Holder::PeerId(peer_id.into()),
+                crate::carl::peer::CredentialsHolder::Cleo(cleo_id) => credentials_holder::Holder::CleoId(cleo_id.into()),
+            };
+            CredentialsHolder {
+                holder: Some(holder)
+            }
+        }
+    }
+
+    impl TryFrom<CredentialsHolder> for crate::carl::peer::CredentialsHolder {
+        type Error = ConversionError;
+        fn try_from(holder: CredentialsHolder) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CredentialsHolder, crate::carl::peer::CredentialsHolder>;
+            let holder = match holder.holder
+                .ok_or_else(|| ErrorBuilder::field_not_set("holder"))? {
+                credentials_holder::Holder::PeerId(peer_id) => crate::carl::peer::CredentialsHolder::Peer(peer_id.try_into()?),
+                credentials_holder::Holder::CleoId(cleo_id) => crate::carl::peer::CredentialsHolder::Cleo(cleo_id.try_into()?),
+            };
+            Ok(holder)
+        }
+    }
+
+    impl From<RotateClientSecretError> for RotateClientSecretFailure {
+        fn from(error: RotateClientSecretError) -> Self {
+            let proto_error = match error {
+                RotateClientSecretError::ClientNotFound { holder } => {
+                    rotate_client_secret_failure::Error::ClientNotFound(RotateClientSecretFailureClientNotFound {
+                        holder: Some(holder.into()),
+                    })
+                }
+                RotateClientSecretError::PeerNotConnected { peer_id } => {
+                    rotate_client_secret_failure::Error::PeerNotConnected(RotateClientSecretFailurePeerNotConnected {
+                        peer_id: Some(peer_id.into()),
+                    })
+                }
+                RotateClientSecretError::Internal { holder, cause } => {
+                    rotate_client_secret_failure::Error::Internal(RotateClientSecretFailureInternal {
+                        holder: Some(holder.into()),
+                        cause,
+                    })
+                }
+            };
+            RotateClientSecretFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<RotateClientSecretFailure> for RotateClientSecretError {
+        type Error = ConversionError;
+        fn try_from(failure: RotateClientSecretFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<RotateClientSecretFailure, RotateClientSecretError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                rotate_client_secret_failure::Error::ClientNotFound(failure) => {
+                    let holder = failure.holder
+                        .ok_or_else(|| ErrorBuilder::field_not_set("holder"))?
+                        .try_into()?;
+                    RotateClientSecretError::ClientNotFound { holder }
+                }
+                rotate_client_secret_failure::Error::PeerNotConnected(failure) => {
+                    let peer_id: PeerId = failure.peer_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("peer_id"))?
+                        .try_into()?;
+                    RotateClientSecretError::PeerNotConnected { peer_id }
+                }
+                rotate_client_secret_failure::Error::Internal(failure) => {
+                    let holder = failure.holder
+                        .ok_or_else(|| ErrorBuilder::field_not_set("holder"))?
+                        .try_into()?;
+                    RotateClientSecretError::Internal { holder, cause: failure.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<RevokeClientError> for RevokeClientFailure {
+        fn from(error: RevokeClientError) -> Self {
+            let proto_error = match error {
+                RevokeClientError::ClientNotFound { holder } => {
+                    revoke_client_failure::Error::ClientNotFound(RevokeClientFailureClientNotFound {
+                        holder: Some(holder.into()),
+                    })
+                }
+                RevokeClientError::Internal { holder, cause } => {
+                    revoke_client_failure::Error::Internal(RevokeClientFailureInternal {
+                        holder: Some(holder.into()),
+                        cause,
+                    })
+                }
+            };
+            RevokeClientFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<RevokeClientFailure> for RevokeClientError {
+        type Error = ConversionError;
+        fn try_from(failure: RevokeClientFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<RevokeClientFailure, RevokeClientError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                revoke_client_failure::Error::ClientNotFound(failure) => {
+                    let holder = failure.holder
+                        .ok_or_else(|| ErrorBuilder::field_not_set("holder"))?
+                        .try_into()?;
+                    RevokeClientError::ClientNotFound { holder }
+                }
+                revoke_client_failure::Error::Internal(failure) => {
+                    let holder = failure.holder
+                        .ok_or_else(|| ErrorBuilder::field_not_set("holder"))?
+                        .try_into()?;
+                    RevokeClientError::Internal { holder, cause: failure.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<ListExecutorRunsError> for ListExecutorRunsFailure {
+        fn from(error: ListExecutorRunsError) -> Self {
+            let proto_error = match error {
+                ListExecutorRunsError::Internal { cause } => {
+                    list_executor_runs_failure::Error::Internal(ListExecutorRunsFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ListExecutorRunsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListExecutorRunsFailure> for ListExecutorRunsError {
+        type Error = ConversionError;
+        fn try_from(failure: ListExecutorRunsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListExecutorRunsFailure, ListExecutorRunsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_executor_runs_failure::Error::Internal(failure) => {
+                    ListExecutorRunsError::Internal { cause: failure.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<ListResultArtifactsError> for ListResultArtifactsFailure {
+        fn from(error: ListResultArtifactsError) -> Self {
+            let proto_error = match error {
+                ListResultArtifactsError::Internal { cause } => {
+                    list_result_artifacts_failure::Error::Internal(ListResultArtifactsFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ListResultArtifactsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListResultArtifactsFailure> for ListResultArtifactsError {
+        type Error = ConversionError;
+        fn try_from(failure: ListResultArtifactsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListResultArtifactsFailure, ListResultArtifactsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_result_artifacts_failure::Error::Internal(failure) => {
+                    ListResultArtifactsError::Internal { cause: failure.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+}
+
+pub mod peer_messaging_broker {
+    tonic::include_proto!("opendut.carl.services.peer_messaging_broker");
+}
+
+pub mod secret_manager {
+    use opendut_types::proto;
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+    use opendut_types::secret::SecretName;
+
+    use crate::carl::secret::{DeleteSecretError, ListSecretsError, StoreSecretError};
+
+    tonic::include_proto!("opendut.carl.services.secret_manager");
+
+    impl From<StoreSecretError> for StoreSecretFailure {
+        fn from(error: StoreSecretError) -> Self {
+            let proto_error = match error {
+                StoreSecretError::Internal { secret_name, cause } => {
+                    store_secret_failure::Error::Internal(StoreSecretFailureInternal {
+                        secret_name: Some(secret_name.into()),
+                        cause
+                    })
+                }
+            };
+            StoreSecretFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<StoreSecretFailure> for StoreSecretError {
+        type Error = ConversionError;
+        fn try_from(failure: StoreSecretFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StoreSecretFailure, StoreSecretError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                store_secret_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<StoreSecretFailureInternal> for StoreSecretError {
+        type Error = ConversionError;
+        fn try_from(failure: StoreSecretFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<StoreSecretFailureInternal, StoreSecretError>;
+            let secret_name: SecretName = failure.secret_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("secret_name"))?
+                .try_into()?;
+            Ok(StoreSecretError::Internal { secret_name, cause: failure.cause })
+        }
+    }
+
+    impl From<DeleteSecretError> for DeleteSecretFailure {
+        fn from(error: DeleteSecretError) -> Self {
+            let proto_error = match error {
+                DeleteSecretError::SecretNotFound { secret_name } => {
+                    delete_secret_failure::Error::NotFound(DeleteSecretFailureNotFound {
+                        secret_name: Some(secret_name.into()),
+                    })
+                }
+                DeleteSecretError::SecretInUse { secret_name, peer_ids } => {
+                    delete_secret_failure::Error::InUse(DeleteSecretFailureInUse {
+                        secret_name: Some(secret_name.into()),
+                        peer_ids: peer_ids.into_iter().map(Into::into).collect(),
+                    })
+                }
+                DeleteSecretError::Internal { secret_name, cause } => {
+                    delete_secret_failure::Error::Internal(DeleteSecretFailureInternal {
+                        secret_name: Some(secret_name.into()),
+                        cause
+                    })
+                }
+            };
+            DeleteSecretFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DeleteSecretFailure> for DeleteSecretError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteSecretFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteSecretFailure, DeleteSecretError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                delete_secret_failure::Error::NotFound(error) => {
+                    error.try_into()?
+                }
+                delete_secret_failure::Error::InUse(error) => {
+                    error.try_into()?
+                }
+                delete_secret_failure::Error::Internal(error) => {
+                    error.try_into()?
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl TryFrom<DeleteSecretFailureNotFound> for DeleteSecretError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteSecretFailureNotFound) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteSecretFailureNotFound, DeleteSecretError>;
+            let secret_name: SecretName = failure.secret_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("secret_name"))?
+                .try_into()?;
+            Ok(DeleteSecretError::SecretNotFound { secret_name })
+        }
+    }
+
+    impl TryFrom<DeleteSecretFailureInUse> for DeleteSecretError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteSecretFailureInUse) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteSecretFailureInUse, DeleteSecretError>;
+            let secret_name: SecretName = failure.secret_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("secret_name"))?
+                .try_into()?;
+            let peer_ids = failure.peer_ids.into_iter()
+                .map(proto::peer::PeerId::try_into)
+                .collect::<Result<_, _>>()?;
+            Ok(DeleteSecretError::SecretInUse { secret_name, peer_ids })
+        }
+    }
+
+    impl TryFrom<DeleteSecretFailureInternal> for DeleteSecretError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteSecretFailureInternal) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteSecretFailureInternal, DeleteSecretError>;
+            let secret_name: SecretName = failure.secret_name
+                .ok_or_else(|| ErrorBuilder::field_not_set("secret_name"))?
+                .try_into()?;
+            Ok(DeleteSecretError::Internal { secret_name, cause: failure.cause })
+        }
+    }
+
+    impl From<ListSecretsError> for ListSecretsFailure {
+        fn from(error: ListSecretsError) -> Self {
+            let proto_error = match error {
+                ListSecretsError::Internal { cause } => {
+                    list_secrets_failure::Error::Internal(ListSecretsFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ListSecretsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListSecretsFailure> for ListSecretsError {
+        type Error = ConversionError;
+        fn try_from(failure: ListSecretsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListSecretsFailure, ListSecretsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_secrets_failure::Error::Internal(failure) => {
+                    ListSecretsError::Internal { cause: failure.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+}
+
+pub mod service_account_manager {
+    use opendut_types::service_account::{ApiTokenId, ServiceAccountId, ServiceAccountName};
+    use opendut_types::proto::{ConversionError, ConversionErrorBuilder};
+
+    use crate::carl::service_account::{CreateApiTokenError, CreateServiceAccountError, DeleteServiceAccountError, ListApiTokensError, ListServiceAccountsError, RevokeApiTokenError};
+
+    tonic::include_proto!("opendut.carl.services.service_account_manager");
+
+    impl From<CreateServiceAccountError> for CreateServiceAccountFailure {
+        fn from(error: CreateServiceAccountError) -> Self {
+            let proto_error = match error {
+                CreateServiceAccountError::AlreadyExists { name, other_id } => {
+                    create_service_account_failure::Error::AlreadyExists(CreateServiceAccountFailureAlreadyExists {
+                        name: Some(name.into()),
+                        other_id: Some(other_id.into()),
+                    })
+                }
+                CreateServiceAccountError::Internal { name, cause } => {
+                    create_service_account_failure::Error::Internal(CreateServiceAccountFailureInternal {
+                        name: Some(name.into()),
+                        cause
+                    })
+                }
+            };
+            CreateServiceAccountFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<CreateServiceAccountFailure> for CreateServiceAccountError {
+        type Error = ConversionError;
+        fn try_from(failure: CreateServiceAccountFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreateServiceAccountFailure, CreateServiceAccountError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                create_service_account_failure::Error::AlreadyExists(failure) => {
+                    let name: ServiceAccountName = failure.name
+                        .ok_or_else(|| ErrorBuilder::field_not_set("name"))?
+                        .try_into()?;
+                    let other_id: ServiceAccountId = failure.other_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("other_id"))?
+                        .try_into()?;
+                    CreateServiceAccountError::AlreadyExists { name, other_id }
+                }
+                create_service_account_failure::Error::Internal(failure) => {
+                    let name: ServiceAccountName = failure.name
+                        .ok_or_else(|| ErrorBuilder::field_not_set("name"))?
+                        .try_into()?;
+                    CreateServiceAccountError::Internal { name, cause: failure.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<DeleteServiceAccountError> for DeleteServiceAccountFailure {
+        fn from(error: DeleteServiceAccountError) -> Self {
+            let proto_error = match error {
+                DeleteServiceAccountError::ServiceAccountNotFound { service_account_id } => {
+                    delete_service_account_failure::Error::NotFound(DeleteServiceAccountFailureNotFound {
+                        service_account_id: Some(service_account_id.into()),
+                    })
+                }
+                DeleteServiceAccountError::Internal { service_account_id, cause } => {
+                    delete_service_account_failure::Error::Internal(DeleteServiceAccountFailureInternal {
+                        service_account_id: Some(service_account_id.into()),
+                        cause
+                    })
+                }
+            };
+            DeleteServiceAccountFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<DeleteServiceAccountFailure> for DeleteServiceAccountError {
+        type Error = ConversionError;
+        fn try_from(failure: DeleteServiceAccountFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<DeleteServiceAccountFailure, DeleteServiceAccountError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                delete_service_account_failure::Error::NotFound(failure) => {
+                    let service_account_id: ServiceAccountId = failure.service_account_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("service_account_id"))?
+                        .try_into()?;
+                    DeleteServiceAccountError::ServiceAccountNotFound { service_account_id }
+                }
+                delete_service_account_failure::Error::Internal(failure) => {
+                    let service_account_id: ServiceAccountId = failure.service_account_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("service_account_id"))?
+                        .try_into()?;
+                    DeleteServiceAccountError::Internal { service_account_id, cause: failure.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<ListServiceAccountsError> for ListServiceAccountsFailure {
+        fn from(error: ListServiceAccountsError) -> Self {
+            let proto_error = match error {
+                ListServiceAccountsError::Internal { cause } => {
+                    list_service_accounts_failure::Error::Internal(ListServiceAccountsFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ListServiceAccountsFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListServiceAccountsFailure> for ListServiceAccountsError {
+        type Error = ConversionError;
+        fn try_from(failure: ListServiceAccountsFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListServiceAccountsFailure, ListServiceAccountsError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_service_accounts_failure::Error::Internal(failure) => {
+                    ListServiceAccountsError::Internal { cause: failure.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<CreateApiTokenError> for CreateApiTokenFailure {
+        fn from(error: CreateApiTokenError) -> Self {
+            let proto_error = match error {
+                CreateApiTokenError::ServiceAccountNotFound { service_account_id } => {
+                    create_api_token_failure::Error::ServiceAccountNotFound(CreateApiTokenFailureServiceAccountNotFound {
+                        service_account_id: Some(service_account_id.into()),
+                    })
+                }
+                CreateApiTokenError::Internal { service_account_id, cause } => {
+                    create_api_token_failure::Error::Internal(CreateApiTokenFailureInternal {
+                        service_account_id: Some(service_account_id.into()),
+                        cause
+                    })
+                }
+            };
+            CreateApiTokenFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<CreateApiTokenFailure> for CreateApiTokenError {
+        type Error = ConversionError;
+        fn try_from(failure: CreateApiTokenFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<CreateApiTokenFailure, CreateApiTokenError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                create_api_token_failure::Error::ServiceAccountNotFound(failure) => {
+                    let service_account_id: ServiceAccountId = failure.service_account_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("service_account_id"))?
+                        .try_into()?;
+                    CreateApiTokenError::ServiceAccountNotFound { service_account_id }
+                }
+                create_api_token_failure::Error::Internal(failure) => {
+                    let service_account_id: ServiceAccountId = failure.service_account_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("service_account_id"))?
+                        .try_into()?;
+                    CreateApiTokenError::Internal { service_account_id, cause: failure.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<RevokeApiTokenError> for RevokeApiTokenFailure {
+        fn from(error: RevokeApiTokenError) -> Self {
+            let proto_error = match error {
+                RevokeApiTokenError::ApiTokenNotFound { token_id } => {
+                    revoke_api_token_failure::Error::NotFound(RevokeApiTokenFailureNotFound {
+                        token_id: Some(token_id.into()),
+                    })
+                }
+                RevokeApiTokenError::Internal { token_id, cause } => {
+                    revoke_api_token_failure::Error::Internal(RevokeApiTokenFailureInternal {
+                        token_id: Some(token_id.into()),
+                        cause
+                    })
+                }
+            };
+            RevokeApiTokenFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<RevokeApiTokenFailure> for RevokeApiTokenError {
+        type Error = ConversionError;
+        fn try_from(failure: RevokeApiTokenFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<RevokeApiTokenFailure, RevokeApiTokenError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                revoke_api_token_failure::Error::NotFound(failure) => {
+                    let token_id: ApiTokenId = failure.token_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("token_id"))?
+                        .try_into()?;
+                    RevokeApiTokenError::ApiTokenNotFound { token_id }
+                }
+                revoke_api_token_failure::Error::Internal(failure) => {
+                    let token_id: ApiTokenId = failure.token_id
+                        .ok_or_else(|| ErrorBuilder::field_not_set("token_id"))?
+                        .try_into()?;
+                    RevokeApiTokenError::Internal { token_id, cause: failure.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
+
+    impl From<ListApiTokensError> for ListApiTokensFailure {
+        fn from(error: ListApiTokensError) -> Self {
+            let proto_error = match error {
+                ListApiTokensError::Internal { cause } => {
+                    list_api_tokens_failure::Error::Internal(ListApiTokensFailureInternal {
+                        cause
+                    })
+                }
+            };
+            ListApiTokensFailure {
+                error: Some(proto_error)
+            }
+        }
+    }
+
+    impl TryFrom<ListApiTokensFailure> for ListApiTokensError {
+        type Error = ConversionError;
+        fn try_from(failure: ListApiTokensFailure) -> Result<Self, Self::Error> {
+            type ErrorBuilder = ConversionErrorBuilder<ListApiTokensFailure, ListApiTokensError>;
+            let error = failure.error
+                .ok_or_else(|| ErrorBuilder::field_not_set("error"))?;
+            let error = match error {
+                list_api_tokens_failure::Error::Internal(failure) => {
+                    ListApiTokensError::Internal { cause: failure.cause }
+                }
+            };
+            Ok(error)
+        }
+    }
 }
@@ -0,0 +1,304 @@
+use std::ops::Not;
+
+use tracing::{debug, error, info};
+
+pub use opendut_carl_api::carl::can_database::{
+    DeleteCanDatabaseError,
+    ListCanDatabasesError,
+    StoreCanDatabaseError,
+};
+use opendut_types::can::{CanDatabase, CanDatabaseDescriptor, CanDatabaseId};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::peer::executor::ExecutorKind;
+
+use crate::resources::manager::ResourcesManagerRef;
+
+pub struct StoreCanDatabaseParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub can_database: CanDatabase,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn store_can_database(params: StoreCanDatabaseParams) -> Result<CanDatabaseId, StoreCanDatabaseError> {
+
+    async fn inner(params: StoreCanDatabaseParams) -> Result<CanDatabaseId, StoreCanDatabaseError> {
+
+        let database_id = params.can_database.descriptor.id;
+        let database_name = Clone::clone(&params.can_database.descriptor.name);
+        let database_version = Clone::clone(&params.can_database.descriptor.version);
+        let resources_manager = params.resources_manager;
+
+        params.can_database.verify()
+            .map_err(|cause| StoreCanDatabaseError::IllegalContent {
+                database_id,
+                database_name: Clone::clone(&database_name),
+                cause: cause.to_string(),
+            })?;
+
+        debug!("Storing CAN database '{database_name}' <{database_id}> in version '{database_version}'.");
+
+        resources_manager.resources_mut(|resources| {
+            let conflicting_database = resources.iter::<CanDatabase>()
+                .find(|other| other.descriptor.id != database_id
+                    && other.descriptor.name == database_name
+                    && other.descriptor.version == database_version
+                )
+                .map(|other| other.descriptor.id);
+
+            if let Some(other_id) = conflicting_database {
+                return Err(StoreCanDatabaseError::AlreadyExists {
+                    database_id,
+                    database_name: Clone::clone(&database_name),
+                    database_version: Clone::clone(&database_version),
+                    other_id,
+                });
+            }
+
+            resources.insert(database_id, params.can_database);
+            Ok(())
+        }).await?;
+
+        info!("Successfully stored CAN database '{database_name}' <{database_id}> in version '{database_version}'.");
+
+        Ok(database_id)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct DeleteCanDatabaseParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub database_id: CanDatabaseId,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn delete_can_database(params: DeleteCanDatabaseParams) -> Result<CanDatabaseDescriptor, DeleteCanDatabaseError> {
+
+    async fn inner(params: DeleteCanDatabaseParams) -> Result<CanDatabaseDescriptor, DeleteCanDatabaseError> {
+
+        let database_id = params.database_id;
+        let resources_manager = params.resources_manager;
+
+        debug!("Deleting CAN database <{database_id}>.");
+
+        let can_database = resources_manager.resources_mut(|resources| {
+
+            let can_database = resources.get::<CanDatabase>(database_id)
+                .ok_or(DeleteCanDatabaseError::CanDatabaseNotFound { database_id })?;
+
+            let referencing_peers = resources.iter::<PeerDescriptor>()
+                .filter(|peer| references_can_database(peer, database_id))
+                .map(|peer| peer.id)
+                .collect::<Vec<PeerId>>();
+
+            if referencing_peers.is_empty().not() {
+                return Err(DeleteCanDatabaseError::CanDatabaseInUse {
+                    database_id,
+                    database_name: can_database.descriptor.name,
+                    peer_ids: referencing_peers,
+                });
+            }
+
+            resources.remove::<CanDatabase>(database_id)
+                .ok_or(DeleteCanDatabaseError::CanDatabaseNotFound { database_id })
+        }).await?;
+
+        let database_name = &can_database.descriptor.name;
+
+        info!("Successfully deleted CAN database '{database_name}' <{database_id}>.");
+
+        Ok(can_database.descriptor)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct ListCanDatabasesParams {
+    pub resources_manager: ResourcesManagerRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_can_databases(params: ListCanDatabasesParams) -> Result<Vec<CanDatabaseDescriptor>, ListCanDatabasesError> {
+
+    async fn inner(params: ListCanDatabasesParams) -> Result<Vec<CanDatabaseDescriptor>, ListCanDatabasesError> {
+
+        let resources_manager = params.resources_manager;
+
+        debug!("Querying all CAN databases.");
+
+        let descriptors = resources_manager.resources(|resources| {
+            resources.iter::<CanDatabase>()
+                .map(|database| Clone::clone(&database.descriptor))
+                .collect::<Vec<_>>()
+        }).await;
+
+        info!("Successfully queried all CAN databases.");
+
+        Ok(descriptors)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+fn references_can_database(peer: &PeerDescriptor, database_id: CanDatabaseId) -> bool {
+    peer.executors.executors.iter()
+        .any(|executor| match &executor.kind {
+            ExecutorKind::Executable { .. } => false,
+            ExecutorKind::Container { can_databases, .. } => can_databases.contains(&database_id),
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+
+    use opendut_types::can::{CanDatabaseFormat, CanDatabaseName, CanDatabaseVersion};
+    use opendut_types::peer::{PeerLocation, PeerName, PeerNetworkDescriptor};
+    use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorDescriptors};
+    use opendut_types::peer::executor::container::{ContainerCommand, ContainerImage, ContainerImagePullPolicy, ContainerName, Engine};
+    use opendut_types::topology::Topology;
+
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    fn can_database(name: &str, version: &str) -> anyhow::Result<CanDatabase> {
+        Ok(CanDatabase::new(
+            CanDatabaseId::random(),
+            CanDatabaseName::try_from(name)?,
+            CanDatabaseVersion::try_from(version)?,
+            CanDatabaseFormat::Dbc,
+            Vec::from(b"VERSION \"\"\n"),
+        )?)
+    }
+
+    #[tokio::test]
+    async fn should_store_and_list_can_databases() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let database = can_database("powertrain", "1.0")?;
+
+        let database_id = store_can_database(StoreCanDatabaseParams {
+            resources_manager: Arc::clone(&resources_manager),
+            can_database: Clone::clone(&database),
+        }).await?;
+
+        assert_that!(database_id, eq(database.descriptor.id));
+        assert_that!(resources_manager.get::<CanDatabase>(database_id).await.as_ref(), some(eq(&database)));
+
+        let descriptors = list_can_databases(ListCanDatabasesParams {
+            resources_manager: Arc::clone(&resources_manager),
+        }).await?;
+
+        assert_that!(descriptors, elements_are![eq(database.descriptor)]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_duplicate_name_and_version() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+
+        store_can_database(StoreCanDatabaseParams {
+            resources_manager: Arc::clone(&resources_manager),
+            can_database: can_database("powertrain", "1.0")?,
+        }).await?;
+
+        let result = store_can_database(StoreCanDatabaseParams {
+            resources_manager: Arc::clone(&resources_manager),
+            can_database: can_database("powertrain", "1.0")?,
+        }).await;
+
+        assert!(matches!(result, Err(StoreCanDatabaseError::AlreadyExists { .. })));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_content_not_matching_its_checksum() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let mut database = can_database("powertrain", "1.0")?;
+        database.content = Vec::from(b"VERSION \"2\"\n");
+
+        let result = store_can_database(StoreCanDatabaseParams {
+            resources_manager: Arc::clone(&resources_manager),
+            can_database: database,
+        }).await;
+
+        assert!(matches!(result, Err(StoreCanDatabaseError::IllegalContent { .. })));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_delete_can_database_referenced_by_a_peer() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let database = can_database("powertrain", "1.0")?;
+        let database_id = database.descriptor.id;
+        resources_manager.insert(database_id, database).await;
+
+        let peer_id = PeerId::random();
+        let peer = PeerDescriptor {
+            id: peer_id,
+            name: PeerName::try_from("PeerA")?,
+            location: PeerLocation::try_from("Ulm").ok(),
+            network: PeerNetworkDescriptor {
+                interfaces: vec![],
+                bridge_name: None,
+            },
+            topology: Topology::default(),
+            executors: ExecutorDescriptors {
+                executors: vec![ExecutorDescriptor {
+                    kind: ExecutorKind::Container {
+                        engine: Engine::Docker,
+                        name: ContainerName::Empty,
+                        image: ContainerImage::try_from("restbus-simulation")?,
+                        pull_policy: ContainerImagePullPolicy::IfNotPresent,
+                        digest: None,
+                        registry_credential: None,
+                        volumes: vec![],
+                        devices: vec![],
+                        envs: vec![],
+                        ports: vec![],
+                        command: ContainerCommand::Default,
+                        args: vec![],
+                        can_databases: vec![database_id],
+                        cpus: None,
+                        memory: None,
+                        pids_limit: None,
+                        network_mode: Default::default(),
+                        cap_add: vec![],
+                        cap_drop: vec![],
+                        read_only_rootfs: false,
+                    },
+                    results_url: None,
+                }],
+            },
+        };
+        resources_manager.insert(peer_id, Clone::clone(&peer)).await;
+
+        let result = delete_can_database(DeleteCanDatabaseParams {
+            resources_manager: Arc::clone(&resources_manager),
+            database_id,
+        }).await;
+
+        assert!(matches!(result, Err(DeleteCanDatabaseError::CanDatabaseInUse { .. })));
+        assert_that!(resources_manager.get::<CanDatabase>(database_id).await, some(anything()));
+
+        resources_manager.remove::<PeerDescriptor>(peer_id).await;
+
+        let descriptor = delete_can_database(DeleteCanDatabaseParams {
+            resources_manager: Arc::clone(&resources_manager),
+            database_id,
+        }).await?;
+
+        assert_that!(descriptor.id, eq(database_id));
+        assert_that!(resources_manager.get::<CanDatabase>(database_id).await, none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,417 @@
+use std::ops::Not;
+
+use tracing::{debug, error, info, warn};
+
+pub use opendut_carl_api::carl::cluster::{
+    DeleteCanFaultInjectionRuleError,
+    ListCanFaultInjectionRulesError,
+    StoreCanFaultInjectionRuleError,
+};
+use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyCanFaultInjectionRules, downstream};
+use opendut_types::cluster::{CanFaultInjectionRule, CanFaultInjectionRuleId, ClusterConfiguration, ClusterId};
+use opendut_types::peer::configuration::PeerConfiguration;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+
+use crate::auth::ownership::{Ownership, Requester};
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::Resources;
+
+pub struct StoreCanFaultInjectionRuleParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+    pub rule: CanFaultInjectionRule,
+    pub requester: Requester,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn store_can_fault_injection_rule(params: StoreCanFaultInjectionRuleParams) -> Result<CanFaultInjectionRuleId, StoreCanFaultInjectionRuleError> {
+
+    async fn inner(params: StoreCanFaultInjectionRuleParams) -> Result<CanFaultInjectionRuleId, StoreCanFaultInjectionRuleError> {
+
+        let rule_id = params.rule.id;
+        let cluster_id = params.rule.cluster_id;
+        let resources_manager = params.resources_manager;
+
+        params.rule.fault.verify()
+            .map_err(|cause| StoreCanFaultInjectionRuleError::Internal {
+                rule_id,
+                cause: cause.to_string(),
+            })?;
+
+        debug!("Storing CAN fault injection rule <{rule_id}> for cluster <{cluster_id}>.");
+
+        resources_manager.resources_mut(|resources| {
+            if resources.get::<ClusterConfiguration>(cluster_id).is_none()
+                || may_access_cluster(resources, &params.requester, cluster_id).not() {
+                return Err(StoreCanFaultInjectionRuleError::ClusterConfigurationNotFound { rule_id, cluster_id });
+            }
+            if let Some(existing) = resources.get::<CanFaultInjectionRule>(rule_id) {
+                if may_access_cluster(resources, &params.requester, existing.cluster_id).not() {
+                    return Err(StoreCanFaultInjectionRuleError::ClusterConfigurationNotFound { rule_id, cluster_id: existing.cluster_id });
+                }
+            }
+            resources.insert(rule_id, params.rule);
+            Ok(())
+        }).await?;
+
+        info!("Successfully stored CAN fault injection rule <{rule_id}> for cluster <{cluster_id}>.");
+
+        send_can_fault_injection_rules_to_cluster(resources_manager, params.peer_messaging_broker, cluster_id).await;
+
+        Ok(rule_id)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct DeleteCanFaultInjectionRuleParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+    pub rule_id: CanFaultInjectionRuleId,
+    pub requester: Requester,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn delete_can_fault_injection_rule(params: DeleteCanFaultInjectionRuleParams) -> Result<CanFaultInjectionRule, DeleteCanFaultInjectionRuleError> {
+
+    async fn inner(params: DeleteCanFaultInjectionRuleParams) -> Result<CanFaultInjectionRule, DeleteCanFaultInjectionRuleError> {
+
+        let rule_id = params.rule_id;
+        let resources_manager = params.resources_manager;
+
+        debug!("Deleting CAN fault injection rule <{rule_id}>.");
+
+        let rule = resources_manager.resources_mut(|resources| {
+            let accessible = resources.get::<CanFaultInjectionRule>(rule_id)
+                .is_some_and(|rule| may_access_cluster(resources, &params.requester, rule.cluster_id));
+            if accessible.not() {
+                return Err(DeleteCanFaultInjectionRuleError::RuleNotFound { rule_id });
+            }
+            resources.remove::<CanFaultInjectionRule>(rule_id)
+                .ok_or(DeleteCanFaultInjectionRuleError::RuleNotFound { rule_id })
+        }).await?;
+
+        info!("Successfully deleted CAN fault injection rule <{rule_id}>.");
+
+        send_can_fault_injection_rules_to_cluster(resources_manager, params.peer_messaging_broker, rule.cluster_id).await;
+
+        Ok(rule)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct ListCanFaultInjectionRulesParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub cluster_id: Option<ClusterId>,
+    pub requester: Requester,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_can_fault_injection_rules(params: ListCanFaultInjectionRulesParams) -> Result<Vec<CanFaultInjectionRule>, ListCanFaultInjectionRulesError> {
+
+    async fn inner(params: ListCanFaultInjectionRulesParams) -> Result<Vec<CanFaultInjectionRule>, ListCanFaultInjectionRulesError> {
+
+        let resources_manager = params.resources_manager;
+
+        debug!("Querying CAN fault injection rules.");
+
+        let rules = resources_manager.resources(|resources| {
+            resources.iter::<CanFaultInjectionRule>()
+                .filter(|rule| match params.cluster_id {
+                    Some(cluster_id) => rule.cluster_id == cluster_id,
+                    None => true,
+                })
+                .filter(|rule| may_access_cluster(resources, &params.requester, rule.cluster_id))
+                .cloned()
+                .collect::<Vec<_>>()
+        }).await;
+
+        info!("Successfully queried CAN fault injection rules.");
+
+        Ok(rules)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub(crate) fn can_fault_injection_rules_of_cluster(resources: &Resources, cluster_id: ClusterId) -> Vec<CanFaultInjectionRule> {
+    resources.iter::<CanFaultInjectionRule>()
+        .filter(|rule| rule.cluster_id == cluster_id)
+        .cloned()
+        .collect()
+}
+
+/// Rules are scoped by the ownership of the cluster they belong to.
+fn may_access_cluster(resources: &Resources, requester: &Requester, cluster_id: ClusterId) -> bool {
+    requester.may_access(resources.get::<Ownership>(cluster_id).as_ref())
+}
+
+/// Sends the current rules to all peers, which the cluster is deployed to.
+/// Peers, which are not connected, receive the rules when they reconnect.
+async fn send_can_fault_injection_rules_to_cluster(resources_manager: ResourcesManagerRef, peer_messaging_broker: PeerMessagingBrokerRef, cluster_id: ClusterId) {
+
+    let (member_ids, rules) = resources_manager.resources(|resources| {
+        let member_ids = resources.iter::<PeerDescriptor>()
+            .map(|peer| peer.id)
+            .filter(|peer_id| {
+                resources.get::<PeerConfiguration>(*peer_id)
+                    .and_then(|configuration| configuration.cluster_assignment)
+                    .is_some_and(|assignment| assignment.id == cluster_id)
+            })
+            .collect::<Vec<PeerId>>();
+
+        (member_ids, can_fault_injection_rules_of_cluster(resources, cluster_id))
+    }).await;
+
+    for peer_id in member_ids {
+        let result = peer_messaging_broker.send_to_peer(peer_id, downstream::Message::ApplyCanFaultInjectionRules(
+            ApplyCanFaultInjectionRules {
+                cluster_id: Some(cluster_id.into()),
+                rules: rules.iter().cloned().map(Into::into).collect(),
+            }
+        )).await;
+
+        if let Err(cause) = result {
+            warn!("Failed to send CAN fault injection rules of cluster <{cluster_id}> to peer <{peer_id}>: {cause}");
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::IpAddr;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::{CanFault, ClusterAssignment, ClusterName};
+    use opendut_types::peer::{PeerLocation, PeerName, PeerNetworkDescriptor};
+    use opendut_types::peer::configuration::{PeerConfiguration2, PeerNetworkConfiguration};
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::topology::Topology;
+    use opendut_types::util::net::{CanIdFilter, NetworkInterfaceName};
+
+    use crate::auth::permission::Role;
+    use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+
+    use crate::secrets::SecretCipher;
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_send_rules_to_the_peers_of_a_deployed_cluster() -> anyhow::Result<()> {
+        let settings = crate::settings::load_defaults()?;
+        let resources_manager = ResourcesManager::new();
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            Arc::new(SecretCipher::generate()?),
+            PeerMessagingBrokerOptions::load(&settings.config)?,
+        );
+
+        let cluster_id = ClusterId::random();
+        let peer_id = PeerId::random();
+
+        resources_manager.resources_mut(|resources| {
+            resources.insert(cluster_id, ClusterConfiguration {
+                id: cluster_id,
+                name: ClusterName::try_from("MyCluster").unwrap(),
+                leader: peer_id,
+                devices: Default::default(),
+                can_capture: None,
+            });
+            resources.insert(peer_id, PeerDescriptor {
+                id: peer_id,
+                name: PeerName::try_from("PeerA").unwrap(),
+                location: PeerLocation::try_from("Ulm").ok(),
+                network: PeerNetworkDescriptor {
+                    interfaces: vec![],
+                    bridge_name: None,
+                },
+                topology: Topology::default(),
+                executors: ExecutorDescriptors { executors: vec![] },
+            });
+            resources.insert(peer_id, PeerConfiguration {
+                cluster_assignment: Some(ClusterAssignment {
+                    id: cluster_id,
+                    leader: peer_id,
+                    assignments: vec![],
+                    can_capture: None,
+                }),
+                network: PeerNetworkConfiguration {
+                    bridge_name: NetworkInterfaceName::try_from("br-opendut-1").unwrap(),
+                },
+            });
+            resources.insert(peer_id, PeerConfiguration2 {
+                executors: vec![],
+                can_databases: vec![],
+                registry_credentials: vec![],
+                secrets: vec![],
+            });
+        }).await;
+
+        let (_, mut receiver) = peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?).await?;
+        let received = receiver.recv().await.unwrap().message.unwrap();
+        assert!(matches!(received, downstream::Message::ApplyPeerConfiguration(_)));
+        let received = receiver.recv().await.unwrap().message.unwrap();
+        assert!(matches!(received, downstream::Message::ApplyCanFaultInjectionRules(ApplyCanFaultInjectionRules { ref rules, .. }) if rules.is_empty()));
+
+        let rule = CanFaultInjectionRule {
+            id: CanFaultInjectionRuleId::random(),
+            cluster_id,
+            filter: CanIdFilter::exact(0x123, false)?,
+            fault: CanFault::Drop { percentage: 50 },
+            enabled: true,
+        };
+
+        let rule_id = store_can_fault_injection_rule(StoreCanFaultInjectionRuleParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            rule: Clone::clone(&rule),
+            requester: Requester::Unrestricted,
+        }).await?;
+
+        assert_that!(rule_id, eq(rule.id));
+
+        let received = receiver.recv().await.unwrap().message.unwrap();
+        assert_that!(received, eq(downstream::Message::ApplyCanFaultInjectionRules(ApplyCanFaultInjectionRules {
+            cluster_id: Some(cluster_id.into()),
+            rules: vec![Clone::clone(&rule).into()],
+        })));
+
+        let deleted = delete_can_fault_injection_rule(DeleteCanFaultInjectionRuleParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            rule_id,
+            requester: Requester::Unrestricted,
+        }).await?;
+
+        assert_that!(deleted, eq(rule));
+
+        let received = receiver.recv().await.unwrap().message.unwrap();
+        assert_that!(received, eq(downstream::Message::ApplyCanFaultInjectionRules(ApplyCanFaultInjectionRules {
+            cluster_id: Some(cluster_id.into()),
+            rules: vec![],
+        })));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_store_rule_for_unknown_cluster() -> anyhow::Result<()> {
+        let settings = crate::settings::load_defaults()?;
+        let resources_manager = ResourcesManager::new();
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            Arc::new(SecretCipher::generate()?),
+            PeerMessagingBrokerOptions::load(&settings.config)?,
+        );
+
+        let result = store_can_fault_injection_rule(StoreCanFaultInjectionRuleParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_messaging_broker,
+            rule: CanFaultInjectionRule {
+                id: CanFaultInjectionRuleId::random(),
+                cluster_id: ClusterId::random(),
+                filter: CanIdFilter::exact(0x123, false)?,
+                fault: CanFault::SetDlc { dlc: 2 },
+                enabled: true,
+            },
+            requester: Requester::Unrestricted,
+        }).await;
+
+        assert!(matches!(result, Err(StoreCanFaultInjectionRuleError::ClusterConfigurationNotFound { .. })));
+
+        let rules = list_can_fault_injection_rules(ListCanFaultInjectionRulesParams {
+            resources_manager,
+            cluster_id: None,
+            requester: Requester::Unrestricted,
+        }).await?;
+        assert!(rules.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_only_manage_rules_of_clusters_of_the_own_team() -> anyhow::Result<()> {
+        let settings = crate::settings::load_defaults()?;
+        let resources_manager = ResourcesManager::new();
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            Arc::new(SecretCipher::generate()?),
+            PeerMessagingBrokerOptions::load(&settings.config)?,
+        );
+
+        let cluster_a = ClusterId::random();
+        let cluster_b = ClusterId::random();
+        let rule_b = CanFaultInjectionRule {
+            id: CanFaultInjectionRuleId::random(),
+            cluster_id: cluster_b,
+            filter: CanIdFilter::exact(0x123, false)?,
+            fault: CanFault::Drop { percentage: 50 },
+            enabled: true,
+        };
+
+        resources_manager.resources_mut(|resources| {
+            for (cluster_id, name, owner, group) in [(cluster_a, "ClusterA", "alice", "/team-a"), (cluster_b, "ClusterB", "bob", "/team-b")] {
+                resources.insert(cluster_id, ClusterConfiguration {
+                    id: cluster_id,
+                    name: ClusterName::try_from(name).unwrap(),
+                    leader: PeerId::random(),
+                    devices: Default::default(),
+                    can_capture: None,
+                });
+                resources.insert(cluster_id, Ownership { owner: String::from(owner), group: Some(String::from(group)) });
+            }
+            resources.insert(rule_b.id, Clone::clone(&rule_b));
+        }).await;
+
+        let carol = Requester::User { name: String::from("carol"), groups: vec![String::from("/team-a")], role: Some(Role::Operator) };
+
+        let rules = list_can_fault_injection_rules(ListCanFaultInjectionRulesParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_id: None,
+            requester: Clone::clone(&carol),
+        }).await?;
+        assert!(rules.is_empty());
+
+        let result = store_can_fault_injection_rule(StoreCanFaultInjectionRuleParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            rule: CanFaultInjectionRule { cluster_id: cluster_b, ..Clone::clone(&rule_b) },
+            requester: Clone::clone(&carol),
+        }).await;
+        assert!(matches!(result, Err(StoreCanFaultInjectionRuleError::ClusterConfigurationNotFound { .. })));
+
+        let result = store_can_fault_injection_rule(StoreCanFaultInjectionRuleParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            rule: CanFaultInjectionRule { cluster_id: cluster_a, ..Clone::clone(&rule_b) },
+            requester: Clone::clone(&carol),
+        }).await;
+        assert!(matches!(result, Err(StoreCanFaultInjectionRuleError::ClusterConfigurationNotFound { .. })));
+
+        let result = delete_can_fault_injection_rule(DeleteCanFaultInjectionRuleParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            rule_id: rule_b.id,
+            requester: carol,
+        }).await;
+        assert!(matches!(result, Err(DeleteCanFaultInjectionRuleError::RuleNotFound { .. })));
+
+        let rules = list_can_fault_injection_rules(ListCanFaultInjectionRulesParams {
+            resources_manager,
+            cluster_id: None,
+            requester: Requester::Unrestricted,
+        }).await?;
+        assert_that!(rules, elements_are![eq(rule_b)]);
+
+        Ok(())
+    }
+}
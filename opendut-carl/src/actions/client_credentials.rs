@@ -0,0 +1,296 @@
+use std::time::Duration;
+
+use tracing::{debug, error, info};
+
+use opendut_auth::registration::client::{RegistrationClientError, RegistrationClientRef};
+pub use opendut_carl_api::carl::peer::{
+    CredentialsHolder,
+    RevokeClientError,
+    RotateClientSecretError,
+};
+use opendut_carl_api::proto::services::peer_messaging_broker::{downstream, RotateClientSecret};
+use opendut_types::resources::Id;
+use opendut_types::util::net::{ClientCredentials, ClientId, ClientSecret};
+
+use crate::auth::ownership::Requester;
+use crate::auth::peer_certificates::PeerCertificates;
+use crate::auth::revocation::RevokedClients;
+use crate::peer::broker::PeerMessagingBrokerRef;
+
+pub struct RotateClientSecretParams {
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+    pub oidc_registration_client: Option<RegistrationClientRef>,
+    pub holder: CredentialsHolder,
+}
+
+/// Time, which a peer has to confirm, that it stored its rotated client secret.
+const CLIENT_SECRET_CONFIRMATION_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Generates a new client secret for a peer or CLEO. The new credentials of CLEO are returned.
+/// Peers receive the new secret via the broker first. It replaces the previous secret at the identity provider only,
+/// once the peer confirmed storing it, after which the peer is disconnected to restart with it.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn rotate_client_secret(params: RotateClientSecretParams) -> Result<Option<ClientCredentials>, RotateClientSecretError> {
+
+    async fn inner(params: RotateClientSecretParams) -> Result<Option<ClientCredentials>, RotateClientSecretError> {
+
+        let RotateClientSecretParams { peer_messaging_broker, oidc_registration_client, holder } = params;
+
+        debug!("Rotating client secret of {holder}.");
+
+        if let CredentialsHolder::Peer(peer_id) = holder {
+            if !peer_messaging_broker.list_peers(Requester::Unrestricted).await.contains(&peer_id) {
+                return Err(RotateClientSecretError::PeerNotConnected { peer_id });
+            }
+        }
+
+        let registration_client = oidc_registration_client
+            .ok_or(RotateClientSecretError::ClientNotFound { holder })?;
+
+        let map_registration_error = |cause: RegistrationClientError| match cause {
+            RegistrationClientError::ClientNotFound => RotateClientSecretError::ClientNotFound { holder },
+            other => RotateClientSecretError::Internal { holder, cause: other.to_string() },
+        };
+
+        match holder {
+            CredentialsHolder::Peer(peer_id) => {
+                let client = registration_client.find_client_by_resource_id(peer_id.into()).await
+                    .map_err(map_registration_error)?;
+                let client_id = ClientId(Clone::clone(&client.client_id));
+                let client_secret = ClientSecret::random();
+
+                let confirmation = peer_messaging_broker.expect_client_secret_stored(peer_id);
+
+                peer_messaging_broker.send_to_peer(peer_id, downstream::Message::RotateClientSecret(
+                    RotateClientSecret {
+                        client_id: Some(Clone::clone(&client_id).into()),
+                        client_secret: Some(Clone::clone(&client_secret).into()),
+                    }
+                )).await
+                    .map_err(|cause| RotateClientSecretError::Internal { holder, cause: format!("New client secret could not be sent to the peer. The previous secret remains valid: {cause}") })?;
+
+                match tokio::time::timeout(CLIENT_SECRET_CONFIRMATION_TIMEOUT, confirmation).await {
+                    Ok(Ok(confirmed_client_id)) if confirmed_client_id == client_id => {}
+                    Ok(Ok(confirmed_client_id)) => {
+                        return Err(RotateClientSecretError::Internal { holder, cause: format!("Peer confirmed storing the secret of client '{}' instead of '{}'. The previous secret remains valid.", confirmed_client_id.value(), client_id.value()) });
+                    }
+                    Ok(Err(_)) | Err(_) => {
+                        return Err(RotateClientSecretError::Internal { holder, cause: String::from("Peer did not confirm storing the new client secret. The previous secret remains valid.") });
+                    }
+                }
+
+                registration_client.set_client_secret(&client, client_secret).await
+                    .map_err(|cause| RotateClientSecretError::Internal { holder, cause: format!("Peer stored the new client secret, but it could not be set at the identity provider. The previous secret remains valid, until the rotation is repeated: {cause}") })?;
+
+                if peer_messaging_broker.disconnect(peer_id).await.is_ok() {
+                    debug!("Disconnected peer <{peer_id}>, so that it restarts with its rotated client secret.");
+                }
+
+                info!("Successfully rotated client secret of {holder}.");
+                Ok(None)
+            }
+            CredentialsHolder::Cleo(_) => {
+                let credentials = registration_client.rotate_client_secret_by_resource_id(resource_id_of(holder)).await
+                    .map_err(map_registration_error)?;
+
+                info!("Successfully rotated client secret of {holder}.");
+                Ok(Some(credentials))
+            }
+        }
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct RevokeClientParams {
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+    pub oidc_registration_client: Option<RegistrationClientRef>,
+    pub revoked_clients: RevokedClients,
+    pub peer_certificates: PeerCertificates,
+    pub holder: CredentialsHolder,
+}
+
+/// Deletes the OIDC clients of a peer or CLEO and rejects access tokens, which were issued to them before.
+/// The client certificate of a peer is rejected as well. The revoked OIDC clients are returned.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn revoke_client(params: RevokeClientParams) -> Result<Vec<ClientId>, RevokeClientError> {
+
+    async fn inner(params: RevokeClientParams) -> Result<Vec<ClientId>, RevokeClientError> {
+
+        let RevokeClientParams { peer_messaging_broker, oidc_registration_client, revoked_clients, peer_certificates, holder } = params;
+
+        debug!("Revoking OIDC clients of {holder}.");
+
+        let certificate_revoked = match holder {
+            CredentialsHolder::Peer(peer_id) => peer_certificates.revoke(peer_id).await
+                .map_err(|cause| RevokeClientError::Internal { holder, cause: cause.to_string() })?,
+            CredentialsHolder::Cleo(_) => false,
+        };
+
+        let client_ids = match oidc_registration_client {
+            None => Vec::new(),
+            Some(registration_client) => revoke_oidc_clients(holder, registration_client, &revoked_clients).await?,
+        };
+
+        if client_ids.is_empty() && !certificate_revoked {
+            return Err(RevokeClientError::ClientNotFound { holder });
+        }
+
+        if let CredentialsHolder::Peer(peer_id) = holder {
+            if peer_messaging_broker.disconnect(peer_id).await.is_ok() {
+                debug!("Disconnected peer <{peer_id}>, as its credentials were revoked.");
+            }
+        }
+
+        if certificate_revoked {
+            info!("Successfully revoked the client certificate of {holder}.");
+        }
+        if !client_ids.is_empty() {
+            info!("Successfully revoked {} OIDC client(s) of {holder}.", client_ids.len());
+        }
+
+        Ok(client_ids)
+    }
+
+    async fn revoke_oidc_clients(holder: CredentialsHolder, registration_client: RegistrationClientRef, revoked_clients: &RevokedClients) -> Result<Vec<ClientId>, RevokeClientError> {
+
+        let resource_id = resource_id_of(holder);
+
+        let client_ids = registration_client.list_clients().await
+            .map_err(|cause| RevokeClientError::Internal { holder, cause: cause.to_string() })?
+            .filter_clients_by_resource_id(resource_id)
+            .into_iter()
+            .map(|client| ClientId(client.client_id))
+            .collect::<Vec<_>>();
+
+        if client_ids.is_empty() {
+            return Ok(client_ids);
+        }
+
+        for client_id in &client_ids {
+            revoked_clients.revoke(Clone::clone(client_id)).await
+                .map_err(|cause| RevokeClientError::Internal { holder, cause: cause.to_string() })?;
+        }
+
+        registration_client.delete_client_by_resource_id(resource_id).await
+            .map_err(|cause| RevokeClientError::Internal { holder, cause: cause.to_string() })?;
+
+        Ok(client_ids)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+fn resource_id_of(holder: CredentialsHolder) -> Id {
+    match holder {
+        CredentialsHolder::Peer(peer_id) => peer_id.into(),
+        CredentialsHolder::Cleo(cleo_id) => cleo_id.into(),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::IpAddr;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use opendut_types::cleo::CleoId;
+    use opendut_types::peer::PeerId;
+
+    use crate::auth::client_certificate::CertificateSerial;
+    use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+
+    use crate::secrets::SecretCipher;
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_only_rotate_the_secret_of_connected_peers() -> anyhow::Result<()> {
+        let settings = crate::settings::load_defaults()?;
+        let resources_manager = ResourcesManager::new();
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            Arc::new(SecretCipher::generate()?),
+            PeerMessagingBrokerOptions::load(&settings.config)?,
+        );
+
+        let peer_id = PeerId::random();
+        let params = || RotateClientSecretParams {
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            oidc_registration_client: None,
+            holder: CredentialsHolder::Peer(peer_id),
+        };
+
+        let result = rotate_client_secret(params()).await;
+        assert!(matches!(result, Err(RotateClientSecretError::PeerNotConnected { .. })));
+
+        let (_, _receiver) = peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?).await?;
+
+        let result = rotate_client_secret(params()).await;
+        assert!(matches!(result, Err(RotateClientSecretError::ClientNotFound { holder: CredentialsHolder::Peer(_) })));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_find_clients_while_authentication_is_disabled() -> anyhow::Result<()> {
+        let settings = crate::settings::load_defaults()?;
+        let resources_manager = ResourcesManager::new();
+        let revoked_clients = RevokedClients::default();
+
+        let result = revoke_client(RevokeClientParams {
+            peer_messaging_broker: PeerMessagingBroker::new(
+                Arc::clone(&resources_manager),
+                Arc::new(SecretCipher::generate()?),
+                PeerMessagingBrokerOptions::load(&settings.config)?,
+            ),
+            oidc_registration_client: None,
+            revoked_clients: Clone::clone(&revoked_clients),
+            peer_certificates: PeerCertificates::default(),
+            holder: CredentialsHolder::Cleo(CleoId::random()),
+        }).await;
+
+        assert!(matches!(result, Err(RevokeClientError::ClientNotFound { holder: CredentialsHolder::Cleo(_) })));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_revoke_the_client_certificate_of_a_peer() -> anyhow::Result<()> {
+        let settings = crate::settings::load_defaults()?;
+        let resources_manager = ResourcesManager::new();
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            Arc::new(SecretCipher::generate()?),
+            PeerMessagingBrokerOptions::load(&settings.config)?,
+        );
+
+        let peer_id = PeerId::random();
+        let peer_certificates = PeerCertificates::default();
+        peer_certificates.issue(peer_id, CertificateSerial(String::from("01"))).await?;
+        let (_, mut receiver) = peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?).await?;
+
+        let params = || RevokeClientParams {
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            oidc_registration_client: None,
+            revoked_clients: RevokedClients::default(),
+            peer_certificates: Clone::clone(&peer_certificates),
+            holder: CredentialsHolder::Peer(peer_id),
+        };
+
+        let client_ids = revoke_client(params()).await?;
+        assert!(client_ids.is_empty());
+        assert!(peer_certificates.is_revoked(&CertificateSerial(String::from("01"))));
+
+        while receiver.recv().await.is_some() {} //drain messages sent before the disconnect, the stream ends afterwards
+        assert!(peer_messaging_broker.list_peers(Requester::Unrestricted).await.is_empty());
+
+        let result = revoke_client(params()).await;
+        assert!(matches!(result, Err(RevokeClientError::ClientNotFound { holder: CredentialsHolder::Peer(_) })));
+
+        Ok(())
+    }
+}
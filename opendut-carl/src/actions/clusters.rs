@@ -1,15 +1,24 @@
+use std::ops::Not;
+
 use tracing::{debug, error, info};
 pub use opendut_carl_api::carl::cluster::{
     CreateClusterConfigurationError,
-    DeleteClusterConfigurationError
+    DeleteClusterConfigurationError,
+    ListClusterConfigurationsError,
+    TransferClusterConfigurationOwnershipError,
 };
-use opendut_types::cluster::{ClusterConfiguration, ClusterId};
+use opendut_types::cluster::{CanFaultInjectionRule, ClusterConfiguration, ClusterId};
+use opendut_types::peer::PeerDescriptor;
 
+use crate::auth::ownership::{Ownership, Requester};
 use crate::resources::manager::ResourcesManagerRef;
 
 pub struct CreateClusterConfigurationParams {
     pub resources_manager: ResourcesManagerRef,
     pub cluster_configuration: ClusterConfiguration,
+    /// Becomes the owner of newly created cluster configurations. Existing ones may only be replaced, if the requester may access them.
+    /// The leader and the devices of the cluster must belong to peers, which the requester may access.
+    pub requester: Requester,
 }
 
 #[tracing::instrument(skip(params), level="trace")]
@@ -24,8 +33,42 @@ pub async fn create_cluster_configuration(params: CreateClusterConfigurationPara
         debug!("Creating cluster configuration '{cluster_name}' <{cluster_id}>.");
 
         resources_manager.resources_mut(|resources| {
+            let existing = resources.get::<ClusterConfiguration>(cluster_id);
+            if let Some(other) = &existing {
+                if params.requester.may_access(resources.get::<Ownership>(cluster_id).as_ref()).not() {
+                    return Err(CreateClusterConfigurationError::ClusterConfigurationAlreadyExists {
+                        actual_id: cluster_id,
+                        actual_name: Clone::clone(&cluster_name),
+                        other_id: other.id,
+                        other_name: Clone::clone(&other.name),
+                    });
+                }
+            }
+
+            let accessible_peers = resources.iter::<PeerDescriptor>()
+                .filter(|peer| params.requester.may_access(resources.get::<Ownership>(peer.id).as_ref()))
+                .collect::<Vec<_>>();
+
+            let leader_id = params.cluster_configuration.leader;
+            if accessible_peers.iter().any(|peer| peer.id == leader_id).not() {
+                return Err(CreateClusterConfigurationError::LeaderNotFound { cluster_id, cluster_name: Clone::clone(&cluster_name), leader_id });
+            }
+            for &device_id in &params.cluster_configuration.devices {
+                let device_exists = accessible_peers.iter()
+                    .any(|peer| peer.topology.devices.iter().any(|device| device.id == device_id));
+                if device_exists.not() {
+                    return Err(CreateClusterConfigurationError::DeviceNotFound { cluster_id, cluster_name: Clone::clone(&cluster_name), device_id });
+                }
+            }
+
+            if existing.is_none() {
+                if let Some(ownership) = params.requester.ownership() {
+                    resources.insert(cluster_id, ownership);
+                }
+            }
             resources.insert(cluster_id, params.cluster_configuration);
-        }).await;
+            Ok(())
+        }).await?;
 
         info!("Successfully created cluster configuration '{cluster_name}' <{cluster_id}>.");
 
@@ -39,6 +82,7 @@ pub async fn create_cluster_configuration(params: CreateClusterConfigurationPara
 pub struct DeleteClusterConfigurationParams {
     pub resources_manager: ResourcesManagerRef,
     pub cluster_id: ClusterId,
+    pub requester: Requester,
 }
 
 #[tracing::instrument(skip(params), level="trace")]
@@ -52,8 +96,19 @@ pub async fn delete_cluster_configuration(params: DeleteClusterConfigurationPara
         debug!("Deleting cluster configuration <{cluster_id}>.");
 
         let cluster_configuration = resources_manager.resources_mut(|resources| {
-            resources.remove::<ClusterConfiguration>(cluster_id)
-                .ok_or_else(|| DeleteClusterConfigurationError::ClusterConfigurationNotFound { cluster_id })
+            if params.requester.may_access(resources.get::<Ownership>(cluster_id).as_ref()).not() {
+                return Err(DeleteClusterConfigurationError::ClusterConfigurationNotFound { cluster_id });
+            }
+
+            let cluster_configuration = resources.remove::<ClusterConfiguration>(cluster_id)
+                .ok_or_else(|| DeleteClusterConfigurationError::ClusterConfigurationNotFound { cluster_id })?;
+            resources.remove::<Ownership>(cluster_id);
+
+            for rule in crate::actions::can_fault_injection_rules_of_cluster(resources, cluster_id) {
+                resources.remove::<CanFaultInjectionRule>(rule.id);
+            }
+
+            Ok(cluster_configuration)
         }).await?;
 
         let cluster_name = Clone::clone(&cluster_configuration.name);
@@ -66,3 +121,250 @@ pub async fn delete_cluster_configuration(params: DeleteClusterConfigurationPara
     inner(params).await
         .inspect_err(|err| error!("{err}"))
 }
+
+pub struct ListClusterConfigurationsParams {
+    pub resources_manager: ResourcesManagerRef,
+    /// Only cluster configurations, which the requester may access, are listed.
+    pub requester: Requester,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_cluster_configurations(params: ListClusterConfigurationsParams) -> Result<Vec<ClusterConfiguration>, ListClusterConfigurationsError> {
+
+    async fn inner(params: ListClusterConfigurationsParams) -> Result<Vec<ClusterConfiguration>, ListClusterConfigurationsError> {
+
+        debug!("Querying all cluster configurations.");
+
+        let configurations = params.resources_manager.resources(|resources| {
+            resources.iter::<ClusterConfiguration>()
+                .filter(|configuration| params.requester.may_access(resources.get::<Ownership>(configuration.id).as_ref()))
+                .cloned()
+                .collect::<Vec<_>>()
+        }).await;
+
+        info!("Successfully queried all cluster configurations.");
+
+        Ok(configurations)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct TransferClusterConfigurationOwnershipParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub cluster_id: ClusterId,
+    pub ownership: Ownership,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn transfer_cluster_configuration_ownership(params: TransferClusterConfigurationOwnershipParams) -> Result<ClusterId, TransferClusterConfigurationOwnershipError> {
+
+    async fn inner(params: TransferClusterConfigurationOwnershipParams) -> Result<ClusterId, TransferClusterConfigurationOwnershipError> {
+
+        let cluster_id = params.cluster_id;
+        let ownership = params.ownership;
+
+        debug!("Transferring ownership of cluster configuration <{cluster_id}> to {ownership:?}.");
+
+        params.resources_manager.resources_mut(|resources| {
+            if resources.get::<ClusterConfiguration>(cluster_id).is_none() {
+                return Err(TransferClusterConfigurationOwnershipError::ClusterConfigurationNotFound { cluster_id });
+            }
+            resources.insert(cluster_id, Clone::clone(&ownership));
+            Ok(())
+        }).await?;
+
+        info!("Successfully transferred ownership of cluster configuration <{cluster_id}> to {ownership:?}.");
+
+        Ok(cluster_id)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+
+    use opendut_types::cluster::ClusterName;
+    use opendut_types::peer::{PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::topology::{DeviceDescriptor, DeviceId, DeviceName, Topology};
+    use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceName};
+
+    use crate::auth::permission::Role;
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    fn user(name: &str, groups: &[&str]) -> Requester {
+        Requester::User {
+            name: name.to_string(),
+            groups: groups.iter().map(|group| group.to_string()).collect(),
+            role: Some(Role::Operator),
+        }
+    }
+
+    fn cluster_configuration(name: &str, leader: PeerId) -> ClusterConfiguration {
+        ClusterConfiguration {
+            id: ClusterId::random(),
+            name: ClusterName::try_from(name).unwrap(),
+            leader,
+            devices: HashSet::new(),
+            can_capture: None,
+        }
+    }
+
+    /// Stores a peer with one device and returns the ids of both.
+    async fn store_peer(resources_manager: &ResourcesManagerRef, ownership: Option<Ownership>) -> (PeerId, DeviceId) {
+        let peer_id = PeerId::random();
+        let device_id = DeviceId::random();
+        let peer_descriptor = PeerDescriptor {
+            id: peer_id,
+            name: PeerName::try_from("PeerA").unwrap(),
+            location: PeerLocation::try_from("Ulm").ok(),
+            network: PeerNetworkDescriptor {
+                interfaces: vec![],
+                bridge_name: None,
+            },
+            topology: Topology {
+                devices: vec![
+                    DeviceDescriptor {
+                        id: device_id,
+                        name: DeviceName::try_from("DeviceA").unwrap(),
+                        description: None,
+                        interface: NetworkInterfaceDescriptor {
+                            name: NetworkInterfaceName::try_from("eth0").unwrap(),
+                            configuration: NetworkInterfaceConfiguration::Ethernet,
+                        },
+                        tags: vec![],
+                    }
+                ],
+            },
+            executors: ExecutorDescriptors { executors: vec![] },
+        };
+        resources_manager.resources_mut(|resources| {
+            resources.insert(peer_id, peer_descriptor);
+            if let Some(ownership) = ownership {
+                resources.insert(peer_id, ownership);
+            }
+        }).await;
+        (peer_id, device_id)
+    }
+
+    async fn list(resources_manager: &ResourcesManagerRef, requester: Requester) -> anyhow::Result<Vec<ClusterId>> {
+        let configurations = list_cluster_configurations(ListClusterConfigurationsParams {
+            resources_manager: Arc::clone(resources_manager),
+            requester,
+        }).await?;
+        Ok(configurations.into_iter().map(|configuration| configuration.id).collect())
+    }
+
+    #[tokio::test]
+    async fn should_only_list_replace_and_delete_cluster_configurations_of_the_own_team() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let (leader, _) = store_peer(&resources_manager, None).await;
+        let configuration = cluster_configuration("TeamACluster", leader);
+        let cluster_id = configuration.id;
+
+        create_cluster_configuration(CreateClusterConfigurationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_configuration: Clone::clone(&configuration),
+            requester: user("alice", &["/team-a"]),
+        }).await?;
+
+        assert_that!(list(&resources_manager, user("carol", &["/team-a"])).await?, elements_are![eq(cluster_id)]);
+        assert!(list(&resources_manager, user("bob", &["/team-b"])).await?.is_empty());
+
+        let result = create_cluster_configuration(CreateClusterConfigurationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_configuration: ClusterConfiguration { name: ClusterName::try_from("Hijacked").unwrap(), ..Clone::clone(&configuration) },
+            requester: user("bob", &["/team-b"]),
+        }).await;
+        assert!(matches!(result, Err(CreateClusterConfigurationError::ClusterConfigurationAlreadyExists { .. })));
+
+        let result = delete_cluster_configuration(DeleteClusterConfigurationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_id,
+            requester: user("bob", &["/team-b"]),
+        }).await;
+        assert!(matches!(result, Err(DeleteClusterConfigurationError::ClusterConfigurationNotFound { .. })));
+        assert_that!(resources_manager.get::<ClusterConfiguration>(cluster_id).await, some(eq(configuration)));
+
+        delete_cluster_configuration(DeleteClusterConfigurationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_id,
+            requester: user("carol", &["/team-a"]),
+        }).await?;
+        assert_that!(resources_manager.get::<Ownership>(cluster_id).await, none());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_create_cluster_configurations_with_peers_of_other_teams() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let team_a = Some(Ownership { owner: String::from("alice"), group: Some(String::from("/team-a")) });
+        let team_b = Some(Ownership { owner: String::from("bob"), group: Some(String::from("/team-b")) });
+        let (peer_a, device_a) = store_peer(&resources_manager, team_a).await;
+        let (peer_b, device_b) = store_peer(&resources_manager, team_b).await;
+
+        let configuration = cluster_configuration("CrossTeamCluster", peer_a);
+        let result = create_cluster_configuration(CreateClusterConfigurationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_configuration: Clone::clone(&configuration),
+            requester: user("bob", &["/team-b"]),
+        }).await;
+        assert!(matches!(result, Err(CreateClusterConfigurationError::LeaderNotFound { leader_id, .. }) if leader_id == peer_a));
+
+        let configuration = ClusterConfiguration { devices: HashSet::from([device_b, device_a]), ..cluster_configuration("CrossTeamCluster", peer_b) };
+        let result = create_cluster_configuration(CreateClusterConfigurationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_configuration: Clone::clone(&configuration),
+            requester: user("bob", &["/team-b"]),
+        }).await;
+        assert!(matches!(result, Err(CreateClusterConfigurationError::DeviceNotFound { device_id, .. }) if device_id == device_a));
+        assert_that!(resources_manager.get::<ClusterConfiguration>(configuration.id).await, none());
+
+        let configuration = ClusterConfiguration { devices: HashSet::from([device_b]), ..cluster_configuration("TeamBCluster", peer_b) };
+        create_cluster_configuration(CreateClusterConfigurationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_configuration: Clone::clone(&configuration),
+            requester: user("bob", &["/team-b"]),
+        }).await?;
+        assert_that!(resources_manager.get::<ClusterConfiguration>(configuration.id).await, some(eq(configuration)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_transfer_ownership() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let (leader, _) = store_peer(&resources_manager, None).await;
+        let configuration = cluster_configuration("TeamACluster", leader);
+        let cluster_id = configuration.id;
+
+        create_cluster_configuration(CreateClusterConfigurationParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_configuration: configuration,
+            requester: user("alice", &["/team-a"]),
+        }).await?;
+
+        transfer_cluster_configuration_ownership(TransferClusterConfigurationOwnershipParams {
+            resources_manager: Arc::clone(&resources_manager),
+            cluster_id,
+            ownership: Ownership { owner: String::from("bob"), group: None },
+        }).await?;
+
+        assert!(list(&resources_manager, user("alice", &["/team-a"])).await?.is_empty());
+        assert_that!(list(&resources_manager, user("bob", &[])).await?, elements_are![eq(cluster_id)]);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,228 @@
+use std::ops::Not;
+
+use tracing::{debug, error, info};
+
+use opendut_carl_api::proto::services::peer_messaging_broker::{downstream, ExecuteExecutorCommand};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::peer::executor::ExecutorCommand;
+
+use crate::auth::ownership::{Ownership, Requester};
+use crate::peer::broker::{self, PeerMessagingBrokerRef};
+use crate::resources::manager::ResourcesManagerRef;
+
+pub struct ExecuteExecutorCommandParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
+    pub peer_id: PeerId,
+    pub executor_name: String,
+    pub command: ExecutorCommand,
+    /// Peers, which the requester may not access, are treated as if they did not exist.
+    pub requester: Requester,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ExecuteExecutorCommandError {
+    #[error("Command '{command}' could not be executed, because peer <{peer_id}> does not exist.")]
+    PeerNotFound {
+        peer_id: PeerId,
+        command: ExecutorCommand,
+    },
+    #[error("Command '{command}' could not be executed, because peer <{peer_id}> has no executor named '{executor_name}'.")]
+    ExecutorNotFound {
+        peer_id: PeerId,
+        executor_name: String,
+        command: ExecutorCommand,
+    },
+    #[error("Command '{command}' could not be executed, because peer <{peer_id}> is not connected.")]
+    PeerNotConnected {
+        peer_id: PeerId,
+        command: ExecutorCommand,
+    },
+    #[error("Command '{command}' could not be sent to peer <{peer_id}>:\n  {cause}")]
+    Internal {
+        peer_id: PeerId,
+        command: ExecutorCommand,
+        cause: String,
+    },
+}
+
+/// Sends a command to an executor of a connected peer. Whether the command succeeds, is reported by the peer via the runs of the executor.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn execute_executor_command(params: ExecuteExecutorCommandParams) -> Result<(), ExecuteExecutorCommandError> {
+
+    async fn inner(params: ExecuteExecutorCommandParams) -> Result<(), ExecuteExecutorCommandError> {
+
+        let ExecuteExecutorCommandParams { resources_manager, peer_messaging_broker, peer_id, executor_name, command, requester } = params;
+
+        debug!("Executing command '{command}' for executor '{executor_name}' on peer <{peer_id}>.");
+
+        let peer_descriptor = resources_manager.resources(|resources| {
+            if requester.may_access(resources.get::<Ownership>(peer_id).as_ref()).not() {
+                return None;
+            }
+            resources.get::<PeerDescriptor>(peer_id)
+        }).await
+            .ok_or(ExecuteExecutorCommandError::PeerNotFound { peer_id, command })?;
+
+        let has_executor = peer_descriptor.executors.executors.iter()
+            .any(|executor| executor.name() == executor_name);
+        if !has_executor {
+            return Err(ExecuteExecutorCommandError::ExecutorNotFound { peer_id, executor_name, command });
+        }
+
+        peer_messaging_broker.send_to_peer(peer_id, downstream::Message::ExecuteExecutorCommand(
+            ExecuteExecutorCommand {
+                executor_name: Clone::clone(&executor_name),
+                command: Some(command.into()),
+            }
+        )).await
+            .map_err(|cause| match cause {
+                broker::Error::PeerNotFound(_) => ExecuteExecutorCommandError::PeerNotConnected { peer_id, command },
+                other => ExecuteExecutorCommandError::Internal { peer_id, command, cause: other.to_string() },
+            })?;
+
+        info!("Successfully sent command '{command}' for executor '{executor_name}' to peer <{peer_id}>.");
+
+        Ok(())
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod test {
+    use std::net::IpAddr;
+    use std::str::FromStr;
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+
+    use opendut_types::peer::{PeerLocation, PeerName, PeerNetworkDescriptor};
+    use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorDescriptors, ExecutorKind};
+    use opendut_types::peer::executor::container::{ContainerCommand, ContainerImage, ContainerImagePullPolicy, ContainerName, Engine};
+    use opendut_types::topology::Topology;
+
+    use crate::auth::permission::Role;
+    use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+
+    use crate::secrets::SecretCipher;
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    fn peer_descriptor(peer_id: PeerId) -> PeerDescriptor {
+        PeerDescriptor {
+            id: peer_id,
+            name: PeerName::try_from("PeerA").unwrap(),
+            location: PeerLocation::try_from("Ulm").ok(),
+            network: PeerNetworkDescriptor {
+                interfaces: vec![],
+                bridge_name: None,
+            },
+            topology: Topology::default(),
+            executors: ExecutorDescriptors {
+                executors: vec![ExecutorDescriptor {
+                    kind: ExecutorKind::Container {
+                        engine: Engine::Docker,
+                        name: ContainerName::Value(String::from("nmap-scan")),
+                        image: ContainerImage::try_from("testenv-nmap").unwrap(),
+                        pull_policy: ContainerImagePullPolicy::IfNotPresent,
+                        digest: None,
+                        registry_credential: None,
+                        volumes: vec![],
+                        devices: vec![],
+                        envs: vec![],
+                        ports: vec![],
+                        command: ContainerCommand::Default,
+                        args: vec![],
+                        can_databases: vec![],
+                        cpus: None,
+                        memory: None,
+                        pids_limit: None,
+                        network_mode: Default::default(),
+                        cap_add: vec![],
+                        cap_drop: vec![],
+                        read_only_rootfs: false,
+                    },
+                    results_url: None,
+                }],
+            },
+        }
+    }
+
+    #[tokio::test]
+    async fn should_send_command_to_connected_peer() -> anyhow::Result<()> {
+        let settings = crate::settings::load_defaults()?;
+        let resources_manager = ResourcesManager::new();
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            Arc::new(SecretCipher::generate()?),
+            PeerMessagingBrokerOptions::load(&settings.config)?,
+        );
+
+        let peer_id = PeerId::random();
+        resources_manager.resources_mut(|resources| {
+            resources.insert(peer_id, peer_descriptor(peer_id));
+        }).await;
+
+        let params = |executor_name: &str| ExecuteExecutorCommandParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            peer_id,
+            executor_name: String::from(executor_name),
+            command: ExecutorCommand::Restart,
+            requester: Requester::Unrestricted,
+        };
+
+        let result = execute_executor_command(params("nmap-scan")).await;
+        assert!(matches!(result, Err(ExecuteExecutorCommandError::PeerNotConnected { .. })));
+
+        let (_, mut receiver) = peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?).await?;
+
+        let result = execute_executor_command(params("unknown")).await;
+        assert!(matches!(result, Err(ExecuteExecutorCommandError::ExecutorNotFound { .. })));
+
+        execute_executor_command(params("nmap-scan")).await?;
+
+        let received = receiver.recv().await.unwrap().message.unwrap();
+        assert_that!(received, eq(downstream::Message::ExecuteExecutorCommand(ExecuteExecutorCommand {
+            executor_name: String::from("nmap-scan"),
+            command: Some(ExecutorCommand::Restart.into()),
+        })));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_send_commands_to_peers_of_other_teams() -> anyhow::Result<()> {
+        let settings = crate::settings::load_defaults()?;
+        let resources_manager = ResourcesManager::new();
+        let peer_messaging_broker = PeerMessagingBroker::new(
+            Arc::clone(&resources_manager),
+            Arc::new(SecretCipher::generate()?),
+            PeerMessagingBrokerOptions::load(&settings.config)?,
+        );
+
+        let peer_id = PeerId::random();
+        resources_manager.resources_mut(|resources| {
+            resources.insert(peer_id, peer_descriptor(peer_id));
+            resources.insert(peer_id, Ownership { owner: String::from("alice"), group: Some(String::from("/team-a")) });
+        }).await;
+
+        let (_, mut receiver) = peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?).await?;
+
+        let result = execute_executor_command(ExecuteExecutorCommandParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+            peer_id,
+            executor_name: String::from("nmap-scan"),
+            command: ExecutorCommand::Stop,
+            requester: Requester::User { name: String::from("bob"), groups: vec![String::from("/team-b")], role: Some(Role::Operator) },
+        }).await;
+        assert!(matches!(result, Err(ExecuteExecutorCommandError::PeerNotFound { .. })));
+        assert!(receiver.try_recv().is_err());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,338 @@
+use tracing::{debug, error, info};
+
+pub use opendut_carl_api::carl::peer::ListExecutorRunsError;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::peer::configuration::ParameterId;
+use opendut_types::peer::executor::run::{ExecutorRun, ExecutorRunId};
+
+use crate::auth::ownership::{Ownership, Requester};
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::Resources;
+
+/// Number of finished runs, which are kept per executor. Older runs are discarded.
+const MAX_FINISHED_RUNS_PER_EXECUTOR: usize = 10;
+
+pub struct StoreExecutorRunParams {
+    pub resources_manager: ResourcesManagerRef,
+    /// ID of the peer, which reported the run.
+    pub peer_id: PeerId,
+    pub run: ExecutorRun,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum StoreExecutorRunError {
+    #[error("Executor run <{run_id}> reported by peer <{reporting_peer_id}> belongs to another peer <{peer_id}>!")]
+    PeerMismatch {
+        run_id: ExecutorRunId,
+        reporting_peer_id: PeerId,
+        peer_id: PeerId,
+    },
+    #[error("Executor run <{run_id}> could not be stored, because peer <{peer_id}> does not exist!")]
+    PeerNotFound {
+        run_id: ExecutorRunId,
+        peer_id: PeerId,
+    },
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn store_executor_run(params: StoreExecutorRunParams) -> Result<ExecutorRunId, StoreExecutorRunError> {
+
+    async fn inner(params: StoreExecutorRunParams) -> Result<ExecutorRunId, StoreExecutorRunError> {
+
+        let run = params.run;
+        let run_id = run.id;
+        let peer_id = run.peer_id;
+
+        if peer_id != params.peer_id {
+            return Err(StoreExecutorRunError::PeerMismatch { run_id, reporting_peer_id: params.peer_id, peer_id });
+        }
+
+        debug!("Storing executor run <{run_id}> of executor '{}' on peer <{peer_id}> in state '{}'.", run.executor_name, run.state);
+
+        params.resources_manager.resources_mut(|resources| {
+            if resources.get::<PeerDescriptor>(peer_id).is_none() {
+                return Err(StoreExecutorRunError::PeerNotFound { run_id, peer_id });
+            }
+
+            let executor_id = run.executor_id;
+            resources.insert(run_id, run);
+            discard_outdated_executor_runs(resources, peer_id, executor_id);
+            Ok(())
+        }).await?;
+
+        info!("Successfully stored executor run <{run_id}> of peer <{peer_id}>.");
+
+        Ok(run_id)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct ListExecutorRunsParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_id: Option<PeerId>,
+    /// Only runs of peers, which the requester may access, are listed.
+    pub requester: Requester,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_executor_runs(params: ListExecutorRunsParams) -> Result<Vec<ExecutorRun>, ListExecutorRunsError> {
+
+    async fn inner(params: ListExecutorRunsParams) -> Result<Vec<ExecutorRun>, ListExecutorRunsError> {
+
+        let resources_manager = params.resources_manager;
+
+        debug!("Querying executor runs.");
+
+        let mut runs = resources_manager.resources(|resources| {
+            resources.iter::<ExecutorRun>()
+                .filter(|run| match params.peer_id {
+                    Some(peer_id) => run.peer_id == peer_id,
+                    None => true,
+                })
+                .filter(|run| params.requester.may_access(resources.get::<Ownership>(run.peer_id).as_ref()))
+                .cloned()
+                .collect::<Vec<_>>()
+        }).await;
+
+        runs.sort_by_key(|run| run.created_at);
+
+        info!("Successfully queried executor runs.");
+
+        Ok(runs)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub(crate) fn remove_executor_runs_of_peer(resources: &mut Resources, peer_id: PeerId) {
+    let run_ids = resources.iter::<ExecutorRun>()
+        .filter(|run| run.peer_id == peer_id)
+        .map(|run| run.id)
+        .collect::<Vec<_>>();
+
+    for run_id in run_ids {
+        resources.remove::<ExecutorRun>(run_id);
+    }
+}
+
+fn discard_outdated_executor_runs(resources: &mut Resources, peer_id: PeerId, executor_id: ParameterId) {
+    let mut finished_runs = resources.iter::<ExecutorRun>()
+        .filter(|run| run.peer_id == peer_id && run.executor_id == executor_id && run.state.is_finished())
+        .map(|run| (run.created_at, run.id))
+        .collect::<Vec<_>>();
+
+    if finished_runs.len() <= MAX_FINISHED_RUNS_PER_EXECUTOR {
+        return;
+    }
+
+    finished_runs.sort_by_key(|(created_at, _)| *created_at);
+    let outdated = finished_runs.len() - MAX_FINISHED_RUNS_PER_EXECUTOR;
+    for (_, run_id) in finished_runs.into_iter().take(outdated) {
+        resources.remove::<ExecutorRun>(run_id);
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use googletest::prelude::*;
+    use uuid::Uuid;
+
+    use opendut_types::peer::{PeerLocation, PeerName, PeerNetworkDescriptor};
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::peer::executor::run::ExecutorRunState;
+    use opendut_types::topology::Topology;
+
+    use crate::auth::permission::Role;
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    fn peer_descriptor(peer_id: PeerId) -> PeerDescriptor {
+        PeerDescriptor {
+            id: peer_id,
+            name: PeerName::try_from("PeerA").unwrap(),
+            location: PeerLocation::try_from("Ulm").ok(),
+            network: PeerNetworkDescriptor {
+                interfaces: vec![],
+                bridge_name: None,
+            },
+            topology: Topology::default(),
+            executors: ExecutorDescriptors { executors: vec![] },
+        }
+    }
+
+    fn executor_run(peer_id: PeerId, executor_id: ParameterId, created_at_secs: u64, state: ExecutorRunState) -> ExecutorRun {
+        ExecutorRun {
+            id: ExecutorRunId::random(),
+            peer_id,
+            executor_id,
+            executor_name: String::from("nmap-test"),
+            state,
+            exit_code: None,
+            created_at: UNIX_EPOCH + Duration::from_secs(created_at_secs),
+            started_at: None,
+            finished_at: None,
+            results_location: None,
+            image_digest: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn should_store_and_list_executor_runs_per_peer() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let peer_a = PeerId::random();
+        let peer_b = PeerId::random();
+        let executor_id = ParameterId(Uuid::new_v4());
+
+        resources_manager.resources_mut(|resources| {
+            resources.insert(peer_a, peer_descriptor(peer_a));
+            resources.insert(peer_b, peer_descriptor(peer_b));
+        }).await;
+
+        let run_a = executor_run(peer_a, executor_id, 1, ExecutorRunState::Running);
+        let run_b = executor_run(peer_b, executor_id, 2, ExecutorRunState::Pending);
+
+        for (peer_id, run) in [(peer_a, Clone::clone(&run_a)), (peer_b, Clone::clone(&run_b))] {
+            store_executor_run(StoreExecutorRunParams {
+                resources_manager: Arc::clone(&resources_manager),
+                peer_id,
+                run,
+            }).await?;
+        }
+
+        let finished_run_a = ExecutorRun {
+            state: ExecutorRunState::Succeeded,
+            exit_code: Some(0),
+            ..Clone::clone(&run_a)
+        };
+        store_executor_run(StoreExecutorRunParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_id: peer_a,
+            run: Clone::clone(&finished_run_a),
+        }).await?;
+
+        let runs = list_executor_runs(ListExecutorRunsParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_id: Some(peer_a),
+            requester: Requester::Unrestricted,
+        }).await?;
+        assert_that!(runs, elements_are![eq(finished_run_a)]);
+
+        let runs = list_executor_runs(ListExecutorRunsParams {
+            resources_manager,
+            peer_id: None,
+            requester: Requester::Unrestricted,
+        }).await?;
+        assert_that!(runs.len(), eq(2));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_only_list_executor_runs_of_peers_of_the_own_team() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let peer_a = PeerId::random();
+        let peer_b = PeerId::random();
+        let executor_id = ParameterId(Uuid::new_v4());
+
+        resources_manager.resources_mut(|resources| {
+            resources.insert(peer_a, peer_descriptor(peer_a));
+            resources.insert(peer_a, Ownership { owner: String::from("alice"), group: Some(String::from("/team-a")) });
+            resources.insert(peer_b, peer_descriptor(peer_b));
+            resources.insert(peer_b, Ownership { owner: String::from("bob"), group: Some(String::from("/team-b")) });
+        }).await;
+
+        let run_a = executor_run(peer_a, executor_id, 1, ExecutorRunState::Running);
+        let run_b = executor_run(peer_b, executor_id, 2, ExecutorRunState::Running);
+        for (peer_id, run) in [(peer_a, Clone::clone(&run_a)), (peer_b, run_b)] {
+            store_executor_run(StoreExecutorRunParams {
+                resources_manager: Arc::clone(&resources_manager),
+                peer_id,
+                run,
+            }).await?;
+        }
+
+        let carol = Requester::User { name: String::from("carol"), groups: vec![String::from("/team-a")], role: Some(Role::Viewer) };
+
+        let runs = list_executor_runs(ListExecutorRunsParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_id: None,
+            requester: Clone::clone(&carol),
+        }).await?;
+        assert_that!(runs, elements_are![eq(run_a)]);
+
+        let runs = list_executor_runs(ListExecutorRunsParams {
+            resources_manager,
+            peer_id: Some(peer_b),
+            requester: carol,
+        }).await?;
+        assert!(runs.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_executor_runs_reported_for_another_peer() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let peer_a = PeerId::random();
+        let peer_b = PeerId::random();
+
+        resources_manager.resources_mut(|resources| {
+            resources.insert(peer_a, peer_descriptor(peer_a));
+            resources.insert(peer_b, peer_descriptor(peer_b));
+        }).await;
+
+        let result = store_executor_run(StoreExecutorRunParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_id: peer_a,
+            run: executor_run(peer_b, ParameterId(Uuid::new_v4()), 1, ExecutorRunState::Running),
+        }).await;
+        assert!(matches!(result, Err(StoreExecutorRunError::PeerMismatch { .. })));
+
+        let runs = list_executor_runs(ListExecutorRunsParams {
+            resources_manager,
+            peer_id: None,
+            requester: Requester::Unrestricted,
+        }).await?;
+        assert!(runs.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_discard_outdated_finished_executor_runs() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let peer_id = PeerId::random();
+        let executor_id = ParameterId(Uuid::new_v4());
+
+        resources_manager.resources_mut(|resources| {
+            resources.insert(peer_id, peer_descriptor(peer_id));
+        }).await;
+
+        let runs_to_store = MAX_FINISHED_RUNS_PER_EXECUTOR + 2;
+        for created_at_secs in 0..runs_to_store as u64 {
+            store_executor_run(StoreExecutorRunParams {
+                resources_manager: Arc::clone(&resources_manager),
+                peer_id,
+                run: executor_run(peer_id, executor_id, created_at_secs, ExecutorRunState::Failed),
+            }).await?;
+        }
+
+        let runs = list_executor_runs(ListExecutorRunsParams {
+            resources_manager,
+            peer_id: Some(peer_id),
+            requester: Requester::Unrestricted,
+        }).await?;
+        assert_that!(runs.len(), eq(MAX_FINISHED_RUNS_PER_EXECUTOR));
+        assert_that!(runs[0].created_at, eq(UNIX_EPOCH + Duration::from_secs(2)));
+
+        Ok(())
+    }
+}
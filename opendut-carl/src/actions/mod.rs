@@ -1,5 +1,57 @@
 #![allow(unused_imports)]
 
+pub use can_databases::{
+    store_can_database,
+    StoreCanDatabaseParams,
+    StoreCanDatabaseError,
+};
+
+pub use can_databases::{
+    delete_can_database,
+    DeleteCanDatabaseParams,
+    DeleteCanDatabaseError,
+};
+
+pub use can_databases::{
+    list_can_databases,
+    ListCanDatabasesParams,
+    ListCanDatabasesError,
+};
+
+pub use can_fault_injection::{
+    store_can_fault_injection_rule,
+    StoreCanFaultInjectionRuleParams,
+    StoreCanFaultInjectionRuleError,
+};
+
+pub use can_fault_injection::{
+    delete_can_fault_injection_rule,
+    DeleteCanFaultInjectionRuleParams,
+    DeleteCanFaultInjectionRuleError,
+};
+
+pub use can_fault_injection::{
+    list_can_fault_injection_rules,
+    ListCanFaultInjectionRulesParams,
+    ListCanFaultInjectionRulesError,
+};
+
+pub(crate) use can_fault_injection::can_fault_injection_rules_of_cluster;
+
+pub use client_credentials::{
+    rotate_client_secret,
+    RotateClientSecretParams,
+    RotateClientSecretError,
+};
+
+pub use client_credentials::{
+    revoke_client,
+    RevokeClientParams,
+    RevokeClientError,
+};
+
+pub use client_credentials::CredentialsHolder;
+
 pub use clusters::{
     create_cluster_configuration,
     CreateClusterConfigurationParams,
@@ -12,6 +64,50 @@ pub use clusters::{
     DeleteClusterConfigurationError,
 };
 
+pub use clusters::{
+    list_cluster_configurations,
+    ListClusterConfigurationsParams,
+    ListClusterConfigurationsError,
+};
+
+pub use clusters::{
+    transfer_cluster_configuration_ownership,
+    TransferClusterConfigurationOwnershipParams,
+    TransferClusterConfigurationOwnershipError,
+};
+
+pub use executor_commands::{
+    execute_executor_command,
+    ExecuteExecutorCommandParams,
+    ExecuteExecutorCommandError,
+};
+
+pub use executor_runs::{
+    store_executor_run,
+    StoreExecutorRunParams,
+    StoreExecutorRunError,
+};
+
+pub use executor_runs::{
+    list_executor_runs,
+    ListExecutorRunsParams,
+    ListExecutorRunsError,
+};
+
+pub(crate) use executor_runs::remove_executor_runs_of_peer;
+
+pub use result_artifacts::{
+    store_result_artifact,
+    StoreResultArtifactParams,
+    StoreResultArtifactError,
+};
+
+pub use result_artifacts::{
+    list_result_artifacts,
+    ListResultArtifactsParams,
+    ListResultArtifactsError,
+};
+
 pub use peers::{
     store_peer_descriptor,
     StorePeerDescriptorParams,
@@ -25,6 +121,12 @@ pub use peers::{
     GeneratePeerSetupError,
 };
 
+pub use peers::{
+    redeem_enrollment_token,
+    RedeemEnrollmentTokenParams,
+    RedeemEnrollmentTokenError,
+};
+
 pub use peers::{
     generate_cleo_setup,
     GenerateCleoSetupParams,
@@ -43,6 +145,12 @@ pub use peers::{
     ListPeerDescriptorsError,
 };
 
+pub use peers::{
+    transfer_peer_ownership,
+    TransferPeerOwnershipParams,
+    TransferPeerOwnershipError,
+};
+
 pub use peers::{
     list_devices,
     ListDevicesParams,
@@ -55,5 +163,67 @@ pub use peers::{
     AssignClusterError,
 };
 
+pub use secrets::{
+    store_secret,
+    StoreSecretParams,
+    StoreSecretError,
+};
+
+pub use secrets::{
+    delete_secret,
+    DeleteSecretParams,
+    DeleteSecretError,
+};
+
+pub use secrets::{
+    list_secrets,
+    ListSecretsParams,
+    ListSecretsError,
+};
+
+pub use service_accounts::{
+    create_service_account,
+    CreateServiceAccountParams,
+    CreateServiceAccountError,
+};
+
+pub use service_accounts::{
+    delete_service_account,
+    DeleteServiceAccountParams,
+    DeleteServiceAccountError,
+};
+
+pub use service_accounts::{
+    list_service_accounts,
+    ListServiceAccountsParams,
+    ListServiceAccountsError,
+};
+
+pub use service_accounts::{
+    create_api_token,
+    CreateApiTokenParams,
+    CreateApiTokenError,
+};
+
+pub use service_accounts::{
+    revoke_api_token,
+    RevokeApiTokenParams,
+    RevokeApiTokenError,
+};
+
+pub use service_accounts::{
+    list_api_tokens,
+    ListApiTokensParams,
+    ListApiTokensError,
+};
+
+mod can_databases;
+mod can_fault_injection;
+mod client_credentials;
+mod executor_commands;
+mod executor_runs;
 mod peers;
+mod result_artifacts;
+mod secrets;
+mod service_accounts;
 mod clusters;
@@ -1,6 +1,8 @@
 use std::ops::Not;
 use std::sync::Arc;
+use std::time::SystemTime;
 
+use itertools::Itertools;
 use pem::Pem;
 use tracing::{debug, error, info, Span, warn};
 use url::Url;
@@ -13,34 +15,48 @@ pub use opendut_carl_api::carl::peer::{
     ListDevicesError,
     ListPeerDescriptorsError,
     StorePeerDescriptorError,
+    TransferPeerOwnershipError,
 };
-use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyPeerConfiguration, downstream};
+use opendut_types::can::CanDatabase;
 use opendut_types::cluster::ClusterAssignment;
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerName, PeerSetup};
 use opendut_types::{peer, proto};
 use opendut_types::cleo::{CleoId, CleoSetup};
 use opendut_types::peer::configuration::{PeerConfiguration, PeerNetworkConfiguration, PeerConfiguration2};
+use opendut_types::peer::enrollment::{EnrollmentResponse, EnrollmentToken, MutualTlsConfig, PeerEnrollment};
+use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorKind};
+use opendut_types::peer::executor::container::ContainerRegistryCredential;
 use opendut_types::proto::peer::configuration::{peer_configuration_parameter, PeerConfigurationParameterTargetPresent, PeerConfigurationParameterExecutor};
 use opendut_types::topology::{DeviceDescriptor, DeviceId};
-use opendut_types::util::net::{AuthConfig, Certificate, ClientCredentials, NetworkInterfaceName};
+use opendut_types::util::net::{AuthConfig, Certificate, ClientCredentials, ClientId, NetworkInterfaceName};
 use opendut_types::vpn::VpnPeerConfiguration;
 use opendut_util::ErrorOr;
+use crate::auth::client_certificate::{authenticated_peer_of, AuthenticatedPeer, MutualTlsEnrollment};
+use crate::auth::ownership::{Ownership, Requester};
+use crate::auth::peer_certificates::PeerCertificates;
+use crate::auth::peer_identity::PeerClient;
+use crate::auth::revocation::{RevokedClients, RevokedClientsError};
 use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerRef};
+use crate::peer::enrollment::{Enrollment, EnrollmentOptions, RedeemEnrollmentError};
 use crate::resources::IntoId;
 
 use crate::resources::manager::ResourcesManagerRef;
+use crate::secrets::EncryptedSecret;
 use crate::vpn::Vpn;
 
 pub struct StorePeerDescriptorParams {
     pub resources_manager: ResourcesManagerRef,
     pub vpn: Vpn,
     pub peer_descriptor: PeerDescriptor,
-    pub options: StorePeerDescriptorOptions
+    pub options: StorePeerDescriptorOptions,
+    /// Becomes the owner of newly created peers.
+    pub requester: Requester,
 }
 
 #[derive(Clone)]
 pub struct StorePeerDescriptorOptions {
-    pub bridge_name_default: NetworkInterfaceName
+    pub bridge_name_default: NetworkInterfaceName,
+    pub registry_credentials: Vec<ContainerRegistryCredential>,
 }
 
 #[tracing::instrument(skip(params), level="trace")]
@@ -55,6 +71,62 @@ pub async fn store_peer_descriptor(params: StorePeerDescriptorParams) -> Result<
 
         let is_new_peer = resources_manager.resources_mut(|resources| {
 
+            let can_databases = peer_descriptor.executors.executors.iter()
+                .flat_map(|executor| match &executor.kind {
+                    ExecutorKind::Executable { .. } => Vec::new(),
+                    ExecutorKind::Container { can_databases, .. } => Clone::clone(can_databases),
+                })
+                .unique()
+                .map(|database_id| resources.get::<CanDatabase>(database_id)
+                    .ok_or_else(|| StorePeerDescriptorError::CanDatabaseNotFound {
+                        peer_id,
+                        peer_name: Clone::clone(&peer_name),
+                        database_id,
+                    })
+                )
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let registry_credentials = peer_descriptor.executors.executors.iter()
+                .filter_map(|executor| match &executor.kind {
+                    ExecutorKind::Executable { .. } => None,
+                    ExecutorKind::Container { registry_credential, .. } => Clone::clone(registry_credential),
+                })
+                .unique()
+                .map(|credential_name| params.options.registry_credentials.iter()
+                    .find(|credential| credential.name == credential_name)
+                    .cloned()
+                    .ok_or_else(|| StorePeerDescriptorError::RegistryCredentialNotFound {
+                        peer_id,
+                        peer_name: Clone::clone(&peer_name),
+                        credential_name,
+                    })
+                )
+                .collect::<Result<Vec<_>, _>>()?;
+
+            let secret_names = peer_descriptor.executors.executors.iter()
+                .map(ExecutorDescriptor::secret_references)
+                .flatten_ok()
+                .collect::<Result<Vec<_>, _>>()
+                .map_err(|cause| StorePeerDescriptorError::IllegalSecretReference {
+                    peer_id,
+                    peer_name: Clone::clone(&peer_name),
+                    cause: cause.to_string(),
+                })?;
+
+            //secrets of other users and groups are reported as not found, as they are not visible to the requester
+            if let Some(secret_name) = secret_names.into_iter()
+                .unique()
+                .find(|secret_name| {
+                    resources.get::<EncryptedSecret>(Clone::clone(secret_name)).is_none()
+                        || params.requester.may_access(resources.get::<Ownership>(Clone::clone(secret_name)).as_ref()).not()
+                }) {
+                return Err(StorePeerDescriptorError::SecretNotFound {
+                    peer_id,
+                    peer_name: Clone::clone(&peer_name),
+                    secret_name,
+                });
+            }
+
             let old_peer_descriptor = resources.get::<PeerDescriptor>(peer_id);
             let is_new_peer = old_peer_descriptor.is_none();
 
@@ -108,14 +180,26 @@ pub async fn store_peer_descriptor(params: StorePeerDescriptorParams) -> Result<
                 for executor in Clone::clone(&peer_descriptor.executors).executors.into_iter() {
                     peer_configuration2.insert_executor(executor, peer::configuration::ParameterTarget::Present); //TODO not always Present
                 }
+                for can_database in can_databases {
+                    peer_configuration2.insert_can_database(can_database, peer::configuration::ParameterTarget::Present);
+                }
+                for registry_credential in registry_credentials {
+                    peer_configuration2.insert_registry_credential(registry_credential, peer::configuration::ParameterTarget::Present);
+                }
                 peer_configuration2
             };
             resources.insert(peer_id, peer_configuration2); //FIXME don't just insert, but rather update existing values via ID with intelligent logic (in a separate action)
 
             resources.insert(peer_id, peer_descriptor);
 
-            is_new_peer
-        }).await;
+            if is_new_peer {
+                if let Some(ownership) = params.requester.ownership() {
+                    resources.insert(peer_id, ownership);
+                }
+            }
+
+            Ok(is_new_peer)
+        }).await?;
 
         if is_new_peer {
             if let Vpn::Enabled { vpn_client } = params.vpn {
@@ -149,9 +233,12 @@ pub async fn store_peer_descriptor(params: StorePeerDescriptorParams) -> Result<
 
 pub struct DeletePeerDescriptorParams {
     pub resources_manager: ResourcesManagerRef,
+    pub peer_messaging_broker: PeerMessagingBrokerRef,
     pub vpn: Vpn,
     pub peer: PeerId,
     pub oidc_registration_client: Option<RegistrationClientRef>,
+    pub peer_certificates: PeerCertificates,
+    pub requester: Requester,
 }
 
 #[tracing::instrument(skip(params), level="trace")]
@@ -166,8 +253,14 @@ pub async fn delete_peer_descriptor(params: DeletePeerDescriptorParams) -> Resul
 
         let peer_descriptor = resources_manager.resources_mut(|resources| {
 
+            if params.requester.may_access(resources.get::<Ownership>(peer_id).as_ref()).not() {
+                return Err(DeletePeerDescriptorError::PeerNotFound { peer_id });
+            }
+
             let peer_descriptor = resources.remove::<PeerDescriptor>(peer_id)
                 .ok_or_else(|| DeletePeerDescriptorError::PeerNotFound { peer_id })?;
+            resources.remove::<Ownership>(peer_id);
+            resources.remove::<PeerClient>(peer_id);
 
             let peer_name = &peer_descriptor.name;
 
@@ -178,11 +271,20 @@ pub async fn delete_peer_descriptor(params: DeletePeerDescriptorParams) -> Resul
                 debug!("Deleted device '{device_name}' <{device_id}> of peer '{peer_name}' <{peer_id}>.");
             });
 
+            crate::actions::remove_executor_runs_of_peer(resources, peer_id);
+
             Ok(peer_descriptor)
         }).await?;
 
         let peer_name = &peer_descriptor.name;
 
+        params.peer_certificates.revoke(peer_id).await
+            .map_err(|cause| DeletePeerDescriptorError::Internal { peer_id, peer_name: Clone::clone(peer_name), cause: cause.to_string() })?;
+
+        if params.peer_messaging_broker.disconnect(peer_id).await.is_ok() {
+            debug!("Disconnected peer '{peer_name}' <{peer_id}>, as it was deleted.");
+        }
+
         if let Some(registration_client) = params.oidc_registration_client {
             let resource_id = peer_id.into();
             debug!("Deleting OIDC client for peer '{peer_name}' <{peer_id}>.");
@@ -218,6 +320,8 @@ pub async fn delete_peer_descriptor(params: DeletePeerDescriptorParams) -> Resul
 
 pub struct ListPeerDescriptorsParams {
     pub resources_manager: ResourcesManagerRef,
+    /// Only peers, which the requester may access, are listed.
+    pub requester: Requester,
 }
 
 #[tracing::instrument(skip(params), level="trace")]
@@ -231,6 +335,7 @@ pub async fn list_peer_descriptors(params: ListPeerDescriptorsParams) -> Result<
 
         let peers = resources_manager.resources(|resources| {
             resources.iter::<PeerDescriptor>()
+                .filter(|peer| params.requester.may_access(resources.get::<Ownership>(peer.id).as_ref()))
                 .cloned()
                 .collect::<Vec<PeerDescriptor>>()
         }).await;
@@ -246,6 +351,8 @@ pub async fn list_peer_descriptors(params: ListPeerDescriptorsParams) -> Result<
 
 pub struct ListDevicesParams {
     pub resources_manager: ResourcesManagerRef,
+    /// Only devices of peers, which the requester may access, are listed.
+    pub requester: Requester,
 }
 
 #[tracing::instrument(skip(params), level="trace")]
@@ -257,8 +364,12 @@ pub async fn list_devices(params: ListDevicesParams) -> Result<Vec<DeviceDescrip
 
         debug!("Querying all devices.");
 
-        let devices = resources_manager.resources(|resource| {
-            resource.iter::<DeviceDescriptor>().cloned().collect::<Vec<_>>()
+        let devices = resources_manager.resources(|resources| {
+            resources.iter::<PeerDescriptor>()
+                .filter(|peer| params.requester.may_access(resources.get::<Ownership>(peer.id).as_ref()))
+                .flat_map(|peer| peer.topology.devices.iter())
+                .filter_map(|device| resources.get::<DeviceDescriptor>(device.id))
+                .collect::<Vec<_>>()
         }).await;
 
         info!("Successfully queried all peers.");
@@ -270,6 +381,39 @@ pub async fn list_devices(params: ListDevicesParams) -> Result<Vec<DeviceDescrip
         .inspect_err(|err| error!("{err}"))
 }
 
+pub struct TransferPeerOwnershipParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_id: PeerId,
+    pub ownership: Ownership,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn transfer_peer_ownership(params: TransferPeerOwnershipParams) -> Result<PeerId, TransferPeerOwnershipError> {
+
+    async fn inner(params: TransferPeerOwnershipParams) -> Result<PeerId, TransferPeerOwnershipError> {
+
+        let peer_id = params.peer_id;
+        let ownership = params.ownership;
+
+        debug!("Transferring ownership of peer <{peer_id}> to {ownership:?}.");
+
+        params.resources_manager.resources_mut(|resources| {
+            if resources.get::<PeerDescriptor>(peer_id).is_none() {
+                return Err(TransferPeerOwnershipError::PeerNotFound { peer_id });
+            }
+            resources.insert(peer_id, Clone::clone(&ownership));
+            Ok(())
+        }).await?;
+
+        info!("Successfully transferred ownership of peer <{peer_id}> to {ownership:?}.");
+
+        Ok(peer_id)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
 pub struct GeneratePeerSetupParams {
     pub resources_manager: ResourcesManagerRef,
     pub peer: PeerId,
@@ -277,6 +421,7 @@ pub struct GeneratePeerSetupParams {
     pub ca: Pem,
     pub vpn: Vpn,
     pub oidc_registration_client: Option<RegistrationClientRef>,
+    pub enrollment_options: EnrollmentOptions,
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -317,35 +462,173 @@ pub async fn generate_peer_setup(params: GeneratePeerSetupParams, user_id: Strin
             VpnPeerConfiguration::Disabled
         };
 
-        let auth_config = match params.oidc_registration_client {
+        let enrollment = match (params.oidc_registration_client, params.enrollment_options.mutual_tls) {
+            (None, false) => {
+                PeerEnrollment::Disabled
+            }
+            (Some(_), _) | (None, true) => {
+                let enrollment = Enrollment::issue(user_id, params.enrollment_options.token_lifetime, SystemTime::now());
+                let token = Clone::clone(&enrollment.token);
+                params.resources_manager.resources_mut(|resources| {
+                    resources.insert(peer_id, enrollment);
+                }).await;
+                debug!("Issued enrollment token for peer '{peer_name}' <{peer_id}>, which is valid for {} seconds.", params.enrollment_options.token_lifetime.as_secs());
+                PeerEnrollment::Token(token)
+            }
+        };
+
+        Ok(PeerSetup {
+            id: peer_id,
+            carl: params.carl_url,
+            ca: Certificate(params.ca),
+            enrollment,
+            vpn: vpn_config,
+        })
+    }
+
+    inner(params, user_id).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct RedeemEnrollmentTokenParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_id: PeerId,
+    pub token: EnrollmentToken,
+    pub oidc_registration_client: Option<RegistrationClientRef>,
+    pub mutual_tls: Option<MutualTlsEnrollment>,
+    pub revoked_clients: RevokedClients,
+    pub peer_certificates: PeerCertificates,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RedeemEnrollmentTokenError {
+    #[error("Enrollment token for peer <{peer_id}> could not be redeemed:\n  {cause}")]
+    Rejected {
+        peer_id: PeerId,
+        cause: RedeemEnrollmentError,
+    },
+    #[error("An internal error occurred while redeeming the enrollment token for peer <{peer_id}>:\n  {cause}")]
+    Internal {
+        peer_id: PeerId,
+        cause: String,
+    }
+}
+
+/// Redeems the enrollment token of a peer's setup string once and issues the credentials, which the peer authenticates with.
+/// These are a client certificate, if mutual TLS is enabled, or otherwise an OIDC client.
+/// A new OIDC client replaces the one issued with a previous setup string, which is revoked.
+/// If issuing the credentials fails, the token remains redeemable.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn redeem_enrollment_token(params: RedeemEnrollmentTokenParams) -> Result<EnrollmentResponse, RedeemEnrollmentTokenError> {
+
+    async fn inner(params: RedeemEnrollmentTokenParams) -> Result<EnrollmentResponse, RedeemEnrollmentTokenError> {
+
+        let peer_id = params.peer_id;
+
+        debug!("Redeeming enrollment token for peer <{peer_id}>.");
+
+        let enrollment = params.resources_manager.resources_mut(|resources| {
+            let mut enrollment = resources.get::<Enrollment>(peer_id)
+                .ok_or(RedeemEnrollmentTokenError::Rejected { peer_id, cause: RedeemEnrollmentError::InvalidToken })?;
+            let result = enrollment.redeem(&params.token, SystemTime::now());
+            resources.insert(peer_id, Clone::clone(&enrollment));
+            result
+                .map(|_| enrollment)
+                .map_err(|cause| RedeemEnrollmentTokenError::Rejected { peer_id, cause })
+        }).await?;
+
+        let response = issue_credentials(peer_id, enrollment.user_id, params.mutual_tls, params.oidc_registration_client, &params.resources_manager, &params.revoked_clients, &params.peer_certificates).await;
+
+        if response.is_err() {
+            //The peer did not receive any credentials, so it may retry with the same setup string.
+            params.resources_manager.resources_mut(|resources| {
+                if let Some(mut enrollment) = resources.get::<Enrollment>(peer_id) {
+                    enrollment.revert_redemption(&params.token);
+                    resources.insert(peer_id, enrollment);
+                }
+            }).await;
+        }
+        response
+    }
+
+    async fn issue_credentials(
+        peer_id: PeerId,
+        user_id: String,
+        mutual_tls: Option<MutualTlsEnrollment>,
+        oidc_registration_client: Option<RegistrationClientRef>,
+        resources_manager: &ResourcesManagerRef,
+        revoked_clients: &RevokedClients,
+        peer_certificates: &PeerCertificates,
+    ) -> Result<EnrollmentResponse, RedeemEnrollmentTokenError> {
+
+        if let Some(MutualTlsEnrollment { authority, carl_port }) = mutual_tls {
+            let identity = authority.issue(peer_id, SystemTime::now())
+                .map_err(|cause| RedeemEnrollmentTokenError::Internal { peer_id, cause: cause.to_string() })?;
+            let AuthenticatedPeer { serial, .. } = authenticated_peer_of(identity.certificate.0.contents())
+                .ok_or_else(|| RedeemEnrollmentTokenError::Internal { peer_id, cause: String::from("Issued client certificate does not identify the peer.") })?;
+            peer_certificates.issue(peer_id, serial).await
+                .map_err(|cause| RedeemEnrollmentTokenError::Internal { peer_id, cause: cause.to_string() })?;
+
+            info!("Peer <{peer_id}> redeemed its enrollment token and received a client certificate.");
+
+            return Ok(EnrollmentResponse {
+                auth_config: AuthConfig::Disabled,
+                mutual_tls: Some(MutualTlsConfig { identity, carl_port }),
+            });
+        }
+
+        let auth_config = match oidc_registration_client {
             None => {
                 AuthConfig::Disabled
             }
             Some(registration_client) => {
-                let resource_id = peer_id.into();
-                debug!("Generating OIDC client for peer '{peer_name}' <{peer_id}>.");
+                debug!("Generating OIDC client for peer <{peer_id}>.");
                 let issuer_url = registration_client.config.issuer_remote_url.clone();
-                let client_credentials = registration_client.register_new_client_for_user(resource_id, user_id)
+                let client_credentials = registration_client.register_new_client_for_user(peer_id.into(), user_id)
                     .await
-                    .map_err(|cause| GeneratePeerSetupError::Internal { peer_id, peer_name: Clone::clone(&peer_name), cause: cause.to_string() })?;
-                debug!("Successfully generated peer setup for peer '{peer_name}' <{peer_id}>. OIDC client_id='{}'.", client_credentials.client_id.clone().value());
+                    .map_err(|cause| RedeemEnrollmentTokenError::Internal { peer_id, cause: cause.to_string() })?;
+                debug!("Successfully redeemed enrollment token for peer <{peer_id}>. OIDC client_id='{}'.", client_credentials.client_id.clone().value());
+                let previous_client = replace_peer_client(peer_id, Clone::clone(&client_credentials.client_id), resources_manager, revoked_clients).await
+                    .map_err(|cause| RedeemEnrollmentTokenError::Internal { peer_id, cause: cause.to_string() })?;
+                if let Some(previous_client_id) = previous_client.map(ClientId::value) {
+                    if let Err(cause) = registration_client.delete_client(&previous_client_id).await {
+                        warn!("Failed to delete the previous OIDC client '{previous_client_id}' of peer <{peer_id}>, which is revoked nonetheless: {cause}");
+                    }
+                }
                 AuthConfig::from_credentials(issuer_url, client_credentials)
             }
         };
 
-        Ok(PeerSetup {
-            id: peer_id,
-            carl: params.carl_url,
-            ca: Certificate(params.ca),
+        info!("Peer <{peer_id}> redeemed its enrollment token.");
+
+        Ok(EnrollmentResponse {
             auth_config,
-            vpn: vpn_config,
+            mutual_tls: None,
         })
     }
 
-    inner(params, user_id).await
+    inner(params).await
         .inspect_err(|err| error!("{err}"))
 }
 
+/// Records the OIDC client registered for the peer and revokes the client it replaces, which is returned.
+/// Fixed peer credentials are shared by all peers and are therefore never revoked.
+async fn replace_peer_client(peer_id: PeerId, client_id: ClientId, resources_manager: &ResourcesManagerRef, revoked_clients: &RevokedClients) -> Result<Option<ClientId>, RevokedClientsError> {
+    let previous_client = resources_manager.resources_mut(|resources| {
+        let previous_client = resources.get::<PeerClient>(peer_id);
+        resources.insert(peer_id, PeerClient { client_id: Clone::clone(&client_id) });
+        previous_client
+    }).await
+        .map(|previous_client| previous_client.client_id)
+        .filter(|previous_client_id| *previous_client_id != client_id);
+
+    if let Some(previous_client_id) = &previous_client {
+        revoked_clients.revoke(Clone::clone(previous_client_id)).await?;
+        info!("Revoked the previous OIDC client '{}' of peer <{peer_id}>.", previous_client_id.clone().value());
+    }
+    Ok(previous_client)
+}
+
 pub struct GenerateCleoSetupParams {
     pub cleo: CleoId,
     pub carl_url: Url,
@@ -435,12 +718,10 @@ pub async fn assign_cluster(params: AssignClusterParams) -> Result<(), AssignClu
         Ok((peer_configuration, peer_configuration2))
     }).await?;
 
-    params.peer_messaging_broker.send_to_peer(
+    params.peer_messaging_broker.apply_peer_configuration(
         peer_id,
-        downstream::Message::ApplyPeerConfiguration(ApplyPeerConfiguration {
-            configuration: Some(peer_configuration.into()),
-            configuration2: Some(peer_configuration2.into()),
-        }),
+        peer_configuration,
+        peer_configuration2,
     ).await
     .map_err(|cause| AssignClusterError::SendingToPeerFailed {
         peer_id,
@@ -458,18 +739,25 @@ mod test {
     use rstest::*;
 
     use opendut_types::peer::{PeerLocation, PeerName, PeerNetworkDescriptor};
-    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorDescriptors};
+    use opendut_types::peer::executor::container::{ContainerCommand, ContainerImage, ContainerImagePullPolicy, ContainerName, ContainerRegistryCredentialName, Engine};
     use opendut_types::topology::{DeviceDescription, DeviceName, Topology};
     use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceName};
 
+    use crate::peer::broker::PeerMessagingBrokerOptions;
     use crate::resources::manager::ResourcesManager;
+    use crate::secrets::SecretCipher;
 
     use super::*;
 
     mod store_peer_descriptor {
+        use opendut_types::peer::executor::executable::{ExecutableEnvironmentVariable, ExecutableName, ExecutableProgram};
+        use opendut_types::secret::{Secret, SecretName};
         use opendut_types::topology::{DeviceDescription, DeviceName};
         use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceName};
 
+        use crate::auth::permission::Role;
+
         use super::*;
 
         #[rstest]
@@ -483,6 +771,7 @@ mod test {
                 vpn: Clone::clone(&fixture.vpn),
                 peer_descriptor: Clone::clone(&fixture.peer_a_descriptor),
                 options: store_peer_descriptor_options.clone(),
+                requester: Requester::Unrestricted,
             }).await?;
 
             assert_that!(resources_manager.get::<PeerDescriptor>(fixture.peer_a_id).await.as_ref(), some(eq(&fixture.peer_a_descriptor)));
@@ -516,6 +805,7 @@ mod test {
                 vpn: Clone::clone(&fixture.vpn),
                 peer_descriptor: Clone::clone(&changed_descriptor),
                 options: store_peer_descriptor_options,
+                requester: Requester::Unrestricted,
             }).await?;
 
             assert_that!(resources_manager.get::<PeerDescriptor>(fixture.peer_a_id).await.as_ref(), some(eq(&changed_descriptor)));
@@ -525,14 +815,352 @@ mod test {
 
             Ok(())
         }
+
+        #[rstest]
+        #[tokio::test]
+        async fn should_pass_referenced_registry_credentials_to_the_peer(fixture: Fixture, store_peer_descriptor_options: StorePeerDescriptorOptions) -> anyhow::Result<()> {
+
+            let resources_manager = fixture.resources_manager;
+            let peer_descriptor = peer_descriptor_with_registry_credential(&fixture.peer_a_descriptor, "ci-registry")?;
+
+            store_peer_descriptor(StorePeerDescriptorParams {
+                resources_manager: Arc::clone(&resources_manager),
+                vpn: Clone::clone(&fixture.vpn),
+                peer_descriptor,
+                options: store_peer_descriptor_options.clone(),
+                requester: Requester::Unrestricted,
+            }).await?;
+
+            let peer_configuration2 = resources_manager.get::<PeerConfiguration2>(fixture.peer_a_id).await
+                .expect("PeerConfiguration2 should be stored");
+            let registry_credentials = peer_configuration2.registry_credentials.into_iter()
+                .map(|parameter| parameter.value)
+                .collect::<Vec<_>>();
+            assert_that!(registry_credentials, eq(store_peer_descriptor_options.registry_credentials));
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn should_reject_executors_referencing_unknown_registry_credentials(fixture: Fixture, store_peer_descriptor_options: StorePeerDescriptorOptions) -> anyhow::Result<()> {
+
+            let resources_manager = fixture.resources_manager;
+            let peer_descriptor = peer_descriptor_with_registry_credential(&fixture.peer_a_descriptor, "unknown-registry")?;
+
+            let result = store_peer_descriptor(StorePeerDescriptorParams {
+                resources_manager: Arc::clone(&resources_manager),
+                vpn: Clone::clone(&fixture.vpn),
+                peer_descriptor,
+                options: store_peer_descriptor_options,
+                requester: Requester::Unrestricted,
+            }).await;
+
+            assert!(matches!(result, Err(StorePeerDescriptorError::RegistryCredentialNotFound { ref credential_name, .. }) if credential_name.value() == "unknown-registry"));
+            assert_that!(resources_manager.get::<PeerDescriptor>(fixture.peer_a_id).await, none());
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn should_only_accept_executors_referencing_existing_secrets(fixture: Fixture, store_peer_descriptor_options: StorePeerDescriptorOptions) -> anyhow::Result<()> {
+
+            let resources_manager = fixture.resources_manager;
+            let secret = Secret {
+                name: SecretName::try_from("db-password")?,
+                value: String::from("hunter2"),
+            };
+            let peer_descriptor = PeerDescriptor {
+                executors: ExecutorDescriptors {
+                    executors: vec![
+                        ExecutorDescriptor {
+                            kind: ExecutorKind::Executable {
+                                name: ExecutableName::try_from("tester")?,
+                                program: ExecutableProgram::try_from("/usr/bin/tester")?,
+                                args: vec![],
+                                envs: vec![ExecutableEnvironmentVariable::new("DB_PASSWORD", secret.name.reference())?],
+                                working_directory: None,
+                                user: None,
+                            },
+                            results_url: None,
+                        },
+                    ],
+                },
+                ..Clone::clone(&fixture.peer_a_descriptor)
+            };
+
+            let result = store_peer_descriptor(StorePeerDescriptorParams {
+                resources_manager: Arc::clone(&resources_manager),
+                vpn: Clone::clone(&fixture.vpn),
+                peer_descriptor: Clone::clone(&peer_descriptor),
+                options: store_peer_descriptor_options.clone(),
+                requester: Requester::Unrestricted,
+            }).await;
+
+            assert!(matches!(result, Err(StorePeerDescriptorError::SecretNotFound { ref secret_name, .. }) if secret_name.value() == "db-password"));
+            assert_that!(resources_manager.get::<PeerDescriptor>(fixture.peer_a_id).await, none());
+
+            let secret_cipher = SecretCipher::generate()?;
+            resources_manager.insert(Clone::clone(&secret.name), secret_cipher.encrypt(&secret)?).await;
+
+            store_peer_descriptor(StorePeerDescriptorParams {
+                resources_manager: Arc::clone(&resources_manager),
+                vpn: Clone::clone(&fixture.vpn),
+                peer_descriptor,
+                options: store_peer_descriptor_options,
+                requester: Requester::Unrestricted,
+            }).await?;
+
+            let peer_configuration2 = resources_manager.get::<PeerConfiguration2>(fixture.peer_a_id).await
+                .expect("PeerConfiguration2 should be stored");
+            assert!(peer_configuration2.secrets.is_empty());
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn should_reject_executors_referencing_secrets_of_another_team(fixture: Fixture, store_peer_descriptor_options: StorePeerDescriptorOptions) -> anyhow::Result<()> {
+
+            let resources_manager = fixture.resources_manager;
+            let secret = Secret {
+                name: SecretName::try_from("db-password")?,
+                value: String::from("hunter2"),
+            };
+            let secret_cipher = SecretCipher::generate()?;
+            resources_manager.insert(Clone::clone(&secret.name), secret_cipher.encrypt(&secret)?).await;
+            resources_manager.insert(Clone::clone(&secret.name), Ownership { owner: String::from("bob"), group: Some(String::from("/team-b")) }).await;
+
+            let peer_descriptor = PeerDescriptor {
+                executors: ExecutorDescriptors {
+                    executors: vec![
+                        ExecutorDescriptor {
+                            kind: ExecutorKind::Executable {
+                                name: ExecutableName::try_from("tester")?,
+                                program: ExecutableProgram::try_from("/usr/bin/tester")?,
+                                args: vec![],
+                                envs: vec![ExecutableEnvironmentVariable::new("DB_PASSWORD", secret.name.reference())?],
+                                working_directory: None,
+                                user: None,
+                            },
+                            results_url: None,
+                        },
+                    ],
+                },
+                ..Clone::clone(&fixture.peer_a_descriptor)
+            };
+            let alice = Requester::User {
+                name: String::from("alice"),
+                groups: vec![String::from("/team-a")],
+                role: Some(Role::Operator),
+            };
+
+            let result = store_peer_descriptor(StorePeerDescriptorParams {
+                resources_manager: Arc::clone(&resources_manager),
+                vpn: Clone::clone(&fixture.vpn),
+                peer_descriptor: Clone::clone(&peer_descriptor),
+                options: store_peer_descriptor_options.clone(),
+                requester: Clone::clone(&alice),
+            }).await;
+
+            assert!(matches!(result, Err(StorePeerDescriptorError::SecretNotFound { ref secret_name, .. }) if secret_name.value() == "db-password"));
+            assert_that!(resources_manager.get::<PeerDescriptor>(fixture.peer_a_id).await, none());
+
+            resources_manager.insert(Clone::clone(&secret.name), Ownership { owner: String::from("carol"), group: Some(String::from("/team-a")) }).await;
+
+            store_peer_descriptor(StorePeerDescriptorParams {
+                resources_manager: Arc::clone(&resources_manager),
+                vpn: Clone::clone(&fixture.vpn),
+                peer_descriptor,
+                options: store_peer_descriptor_options,
+                requester: alice,
+            }).await?;
+
+            Ok(())
+        }
+
+        fn peer_descriptor_with_registry_credential(peer_descriptor: &PeerDescriptor, credential_name: &str) -> anyhow::Result<PeerDescriptor> {
+            Ok(PeerDescriptor {
+                executors: ExecutorDescriptors {
+                    executors: vec![
+                        ExecutorDescriptor {
+                            kind: ExecutorKind::Container {
+                                engine: Engine::Podman,
+                                name: ContainerName::Empty,
+                                image: ContainerImage::try_from("registry.example.com/testenv-nmap")?,
+                                pull_policy: ContainerImagePullPolicy::Always,
+                                digest: None,
+                                registry_credential: Some(ContainerRegistryCredentialName::try_from(credential_name)?),
+                                volumes: vec![],
+                                devices: vec![],
+                                envs: vec![],
+                                ports: vec![],
+                                command: ContainerCommand::Default,
+                                args: vec![],
+                                can_databases: vec![],
+                                cpus: None,
+                                memory: None,
+                                pids_limit: None,
+                                network_mode: Default::default(),
+                                cap_add: vec![],
+                                cap_drop: vec![],
+                                read_only_rootfs: false,
+                            },
+                            results_url: None,
+                        },
+                    ],
+                },
+                ..Clone::clone(peer_descriptor)
+            })
+        }
+    }
+
+    mod ownership {
+        use crate::auth::permission::Role;
+
+        use super::*;
+
+        fn user(name: &str, groups: &[&str]) -> Requester {
+            Requester::User {
+                name: name.to_string(),
+                groups: groups.iter().map(|group| group.to_string()).collect(),
+                role: Some(Role::Operator),
+            }
+        }
+
+        async fn list(resources_manager: &ResourcesManagerRef, requester: Requester) -> anyhow::Result<Vec<PeerId>> {
+            let peers = list_peer_descriptors(ListPeerDescriptorsParams {
+                resources_manager: Arc::clone(resources_manager),
+                requester,
+            }).await?;
+            Ok(peers.into_iter().map(|peer| peer.id).collect())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn should_only_list_and_delete_peers_of_the_own_team(fixture: Fixture, store_peer_descriptor_options: StorePeerDescriptorOptions) -> anyhow::Result<()> {
+
+            let resources_manager = fixture.resources_manager;
+
+            store_peer_descriptor(StorePeerDescriptorParams {
+                resources_manager: Arc::clone(&resources_manager),
+                vpn: Clone::clone(&fixture.vpn),
+                peer_descriptor: Clone::clone(&fixture.peer_a_descriptor),
+                options: store_peer_descriptor_options,
+                requester: user("alice", &["/team-a"]),
+            }).await?;
+
+            assert_that!(list(&resources_manager, user("carol", &["/team-a"])).await?, elements_are![eq(fixture.peer_a_id)]);
+            assert!(list(&resources_manager, user("bob", &["/team-b"])).await?.is_empty());
+            assert_that!(list(&resources_manager, Requester::Unrestricted).await?, elements_are![eq(fixture.peer_a_id)]);
+
+            let devices = list_devices(ListDevicesParams {
+                resources_manager: Arc::clone(&resources_manager),
+                requester: user("bob", &["/team-b"]),
+            }).await?;
+            assert!(devices.is_empty());
+
+            let result = delete_peer_descriptor(DeletePeerDescriptorParams {
+                resources_manager: Arc::clone(&resources_manager),
+                peer_messaging_broker: peer_messaging_broker(&resources_manager)?,
+                vpn: Clone::clone(&fixture.vpn),
+                peer: fixture.peer_a_id,
+                oidc_registration_client: None,
+                peer_certificates: PeerCertificates::default(),
+                requester: user("bob", &["/team-b"]),
+            }).await;
+            assert!(matches!(result, Err(DeletePeerDescriptorError::PeerNotFound { peer_id }) if peer_id == fixture.peer_a_id));
+            assert_that!(resources_manager.get::<PeerDescriptor>(fixture.peer_a_id).await, some(anything()));
+
+            Ok(())
+        }
+
+        #[rstest]
+        #[tokio::test]
+        async fn should_transfer_ownership(fixture: Fixture, store_peer_descriptor_options: StorePeerDescriptorOptions) -> anyhow::Result<()> {
+
+            let resources_manager = fixture.resources_manager;
+
+            store_peer_descriptor(StorePeerDescriptorParams {
+                resources_manager: Arc::clone(&resources_manager),
+                vpn: Clone::clone(&fixture.vpn),
+                peer_descriptor: Clone::clone(&fixture.peer_a_descriptor),
+                options: store_peer_descriptor_options,
+                requester: user("alice", &["/team-a"]),
+            }).await?;
+
+            transfer_peer_ownership(TransferPeerOwnershipParams {
+                resources_manager: Arc::clone(&resources_manager),
+                peer_id: fixture.peer_a_id,
+                ownership: Ownership { owner: String::from("bob"), group: Some(String::from("/team-b")) },
+            }).await?;
+
+            assert!(list(&resources_manager, user("alice", &["/team-a"])).await?.is_empty());
+            assert_that!(list(&resources_manager, user("dave", &["/team-b"])).await?, elements_are![eq(fixture.peer_a_id)]);
+
+            let result = transfer_peer_ownership(TransferPeerOwnershipParams {
+                resources_manager: Arc::clone(&resources_manager),
+                peer_id: PeerId::random(),
+                ownership: Ownership { owner: String::from("bob"), group: None },
+            }).await;
+            assert!(matches!(result, Err(TransferPeerOwnershipError::PeerNotFound { .. })));
+
+            Ok(())
+        }
+    }
+
+    mod delete_peer_descriptor {
+        use std::net::IpAddr;
+        use std::str::FromStr;
+
+        use crate::auth::client_certificate::CertificateSerial;
+
+        use super::*;
+
+        #[rstest]
+        #[tokio::test]
+        async fn should_disconnect_deleted_peers_and_reject_their_client_certificate(fixture: Fixture, store_peer_descriptor_options: StorePeerDescriptorOptions) -> anyhow::Result<()> {
+
+            let resources_manager = fixture.resources_manager;
+            let peer_messaging_broker = peer_messaging_broker(&resources_manager)?;
+            let peer_id = fixture.peer_a_id;
+
+            store_peer_descriptor(StorePeerDescriptorParams {
+                resources_manager: Arc::clone(&resources_manager),
+                vpn: Clone::clone(&fixture.vpn),
+                peer_descriptor: Clone::clone(&fixture.peer_a_descriptor),
+                options: store_peer_descriptor_options,
+                requester: Requester::Unrestricted,
+            }).await?;
+            let peer_certificates = PeerCertificates::default();
+            peer_certificates.issue(peer_id, CertificateSerial(String::from("01"))).await?;
+
+            let (_, mut receiver) = peer_messaging_broker.open(peer_id, IpAddr::from_str("1.2.3.4")?).await?;
+
+            delete_peer_descriptor(DeletePeerDescriptorParams {
+                resources_manager: Arc::clone(&resources_manager),
+                peer_messaging_broker: Arc::clone(&peer_messaging_broker),
+                vpn: Clone::clone(&fixture.vpn),
+                peer: peer_id,
+                oidc_registration_client: None,
+                peer_certificates: Clone::clone(&peer_certificates),
+                requester: Requester::Unrestricted,
+            }).await?;
+
+            assert!(peer_certificates.is_revoked(&CertificateSerial(String::from("01"))));
+            while receiver.recv().await.is_some() {} //drain messages sent before the disconnect, the stream ends afterwards
+            assert!(peer_messaging_broker.list_peers(Requester::Unrestricted).await.is_empty());
+
+            Ok(())
+        }
     }
 
     mod assign_cluster {
         use std::net::IpAddr;
         use std::str::FromStr;
-        use opendut_carl_api::proto::services::peer_messaging_broker::Pong;
+        use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyPeerConfiguration, downstream, Pong};
         use opendut_types::cluster::{ClusterAssignment, ClusterId};
-        use crate::peer::broker::PeerMessagingBrokerOptions;
         use super::*;
 
         #[rstest]
@@ -545,6 +1173,7 @@ mod test {
             let resources_manager = fixture.resources_manager;
             let peer_messaging_broker = PeerMessagingBroker::new(
                 Arc::clone(&resources_manager),
+                Arc::new(SecretCipher::generate().unwrap()),
                 PeerMessagingBrokerOptions::load(&settings.config).unwrap(),
             );
 
@@ -559,6 +1188,9 @@ mod test {
             }).await;
             let peer_configuration2 = PeerConfiguration2 {
                 executors: vec![],
+                can_databases: vec![],
+                registry_credentials: vec![],
+                secrets: vec![],
             };
             resources_manager.resources_mut(|resources| {
                 resources.insert(peer_id, Clone::clone(&peer_configuration2));
@@ -580,6 +1212,7 @@ mod test {
                 id: ClusterId::random(),
                 leader: PeerId::random(),
                 assignments: vec![],
+                can_capture: None,
             };
 
 
@@ -617,6 +1250,65 @@ mod test {
         }
     }
 
+    mod redeem_enrollment_token {
+        use std::time::Duration;
+
+        use super::*;
+
+        #[tokio::test]
+        async fn should_reject_a_foreign_token() -> anyhow::Result<()> {
+            let resources_manager = ResourcesManager::new();
+            let peer_id = PeerId::random();
+            resources_manager.insert(peer_id, Enrollment::issue(String::from("alice"), Duration::from_secs(900), SystemTime::now())).await;
+
+            let result = redeem_enrollment_token(RedeemEnrollmentTokenParams {
+                resources_manager: Arc::clone(&resources_manager),
+                peer_id,
+                token: EnrollmentToken::random(),
+                oidc_registration_client: None,
+                mutual_tls: None,
+                revoked_clients: RevokedClients::default(),
+                peer_certificates: PeerCertificates::default(),
+            }).await;
+
+            assert!(matches!(result, Err(RedeemEnrollmentTokenError::Rejected { cause: RedeemEnrollmentError::InvalidToken, .. })));
+            Ok(())
+        }
+
+        #[tokio::test]
+        async fn should_revoke_the_client_replaced_by_a_new_enrollment() -> anyhow::Result<()> {
+            let resources_manager = ResourcesManager::new();
+            let revoked_clients = RevokedClients::default();
+            let peer_id = PeerId::random();
+
+            let previous_client = replace_peer_client(peer_id, ClientId::from("edgar-first"), &resources_manager, &revoked_clients).await?;
+            assert_that!(previous_client, none());
+
+            let previous_client = replace_peer_client(peer_id, ClientId::from("edgar-second"), &resources_manager, &revoked_clients).await?;
+            assert_that!(previous_client, some(eq(ClientId::from("edgar-first"))));
+            assert!(revoked_clients.is_revoked("edgar-first"));
+            assert!(revoked_clients.is_revoked("edgar-second").not());
+            assert_that!(resources_manager.get::<PeerClient>(peer_id).await, some(eq(PeerClient { client_id: ClientId::from("edgar-second") })));
+
+            Ok(())
+        }
+
+        #[tokio::test]
+        async fn should_not_revoke_fixed_peer_credentials_issued_again() -> anyhow::Result<()> {
+            let resources_manager = ResourcesManager::new();
+            let revoked_clients = RevokedClients::default();
+            let peer_id = PeerId::random();
+
+            replace_peer_client(peer_id, ClientId::from("opendut-edgar-client"), &resources_manager, &revoked_clients).await?;
+            let previous_client = replace_peer_client(peer_id, ClientId::from("opendut-edgar-client"), &resources_manager, &revoked_clients).await?;
+
+            assert_that!(previous_client, none());
+            assert!(revoked_clients.is_revoked("opendut-edgar-client").not());
+
+            Ok(())
+        }
+    }
+
     mod create_setup_string {
         use std::str::FromStr;
         use opendut_auth_tests::registration_client;
@@ -650,6 +1342,15 @@ mod test {
         const CERTIFICATE_AUTHORITY_STRING: &str = include_str!("../../../resources/development/tls/insecure-development-ca.pem");
     }
 
+    fn peer_messaging_broker(resources_manager: &ResourcesManagerRef) -> anyhow::Result<PeerMessagingBrokerRef> {
+        let settings = crate::settings::load_defaults()?;
+        Ok(PeerMessagingBroker::new(
+            Arc::clone(resources_manager),
+            Arc::new(SecretCipher::generate()?),
+            PeerMessagingBrokerOptions::load(&settings.config)?,
+        ))
+    }
+
     struct Fixture {
         resources_manager: ResourcesManagerRef,
         vpn: Vpn,
@@ -719,6 +1420,14 @@ mod test {
     fn store_peer_descriptor_options() -> StorePeerDescriptorOptions {
         StorePeerDescriptorOptions {
             bridge_name_default: NetworkInterfaceName::try_from("br-opendut").unwrap(),
+            registry_credentials: vec![
+                ContainerRegistryCredential {
+                    name: ContainerRegistryCredentialName::try_from("ci-registry").unwrap(),
+                    server: String::from("registry.example.com"),
+                    username: String::from("ci"),
+                    password: String::from("secret"),
+                },
+            ],
         }
     }
 }
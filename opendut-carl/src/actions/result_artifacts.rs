@@ -0,0 +1,342 @@
+use tracing::{debug, error, info};
+
+pub use opendut_carl_api::carl::peer::ListResultArtifactsError;
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::peer::configuration::PeerConfiguration;
+use opendut_types::peer::executor::run::{ExecutorRunId, ResultArtifact, ResultArtifactId};
+
+use crate::auth::ownership::{Ownership, Requester};
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::Resources;
+
+/// Number of result artifacts, which are kept per peer. Older artifacts are discarded from the catalogue,
+/// but the uploaded files themselves are not touched.
+const MAX_RESULT_ARTIFACTS_PER_PEER: usize = 1000;
+
+pub struct StoreResultArtifactParams {
+    pub resources_manager: ResourcesManagerRef,
+    /// ID of the peer, which reported the artifact.
+    pub peer_id: PeerId,
+    pub artifact: ResultArtifact,
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum StoreResultArtifactError {
+    #[error("Result artifact <{artifact_id}> reported by peer <{reporting_peer_id}> belongs to another peer <{peer_id}>!")]
+    PeerMismatch {
+        artifact_id: ResultArtifactId,
+        reporting_peer_id: PeerId,
+        peer_id: PeerId,
+    },
+    #[error("Result artifact <{artifact_id}> could not be stored, because peer <{peer_id}> does not exist!")]
+    PeerNotFound {
+        artifact_id: ResultArtifactId,
+        peer_id: PeerId,
+    },
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn store_result_artifact(params: StoreResultArtifactParams) -> Result<ResultArtifactId, StoreResultArtifactError> {
+
+    async fn inner(params: StoreResultArtifactParams) -> Result<ResultArtifactId, StoreResultArtifactError> {
+
+        let mut artifact = params.artifact;
+        let artifact_id = artifact.id;
+        let peer_id = artifact.peer_id;
+
+        if peer_id != params.peer_id {
+            return Err(StoreResultArtifactError::PeerMismatch { artifact_id, reporting_peer_id: params.peer_id, peer_id });
+        }
+
+        debug!("Storing result artifact <{artifact_id}> of executor run <{}> on peer <{peer_id}> located at '{}'.", artifact.run_id, artifact.location);
+
+        params.resources_manager.resources_mut(|resources| {
+            if resources.get::<PeerDescriptor>(peer_id).is_none() {
+                return Err(StoreResultArtifactError::PeerNotFound { artifact_id, peer_id });
+            }
+
+            artifact.cluster_id = resources.get::<PeerConfiguration>(peer_id)
+                .and_then(|configuration| configuration.cluster_assignment)
+                .map(|assignment| assignment.id);
+
+            resources.insert(artifact_id, artifact);
+            discard_outdated_result_artifacts(resources, peer_id);
+            Ok(())
+        }).await?;
+
+        info!("Successfully stored result artifact <{artifact_id}> of peer <{peer_id}>.");
+
+        Ok(artifact_id)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct ListResultArtifactsParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub peer_id: Option<PeerId>,
+    pub cluster_id: Option<ClusterId>,
+    pub run_id: Option<ExecutorRunId>,
+    /// Only artifacts of peers, which the requester may access, are listed.
+    pub requester: Requester,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_result_artifacts(params: ListResultArtifactsParams) -> Result<Vec<ResultArtifact>, ListResultArtifactsError> {
+
+    async fn inner(params: ListResultArtifactsParams) -> Result<Vec<ResultArtifact>, ListResultArtifactsError> {
+
+        let resources_manager = params.resources_manager;
+
+        debug!("Querying result artifacts.");
+
+        let mut artifacts = resources_manager.resources(|resources| {
+            resources.iter::<ResultArtifact>()
+                .filter(|artifact| match params.peer_id {
+                    Some(peer_id) => artifact.peer_id == peer_id,
+                    None => true,
+                })
+                .filter(|artifact| match params.cluster_id {
+                    Some(cluster_id) => artifact.cluster_id == Some(cluster_id),
+                    None => true,
+                })
+                .filter(|artifact| match params.run_id {
+                    Some(run_id) => artifact.run_id == run_id,
+                    None => true,
+                })
+                .filter(|artifact| params.requester.may_access(resources.get::<Ownership>(artifact.peer_id).as_ref()))
+                .cloned()
+                .collect::<Vec<_>>()
+        }).await;
+
+        artifacts.sort_by_key(|artifact| artifact.uploaded_at);
+
+        info!("Successfully queried result artifacts.");
+
+        Ok(artifacts)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+fn discard_outdated_result_artifacts(resources: &mut Resources, peer_id: PeerId) {
+    let mut artifacts = resources.iter::<ResultArtifact>()
+        .filter(|artifact| artifact.peer_id == peer_id)
+        .map(|artifact| (artifact.uploaded_at, artifact.id))
+        .collect::<Vec<_>>();
+
+    if artifacts.len() <= MAX_RESULT_ARTIFACTS_PER_PEER {
+        return;
+    }
+
+    artifacts.sort_by_key(|(uploaded_at, _)| *uploaded_at);
+    let outdated = artifacts.len() - MAX_RESULT_ARTIFACTS_PER_PEER;
+    for (_, artifact_id) in artifacts.into_iter().take(outdated) {
+        resources.remove::<ResultArtifact>(artifact_id);
+    }
+}
+
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use googletest::prelude::*;
+    use url::Url;
+    use uuid::Uuid;
+
+    use opendut_types::cluster::ClusterAssignment;
+    use opendut_types::peer::{PeerLocation, PeerName, PeerNetworkDescriptor};
+    use opendut_types::peer::configuration::{ParameterId, PeerNetworkConfiguration};
+    use opendut_types::peer::executor::ExecutorDescriptors;
+    use opendut_types::topology::Topology;
+    use opendut_types::util::net::NetworkInterfaceName;
+
+    use crate::auth::permission::Role;
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    fn peer_descriptor(peer_id: PeerId) -> PeerDescriptor {
+        PeerDescriptor {
+            id: peer_id,
+            name: PeerName::try_from("PeerA").unwrap(),
+            location: PeerLocation::try_from("Ulm").ok(),
+            network: PeerNetworkDescriptor {
+                interfaces: vec![],
+                bridge_name: None,
+            },
+            topology: Topology::default(),
+            executors: ExecutorDescriptors { executors: vec![] },
+        }
+    }
+
+    fn result_artifact(peer_id: PeerId, run_id: ExecutorRunId, uploaded_at_secs: u64) -> ResultArtifact {
+        ResultArtifact {
+            id: ResultArtifactId::random(),
+            peer_id,
+            cluster_id: None,
+            run_id,
+            executor_id: ParameterId(Uuid::new_v4()),
+            executor_name: String::from("nmap-test"),
+            uploaded_at: UNIX_EPOCH + Duration::from_secs(uploaded_at_secs),
+            size: 42,
+            location: Url::parse("http://nginx-webdav/nmap-test.zip").unwrap(),
+            checksum: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_store_result_artifacts_with_the_cluster_of_the_peer() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let peer_a = PeerId::random();
+        let peer_b = PeerId::random();
+        let cluster_id = ClusterId::random();
+
+        resources_manager.resources_mut(|resources| {
+            resources.insert(peer_a, peer_descriptor(peer_a));
+            resources.insert(peer_b, peer_descriptor(peer_b));
+            resources.insert(peer_a, PeerConfiguration {
+                cluster_assignment: Some(ClusterAssignment {
+                    id: cluster_id,
+                    leader: peer_a,
+                    assignments: vec![],
+                    can_capture: None,
+                }),
+                network: PeerNetworkConfiguration {
+                    bridge_name: NetworkInterfaceName::try_from("br-opendut-1").unwrap(),
+                },
+            });
+        }).await;
+
+        let run_id = ExecutorRunId::random();
+        let artifact_a = result_artifact(peer_a, run_id, 2);
+        let artifact_b = result_artifact(peer_b, ExecutorRunId::random(), 1);
+
+        for (peer_id, artifact) in [(peer_a, Clone::clone(&artifact_a)), (peer_b, Clone::clone(&artifact_b))] {
+            store_result_artifact(StoreResultArtifactParams {
+                resources_manager: Arc::clone(&resources_manager),
+                peer_id,
+                artifact,
+            }).await?;
+        }
+
+        let artifacts = list_result_artifacts(ListResultArtifactsParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_id: None,
+            cluster_id: Some(cluster_id),
+            run_id: None,
+            requester: Requester::Unrestricted,
+        }).await?;
+        assert_that!(artifacts, eq(vec![ResultArtifact { cluster_id: Some(cluster_id), ..artifact_a }]));
+
+        let artifacts = list_result_artifacts(ListResultArtifactsParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_id: Some(peer_b),
+            cluster_id: None,
+            run_id: None,
+            requester: Requester::Unrestricted,
+        }).await?;
+        assert_that!(artifacts, eq(vec![artifact_b]));
+
+        let artifacts = list_result_artifacts(ListResultArtifactsParams {
+            resources_manager,
+            peer_id: None,
+            cluster_id: None,
+            run_id: None,
+            requester: Requester::Unrestricted,
+        }).await?;
+        assert_that!(artifacts.len(), eq(2));
+        assert_that!(artifacts[1].run_id, eq(run_id));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_only_list_result_artifacts_of_peers_of_the_own_team() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let peer_a = PeerId::random();
+        let peer_b = PeerId::random();
+
+        resources_manager.resources_mut(|resources| {
+            resources.insert(peer_a, peer_descriptor(peer_a));
+            resources.insert(peer_a, Ownership { owner: String::from("alice"), group: Some(String::from("/team-a")) });
+            resources.insert(peer_b, peer_descriptor(peer_b));
+            resources.insert(peer_b, Ownership { owner: String::from("bob"), group: Some(String::from("/team-b")) });
+        }).await;
+
+        let artifact_a = result_artifact(peer_a, ExecutorRunId::random(), 1);
+        let artifact_b = result_artifact(peer_b, ExecutorRunId::random(), 2);
+        for (peer_id, artifact) in [(peer_a, Clone::clone(&artifact_a)), (peer_b, Clone::clone(&artifact_b))] {
+            store_result_artifact(StoreResultArtifactParams {
+                resources_manager: Arc::clone(&resources_manager),
+                peer_id,
+                artifact,
+            }).await?;
+        }
+
+        let carol = Requester::User { name: String::from("carol"), groups: vec![String::from("/team-a")], role: Some(Role::Viewer) };
+
+        let artifacts = list_result_artifacts(ListResultArtifactsParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_id: None,
+            cluster_id: None,
+            run_id: None,
+            requester: Clone::clone(&carol),
+        }).await?;
+        assert_that!(artifacts, elements_are![eq(artifact_a)]);
+
+        let artifacts = list_result_artifacts(ListResultArtifactsParams {
+            resources_manager,
+            peer_id: None,
+            cluster_id: None,
+            run_id: Some(artifact_b.run_id),
+            requester: carol,
+        }).await?;
+        assert!(artifacts.is_empty());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_result_artifacts_of_other_or_unknown_peers() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let peer_a = PeerId::random();
+        let peer_b = PeerId::random();
+
+        resources_manager.resources_mut(|resources| {
+            resources.insert(peer_a, peer_descriptor(peer_a));
+            resources.insert(peer_b, peer_descriptor(peer_b));
+        }).await;
+
+        let result = store_result_artifact(StoreResultArtifactParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_id: peer_a,
+            artifact: result_artifact(peer_b, ExecutorRunId::random(), 1),
+        }).await;
+        assert!(matches!(result, Err(StoreResultArtifactError::PeerMismatch { .. })));
+
+        let unknown_peer = PeerId::random();
+        let result = store_result_artifact(StoreResultArtifactParams {
+            resources_manager: Arc::clone(&resources_manager),
+            peer_id: unknown_peer,
+            artifact: result_artifact(unknown_peer, ExecutorRunId::random(), 1),
+        }).await;
+        assert!(matches!(result, Err(StoreResultArtifactError::PeerNotFound { .. })));
+
+        let artifacts = list_result_artifacts(ListResultArtifactsParams {
+            resources_manager,
+            peer_id: None,
+            cluster_id: None,
+            run_id: None,
+            requester: Requester::Unrestricted,
+        }).await?;
+        assert!(artifacts.is_empty());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,374 @@
+use std::ops::Not;
+
+use tracing::{debug, error, info};
+
+pub use opendut_carl_api::carl::secret::{
+    DeleteSecretError,
+    ListSecretsError,
+    StoreSecretError,
+};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::secret::{Secret, SecretName};
+
+use crate::auth::ownership::{Ownership, Requester};
+use crate::resources::manager::ResourcesManagerRef;
+use crate::secrets::{EncryptedSecret, SecretCipherRef, SecretStore};
+
+pub struct StoreSecretParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub secret_cipher: SecretCipherRef,
+    pub secret_store: SecretStore,
+    pub secret: Secret,
+    /// Owner of the secret, whose peers may reference it. If `None`, a new secret belongs to the requester and an existing one keeps its owner.
+    pub ownership: Option<Ownership>,
+    pub requester: Requester,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn store_secret(params: StoreSecretParams) -> Result<SecretName, StoreSecretError> {
+
+    async fn inner(params: StoreSecretParams) -> Result<SecretName, StoreSecretError> {
+
+        let secret_name = Clone::clone(&params.secret.name);
+        let resources_manager = params.resources_manager;
+
+        debug!("Storing secret '{secret_name}'.");
+
+        let encrypted_secret = params.secret_cipher.encrypt(&params.secret)
+            .map_err(|cause| StoreSecretError::Internal {
+                secret_name: Clone::clone(&secret_name),
+                cause: cause.to_string(),
+            })?;
+
+        resources_manager.resources_mut(|resources| {
+            let previous_secret = resources.get::<EncryptedSecret>(Clone::clone(&secret_name));
+            let previous_ownership = resources.get::<Ownership>(Clone::clone(&secret_name));
+            resources.insert(Clone::clone(&secret_name), encrypted_secret);
+
+            let ownership = match params.ownership {
+                Some(ownership) => Some(ownership),
+                None if previous_secret.is_none() => params.requester.ownership(),
+                None => None,
+            };
+            if let Some(ownership) = ownership {
+                resources.insert(Clone::clone(&secret_name), ownership);
+            }
+
+            params.secret_store.save(resources)
+                .map_err(|cause| {
+                    //keep the secrets in memory consistent with the stored ones
+                    match previous_secret {
+                        Some(previous_secret) => { resources.insert(Clone::clone(&secret_name), previous_secret); }
+                        None => { resources.remove::<EncryptedSecret>(Clone::clone(&secret_name)); }
+                    }
+                    match previous_ownership {
+                        Some(previous_ownership) => { resources.insert(Clone::clone(&secret_name), previous_ownership); }
+                        None => { resources.remove::<Ownership>(Clone::clone(&secret_name)); }
+                    }
+                    StoreSecretError::Internal { secret_name: Clone::clone(&secret_name), cause: cause.to_string() }
+                })
+        }).await?;
+
+        info!("Successfully stored secret '{secret_name}'.");
+
+        Ok(secret_name)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct DeleteSecretParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub secret_store: SecretStore,
+    pub secret_name: SecretName,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn delete_secret(params: DeleteSecretParams) -> Result<SecretName, DeleteSecretError> {
+
+    async fn inner(params: DeleteSecretParams) -> Result<SecretName, DeleteSecretError> {
+
+        let secret_name = params.secret_name;
+        let resources_manager = params.resources_manager;
+
+        debug!("Deleting secret '{secret_name}'.");
+
+        resources_manager.resources_mut(|resources| {
+
+            let referencing_peers = resources.iter::<PeerDescriptor>()
+                .filter(|peer| references_secret(peer, &secret_name))
+                .map(|peer| peer.id)
+                .collect::<Vec<PeerId>>();
+
+            if referencing_peers.is_empty().not() {
+                return Err(DeleteSecretError::SecretInUse {
+                    secret_name: Clone::clone(&secret_name),
+                    peer_ids: referencing_peers,
+                });
+            }
+
+            let encrypted_secret = resources.remove::<EncryptedSecret>(Clone::clone(&secret_name))
+                .ok_or_else(|| DeleteSecretError::SecretNotFound { secret_name: Clone::clone(&secret_name) })?;
+            let ownership = resources.remove::<Ownership>(Clone::clone(&secret_name));
+
+            if let Err(cause) = params.secret_store.save(resources) {
+                //keep the secrets in memory consistent with the stored ones
+                resources.insert(Clone::clone(&secret_name), encrypted_secret);
+                if let Some(ownership) = ownership {
+                    resources.insert(Clone::clone(&secret_name), ownership);
+                }
+                return Err(DeleteSecretError::Internal { secret_name: Clone::clone(&secret_name), cause: cause.to_string() });
+            }
+            Ok(())
+        }).await?;
+
+        info!("Successfully deleted secret '{secret_name}'.");
+
+        Ok(secret_name)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct ListSecretsParams {
+    pub resources_manager: ResourcesManagerRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_secrets(params: ListSecretsParams) -> Result<Vec<SecretName>, ListSecretsError> {
+
+    async fn inner(params: ListSecretsParams) -> Result<Vec<SecretName>, ListSecretsError> {
+
+        let resources_manager = params.resources_manager;
+
+        debug!("Querying all secrets.");
+
+        let mut secret_names = resources_manager.resources(|resources| {
+            resources.iter::<EncryptedSecret>()
+                .map(|secret| Clone::clone(&secret.name))
+                .collect::<Vec<_>>()
+        }).await;
+        secret_names.sort();
+
+        info!("Successfully queried all secrets.");
+
+        Ok(secret_names)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+fn references_secret(peer: &PeerDescriptor, secret_name: &SecretName) -> bool {
+    peer.executors.executors.iter()
+        .any(|executor| executor.secret_references()
+            .map(|names| names.contains(secret_name))
+            .unwrap_or(false)
+        )
+}
+
+#[cfg(test)]
+mod test {
+    use std::fs;
+    use std::sync::Arc;
+
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+
+    use opendut_types::peer::{PeerLocation, PeerName, PeerNetworkDescriptor};
+    use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorDescriptors, ExecutorKind};
+    use opendut_types::peer::executor::executable::{ExecutableEnvironmentVariable, ExecutableName, ExecutableProgram};
+    use opendut_types::topology::Topology;
+
+    use crate::auth::permission::Role;
+    use crate::resources::manager::ResourcesManager;
+    use crate::secrets::SecretCipher;
+
+    use super::*;
+
+    fn secret(name: &str, value: &str) -> anyhow::Result<Secret> {
+        Ok(Secret {
+            name: SecretName::try_from(name)?,
+            value: value.to_owned(),
+        })
+    }
+
+    #[tokio::test]
+    async fn should_store_secrets_encrypted_and_list_only_their_names() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let secret_cipher = Arc::new(SecretCipher::generate()?);
+        let secret = secret("db-password", "hunter2")?;
+
+        let secret_name = store_secret(StoreSecretParams {
+            resources_manager: Arc::clone(&resources_manager),
+            secret_cipher: Arc::clone(&secret_cipher),
+            secret_store: SecretStore::default(),
+            secret: Clone::clone(&secret),
+            ownership: None,
+            requester: Requester::Unrestricted,
+        }).await?;
+
+        let encrypted = resources_manager.get::<EncryptedSecret>(Clone::clone(&secret_name)).await
+            .expect("Secret should be stored.");
+        assert_that!(secret_cipher.decrypt(&encrypted)?, eq(secret));
+
+        let secret_names = list_secrets(ListSecretsParams {
+            resources_manager: Arc::clone(&resources_manager),
+        }).await?;
+
+        assert_that!(secret_names, elements_are![eq(secret_name)]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_record_the_owner_of_new_secrets_and_keep_it_when_replacing_them() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let secret_cipher = Arc::new(SecretCipher::generate()?);
+        let admin = Requester::User {
+            name: String::from("admin"),
+            groups: vec![String::from("/admins")],
+            role: Some(Role::Admin),
+        };
+        let team_a = Ownership { owner: String::from("alice"), group: Some(String::from("/team-a")) };
+
+        let params = |value: &str, ownership: Option<Ownership>| -> anyhow::Result<StoreSecretParams> {
+            Ok(StoreSecretParams {
+                resources_manager: Arc::clone(&resources_manager),
+                secret_cipher: Arc::clone(&secret_cipher),
+                secret_store: SecretStore::default(),
+                secret: secret("db-password", value)?,
+                ownership,
+                requester: Clone::clone(&admin),
+            })
+        };
+
+        let secret_name = store_secret(params("hunter2", None)?).await?;
+        assert_that!(
+            resources_manager.get::<Ownership>(Clone::clone(&secret_name)).await,
+            some(eq(Ownership { owner: String::from("admin"), group: Some(String::from("/admins")) }))
+        );
+
+        store_secret(params("hunter3", Some(Clone::clone(&team_a)))?).await?;
+        store_secret(params("hunter4", None)?).await?;
+        assert_that!(resources_manager.get::<Ownership>(Clone::clone(&secret_name)).await, some(eq(team_a)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_restore_stored_secrets_and_their_owners_after_a_restart() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let secret_cipher = Arc::new(SecretCipher::generate()?);
+        let secret_store = SecretStore::at(temp.path().join("secrets").join("secrets.json"));
+        let team_a = Ownership { owner: String::from("alice"), group: Some(String::from("/team-a")) };
+
+        let resources_manager = ResourcesManager::new();
+        for (name, value) in [("db-password", "hunter2"), ("api-token", "12345")] {
+            store_secret(StoreSecretParams {
+                resources_manager: Arc::clone(&resources_manager),
+                secret_cipher: Arc::clone(&secret_cipher),
+                secret_store: Clone::clone(&secret_store),
+                secret: secret(name, value)?,
+                ownership: Some(Clone::clone(&team_a)),
+                requester: Requester::Unrestricted,
+            }).await?;
+        }
+        delete_secret(DeleteSecretParams {
+            resources_manager: Arc::clone(&resources_manager),
+            secret_store: Clone::clone(&secret_store),
+            secret_name: SecretName::try_from("api-token")?,
+        }).await?;
+
+        let stored = fs::read_to_string(temp.path().join("secrets").join("secrets.json"))?;
+        assert_that!(stored, not(contains_substring("hunter2")));
+
+        let restarted_resources_manager = ResourcesManager::new();
+        assert_that!(secret_store.restore(&restarted_resources_manager).await?, eq(1));
+
+        let secret_names = list_secrets(ListSecretsParams {
+            resources_manager: Arc::clone(&restarted_resources_manager),
+        }).await?;
+        assert_that!(secret_names, elements_are![eq(SecretName::try_from("db-password")?)]);
+
+        let encrypted = restarted_resources_manager.get::<EncryptedSecret>(SecretName::try_from("db-password")?).await
+            .expect("Secret should be restored.");
+        assert_that!(secret_cipher.decrypt(&encrypted)?.value, eq("hunter2"));
+        assert_that!(restarted_resources_manager.get::<Ownership>(SecretName::try_from("db-password")?).await, some(eq(team_a)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_delete_secret_referenced_by_a_peer() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let secret_cipher = Arc::new(SecretCipher::generate()?);
+
+        let secret_name = store_secret(StoreSecretParams {
+            resources_manager: Arc::clone(&resources_manager),
+            secret_cipher,
+            secret_store: SecretStore::default(),
+            secret: secret("db-password", "hunter2")?,
+            ownership: None,
+            requester: Requester::Unrestricted,
+        }).await?;
+
+        let peer_id = PeerId::random();
+        let peer = PeerDescriptor {
+            id: peer_id,
+            name: PeerName::try_from("PeerA")?,
+            location: PeerLocation::try_from("Ulm").ok(),
+            network: PeerNetworkDescriptor {
+                interfaces: vec![],
+                bridge_name: None,
+            },
+            topology: Topology::default(),
+            executors: ExecutorDescriptors {
+                executors: vec![ExecutorDescriptor {
+                    kind: ExecutorKind::Executable {
+                        name: ExecutableName::try_from("tester")?,
+                        program: ExecutableProgram::try_from("/usr/bin/tester")?,
+                        args: vec![],
+                        envs: vec![ExecutableEnvironmentVariable::new("DB_PASSWORD", secret_name.reference())?],
+                        working_directory: None,
+                        user: None,
+                    },
+                    results_url: None,
+                }],
+            },
+        };
+        resources_manager.insert(peer_id, peer).await;
+
+        let result = delete_secret(DeleteSecretParams {
+            resources_manager: Arc::clone(&resources_manager),
+            secret_store: SecretStore::default(),
+            secret_name: Clone::clone(&secret_name),
+        }).await;
+
+        assert!(matches!(result, Err(DeleteSecretError::SecretInUse { .. })));
+        assert_that!(resources_manager.get::<EncryptedSecret>(Clone::clone(&secret_name)).await, some(anything()));
+
+        resources_manager.remove::<PeerDescriptor>(peer_id).await;
+
+        let deleted = delete_secret(DeleteSecretParams {
+            resources_manager: Arc::clone(&resources_manager),
+            secret_store: SecretStore::default(),
+            secret_name: Clone::clone(&secret_name),
+        }).await?;
+
+        assert_that!(deleted, eq(&secret_name));
+        assert_that!(resources_manager.get::<EncryptedSecret>(Clone::clone(&secret_name)).await, none());
+
+        let result = delete_secret(DeleteSecretParams {
+            resources_manager: Arc::clone(&resources_manager),
+            secret_store: SecretStore::default(),
+            secret_name,
+        }).await;
+
+        assert!(matches!(result, Err(DeleteSecretError::SecretNotFound { .. })));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,328 @@
+use std::time::{Duration, SystemTime};
+
+use tracing::{debug, error, info};
+
+pub use opendut_carl_api::carl::service_account::{
+    CreateApiTokenError,
+    CreateServiceAccountError,
+    DeleteServiceAccountError,
+    ListApiTokensError,
+    ListServiceAccountsError,
+    RevokeApiTokenError,
+};
+use opendut_types::service_account::{ApiToken, ApiTokenDescriptor, ApiTokenId, ServiceAccountDescriptor, ServiceAccountId, ServiceAccountName, ServiceAccountRole};
+
+use crate::auth::api_token::StoredApiToken;
+use crate::resources::manager::ResourcesManagerRef;
+
+pub struct CreateServiceAccountParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub name: ServiceAccountName,
+    pub role: ServiceAccountRole,
+    pub group: Option<String>,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn create_service_account(params: CreateServiceAccountParams) -> Result<ServiceAccountDescriptor, CreateServiceAccountError> {
+
+    async fn inner(params: CreateServiceAccountParams) -> Result<ServiceAccountDescriptor, CreateServiceAccountError> {
+
+        let name = params.name;
+        let resources_manager = params.resources_manager;
+
+        debug!("Creating service account '{name}' with role '{}'.", params.role);
+
+        let service_account = resources_manager.resources_mut(|resources| {
+            let other = resources.iter::<ServiceAccountDescriptor>()
+                .find(|other| other.name == name)
+                .map(|other| other.id);
+
+            if let Some(other_id) = other {
+                return Err(CreateServiceAccountError::AlreadyExists { name: Clone::clone(&name), other_id });
+            }
+
+            let service_account = ServiceAccountDescriptor {
+                id: ServiceAccountId::random(),
+                name: Clone::clone(&name),
+                role: params.role,
+                group: params.group,
+            };
+            resources.insert(service_account.id, Clone::clone(&service_account));
+            Ok(service_account)
+        }).await?;
+
+        info!("Successfully created service account '{name}' <{}>.", service_account.id);
+
+        Ok(service_account)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct DeleteServiceAccountParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub service_account_id: ServiceAccountId,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn delete_service_account(params: DeleteServiceAccountParams) -> Result<ServiceAccountDescriptor, DeleteServiceAccountError> {
+
+    async fn inner(params: DeleteServiceAccountParams) -> Result<ServiceAccountDescriptor, DeleteServiceAccountError> {
+
+        let service_account_id = params.service_account_id;
+        let resources_manager = params.resources_manager;
+
+        debug!("Deleting service account <{service_account_id}> and its API tokens.");
+
+        let (service_account, revoked_tokens) = resources_manager.resources_mut(|resources| {
+            let service_account = resources.remove::<ServiceAccountDescriptor>(service_account_id)
+                .ok_or(DeleteServiceAccountError::ServiceAccountNotFound { service_account_id })?;
+
+            let token_ids = resources.iter::<StoredApiToken>()
+                .filter(|token| token.descriptor.service_account_id == service_account_id)
+                .map(|token| token.descriptor.id)
+                .collect::<Vec<_>>();
+            for token_id in &token_ids {
+                resources.remove::<StoredApiToken>(*token_id);
+            }
+            Ok((service_account, token_ids.len()))
+        }).await?;
+
+        info!("Successfully deleted service account '{}' <{service_account_id}> and revoked its {revoked_tokens} API token(s).", service_account.name);
+
+        Ok(service_account)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct ListServiceAccountsParams {
+    pub resources_manager: ResourcesManagerRef,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_service_accounts(params: ListServiceAccountsParams) -> Result<Vec<ServiceAccountDescriptor>, ListServiceAccountsError> {
+
+    async fn inner(params: ListServiceAccountsParams) -> Result<Vec<ServiceAccountDescriptor>, ListServiceAccountsError> {
+
+        let resources_manager = params.resources_manager;
+
+        debug!("Querying all service accounts.");
+
+        let service_accounts = resources_manager.resources(|resources| {
+            resources.iter::<ServiceAccountDescriptor>()
+                .cloned()
+                .collect::<Vec<_>>()
+        }).await;
+
+        info!("Successfully queried all service accounts.");
+
+        Ok(service_accounts)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct CreateApiTokenParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub service_account_id: ServiceAccountId,
+    pub lifetime: Duration,
+}
+
+/// Creates an API token for the service account. Only the digest of the token is stored.
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn create_api_token(params: CreateApiTokenParams) -> Result<(ApiTokenDescriptor, ApiToken), CreateApiTokenError> {
+
+    async fn inner(params: CreateApiTokenParams) -> Result<(ApiTokenDescriptor, ApiToken), CreateApiTokenError> {
+
+        let service_account_id = params.service_account_id;
+        let resources_manager = params.resources_manager;
+
+        debug!("Creating API token for service account <{service_account_id}>, which is valid for {} seconds.", params.lifetime.as_secs());
+
+        let token_id = ApiTokenId::random();
+        let token = ApiToken::generate(token_id);
+        let created_at = SystemTime::now();
+        let expires_at = created_at.checked_add(params.lifetime)
+            .ok_or_else(|| CreateApiTokenError::Internal { service_account_id, cause: format!("Lifetime of {} seconds is too long.", params.lifetime.as_secs()) })?;
+
+        let descriptor = ApiTokenDescriptor {
+            id: token_id,
+            service_account_id,
+            created_at,
+            expires_at,
+        };
+
+        resources_manager.resources_mut(|resources| {
+            if resources.get::<ServiceAccountDescriptor>(service_account_id).is_none() {
+                return Err(CreateApiTokenError::ServiceAccountNotFound { service_account_id });
+            }
+            resources.insert(token_id, StoredApiToken {
+                descriptor: Clone::clone(&descriptor),
+                digest: token.digest(),
+            });
+            Ok(())
+        }).await?;
+
+        info!("Successfully created API token <{token_id}> for service account <{service_account_id}>.");
+
+        Ok((descriptor, token))
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct RevokeApiTokenParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub token_id: ApiTokenId,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn revoke_api_token(params: RevokeApiTokenParams) -> Result<ApiTokenDescriptor, RevokeApiTokenError> {
+
+    async fn inner(params: RevokeApiTokenParams) -> Result<ApiTokenDescriptor, RevokeApiTokenError> {
+
+        let token_id = params.token_id;
+        let resources_manager = params.resources_manager;
+
+        debug!("Revoking API token <{token_id}>.");
+
+        let token = resources_manager.remove::<StoredApiToken>(token_id).await
+            .ok_or(RevokeApiTokenError::ApiTokenNotFound { token_id })?;
+
+        info!("Successfully revoked API token <{token_id}> of service account <{}>.", token.descriptor.service_account_id);
+
+        Ok(token.descriptor)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+pub struct ListApiTokensParams {
+    pub resources_manager: ResourcesManagerRef,
+    pub service_account_id: Option<ServiceAccountId>,
+}
+
+#[tracing::instrument(skip(params), level="trace")]
+pub async fn list_api_tokens(params: ListApiTokensParams) -> Result<Vec<ApiTokenDescriptor>, ListApiTokensError> {
+
+    async fn inner(params: ListApiTokensParams) -> Result<Vec<ApiTokenDescriptor>, ListApiTokensError> {
+
+        let service_account_id = params.service_account_id;
+        let resources_manager = params.resources_manager;
+
+        debug!("Querying API tokens.");
+
+        let tokens = resources_manager.resources(|resources| {
+            resources.iter::<StoredApiToken>()
+                .filter(|token| match service_account_id {
+                    None => true,
+                    Some(service_account_id) => token.descriptor.service_account_id == service_account_id,
+                })
+                .map(|token| Clone::clone(&token.descriptor))
+                .collect::<Vec<_>>()
+        }).await;
+
+        info!("Successfully queried API tokens.");
+
+        Ok(tokens)
+    }
+
+    inner(params).await
+        .inspect_err(|err| error!("{err}"))
+}
+
+#[cfg(test)]
+mod test {
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_reject_service_accounts_with_duplicate_names() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+
+        let service_account = create_service_account(CreateServiceAccountParams {
+            resources_manager: Arc::clone(&resources_manager),
+            name: ServiceAccountName::try_from("ci-pipeline")?,
+            role: ServiceAccountRole::Viewer,
+            group: None,
+        }).await?;
+
+        let result = create_service_account(CreateServiceAccountParams {
+            resources_manager: Arc::clone(&resources_manager),
+            name: ServiceAccountName::try_from("ci-pipeline")?,
+            role: ServiceAccountRole::Admin,
+            group: None,
+        }).await;
+
+        assert!(matches!(result, Err(CreateServiceAccountError::AlreadyExists { other_id, .. }) if other_id == service_account.id));
+
+        let service_accounts = list_service_accounts(ListServiceAccountsParams {
+            resources_manager: Arc::clone(&resources_manager),
+        }).await?;
+        assert_that!(service_accounts, elements_are![eq(service_account)]);
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_revoke_the_api_tokens_of_a_deleted_service_account() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+
+        let service_account = create_service_account(CreateServiceAccountParams {
+            resources_manager: Arc::clone(&resources_manager),
+            name: ServiceAccountName::try_from("ci-pipeline")?,
+            role: ServiceAccountRole::Operator,
+            group: None,
+        }).await?;
+
+        let (descriptor, token) = create_api_token(CreateApiTokenParams {
+            resources_manager: Arc::clone(&resources_manager),
+            service_account_id: service_account.id,
+            lifetime: Duration::from_secs(3600),
+        }).await?;
+
+        assert_that!(token.id(), some(eq(descriptor.id)));
+        assert_that!(descriptor.expires_at.duration_since(descriptor.created_at)?, eq(Duration::from_secs(3600)));
+        let stored_token = resources_manager.get::<StoredApiToken>(descriptor.id).await;
+        assert_that!(stored_token.map(|stored_token| stored_token.digest), some(eq(token.digest())));
+
+        let tokens = list_api_tokens(ListApiTokensParams {
+            resources_manager: Arc::clone(&resources_manager),
+            service_account_id: Some(service_account.id),
+        }).await?;
+        assert_that!(tokens, elements_are![eq(Clone::clone(&descriptor))]);
+
+        delete_service_account(DeleteServiceAccountParams {
+            resources_manager: Arc::clone(&resources_manager),
+            service_account_id: service_account.id,
+        }).await?;
+
+        assert_that!(resources_manager.get::<StoredApiToken>(descriptor.id).await, none());
+
+        let result = create_api_token(CreateApiTokenParams {
+            resources_manager: Arc::clone(&resources_manager),
+            service_account_id: service_account.id,
+            lifetime: Duration::from_secs(3600),
+        }).await;
+        assert!(matches!(result, Err(CreateApiTokenError::ServiceAccountNotFound { .. })));
+
+        let result = revoke_api_token(RevokeApiTokenParams {
+            resources_manager: Arc::clone(&resources_manager),
+            token_id: descriptor.id,
+        }).await;
+        assert!(matches!(result, Err(RevokeApiTokenError::ApiTokenNotFound { .. })));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,365 @@
+use std::fmt::Display;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{mpsc, Arc};
+use std::thread;
+use std::time::SystemTime;
+
+use tokio::sync::oneshot;
+use tonic::Request;
+use tracing::{error, warn};
+
+use opendut_types::audit::{AuditEvent, AuditEventFilter, AuditEventId, AuditOutcome};
+use opendut_types::resources::Id;
+use opendut_util::project;
+
+use crate::auth::CurrentUser;
+
+pub mod summary;
+
+pub type AuditLogRef = Arc<AuditLog>;
+
+/// Append-only log of the mutating operations, which users requested from CARL.
+/// Events are written as JSON lines to a file, which is rotated when it exceeds its maximum size.
+/// The file is only accessed by a dedicated writer thread, so that recording and querying events does not block the async runtime.
+pub struct AuditLog {
+    /// `None`, if the audit log is disabled.
+    writer: Option<mpsc::Sender<AuditLogCommand>>,
+}
+
+enum AuditLogCommand {
+    Record(AuditEvent),
+    Query { filter: AuditEventFilter, reply: oneshot::Sender<Result<Vec<AuditEvent>, AuditLogError>> },
+}
+
+struct AuditLogFile {
+    path: PathBuf,
+    rotation: AuditLogRotation,
+}
+
+impl AuditLog {
+    pub fn new(options: AuditLogOptions) -> AuditLogRef {
+        let writer = options.enabled.then(|| {
+            let file = AuditLogFile {
+                path: options.file,
+                rotation: options.rotation,
+            };
+            let (sender, receiver) = mpsc::channel();
+            thread::Builder::new()
+                .name(String::from("audit-log"))
+                .spawn(move || file.process(receiver))
+                .expect("Failed to spawn the thread of the audit log.");
+            sender
+        });
+        Arc::new(Self { writer })
+    }
+
+    pub fn disabled() -> AuditLogRef {
+        Arc::new(Self { writer: None })
+    }
+
+    /// Starts an audit event for the operation requested by the current user of the request.
+    /// Has to be called before the request is consumed, since the user is taken from its extensions.
+    pub fn start<T>(&self, request: &Request<T>, operation: &'static str) -> AuditEntry<'_> {
+        AuditEntry {
+            audit_log: self,
+            user: request.extensions().get::<CurrentUser>().map(|user| Clone::clone(&user.name)),
+            operation,
+            resource_id: None,
+            before: None,
+            after: None,
+        }
+    }
+
+    /// Hands the event to the writer thread, which appends it to the log. Failures are logged, but do not fail the audited operation.
+    pub fn record(&self, event: AuditEvent) {
+        let Some(writer) = &self.writer else { return };
+
+        if let Err(mpsc::SendError(AuditLogCommand::Record(event))) = writer.send(AuditLogCommand::Record(event)) {
+            error!("Failed to record audit event for operation '{}', as the writer of the audit log stopped.", event.operation);
+        }
+    }
+
+    /// Returns the recorded events matching the filter, most recent first.
+    /// Events, which were recorded before, are included, as the writer thread handles commands in order.
+    pub async fn query(&self, filter: &AuditEventFilter) -> Result<Vec<AuditEvent>, AuditLogError> {
+        let Some(writer) = &self.writer else { return Ok(Vec::new()) };
+
+        let (reply, result) = oneshot::channel();
+        writer.send(AuditLogCommand::Query { filter: Clone::clone(filter), reply })
+            .map_err(|_| AuditLogError::WriterStopped)?;
+        result.await
+            .map_err(|_| AuditLogError::WriterStopped)?
+    }
+}
+
+impl AuditLogFile {
+
+    /// Handles the commands of the audit log until it is dropped.
+    fn process(self, commands: mpsc::Receiver<AuditLogCommand>) {
+        for command in commands {
+            match command {
+                AuditLogCommand::Record(event) => {
+                    if let Err(cause) = self.append(&event) {
+                        error!("Failed to write audit event for operation '{}' to '{}': {cause}", event.operation, self.path.display());
+                    }
+                }
+                AuditLogCommand::Query { filter, reply } => {
+                    let _ = reply.send(self.query(&filter)); //requester is no longer interested, if it dropped the receiver
+                }
+            }
+        }
+    }
+
+    fn query(&self, filter: &AuditEventFilter) -> Result<Vec<AuditEvent>, AuditLogError> {
+        let mut events = Vec::new();
+        for path in self.paths_oldest_first() {
+            let reader = match File::open(&path) {
+                Ok(reader) => BufReader::new(reader),
+                Err(cause) if cause.kind() == io::ErrorKind::NotFound => continue,
+                Err(cause) => return Err(AuditLogError::Read { path, cause }),
+            };
+            for line in reader.lines() {
+                let line = line.map_err(|cause| AuditLogError::Read { path: Clone::clone(&path), cause })?;
+                match serde_json::from_str::<AuditEvent>(&line) {
+                    Ok(event) if filter.matches(&event) => events.push(event),
+                    Ok(_) => {}
+                    Err(cause) => warn!("Skipping malformed line in audit log '{}': {cause}", path.display()),
+                }
+            }
+        }
+
+        events.reverse();
+        if let Some(limit) = filter.limit {
+            events.truncate(limit as usize);
+        }
+        Ok(events)
+    }
+
+    fn append(&self, event: &AuditEvent) -> io::Result<()> {
+        let mut line = serde_json::to_string(event)?;
+        line.push('\n');
+
+        if let Some(parent) = self.path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+
+        let size = match fs::metadata(&self.path) {
+            Ok(metadata) => metadata.len(),
+            Err(cause) if cause.kind() == io::ErrorKind::NotFound => 0,
+            Err(cause) => return Err(cause),
+        };
+        if size > 0 && size + line.len() as u64 > self.rotation.max_file_size {
+            self.rotate()?;
+        }
+
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?
+            .write_all(line.as_bytes())
+    }
+
+    /// Shifts the current and rotated files by one, discarding the oldest file beyond the maximum number of files.
+    fn rotate(&self) -> io::Result<()> {
+        let paths = self.paths_oldest_first();
+        let (oldest, rest) = paths.split_first().expect("paths should contain at least the current file");
+
+        ignore_not_found(fs::remove_file(oldest))?;
+        for (to, from) in paths.iter().zip(rest) {
+            ignore_not_found(fs::rename(from, to))?;
+        }
+        Ok(())
+    }
+
+    /// Rotated files `<file>.<n>` down to `<file>.1`, followed by the current file.
+    fn paths_oldest_first(&self) -> Vec<PathBuf> {
+        (1..=self.rotation.max_files).rev()
+            .map(|index| rotated_path(&self.path, index))
+            .chain([Clone::clone(&self.path)])
+            .collect()
+    }
+}
+
+fn ignore_not_found(result: io::Result<()>) -> io::Result<()> {
+    match result {
+        Err(cause) if cause.kind() == io::ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+fn rotated_path(path: &Path, index: usize) -> PathBuf {
+    let mut file_name = path.file_name().unwrap_or_default().to_os_string();
+    file_name.push(format!(".{index}"));
+    path.with_file_name(file_name)
+}
+
+/// Audit event under construction, which is recorded once the outcome of the operation is known.
+#[must_use]
+pub struct AuditEntry<'a> {
+    audit_log: &'a AuditLog,
+    user: Option<String>,
+    operation: &'static str,
+    resource_id: Option<Id>,
+    before: Option<String>,
+    after: Option<String>,
+}
+
+impl AuditEntry<'_> {
+    pub fn resource(mut self, resource_id: Id) -> Self {
+        self.resource_id = Some(resource_id);
+        self
+    }
+
+    pub fn before(mut self, summary: Option<String>) -> Self {
+        self.before = summary;
+        self
+    }
+
+    pub fn after(mut self, summary: Option<String>) -> Self {
+        self.after = summary;
+        self
+    }
+
+    pub fn finish<T, E: Display>(self, result: &Result<T, E>) {
+        self.audit_log.record(AuditEvent {
+            id: AuditEventId::random(),
+            timestamp: SystemTime::now(),
+            user: self.user,
+            operation: String::from(self.operation),
+            resource_id: self.resource_id,
+            before: self.before,
+            after: self.after,
+            outcome: AuditOutcome::of(result),
+        });
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct AuditLogOptions {
+    pub enabled: bool,
+    pub file: PathBuf,
+    pub rotation: AuditLogRotation,
+}
+
+#[derive(Clone, Copy, Debug)]
+pub struct AuditLogRotation {
+    /// Size in bytes, beyond which the file is rotated.
+    pub max_file_size: u64,
+    /// Number of rotated files, which are kept in addition to the current file.
+    pub max_files: usize,
+}
+
+impl AuditLogOptions {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let enabled = config.get_bool("audit.enabled")?;
+        let file = project::make_path_absolute(config.get_string("audit.log.file")?)?;
+        let max_file_size = config.get::<u64>("audit.log.rotation.size.bytes")?;
+        let max_files = config.get::<usize>("audit.log.rotation.files")?;
+
+        Ok(AuditLogOptions {
+            enabled,
+            file,
+            rotation: AuditLogRotation {
+                max_file_size,
+                max_files,
+            },
+        })
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum AuditLogError {
+    #[error("Failed to read audit log '{}':\n  {cause}", path.display())]
+    Read { path: PathBuf, cause: io::Error },
+    #[error("Writer of the audit log stopped.")]
+    WriterStopped,
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use assert_fs::TempDir;
+
+    use super::*;
+
+    fn event(operation: &str, user: &str, timestamp_secs: u64) -> AuditEvent {
+        AuditEvent {
+            id: AuditEventId::random(),
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(timestamp_secs),
+            user: Some(String::from(user)),
+            operation: String::from(operation),
+            resource_id: None,
+            before: None,
+            after: None,
+            outcome: AuditOutcome::Success,
+        }
+    }
+
+    fn audit_log(temp: &TempDir, max_file_size: u64, max_files: usize) -> AuditLogRef {
+        AuditLog::new(AuditLogOptions {
+            enabled: true,
+            file: temp.path().join("audit").join("audit.log"),
+            rotation: AuditLogRotation { max_file_size, max_files },
+        })
+    }
+
+    #[tokio::test]
+    async fn should_query_recorded_events_most_recent_first() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let testee = audit_log(&temp, 1024 * 1024, 2);
+
+        testee.record(event("store_peer_descriptor", "alice", 1));
+        testee.record(event("delete_peer_descriptor", "bob", 2));
+        testee.record(event("store_peer_descriptor", "alice", 3));
+
+        let operations = |events: Vec<AuditEvent>| events.into_iter().map(|event| (event.operation, event.timestamp)).collect::<Vec<_>>();
+
+        let events = testee.query(&AuditEventFilter { user: Some(String::from("alice")), ..Default::default() }).await?;
+        assert_eq!(operations(events), vec![
+            (String::from("store_peer_descriptor"), SystemTime::UNIX_EPOCH + Duration::from_secs(3)),
+            (String::from("store_peer_descriptor"), SystemTime::UNIX_EPOCH + Duration::from_secs(1)),
+        ]);
+
+        let events = testee.query(&AuditEventFilter { limit: Some(1), ..Default::default() }).await?;
+        assert_eq!(operations(events), vec![
+            (String::from("store_peer_descriptor"), SystemTime::UNIX_EPOCH + Duration::from_secs(3)),
+        ]);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_rotate_the_file_and_discard_the_oldest_events() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let line_length = serde_json::to_string(&event("operation-0", "alice", 0))?.len() as u64 + 1;
+        let testee = audit_log(&temp, line_length, 2);
+
+        for index in 0..5 {
+            testee.record(event(&format!("operation-{index}"), "alice", index));
+        }
+
+        let operations = testee.query(&AuditEventFilter::default()).await?
+            .into_iter()
+            .map(|event| event.operation)
+            .collect::<Vec<_>>();
+        assert_eq!(operations, vec!["operation-4", "operation-3", "operation-2"]);
+
+        let file = temp.path().join("audit").join("audit.log");
+        assert!(rotated_path(&file, 1).exists());
+        assert!(rotated_path(&file, 2).exists());
+        assert!(!rotated_path(&file, 3).exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_record_events_when_disabled() -> anyhow::Result<()> {
+        let testee = AuditLog::disabled();
+
+        testee.record(event("store_peer_descriptor", "alice", 1));
+
+        assert_eq!(testee.query(&AuditEventFilter::default()).await?.len(), 0);
+        Ok(())
+    }
+}
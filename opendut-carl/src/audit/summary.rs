@@ -0,0 +1,129 @@
+use chrono::{DateTime, SecondsFormat, Utc};
+
+use opendut_types::can::CanDatabaseDescriptor;
+use opendut_types::cluster::{CanFaultInjectionRule, ClusterConfiguration, ClusterDeployment};
+use opendut_types::peer::PeerDescriptor;
+use opendut_types::secret::SecretName;
+use opendut_types::service_account::{ApiTokenDescriptor, ServiceAccountDescriptor};
+
+use crate::auth::ownership::Ownership;
+
+/// Short description of a resource for the audit log.
+/// Must not disclose secrets, like the environment variables of executors.
+pub trait AuditSummary {
+    fn audit_summary(&self) -> String;
+}
+
+impl AuditSummary for PeerDescriptor {
+    fn audit_summary(&self) -> String {
+        let interfaces = self.network.interfaces.iter()
+            .map(|interface| interface.name.name())
+            .collect::<Vec<_>>();
+        let executors = self.executors.executors.iter()
+            .map(|executor| executor.name())
+            .collect::<Vec<_>>();
+        format!("Peer '{}' with interfaces [{}] and executors [{}]", self.name, interfaces.join(", "), executors.join(", "))
+    }
+}
+
+impl AuditSummary for ClusterConfiguration {
+    fn audit_summary(&self) -> String {
+        format!("Cluster '{}' led by peer <{}> with {} device(s)", self.name, self.leader, self.devices.len())
+    }
+}
+
+impl AuditSummary for ClusterDeployment {
+    fn audit_summary(&self) -> String {
+        format!("Deployment of cluster <{}>", self.id)
+    }
+}
+
+impl AuditSummary for CanDatabaseDescriptor {
+    fn audit_summary(&self) -> String {
+        format!("CAN database '{}' version '{}' in format '{}' with checksum '{}'", self.name, self.version, self.format, self.hash)
+    }
+}
+
+impl AuditSummary for CanFaultInjectionRule {
+    fn audit_summary(&self) -> String {
+        let state = if self.enabled { "enabled" } else { "disabled" };
+        format!("Fault '{}' for {} in cluster <{}>, {state}", self.fault, self.filter, self.cluster_id)
+    }
+}
+
+impl AuditSummary for Ownership {
+    fn audit_summary(&self) -> String {
+        match &self.group {
+            Some(group) => format!("Owned by '{}' and group '{group}'", self.owner),
+            None => format!("Owned by '{}'", self.owner),
+        }
+    }
+}
+
+impl AuditSummary for SecretName {
+    fn audit_summary(&self) -> String {
+        format!("Secret '{self}'")
+    }
+}
+
+impl AuditSummary for ServiceAccountDescriptor {
+    fn audit_summary(&self) -> String {
+        match &self.group {
+            None => format!("Service account '{}' with role '{}'", self.name, self.role),
+            Some(group) => format!("Service account '{}' with role '{}' in group '{group}'", self.name, self.role),
+        }
+    }
+}
+
+impl AuditSummary for ApiTokenDescriptor {
+    fn audit_summary(&self) -> String {
+        let expires_at = DateTime::<Utc>::from(self.expires_at).to_rfc3339_opts(SecondsFormat::Secs, true);
+        format!("API token of service account <{}>, expiring at {expires_at}", self.service_account_id)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use opendut_types::peer::{PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
+    use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorDescriptors, ExecutorKind};
+    use opendut_types::peer::executor::executable::{ExecutableEnvironmentVariable, ExecutableName, ExecutableProgram};
+    use opendut_types::topology::Topology;
+    use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceName};
+
+    use super::*;
+
+    #[test]
+    fn should_not_disclose_the_environment_of_executors() -> anyhow::Result<()> {
+        let peer = PeerDescriptor {
+            id: PeerId::random(),
+            name: PeerName::try_from("TestPeer")?,
+            location: PeerLocation::try_from("SiFi").ok(),
+            network: PeerNetworkDescriptor {
+                interfaces: vec![NetworkInterfaceDescriptor {
+                    name: NetworkInterfaceName::try_from("eth0")?,
+                    configuration: NetworkInterfaceConfiguration::Ethernet,
+                }],
+                bridge_name: None,
+            },
+            topology: Topology::default(),
+            executors: ExecutorDescriptors {
+                executors: vec![ExecutorDescriptor {
+                    kind: ExecutorKind::Executable {
+                        name: ExecutableName::try_from("simulation")?,
+                        program: ExecutableProgram::try_from("/opt/simulation/run")?,
+                        args: vec![],
+                        envs: vec![ExecutableEnvironmentVariable::new("API_TOKEN", "secret")?],
+                        working_directory: None,
+                        user: None,
+                    },
+                    results_url: None,
+                }],
+            },
+        };
+
+        let summary = peer.audit_summary();
+        assert_eq!(summary, "Peer 'TestPeer' with interfaces [eth0] and executors [simulation]");
+        assert!(!summary.contains("secret"));
+        Ok(())
+    }
+}
@@ -0,0 +1,185 @@
+use std::time::SystemTime;
+
+use opendut_types::service_account::{ApiToken, ApiTokenDescriptor, ServiceAccountDescriptor, ServiceAccountRole};
+
+use crate::auth::CurrentUser;
+use crate::auth::permission::Role;
+use crate::resources::manager::ResourcesManagerRef;
+
+/// Prefix of the user name, under which a service account is authenticated.
+/// Users of the identity provider are named without it, so a service account cannot act as a human user of the same name, e.g. as owner of resources.
+pub const SERVICE_ACCOUNT_USER_PREFIX: &str = "service-account:";
+
+/// API token, as CARL stores it. Only the digest of the token is kept, so the token cannot be recovered from it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoredApiToken {
+    pub descriptor: ApiTokenDescriptor,
+    pub digest: String,
+}
+
+impl From<ServiceAccountRole> for Role {
+    fn from(value: ServiceAccountRole) -> Self {
+        match value {
+            ServiceAccountRole::Viewer => Role::Viewer,
+            ServiceAccountRole::Operator => Role::Operator,
+            ServiceAccountRole::Admin => Role::Admin,
+        }
+    }
+}
+
+/// Authenticates the service account, which the API token was created for.
+/// Returns the service account as user, named after the account with the prefix [`SERVICE_ACCOUNT_USER_PREFIX`] and in the group of the account, and the role of the account.
+pub async fn authenticate_api_token(
+    bearer_token: &str,
+    resources_manager: &ResourcesManagerRef,
+    now: SystemTime,
+) -> Result<(CurrentUser, Role), ApiTokenError> {
+
+    let token = ApiToken::from(bearer_token);
+    let token_id = token.id()
+        .ok_or(ApiTokenError::Malformed)?;
+
+    let (stored_token, service_account) = resources_manager.resources(|resources| {
+        let stored_token = resources.get::<StoredApiToken>(token_id)?;
+        let service_account = resources.get::<ServiceAccountDescriptor>(stored_token.descriptor.service_account_id)?;
+        Some((stored_token, service_account))
+    }).await
+        .ok_or(ApiTokenError::Unknown)?;
+
+    if stored_token.digest != token.digest() {
+        return Err(ApiTokenError::Unknown);
+    }
+    if stored_token.descriptor.is_expired(now) {
+        return Err(ApiTokenError::Expired { service_account: service_account.name.to_string() });
+    }
+
+    let user = CurrentUser {
+        name: format!("{SERVICE_ACCOUNT_USER_PREFIX}{}", service_account.name),
+        claims: None,
+        groups: service_account.group.into_iter().collect(),
+    };
+    Ok((user, Role::from(service_account.role)))
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ApiTokenError {
+    #[error("API token is malformed.")]
+    Malformed,
+    #[error("API token is unknown or was revoked.")]
+    Unknown,
+    #[error("API token of service account '{service_account}' has expired.")]
+    Expired { service_account: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+
+    use opendut_types::service_account::{ApiTokenId, ServiceAccountId, ServiceAccountName};
+
+    use crate::auth::ownership::{Ownership, Requester};
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    async fn store_token(resources_manager: &ResourcesManagerRef, created_at: SystemTime) -> anyhow::Result<ApiToken> {
+        store_token_of_group(resources_manager, created_at, None).await
+    }
+
+    async fn store_token_of_group(resources_manager: &ResourcesManagerRef, created_at: SystemTime, group: Option<&str>) -> anyhow::Result<ApiToken> {
+        let service_account = ServiceAccountDescriptor {
+            id: ServiceAccountId::random(),
+            name: ServiceAccountName::try_from("ci-pipeline")?,
+            role: ServiceAccountRole::Operator,
+            group: group.map(String::from),
+        };
+        let token_id = ApiTokenId::random();
+        let token = ApiToken::generate(token_id);
+        let stored_token = StoredApiToken {
+            descriptor: ApiTokenDescriptor {
+                id: token_id,
+                service_account_id: service_account.id,
+                created_at,
+                expires_at: created_at + Duration::from_secs(3600),
+            },
+            digest: token.digest(),
+        };
+        resources_manager.insert(service_account.id, service_account).await;
+        resources_manager.insert(token_id, stored_token).await;
+        Ok(token)
+    }
+
+    #[tokio::test]
+    async fn should_authenticate_the_service_account_of_a_valid_api_token() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let now = SystemTime::now();
+        let token = store_token(&resources_manager, now).await?;
+
+        let (user, role) = authenticate_api_token(token.value(), &resources_manager, now).await?;
+
+        assert_that!(user.name, eq("service-account:ci-pipeline"));
+        assert!(user.claims.is_none());
+        assert!(user.groups.is_empty());
+        assert_that!(role, eq(Role::Operator));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_access_resources_of_a_human_user_with_the_same_name() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let now = SystemTime::now();
+        let token = store_token(&resources_manager, now).await?;
+
+        let (user, role) = authenticate_api_token(token.value(), &resources_manager, now).await?;
+        let requester = Requester::User { name: user.name, groups: vec![], role: Some(role) };
+
+        let owned_by_human = Ownership { owner: String::from("ci-pipeline"), group: None };
+        assert!(!requester.may_access(Some(&owned_by_human)));
+        assert!(requester.may_access(requester.ownership().as_ref()));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_create_resources_for_and_access_resources_of_the_group_of_the_service_account() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let now = SystemTime::now();
+        let token = store_token_of_group(&resources_manager, now, Some("/team-a")).await?;
+
+        let (user, role) = authenticate_api_token(token.value(), &resources_manager, now).await?;
+        assert_that!(user.groups, elements_are![eq("/team-a")]);
+
+        let requester = Requester::User { name: user.name, groups: user.groups, role: Some(role) };
+        assert_that!(requester.ownership(), some(eq(Ownership { owner: String::from("service-account:ci-pipeline"), group: Some(String::from("/team-a")) })));
+
+        let owned_by_team_a = Ownership { owner: String::from("alice"), group: Some(String::from("/team-a")) };
+        let owned_by_team_b = Ownership { owner: String::from("bob"), group: Some(String::from("/team-b")) };
+        assert!(requester.may_access(Some(&owned_by_team_a)));
+        assert!(!requester.may_access(Some(&owned_by_team_b)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_expired_forged_and_revoked_api_tokens() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let now = SystemTime::now();
+        let token = store_token(&resources_manager, now).await?;
+        let token_id = token.id().expect("Generated token should contain its id.");
+
+        let result = authenticate_api_token(token.value(), &resources_manager, now + Duration::from_secs(3600)).await;
+        assert!(matches!(result, Err(ApiTokenError::Expired { .. })));
+
+        let forged = ApiToken::generate(token_id);
+        let result = authenticate_api_token(forged.value(), &resources_manager, now).await;
+        assert!(matches!(result, Err(ApiTokenError::Unknown)));
+
+        let result = authenticate_api_token("opendut_not-a-token", &resources_manager, now).await;
+        assert!(matches!(result, Err(ApiTokenError::Malformed)));
+
+        resources_manager.remove::<StoredApiToken>(token_id).await;
+        let result = authenticate_api_token(token.value(), &resources_manager, now).await;
+        assert!(matches!(result, Err(ApiTokenError::Unknown)));
+        Ok(())
+    }
+}
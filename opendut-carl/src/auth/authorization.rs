@@ -1,22 +1,27 @@
+use std::time::SystemTime;
+
 use tracing::debug;
-use url::Url;
 use crate::auth::{CurrentUser};
-use crate::auth::json_web_key::JwkCacheValue;
-use crate::util::in_memory_cache::CustomInMemoryCache;
-use crate::auth::validation::{authorize_user, Jwk};
+use crate::auth::revocation::RevokedClients;
+use crate::auth::validation::JwtValidatorRef;
 
-pub(crate) async fn authorize_current_user(auth_token: &str, issuer_url: Url, issuer_remote_url: Url, cache: CustomInMemoryCache<String, JwkCacheValue>) -> Option<CurrentUser> {
+pub(crate) async fn authorize_current_user(auth_token: &str, jwt_validator: &JwtValidatorRef, revoked_clients: &RevokedClients) -> Option<CurrentUser> {
     // decode token
     let token_part: Vec<&str> = auth_token.split(' ').collect();
-    let jwk_requester = Jwk;
-    let result = authorize_user(issuer_url, issuer_remote_url, token_part.get(1).unwrap(), cache, jwk_requester, false).await
-        .map_err(|err| format!("Failed to validate token: {:?}", err));
+    let result = jwt_validator.authorize_user(token_part.get(1).unwrap(), SystemTime::now()).await;
     
     match result {
+        Ok(user) if revoked_clients.is_revoked_for(&user) => {
+            debug!("Rejecting token of user {:?}, because its client was revoked.", user.name);
+            None
+        }
         Ok(user) => {
             debug!("User: {:?} - Claims: {:?}", user.name, user.claims);
             Some(user)
         }
-        Err(_) => { None }
+        Err(cause) => {
+            debug!("Failed to validate token: {cause}");
+            None
+        }
     }
 }
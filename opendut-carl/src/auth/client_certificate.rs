@@ -0,0 +1,187 @@
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use pem::Pem;
+use rcgen::{Certificate as CertificateGenerator, CertificateParams, DistinguishedName, DnType, ExtendedKeyUsagePurpose, IsCa, KeyPair};
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+use opendut_types::peer::PeerId;
+use opendut_types::util::net::{Certificate, ClientIdentity, PrivateKey};
+use opendut_util::project;
+
+/// Prefix of the common name in client certificates of peers, which is followed by the peer's ID.
+const COMMON_NAME_PREFIX: &str = "opendut-peer-";
+
+/// Peer, which authenticated with a client certificate issued by CARL.
+/// The mutual TLS acceptor attaches it to every request of the connection.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AuthenticatedPeer {
+    pub peer_id: PeerId,
+    pub serial: CertificateSerial,
+}
+
+/// Serial number of a client certificate, which distinguishes the certificates issued to the same peer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CertificateSerial(pub String);
+
+#[derive(Clone, Debug)]
+pub struct ClientCertificateOptions {
+    pub ca_key: std::path::PathBuf,
+    pub bind_port: u16,
+    pub remote_port: u16,
+    pub validity: Duration,
+}
+
+impl ClientCertificateOptions {
+    /// Returns `None`, if peers do not authenticate via client certificates.
+    pub fn load(config: &config::Config) -> Result<Option<Self>, ClientCertificateError> {
+        let load = || -> anyhow::Result<Option<Self>> {
+            if !config.get_bool("network.tls.client.auth.enabled")? {
+                return Ok(None);
+            }
+            let ca_key = project::make_path_absolute(config.get_string("network.tls.client.auth.ca.key")?)?;
+            let bind_port = u16::try_from(config.get_int("network.tls.client.auth.bind.port")?)?;
+            let remote_port = u16::try_from(config.get_int("network.tls.client.auth.remote.port")?)?;
+            let validity = Duration::from_secs(
+                u64::try_from(config.get_int("network.tls.client.auth.certificate.validity.days")?)? * 24 * 60 * 60
+            );
+            Ok(Some(Self { ca_key, bind_port, remote_port, validity }))
+        };
+        load().map_err(|cause| ClientCertificateError::Configuration { cause: cause.to_string() })
+    }
+}
+
+pub type ClientCertificateAuthorityRef = Arc<ClientCertificateAuthority>;
+
+/// Issues client certificates for peers, which are signed by the CA, that CARL distributes in setup strings.
+pub struct ClientCertificateAuthority {
+    ca: CertificateGenerator,
+    validity: Duration,
+}
+
+impl ClientCertificateAuthority {
+    pub fn new(ca_certificate: &Pem, ca_key: &str, validity: Duration) -> Result<Self, ClientCertificateError> {
+        let key_pair = KeyPair::from_pem(ca_key)
+            .map_err(|cause| ClientCertificateError::Configuration { cause: format!("Failed to load CA key: {cause}") })?;
+        let params = CertificateParams::from_ca_cert_pem(&pem::encode(ca_certificate), key_pair)
+            .map_err(|cause| ClientCertificateError::Configuration { cause: format!("Failed to load CA certificate: {cause}") })?;
+        let ca = CertificateGenerator::from_params(params)
+            .map_err(|cause| ClientCertificateError::Configuration { cause: format!("Failed to load CA certificate: {cause}") })?;
+
+        Ok(Self { ca, validity })
+    }
+
+    pub fn load(ca_certificate: &Pem, options: &ClientCertificateOptions) -> Result<Self, ClientCertificateError> {
+        let ca_key = std::fs::read_to_string(&options.ca_key)
+            .map_err(|cause| ClientCertificateError::Configuration { cause: format!("Failed to read CA key from '{}': {cause}", options.ca_key.display()) })?;
+        Self::new(ca_certificate, &ca_key, options.validity)
+    }
+
+    /// Generates a new key pair for the peer and a certificate, whose common name identifies the peer.
+    pub fn issue(&self, peer_id: PeerId, now: SystemTime) -> Result<ClientIdentity, ClientCertificateError> {
+        let mut params = CertificateParams::default();
+        params.distinguished_name = {
+            let mut name = DistinguishedName::new();
+            name.push(DnType::CommonName, format!("{COMMON_NAME_PREFIX}{}", peer_id.uuid));
+            name
+        };
+        params.is_ca = IsCa::ExplicitNoCa;
+        params.extended_key_usages = vec![ExtendedKeyUsagePurpose::ClientAuth];
+        params.not_before = now.into();
+        params.not_after = (now + self.validity).into();
+
+        let certificate = CertificateGenerator::from_params(params)
+            .map_err(|cause| ClientCertificateError::Issue { peer_id, cause: cause.to_string() })?;
+        let certificate_der = certificate.serialize_der_with_signer(&self.ca)
+            .map_err(|cause| ClientCertificateError::Issue { peer_id, cause: cause.to_string() })?;
+
+        Ok(ClientIdentity {
+            certificate: Certificate(Pem::new("CERTIFICATE", certificate_der)),
+            private_key: PrivateKey(Pem::new("PRIVATE KEY", certificate.serialize_private_key_der())),
+        })
+    }
+}
+
+/// Client certificates, which peers receive on enrollment, and the port, at which CARL accepts them.
+#[derive(Clone)]
+pub struct MutualTlsEnrollment {
+    pub authority: ClientCertificateAuthorityRef,
+    pub carl_port: u16,
+}
+
+/// Determines the peer from the common name of a client certificate, which the TLS handshake verified against the CA.
+pub fn authenticated_peer_of(certificate_der: &[u8]) -> Option<AuthenticatedPeer> {
+    let (_, certificate) = X509Certificate::from_der(certificate_der).ok()?;
+    let common_name = certificate.subject().iter_common_name().next()?
+        .as_str().ok()?;
+    let peer_id = common_name.strip_prefix(COMMON_NAME_PREFIX)?;
+    let peer_id = PeerId::try_from(peer_id).ok()?;
+
+    Some(AuthenticatedPeer {
+        peer_id,
+        serial: CertificateSerial(certificate.raw_serial_as_string()),
+    })
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ClientCertificateError {
+    #[error("Invalid configuration of client certificates:\n  {cause}")]
+    Configuration { cause: String },
+    #[error("Failed to issue client certificate for peer <{peer_id}>:\n  {cause}")]
+    Issue { peer_id: PeerId, cause: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    const VALIDITY: Duration = Duration::from_secs(24 * 60 * 60);
+
+    fn authority() -> ClientCertificateAuthority {
+        let mut params = CertificateParams::default();
+        params.distinguished_name.push(DnType::CommonName, "Test CA");
+        params.is_ca = IsCa::Ca(rcgen::BasicConstraints::Unconstrained);
+        let ca = CertificateGenerator::from_params(params).unwrap();
+
+        let ca_certificate = pem::parse(ca.serialize_pem().unwrap()).unwrap();
+        ClientCertificateAuthority::new(&ca_certificate, &ca.serialize_private_key_pem(), VALIDITY).unwrap()
+    }
+
+    #[test]
+    fn should_identify_the_peer_by_its_issued_certificate() -> anyhow::Result<()> {
+        let peer_id = PeerId::random();
+
+        let identity = authority().issue(peer_id, SystemTime::now())?;
+
+        assert_that!(identity.certificate.0.tag(), eq("CERTIFICATE"));
+        let authenticated_peer = authenticated_peer_of(identity.certificate.0.contents());
+        assert_that!(authenticated_peer.map(|peer| peer.peer_id), some(eq(peer_id)));
+        Ok(())
+    }
+
+    #[test]
+    fn should_issue_certificates_with_distinct_serials() -> anyhow::Result<()> {
+        let authority = authority();
+        let peer_id = PeerId::random();
+
+        let first = authority.issue(peer_id, SystemTime::now())?;
+        let second = authority.issue(peer_id, SystemTime::now())?;
+
+        let first = authenticated_peer_of(first.certificate.0.contents()).unwrap();
+        let second = authenticated_peer_of(second.certificate.0.contents()).unwrap();
+        assert_that!(first.serial, not(eq(second.serial)));
+        Ok(())
+    }
+
+    #[test]
+    fn should_not_identify_a_peer_by_a_foreign_common_name() -> anyhow::Result<()> {
+        let mut params = CertificateParams::default();
+        params.distinguished_name.push(DnType::CommonName, PeerId::random().uuid.to_string());
+        let certificate = CertificateGenerator::from_params(params)?;
+
+        assert_that!(authenticated_peer_of(&certificate.serialize_der()?), none());
+        Ok(())
+    }
+}
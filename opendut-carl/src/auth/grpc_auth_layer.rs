@@ -1,28 +1,43 @@
+use std::time::SystemTime;
+
 use tonic::Status;
-use url::Url;
+use tracing::debug;
+
+use opendut_types::service_account::ApiToken;
+
+use crate::auth::api_token::authenticate_api_token;
+use crate::auth::client_certificate::AuthenticatedPeer;
 use crate::auth::grpc_auth_layer::GrpcAuthenticationLayer::GrpcAuthLayerEnabled;
-use crate::auth::json_web_key::JwkCacheValue;
-use crate::util::in_memory_cache::CustomInMemoryCache;
+use crate::auth::permission::{CurrentRole, RoleMapping};
+use crate::auth::revocation::RevokedClients;
+use crate::auth::validation::JwtValidatorRef;
+use crate::resources::manager::ResourcesManagerRef;
 
 #[allow(clippy::large_enum_variant)]
 #[derive(Debug, Clone)]
 pub enum GrpcAuthenticationLayer {
     AuthDisabled,
     GrpcAuthLayerEnabled {
-        issuer_url: Url,
-        issuer_remote_url: Url,
-        cache: CustomInMemoryCache<String, JwkCacheValue>,
+        jwt_validator: JwtValidatorRef,
+        role_mapping: RoleMapping,
+        revoked_clients: RevokedClients,
+        resources_manager: ResourcesManagerRef,
     },
 }
 
 impl GrpcAuthenticationLayer {
     pub async fn auth_interceptor(self, mut request: tonic::Request<()>) -> anyhow::Result<tonic::Request<()>, Status> {
 
+        if request.extensions().get::<AuthenticatedPeer>().is_some() {
+            // peer was already authenticated by its client certificate during the TLS handshake
+            return Ok(request);
+        }
+
         match self {
             GrpcAuthenticationLayer::AuthDisabled => {
                 Ok(request)
             }
-            GrpcAuthLayerEnabled { issuer_url, issuer_remote_url, cache } => {
+            GrpcAuthLayerEnabled { jwt_validator, role_mapping, revoked_clients, resources_manager } => {
                 let auth_header = match request.metadata().get("authorization") {
                     None => {
                         return Err(Status::unauthenticated("CARL says, you did not provide credentials!"))
@@ -32,9 +47,28 @@ impl GrpcAuthenticationLayer {
                     }
                 };
 
-                if let Some(current_user) = crate::auth::authorization::authorize_current_user(auth_header, issuer_url, issuer_remote_url, cache).await {
+                let bearer_token = auth_header.strip_prefix("Bearer ").unwrap_or_default();
+                if ApiToken::is_api_token(bearer_token) {
+                    // service accounts authenticate with API tokens issued by CARL instead of OIDC access tokens
+                    return match authenticate_api_token(bearer_token, &resources_manager, SystemTime::now()).await {
+                        Ok((current_user, role)) => {
+                            request.extensions_mut().insert(current_user);
+                            request.extensions_mut().insert(CurrentRole(Some(role)));
+                            Ok(request)
+                        }
+                        Err(cause) => {
+                            debug!("Rejecting API token: {cause}");
+                            Err(Status::unauthenticated("CARL says, invalid credentials!"))
+                        }
+                    };
+                }
+
+                if let Some(current_user) = crate::auth::authorization::authorize_current_user(auth_header, &jwt_validator, &revoked_clients).await {
+                    let role = current_user.claims.as_ref()
+                        .and_then(|claims| role_mapping.role_of(claims.additional_claims()));
                     // insert the current user info into a request extension
                     request.extensions_mut().insert(current_user);
+                    request.extensions_mut().insert(CurrentRole(role));
                     Ok(request)
                 } else {
                     Err(Status::unauthenticated("CARL says, invalid credentials!"))
@@ -0,0 +1,306 @@
+use std::collections::{BTreeMap, HashMap};
+use std::time::{Duration, SystemTime};
+
+use serde::Deserialize;
+use tokio::sync::RwLock;
+use tracing::{debug, warn};
+use url::Url;
+
+use crate::auth::json_web_key::{JsonWebKey, OidcJsonWebKeySet};
+use crate::auth::validation::ValidationError;
+
+pub const DISCOVERY_PATH: &str = ".well-known/openid-configuration";
+
+/// Minimum time between two fetches of the keys of an issuer, which are triggered by access tokens with an unknown key id.
+/// Prevents clients from making CARL query the identity provider with each request.
+const UNKNOWN_KEY_REFETCH_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Identity provider, whose access tokens CARL accepts.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TrustedIssuer {
+    /// URL, under which CARL reaches the issuer and fetches its discovery document.
+    pub url: Url,
+    /// Values of the `iss` claim, which identify the issuer in access tokens.
+    /// These differ from `url`, if the clients reach the issuer under another address than CARL.
+    pub names: Vec<String>,
+}
+
+impl TrustedIssuer {
+    pub fn new(url: Url) -> Self {
+        let names = vec![issuer_name(&url)];
+        Self { url, names }
+    }
+
+    /// Additionally accepts access tokens, which name the issuer with `url`.
+    pub fn also_named(mut self, url: &Url) -> Self {
+        let name = issuer_name(url);
+        if !self.names.contains(&name) {
+            self.names.push(name);
+        }
+        self
+    }
+
+    pub fn is_named(&self, issuer: &str) -> bool {
+        let issuer = issuer.trim_end_matches('/');
+        self.names.iter().any(|name| name == issuer)
+    }
+
+    fn discovery_url(&self) -> Result<Url, ValidationError> {
+        let mut url = Clone::clone(&self.url);
+        if !url.path().ends_with('/') {
+            url.set_path(&format!("{}/", url.path()));
+        }
+        url.join(DISCOVERY_PATH)
+            .map_err(|cause| ValidationError::Configuration(format!("Invalid discovery URL of issuer '{}': {cause}", self.url)))
+    }
+}
+
+fn issuer_name(url: &Url) -> String {
+    url.as_str().trim_end_matches('/').to_string()
+}
+
+#[derive(Clone, Debug, Deserialize)]
+struct OidcDiscoveryDocument {
+    jwks_uri: Url,
+}
+
+pub trait IssuerRequester {
+    async fn fetch(&self, url: Url) -> Result<String, ValidationError>;
+}
+
+#[derive(Debug)]
+pub struct HttpIssuerRequester;
+
+impl IssuerRequester for HttpIssuerRequester {
+    async fn fetch(&self, url: Url) -> Result<String, ValidationError> {
+        let response = reqwest::get(Clone::clone(&url)).await
+            .and_then(|response| response.error_for_status())
+            .map_err(|cause| ValidationError::Configuration(format!("Failed to fetch '{url}' from identity provider: {cause}")))?;
+        let result = response.text().await
+            .map_err(|cause| ValidationError::Configuration(format!("Failed to read '{url}' from identity provider: {cause}")))?;
+        Ok(result)
+    }
+}
+
+#[derive(Clone, Debug, Default)]
+struct IssuerKeys {
+    keys: BTreeMap<String, JsonWebKey>,
+    /// Time of the last successful fetch, or `None`, if the keys were never fetched.
+    fetched_at: Option<SystemTime>,
+    /// Time of the last attempt to fetch the keys, whether successful or not.
+    attempted_at: Option<SystemTime>,
+}
+
+/// Signing keys of the trusted issuers, which CARL discovers via [`DISCOVERY_PATH`] and keeps in memory.
+///
+/// The keys are refreshed periodically in the background. If an issuer cannot be reached, its keys continue
+/// to be used until they are older than `max_age`, so that access tokens can be validated offline in the meantime.
+#[derive(Debug)]
+pub struct IssuerKeyStore<R> {
+    issuers: Vec<TrustedIssuer>,
+    requester: R,
+    max_age: Duration,
+    keys: RwLock<HashMap<Url, IssuerKeys>>,
+}
+
+impl<R: IssuerRequester> IssuerKeyStore<R> {
+    pub fn new(issuers: Vec<TrustedIssuer>, requester: R, max_age: Duration) -> Self {
+        Self {
+            issuers,
+            requester,
+            max_age,
+            keys: Default::default(),
+        }
+    }
+
+    /// Returns the trusted issuer, which is named `issuer` in the `iss` claim of an access token.
+    pub fn issuer_named(&self, issuer: &str) -> Option<&TrustedIssuer> {
+        self.issuers.iter()
+            .find(|trusted| trusted.is_named(issuer))
+    }
+
+    /// Returns the key, with which the issuer signed an access token.
+    /// Unknown keys are fetched from the issuer, as it may have rotated its keys since the last refresh.
+    pub async fn key(&self, issuer: &TrustedIssuer, key_id: &str, now: SystemTime) -> Result<JsonWebKey, ValidationError> {
+        let attempted_at = {
+            let keys = self.keys.read().await;
+            let issuer_keys = keys.get(&issuer.url);
+            if let Some(key) = issuer_keys.and_then(|issuer_keys| self.usable_key(issuer_keys, key_id, now)) {
+                return Ok(key);
+            }
+            issuer_keys.and_then(|issuer_keys| issuer_keys.attempted_at)
+        };
+
+        let recently_attempted = attempted_at
+            .is_some_and(|attempted_at| now.duration_since(attempted_at).unwrap_or_default() < UNKNOWN_KEY_REFETCH_INTERVAL);
+        if recently_attempted {
+            return Err(ValidationError::UnknownKey { key_id: key_id.to_owned(), issuer: issuer_name(&issuer.url) });
+        }
+
+        self.refresh(issuer, now).await?;
+
+        let keys = self.keys.read().await;
+        keys.get(&issuer.url)
+            .and_then(|issuer_keys| self.usable_key(issuer_keys, key_id, now))
+            .ok_or_else(|| ValidationError::UnknownKey { key_id: key_id.to_owned(), issuer: issuer_name(&issuer.url) })
+    }
+
+    fn usable_key(&self, issuer_keys: &IssuerKeys, key_id: &str, now: SystemTime) -> Option<JsonWebKey> {
+        let fetched_at = issuer_keys.fetched_at?;
+        if now.duration_since(fetched_at).unwrap_or_default() > self.max_age {
+            return None;
+        }
+        issuer_keys.keys.get(key_id).cloned()
+    }
+
+    /// Fetches the current keys of the issuer. If this fails, the previously fetched keys are kept.
+    pub async fn refresh(&self, issuer: &TrustedIssuer, now: SystemTime) -> Result<(), ValidationError> {
+        self.keys.write().await
+            .entry(Clone::clone(&issuer.url))
+            .or_default()
+            .attempted_at = Some(now);
+
+        let discovery_url = issuer.discovery_url()?;
+        let discovery = self.requester.fetch(discovery_url).await?;
+        let discovery = serde_json::from_str::<OidcDiscoveryDocument>(&discovery)
+            .map_err(|cause| ValidationError::Configuration(format!("Failed to parse discovery document of issuer '{}': {cause}", issuer.url)))?;
+
+        let key_set = self.requester.fetch(discovery.jwks_uri).await?;
+        let key_set = serde_json::from_str::<OidcJsonWebKeySet>(&key_set)
+            .map_err(|cause| ValidationError::Configuration(format!("Failed to parse JSON web key set of issuer '{}': {cause}", issuer.url)))?;
+        let fetched_keys = key_set.signing_keys();
+
+        debug!("Fetched {} signing key(s) of issuer '{}'.", fetched_keys.len(), issuer.url);
+
+        let mut keys = self.keys.write().await;
+        let issuer_keys = keys.entry(Clone::clone(&issuer.url)).or_default();
+        issuer_keys.keys = fetched_keys;
+        issuer_keys.fetched_at = Some(now);
+        Ok(())
+    }
+
+    /// Fetches the current keys of all trusted issuers.
+    pub async fn refresh_all(&self, now: SystemTime) {
+        for issuer in &self.issuers {
+            if let Err(cause) = self.refresh(issuer, now).await {
+                warn!("Failed to refresh the signing keys of issuer '{}'. Continuing to use the keys fetched before.\n  {cause}", issuer.url);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    const ISSUER_URL: &str = "https://keycloak/realms/opendut/";
+    const KEY_SET: &str = r#"{"keys":[
+        {"kid":"signing","kty":"RSA","alg":"RS256","use":"sig","n":"AQAB","e":"AQAB"},
+        {"kid":"encryption","kty":"RSA","alg":"RSA-OAEP","use":"enc","n":"AQAB","e":"AQAB"},
+        {"kid":"elliptic","kty":"EC","alg":"ES256","use":"sig","crv":"P-256","x":"AQAB","y":"AQAB"}
+    ]}"#;
+
+    #[derive(Clone, Default)]
+    struct MockRequester {
+        available: Arc<AtomicBool>,
+        requests: Arc<AtomicUsize>,
+    }
+
+    impl MockRequester {
+        fn available() -> Self {
+            let requester = Self::default();
+            requester.available.store(true, Ordering::SeqCst);
+            requester
+        }
+    }
+
+    impl IssuerRequester for MockRequester {
+        async fn fetch(&self, url: Url) -> Result<String, ValidationError> {
+            self.requests.fetch_add(1, Ordering::SeqCst);
+            if !self.available.load(Ordering::SeqCst) {
+                return Err(ValidationError::Configuration(String::from("Identity provider is not available")));
+            }
+            match url.as_str() {
+                "https://keycloak/realms/opendut/.well-known/openid-configuration" => Ok(String::from(r#"{
+                    "issuer": "https://keycloak/realms/opendut",
+                    "jwks_uri": "https://keycloak/realms/opendut/protocol/openid-connect/certs"
+                }"#)),
+                "https://keycloak/realms/opendut/protocol/openid-connect/certs" => Ok(String::from(KEY_SET)),
+                other => Err(ValidationError::Configuration(format!("Unexpected request to '{other}'"))),
+            }
+        }
+    }
+
+    fn issuer() -> TrustedIssuer {
+        TrustedIssuer::new(Url::parse(ISSUER_URL).unwrap())
+    }
+
+    #[test]
+    fn should_match_issuer_names_regardless_of_trailing_slashes() {
+        let issuer = issuer()
+            .also_named(&Url::parse("https://auth.example.com/realms/opendut").unwrap());
+
+        assert!(issuer.is_named("https://keycloak/realms/opendut"));
+        assert!(issuer.is_named("https://keycloak/realms/opendut/"));
+        assert!(issuer.is_named("https://auth.example.com/realms/opendut"));
+        assert!(!issuer.is_named("https://keycloak/realms/other"));
+        assert_that!(issuer.discovery_url().unwrap().as_str(), eq("https://keycloak/realms/opendut/.well-known/openid-configuration"));
+    }
+
+    #[tokio::test]
+    async fn should_discover_the_signing_keys_of_an_issuer() {
+        let store = IssuerKeyStore::new(vec![issuer()], MockRequester::available(), Duration::from_secs(3600));
+        let now = SystemTime::now();
+
+        store.refresh_all(now).await;
+
+        let key = store.key(&issuer(), "signing", now).await;
+        assert_that!(key.map(|key| key.key_identifier), ok(eq("signing")));
+        assert!(store.key(&issuer(), "encryption", now).await.is_err());
+        assert!(store.key(&issuer(), "elliptic", now).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_use_stale_keys_while_the_issuer_is_unavailable_until_they_expire() {
+        let requester = MockRequester::available();
+        let store = IssuerKeyStore::new(vec![issuer()], Clone::clone(&requester), Duration::from_secs(3600));
+        let fetched_at = SystemTime::now();
+        store.refresh_all(fetched_at).await;
+
+        requester.available.store(false, Ordering::SeqCst);
+        let later = fetched_at + Duration::from_secs(1800);
+        store.refresh_all(later).await;
+        assert!(store.key(&issuer(), "signing", later).await.is_ok());
+
+        let expired = fetched_at + Duration::from_secs(7200);
+        assert!(matches!(store.key(&issuer(), "signing", expired).await, Err(ValidationError::Configuration(_))));
+
+        requester.available.store(true, Ordering::SeqCst);
+        let recovered = expired + UNKNOWN_KEY_REFETCH_INTERVAL;
+        assert!(store.key(&issuer(), "signing", recovered).await.is_ok());
+    }
+
+    #[tokio::test]
+    async fn should_throttle_fetches_triggered_by_unknown_key_ids() {
+        let requester = MockRequester::available();
+        let store = IssuerKeyStore::new(vec![issuer()], Clone::clone(&requester), Duration::from_secs(3600));
+        let now = SystemTime::now();
+
+        let result = store.key(&issuer(), "unknown", now).await;
+        assert!(matches!(result, Err(ValidationError::UnknownKey { .. })));
+        assert_that!(requester.requests.load(Ordering::SeqCst), eq(2));
+
+        let result = store.key(&issuer(), "unknown", now + Duration::from_secs(1)).await;
+        assert!(matches!(result, Err(ValidationError::UnknownKey { .. })));
+        assert_that!(requester.requests.load(Ordering::SeqCst), eq(2));
+
+        let result = store.key(&issuer(), "unknown", now + UNKNOWN_KEY_REFETCH_INTERVAL).await;
+        assert!(matches!(result, Err(ValidationError::UnknownKey { .. })));
+        assert_that!(requester.requests.load(Ordering::SeqCst), eq(4));
+    }
+}
@@ -1,27 +1,56 @@
-use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
-#[derive(Debug, Clone)]
-pub struct JwkCacheValue {
-    pub jwk: JsonWebKey,
-    pub last_cached: i64
-}
+use serde::{Deserialize, Serialize};
+use tracing::debug;
 
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct JsonWebKey {
-    pub alg: String,
+    #[serde(default)]
+    pub alg: Option<String>,
     pub kty: String,
-    pub r#use: String,
+    #[serde(default)]
+    pub r#use: Option<String>,
     #[serde(rename = "n")]
     pub modulus: String,
     #[serde(rename = "e")]
     pub exponent: String,
     #[serde(rename = "kid")]
     pub key_identifier: String,  // kid or key id is the id of the public certificate (of the issuer/identity provider)
-    pub x5t: String,
+    #[serde(default)]
+    pub x5t: Option<String>,
+    #[serde(default)]
     pub x5c: Vec<String>,
 }
 
+impl JsonWebKey {
+    /// Whether the key may be used to verify the signature of access tokens.
+    pub fn is_signing_key(&self) -> bool {
+        self.kty == "RSA"
+            && matches!(self.r#use.as_deref(), None | Some("sig"))
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct OidcJsonWebKeySet {
-    pub(crate) keys: Vec<JsonWebKey>,
-}
\ No newline at end of file
+    pub(crate) keys: Vec<serde_json::Value>,
+}
+
+impl OidcJsonWebKeySet {
+    /// Returns the RSA signing keys of the set by their key id.
+    /// Identity providers may publish further keys, e.g. elliptic-curve or encryption keys, which are skipped.
+    pub fn signing_keys(self) -> BTreeMap<String, JsonWebKey> {
+        self.keys.into_iter()
+            .filter_map(|key| match serde_json::from_value::<JsonWebKey>(key) {
+                Ok(jwk) if jwk.is_signing_key() => Some((Clone::clone(&jwk.key_identifier), jwk)),
+                Ok(jwk) => {
+                    debug!("Skipping JSON web key '{}' of type '{}', which is not an RSA signing key.", jwk.key_identifier, jwk.kty);
+                    None
+                }
+                Err(cause) => {
+                    debug!("Skipping unsupported JSON web key: {cause}");
+                    None
+                }
+            })
+            .collect()
+    }
+}
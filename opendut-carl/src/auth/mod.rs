@@ -1,7 +1,16 @@
-mod validation;
+pub(crate) mod validation;
+pub(crate) mod api_token;
 pub(crate) mod json_web_key;
 mod authorization;
+pub(crate) mod client_certificate;
 pub(crate) mod grpc_auth_layer;
+pub(crate) mod issuer;
+pub(crate) mod mutual_tls;
+pub(crate) mod ownership;
+pub(crate) mod peer_certificates;
+pub(crate) mod peer_identity;
+pub(crate) mod permission;
+pub(crate) mod revocation;
 
 use openidconnect::core::CoreGenderClaim;
 use openidconnect::{AdditionalClaims, IdTokenClaims};
@@ -12,7 +21,10 @@ pub type Claims<AC> = IdTokenClaims<AC, CoreGenderClaim>;
 #[derive(Clone, Debug)]
 pub struct CurrentUser {
     pub name: String,
-    pub claims: Claims<MyAdditionalClaims>,
+    /// Claims of the OIDC access token, or `None` for service accounts, which authenticate with an API token.
+    pub claims: Option<Claims<MyAdditionalClaims>>,
+    /// Groups of the user, taken from the claims of the access token, respectively the group of the service account.
+    pub groups: Vec<String>,
 }
 
 #[derive(Debug, Clone, Default, Serialize, Deserialize)]
@@ -23,6 +35,9 @@ pub struct MyAdditionalClaims {
     /// Groups of the user (custom claim) may be omitted by identity provider, so we need a default value
     #[serde(default = "MyAdditionalClaims::empty_vector")]
     pub groups: Vec<String>,
+    /// Space-separated scopes granted to the client (standard claim of access tokens, which is not part of ID tokens)
+    #[serde(default)]
+    pub scope: Option<String>,
 }
 
 impl MyAdditionalClaims {
@@ -0,0 +1,80 @@
+use std::io;
+use std::path::Path;
+use std::sync::Arc;
+
+use axum::middleware::AddExtension;
+use axum::Extension;
+use axum_server::accept::Accept;
+use axum_server::tls_rustls::{RustlsAcceptor, RustlsConfig};
+use futures::future::BoxFuture;
+use pem::Pem;
+use rustls::server::AllowAnyAuthenticatedClient;
+use rustls::{RootCertStore, ServerConfig};
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio_rustls::server::TlsStream;
+use tower::Layer;
+use tracing::debug;
+
+use crate::auth::client_certificate::{authenticated_peer_of, AuthenticatedPeer};
+
+/// Creates the TLS configuration of the port for peers, which requires a client certificate signed by the CA.
+pub fn server_config(certificate: &Path, key: &Path, ca: &Pem) -> anyhow::Result<RustlsConfig> {
+    let certificates = rustls_pemfile::certs(&mut io::BufReader::new(std::fs::File::open(certificate)?))?
+        .into_iter()
+        .map(rustls::Certificate)
+        .collect();
+    let key = rustls_pemfile::pkcs8_private_keys(&mut io::BufReader::new(std::fs::File::open(key)?))?
+        .into_iter()
+        .next()
+        .map(rustls::PrivateKey)
+        .ok_or_else(|| anyhow::anyhow!("No PKCS#8 private key found in '{}'.", key.display()))?;
+
+    let mut roots = RootCertStore::empty();
+    roots.add(&rustls::Certificate(ca.contents().to_vec()))?;
+
+    let mut config = ServerConfig::builder()
+        .with_safe_defaults()
+        .with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots).boxed())
+        .with_single_cert(certificates, key)?;
+    config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+    Ok(RustlsConfig::from_config(Arc::new(config)))
+}
+
+/// Performs the TLS handshake and attaches the [`AuthenticatedPeer`] of the client certificate to the requests of the connection.
+#[derive(Clone)]
+pub struct ClientCertificateAcceptor {
+    inner: RustlsAcceptor,
+}
+
+impl ClientCertificateAcceptor {
+    pub fn new(config: RustlsConfig) -> Self {
+        Self { inner: RustlsAcceptor::new(config) }
+    }
+}
+
+impl<I, S> Accept<I, S> for ClientCertificateAcceptor
+where
+    I: AsyncRead + AsyncWrite + Unpin + Send + 'static,
+    S: Send + 'static,
+{
+    type Stream = TlsStream<I>;
+    type Service = AddExtension<S, AuthenticatedPeer>;
+    type Future = BoxFuture<'static, io::Result<(Self::Stream, Self::Service)>>;
+
+    fn accept(&self, stream: I, service: S) -> Self::Future {
+        let acceptor = self.inner.clone();
+
+        Box::pin(async move {
+            let (stream, service) = acceptor.accept(stream, service).await?;
+
+            let peer = stream.get_ref().1.peer_certificates()
+                .and_then(|certificates| certificates.first())
+                .and_then(|certificate| authenticated_peer_of(&certificate.0))
+                .ok_or_else(|| io::Error::new(io::ErrorKind::PermissionDenied, "Client certificate does not identify a peer."))?;
+
+            debug!("Peer <{}> connected with its client certificate.", peer.peer_id);
+            Ok((stream, Extension(peer).layer(service)))
+        })
+    }
+}
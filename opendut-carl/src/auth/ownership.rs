@@ -0,0 +1,126 @@
+use tonic::Request;
+
+use crate::auth::CurrentUser;
+use crate::auth::permission::{CurrentRole, Role};
+
+/// User and group, which a peer, cluster or secret belongs to.
+/// Resources, which were created while authentication was disabled, have no ownership and are visible to everyone.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Ownership {
+    pub owner: String,
+    pub group: Option<String>,
+}
+
+/// The user, on whose behalf an action accesses resources.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Requester {
+    /// CARL itself or, if authentication is disabled, any client. All resources are accessible.
+    Unrestricted,
+    User {
+        name: String,
+        groups: Vec<String>,
+        role: Option<Role>,
+    },
+}
+
+impl Requester {
+
+    pub fn from_request<T>(request: &Request<T>) -> Self {
+        match request.extensions().get::<CurrentUser>() {
+            None => Requester::Unrestricted,
+            Some(user) => Requester::User {
+                name: Clone::clone(&user.name),
+                groups: Clone::clone(&user.groups),
+                role: request.extensions().get::<CurrentRole>().and_then(|CurrentRole(role)| *role),
+            },
+        }
+    }
+
+    /// The owner of a resource, e.g. of a peer, on whose behalf CARL accesses further resources for it.
+    /// Resources without ownership act unrestricted.
+    pub fn on_behalf_of(ownership: Option<&Ownership>) -> Self {
+        match ownership {
+            None => Requester::Unrestricted,
+            Some(Ownership { owner, group }) => Requester::User {
+                name: Clone::clone(owner),
+                groups: group.iter().cloned().collect(),
+                role: None,
+            },
+        }
+    }
+
+    /// Ownership of resources created by this requester. The first group of the user becomes the owning group.
+    pub fn ownership(&self) -> Option<Ownership> {
+        match self {
+            Requester::Unrestricted => None,
+            Requester::User { name, groups, .. } => Some(Ownership {
+                owner: Clone::clone(name),
+                group: groups.first().cloned(),
+            }),
+        }
+    }
+
+    /// Admins may access all resources, other users only unowned resources, their own and those of their groups.
+    pub fn may_access(&self, ownership: Option<&Ownership>) -> bool {
+        match (self, ownership) {
+            (Requester::Unrestricted, _) => true,
+            (Requester::User { role: Some(Role::Admin), .. }, _) => true,
+            (Requester::User { .. }, None) => true,
+            (Requester::User { name, groups, .. }, Some(ownership)) => {
+                ownership.owner == *name
+                    || ownership.group.as_ref().is_some_and(|group| groups.contains(group))
+            }
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn user(name: &str, groups: &[&str], role: Role) -> Requester {
+        Requester::User {
+            name: name.to_string(),
+            groups: groups.iter().map(|group| group.to_string()).collect(),
+            role: Some(role),
+        }
+    }
+
+    #[test]
+    fn should_grant_access_to_owner_group_members_and_admins() {
+        let ownership = Ownership { owner: String::from("alice"), group: Some(String::from("/team-a")) };
+
+        assert!(user("alice", &[], Role::Viewer).may_access(Some(&ownership)));
+        assert!(user("bob", &["/team-b", "/team-a"], Role::Operator).may_access(Some(&ownership)));
+        assert!(user("admin", &[], Role::Admin).may_access(Some(&ownership)));
+        assert!(Requester::Unrestricted.may_access(Some(&ownership)));
+
+        assert!(!user("bob", &["/team-b"], Role::Operator).may_access(Some(&ownership)));
+        assert!(!user("bob", &["/team-a"], Role::Operator).may_access(Some(&Ownership { group: None, ..ownership })));
+    }
+
+    #[test]
+    fn should_grant_access_to_unowned_resources() {
+        assert!(user("bob", &[], Role::Viewer).may_access(None));
+    }
+
+    #[test]
+    fn should_act_on_behalf_of_the_owner_of_a_resource() {
+        let peer_ownership = Ownership { owner: String::from("alice"), group: Some(String::from("/team-a")) };
+        let requester = Requester::on_behalf_of(Some(&peer_ownership));
+
+        assert!(requester.may_access(Some(&Ownership { owner: String::from("carol"), group: Some(String::from("/team-a")) })));
+        assert!(!requester.may_access(Some(&Ownership { owner: String::from("bob"), group: Some(String::from("/team-b")) })));
+        assert_eq!(Requester::on_behalf_of(None), Requester::Unrestricted);
+    }
+
+    #[test]
+    fn should_own_resources_with_first_group() {
+        assert_eq!(
+            user("alice", &["/team-a", "/team-b"], Role::Operator).ownership(),
+            Some(Ownership { owner: String::from("alice"), group: Some(String::from("/team-a")) })
+        );
+        assert_eq!(Requester::Unrestricted.ownership(), None);
+    }
+}
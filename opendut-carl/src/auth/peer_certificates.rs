@@ -0,0 +1,235 @@
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use tokio::io::AsyncWriteExt;
+
+use opendut_types::peer::PeerId;
+use opendut_util::project;
+
+use crate::auth::client_certificate::CertificateSerial;
+
+const ISSUED: &str = "issued";
+const REVOKED: &str = "revoked";
+
+/// Client certificates, which CARL issued to peers, when they redeemed their enrollment token.
+/// Issuing a new certificate to a peer revokes the previous one, as does revoking the peer's credentials or deleting the peer.
+/// Both are appended to a file, so that certificates remain accepted, respectively rejected, after CARL restarts.
+#[derive(Clone, Debug, Default)]
+pub struct PeerCertificates {
+    state: Arc<RwLock<State>>,
+    /// `None`, if the certificates are only kept in memory.
+    file: Option<PathBuf>,
+}
+
+#[derive(Debug, Default)]
+struct State {
+    issued: HashMap<PeerId, String>,
+    revoked: HashSet<String>,
+}
+
+impl PeerCertificates {
+    pub fn load(config: &config::Config) -> Result<Self, PeerCertificatesError> {
+        let file = project::make_path_absolute(config.get_string("auth.peer.certificates.file")
+            .map_err(|cause| PeerCertificatesError::Configuration { cause: cause.to_string() })?)
+            .map_err(|cause| PeerCertificatesError::Configuration { cause: cause.to_string() })?;
+        Self::load_from(file)
+    }
+
+    pub(crate) fn load_from(file: PathBuf) -> Result<Self, PeerCertificatesError> {
+        let mut state = State::default();
+
+        match fs::read_to_string(&file) {
+            Ok(content) => {
+                for line in content.lines().map(str::trim).filter(|line| !line.is_empty()) {
+                    let invalid_line = || PeerCertificatesError::InvalidLine { path: Clone::clone(&file), line: line.to_owned() };
+
+                    let mut fields = line.split_whitespace();
+                    let (Some(kind), Some(peer_id), Some(serial), None) = (fields.next(), fields.next(), fields.next(), fields.next()) else {
+                        return Err(invalid_line());
+                    };
+                    let peer_id = PeerId::try_from(peer_id)
+                        .map_err(|_| invalid_line())?;
+
+                    match kind {
+                        ISSUED => { state.issue(peer_id, serial.to_owned()); }
+                        REVOKED => state.revoke(peer_id, serial.to_owned()),
+                        _ => return Err(invalid_line()),
+                    }
+                }
+            }
+            Err(cause) if cause.kind() == io::ErrorKind::NotFound => {}
+            Err(cause) => return Err(PeerCertificatesError::Read { path: file, cause }),
+        };
+
+        Ok(Self {
+            state: Arc::new(RwLock::new(state)),
+            file: Some(file),
+        })
+    }
+
+    /// Records the certificate issued to the peer and revokes the certificate it replaces.
+    pub async fn issue(&self, peer_id: PeerId, serial: CertificateSerial) -> Result<(), PeerCertificatesError> {
+        let serial = serial.0;
+
+        let replaced = self.state.write().expect("Lock of peer certificates should not be poisoned.")
+            .issue(peer_id, Clone::clone(&serial));
+
+        let mut lines = Vec::new();
+        if let Some(replaced) = replaced {
+            lines.push(format!("{REVOKED} {peer_id} {replaced}"));
+        }
+        lines.push(format!("{ISSUED} {peer_id} {serial}"));
+
+        self.append(&lines).await
+    }
+
+    /// Revokes the certificate, which was issued to the peer last. Returns `false`, if the peer had none.
+    pub async fn revoke(&self, peer_id: PeerId) -> Result<bool, PeerCertificatesError> {
+        let revoked = self.state.write().expect("Lock of peer certificates should not be poisoned.")
+            .revoke_issued(peer_id);
+
+        match revoked {
+            None => Ok(false),
+            Some(serial) => {
+                self.append(&[format!("{REVOKED} {peer_id} {serial}")]).await?;
+                Ok(true)
+            }
+        }
+    }
+
+    pub fn is_revoked(&self, serial: &CertificateSerial) -> bool {
+        self.state.read().expect("Lock of peer certificates should not be poisoned.")
+            .revoked.contains(&serial.0)
+    }
+
+    pub fn issued(&self, peer_id: PeerId) -> Option<CertificateSerial> {
+        self.state.read().expect("Lock of peer certificates should not be poisoned.")
+            .issued.get(&peer_id)
+            .cloned()
+            .map(CertificateSerial)
+    }
+
+    async fn append(&self, lines: &[String]) -> Result<(), PeerCertificatesError> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+
+        async fn append(file: &Path, lines: &[String]) -> io::Result<()> {
+            if let Some(parent) = file.parent() {
+                tokio::fs::create_dir_all(parent).await?;
+            }
+            let mut file = tokio::fs::OpenOptions::new()
+                .create(true)
+                .append(true)
+                .open(file).await?;
+            for line in lines {
+                file.write_all(format!("{line}\n").as_bytes()).await?;
+            }
+            file.flush().await
+        }
+
+        append(file, lines).await
+            .map_err(|cause| PeerCertificatesError::Write { path: Clone::clone(file), cause })
+    }
+}
+
+impl State {
+    /// Returns the serial of the replaced certificate, which is revoked.
+    fn issue(&mut self, peer_id: PeerId, serial: String) -> Option<String> {
+        let replaced = self.issued.insert(peer_id, Clone::clone(&serial))
+            .filter(|replaced| *replaced != serial);
+
+        if let Some(replaced) = &replaced {
+            self.revoked.insert(Clone::clone(replaced));
+        }
+        replaced
+    }
+
+    fn revoke(&mut self, peer_id: PeerId, serial: String) {
+        if self.issued.get(&peer_id) == Some(&serial) {
+            self.issued.remove(&peer_id);
+        }
+        self.revoked.insert(serial);
+    }
+
+    fn revoke_issued(&mut self, peer_id: PeerId) -> Option<String> {
+        let serial = self.issued.remove(&peer_id)?;
+        self.revoked.insert(Clone::clone(&serial));
+        Some(serial)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum PeerCertificatesError {
+    #[error("Invalid configuration for the file of peer certificates:\n  {cause}")]
+    Configuration { cause: String },
+    #[error("Failed to read peer certificates from '{}':\n  {cause}", path.display())]
+    Read { path: PathBuf, cause: io::Error },
+    #[error("Invalid line in the file of peer certificates '{}': {line}", path.display())]
+    InvalidLine { path: PathBuf, line: String },
+    #[error("Failed to write peer certificate to '{}':\n  {cause}", path.display())]
+    Write { path: PathBuf, cause: io::Error },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn serial(value: &str) -> CertificateSerial {
+        CertificateSerial(String::from(value))
+    }
+
+    #[tokio::test]
+    async fn should_revoke_the_certificate_replaced_by_a_new_one() -> anyhow::Result<()> {
+        let peer_certificates = PeerCertificates::default();
+        let peer_id = PeerId::random();
+
+        peer_certificates.issue(peer_id, serial("01")).await?;
+        peer_certificates.issue(peer_id, serial("02")).await?;
+
+        assert!(peer_certificates.is_revoked(&serial("01")));
+        assert!(!peer_certificates.is_revoked(&serial("02")));
+        assert_eq!(peer_certificates.issued(peer_id), Some(serial("02")));
+
+        assert!(peer_certificates.revoke(peer_id).await?);
+        assert!(peer_certificates.is_revoked(&serial("02")));
+        assert!(!peer_certificates.revoke(peer_id).await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_remember_issued_and_revoked_certificates_after_reloading_the_file() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        let file = directory.path().join("auth").join("peer-certificates");
+        let peer_id = PeerId::random();
+        let other_peer_id = PeerId::random();
+
+        let peer_certificates = PeerCertificates::load_from(Clone::clone(&file))?;
+        peer_certificates.issue(peer_id, serial("01")).await?;
+        peer_certificates.issue(peer_id, serial("02")).await?;
+        peer_certificates.issue(other_peer_id, serial("03")).await?;
+        peer_certificates.revoke(other_peer_id).await?;
+
+        let reloaded = PeerCertificates::load_from(Clone::clone(&file))?;
+        assert!(reloaded.is_revoked(&serial("01")));
+        assert!(!reloaded.is_revoked(&serial("02")));
+        assert!(reloaded.is_revoked(&serial("03")));
+        assert_eq!(reloaded.issued(peer_id), Some(serial("02")));
+        assert_eq!(reloaded.issued(other_peer_id), None);
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_a_corrupted_file() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        let file = directory.path().join("peer-certificates");
+        fs::write(&file, "issued not-a-peer-id 01\n")?;
+
+        let result = PeerCertificates::load_from(file);
+        assert!(matches!(result, Err(PeerCertificatesError::InvalidLine { .. })));
+        Ok(())
+    }
+}
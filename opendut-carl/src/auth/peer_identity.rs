@@ -0,0 +1,235 @@
+use tonic::Request;
+use tracing::debug;
+
+use opendut_auth::registration::client::RegistrationClientRef;
+use opendut_types::peer::PeerId;
+use opendut_types::util::net::ClientId;
+
+use crate::auth::client_certificate::{AuthenticatedPeer, CertificateSerial};
+use crate::auth::CurrentUser;
+use crate::auth::peer_certificates::PeerCertificates;
+use crate::resources::manager::ResourcesManagerRef;
+
+/// OIDC client, which CARL registered for a peer, when the peer redeemed its enrollment token.
+/// Only access tokens issued to this client may open the stream of the peer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PeerClient {
+    pub client_id: ClientId,
+}
+
+/// Credentials, with which a client opens the stream of a peer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum StreamIdentity {
+    /// Authentication is disabled, so the claimed peer ID is trusted.
+    Unauthenticated,
+    /// Authenticated by the client certificate of a peer.
+    Certificate(AuthenticatedPeer),
+    /// Authenticated by an access token, which was issued to the given OIDC client, if the token names one.
+    Client(Option<ClientId>),
+}
+
+impl StreamIdentity {
+    pub fn of<T>(request: &Request<T>) -> Self {
+        if let Some(peer) = request.extensions().get::<AuthenticatedPeer>() {
+            return StreamIdentity::Certificate(Clone::clone(peer));
+        }
+        match request.extensions().get::<CurrentUser>() {
+            None => StreamIdentity::Unauthenticated,
+            Some(user) => StreamIdentity::Client(
+                user.claims.as_ref()
+                    .and_then(|claims| claims.authorized_party())
+                    .map(|client_id| ClientId::from(client_id.as_str()))
+            ),
+        }
+    }
+}
+
+/// Checks that the credentials of a client belong to the peer, whose stream it opens.
+/// Client certificates are accepted, unless they were revoked, since only CARL issues them.
+/// If no OIDC client was recorded for the peer, e.g. because it was set up before CARL restarted,
+/// the clients registered for the peer's resource ID at the identity provider are looked up and the match is recorded.
+pub async fn verify_stream_identity(
+    identity: StreamIdentity,
+    peer_id: PeerId,
+    resources_manager: &ResourcesManagerRef,
+    peer_certificates: &PeerCertificates,
+    oidc_registration_client: Option<&RegistrationClientRef>,
+) -> Result<(), PeerIdentityError> {
+
+    let client_id = match identity {
+        StreamIdentity::Unauthenticated => return Ok(()),
+        StreamIdentity::Certificate(AuthenticatedPeer { peer_id: authenticated_peer_id, serial }) => {
+            if authenticated_peer_id != peer_id {
+                return Err(PeerIdentityError::CertificateMismatch { authenticated_peer_id, peer_id });
+            }
+            return if peer_certificates.is_revoked(&serial) {
+                Err(PeerIdentityError::CertificateRevoked { serial, peer_id })
+            } else {
+                Ok(())
+            };
+        }
+        StreamIdentity::Client(None) => return Err(PeerIdentityError::UnknownClient { peer_id }),
+        StreamIdentity::Client(Some(client_id)) => client_id,
+    };
+
+    if let Some(PeerClient { client_id: recorded_client_id }) = resources_manager.get::<PeerClient>(peer_id).await {
+        return if recorded_client_id == client_id {
+            Ok(())
+        } else {
+            Err(PeerIdentityError::ClientMismatch { client_id, peer_id })
+        };
+    }
+
+    let Some(registration_client) = oidc_registration_client else {
+        return Err(PeerIdentityError::ClientMismatch { client_id, peer_id });
+    };
+
+    let registered = registration_client.list_clients().await
+        .map_err(|cause| PeerIdentityError::Internal { peer_id, cause: cause.to_string() })?
+        .filter_clients_by_resource_id(peer_id.into())
+        .into_iter()
+        .any(|client| client.client_id == client_id.0);
+
+    if registered {
+        debug!("Recording OIDC client '{}' of peer <{peer_id}>, which is registered at the identity provider.", client_id.0);
+        resources_manager.insert(peer_id, PeerClient { client_id }).await;
+        Ok(())
+    } else {
+        Err(PeerIdentityError::ClientMismatch { client_id, peer_id })
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum PeerIdentityError {
+    #[error("Client certificate belongs to peer <{authenticated_peer_id}>, not to peer <{peer_id}>.")]
+    CertificateMismatch { authenticated_peer_id: PeerId, peer_id: PeerId },
+    #[error("Client certificate with serial '{}' of peer <{peer_id}> was revoked.", .serial.0)]
+    CertificateRevoked { serial: CertificateSerial, peer_id: PeerId },
+    #[error("OIDC client '{}' was not registered for peer <{peer_id}>.", .client_id.0)]
+    ClientMismatch { client_id: ClientId, peer_id: PeerId },
+    #[error("Access token does not name the OIDC client, which it was issued to, so it cannot open the stream of peer <{peer_id}>.")]
+    UnknownClient { peer_id: PeerId },
+    #[error("Failed to verify the identity of peer <{peer_id}>:\n  {cause}")]
+    Internal { peer_id: PeerId, cause: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use opendut_auth_tests::registration_client;
+    use rstest::rstest;
+
+    use crate::resources::manager::ResourcesManager;
+
+    use super::*;
+
+    fn certificate(peer_id: PeerId, serial: &str) -> StreamIdentity {
+        StreamIdentity::Certificate(AuthenticatedPeer { peer_id, serial: CertificateSerial(String::from(serial)) })
+    }
+
+    #[tokio::test]
+    async fn should_only_open_the_stream_of_the_peer_of_the_client_certificate() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let peer_certificates = PeerCertificates::default();
+        let peer_id = PeerId::random();
+        peer_certificates.issue(peer_id, CertificateSerial(String::from("01"))).await?;
+
+        verify_stream_identity(certificate(peer_id, "01"), peer_id, &resources_manager, &peer_certificates, None).await?;
+
+        let result = verify_stream_identity(certificate(PeerId::random(), "01"), peer_id, &resources_manager, &peer_certificates, None).await;
+        assert!(matches!(result, Err(PeerIdentityError::CertificateMismatch { .. })));
+
+        verify_stream_identity(StreamIdentity::Unauthenticated, peer_id, &resources_manager, &peer_certificates, None).await?;
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_reject_client_certificates_which_were_replaced_or_revoked() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let peer_certificates = PeerCertificates::default();
+        let peer_id = PeerId::random();
+        peer_certificates.issue(peer_id, CertificateSerial(String::from("01"))).await?;
+        peer_certificates.issue(peer_id, CertificateSerial(String::from("02"))).await?;
+
+        let result = verify_stream_identity(certificate(peer_id, "01"), peer_id, &resources_manager, &peer_certificates, None).await;
+        assert!(matches!(result, Err(PeerIdentityError::CertificateRevoked { .. })));
+
+        verify_stream_identity(certificate(peer_id, "02"), peer_id, &resources_manager, &peer_certificates, None).await?;
+
+        peer_certificates.revoke(peer_id).await?;
+
+        let result = verify_stream_identity(certificate(peer_id, "02"), peer_id, &resources_manager, &peer_certificates, None).await;
+        assert!(matches!(result, Err(PeerIdentityError::CertificateRevoked { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_keep_accepting_issued_and_rejecting_revoked_certificates_after_a_restart() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        let file = directory.path().join("peer-certificates");
+        let peer_id = PeerId::random();
+        let revoked_peer_id = PeerId::random();
+
+        let peer_certificates = PeerCertificates::load_from(Clone::clone(&file))?;
+        peer_certificates.issue(peer_id, CertificateSerial(String::from("01"))).await?;
+        peer_certificates.issue(revoked_peer_id, CertificateSerial(String::from("02"))).await?;
+        peer_certificates.revoke(revoked_peer_id).await?;
+
+        let resources_manager = ResourcesManager::new();
+        let peer_certificates = PeerCertificates::load_from(file)?;
+
+        verify_stream_identity(certificate(peer_id, "01"), peer_id, &resources_manager, &peer_certificates, None).await?;
+
+        let result = verify_stream_identity(certificate(revoked_peer_id, "02"), revoked_peer_id, &resources_manager, &peer_certificates, None).await;
+        assert!(matches!(result, Err(PeerIdentityError::CertificateRevoked { .. })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_only_open_the_stream_with_the_recorded_client_of_the_peer() -> anyhow::Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let peer_certificates = PeerCertificates::default();
+        let peer_id = PeerId::random();
+        let other_peer_id = PeerId::random();
+        resources_manager.insert(peer_id, PeerClient { client_id: ClientId::from("edgar-123") }).await;
+
+        verify_stream_identity(StreamIdentity::Client(Some(ClientId::from("edgar-123"))), peer_id, &resources_manager, &peer_certificates, None).await?;
+
+        let result = verify_stream_identity(StreamIdentity::Client(Some(ClientId::from("edgar-123"))), other_peer_id, &resources_manager, &peer_certificates, None).await;
+        assert!(matches!(result, Err(PeerIdentityError::ClientMismatch { .. })));
+
+        let result = verify_stream_identity(StreamIdentity::Client(Some(ClientId::from("edgar-456"))), peer_id, &resources_manager, &peer_certificates, None).await;
+        assert!(matches!(result, Err(PeerIdentityError::ClientMismatch { .. })));
+
+        let result = verify_stream_identity(StreamIdentity::Client(None), peer_id, &resources_manager, &peer_certificates, None).await;
+        assert!(matches!(result, Err(PeerIdentityError::UnknownClient { .. })));
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    #[ignore]
+    async fn should_look_up_the_client_registered_for_the_peer(#[future] registration_client: RegistrationClientRef) -> anyhow::Result<()> {
+        /*
+         * This test is ignored because it requires a running keycloak server from the test environment.
+         * To run this test, execute the following command: cargo test -- --include-ignored
+         */
+        let registration_client = registration_client.await;
+        let resources_manager = ResourcesManager::new();
+        let peer_certificates = PeerCertificates::default();
+        let peer_id = PeerId::random();
+        let other_peer_id = PeerId::random();
+
+        let credentials = registration_client.register_new_client_for_user(peer_id.into(), String::from("peerIdentityTest")).await?;
+        let other_credentials = registration_client.register_new_client_for_user(other_peer_id.into(), String::from("peerIdentityTest")).await?;
+
+        let result = verify_stream_identity(StreamIdentity::Client(Some(Clone::clone(&other_credentials.client_id))), peer_id, &resources_manager, &peer_certificates, Some(&registration_client)).await;
+        assert!(matches!(result, Err(PeerIdentityError::ClientMismatch { .. })));
+
+        verify_stream_identity(StreamIdentity::Client(Some(Clone::clone(&credentials.client_id))), peer_id, &resources_manager, &peer_certificates, Some(&registration_client)).await?;
+        assert_eq!(resources_manager.get::<PeerClient>(peer_id).await, Some(PeerClient { client_id: credentials.client_id }));
+
+        registration_client.delete_client_by_resource_id(peer_id.into()).await?;
+        registration_client.delete_client_by_resource_id(other_peer_id.into()).await?;
+        Ok(())
+    }
+}
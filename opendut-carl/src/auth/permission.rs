@@ -0,0 +1,223 @@
+use std::fmt;
+
+use tonic::{Request, Status};
+
+use crate::auth::{CurrentUser, MyAdditionalClaims};
+use crate::auth::client_certificate::AuthenticatedPeer;
+
+/// Roles of openDuT users, each of which includes the permissions of the roles before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Role {
+    /// May read clusters, peers, executor runs, logs and results.
+    Viewer,
+    /// May additionally configure and deploy clusters, manage CAN databases and fault injection rules and control executors.
+    Operator,
+    /// May additionally create and delete peers, generate setup strings and manage secrets.
+    Admin,
+}
+
+impl Role {
+    const ALL: [Role; 3] = [Role::Viewer, Role::Operator, Role::Admin];
+
+    fn config_key(&self) -> &'static str {
+        match self {
+            Role::Viewer => "viewer",
+            Role::Operator => "operator",
+            Role::Admin => "admin",
+        }
+    }
+}
+
+impl fmt::Display for Role {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.config_key())
+    }
+}
+
+/// Determines the [`Role`] of a user from the `roles` and `groups` claims of their access token.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RoleMapping {
+    /// Role of users, whose claims match none of the mappings.
+    pub default: Option<Role>,
+    pub mappings: Vec<(Role, RoleClaims)>,
+}
+
+/// OIDC roles and groups, of which a user needs at least one to be granted a [`Role`].
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct RoleClaims {
+    pub roles: Vec<String>,
+    pub groups: Vec<String>,
+}
+
+const CONFIG_PREFIX: &str = "network.oidc.authorization";
+
+impl RoleMapping {
+
+    pub fn load(config: &config::Config) -> Result<Self, RoleMappingLoadError> {
+        let default = config.get_string(&format!("{CONFIG_PREFIX}.default.role"))
+            .map_err(|cause| RoleMappingLoadError { message: cause.to_string() })?;
+
+        let default = match default.as_str() {
+            "" | "none" => None,
+            role => Some(
+                Role::ALL.into_iter()
+                    .find(|candidate| candidate.config_key() == role)
+                    .ok_or_else(|| RoleMappingLoadError { message: format!("Unknown default role '{role}'. Expected 'none', 'viewer', 'operator' or 'admin'.") })?
+            ),
+        };
+
+        let mappings = Role::ALL.into_iter()
+            .map(|role| {
+                let claims = RoleClaims {
+                    roles: load_list(config, &format!("{CONFIG_PREFIX}.{}.roles", role.config_key()))
+                        .map_err(|message| RoleMappingLoadError { message })?,
+                    groups: load_list(config, &format!("{CONFIG_PREFIX}.{}.groups", role.config_key()))
+                        .map_err(|message| RoleMappingLoadError { message })?,
+                };
+                Ok((role, claims))
+            })
+            .collect::<Result<Vec<_>, RoleMappingLoadError>>()?;
+
+        Ok(Self { default, mappings })
+    }
+
+    /// Returns the highest role, which the claims are mapped to, or the default role.
+    pub fn role_of(&self, claims: &MyAdditionalClaims) -> Option<Role> {
+        self.mappings.iter()
+            .filter(|(_, required)| {
+                required.roles.iter().any(|role| claims.roles.contains(role))
+                    || required.groups.iter().any(|group| claims.groups.contains(group))
+            })
+            .map(|(role, _)| *role)
+            .max()
+            .or(self.default)
+    }
+}
+
+/// Lists can be configured as TOML arrays or, e.g. in environment variables, as comma-separated strings.
+pub(crate) fn load_list(config: &config::Config, key: &str) -> Result<Vec<String>, String> {
+    let values = match config.get_array(key) {
+        Ok(values) => values.into_iter()
+            .map(|value| value.into_string())
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|cause| format!("Invalid value of '{key}': {cause}"))?,
+        Err(config::ConfigError::NotFound(_)) => Vec::new(),
+        Err(_) => {
+            let value = config.get_string(key)
+                .map_err(|cause| format!("Invalid value of '{key}': {cause}"))?;
+            value.split(',')
+                .map(str::trim)
+                .filter(|value| !value.is_empty())
+                .map(String::from)
+                .collect()
+        }
+    };
+    Ok(values)
+}
+
+#[derive(thiserror::Error, Debug)]
+#[error("Error while loading the role mapping from '{CONFIG_PREFIX}': {message}")]
+pub struct RoleMappingLoadError {
+    message: String,
+}
+
+/// Role of the authenticated user, which the authentication layer attaches to each request.
+#[derive(Clone, Debug)]
+pub struct CurrentRole(pub Option<Role>);
+
+/// Checks that the user of the request has at least the `required` role.
+/// Requests without an authenticated user are permitted, since they only pass the authentication layer, if OIDC is disabled.
+/// Peers, which authenticated with a client certificate, have no role and may only open their stream.
+pub fn require_role<T>(request: &Request<T>, required: Role) -> Result<(), Status> {
+    if let Some(AuthenticatedPeer { peer_id, .. }) = request.extensions().get::<AuthenticatedPeer>() {
+        return Err(Status::permission_denied(format!("CARL says, peer <{peer_id}> authenticated with a client certificate, but the role '{required}' is required.")));
+    }
+    let Some(user) = request.extensions().get::<CurrentUser>() else {
+        return Ok(());
+    };
+    let role = request.extensions().get::<CurrentRole>()
+        .and_then(|CurrentRole(role)| *role);
+
+    match role {
+        Some(role) if role >= required => Ok(()),
+        Some(role) => Err(Status::permission_denied(format!("CARL says, user '{}' has the role '{role}', but the role '{required}' is required.", user.name))),
+        None => Err(Status::permission_denied(format!("CARL says, user '{}' has no role, but the role '{required}' is required.", user.name))),
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use crate::auth::client_certificate::CertificateSerial;
+
+    use super::*;
+
+    fn claims(roles: &[&str], groups: &[&str]) -> MyAdditionalClaims {
+        MyAdditionalClaims {
+            roles: roles.iter().map(|role| role.to_string()).collect(),
+            groups: groups.iter().map(|group| group.to_string()).collect(),
+            scope: None,
+        }
+    }
+
+    #[test]
+    fn should_load_role_mapping_from_arrays_and_comma_separated_strings() -> anyhow::Result<()> {
+        let config = config::Config::builder()
+            .add_source(config::File::from_str(r#"
+                [network.oidc.authorization]
+                default.role = "none"
+                viewer.roles = []
+                operator.roles = ["opendut-operator"]
+                operator.groups = "/operators, /testers"
+                admin.roles = ["opendut-admin"]
+                admin.groups = []
+            "#, config::FileFormat::Toml))
+            .build()?;
+
+        let mapping = RoleMapping::load(&config)?;
+
+        assert_that!(mapping, eq(RoleMapping {
+            default: None,
+            mappings: vec![
+                (Role::Viewer, RoleClaims::default()),
+                (Role::Operator, RoleClaims { roles: vec![String::from("opendut-operator")], groups: vec![String::from("/operators"), String::from("/testers")] }),
+                (Role::Admin, RoleClaims { roles: vec![String::from("opendut-admin")], groups: vec![] }),
+            ],
+        }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_map_claims_to_the_highest_matching_role() {
+        let mapping = RoleMapping {
+            default: Some(Role::Viewer),
+            mappings: vec![
+                (Role::Operator, RoleClaims { roles: vec![String::from("testrole")], groups: vec![String::from("/testgroup")] }),
+                (Role::Admin, RoleClaims { roles: vec![String::from("opendut-admin")], groups: vec![] }),
+            ],
+        };
+
+        assert_eq!(mapping.role_of(&claims(&["offline_access"], &[])), Some(Role::Viewer));
+        assert_eq!(mapping.role_of(&claims(&[], &["/testgroup"])), Some(Role::Operator));
+        assert_eq!(mapping.role_of(&claims(&["testrole", "opendut-admin"], &[])), Some(Role::Admin));
+
+        let mapping = RoleMapping { default: None, ..mapping };
+        assert_eq!(mapping.role_of(&claims(&["offline_access"], &["/othergroup"])), None);
+    }
+
+    #[test]
+    fn should_deny_peers_authenticated_with_a_client_certificate() {
+        let mut request = Request::new(());
+        assert!(require_role(&request, Role::Viewer).is_ok());
+
+        request.extensions_mut().insert(AuthenticatedPeer {
+            peer_id: opendut_types::peer::PeerId::random(),
+            serial: CertificateSerial(String::from("01")),
+        });
+        let status = require_role(&request, Role::Viewer).unwrap_err();
+        assert_that!(status.code(), eq(tonic::Code::PermissionDenied));
+    }
+}
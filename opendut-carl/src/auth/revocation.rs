@@ -0,0 +1,131 @@
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+
+use tokio::io::AsyncWriteExt;
+
+use opendut_types::util::net::ClientId;
+use opendut_util::project;
+
+use crate::auth::CurrentUser;
+
+/// OIDC clients, whose credentials were revoked. Access tokens, which were issued to them before the revocation,
+/// remain valid at the identity provider until they expire, so CARL rejects them itself.
+/// The revoked clients are appended to a file, so that they remain rejected after CARL restarts.
+#[derive(Clone, Debug, Default)]
+pub struct RevokedClients {
+    client_ids: Arc<RwLock<HashSet<String>>>,
+    /// `None`, if the revoked clients are only kept in memory.
+    file: Option<PathBuf>,
+}
+
+impl RevokedClients {
+    pub fn load(config: &config::Config) -> Result<Self, RevokedClientsError> {
+        let file = project::make_path_absolute(config.get_string("auth.revoked.clients.file")
+            .map_err(|cause| RevokedClientsError::Configuration { cause: cause.to_string() })?)
+            .map_err(|cause| RevokedClientsError::Configuration { cause: cause.to_string() })?;
+        Self::load_from(file)
+    }
+
+    fn load_from(file: PathBuf) -> Result<Self, RevokedClientsError> {
+        let client_ids = match fs::read_to_string(&file) {
+            Ok(content) => content.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty())
+                .map(ToOwned::to_owned)
+                .collect(),
+            Err(cause) if cause.kind() == io::ErrorKind::NotFound => HashSet::new(),
+            Err(cause) => return Err(RevokedClientsError::Read { path: file, cause }),
+        };
+
+        Ok(Self {
+            client_ids: Arc::new(RwLock::new(client_ids)),
+            file: Some(file),
+        })
+    }
+
+    pub async fn revoke(&self, client_id: ClientId) -> Result<(), RevokedClientsError> {
+        let client_id = client_id.value();
+
+        let newly_revoked = self.client_ids.write().expect("Lock of revoked clients should not be poisoned.")
+            .insert(Clone::clone(&client_id));
+
+        if let (true, Some(file)) = (newly_revoked, &self.file) {
+            Self::append(file, &client_id).await
+                .map_err(|cause| RevokedClientsError::Write { path: Clone::clone(file), cause })?;
+        }
+        Ok(())
+    }
+
+    async fn append(file: &Path, client_id: &str) -> io::Result<()> {
+        if let Some(parent) = file.parent() {
+            tokio::fs::create_dir_all(parent).await?;
+        }
+        let mut file = tokio::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(file).await?;
+        file.write_all(format!("{client_id}\n").as_bytes()).await?;
+        file.flush().await
+    }
+
+    pub fn is_revoked(&self, client_id: &str) -> bool {
+        self.client_ids.read().expect("Lock of revoked clients should not be poisoned.")
+            .contains(client_id)
+    }
+
+    /// Checks the authorized party of the user's token, which is the OIDC client the token was issued to.
+    pub fn is_revoked_for(&self, user: &CurrentUser) -> bool {
+        user.claims.as_ref()
+            .and_then(|claims| claims.authorized_party())
+            .is_some_and(|client_id| self.is_revoked(client_id.as_str()))
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum RevokedClientsError {
+    #[error("Invalid configuration for the file of revoked clients:\n  {cause}")]
+    Configuration { cause: String },
+    #[error("Failed to read revoked clients from '{}':\n  {cause}", path.display())]
+    Read { path: PathBuf, cause: io::Error },
+    #[error("Failed to write revoked client to '{}':\n  {cause}", path.display())]
+    Write { path: PathBuf, cause: io::Error },
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_remember_revoked_clients_across_clones() -> anyhow::Result<()> {
+        let revoked_clients = RevokedClients::default();
+        let shared = Clone::clone(&revoked_clients);
+
+        revoked_clients.revoke(ClientId::from("edgar-123")).await?;
+
+        assert!(shared.is_revoked("edgar-123"));
+        assert!(!shared.is_revoked("edgar-456"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_remember_revoked_clients_after_reloading_the_file() -> anyhow::Result<()> {
+        let directory = tempfile::tempdir()?;
+        let file = directory.path().join("auth").join("revoked-clients");
+
+        let revoked_clients = RevokedClients::load_from(Clone::clone(&file))?;
+        revoked_clients.revoke(ClientId::from("edgar-123")).await?;
+        revoked_clients.revoke(ClientId::from("edgar-123")).await?;
+        revoked_clients.revoke(ClientId::from("cleo-456")).await?;
+
+        let reloaded = RevokedClients::load_from(Clone::clone(&file))?;
+        assert!(reloaded.is_revoked("edgar-123"));
+        assert!(reloaded.is_revoked("cleo-456"));
+        assert!(!reloaded.is_revoked("edgar-789"));
+
+        assert_eq!(fs::read_to_string(&file)?.lines().count(), 2);
+        Ok(())
+    }
+}
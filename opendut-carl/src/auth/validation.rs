@@ -1,13 +1,17 @@
-use std::collections::BTreeMap;
-use chrono::{Duration, Utc};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+
+use anyhow::Context;
 use jsonwebtoken::{Algorithm, DecodingKey, Validation};
 use serde::{Deserialize, Serialize};
-use crate::auth::{Claims, CurrentUser, MyAdditionalClaims};
-use crate::auth::json_web_key::{JsonWebKey, JwkCacheValue, OidcJsonWebKeySet};
 use url::Url;
-use crate::util::in_memory_cache::CustomInMemoryCache;
 
-pub const GIVEN_ISSUER_JWK_URL: &str = "protocol/openid-connect/certs";
+use crate::auth::{Claims, CurrentUser, MyAdditionalClaims};
+use crate::auth::issuer::{HttpIssuerRequester, IssuerKeyStore, IssuerRequester, TrustedIssuer};
+use crate::auth::json_web_key::JsonWebKey;
+use crate::auth::permission::load_list;
+
+const CONFIG_PREFIX: &str = "network.oidc.validation";
 
 #[derive(thiserror::Error, Debug, Clone, Deserialize, Serialize)]
 pub enum ValidationError {
@@ -17,288 +21,349 @@ pub enum ValidationError {
     InvalidAlgorithm(String),
     #[error("Fatal validation Error: {0}")]
     Failed(String),
-    #[error("Failed to process cache: {0}")]
-    Cache(String),
+    #[error("Access token was issued by the untrusted issuer '{0}'")]
+    UntrustedIssuer(String),
+    #[error("Unknown key id '{key_id}' of issuer '{issuer}'")]
+    UnknownKey { key_id: String, issuer: String },
+    #[error("Access token lacks the required scope '{0}'")]
+    MissingScope(String),
 }
 
-pub fn validate_token(issuer_remote_url: Url, access_token: &str, jwk: JsonWebKey, validate_expiration: bool) -> Result<CurrentUser, ValidationError> {
-    let mut validation = Validation::new(Algorithm::RS256);
-    validation.set_issuer(&[issuer_remote_url.as_str().trim_end_matches('/')]);
-    // TODO: evaluate if account audience is appropriate
-    validation.set_audience(&["account".to_string()]);
-    validation.validate_exp = validate_expiration;
+/// Checks, which CARL applies to access tokens in addition to their signature.
+#[derive(Clone, Debug)]
+pub struct JwtValidationOptions {
+    pub issuers: Vec<TrustedIssuer>,
+    /// Audiences, of which an access token has to name at least one in its `aud` claim. Not checked, if empty.
+    pub audiences: Vec<String>,
+    /// Scopes, which an access token has to contain in its `scope` claim.
+    pub required_scopes: Vec<String>,
+    pub validate_expiration: bool,
+    /// Interval, in which the signing keys of the trusted issuers are refreshed in the background.
+    pub key_refresh_interval: Duration,
+    /// Age, after which signing keys are no longer used, if they could not be refreshed in the meantime.
+    pub key_max_age: Duration,
+}
 
-    let decoding_key = DecodingKey::from_rsa_components(&jwk.modulus, &jwk.exponent).expect("failed to create decoding key");
+impl JwtValidationOptions {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let issuer_url = Url::parse(&config.get_string("network.oidc.client.issuer.url")?)
+            .context("Invalid value of 'network.oidc.client.issuer.url'.")?;
+        let issuer_remote_url = Url::parse(&config.get_string("network.oidc.client.issuer.remote.url")?)
+            .context("Invalid value of 'network.oidc.client.issuer.remote.url'.")?;
+
+        let mut issuers = vec![
+            TrustedIssuer::new(issuer_url).also_named(&issuer_remote_url)
+        ];
+        for url in load_list(config, &format!("{CONFIG_PREFIX}.additional.issuers")).map_err(anyhow::Error::msg)? {
+            let url = Url::parse(&url)
+                .with_context(|| format!("Invalid URL of additional issuer '{url}'."))?;
+            issuers.push(TrustedIssuer::new(url));
+        }
 
-    let token = jsonwebtoken::decode::<Claims<MyAdditionalClaims>>(access_token, &decoding_key, &validation)
-        .map_err(|err| ValidationError::Failed(format!("failed to decode token: {}", err)))?;
+        let audiences = load_list(config, &format!("{CONFIG_PREFIX}.audiences")).map_err(anyhow::Error::msg)?;
+        let required_scopes = load_list(config, &format!("{CONFIG_PREFIX}.required.scopes")).map_err(anyhow::Error::msg)?;
+
+        let key_refresh_interval = Duration::from_millis(
+            config.get::<u64>(&format!("{CONFIG_PREFIX}.jwks.refresh.interval.ms"))?
+        );
+        let key_max_age = Duration::from_millis(
+            config.get::<u64>(&format!("{CONFIG_PREFIX}.jwks.max.age.ms"))?
+        );
+
+        Ok(JwtValidationOptions {
+            issuers,
+            audiences,
+            required_scopes,
+            validate_expiration: true,
+            key_refresh_interval,
+            key_max_age,
+        })
+    }
+}
 
-    let username = match token.claims.preferred_username() {
-        None => { return Err(ValidationError::Configuration("Missing preferred username".to_string())); }
-        Some(username) => { username.to_string() }
-    };
+pub type JwtValidatorRef = Arc<JwtValidator<HttpIssuerRequester>>;
 
-    Ok(CurrentUser {
-        name: username,
-        claims: token.claims,
-    })
+/// Validates access tokens offline with the signing keys of the trusted issuers.
+#[derive(Debug)]
+pub struct JwtValidator<R> {
+    options: JwtValidationOptions,
+    keys: IssuerKeyStore<R>,
 }
 
-pub async fn authorize_user(issuer_url: Url, issuer_remote_url: Url, access_token: &str, cache: CustomInMemoryCache<String, JwkCacheValue>, jwk_requester: impl JwkRequester, token_validate_expiration: bool) -> Result<CurrentUser, ValidationError> {
-    
-    // TODO:  additional DDOS prevention? token contains unknown kid -> refetch jwk, kid still unknown, malicious DDOS?, blacklist kid?
+impl<R: IssuerRequester> JwtValidator<R> {
+    pub fn new(options: JwtValidationOptions, requester: R) -> Self {
+        let keys = IssuerKeyStore::new(Clone::clone(&options.issuers), requester, options.key_max_age);
+        Self { options, keys }
+    }
 
-    let key_id = get_key_id(access_token)?;
+    pub async fn authorize_user(&self, access_token: &str, now: SystemTime) -> Result<CurrentUser, ValidationError> {
+        let (algorithm, key_id) = get_key_id(access_token)?;
 
-    let jwk = match cache.get(&key_id) {
-        Ok(Some(jwk_cache_value)) => {
-            let timestamp_now = Utc::now().timestamp();
-            if timestamp_now - jwk_cache_value.last_cached > Duration::days(1).num_seconds() {
-                fetch_and_cache_jwk_from_idp(&issuer_url, key_id, jwk_requester, cache).await?
-            } else {
-                jwk_cache_value.jwk
-            }
-        }
-        Ok(None) => {
-            fetch_and_cache_jwk_from_idp(&issuer_url, key_id, jwk_requester, cache).await?
-        }
-        Err(error) => {
-            return Err(ValidationError::Cache(format!("Failed to get cache entry: {}", error)));
-        }
-    };
+        // the issuer determines the key to verify the signature with, so it can only be checked afterwards
+        let issuer_name = get_unverified_issuer(access_token)?;
+        let issuer = self.keys.issuer_named(&issuer_name)
+            .ok_or(ValidationError::UntrustedIssuer(issuer_name))?;
 
-    let validate_with_remote_issuer = validate_token(issuer_remote_url, access_token, jwk.clone(), token_validate_expiration);
-    match validate_with_remote_issuer {
-        Ok(user) => {
-            Ok(user)
-        }
-        Err(error) => {
-            // Fallback for dev/test environment
-            if error.to_string().contains("InvalidIssuer") {
-                validate_token(issuer_url, access_token, jwk, token_validate_expiration)
-            } else {
-                Err(error)
-            }
+        let jwk = self.keys.key(issuer, &key_id, now).await?;
+
+        validate_token(issuer, access_token, &jwk, algorithm, &self.options)
+    }
+
+    /// Fetches the signing keys of all trusted issuers in the configured interval, starting immediately.
+    pub async fn refresh_keys_periodically(&self) {
+        let mut interval = tokio::time::interval(self.options.key_refresh_interval);
+        loop {
+            interval.tick().await;
+            self.keys.refresh_all(SystemTime::now()).await;
         }
     }
 }
 
-async fn fetch_and_cache_jwk_from_idp(issuer_url: &Url, key_id: String, jwk_requester: impl JwkRequester, mut cache: CustomInMemoryCache<String, JwkCacheValue>) -> Result<JsonWebKey, ValidationError> {
-    let issuer_jwk_url = issuer_url.join(GIVEN_ISSUER_JWK_URL)
-        .map_err(|cause| ValidationError::Configuration(format!("Issuer JWK error: {cause}")))?;
+pub fn validate_token(issuer: &TrustedIssuer, access_token: &str, jwk: &JsonWebKey, algorithm: Algorithm, options: &JwtValidationOptions) -> Result<CurrentUser, ValidationError> {
+    let mut validation = Validation::new(algorithm);
+    validation.set_issuer(&issuer.names);
+    if options.audiences.is_empty() {
+        validation.validate_aud = false;
+    } else {
+        validation.set_audience(&options.audiences);
+    }
+    validation.validate_exp = options.validate_expiration;
 
-    let jwk_map = fetch_jwk_custom(issuer_jwk_url, jwk_requester).await?;
+    let decoding_key = DecodingKey::from_rsa_components(&jwk.modulus, &jwk.exponent)
+        .map_err(|cause| ValidationError::Configuration(format!("Failed to create decoding key from key id '{}': {cause}", jwk.key_identifier)))?;
 
-    if let Err(error) = cache.delete_all() {
-        return Err(ValidationError::Cache(format!("Failed to evict cache: {}", error)));
+    let token = jsonwebtoken::decode::<Claims<MyAdditionalClaims>>(access_token, &decoding_key, &validation)
+        .map_err(|err| ValidationError::Failed(format!("failed to decode token: {}", err)))?;
+
+    let scopes = token.claims.additional_claims().scope.as_deref().unwrap_or_default();
+    if let Some(missing) = options.required_scopes.iter().find(|required| !scopes.split(' ').any(|scope| scope == required.as_str())) {
+        return Err(ValidationError::MissingScope(Clone::clone(missing)));
     }
 
-    for (kid, jwk) in &jwk_map {
-        let jwk_cache_value = JwkCacheValue {
-            jwk: Clone::clone(jwk),
-            last_cached: Utc::now().timestamp()
-        };
-        if let Err(error) = cache.insert(String::from(kid), jwk_cache_value) {
-            return Err(ValidationError::Cache(format!("Failed to store jwk: {}", error)));
-        }
+    let username = match token.claims.preferred_username() {
+        None => { return Err(ValidationError::Configuration("Missing preferred username".to_string())); }
+        Some(username) => { username.to_string() }
     };
 
-    let json_web_key = match jwk_map.get(&key_id) {
-        None => { return Err(ValidationError::InvalidAlgorithm(format!("Could not find key id: {}", key_id))); }
-        Some(jwk) => { jwk.clone() }
-    };
-    Ok(json_web_key)
+    Ok(CurrentUser {
+        name: username,
+        groups: Clone::clone(&token.claims.additional_claims().groups),
+        claims: Some(token.claims),
+    })
 }
 
-fn get_key_id(access_token: &str) -> Result<String, ValidationError> {
+fn get_key_id(access_token: &str) -> Result<(Algorithm, String), ValidationError> {
     let header = jsonwebtoken::decode_header(access_token)
         .map_err(|error| ValidationError::Configuration(format!("Failed to decode header: {}", error)))?;
 
-    // TODO: valid algorithms are determined by the issuer (fetch keycloak certificates / jwk)
+    // only RSA keys are taken from the key sets of the issuers
     match header.alg {
-        Algorithm::RS256 => {}
+        Algorithm::RS256 | Algorithm::RS384 | Algorithm::RS512 => {}
         _ => { return Err(ValidationError::InvalidAlgorithm(format!("Could not handle algorithm: {:?}", header.alg))); }
     }
 
     match header.kid {
         None => { Err(ValidationError::InvalidAlgorithm("Missing key id".to_string())) }
-        Some(kid) => { Ok(kid) }
+        Some(kid) => { Ok((header.alg, kid)) }
     }
 }
 
-async fn fetch_jwk_custom(issuer_jwk_url: Url, jwk_requester: impl JwkRequester) -> Result<BTreeMap<String, JsonWebKey>, ValidationError> {
-    let result = jwk_requester.fetch_jwk(issuer_jwk_url).await?;
-    let json_web_key_set = serde_json::from_str::<OidcJsonWebKeySet>(result.as_str())
-        .map_err(|cause| ValidationError::Configuration(format!("Failed to parse json: {}", cause)))?;
-    let jwk_map = json_web_key_set.keys.into_iter().map(|jwk| {
-        (jwk.key_identifier.clone(), jwk)
-    }).collect::<BTreeMap<_, _>>();
-    Ok(jwk_map)
+#[derive(Deserialize)]
+struct UnverifiedIssuer {
+    iss: String,
 }
 
-pub trait JwkRequester {
-    async fn fetch_jwk(&self, issuer_jwk_url: Url) -> Result<String, ValidationError>;
-}
+fn get_unverified_issuer(access_token: &str) -> Result<String, ValidationError> {
+    let mut validation = Validation::default();
+    validation.insecure_disable_signature_validation();
+    validation.required_spec_claims.clear();
+    validation.validate_exp = false;
+    validation.validate_aud = false;
 
-pub struct Jwk;
-
-impl JwkRequester for Jwk {
-    async fn fetch_jwk(&self, issuer_jwk_url: Url) -> Result<String, ValidationError> {
-        let response = reqwest::get(issuer_jwk_url.clone()).await.map_err(|cause| ValidationError::Configuration(format!("Failed to fetch IDP jwk URL from '{}': {}", issuer_jwk_url, cause)))?;
-        let result = response.text().await.map_err(|cause| ValidationError::Configuration(format!("Failed to read IDP configuration URL from '{}': {}", issuer_jwk_url, cause)))?;
-        Ok(result)
-    }
+    let token = jsonwebtoken::decode::<UnverifiedIssuer>(access_token, &DecodingKey::from_secret(&[]), &validation)
+        .map_err(|cause| ValidationError::Failed(format!("Failed to read issuer of token: {cause}")))?;
+    Ok(token.claims.iss)
 }
 
 
 #[cfg(test)]
 mod tests {
-    use std::collections::BTreeMap;
-    use std::ops::Sub;
-    use chrono::{Duration, Utc};
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::time::{Duration, SystemTime};
+
     use googletest::{assert_that};
     use googletest::matchers::{contains_substring};
+    use jsonwebtoken::Algorithm;
     use rstest::{fixture, rstest};
     use url::Url;
-    use crate::auth::json_web_key::{JsonWebKey, JwkCacheValue, OidcJsonWebKeySet};
-    use crate::util::in_memory_cache::CustomInMemoryCache;
-    use crate::auth::validation::{authorize_user, JwkRequester, validate_token, ValidationError};
+    use crate::auth::issuer::{IssuerRequester, TrustedIssuer};
+    use crate::auth::json_web_key::{JsonWebKey, OidcJsonWebKeySet};
+    use crate::auth::validation::{JwtValidationOptions, JwtValidator, validate_token, ValidationError};
 
     const KEY_ID: &str = "9RcB1okOXQ6QibEeXzAxFVym9PmBynkFe8mbh6X-DB0";
     const TEST_TOKEN: &str = "eyJhbGciOiJSUzI1NiIsInR5cCIgOiAiSldUIiwia2lkIiA6ICI5UmNCMW9rT1hRNlFpYkVlWHpBeEZWeW05UG1CeW5rRmU4bWJoNlgtREIwIn0.eyJleHAiOjE3MjE3MjUzMjgsImlhdCI6MTcyMTcyNTAyOCwiYXV0aF90aW1lIjoxNzIxNzI1MDI4LCJqdGkiOiJkNzI1ZjlkZi04OWQ5LTQwZGQtOGMzYi0yMzA2ZGUzNzkzODUiLCJpc3MiOiJodHRwczovL2tleWNsb2FrL3JlYWxtcy9vcGVuZHV0IiwiYXVkIjoiYWNjb3VudCIsInN1YiI6IjljNTBhOGU5LTRlZjYtNGE4Zi04ZDZlLWFkNjhiYjk4NGJhMSIsInR5cCI6IkJlYXJlciIsImF6cCI6Im9wZW5kdXQtbGVhLWNsaWVudCIsInNlc3Npb25fc3RhdGUiOiIzZGYxZGM5YS1jMjMzLTRiMWEtODdlYS1kMGYyOTVlMDBmNzUiLCJhY3IiOiIxIiwiYWxsb3dlZC1vcmlnaW5zIjpbIioiXSwicmVzb3VyY2VfYWNjZXNzIjp7ImFjY291bnQiOnsicm9sZXMiOlsibWFuYWdlLWFjY291bnQiLCJtYW5hZ2UtYWNjb3VudC1saW5rcyIsInZpZXctcHJvZmlsZSJdfX0sInNjb3BlIjoib3BlbmlkIGVtYWlsIHByb2ZpbGUgZ3JvdXBzIiwic2lkIjoiM2RmMWRjOWEtYzIzMy00YjFhLTg3ZWEtZDBmMjk1ZTAwZjc1IiwiZW1haWxfdmVyaWZpZWQiOmZhbHNlLCJyb2xlcyI6WyJvZmZsaW5lX2FjY2VzcyIsImRlZmF1bHQtcm9sZXMtb3BlbmR1dCIsInRlc3Ryb2xlIiwidW1hX2F1dGhvcml6YXRpb24iXSwibmFtZSI6IkZpcnN0bmFtZSBMYXN0bmFtZSIsImdyb3VwcyI6WyIvdGVzdGdyb3VwIl0sInByZWZlcnJlZF91c2VybmFtZSI6Im9wZW5kdXQiLCJnaXZlbl9uYW1lIjoiRmlyc3RuYW1lIiwiZmFtaWx5X25hbWUiOiJMYXN0bmFtZSIsImVtYWlsIjoib3BlbmR1dEBleGFtcGxlLmNvbSJ9.PLYTZ_v4GGM6YPZC_afI67eJ8U5sbV6aS2YbBDhmvNfhH-g-Sn_2NZImcPLxiz50_5pbRhhi8pnDnshbLHkxv2uEj1ltdPRmSCD4xqzlP7kDLn0kMVsBJHIeL5olj7zY8KjWJAieFH2oOZIiMiWRAsD9SAUSyr1tTNv38p6i0Pyy_Op-fDlF1zZel2adLke8j0Svb7H63OSsOTt8HES-sUIMd4VJDH3yb83OECFVBEieE3GRq_77BgtffzgXgJZAAA84ija7O-ao_raSoy1ycqykEqmdSu9X-dzw_YrjtroUBM7RS4hrI9iJ5pGwH_LESUd8L93xUX5yYEZeN-0r-g";
     const ISSUER_URL: &str  = "https://keycloak/realms/opendut/";
+    const OTHER_ISSUER_URL: &str  = "https://auth.example.com/";
     const JWK_RAW_DATA: &str = r#"{"keys":[{"kid":"9RcB1okOXQ6QibEeXzAxFVym9PmBynkFe8mbh6X-DB0","kty":"RSA","alg":"RS256","use":"sig","n":"jJTeGo90wWqXEk4JHRlPVF5hOXViKk5qnIlwiUAyx3CfBBuwSVEKVCq73TtuG57EQFca-o01SYKGGg-yU2VyleEDKbSGBzdl2LelrUwHCdSphupnIGPJ12wU8EDBgfOh0llWpNYTrEtNjbHLaYbMZL9_a7sXOTJxC6-S9EcpyhvI0LZHjOJe_YAnkj1Wx5OKWRZhiV5_y00SQI8xHinnOKLWH86giOBBJuN5Z-Ii3xNPF8jtHLdEXNw6cbeueaeU56Rlmy9AkuGdnQzBnP4hMRVul7Poam7iDD30Rl_qfH4yO-jhDnw1Mz4JALBPToaZ3WC6oXkfoGQo0Q4wmN3oNQ","e":"AQAB","x5c":["MIICnTCCAYUCBgGQgfqpwDANBgkqhkiG9w0BAQsFADASMRAwDgYDVQQDDAdvcGVuZHV0MB4XDTI0MDcwNTA4MTgyNloXDTM0MDcwNTA4MjAwNlowEjEQMA4GA1UEAwwHb3BlbmR1dDCCASIwDQYJKoZIhvcNAQEBBQADggEPADCCAQoCggEBAIyU3hqPdMFqlxJOCR0ZT1ReYTl1YipOapyJcIlAMsdwnwQbsElRClQqu907bhuexEBXGvqNNUmChhoPslNlcpXhAym0hgc3Zdi3pa1MBwnUqYbqZyBjyddsFPBAwYHzodJZVqTWE6xLTY2xy2mGzGS/f2u7FzkycQuvkvRHKcobyNC2R4ziXv2AJ5I9VseTilkWYYlef8tNEkCPMR4p5zii1h/OoIjgQSbjeWfiIt8TTxfI7Ry3RFzcOnG3rnmnlOekZZsvQJLhnZ0MwZz+ITEVbpez6Gpu4gw99EZf6nx+Mjvo4Q58NTM+CQCwT06Gmd1guqF5H6BkKNEOMJjd6DUCAwEAATANBgkqhkiG9w0BAQsFAAOCAQEAczQGabVZrMKsJNV+eoLcCUxzLv9tYRaFbLrT5+keotgl6YYfZ3W63wY9IaZp0wT5zKdG2meifJ48173VP/8/437A+t0zCkH2kfQY9sP3EXDKVbw8LuViaoVO2w3GoanRJP8BKSAMo3voRCnd6QAPCbaTIUM2M0bRl1RADRuAZXbWM8817Sk2w0qMkSyxDJY9JNRviUQBU0V4ziro9mB+pVIMJ/Z4anNGsTNL6D9HdI3/7iBuC7SLTVh8x/Yg0mYnud8WwRePOZuxDbA65V2lL3ixB4uhjq9yuo5F76c/TuyrFFUrXXmUMn5+0/OjRhHEKBZSUJHGvvQlgkjzkOcovg=="],"x5t":"pa3zfyZhNzSUhKHXzIn5QbOuFyA","x5t#S256":"v8an46MZ8wHfjnUW2fUGl5Xh602pXEC8Lb_p7EUSATg"},{"kid":"rSPOu3JnH_GrUFiekXboNx7s4xO816XM7Hb_F8bz8Y0","kty":"RSA","alg":"RSA-OAEP","use":"enc","n":"kKo_9nNiiLcImSd5xdNFEUEaQ6BFe9j__XOdEaFNMfa0zc-lu4J6wjyDEILR5HdgzQfaRlne66z4TwiJwyoyDRz7EqB75voagmsZn9UK8CGp4h27Tz7y1doPletRV3458PWPzy4epYAgsu-yEYVXTc8OT_XnXlnNAN4z1DpI-1Kk4uFS1zvRUiUvr8kzauJbPdA7LTKMU5vw5yfjATMZL3ZlhwNLnU82xqr4zqnMdrAeQewuGEXud8-IUHotTKCuM-KwkRjLRrIxYNMyM9h8UStOXpxlc8ARwyrjWGfFVbUPNlxossSzLP223OiCEBY_SEDF8d9gsl7NkSAJdOUE6w","e":"AQAB","x5c":["MIICnTCCAYUCBgGQgfqq2jANBgkqhkiG9w0BAQsFADASMRAwDgYDVQQDDAdvcGVuZHV0MB4XDTI0MDcwNTA4MTgyN1oXDTM0MDcwNTA4MjAwN1owEjEQMA4GA1UEAwwHb3BlbmR1dDCCASIwDQYJKoZIhvcNAQEBBQADggEPADCCAQoCggEBAJCqP/ZzYoi3CJknecXTRRFBGkOgRXvY//1znRGhTTH2tM3PpbuCesI8gxCC0eR3YM0H2kZZ3uus+E8IicMqMg0c+xKge+b6GoJrGZ/VCvAhqeIdu08+8tXaD5XrUVd+OfD1j88uHqWAILLvshGFV03PDk/1515ZzQDeM9Q6SPtSpOLhUtc70VIlL6/JM2riWz3QOy0yjFOb8Ocn4wEzGS92ZYcDS51PNsaq+M6pzHawHkHsLhhF7nfPiFB6LUygrjPisJEYy0ayMWDTMjPYfFErTl6cZXPAEcMq41hnxVW1DzZcaLLEsyz9ttzoghAWP0hAxfHfYLJezZEgCXTlBOsCAwEAATANBgkqhkiG9w0BAQsFAAOCAQEAQsLO8nRuRGl5YqV0IJaX4GDunc7EGfD4Gofl5NNtG3SojISC0lmO4EyZdFsXJmmWgzFkg1aO91jdcZyIaf6qBbj+GPtoBltA0+nSAcCTDvOsmV1J1Gymxm/CJLTBGqIrLwEXDBFyFpF2W7OE7XdXby+d/mYVkpCc0fHC854w+tOLdvEr4AYD/3JNK5VWd1RLI1CeZ7nJeLbDUR5UkGGb2Na3SXaEsWWwor2L9OAY4bWq9+gIom7ihaDvXMMpMHbQ7gis8Ku5ltK80PISW/9b+G1IxKNYy+euCr9ZWiIeEcKBt0/dKSvCcfhG0mShmliETgGAfAdZu0eqqhuxATAi9A=="],"x5t":"gfshQCGXfVblp5YrHiYSlYUto90","x5t#S256":"h53Q-c8zYde1UjhjhLZB1I5Q7tjX-t9bz7lE_fV6Bbg"}]}"#;
 
     #[rstest]
     fn test_validate_token(fixture: Fixture) {
-        let result = validate_token(fixture.issuer_url, TEST_TOKEN, fixture.jwk, false)
+        let result = validate_token(&fixture.issuer, TEST_TOKEN, &fixture.jwk, Algorithm::RS256, &fixture.options)
             .map_err(|err| println!("Failed to get current user: {:?}", err));
         assert!(result.is_ok());
     }
 
     #[rstest]
-    fn test_validate_expired_token(fixture: Fixture) {
-        let result = validate_token(fixture.issuer_url, TEST_TOKEN, fixture.jwk, true);
+    fn test_validate_expired_token(mut fixture: Fixture) {
+        fixture.options.validate_expiration = true;
+        let result = validate_token(&fixture.issuer, TEST_TOKEN, &fixture.jwk, Algorithm::RS256, &fixture.options);
         assert!(result.is_err());
         assert_that!(result.err().unwrap().to_string(), contains_substring("ExpiredSignature"));
     }
 
+    #[rstest]
+    fn test_validate_token_with_wrong_audience(mut fixture: Fixture) {
+        fixture.options.audiences = vec![String::from("opendut-carl")];
+        let result = validate_token(&fixture.issuer, TEST_TOKEN, &fixture.jwk, Algorithm::RS256, &fixture.options);
+        assert_that!(result.err().unwrap().to_string(), contains_substring("InvalidAudience"));
+    }
+
+    #[rstest]
+    fn test_validate_token_with_required_scopes(mut fixture: Fixture) {
+        fixture.options.required_scopes = vec![String::from("openid"), String::from("groups")];
+        let result = validate_token(&fixture.issuer, TEST_TOKEN, &fixture.jwk, Algorithm::RS256, &fixture.options);
+        assert!(result.is_ok());
+
+        fixture.options.required_scopes = vec![String::from("openid"), String::from("opendut")];
+        let result = validate_token(&fixture.issuer, TEST_TOKEN, &fixture.jwk, Algorithm::RS256, &fixture.options);
+        assert!(matches!(result, Err(ValidationError::MissingScope(scope)) if scope == "opendut"));
+    }
+
     #[rstest]
     #[tokio::test]
     async fn test_authorize_user(fixture: Fixture) {
-        let cache: CustomInMemoryCache<String, JwkCacheValue> = CustomInMemoryCache::new();
-        let jwk_requester = MockJwk { jwk: String::from(JWK_RAW_DATA) };
-
-        let result = authorize_user(
-            fixture.issuer_url, 
-            fixture.issuer_remote_url, 
-            TEST_TOKEN, 
-            cache.clone(), 
-            jwk_requester, false
-        ).await;
-        
+        let validator = JwtValidator::new(fixture.options, MockIssuer::available());
+
+        let result = validator.authorize_user(TEST_TOKEN, SystemTime::now()).await;
+
         assert!(result.is_ok());
-        assert!(cache.get(&fixture.key_id).is_ok())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_authorize_user_of_one_of_several_issuers(mut fixture: Fixture) {
+        fixture.options.issuers = vec![
+            TrustedIssuer::new(Url::parse(OTHER_ISSUER_URL).unwrap()),
+            fixture.issuer,
+        ];
+        let validator = JwtValidator::new(fixture.options, MockIssuer::available());
+
+        let result = validator.authorize_user(TEST_TOKEN, SystemTime::now()).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn test_authorize_user_of_untrusted_issuer(mut fixture: Fixture) {
+        fixture.options.issuers = vec![
+            TrustedIssuer::new(Url::parse(OTHER_ISSUER_URL).unwrap()),
+        ];
+        let validator = JwtValidator::new(fixture.options, MockIssuer::available());
+
+        let result = validator.authorize_user(TEST_TOKEN, SystemTime::now()).await;
+
+        assert!(matches!(result, Err(ValidationError::UntrustedIssuer(issuer)) if issuer == "https://keycloak/realms/opendut"));
     }
 
     #[rstest]
     #[tokio::test]
     async fn test_authorize_user_jwk_already_cached(fixture: Fixture) {
-        let jwk_requester = MockJwkError { };
-
-        let result = authorize_user(
-            fixture.issuer_url, 
-            fixture.issuer_remote_url, 
-            TEST_TOKEN,
-            fixture.up_to_date_cache.clone(), 
-            jwk_requester, 
-            false
-        ).await;
-        
+        let issuer = MockIssuer::available();
+        let validator = JwtValidator::new(fixture.options, Clone::clone(&issuer));
+        let now = SystemTime::now();
+        validator.keys.refresh_all(now).await;
+
+        issuer.available.store(false, Ordering::SeqCst);
+        let result = validator.authorize_user(TEST_TOKEN, now + Duration::from_secs(60)).await;
+
         assert!(result.is_ok());
-        assert!(fixture.up_to_date_cache.get(&fixture.key_id).is_ok())
     }
 
     #[rstest]
     #[tokio::test]
     async fn test_authorize_user_with_jwk_cached_two_days_ago(fixture: Fixture) {
-        let jwk_requester = MockJwk { jwk: String::from(JWK_RAW_DATA) };
-
-        let result = authorize_user(
-            fixture.issuer_url,
-            fixture.issuer_remote_url,
-            TEST_TOKEN,
-            fixture.two_day_old_cache.clone(),
-            jwk_requester,
-            false
-        ).await;
-        
+        let issuer = MockIssuer::available();
+        let validator = JwtValidator::new(fixture.options, Clone::clone(&issuer));
+        let now = SystemTime::now();
+        let two_days_ago = now - Duration::from_secs(2 * 24 * 60 * 60);
+        validator.keys.refresh_all(two_days_ago).await;
+
+        issuer.available.store(false, Ordering::SeqCst);
+        let result = validator.authorize_user(TEST_TOKEN, now).await;
+        assert!(result.is_err());
+
+        issuer.available.store(true, Ordering::SeqCst);
+        let result = validator.authorize_user(TEST_TOKEN, now + Duration::from_secs(60)).await;
         assert!(result.is_ok());
-        assert!(fixture.two_day_old_cache.get(&fixture.key_id).is_ok())
     }
 
-    struct MockJwk {
-        jwk: String
+    #[derive(Clone)]
+    struct MockIssuer {
+        available: Arc<AtomicBool>,
     }
 
-    impl JwkRequester for MockJwk {
-        async fn fetch_jwk(&self, _issuer_jwk_url: Url) -> Result<String, ValidationError> {
-            Ok(self.jwk.clone())
+    impl MockIssuer {
+        fn available() -> Self {
+            Self { available: Arc::new(AtomicBool::new(true)) }
         }
     }
 
-    struct MockJwkError {}
-
-    impl JwkRequester for MockJwkError{
-        async fn fetch_jwk(&self, _issuer_jwk_url: Url) -> Result<String, ValidationError> {
-            Err(ValidationError::Cache(String::from("Use cached value")))
+    impl IssuerRequester for MockIssuer {
+        async fn fetch(&self, url: Url) -> Result<String, ValidationError> {
+            if !self.available.load(Ordering::SeqCst) {
+                return Err(ValidationError::Configuration(String::from("Identity provider is not available")));
+            }
+            match url.as_str() {
+                "https://keycloak/realms/opendut/.well-known/openid-configuration" => Ok(String::from(r#"{
+                    "issuer": "https://keycloak/realms/opendut",
+                    "jwks_uri": "https://keycloak/realms/opendut/protocol/openid-connect/certs"
+                }"#)),
+                "https://keycloak/realms/opendut/protocol/openid-connect/certs" => Ok(String::from(JWK_RAW_DATA)),
+                other => Err(ValidationError::Configuration(format!("Unexpected request to '{other}'"))),
+            }
         }
     }
 
     struct Fixture {
-        key_id: String,
-        issuer_url: Url,
-        issuer_remote_url: Url,
+        issuer: TrustedIssuer,
         jwk: JsonWebKey,
-        up_to_date_cache: CustomInMemoryCache<String, JwkCacheValue>,
-        two_day_old_cache: CustomInMemoryCache<String, JwkCacheValue>,
+        options: JwtValidationOptions,
     }
 
     #[fixture]
     fn fixture() -> Fixture {
-        let key_id = String::from(KEY_ID);
-        let issuer_url: Url = Url::parse(ISSUER_URL).unwrap();
-        let issuer_remote_url: Url = Url::parse(ISSUER_URL).unwrap();
-        
+        let issuer = TrustedIssuer::new(Url::parse(ISSUER_URL).unwrap());
+
         let json_web_key_set = serde_json::from_str::<OidcJsonWebKeySet>(JWK_RAW_DATA).unwrap();
-        let jwk_map = json_web_key_set.keys.into_iter().map(|jwk| {
-            (jwk.key_identifier.clone(), jwk)
-        }).collect::<BTreeMap<_, _>>();
+        let jwk_map = json_web_key_set.signing_keys();
 
         let jwk = jwk_map.get(KEY_ID).expect("test could not get key_id from jwk map");
 
-        let mut up_to_date_cache: CustomInMemoryCache<String, JwkCacheValue> = CustomInMemoryCache::new();
-        let jwk_cache_value = JwkCacheValue {
-            jwk: Clone::clone(&jwk),
-            last_cached: Utc::now().timestamp()
+        let options = JwtValidationOptions {
+            issuers: vec![Clone::clone(&issuer)],
+            audiences: vec![String::from("account")],
+            required_scopes: vec![],
+            validate_expiration: false,
+            key_refresh_interval: Duration::from_secs(5 * 60),
+            key_max_age: Duration::from_secs(24 * 60 * 60),
         };
-        up_to_date_cache.insert(key_id.clone(), jwk_cache_value).expect("Could not cache jwk");
-        
-        let mut two_day_old_cache: CustomInMemoryCache<String, JwkCacheValue> = CustomInMemoryCache::new();
-        let utc_two_days_ago = Utc::now().sub(Duration::days(2));
-        let jwk_two_day_old_cache_value = JwkCacheValue {
-            jwk: Clone::clone(&jwk),
-            last_cached: utc_two_days_ago.timestamp()
-        };
-        two_day_old_cache.insert(key_id.clone(), jwk_two_day_old_cache_value).expect("Could not cache jwk");
-        
+
         Fixture {
-            key_id,
-            issuer_url,
-            issuer_remote_url,
+            issuer,
             jwk: jwk.clone(),
-            up_to_date_cache,
-            two_day_old_cache
+            options,
         }
     }
 }
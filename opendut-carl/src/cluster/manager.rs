@@ -1,4 +1,5 @@
 use std::collections::{HashMap, HashSet};
+use std::ops::Not;
 use std::sync::Arc;
 use tokio::sync::Mutex;
 
@@ -16,6 +17,7 @@ use opendut_types::util::Port;
 
 use crate::actions;
 use crate::actions::{AssignClusterParams, ListPeerDescriptorsParams};
+use crate::auth::ownership::{Ownership, Requester};
 use crate::peer::broker::PeerMessagingBrokerRef;
 use crate::resources::manager::ResourcesManagerRef;
 use crate::vpn::Vpn;
@@ -69,11 +71,13 @@ impl ClusterManager {
             can_server_port_counter
         }))
     }
+    /// Deploys the cluster with the peers, which the requester may access. Peers of other teams are treated as if they did not exist.
     #[tracing::instrument(skip(self), level="trace")]
-    pub async fn deploy(&mut self, cluster_id: ClusterId) -> Result<(), DeployClusterError> {
+    pub async fn deploy(&mut self, cluster_id: ClusterId, requester: Requester) -> Result<(), DeployClusterError> {
 
         let cluster_config = self.resources_manager.resources(|resources| {
             resources.get::<ClusterConfiguration>(cluster_id)
+                .filter(|_| requester.may_access(resources.get::<Ownership>(cluster_id).as_ref()))
         }).await
         .ok_or(DeployClusterError::ClusterConfigurationNotFound(cluster_id))?;
 
@@ -81,8 +85,12 @@ impl ClusterManager {
 
         let all_peers = actions::list_peer_descriptors(ListPeerDescriptorsParams {
             resources_manager: Arc::clone(&self.resources_manager),
+            requester,
         }).await.map_err(|cause| DeployClusterError::Internal { cluster_id, cause: cause.to_string() })?;
 
+        if all_peers.iter().any(|peer| peer.id == cluster_config.leader).not() {
+            return Err(DeployClusterError::LeaderNotFound { leader_id: cluster_config.leader, cluster_id, cluster_name });
+        }
 
         let member_interface_mapping = determine_member_interface_mapping(cluster_config.devices, all_peers, cluster_config.leader)
             .map_err(|cause| match cause {
@@ -165,6 +173,7 @@ impl ClusterManager {
                     id: cluster_id,
                     leader: cluster_config.leader,
                     assignments: member_assignments.clone(),
+                    can_capture: Clone::clone(&cluster_config.can_capture),
                 },
             }).await
             .map_err(|cause| {
@@ -177,37 +186,31 @@ impl ClusterManager {
         Ok(())
     }
 
+    /// Deploys the cluster, if the requester may access its configuration. Otherwise, the configuration is treated as if it did not exist.
     #[tracing::instrument(skip(self), level="trace")]
-    pub async fn find_configuration(&self, id: ClusterId) -> Option<ClusterConfiguration> {
-        self.resources_manager.resources(|resources| {
-            resources.get::<ClusterConfiguration>(id)
-        }).await
-    }
-
-    #[tracing::instrument(skip(self), level="trace")]
-    pub async fn list_configuration(&self) -> Vec<ClusterConfiguration> {
-        self.resources_manager.resources(|resources| {
-            resources.iter::<ClusterConfiguration>().cloned().collect::<Vec<_>>()
-        }).await
-    }
-
-    #[tracing::instrument(skip(self), level="trace")]
-    pub async fn store_cluster_deployment(&mut self, deployment: ClusterDeployment) -> Result<ClusterId, StoreClusterDeploymentError> {
+    pub async fn store_cluster_deployment(&mut self, deployment: ClusterDeployment, requester: Requester) -> Result<ClusterId, StoreClusterDeploymentError> {
         let cluster_id = deployment.id;
         self.resources_manager.resources_mut(|resources| {
+            if requester.may_access(resources.get::<Ownership>(cluster_id).as_ref()).not() {
+                return Err(StoreClusterDeploymentError::ClusterConfigurationNotFound { cluster_id });
+            }
             resources.insert(deployment.id, deployment);
-        }).await;
-        if let Err(error) = self.deploy(cluster_id).await {
+            Ok(())
+        }).await?;
+        if let Err(error) = self.deploy(cluster_id, requester).await {
             error!("Failed to deploy cluster <{cluster_id}>, due to:\n  {error}");
         }
         Ok(cluster_id)
     }
 
     #[tracing::instrument(skip(self), level="trace")]
-    pub async fn delete_cluster_deployment(&self, cluster_id: ClusterId) -> Result<ClusterDeployment, DeleteClusterDeploymentError> {
+    pub async fn delete_cluster_deployment(&self, cluster_id: ClusterId, requester: Requester) -> Result<ClusterDeployment, DeleteClusterDeploymentError> {
 
         let (deployment, configuration) = self.resources_manager
             .resources_mut(|resources| {
+                if requester.may_access(resources.get::<Ownership>(cluster_id).as_ref()).not() {
+                    return None;
+                }
                 resources.remove::<ClusterDeployment>(cluster_id)
                     .map(|deployment| (deployment, resources.get::<ClusterConfiguration>(cluster_id)))
             })
@@ -230,10 +233,14 @@ impl ClusterManager {
         }).await
     }
 
+    /// Lists the deployments of all clusters, whose configuration the requester may access.
     #[tracing::instrument(skip(self), level="trace")]
-    pub async fn list_deployment(&self) -> Vec<ClusterDeployment> {
+    pub async fn list_deployment(&self, requester: Requester) -> Vec<ClusterDeployment> {
         self.resources_manager.resources(|resources| {
-            resources.iter::<ClusterDeployment>().cloned().collect::<Vec<_>>()
+            resources.iter::<ClusterDeployment>()
+                .filter(|deployment| requester.may_access(resources.get::<Ownership>(deployment.id).as_ref()))
+                .cloned()
+                .collect::<Vec<_>>()
         }).await
     }
 }
@@ -311,12 +318,15 @@ mod test {
     use opendut_carl_api::proto::services::peer_messaging_broker::downstream;
     use opendut_types::cluster::ClusterName;
     use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
-    use opendut_types::peer::executor::{container::{ContainerCommand, ContainerImage, ContainerName, Engine}, ExecutorKind, ExecutorDescriptors, ExecutorDescriptor};
+    use opendut_types::peer::configuration::PeerConfiguration;
+    use opendut_types::peer::executor::{container::{ContainerCommand, ContainerImage, ContainerImagePullPolicy, ContainerName, Engine}, ExecutorKind, ExecutorDescriptors, ExecutorDescriptor};
     use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, Topology};
     use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceName};
 
     use crate::actions::{CreateClusterConfigurationParams, StorePeerDescriptorParams};
+    use crate::auth::permission::Role;
     use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::secrets::SecretCipher;
     use crate::resources::manager::ResourcesManager;
     use crate::settings;
 
@@ -325,7 +335,7 @@ mod test {
     mod deploy_cluster {
         use opendut_carl_api::proto::services::peer_messaging_broker::ApplyPeerConfiguration;
         use crate::actions::StorePeerDescriptorOptions;
-        use opendut_types::peer::configuration::{PeerConfiguration, PeerConfiguration2};
+        use opendut_types::peer::configuration::PeerConfiguration2;
 
         use super::*;
 
@@ -344,16 +354,19 @@ mod test {
                 name: ClusterName::try_from("MyAwesomeCluster").unwrap(),
                 leader: leader_id,
                 devices: HashSet::from([peer_a.device, peer_b.device]),
+                can_capture: None,
             };
 
             let store_peer_descriptor_options = StorePeerDescriptorOptions {
                 bridge_name_default: NetworkInterfaceName::try_from("br-opendut").unwrap(),
+                registry_credentials: vec![],
             };
             actions::store_peer_descriptor(StorePeerDescriptorParams {
                 resources_manager: Arc::clone(&fixture.resources_manager),
                 vpn: Vpn::Disabled,
                 peer_descriptor: Clone::clone(&peer_a.descriptor),
                 options: store_peer_descriptor_options.clone(),
+                requester: Requester::Unrestricted,
             }).await?;
 
             actions::store_peer_descriptor(StorePeerDescriptorParams {
@@ -361,6 +374,7 @@ mod test {
                 vpn: Vpn::Disabled,
                 peer_descriptor: Clone::clone(&peer_b.descriptor),
                 options: store_peer_descriptor_options,
+                requester: Requester::Unrestricted,
             }).await?;
 
 
@@ -371,15 +385,17 @@ mod test {
             actions::create_cluster_configuration(CreateClusterConfigurationParams {
                 resources_manager: Arc::clone(&fixture.resources_manager),
                 cluster_configuration,
+                requester: Requester::Unrestricted,
             }).await?;
 
-            assert_that!(fixture.testee.lock().await.deploy(cluster_id).await, ok(eq(())));
+            assert_that!(fixture.testee.lock().await.deploy(cluster_id, Requester::Unrestricted).await, ok(eq(())));
 
 
             let expectation = || {
                 matches_pattern!(ClusterAssignment {
                     id: eq(cluster_id),
                     leader: eq(leader_id),
+                    can_capture: none(),
                     assignments: any![
                         unordered_elements_are![
                             eq(PeerClusterAssignment {
@@ -446,13 +462,90 @@ mod test {
         }
     }
 
+    #[rstest]
+    #[tokio::test]
+    async fn should_only_deploy_and_list_clusters_accessible_to_the_requester(fixture: Fixture, peer_a: PeerFixture) -> anyhow::Result<()> {
+        let alice = Requester::User { name: String::from("alice"), groups: vec![String::from("/team-a")], role: Some(Role::Operator) };
+        let bob = Requester::User { name: String::from("bob"), groups: vec![String::from("/team-b")], role: Some(Role::Operator) };
+
+        fixture.resources_manager.insert(peer_a.id, peer_a.descriptor).await;
+        fixture.resources_manager.insert(peer_a.id, alice.ownership().unwrap()).await;
+
+        let cluster_id = ClusterId::random();
+        actions::create_cluster_configuration(CreateClusterConfigurationParams {
+            resources_manager: Arc::clone(&fixture.resources_manager),
+            cluster_configuration: ClusterConfiguration {
+                id: cluster_id,
+                name: ClusterName::try_from("TeamACluster")?,
+                leader: peer_a.id,
+                devices: HashSet::from([peer_a.device]),
+                can_capture: None,
+            },
+            requester: Clone::clone(&alice),
+        }).await?;
+
+        let result = fixture.testee.lock().await.store_cluster_deployment(ClusterDeployment { id: cluster_id }, Clone::clone(&bob)).await;
+        assert!(matches!(result, Err(StoreClusterDeploymentError::ClusterConfigurationNotFound { .. })));
+        assert_that!(fixture.resources_manager.get::<ClusterDeployment>(cluster_id).await, none());
+
+        fixture.testee.lock().await.store_cluster_deployment(ClusterDeployment { id: cluster_id }, Clone::clone(&alice)).await?;
+
+        assert!(fixture.testee.lock().await.list_deployment(Clone::clone(&bob)).await.is_empty());
+        assert_that!(fixture.testee.lock().await.list_deployment(Clone::clone(&alice)).await, elements_are![eq(ClusterDeployment { id: cluster_id })]);
+
+        let result = fixture.testee.lock().await.delete_cluster_deployment(cluster_id, bob).await;
+        assert!(matches!(result, Err(DeleteClusterDeploymentError::ClusterDeploymentNotFound { .. })));
+        assert_that!(fixture.resources_manager.get::<ClusterDeployment>(cluster_id).await, some(anything()));
+
+        fixture.testee.lock().await.delete_cluster_deployment(cluster_id, alice).await?;
+        assert_that!(fixture.resources_manager.get::<ClusterDeployment>(cluster_id).await, none());
+
+        Ok(())
+    }
+
+    #[rstest]
+    #[tokio::test]
+    async fn should_not_deploy_clusters_with_peers_of_other_teams(fixture: Fixture, peer_a: PeerFixture, peer_b: PeerFixture) -> anyhow::Result<()> {
+        let alice = Requester::User { name: String::from("alice"), groups: vec![String::from("/team-a")], role: Some(Role::Operator) };
+        let bob = Requester::User { name: String::from("bob"), groups: vec![String::from("/team-b")], role: Some(Role::Operator) };
+
+        let cluster_id = ClusterId::random();
+        let cluster_name = ClusterName::try_from("CrossTeamCluster")?;
+        fixture.resources_manager.resources_mut(|resources| {
+            resources.insert(peer_a.id, peer_a.descriptor);
+            resources.insert(peer_a.id, alice.ownership().unwrap());
+            resources.insert(peer_b.id, peer_b.descriptor);
+            resources.insert(peer_b.id, bob.ownership().unwrap());
+            resources.insert(cluster_id, ClusterConfiguration {
+                id: cluster_id,
+                name: Clone::clone(&cluster_name),
+                leader: peer_b.id,
+                devices: HashSet::from([peer_a.device, peer_b.device]),
+                can_capture: None,
+            });
+            resources.insert(cluster_id, bob.ownership().unwrap());
+        }).await;
+
+        assert_that!(
+            fixture.testee.lock().await.deploy(cluster_id, Clone::clone(&alice)).await,
+            err(eq(DeployClusterError::ClusterConfigurationNotFound(cluster_id)))
+        );
+        assert_that!(
+            fixture.testee.lock().await.deploy(cluster_id, bob).await,
+            err(eq(DeployClusterError::PeerForDeviceNotFound { device_id: peer_a.device, cluster_id, cluster_name }))
+        );
+        assert_that!(fixture.resources_manager.get::<PeerConfiguration>(peer_a.id).await.and_then(|configuration| configuration.cluster_assignment), none());
+
+        Ok(())
+    }
+
     #[rstest]
     #[tokio::test]
     async fn deploy_should_fail_for_unknown_cluster(fixture: Fixture) -> anyhow::Result<()> {
         let unknown_cluster = ClusterId::random();
 
         assert_that!(
-            fixture.testee.lock().await.deploy(unknown_cluster).await,
+            fixture.testee.lock().await.deploy(unknown_cluster, Requester::Unrestricted).await,
             err(eq(DeployClusterError::ClusterConfigurationNotFound(unknown_cluster)))
         );
 
@@ -534,6 +627,7 @@ mod test {
         let resources_manager = ResourcesManager::new();
         let peer_messaging_broker = PeerMessagingBroker::new(
             Arc::clone(&resources_manager),
+            Arc::new(SecretCipher::generate().unwrap()),
             PeerMessagingBrokerOptions::load(&settings.config).unwrap(),
         );
 
@@ -607,12 +701,23 @@ mod test {
                             engine: Engine::Docker,
                             name: ContainerName::Empty,
                             image: ContainerImage::try_from("testUrl").unwrap(),
+                            pull_policy: ContainerImagePullPolicy::IfNotPresent,
+                            digest: None,
+                            registry_credential: None,
                             volumes: vec![],
                             devices: vec![],
                             envs: vec![],
                             ports: vec![],
                             command: ContainerCommand::Default,
                             args: vec![],
+                            can_databases: vec![],
+                            cpus: None,
+                            memory: None,
+                            pids_limit: None,
+                            network_mode: Default::default(),
+                            cap_add: vec![],
+                            cap_drop: vec![],
+                            read_only_rootfs: false,
                         },
                         results_url: None,
                     }
@@ -0,0 +1,66 @@
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::{error, trace};
+
+use opendut_carl_api::carl::audit::ListAuditEventsError;
+use opendut_carl_api::proto::services::audit_log::*;
+use opendut_carl_api::proto::services::audit_log::audit_log_server::{AuditLog as AuditLogService, AuditLogServer};
+use opendut_types::audit::AuditEventFilter;
+use opendut_util::telemetry::logging::NonDisclosingRequestExtension;
+
+use crate::audit::AuditLogRef;
+use crate::auth::permission::{require_role, Role};
+
+pub struct AuditLogFacade {
+    audit_log: AuditLogRef,
+}
+
+impl AuditLogFacade {
+
+    pub fn new(audit_log: AuditLogRef) -> Self {
+        Self {
+            audit_log
+        }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<AuditLogServer<Self>> {
+        tonic_web::enable(AuditLogServer::new(self))
+    }
+}
+
+#[tonic::async_trait]
+impl AuditLogService for AuditLogFacade {
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn list_audit_events(&self, request: Request<ListAuditEventsRequest>) -> Result<Response<ListAuditEventsResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Admin)?;
+
+        let request = request.into_inner();
+        let filter = request.filter
+            .map(AuditEventFilter::from)
+            .unwrap_or_default();
+
+        let result = self.audit_log.query(&filter).await
+            .map_err(|cause| ListAuditEventsError::Internal { cause: cause.to_string() })
+            .inspect_err(|err| error!("{err}"));
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListAuditEventsResponse {
+                    reply: Some(list_audit_events_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(events) => {
+                Ok(Response::new(ListAuditEventsResponse {
+                    reply: Some(list_audit_events_response::Reply::Success(
+                        ListAuditEventsSuccess {
+                            events: events.into_iter().map(From::from).collect()
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+}
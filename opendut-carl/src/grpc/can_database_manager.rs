@@ -0,0 +1,158 @@
+use std::sync::Arc;
+
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::trace;
+
+use opendut_carl_api::proto::services::can_database_manager::*;
+use opendut_carl_api::proto::services::can_database_manager::can_database_manager_server::{CanDatabaseManager as CanDatabaseManagerService, CanDatabaseManagerServer};
+use opendut_types::can::{CanDatabase, CanDatabaseId};
+use opendut_types::resources::Id;
+use opendut_util::telemetry::logging::NonDisclosingRequestExtension;
+
+use crate::actions;
+use crate::actions::{DeleteCanDatabaseParams, ListCanDatabasesParams, StoreCanDatabaseParams};
+use crate::audit::AuditLogRef;
+use crate::audit::summary::AuditSummary;
+use crate::auth::permission::{require_role, Role};
+use crate::grpc::extract;
+use crate::resources::manager::ResourcesManagerRef;
+
+pub struct CanDatabaseManagerFacade {
+    resources_manager: ResourcesManagerRef,
+    audit_log: AuditLogRef,
+}
+
+impl CanDatabaseManagerFacade {
+
+    pub fn new(resources_manager: ResourcesManagerRef, audit_log: AuditLogRef) -> Self {
+        Self {
+            resources_manager,
+            audit_log,
+        }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<CanDatabaseManagerServer<Self>> {
+        tonic_web::enable(CanDatabaseManagerServer::new(self))
+    }
+}
+
+#[tonic::async_trait]
+impl CanDatabaseManagerService for CanDatabaseManagerFacade {
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn store_can_database(&self, request: Request<StoreCanDatabaseRequest>) -> Result<Response<StoreCanDatabaseResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Operator)?;
+        let audit = self.audit_log.start(&request, "store_can_database");
+
+        let request = request.into_inner();
+        let can_database: CanDatabase = extract!(request.database)?;
+
+        let database_id = can_database.descriptor.id;
+        let before = self.resources_manager.get::<CanDatabase>(database_id).await;
+        let after = can_database.descriptor.audit_summary();
+
+        let result =
+            actions::store_can_database(StoreCanDatabaseParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                can_database,
+            }).await;
+
+        audit.resource(Id::from(database_id.0))
+            .before(before.map(|database| database.descriptor.audit_summary()))
+            .after(result.is_ok().then_some(after))
+            .finish(&result);
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(StoreCanDatabaseResponse {
+                    reply: Some(store_can_database_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(database_id) => {
+                Ok(Response::new(StoreCanDatabaseResponse {
+                    reply: Some(store_can_database_response::Reply::Success(
+                        StoreCanDatabaseSuccess {
+                            database_id: Some(database_id.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn delete_can_database(&self, request: Request<DeleteCanDatabaseRequest>) -> Result<Response<DeleteCanDatabaseResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Operator)?;
+        let audit = self.audit_log.start(&request, "delete_can_database");
+
+        let request = request.into_inner();
+        let database_id: CanDatabaseId = extract!(request.database_id)?;
+
+        let before = self.resources_manager.get::<CanDatabase>(database_id).await;
+
+        let result =
+            actions::delete_can_database(DeleteCanDatabaseParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                database_id,
+            }).await;
+
+        audit.resource(Id::from(database_id.0))
+            .before(before.map(|database| database.descriptor.audit_summary()))
+            .finish(&result);
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DeleteCanDatabaseResponse {
+                    reply: Some(delete_can_database_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(descriptor) => {
+                Ok(Response::new(DeleteCanDatabaseResponse {
+                    reply: Some(delete_can_database_response::Reply::Success(
+                        DeleteCanDatabaseSuccess {
+                            descriptor: Some(descriptor.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn list_can_databases(&self, request: Request<ListCanDatabasesRequest>) -> Result<Response<ListCanDatabasesResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Viewer)?;
+
+        let result =
+            actions::list_can_databases(ListCanDatabasesParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+            }).await
+            .map(|descriptors| descriptors.into_iter()
+                .map(From::from)
+                .collect::<Vec<_>>()
+            );
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListCanDatabasesResponse {
+                    reply: Some(list_can_databases_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(descriptors) => {
+                Ok(Response::new(ListCanDatabasesResponse {
+                    reply: Some(list_can_databases_response::Reply::Success(
+                        ListCanDatabasesSuccess {
+                            descriptors
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+}
@@ -6,26 +6,36 @@ use tracing::trace;
 
 use opendut_carl_api::proto::services::cluster_manager::*;
 use opendut_carl_api::proto::services::cluster_manager::cluster_manager_server::{ClusterManager as ClusterManagerService, ClusterManagerServer};
-use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::cluster::{CanFaultInjectionRule, CanFaultInjectionRuleId, ClusterConfiguration, ClusterDeployment, ClusterId};
+use opendut_types::resources::Id;
 use opendut_util::telemetry::logging::NonDisclosingRequestExtension;
 
 use crate::actions;
-use crate::actions::{CreateClusterConfigurationParams, DeleteClusterConfigurationParams};
+use crate::actions::{CreateClusterConfigurationParams, DeleteCanFaultInjectionRuleParams, DeleteClusterConfigurationParams, ListCanFaultInjectionRulesParams, ListClusterConfigurationsParams, StoreCanFaultInjectionRuleParams, TransferClusterConfigurationOwnershipParams};
+use crate::audit::AuditLogRef;
+use crate::audit::summary::AuditSummary;
+use crate::auth::ownership::{Ownership, Requester};
+use crate::auth::permission::{require_role, Role};
 use crate::cluster::manager::ClusterManagerRef;
 use crate::grpc::extract;
+use crate::peer::broker::PeerMessagingBrokerRef;
 use crate::resources::manager::ResourcesManagerRef;
 
 pub struct ClusterManagerFacade {
     cluster_manager: ClusterManagerRef,
     resources_manager: ResourcesManagerRef,
+    peer_messaging_broker: PeerMessagingBrokerRef,
+    audit_log: AuditLogRef,
 }
 
 impl ClusterManagerFacade {
 
-    pub fn new(cluster_manager: ClusterManagerRef, resources_manager: ResourcesManagerRef) -> Self {
+    pub fn new(cluster_manager: ClusterManagerRef, resources_manager: ResourcesManagerRef, peer_messaging_broker: PeerMessagingBrokerRef, audit_log: AuditLogRef) -> Self {
         Self {
             cluster_manager,
-            resources_manager
+            resources_manager,
+            peer_messaging_broker,
+            audit_log,
         }
     }
 
@@ -40,15 +50,28 @@ impl ClusterManagerService for ClusterManagerFacade {
     async fn create_cluster_configuration(&self, request: Request<CreateClusterConfigurationRequest>) -> Result<Response<CreateClusterConfigurationResponse>, Status> {
 
         trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Operator)?;
+        let requester = Requester::from_request(&request);
+        let audit = self.audit_log.start(&request, "create_cluster_configuration");
         
         let request = request.into_inner();
         let cluster_configuration: ClusterConfiguration = extract!(request.cluster_configuration)?;
 
+        let cluster_id = cluster_configuration.id;
+        let before = self.resources_manager.get::<ClusterConfiguration>(cluster_id).await;
+        let after = cluster_configuration.audit_summary();
+
         let result = actions::create_cluster_configuration(CreateClusterConfigurationParams {
             resources_manager: Arc::clone(&self.resources_manager),
             cluster_configuration,
+            requester,
         }).await;
 
+        audit.resource(Id::from(cluster_id.0))
+            .before(before.map(|configuration| configuration.audit_summary()))
+            .after(result.is_ok().then_some(after))
+            .finish(&result);
+
         match result {
             Err(error) => {
                 Ok(Response::new(CreateClusterConfigurationResponse {
@@ -70,16 +93,26 @@ impl ClusterManagerService for ClusterManagerFacade {
     async fn delete_cluster_configuration(&self, request: Request<DeleteClusterConfigurationRequest>) -> Result<Response<DeleteClusterConfigurationResponse>, Status> {
 
         trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Operator)?;
+        let requester = Requester::from_request(&request);
+        let audit = self.audit_log.start(&request, "delete_cluster_configuration");
 
         let request = request.into_inner();
         let cluster_id: ClusterId = extract!(request.cluster_id)?;
 
+        let before = self.resources_manager.get::<ClusterConfiguration>(cluster_id).await;
+
         let result =
             actions::delete_cluster_configuration(DeleteClusterConfigurationParams {
                 resources_manager: Arc::clone(&self.resources_manager),
                 cluster_id,
+                requester,
             }).await;
 
+        audit.resource(Id::from(cluster_id.0))
+            .before(before.map(|configuration| configuration.audit_summary()))
+            .finish(&result);
+
         match result {
             Err(error) => {
                 Ok(Response::new(DeleteClusterConfigurationResponse {
@@ -100,6 +133,8 @@ impl ClusterManagerService for ClusterManagerFacade {
     #[tracing::instrument(skip(self, request), level="trace")]
     async fn get_cluster_configuration(&self, request: Request<GetClusterConfigurationRequest>) -> Result<Response<GetClusterConfigurationResponse>, Status> {
         trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Viewer)?;
+        let requester = Requester::from_request(&request);
         
         match request.into_inner().id {
             None => {
@@ -108,7 +143,15 @@ impl ClusterManagerService for ClusterManagerFacade {
             Some(id) => {
                 let id = ClusterId::try_from(id)
                     .map_err(|_| Status::invalid_argument("Invalid ClusterId."))?;
-                let configuration = self.cluster_manager.lock().await.find_configuration(id).await;
+                let configuration =
+                    actions::list_cluster_configurations(ListClusterConfigurationsParams {
+                        resources_manager: Arc::clone(&self.resources_manager),
+                        requester,
+                    }).await
+                    .ok()
+                    .and_then(|configurations| configurations.into_iter()
+                        .find(|configuration| configuration.id == id)
+                    );
                 match configuration {
                     Some(configuration) => {
                         Ok(Response::new(GetClusterConfigurationResponse {
@@ -133,25 +176,104 @@ impl ClusterManagerService for ClusterManagerFacade {
     #[tracing::instrument(skip(self, request), level="trace")]
     async fn list_cluster_configurations(&self, request: Request<ListClusterConfigurationsRequest>) -> Result<Response<ListClusterConfigurationsResponse>, Status> {
         trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Viewer)?;
+        let requester = Requester::from_request(&request);
         
-        let configurations = self.cluster_manager.lock().await.list_configuration().await;
-        Ok(Response::new(ListClusterConfigurationsResponse {
-            result: Some(list_cluster_configurations_response::Result::Success(
-                ListClusterConfigurationsSuccess {
-                    configurations: configurations.into_iter().map(|configuration| configuration.into()).collect::<Vec<_>>()
-                }
-            ))
-        }))
+        let result =
+            actions::list_cluster_configurations(ListClusterConfigurationsParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                requester,
+            }).await;
+
+        match result {
+            Err(_) => {
+                Ok(Response::new(ListClusterConfigurationsResponse {
+                    result: Some(list_cluster_configurations_response::Result::Failure(
+                        ListClusterConfigurationsFailure {}
+                    ))
+                }))
+            }
+            Ok(configurations) => {
+                Ok(Response::new(ListClusterConfigurationsResponse {
+                    result: Some(list_cluster_configurations_response::Result::Success(
+                        ListClusterConfigurationsSuccess {
+                            configurations: configurations.into_iter().map(|configuration| configuration.into()).collect::<Vec<_>>()
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn transfer_cluster_configuration_ownership(&self, request: Request<TransferClusterConfigurationOwnershipRequest>) -> Result<Response<TransferClusterConfigurationOwnershipResponse>, Status> {
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Admin)?;
+        let audit = self.audit_log.start(&request, "transfer_cluster_configuration_ownership");
+
+        let request = request.into_inner();
+        let cluster_id: ClusterId = extract!(request.cluster_id)?;
+        if request.owner.is_empty() {
+            return Err(Status::invalid_argument("Field 'owner' must not be empty."));
+        }
+
+        let ownership = Ownership {
+            owner: request.owner,
+            group: request.group,
+        };
+        let before = self.resources_manager.get::<Ownership>(cluster_id).await;
+        let after = ownership.audit_summary();
+
+        let result =
+            actions::transfer_cluster_configuration_ownership(TransferClusterConfigurationOwnershipParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                cluster_id,
+                ownership,
+            }).await;
+
+        audit.resource(Id::from(cluster_id.0))
+            .before(before.map(|ownership| ownership.audit_summary()))
+            .after(result.is_ok().then_some(after))
+            .finish(&result);
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(TransferClusterConfigurationOwnershipResponse {
+                    reply: Some(transfer_cluster_configuration_ownership_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(cluster_id) => {
+                Ok(Response::new(TransferClusterConfigurationOwnershipResponse {
+                    reply: Some(transfer_cluster_configuration_ownership_response::Reply::Success(
+                        TransferClusterConfigurationOwnershipSuccess {
+                            cluster_id: Some(cluster_id.into())
+                        }
+                    ))
+                }))
+            }
+        }
     }
 
     #[tracing::instrument(skip(self, request), level="trace")]
     async fn store_cluster_deployment(&self, request: Request<StoreClusterDeploymentRequest>) -> Result<Response<StoreClusterDeploymentResponse>, Status> {
         trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Operator)?;
+        let requester = Requester::from_request(&request);
+        let audit = self.audit_log.start(&request, "store_cluster_deployment");
         
         let request = request.into_inner();
         let cluster_deployment: ClusterDeployment = extract!(request.cluster_deployment)?;
 
-        let result = self.cluster_manager.lock().await.store_cluster_deployment(cluster_deployment).await;
+        let cluster_id = cluster_deployment.id;
+        let before = self.resources_manager.get::<ClusterDeployment>(cluster_id).await;
+        let after = cluster_deployment.audit_summary();
+
+        let result = self.cluster_manager.lock().await.store_cluster_deployment(cluster_deployment, requester).await;
+
+        audit.resource(Id::from(cluster_id.0))
+            .before(before.map(|deployment| deployment.audit_summary()))
+            .after(result.is_ok().then_some(after))
+            .finish(&result);
 
         match result {
             Err(error) => {
@@ -173,11 +295,20 @@ impl ClusterManagerService for ClusterManagerFacade {
     #[tracing::instrument(skip(self, request), level="trace")]
     async fn delete_cluster_deployment(&self, request: Request<DeleteClusterDeploymentRequest>) -> Result<Response<DeleteClusterDeploymentResponse>, Status> {
         trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Operator)?;
+        let requester = Requester::from_request(&request);
+        let audit = self.audit_log.start(&request, "delete_cluster_deployment");
 
         let request = request.into_inner();
         let cluster_id: ClusterId = extract!(request.cluster_id)?;
 
-        let result = self.cluster_manager.lock().await.delete_cluster_deployment(cluster_id).await; // TODO: Replace with action
+        let before = self.resources_manager.get::<ClusterDeployment>(cluster_id).await;
+
+        let result = self.cluster_manager.lock().await.delete_cluster_deployment(cluster_id, requester).await; // TODO: Replace with action
+
+        audit.resource(Id::from(cluster_id.0))
+            .before(before.map(|deployment| deployment.audit_summary()))
+            .finish(&result);
 
         match result {
             Err(error) => {
@@ -200,8 +331,10 @@ impl ClusterManagerService for ClusterManagerFacade {
     #[tracing::instrument(skip(self, request), level="trace")]
     async fn list_cluster_deployments(&self, request: Request<ListClusterDeploymentsRequest>) -> Result<Response<ListClusterDeploymentsResponse>, Status> {
         trace!("Received request: {}", request.debug_output());
-        
-        let deployments = self.cluster_manager.lock().await.list_deployment().await;
+        require_role(&request, Role::Viewer)?;
+        let requester = Requester::from_request(&request);
+
+        let deployments = self.cluster_manager.lock().await.list_deployment(requester).await;
         Ok(Response::new(ListClusterDeploymentsResponse {
             result: Some(list_cluster_deployments_response::Result::Success(
                 ListClusterDeploymentsSuccess {
@@ -210,4 +343,131 @@ impl ClusterManagerService for ClusterManagerFacade {
             ))
         }))
     }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn store_can_fault_injection_rule(&self, request: Request<StoreCanFaultInjectionRuleRequest>) -> Result<Response<StoreCanFaultInjectionRuleResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Operator)?;
+        let audit = self.audit_log.start(&request, "store_can_fault_injection_rule");
+        let requester = Requester::from_request(&request);
+
+        let request = request.into_inner();
+        let rule: CanFaultInjectionRule = extract!(request.rule)?;
+
+        let rule_id = rule.id;
+        let before = self.resources_manager.get::<CanFaultInjectionRule>(rule_id).await;
+        let after = rule.audit_summary();
+
+        let result =
+            actions::store_can_fault_injection_rule(StoreCanFaultInjectionRuleParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+                rule,
+                requester,
+            }).await;
+
+        audit.resource(Id::from(rule_id.0))
+            .before(before.map(|rule| rule.audit_summary()))
+            .after(result.is_ok().then_some(after))
+            .finish(&result);
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(StoreCanFaultInjectionRuleResponse {
+                    reply: Some(store_can_fault_injection_rule_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(rule_id) => {
+                Ok(Response::new(StoreCanFaultInjectionRuleResponse {
+                    reply: Some(store_can_fault_injection_rule_response::Reply::Success(
+                        StoreCanFaultInjectionRuleSuccess {
+                            rule_id: Some(rule_id.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn delete_can_fault_injection_rule(&self, request: Request<DeleteCanFaultInjectionRuleRequest>) -> Result<Response<DeleteCanFaultInjectionRuleResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Operator)?;
+        let audit = self.audit_log.start(&request, "delete_can_fault_injection_rule");
+        let requester = Requester::from_request(&request);
+
+        let request = request.into_inner();
+        let rule_id: CanFaultInjectionRuleId = extract!(request.rule_id)?;
+
+        let before = self.resources_manager.get::<CanFaultInjectionRule>(rule_id).await;
+
+        let result =
+            actions::delete_can_fault_injection_rule(DeleteCanFaultInjectionRuleParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+                rule_id,
+                requester,
+            }).await;
+
+        audit.resource(Id::from(rule_id.0))
+            .before(before.map(|rule| rule.audit_summary()))
+            .finish(&result);
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DeleteCanFaultInjectionRuleResponse {
+                    reply: Some(delete_can_fault_injection_rule_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(rule) => {
+                Ok(Response::new(DeleteCanFaultInjectionRuleResponse {
+                    reply: Some(delete_can_fault_injection_rule_response::Reply::Success(
+                        DeleteCanFaultInjectionRuleSuccess {
+                            rule: Some(rule.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn list_can_fault_injection_rules(&self, request: Request<ListCanFaultInjectionRulesRequest>) -> Result<Response<ListCanFaultInjectionRulesResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Viewer)?;
+        let requester = Requester::from_request(&request);
+
+        let request = request.into_inner();
+        let cluster_id: Option<ClusterId> = request.cluster_id
+            .map(ClusterId::try_from)
+            .transpose()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'cluster_id' is not valid: {cause}")))?;
+
+        let result =
+            actions::list_can_fault_injection_rules(ListCanFaultInjectionRulesParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                cluster_id,
+                requester,
+            }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListCanFaultInjectionRulesResponse {
+                    reply: Some(list_can_fault_injection_rules_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(rules) => {
+                Ok(Response::new(ListCanFaultInjectionRulesResponse {
+                    reply: Some(list_can_fault_injection_rules_response::Reply::Success(
+                        ListCanFaultInjectionRulesSuccess {
+                            rules: rules.into_iter().map(From::from).collect()
+                        }
+                    ))
+                }))
+            }
+        }
+    }
 }
@@ -1,13 +1,21 @@
 use std::fmt::Display;
 
+pub use audit_log::AuditLogFacade;
+pub use can_database_manager::CanDatabaseManagerFacade;
 pub use cluster_manager::ClusterManagerFacade;
 pub use metadata_provider::MetadataProviderFacade;
 pub use peer_manager::{PeerManagerFacade, PeerManagerFacadeOptions};
 pub use peer_messaging_broker::PeerMessagingBrokerFacade;
+pub use secret_manager::SecretManagerFacade;
+pub use service_account_manager::ServiceAccountManagerFacade;
 
+mod audit_log;
+mod can_database_manager;
 mod cluster_manager;
 mod peer_manager;
 mod peer_messaging_broker;
+mod secret_manager;
+mod service_account_manager;
 mod metadata_provider;
 
 pub trait ExtractOrInvalidArgument<A, B>
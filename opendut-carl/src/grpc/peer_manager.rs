@@ -13,12 +13,24 @@ use opendut_carl_api::proto::services::peer_manager::*;
 use opendut_carl_api::proto::services::peer_manager::peer_manager_server::{PeerManager as PeerManagerService, PeerManagerServer};
 use opendut_types::peer::{PeerDescriptor, PeerId};
 use opendut_types::cleo::{CleoId};
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::executor::container::{ContainerRegistryCredential, ContainerRegistryCredentialName};
+use opendut_types::peer::executor::run::ExecutorRunId;
+use opendut_types::resources::Id;
 use opendut_types::util::net::NetworkInterfaceName;
 use opendut_util::telemetry::logging::NonDisclosingRequestExtension;
 
 use crate::actions;
-use crate::actions::{DeletePeerDescriptorParams, GenerateCleoSetupParams, GeneratePeerSetupParams, ListDevicesParams, ListPeerDescriptorsParams, StorePeerDescriptorOptions, StorePeerDescriptorParams};
+use crate::actions::{CredentialsHolder, DeletePeerDescriptorParams, GenerateCleoSetupParams, GeneratePeerSetupParams, ListDevicesParams, ListExecutorRunsParams, ListPeerDescriptorsParams, ListResultArtifactsParams, RevokeClientParams, RotateClientSecretParams, StorePeerDescriptorOptions, StorePeerDescriptorParams, TransferPeerOwnershipParams};
+use crate::audit::AuditLogRef;
+use crate::audit::summary::AuditSummary;
+use crate::auth::ownership::{Ownership, Requester};
+use crate::auth::permission::{require_role, Role};
+use crate::auth::peer_certificates::PeerCertificates;
+use crate::auth::revocation::RevokedClients;
 use crate::grpc::extract;
+use crate::peer::broker::PeerMessagingBrokerRef;
+use crate::peer::enrollment::EnrollmentOptions;
 use crate::resources::manager::ResourcesManagerRef;
 use crate::vpn::Vpn;
 
@@ -28,17 +40,26 @@ pub struct PeerManagerFacade {
     carl_url: Url,
     ca: Pem,
     oidc_registration_client: Option<RegistrationClientRef>,
+    peer_messaging_broker: PeerMessagingBrokerRef,
+    revoked_clients: RevokedClients,
+    peer_certificates: PeerCertificates,
+    audit_log: AuditLogRef,
     options: PeerManagerFacadeOptions,
 }
 
 impl PeerManagerFacade {
 
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         resources_manager: ResourcesManagerRef,
         vpn: Vpn,
         carl_url: Url,
         ca: Pem,
         oidc_registration_client: Option<RegistrationClientRef>,
+        peer_messaging_broker: PeerMessagingBrokerRef,
+        revoked_clients: RevokedClients,
+        peer_certificates: PeerCertificates,
+        audit_log: AuditLogRef,
         options: PeerManagerFacadeOptions
     ) -> Self {
         PeerManagerFacade {
@@ -47,6 +68,10 @@ impl PeerManagerFacade {
             carl_url,
             ca,
             oidc_registration_client,
+            peer_messaging_broker,
+            revoked_clients,
+            peer_certificates,
+            audit_log,
             options
         }
     }
@@ -63,19 +88,32 @@ impl PeerManagerService for PeerManagerFacade {
     async fn store_peer_descriptor(&self, request: Request<StorePeerDescriptorRequest>) -> Result<Response<StorePeerDescriptorResponse>, Status> {
 
         trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Admin)?;
+        let requester = Requester::from_request(&request);
+        let audit = self.audit_log.start(&request, "store_peer_descriptor");
 
         let request = request.into_inner();
         let peer_descriptor: PeerDescriptor = extract!(request.peer)?;
 
+        let before = self.resources_manager.get::<PeerDescriptor>(peer_descriptor.id).await;
+        let after = peer_descriptor.audit_summary();
+
         let result = actions::store_peer_descriptor(StorePeerDescriptorParams {
             resources_manager: Arc::clone(&self.resources_manager),
             vpn: Clone::clone(&self.vpn),
             peer_descriptor: Clone::clone(&peer_descriptor),
             options: StorePeerDescriptorOptions {
                 bridge_name_default: Clone::clone(&self.options.bridge_name_default),
-            }
+                registry_credentials: Clone::clone(&self.options.registry_credentials),
+            },
+            requester,
         }).await;
 
+        audit.resource(Id::from(peer_descriptor.id))
+            .before(before.map(|peer| peer.audit_summary()))
+            .after(result.is_ok().then_some(after))
+            .finish(&result);
+
         match result {
             Err(error) => {
                 Ok(Response::new(StorePeerDescriptorResponse {
@@ -98,18 +136,30 @@ impl PeerManagerService for PeerManagerFacade {
     async fn delete_peer_descriptor(&self, request: Request<DeletePeerDescriptorRequest>) -> Result<Response<DeletePeerDescriptorResponse>, Status> {
 
         trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Admin)?;
+        let requester = Requester::from_request(&request);
+        let audit = self.audit_log.start(&request, "delete_peer_descriptor");
 
         let request = request.into_inner();
         let peer_id: PeerId = extract!(request.peer_id)?;
 
+        let before = self.resources_manager.get::<PeerDescriptor>(peer_id).await;
+
         let result =
             actions::delete_peer_descriptor(DeletePeerDescriptorParams {
                 resources_manager: Arc::clone(&self.resources_manager),
+                peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
                 vpn: Clone::clone(&self.vpn),
                 peer: peer_id,
                 oidc_registration_client: self.oidc_registration_client.clone(),
+                peer_certificates: Clone::clone(&self.peer_certificates),
+                requester,
             }).await;
 
+        audit.resource(Id::from(peer_id))
+            .before(before.map(|peer| peer.audit_summary()))
+            .finish(&result);
+
         match result {
             Err(error) => {
                 Ok(Response::new(DeletePeerDescriptorResponse {
@@ -132,6 +182,8 @@ impl PeerManagerService for PeerManagerFacade {
     async fn get_peer_descriptor(&self, request: Request<GetPeerDescriptorRequest>) -> Result<Response<GetPeerDescriptorResponse>, Status> {
 
         trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Viewer)?;
+        let requester = Requester::from_request(&request);
 
         let request = request.into_inner();
         let peer_id: PeerId = extract!(request.peer_id)?;
@@ -139,6 +191,7 @@ impl PeerManagerService for PeerManagerFacade {
         let result =
             actions::list_peer_descriptors(ListPeerDescriptorsParams {
                 resources_manager: Arc::clone(&self.resources_manager),
+                requester,
             }).await
             .map_err(|error| GetPeerDescriptorError::Internal { peer_id, cause: error.to_string() })
             .and_then(|peers| peers.iter()
@@ -169,10 +222,13 @@ impl PeerManagerService for PeerManagerFacade {
     async fn list_peer_descriptors(&self, request: Request<ListPeerDescriptorsRequest>) -> Result<Response<ListPeerDescriptorsResponse>, Status> {
 
         trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Viewer)?;
+        let requester = Requester::from_request(&request);
 
         let result =
             actions::list_peer_descriptors(ListPeerDescriptorsParams {
                 resources_manager: Arc::clone(&self.resources_manager),
+                requester,
             }).await
             .map(|peers| peers.into_iter()
                 .map(From::from)
@@ -197,13 +253,160 @@ impl PeerManagerService for PeerManagerFacade {
         }
     }
 
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn transfer_peer_ownership(&self, request: Request<TransferPeerOwnershipRequest>) -> Result<Response<TransferPeerOwnershipResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Admin)?;
+        let audit = self.audit_log.start(&request, "transfer_peer_ownership");
+
+        let request = request.into_inner();
+        let peer_id: PeerId = extract!(request.peer_id)?;
+        if request.owner.is_empty() {
+            return Err(Status::invalid_argument("Field 'owner' must not be empty."));
+        }
+
+        let ownership = Ownership {
+            owner: request.owner,
+            group: request.group,
+        };
+        let before = self.resources_manager.get::<Ownership>(peer_id).await;
+        let after = ownership.audit_summary();
+
+        let result =
+            actions::transfer_peer_ownership(TransferPeerOwnershipParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                peer_id,
+                ownership,
+            }).await;
+
+        audit.resource(Id::from(peer_id))
+            .before(before.map(|ownership| ownership.audit_summary()))
+            .after(result.is_ok().then_some(after))
+            .finish(&result);
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(TransferPeerOwnershipResponse {
+                    reply: Some(transfer_peer_ownership_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(peer_id) => {
+                Ok(Response::new(TransferPeerOwnershipResponse {
+                    reply: Some(transfer_peer_ownership_response::Reply::Success(
+                        TransferPeerOwnershipSuccess {
+                            peer_id: Some(peer_id.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn rotate_client_secret(&self, request: Request<RotateClientSecretRequest>) -> Result<Response<RotateClientSecretResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Admin)?;
+        let audit = self.audit_log.start(&request, "rotate_client_secret");
+
+        let request = request.into_inner();
+        let holder: CredentialsHolder = extract!(request.holder)?;
+
+        let result =
+            actions::rotate_client_secret(RotateClientSecretParams {
+                peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+                oidc_registration_client: Clone::clone(&self.oidc_registration_client),
+                holder,
+            }).await;
+
+        audit.resource(holder_resource_id(holder))
+            .finish(&result);
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(RotateClientSecretResponse {
+                    reply: Some(rotate_client_secret_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(credentials) => {
+                let (client_id, client_secret) = credentials
+                    .map(|credentials| (Some(credentials.client_id.into()), Some(credentials.client_secret.into())))
+                    .unwrap_or_default();
+
+                Ok(Response::new(RotateClientSecretResponse {
+                    reply: Some(rotate_client_secret_response::Reply::Success(
+                        RotateClientSecretSuccess {
+                            holder: Some(holder.into()),
+                            client_id,
+                            client_secret,
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn revoke_client(&self, request: Request<RevokeClientRequest>) -> Result<Response<RevokeClientResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Admin)?;
+        let audit = self.audit_log.start(&request, "revoke_client");
+
+        let request = request.into_inner();
+        let holder: CredentialsHolder = extract!(request.holder)?;
+
+        let result =
+            actions::revoke_client(RevokeClientParams {
+                peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+                oidc_registration_client: Clone::clone(&self.oidc_registration_client),
+                revoked_clients: Clone::clone(&self.revoked_clients),
+                peer_certificates: Clone::clone(&self.peer_certificates),
+                holder,
+            }).await;
+
+        let revoked = result.as_ref().ok().map(|client_ids| {
+            if client_ids.is_empty() {
+                String::from("Revoked client certificate")
+            } else {
+                let client_ids = client_ids.iter().map(|client_id| client_id.0.as_str()).collect::<Vec<_>>();
+                format!("Revoked OIDC clients [{}]", client_ids.join(", "))
+            }
+        });
+        audit.resource(holder_resource_id(holder))
+            .after(revoked)
+            .finish(&result);
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(RevokeClientResponse {
+                    reply: Some(revoke_client_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(client_ids) => {
+                Ok(Response::new(RevokeClientResponse {
+                    reply: Some(revoke_client_response::Reply::Success(
+                        RevokeClientSuccess {
+                            holder: Some(holder.into()),
+                            client_ids: client_ids.into_iter().map(Into::into).collect(),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
     #[tracing::instrument(skip(self, request), level="trace")]
     async fn list_devices(&self, request: Request<ListDevicesRequest>) -> Result<Response<ListDevicesResponse>, Status> {
 
         trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Viewer)?;
+        let requester = Requester::from_request(&request);
 
         let devices = actions::list_devices(ListDevicesParams {
             resources_manager: Arc::clone(&self.resources_manager),
+            requester,
         }).await.expect("Devices should be listable");
 
         let devices = devices.into_iter()
@@ -213,23 +416,117 @@ impl PeerManagerService for PeerManagerFacade {
         Ok(Response::new(ListDevicesResponse { devices }))
     }
 
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn list_executor_runs(&self, request: Request<ListExecutorRunsRequest>) -> Result<Response<ListExecutorRunsResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Viewer)?;
+        let requester = Requester::from_request(&request);
+
+        let request = request.into_inner();
+        let peer_id: Option<PeerId> = request.peer_id
+            .map(PeerId::try_from)
+            .transpose()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'peer_id' is not valid: {cause}")))?;
+
+        let result =
+            actions::list_executor_runs(ListExecutorRunsParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                peer_id,
+                requester,
+            }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListExecutorRunsResponse {
+                    reply: Some(list_executor_runs_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(runs) => {
+                Ok(Response::new(ListExecutorRunsResponse {
+                    reply: Some(list_executor_runs_response::Reply::Success(
+                        ListExecutorRunsSuccess {
+                            runs: runs.into_iter().map(From::from).collect()
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn list_result_artifacts(&self, request: Request<ListResultArtifactsRequest>) -> Result<Response<ListResultArtifactsResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Viewer)?;
+        let requester = Requester::from_request(&request);
+
+        let request = request.into_inner();
+        let peer_id: Option<PeerId> = request.peer_id
+            .map(PeerId::try_from)
+            .transpose()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'peer_id' is not valid: {cause}")))?;
+        let cluster_id: Option<ClusterId> = request.cluster_id
+            .map(ClusterId::try_from)
+            .transpose()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'cluster_id' is not valid: {cause}")))?;
+        let run_id: Option<ExecutorRunId> = request.run_id
+            .map(ExecutorRunId::try_from)
+            .transpose()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'run_id' is not valid: {cause}")))?;
+
+        let result =
+            actions::list_result_artifacts(ListResultArtifactsParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                peer_id,
+                cluster_id,
+                run_id,
+                requester,
+            }).await;
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListResultArtifactsResponse {
+                    reply: Some(list_result_artifacts_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(artifacts) => {
+                Ok(Response::new(ListResultArtifactsResponse {
+                    reply: Some(list_result_artifacts_response::Reply::Success(
+                        ListResultArtifactsSuccess {
+                            artifacts: artifacts.into_iter().map(From::from).collect()
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
     #[tracing::instrument(skip(self, request), level="trace")]
     async fn generate_peer_setup(&self, request: Request<GeneratePeerSetupRequest>) -> Result<Response<GeneratePeerSetupResponse>, Status> { // TODO: Refactor error types.
         trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Admin)?;
+        let audit = self.audit_log.start(&request, "generate_peer_setup");
 
         let message = request.into_inner();
         let response = match message.peer {
             Some(peer_id) => {
                 let peer_id = PeerId::try_from(peer_id)
                     .map_err(|cause| Status::invalid_argument(format!("PeerId could not be converted: {}", cause)))?;
-                let setup = actions::generate_peer_setup(GeneratePeerSetupParams {
+                let result = actions::generate_peer_setup(GeneratePeerSetupParams {
                     resources_manager: Arc::clone(&self.resources_manager),
                     peer: peer_id,
                     carl_url: Clone::clone(&self.carl_url),
                     ca: Clone::clone(&self.ca),
                     vpn: Clone::clone(&self.vpn),
                     oidc_registration_client: self.oidc_registration_client.clone(),
-                }, message.user_id).await.map_err(|cause| Status::internal(format!("Peer setup could not be created: {}", cause)))?;
+                    enrollment_options: Clone::clone(&self.options.enrollment),
+                }, message.user_id).await;
+
+                audit.resource(Id::from(peer_id))
+                    .finish(&result);
+
+                let setup = result.map_err(|cause| Status::internal(format!("Peer setup could not be created: {}", cause)))?;
 
                 peer_manager::generate_peer_setup_response::Reply::Success(peer_manager::GeneratePeerSetupSuccess { peer: Some(peer_id.into()), setup: Some(setup.into()) })
             }
@@ -243,14 +540,21 @@ impl PeerManagerService for PeerManagerFacade {
 
     async fn generate_cleo_setup(&self, request: Request<GenerateCleoSetupRequest>) -> Result<Response<GenerateCleoSetupResponse>, Status> {
        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Admin)?;
+        let audit = self.audit_log.start(&request, "generate_cleo_setup");
         
         let cleo_id = CleoId::random();
-        let setup = actions::generate_cleo_setup(GenerateCleoSetupParams {
+        let result = actions::generate_cleo_setup(GenerateCleoSetupParams {
             cleo: cleo_id,
             carl_url: Clone::clone(&self.carl_url),
             ca: Clone::clone(&self.ca),
             oidc_registration_client: self.oidc_registration_client.clone(),
-        }, request.into_inner().user_id).await.map_err(|cause| Status::internal(format!("Cleo setup could not be created: {}", cause)))?;
+        }, request.into_inner().user_id).await;
+
+        audit.resource(Id::from(cleo_id))
+            .finish(&result);
+
+        let setup = result.map_err(|cause| Status::internal(format!("Cleo setup could not be created: {}", cause)))?;
         
         let response = generate_cleo_setup_response::Reply::Success(GenerateCleoSetupSuccess { 
             cleo: Some(cleo_id.into()), 
@@ -261,9 +565,19 @@ impl PeerManagerService for PeerManagerFacade {
     }
 }
 
+fn holder_resource_id(holder: CredentialsHolder) -> Id {
+    match holder {
+        CredentialsHolder::Peer(peer_id) => Id::from(peer_id),
+        CredentialsHolder::Cleo(cleo_id) => Id::from(cleo_id),
+    }
+}
+
 #[derive(Clone)]
 pub struct PeerManagerFacadeOptions {
     pub bridge_name_default: NetworkInterfaceName,
+    /// Credentials for private container registries, which executors reference by name.
+    pub registry_credentials: Vec<ContainerRegistryCredential>,
+    pub enrollment: EnrollmentOptions,
 }
 impl PeerManagerFacadeOptions {
     pub fn load(config: &config::Config) -> Result<Self, PeerManagerFacadeOptionsLoadError> {
@@ -273,10 +587,46 @@ impl PeerManagerFacadeOptions {
         let bridge_name_default = NetworkInterfaceName::try_from(bridge_name_default)
             .map_err(|cause| PeerManagerFacadeOptionsLoadError { message: cause.to_string() })?;
 
+        let registry_credentials = Self::load_registry_credentials(config)?;
+
+        let enrollment = EnrollmentOptions::load(config)
+            .map_err(|cause| PeerManagerFacadeOptionsLoadError { message: cause.to_string() })?;
+
         Ok(PeerManagerFacadeOptions {
             bridge_name_default,
+            registry_credentials,
+            enrollment,
         })
     }
+
+    /// Loads the registry credentials from the tables below `executor.registry.credentials`, each named after its credential.
+    fn load_registry_credentials(config: &config::Config) -> Result<Vec<ContainerRegistryCredential>, PeerManagerFacadeOptionsLoadError> {
+        let credentials = match config.get_table("executor.registry.credentials") {
+            Ok(credentials) => credentials,
+            Err(config::ConfigError::NotFound(_)) => return Ok(Vec::new()),
+            Err(cause) => return Err(PeerManagerFacadeOptionsLoadError { message: cause.to_string() }),
+        };
+
+        credentials.into_iter()
+            .map(|(name, value)| {
+                let error = |message: String| PeerManagerFacadeOptionsLoadError { message: format!("Invalid registry credential '{name}': {message}") };
+
+                let mut fields = value.into_table()
+                    .map_err(|cause| error(cause.to_string()))?;
+                let mut field = |key: &str| fields.remove(key)
+                    .ok_or_else(|| error(format!("Field '{key}' is not set.")))
+                    .and_then(|value| value.into_string().map_err(|cause| error(cause.to_string())));
+
+                let server = field("server")?;
+                let username = field("username")?;
+                let password = field("password")?;
+                let name = ContainerRegistryCredentialName::try_from(Clone::clone(&name))
+                    .map_err(|cause| error(cause.to_string()))?;
+
+                Ok(ContainerRegistryCredential { name, server, username, password })
+            })
+            .collect()
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
@@ -295,12 +645,15 @@ mod tests {
     use url::Url;
 
     use opendut_types::peer::{PeerLocation, PeerName, PeerNetworkDescriptor};
-    use opendut_types::peer::executor::{container::{ContainerCommand, ContainerImage, ContainerName, Engine}, ExecutorKind, ExecutorDescriptors, ExecutorDescriptor};
+    use opendut_types::peer::executor::{container::{ContainerCommand, ContainerImage, ContainerImagePullPolicy, ContainerName, Engine}, ExecutorKind, ExecutorDescriptors, ExecutorDescriptor};
     use opendut_types::proto;
     use opendut_types::topology::Topology;
     use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceName};
     use opendut_auth_tests::registration_client;
 
+    use crate::audit::AuditLog;
+    use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions};
+    use crate::secrets::SecretCipher;
     use crate::resources::manager::ResourcesManager;
     use crate::vpn::Vpn;
 
@@ -328,6 +681,14 @@ mod tests {
             Url::parse("https://example.com:1234").unwrap(),
             get_cert(),
             Some(registration_client.await),
+            PeerMessagingBroker::new(
+                Arc::clone(&resources_manager),
+                Arc::new(SecretCipher::generate()?),
+                PeerMessagingBrokerOptions::load(&settings.config)?,
+            ),
+            RevokedClients::default(),
+            PeerCertificates::default(),
+            AuditLog::disabled(),
             PeerManagerFacadeOptions::load(&settings.config)?
         );
 
@@ -353,12 +714,23 @@ mod tests {
                             engine: Engine::Docker,
                             name: ContainerName::Empty,
                             image: ContainerImage::try_from("testUrl").unwrap(),
+                            pull_policy: ContainerImagePullPolicy::IfNotPresent,
+                            digest: None,
+                            registry_credential: None,
                             volumes: vec![],
                             devices: vec![],
                             envs: vec![],
                             ports: vec![],
                             command: ContainerCommand::Default,
                             args: vec![],
+                            can_databases: vec![],
+                            cpus: None,
+                            memory: None,
+                            pids_limit: None,
+                            network_mode: Default::default(),
+                            cap_add: vec![],
+                            cap_drop: vec![],
+                            read_only_rootfs: false,
                         },
                         results_url: None,
                     }
@@ -430,6 +802,14 @@ mod tests {
             Url::parse("https://example.com:1234").unwrap(),
             get_cert(),
             Some(registration_client.await),
+            PeerMessagingBroker::new(
+                Arc::clone(&resources_manager),
+                Arc::new(SecretCipher::generate()?),
+                PeerMessagingBrokerOptions::load(&settings.config)?,
+            ),
+            RevokedClients::default(),
+            PeerCertificates::default(),
+            AuditLog::disabled(),
             PeerManagerFacadeOptions::load(&settings.config)?
         );
 
@@ -473,6 +853,14 @@ mod tests {
             Url::parse("https://example.com:1234").unwrap(),
             get_cert(),
             Some(registration_client.await),
+            PeerMessagingBroker::new(
+                Arc::clone(&resources_manager),
+                Arc::new(SecretCipher::generate()?),
+                PeerMessagingBrokerOptions::load(&settings.config)?,
+            ),
+            RevokedClients::default(),
+            PeerCertificates::default(),
+            AuditLog::disabled(),
             PeerManagerFacadeOptions::load(&settings.config)?
         );
 
@@ -502,4 +890,33 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn should_load_registry_credentials_from_the_configuration() -> Result<()> {
+        let config = config::Config::builder()
+            .add_source(config::File::from_str(r#"
+                [network.tls.client.auth]
+                enabled = false
+
+                [peer]
+                ethernet.bridge.name.default = "br-opendut"
+                enrollment.token.lifetime.ms = 900000
+
+                [executor.registry.credentials.ci-registry]
+                server = "registry.example.com"
+                username = "ci"
+                password = "secret"
+            "#, config::FileFormat::Toml))
+            .build()?;
+
+        let options = PeerManagerFacadeOptions::load(&config)?;
+
+        assert_that!(options.registry_credentials, elements_are![eq(ContainerRegistryCredential {
+            name: ContainerRegistryCredentialName::try_from("ci-registry")?,
+            server: String::from("registry.example.com"),
+            username: String::from("ci"),
+            password: String::from("secret"),
+        })]);
+        Ok(())
+    }
 }
@@ -2,8 +2,10 @@ use std::net::IpAddr;
 use std::ops::Not;
 use std::pin::Pin;
 use std::str::FromStr;
+use std::sync::Arc;
 
 use futures::StreamExt;
+use tokio::sync::{broadcast, mpsc};
 use tokio_stream::Stream;
 use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status, Streaming};
@@ -12,20 +14,35 @@ use tonic_web::CorsGrpcWeb;
 use tracing::{error, info, trace, warn};
 use uuid::Uuid;
 
-use opendut_carl_api::proto::services::peer_messaging_broker::{Downstream, ListPeersRequest, ListPeersResponse, Upstream};
+use opendut_auth::registration::client::RegistrationClientRef;
+use opendut_carl_api::proto::services::peer_messaging_broker::{Downstream, ExecuteExecutorCommandRequest, ExecuteExecutorCommandResponse, ListPeersRequest, ListPeersResponse, StreamExecutorLogsRequest, StreamExecutorLogsResponse, Upstream};
 use opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_broker_server::PeerMessagingBrokerServer;
 use opendut_carl_api::proto::services::peer_messaging_broker::upstream;
 use opendut_types::peer::PeerId;
+use opendut_types::peer::executor::ExecutorCommand;
+use opendut_types::resources::Id;
 use opendut_util::telemetry::logging::NonDisclosingRequestExtension;
-use crate::peer::broker::{OpenError, PeerMessagingBrokerRef};
+use crate::actions;
+use crate::actions::{ExecuteExecutorCommandError, ExecuteExecutorCommandParams};
+use crate::audit::AuditLogRef;
+use crate::auth::ownership::Requester;
+use crate::auth::peer_certificates::PeerCertificates;
+use crate::auth::peer_identity::{PeerIdentityError, StreamIdentity, verify_stream_identity};
+use crate::auth::permission::{require_role, Role};
+use crate::peer::broker::{Error as BrokerError, OpenError, PeerMessagingBrokerRef};
+use crate::resources::manager::ResourcesManagerRef;
 
 pub struct PeerMessagingBrokerFacade {
+    resources_manager: ResourcesManagerRef,
     peer_messaging_broker: PeerMessagingBrokerRef,
+    oidc_registration_client: Option<RegistrationClientRef>,
+    peer_certificates: PeerCertificates,
+    audit_log: AuditLogRef,
 }
 
 impl PeerMessagingBrokerFacade {
-    pub fn new(peer_messaging_broker: PeerMessagingBrokerRef) -> Self {
-        Self { peer_messaging_broker }
+    pub fn new(resources_manager: ResourcesManagerRef, peer_messaging_broker: PeerMessagingBrokerRef, oidc_registration_client: Option<RegistrationClientRef>, peer_certificates: PeerCertificates, audit_log: AuditLogRef) -> Self {
+        Self { resources_manager, peer_messaging_broker, oidc_registration_client, peer_certificates, audit_log }
     }
     pub fn into_grpc_service(self) -> CorsGrpcWeb<PeerMessagingBrokerServer<Self>> {
         tonic_web::enable(PeerMessagingBrokerServer::new(self))
@@ -38,8 +55,10 @@ impl opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_br
     async fn list_peers(&self, request: Request<ListPeersRequest>) -> Result<Response<ListPeersResponse>, Status> {
 
         trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Viewer)?;
+        let requester = Requester::from_request(&request);
 
-        let peers = self.peer_messaging_broker.list_peers().await;
+        let peers = self.peer_messaging_broker.list_peers(requester).await;
 
         let peers = peers.into_iter()
             .map(From::from)
@@ -63,6 +82,18 @@ impl opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_br
                 Status::invalid_argument(message)
             })?;
 
+        verify_stream_identity(StreamIdentity::of(&request), peer_id, &self.resources_manager, &self.peer_certificates, self.oidc_registration_client.as_ref()).await
+            .map_err(|cause| match cause {
+                PeerIdentityError::Internal { .. } => {
+                    error!("{cause}");
+                    Status::internal(cause.to_string())
+                }
+                _ => {
+                    warn!("Rejected opening the stream of peer <{peer_id}>: {cause}");
+                    Status::permission_denied(format!("CARL says, {cause}"))
+                }
+            })?;
+
         let remote_host = extract_remote_host(request.metadata())
             .map_err(|message| {
                 warn!("Error while parsing remote host address from client request: {message}");
@@ -86,7 +117,9 @@ impl opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_br
                             if matches!(message, upstream::Message::Ping(_)).not() {
                                 trace!("Received message from client <{}>: {:?}", peer_id, message);
                             }
-                            tx_inbound.send(message).await.unwrap();
+                            if tx_inbound.send(message).await.is_err() {
+                                break; //peer was disconnected
+                            }
                         } else {
                             warn!("Ignoring empty message from client <{}>: {:?}", peer_id, upstream);
                         }
@@ -112,6 +145,110 @@ impl opendut_carl_api::proto::services::peer_messaging_broker::peer_messaging_br
 
         Ok(Response::new(Box::pin(outbound)))
     }
+
+    type StreamExecutorLogsStream = Pin<Box<dyn Stream<Item = Result<StreamExecutorLogsResponse, Status>> + Send>>;
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn stream_executor_logs(&self, request: Request<StreamExecutorLogsRequest>) -> Result<Response<Self::StreamExecutorLogsStream>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Viewer)?;
+        let requester = Requester::from_request(&request);
+
+        let StreamExecutorLogsRequest { peer_id, executor_name, follow } = request.into_inner();
+
+        let peer_id: PeerId = peer_id
+            .ok_or_else(|| Status::invalid_argument("PeerId is required."))?
+            .try_into()
+            .map_err(|_| Status::invalid_argument("PeerId could not be converted."))?;
+
+        let subscription = self.peer_messaging_broker.subscribe_executor_logs(peer_id, &executor_name, requester).await
+            .map_err(|cause| match cause {
+                BrokerError::ExecutorRunNotFound { .. } => Status::not_found(cause.to_string()),
+                _ => Status::internal(cause.to_string()),
+            })?;
+
+        let (tx, rx) = mpsc::channel(16);
+
+        tokio::spawn(async move {
+            let buffered = StreamExecutorLogsResponse {
+                lines: subscription.buffered.into_iter().map(From::from).collect(),
+            };
+            if tx.send(buffered).await.is_err() {
+                return;
+            }
+
+            let Some(mut live) = subscription.live.filter(|_| follow) else {
+                return;
+            };
+
+            loop {
+                match live.recv().await {
+                    Ok(line) => {
+                        let response = StreamExecutorLogsResponse { lines: vec![line.into()] };
+                        if tx.send(response).await.is_err() {
+                            break;
+                        }
+                    }
+                    Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                        warn!("Log stream of executor '{executor_name}' on peer <{peer_id}> lagged behind and skipped {skipped} lines.");
+                    }
+                    Err(broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+
+        let outbound = ReceiverStream::new(rx)
+            .map(Ok);
+
+        Ok(Response::new(Box::pin(outbound)))
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn execute_executor_command(&self, request: Request<ExecuteExecutorCommandRequest>) -> Result<Response<ExecuteExecutorCommandResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Operator)?;
+        let audit = self.audit_log.start(&request, "execute_executor_command");
+        let requester = Requester::from_request(&request);
+
+        let ExecuteExecutorCommandRequest { peer_id, executor_name, command } = request.into_inner();
+
+        let peer_id: PeerId = peer_id
+            .ok_or_else(|| Status::invalid_argument("PeerId is required."))?
+            .try_into()
+            .map_err(|_| Status::invalid_argument("PeerId could not be converted."))?;
+
+        let command: ExecutorCommand = command
+            .ok_or_else(|| Status::invalid_argument("ExecutorCommand is required."))?
+            .try_into()
+            .map_err(|_| Status::invalid_argument("ExecutorCommand could not be converted."))?;
+
+        let after = format!("Command '{command}' for executor '{executor_name}'");
+
+        let result = actions::execute_executor_command(ExecuteExecutorCommandParams {
+            resources_manager: Arc::clone(&self.resources_manager),
+            peer_messaging_broker: Arc::clone(&self.peer_messaging_broker),
+            peer_id,
+            executor_name,
+            command,
+            requester,
+        }).await;
+
+        audit.resource(Id::from(peer_id))
+            .after(Some(after))
+            .finish(&result);
+
+        result
+            .map_err(|cause| match cause {
+                ExecuteExecutorCommandError::PeerNotFound { .. }
+                | ExecuteExecutorCommandError::ExecutorNotFound { .. } => Status::not_found(cause.to_string()),
+                ExecuteExecutorCommandError::PeerNotConnected { .. } => Status::failed_precondition(cause.to_string()),
+                ExecuteExecutorCommandError::Internal { .. } => Status::internal(cause.to_string()),
+            })?;
+
+        Ok(Response::new(ExecuteExecutorCommandResponse {}))
+    }
 }
 
 
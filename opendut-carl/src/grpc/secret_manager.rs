@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::trace;
+
+use opendut_carl_api::proto::services::secret_manager::*;
+use opendut_carl_api::proto::services::secret_manager::secret_manager_server::{SecretManager as SecretManagerService, SecretManagerServer};
+use opendut_types::secret::{Secret, SecretName};
+use opendut_util::telemetry::logging::NonDisclosingRequestExtension;
+
+use crate::actions;
+use crate::actions::{DeleteSecretParams, ListSecretsParams, StoreSecretParams};
+use crate::audit::AuditLogRef;
+use crate::audit::summary::AuditSummary;
+use crate::auth::ownership::{Ownership, Requester};
+use crate::auth::permission::{require_role, Role};
+use crate::grpc::extract;
+use crate::resources::IntoId;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::secrets::{EncryptedSecret, SecretCipherRef, SecretStore};
+
+pub struct SecretManagerFacade {
+    resources_manager: ResourcesManagerRef,
+    secret_cipher: SecretCipherRef,
+    secret_store: SecretStore,
+    audit_log: AuditLogRef,
+}
+
+impl SecretManagerFacade {
+
+    pub fn new(resources_manager: ResourcesManagerRef, secret_cipher: SecretCipherRef, secret_store: SecretStore, audit_log: AuditLogRef) -> Self {
+        Self {
+            resources_manager,
+            secret_cipher,
+            secret_store,
+            audit_log,
+        }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<SecretManagerServer<Self>> {
+        tonic_web::enable(SecretManagerServer::new(self))
+    }
+}
+
+#[tonic::async_trait]
+impl SecretManagerService for SecretManagerFacade {
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn store_secret(&self, request: Request<StoreSecretRequest>) -> Result<Response<StoreSecretResponse>, Status> {
+
+        // the request is not logged, since it contains the value of the secret
+        trace!("Received request to store a secret.");
+        require_role(&request, Role::Admin)?;
+        let audit = self.audit_log.start(&request, "store_secret");
+        let requester = Requester::from_request(&request);
+
+        let request = request.into_inner();
+        let secret: Secret = extract!(request.secret)?;
+        if request.owner.as_ref().is_some_and(String::is_empty) {
+            return Err(Status::invalid_argument("Field 'owner' must not be empty."));
+        }
+        let ownership = request.owner.map(|owner| Ownership {
+            owner,
+            group: request.group,
+        });
+
+        let secret_name = Clone::clone(&secret.name);
+        let before = self.resources_manager.get::<EncryptedSecret>(Clone::clone(&secret_name)).await;
+
+        let result =
+            actions::store_secret(StoreSecretParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                secret_cipher: Arc::clone(&self.secret_cipher),
+                secret_store: Clone::clone(&self.secret_store),
+                secret,
+                ownership,
+                requester,
+            }).await;
+
+        audit.resource(IntoId::<EncryptedSecret>::into_id(Clone::clone(&secret_name)))
+            .before(before.map(|secret| secret.name.audit_summary()))
+            .after(result.is_ok().then(|| secret_name.audit_summary()))
+            .finish(&result);
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(StoreSecretResponse {
+                    reply: Some(store_secret_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(secret_name) => {
+                Ok(Response::new(StoreSecretResponse {
+                    reply: Some(store_secret_response::Reply::Success(
+                        StoreSecretSuccess {
+                            secret_name: Some(secret_name.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn delete_secret(&self, request: Request<DeleteSecretRequest>) -> Result<Response<DeleteSecretResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Admin)?;
+        let audit = self.audit_log.start(&request, "delete_secret");
+
+        let request = request.into_inner();
+        let secret_name: SecretName = extract!(request.secret_name)?;
+
+        let before = self.resources_manager.get::<EncryptedSecret>(Clone::clone(&secret_name)).await;
+
+        let result =
+            actions::delete_secret(DeleteSecretParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                secret_store: Clone::clone(&self.secret_store),
+                secret_name: Clone::clone(&secret_name),
+            }).await;
+
+        audit.resource(IntoId::<EncryptedSecret>::into_id(secret_name))
+            .before(before.map(|secret| secret.name.audit_summary()))
+            .finish(&result);
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DeleteSecretResponse {
+                    reply: Some(delete_secret_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(secret_name) => {
+                Ok(Response::new(DeleteSecretResponse {
+                    reply: Some(delete_secret_response::Reply::Success(
+                        DeleteSecretSuccess {
+                            secret_name: Some(secret_name.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn list_secrets(&self, request: Request<ListSecretsRequest>) -> Result<Response<ListSecretsResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Viewer)?;
+
+        let result =
+            actions::list_secrets(ListSecretsParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+            }).await
+            .map(|secret_names| secret_names.into_iter()
+                .map(From::from)
+                .collect::<Vec<_>>()
+            );
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListSecretsResponse {
+                    reply: Some(list_secrets_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(secret_names) => {
+                Ok(Response::new(ListSecretsResponse {
+                    reply: Some(list_secrets_response::Reply::Success(
+                        ListSecretsSuccess {
+                            secret_names
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+}
@@ -0,0 +1,289 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tonic::{Request, Response, Status};
+use tonic_web::CorsGrpcWeb;
+use tracing::trace;
+
+use opendut_carl_api::proto::services::service_account_manager::*;
+use opendut_carl_api::proto::services::service_account_manager::service_account_manager_server::{ServiceAccountManager as ServiceAccountManagerService, ServiceAccountManagerServer};
+use opendut_types::resources::Id;
+use opendut_types::service_account::{ApiTokenId, ServiceAccountDescriptor, ServiceAccountId, ServiceAccountName, ServiceAccountRole};
+use opendut_util::telemetry::logging::NonDisclosingRequestExtension;
+
+use crate::actions;
+use crate::actions::{CreateApiTokenParams, CreateServiceAccountParams, DeleteServiceAccountParams, ListApiTokensParams, ListServiceAccountsParams, RevokeApiTokenParams};
+use crate::audit::AuditLogRef;
+use crate::audit::summary::AuditSummary;
+use crate::auth::api_token::StoredApiToken;
+use crate::auth::permission::{require_role, Role};
+use crate::grpc::extract;
+use crate::resources::manager::ResourcesManagerRef;
+
+pub struct ServiceAccountManagerFacade {
+    resources_manager: ResourcesManagerRef,
+    audit_log: AuditLogRef,
+}
+
+impl ServiceAccountManagerFacade {
+
+    pub fn new(resources_manager: ResourcesManagerRef, audit_log: AuditLogRef) -> Self {
+        Self {
+            resources_manager,
+            audit_log,
+        }
+    }
+
+    pub fn into_grpc_service(self) -> CorsGrpcWeb<ServiceAccountManagerServer<Self>> {
+        tonic_web::enable(ServiceAccountManagerServer::new(self))
+    }
+}
+
+#[tonic::async_trait]
+impl ServiceAccountManagerService for ServiceAccountManagerFacade {
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn create_service_account(&self, request: Request<CreateServiceAccountRequest>) -> Result<Response<CreateServiceAccountResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Admin)?;
+        let audit = self.audit_log.start(&request, "create_service_account");
+
+        let request = request.into_inner();
+        let name: ServiceAccountName = extract!(request.name)?;
+        let role: ServiceAccountRole = extract!(request.role)?;
+        let group = request.group;
+
+        let result =
+            actions::create_service_account(CreateServiceAccountParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                name,
+                role,
+                group,
+            }).await;
+
+        let audit = match &result {
+            Ok(service_account) => audit.resource(Id::from(service_account.id.0))
+                .after(Some(service_account.audit_summary())),
+            Err(_) => audit,
+        };
+        audit.finish(&result);
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(CreateServiceAccountResponse {
+                    reply: Some(create_service_account_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(descriptor) => {
+                Ok(Response::new(CreateServiceAccountResponse {
+                    reply: Some(create_service_account_response::Reply::Success(
+                        CreateServiceAccountSuccess {
+                            descriptor: Some(descriptor.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn delete_service_account(&self, request: Request<DeleteServiceAccountRequest>) -> Result<Response<DeleteServiceAccountResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Admin)?;
+        let audit = self.audit_log.start(&request, "delete_service_account");
+
+        let request = request.into_inner();
+        let service_account_id: ServiceAccountId = extract!(request.service_account_id)?;
+
+        let before = self.resources_manager.get::<ServiceAccountDescriptor>(service_account_id).await;
+
+        let result =
+            actions::delete_service_account(DeleteServiceAccountParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                service_account_id,
+            }).await;
+
+        audit.resource(Id::from(service_account_id.0))
+            .before(before.map(|service_account| service_account.audit_summary()))
+            .finish(&result);
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(DeleteServiceAccountResponse {
+                    reply: Some(delete_service_account_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(descriptor) => {
+                Ok(Response::new(DeleteServiceAccountResponse {
+                    reply: Some(delete_service_account_response::Reply::Success(
+                        DeleteServiceAccountSuccess {
+                            descriptor: Some(descriptor.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn list_service_accounts(&self, request: Request<ListServiceAccountsRequest>) -> Result<Response<ListServiceAccountsResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Admin)?;
+
+        let result =
+            actions::list_service_accounts(ListServiceAccountsParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+            }).await
+            .map(|service_accounts| service_accounts.into_iter()
+                .map(From::from)
+                .collect::<Vec<_>>()
+            );
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListServiceAccountsResponse {
+                    reply: Some(list_service_accounts_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(service_accounts) => {
+                Ok(Response::new(ListServiceAccountsResponse {
+                    reply: Some(list_service_accounts_response::Reply::Success(
+                        ListServiceAccountsSuccess {
+                            service_accounts
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn create_api_token(&self, request: Request<CreateApiTokenRequest>) -> Result<Response<CreateApiTokenResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Admin)?;
+        let audit = self.audit_log.start(&request, "create_api_token");
+
+        let request = request.into_inner();
+        let service_account_id: ServiceAccountId = extract!(request.service_account_id)?;
+        if request.lifetime_seconds == 0 {
+            return Err(Status::invalid_argument("Field 'lifetime_seconds' must be greater than zero"));
+        }
+        let lifetime = Duration::from_secs(request.lifetime_seconds);
+
+        let result =
+            actions::create_api_token(CreateApiTokenParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                service_account_id,
+                lifetime,
+            }).await;
+
+        let audit = match &result {
+            Ok((descriptor, _)) => audit.resource(Id::from(descriptor.id.0))
+                .after(Some(descriptor.audit_summary())),
+            Err(_) => audit.resource(Id::from(service_account_id.0)),
+        };
+        audit.finish(&result);
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(CreateApiTokenResponse {
+                    reply: Some(create_api_token_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok((descriptor, token)) => {
+                Ok(Response::new(CreateApiTokenResponse {
+                    reply: Some(create_api_token_response::Reply::Success(
+                        CreateApiTokenSuccess {
+                            descriptor: Some(descriptor.into()),
+                            token: Some(token.into()),
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn revoke_api_token(&self, request: Request<RevokeApiTokenRequest>) -> Result<Response<RevokeApiTokenResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Admin)?;
+        let audit = self.audit_log.start(&request, "revoke_api_token");
+
+        let request = request.into_inner();
+        let token_id: ApiTokenId = extract!(request.token_id)?;
+
+        let before = self.resources_manager.get::<StoredApiToken>(token_id).await;
+
+        let result =
+            actions::revoke_api_token(RevokeApiTokenParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                token_id,
+            }).await;
+
+        audit.resource(Id::from(token_id.0))
+            .before(before.map(|token| token.descriptor.audit_summary()))
+            .finish(&result);
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(RevokeApiTokenResponse {
+                    reply: Some(revoke_api_token_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(descriptor) => {
+                Ok(Response::new(RevokeApiTokenResponse {
+                    reply: Some(revoke_api_token_response::Reply::Success(
+                        RevokeApiTokenSuccess {
+                            descriptor: Some(descriptor.into())
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+
+    #[tracing::instrument(skip(self, request), level="trace")]
+    async fn list_api_tokens(&self, request: Request<ListApiTokensRequest>) -> Result<Response<ListApiTokensResponse>, Status> {
+
+        trace!("Received request: {}", request.debug_output());
+        require_role(&request, Role::Admin)?;
+
+        let request = request.into_inner();
+        let service_account_id = request.service_account_id
+            .map(ServiceAccountId::try_from)
+            .transpose()
+            .map_err(|cause| Status::invalid_argument(format!("Field 'service_account_id' is not valid: {cause}")))?;
+
+        let result =
+            actions::list_api_tokens(ListApiTokensParams {
+                resources_manager: Arc::clone(&self.resources_manager),
+                service_account_id,
+            }).await
+            .map(|tokens| tokens.into_iter()
+                .map(From::from)
+                .collect::<Vec<_>>()
+            );
+
+        match result {
+            Err(error) => {
+                Ok(Response::new(ListApiTokensResponse {
+                    reply: Some(list_api_tokens_response::Reply::Failure(error.into()))
+                }))
+            }
+            Ok(tokens) => {
+                Ok(Response::new(ListApiTokensResponse {
+                    reply: Some(list_api_tokens_response::Reply::Success(
+                        ListApiTokensSuccess {
+                            tokens
+                        }
+                    ))
+                }))
+            }
+        }
+    }
+}
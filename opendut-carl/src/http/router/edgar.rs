@@ -1,9 +1,16 @@
+use std::sync::Arc;
+
 use axum::body::StreamBody;
 use axum::extract::{Path, State};
+use axum::Json;
 use axum::response::IntoResponse;
 use axum_server_dual_protocol::tokio_util::io::ReaderStream;
 use http::{header, StatusCode};
-use crate::http::state::CarlInstallDirectory;
+use opendut_types::peer::enrollment::EnrollmentRequest;
+use crate::actions;
+use crate::actions::{RedeemEnrollmentTokenError, RedeemEnrollmentTokenParams};
+use crate::http::state::{CarlInstallDirectory, PeerEnrollmentState};
+use crate::peer::enrollment::RedeemEnrollmentError;
 use crate::util::{EDGAR_IDENTIFIER, EdgarArch};
 
 pub async fn download_edgar(
@@ -32,6 +39,36 @@ pub async fn download_edgar(
     (headers, body).into_response()
 }
 
+/// Exchanges the enrollment token of a setup string for the credentials of the peer.
+/// Not protected by OIDC, since EDGAR has no credentials before redeeming its token.
+pub async fn redeem_enrollment_token(
+    State(peer_enrollment): State<PeerEnrollmentState>,
+    Json(request): Json<EnrollmentRequest>,
+) -> impl IntoResponse {
+
+    let result = actions::redeem_enrollment_token(RedeemEnrollmentTokenParams {
+        resources_manager: Arc::clone(&peer_enrollment.resources_manager),
+        peer_id: request.peer_id,
+        token: request.token,
+        oidc_registration_client: peer_enrollment.oidc_registration_client,
+        mutual_tls: peer_enrollment.mutual_tls,
+        revoked_clients: peer_enrollment.revoked_clients,
+        peer_certificates: peer_enrollment.peer_certificates,
+    }).await;
+
+    match result {
+        Ok(enrollment_response) => Json(enrollment_response).into_response(),
+        Err(RedeemEnrollmentTokenError::Rejected { cause, .. }) => {
+            let status = match cause {
+                RedeemEnrollmentError::InvalidToken => StatusCode::UNAUTHORIZED,
+                RedeemEnrollmentError::AlreadyRedeemed | RedeemEnrollmentError::Expired => StatusCode::GONE,
+            };
+            (status, cause.to_string()).into_response()
+        }
+        Err(RedeemEnrollmentTokenError::Internal { .. }) => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+    }
+}
+
 #[cfg(test)]
 mod test {
     use std::fs;
@@ -8,12 +8,19 @@ use serde::Serialize;
 use shadow_rs::formatcp;
 use url::Url;
 use opendut_auth::confidential::config::ConfidentialClientConfigData;
+use opendut_auth::registration::client::RegistrationClientRef;
+
+use crate::auth::client_certificate::MutualTlsEnrollment;
+use crate::auth::peer_certificates::PeerCertificates;
+use crate::auth::revocation::RevokedClients;
+use crate::resources::manager::ResourcesManagerRef;
 
 
 #[derive(Clone)]
 pub struct HttpState {
     pub lea_config: LeaConfig,
     pub carl_installation_directory: CarlInstallDirectory,
+    pub peer_enrollment: PeerEnrollmentState,
 }
 
 #[derive(Clone, Debug, Serialize)]
@@ -83,3 +90,19 @@ impl FromRef<HttpState> for CarlInstallDirectory {
         Clone::clone(&app_state.carl_installation_directory)
     }
 }
+
+/// Everything needed to redeem the enrollment tokens of peer setup strings.
+#[derive(Clone)]
+pub struct PeerEnrollmentState {
+    pub resources_manager: ResourcesManagerRef,
+    pub oidc_registration_client: Option<RegistrationClientRef>,
+    pub mutual_tls: Option<MutualTlsEnrollment>,
+    pub revoked_clients: RevokedClients,
+    pub peer_certificates: PeerCertificates,
+}
+
+impl FromRef<HttpState> for PeerEnrollmentState {
+    fn from_ref(app_state: &HttpState) -> Self {
+        Clone::clone(&app_state.peer_enrollment)
+    }
+}
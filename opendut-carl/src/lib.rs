@@ -6,7 +6,7 @@ use std::sync::Arc;
 
 use ::http::{header::CONTENT_TYPE, Request};
 use anyhow::{anyhow, Context, Result};
-use axum::routing::get;
+use axum::routing::{get, post};
 use axum_server::tls_rustls::RustlsConfig;
 use axum_server_dual_protocol::ServerExt;
 use futures::future::BoxFuture;
@@ -26,17 +26,25 @@ use opendut_util::{telemetry, project};
 use opendut_util::telemetry::logging::LoggingConfig;
 use opendut_util::telemetry::opentelemetry_types::Opentelemetry;
 use opendut_util::settings::LoadedConfig;
+use crate::audit::{AuditLog, AuditLogOptions, AuditLogRef};
+use crate::auth::client_certificate::{ClientCertificateAuthority, ClientCertificateOptions, MutualTlsEnrollment};
 use crate::auth::grpc_auth_layer::{GrpcAuthenticationLayer};
-use crate::auth::json_web_key::JwkCacheValue;
-use util::in_memory_cache::CustomInMemoryCache;
+use crate::auth::issuer::HttpIssuerRequester;
+use crate::auth::mutual_tls;
+use crate::auth::mutual_tls::ClientCertificateAcceptor;
+use crate::auth::permission::RoleMapping;
+use crate::auth::peer_certificates::PeerCertificates;
+use crate::auth::revocation::RevokedClients;
+use crate::auth::validation::{JwtValidationOptions, JwtValidator};
 use crate::cluster::manager::{ClusterManager, ClusterManagerOptions, ClusterManagerRef};
 
-use crate::grpc::{ClusterManagerFacade, MetadataProviderFacade, PeerManagerFacade, PeerManagerFacadeOptions, PeerMessagingBrokerFacade};
+use crate::grpc::{AuditLogFacade, CanDatabaseManagerFacade, ClusterManagerFacade, MetadataProviderFacade, PeerManagerFacade, PeerManagerFacadeOptions, PeerMessagingBrokerFacade, SecretManagerFacade, ServiceAccountManagerFacade};
 use crate::http::router;
-use crate::http::state::{CarlInstallDirectory, HttpState, LeaConfig, LeaIdentityProviderConfig};
+use crate::http::state::{CarlInstallDirectory, HttpState, LeaConfig, LeaIdentityProviderConfig, PeerEnrollmentState};
 use crate::peer::broker::{PeerMessagingBroker, PeerMessagingBrokerOptions, PeerMessagingBrokerRef};
 use crate::provisioning::cleo_script::CleoScript;
 use crate::resources::manager::{ResourcesManager, ResourcesManagerRef};
+use crate::secrets::{SecretCipher, SecretCipherRef, SecretStore};
 use crate::vpn::Vpn;
 
 pub mod grpc;
@@ -44,10 +52,12 @@ pub mod util;
 opendut_util::app_info!();
 
 mod actions;
+mod audit;
 mod cluster;
 mod metrics;
 mod peer;
 mod resources;
+mod secrets;
 pub mod settings;
 mod vpn;
 mod http;
@@ -77,6 +87,13 @@ pub async fn create_with_telemetry(settings_override: config::Config) -> Result<
     Ok(())
 }
 
+/// Port, at which CARL accepts peers, which authenticate with a client certificate.
+struct MutualTlsServer {
+    address: SocketAddr,
+    tls_config: RustlsConfig,
+    enrollment: MutualTlsEnrollment,
+}
+
 pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
     info!("Started with configuration: {settings:?}");
 
@@ -86,30 +103,54 @@ pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
         SocketAddr::from_str(&format!("{host}:{port}"))?
     };
 
-    let tls_config = {
-        let cert_path = project::make_path_absolute(settings.config.get_string("network.tls.certificate")?)?;
-        debug!("Using TLS certificate: {}", cert_path.display());
-        assert!(cert_path.exists(), "TLS certificate file at '{}' not found.", cert_path.display());
+    let cert_path = project::make_path_absolute(settings.config.get_string("network.tls.certificate")?)?;
+    debug!("Using TLS certificate: {}", cert_path.display());
+    assert!(cert_path.exists(), "TLS certificate file at '{}' not found.", cert_path.display());
 
-        let key_path = project::make_path_absolute(settings.config.get_string("network.tls.key")?)?;
-        debug!("Using TLS key: {}", key_path.display());
-        assert!(key_path.exists(), "TLS key file at '{}' not found.", key_path.display());
+    let key_path = project::make_path_absolute(settings.config.get_string("network.tls.key")?)?;
+    debug!("Using TLS key: {}", key_path.display());
+    assert!(key_path.exists(), "TLS key file at '{}' not found.", key_path.display());
 
-        RustlsConfig::from_pem_file(cert_path, key_path).await?
-    };
+    let tls_config = RustlsConfig::from_pem_file(&cert_path, &key_path).await?;
     let carl_url = ResourceHomeUrl::try_from(&settings.config)?;
 
     let ca_certificate = Pem::from_config_path("network.tls.ca", &settings.config).await?;
+
+    let mutual_tls = match ClientCertificateOptions::load(&settings.config)? {
+        None => None,
+        Some(options) => {
+            let authority = ClientCertificateAuthority::load(&ca_certificate, &options)?;
+            Some(MutualTlsServer {
+                address: SocketAddr::new(address.ip(), options.bind_port),
+                tls_config: mutual_tls::server_config(&cert_path, &key_path, &ca_certificate)?,
+                enrollment: MutualTlsEnrollment {
+                    authority: Arc::new(authority),
+                    carl_port: options.remote_port,
+                },
+            })
+        }
+    };
     let oidc_registration_client = RegistrationClient::from_settings(&settings.config).await.expect("Failed to load oidc registration client!");
 
     let vpn = vpn::create(&settings.config)
         .context("Error while parsing VPN configuration.")?;
 
+    let audit_log = AuditLog::new(AuditLogOptions::load(&settings.config)?);
+
     let resources_manager = ResourcesManager::new();
     metrics::initialize_metrics_collection(Arc::clone(&resources_manager));
 
+    let secret_cipher = SecretCipher::load(&settings.config)
+        .context("Error while loading the key for secrets.")?;
+
+    let secret_store = SecretStore::load(&settings.config)
+        .context("Error while loading the configuration of the secret store.")?;
+    secret_store.restore(&resources_manager).await
+        .context("Error while restoring the stored secrets.")?;
+
     let peer_messaging_broker = PeerMessagingBroker::new(
         Arc::clone(&resources_manager),
+        Arc::clone(&secret_cipher),
         PeerMessagingBrokerOptions::load(&settings.config)?,
     );
     let cluster_manager = ClusterManager::new(
@@ -119,17 +160,32 @@ pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
         ClusterManagerOptions::load(&settings.config)?,
     );
 
-    let jwk_cache: CustomInMemoryCache<String, JwkCacheValue> = CustomInMemoryCache::new();
+    let revoked_clients = RevokedClients::load(&settings.config)
+        .context("Error while loading the revoked clients.")?;
+
+    let peer_certificates = PeerCertificates::load(&settings.config)
+        .context("Error while loading the peer certificates.")?;
 
     let grpc_auth_layer = match oidc_registration_client.clone() {
         None => {
             GrpcAuthenticationLayer::AuthDisabled
         }
-        Some(oidc_client_ref) => {
+        Some(_) => {
+            let jwt_validator = Arc::new(JwtValidator::new(
+                JwtValidationOptions::load(&settings.config)?,
+                HttpIssuerRequester,
+            ));
+            {
+                let jwt_validator = Arc::clone(&jwt_validator);
+                tokio::spawn(async move {
+                    jwt_validator.refresh_keys_periodically().await;
+                });
+            }
             GrpcAuthenticationLayer::GrpcAuthLayerEnabled {
-                issuer_url: oidc_client_ref.inner.config.issuer_url.clone(),
-                issuer_remote_url: oidc_client_ref.config.issuer_remote_url.clone(),
-                cache: jwk_cache,
+                jwt_validator,
+                role_mapping: RoleMapping::load(&settings.config)?,
+                revoked_clients: Clone::clone(&revoked_clients),
+                resources_manager: Arc::clone(&resources_manager),
             }
         }
     };
@@ -143,16 +199,24 @@ pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
         resources_manager: ResourcesManagerRef,
         cluster_manager: ClusterManagerRef,
         peer_messaging_broker: PeerMessagingBrokerRef,
+        secret_cipher: SecretCipherRef,
+        secret_store: SecretStore,
         vpn: Vpn,
         carl_url: ResourceHomeUrl,
         settings: config::Config,
         ca: Pem,
         oidc_registration_client: Option<RegistrationClientRef>,
         grpc_auth_layer: GrpcAuthenticationLayer,
+        revoked_clients: RevokedClients,
+        peer_certificates: PeerCertificates,
+        audit_log: AuditLogRef,
+        mutual_tls: Option<MutualTlsServer>,
     ) -> BoxFuture<'static, Result<()>> {
         let oidc_enabled = settings.get_bool("network.oidc.enabled").unwrap_or(false);
 
-        let cluster_manager_facade = ClusterManagerFacade::new(Arc::clone(&cluster_manager), Arc::clone(&resources_manager));
+        let audit_log_facade = AuditLogFacade::new(Arc::clone(&audit_log));
+        let can_database_manager_facade = CanDatabaseManagerFacade::new(Arc::clone(&resources_manager), Arc::clone(&audit_log));
+        let cluster_manager_facade = ClusterManagerFacade::new(Arc::clone(&cluster_manager), Arc::clone(&resources_manager), Arc::clone(&peer_messaging_broker), Arc::clone(&audit_log));
         let metadata_provider_facade = MetadataProviderFacade::new();
 
         let peer_manager_facade_options = PeerManagerFacadeOptions::load(&settings).expect("Error while loading PeerManagerFacadeOptions.");
@@ -161,20 +225,51 @@ pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
             vpn,
             Clone::clone(&carl_url.value()),
             ca.clone(),
-            oidc_registration_client,
+            Clone::clone(&oidc_registration_client),
+            Arc::clone(&peer_messaging_broker),
+            Clone::clone(&revoked_clients),
+            Clone::clone(&peer_certificates),
+            Arc::clone(&audit_log),
             peer_manager_facade_options
         );
-        let peer_messaging_broker_facade = PeerMessagingBrokerFacade::new(Arc::clone(&peer_messaging_broker));
+        let peer_messaging_broker_facade = PeerMessagingBrokerFacade::new(Arc::clone(&resources_manager), Arc::clone(&peer_messaging_broker), Clone::clone(&oidc_registration_client), Clone::clone(&peer_certificates), Arc::clone(&audit_log));
+        let secret_manager_facade = SecretManagerFacade::new(Arc::clone(&resources_manager), secret_cipher, secret_store, Arc::clone(&audit_log));
+        let service_account_manager_facade = ServiceAccountManagerFacade::new(Arc::clone(&resources_manager), Arc::clone(&audit_log));
+
+        let mutual_tls_server = mutual_tls.as_ref().map(|mutual_tls| {
+            let grpc_auth_layer = Clone::clone(&grpc_auth_layer);
+            let peer_messaging_broker_facade = PeerMessagingBrokerFacade::new(Arc::clone(&resources_manager), Arc::clone(&peer_messaging_broker), Clone::clone(&oidc_registration_client), Clone::clone(&peer_certificates), Arc::clone(&audit_log));
+
+            // peers authenticated by their client certificate may only reach the peer messaging broker
+            let grpc = Server::builder()
+                .layer(async_interceptor(move |request| {
+                    Clone::clone(&grpc_auth_layer).auth_interceptor(request)
+                }))
+                .add_service(peer_messaging_broker_facade.into_grpc_service())
+                .into_service()
+                .map_response(|response| response.map(axum::body::boxed))
+                .boxed_clone();
+
+            info!("Peers with client certificates are accepted at {}.", mutual_tls.address);
+            axum_server::bind(mutual_tls.address)
+                .acceptor(ClientCertificateAcceptor::new(Clone::clone(&mutual_tls.tls_config)))
+                .serve(Shared::new(grpc))
+                .map_err(|cause| anyhow!(cause))
+        });
 
         let grpc = Server::builder()
             .layer(async_interceptor(move |request| {
                 Clone::clone(&grpc_auth_layer).auth_interceptor(request)
             }))
             .accept_http1(true) //gRPC-web uses HTTP1
+            .add_service(audit_log_facade.into_grpc_service())
+            .add_service(can_database_manager_facade.into_grpc_service())
             .add_service(cluster_manager_facade.into_grpc_service())
             .add_service(metadata_provider_facade.into_grpc_service())
             .add_service(peer_manager_facade.into_grpc_service())
             .add_service(peer_messaging_broker_facade.into_grpc_service())
+            .add_service(secret_manager_facade.into_grpc_service())
+            .add_service(service_account_manager_facade.into_grpc_service())
             .into_service()
             .map_response(|response| response.map(axum::body::boxed))
             .boxed_clone();
@@ -203,7 +298,14 @@ pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
                 carl_url: carl_url.value(),
                 idp_config: lea_idp_config,
             },
-            carl_installation_directory
+            carl_installation_directory,
+            peer_enrollment: PeerEnrollmentState {
+                resources_manager: Arc::clone(&resources_manager),
+                oidc_registration_client: Clone::clone(&oidc_registration_client),
+                mutual_tls: mutual_tls.map(|mutual_tls| mutual_tls.enrollment),
+                revoked_clients,
+                peer_certificates: Clone::clone(&peer_certificates),
+            },
         };
 
         let lea_index_html = lea_dir.join("index.html").clone();
@@ -237,6 +339,7 @@ pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
                     )
                     .route("/api/cleo/:architecture/download", get(router::cleo::download_cleo))
                     .route("/api/edgar/:architecture/download", get(router::edgar::download_edgar))
+                    .route("/api/edgar/enrollment", post(router::edgar::redeem_enrollment_token))
                     .route("/api/lea/config", get(router::lea_config))
                     .nest_service(
                         "/",
@@ -262,12 +365,18 @@ pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
                 }).unwrap_or(1)
         });
 
-        Box::pin(
-            axum_server_dual_protocol::bind_dual_protocol(address, tls_config)
-                .set_upgrade(true) //http -> https
-                .serve(Shared::new(http_grpc))
-                .map_err(|cause| anyhow!(cause))
-        )
+        let server = axum_server_dual_protocol::bind_dual_protocol(address, tls_config)
+            .set_upgrade(true) //http -> https
+            .serve(Shared::new(http_grpc))
+            .map_err(|cause| anyhow!(cause));
+
+        match mutual_tls_server {
+            None => Box::pin(server),
+            Some(mutual_tls_server) => Box::pin(
+                futures::future::try_join(server, mutual_tls_server)
+                    .map_ok(|_| ())
+            ),
+        }
     }
 
     info!("Server listening at {address}...");
@@ -277,12 +386,18 @@ pub async fn create(settings: LoadedConfig) -> Result<()> { //TODO
         resources_manager,
         cluster_manager,
         peer_messaging_broker,
+        secret_cipher,
+        secret_store,
         vpn,
         carl_url,
         settings.config,
         ca_certificate,
         oidc_registration_client,
         grpc_auth_layer,
+        revoked_clients,
+        peer_certificates,
+        audit_log,
+        mutual_tls,
     ).await.unwrap();
 
     Ok(())
@@ -1,41 +1,57 @@
-use std::collections::HashMap;
+use std::collections::{BTreeSet, HashMap};
 use std::net::IpAddr;
+use std::ops::Not;
 use std::sync::Arc;
 use std::time::Duration;
 
 use opentelemetry::propagation::TextMapPropagator;
 use opentelemetry_sdk::propagation::TraceContextPropagator;
-use tokio::sync::{mpsc, RwLock};
+use tokio::sync::{mpsc, Notify, RwLock};
 use tokio::sync::mpsc::error::SendError;
 use tracing::{debug, error, info, Span, warn};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
-use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyPeerConfiguration, downstream, Downstream, TracingContext};
+use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyCanFaultInjectionRules, ApplyPeerConfiguration, ClientSecretStored, downstream, Downstream, ExecutorLogs as ExecutorLogsMessage, ExecutorRunUpdate, ResultArtifactUploaded, TracingContext};
 use opendut_carl_api::proto::services::peer_messaging_broker::Pong;
 use opendut_carl_api::proto::services::peer_messaging_broker::upstream;
 use opendut_types::peer::PeerId;
-use opendut_types::peer::configuration::{PeerConfiguration, PeerConfiguration2};
+use opendut_types::peer::configuration::{ParameterTarget, PeerConfiguration, PeerConfiguration2};
+use opendut_types::peer::executor::run::{ExecutorLogLine, ExecutorRun, ResultArtifact};
+use opendut_types::proto::ConversionErrorBuilder;
 use opendut_types::peer::state::{PeerState, PeerUpState};
+use opendut_types::util::net::ClientId;
 
+use crate::actions::{StoreExecutorRunParams, StoreResultArtifactParams};
+use crate::auth::ownership::{Ownership, Requester};
+use crate::peer::client_secrets::ClientSecretConfirmations;
+use crate::peer::executor_logs::{ExecutorLogs, ExecutorLogSubscription};
 use crate::resources::manager::ResourcesManagerRef;
+use crate::secrets::{EncryptedSecret, SecretCipherRef};
 
 pub type PeerMessagingBrokerRef = Arc<PeerMessagingBroker>;
 
 
 pub struct PeerMessagingBroker {
     resources_manager: ResourcesManagerRef,
+    secret_cipher: SecretCipherRef,
     peers: Arc<RwLock<HashMap<PeerId, PeerMessagingRef>>>,
+    executor_logs: Arc<ExecutorLogs>,
+    client_secret_confirmations: Arc<ClientSecretConfirmations>,
     options: PeerMessagingBrokerOptions,
 }
 struct PeerMessagingRef {
     downstream: mpsc::Sender<Downstream>,
+    disconnect: Arc<Notify>,
 }
 
 impl PeerMessagingBroker {
-    pub fn new(resources_manager: ResourcesManagerRef, options: PeerMessagingBrokerOptions) -> PeerMessagingBrokerRef {
+    pub fn new(resources_manager: ResourcesManagerRef, secret_cipher: SecretCipherRef, options: PeerMessagingBrokerOptions) -> PeerMessagingBrokerRef {
         Arc::new(Self {
             resources_manager,
+            secret_cipher,
             peers: Default::default(),
+            executor_logs: Default::default(),
+            client_secret_confirmations: Default::default(),
             options,
         })
     }
@@ -65,12 +81,91 @@ impl PeerMessagingBroker {
         Ok(())
     }
 
-    pub async fn list_peers(&self) -> Vec<PeerId> {
-        let peers = self.peers.read().await;
+    /// Sends the configuration to the peer. The secrets referenced by its executors are decrypted and attached to the sent configuration only,
+    /// so they never show up in the stored configuration.
+    /// Only secrets, which the owner of the peer may access, are attached.
+    /// Secrets, which cannot be attached, are left out and reported by the peer, when it fails to resolve them.
+    #[tracing::instrument(skip(self, configuration, configuration2), level="trace")]
+    pub async fn apply_peer_configuration(&self, peer_id: PeerId, configuration: PeerConfiguration, mut configuration2: PeerConfiguration2) -> Result<(), Error> {
+
+        let mut secret_names = BTreeSet::new();
+        for executor in &configuration2.executors {
+            match executor.value.secret_references() {
+                Ok(names) => secret_names.extend(names),
+                Err(cause) => error!("Executor '{}' of peer <{peer_id}> contains an illegal reference to a secret: {cause}", executor.value.name()),
+            }
+        }
+
+        let encrypted_secrets = self.resources_manager.resources(|resources| {
+            let peer_owner = Requester::on_behalf_of(resources.get::<Ownership>(peer_id).as_ref());
+            secret_names.into_iter()
+                .map(|secret_name| {
+                    let encrypted_secret = resources.get::<EncryptedSecret>(Clone::clone(&secret_name));
+                    let accessible = peer_owner.may_access(resources.get::<Ownership>(Clone::clone(&secret_name)).as_ref());
+                    (secret_name, encrypted_secret, accessible)
+                })
+                .collect::<Vec<_>>()
+        }).await;
+
+        for (secret_name, encrypted_secret, accessible) in encrypted_secrets {
+            let Some(encrypted_secret) = encrypted_secret else {
+                error!("Secret '{secret_name}' is referenced by an executor of peer <{peer_id}>, but does not exist.");
+                continue;
+            };
+            if accessible.not() {
+                error!("Secret '{secret_name}' is referenced by an executor of peer <{peer_id}>, but belongs to another user and group than the peer.");
+                continue;
+            }
+            match self.secret_cipher.decrypt(&encrypted_secret) {
+                Ok(secret) => configuration2.insert_secret(secret, ParameterTarget::Present),
+                Err(cause) => error!("Secret '{secret_name}' cannot be sent to peer <{peer_id}>: {cause}"),
+            }
+        }
 
-        peers.keys()
+        self.send_to_peer(peer_id, downstream::Message::ApplyPeerConfiguration(
+            ApplyPeerConfiguration {
+                configuration: Some(configuration.into()),
+                configuration2: Some(configuration2.into()),
+            }
+        )).await
+    }
+
+    /// Lists the connected peers, which the requester may access.
+    pub async fn list_peers(&self, requester: Requester) -> Vec<PeerId> {
+        let peer_ids = self.peers.read().await
+            .keys()
             .cloned()
-            .collect::<Vec<_>>()
+            .collect::<Vec<_>>();
+
+        self.resources_manager.resources(|resources| {
+            peer_ids.into_iter()
+                .filter(|peer_id| requester.may_access(resources.get::<Ownership>(*peer_id).as_ref()))
+                .collect::<Vec<_>>()
+        }).await
+    }
+
+    /// Returns a receiver, which resolves once the peer confirmed, that it stored the rotated client secret.
+    /// Has to be called before the secret is sent to the peer.
+    pub fn expect_client_secret_stored(&self, peer_id: PeerId) -> tokio::sync::oneshot::Receiver<ClientId> {
+        self.client_secret_confirmations.expect(peer_id)
+    }
+
+    /// Subscribes to the log of the latest run of the executor with the given name on the given peer.
+    /// Runs of peers, which the requester may not access, are treated as if they did not exist.
+    pub async fn subscribe_executor_logs(&self, peer_id: PeerId, executor_name: &str, requester: Requester) -> Result<ExecutorLogSubscription, Error> {
+        let latest_run = self.resources_manager.resources(|resources| {
+            if requester.may_access(resources.get::<Ownership>(peer_id).as_ref()).not() {
+                return None;
+            }
+            resources.iter::<ExecutorRun>()
+                .filter(|run| run.peer_id == peer_id && run.executor_name == executor_name)
+                .max_by_key(|run| run.created_at)
+                .map(|run| run.id)
+        }).await;
+
+        let run_id = latest_run.ok_or_else(|| Error::ExecutorRunNotFound { peer_id, executor_name: executor_name.to_owned() })?;
+
+        Ok(self.executor_logs.subscribe(run_id))
     }
 
     pub async fn open(
@@ -82,8 +177,11 @@ impl PeerMessagingBroker {
         let (tx_inbound, mut rx_inbound) = mpsc::channel::<upstream::Message>(1024);
         let (tx_outbound, rx_outbound) = mpsc::channel::<Downstream>(1024);
 
+        let disconnect = Arc::new(Notify::new());
+
         let peer_messaging_ref = PeerMessagingRef {
             downstream: Clone::clone(&tx_outbound),
+            disconnect: Arc::clone(&disconnect),
         };
 
         self.peers.write().await.insert(peer_id, peer_messaging_ref);
@@ -127,16 +225,29 @@ impl PeerMessagingBroker {
         if let Some(configuration) = self.resources_manager.get::<PeerConfiguration>(peer_id).await {
             if let Some(configuration2) = self.resources_manager.get::<PeerConfiguration2>(peer_id).await {
 
-                let result = self.send_to_peer(peer_id, downstream::Message::ApplyPeerConfiguration(
-                    ApplyPeerConfiguration {
-                        configuration: Some(configuration.into()),
-                        configuration2: Some(configuration2.into()),
-                    }
-                )).await;
+                let result = self.apply_peer_configuration(peer_id, Clone::clone(&configuration), configuration2).await;
 
                 if let Err(error) = result {
                     error!("Failed to send ApplyPeerConfiguration message: {error}")
                 }
+
+                if let Some(cluster_assignment) = configuration.cluster_assignment {
+                    let cluster_id = cluster_assignment.id;
+                    let rules = self.resources_manager.resources(|resources| {
+                        crate::actions::can_fault_injection_rules_of_cluster(resources, cluster_id)
+                    }).await;
+
+                    let result = self.send_to_peer(peer_id, downstream::Message::ApplyCanFaultInjectionRules(
+                        ApplyCanFaultInjectionRules {
+                            cluster_id: Some(cluster_id.into()),
+                            rules: rules.into_iter().map(Into::into).collect(),
+                        }
+                    )).await;
+
+                    if let Err(error) = result {
+                        error!("Failed to send ApplyCanFaultInjectionRules message: {error}")
+                    }
+                }
             }
             else {
                 error!("Failed to send ApplyPeerConfiguration message, because no PeerConfiguration2 found for peer: {peer_id}")
@@ -150,13 +261,21 @@ impl PeerMessagingBroker {
         {
             let peers = Arc::clone(&self.peers);
             let resources_manager = Arc::clone(&self.resources_manager);
+            let executor_logs = Arc::clone(&self.executor_logs);
+            let client_secret_confirmations = Arc::clone(&self.client_secret_confirmations);
 
             tokio::spawn(async move {
                 loop {
-                    let received = tokio::time::timeout(timeout_duration, rx_inbound.recv()).await;
+                    let received = tokio::select! {
+                        received = tokio::time::timeout(timeout_duration, rx_inbound.recv()) => received,
+                        _ = disconnect.notified() => {
+                            info!("Disconnecting peer <{peer_id}>.");
+                            break;
+                        }
+                    };
 
                     match received {
-                        Ok(Some(message)) => handle_stream_message(message, peer_id, &tx_outbound).await,
+                        Ok(Some(message)) => handle_stream_message(message, peer_id, &tx_outbound, &resources_manager, &executor_logs, &client_secret_confirmations).await,
                         Ok(None) => {
                             info!("Peer <{peer_id}> disconnected!");
                             break;
@@ -181,6 +300,15 @@ impl PeerMessagingBroker {
         Ok((tx_inbound, rx_outbound))
     }
 
+    /// Closes the stream of the peer, which then has to reconnect and authenticate again.
+    pub async fn disconnect(&self, peer_id: PeerId) -> Result<(), Error> {
+        let peers = self.peers.read().await;
+        let peer = peers.get(&peer_id)
+            .ok_or(Error::PeerNotFound(peer_id))?;
+        peer.disconnect.notify_one();
+        Ok(())
+    }
+
     pub async fn remove_peer(&self, peer_id: PeerId) -> Result<(), Error> {
         let mut peers = self.peers.write().await;
 
@@ -197,6 +325,9 @@ async fn handle_stream_message(
     message: upstream::Message,
     peer_id: PeerId,
     tx_outbound: &mpsc::Sender<Downstream>,
+    resources_manager: &ResourcesManagerRef,
+    executor_logs: &ExecutorLogs,
+    client_secret_confirmations: &ClientSecretConfirmations,
 ) {
     match message {
         upstream::Message::Ping(_) => {
@@ -206,6 +337,80 @@ async fn handle_stream_message(
                 tx_outbound.send(Downstream{message:Some(message), context}).await
                     .inspect_err(|cause| warn!("Failed to send ping to peer <{peer_id}>: {cause}"));
         },
+        upstream::Message::ExecutorRunUpdate(update) => {
+            let run = update.run
+                .ok_or_else(|| ConversionErrorBuilder::<ExecutorRunUpdate, ExecutorRun>::field_not_set("run"))
+                .and_then(ExecutorRun::try_from);
+
+            match run {
+                Ok(run) => {
+                    let run_id = run.id;
+                    let is_finished = run.state.is_finished();
+                    let result = crate::actions::store_executor_run(StoreExecutorRunParams {
+                        resources_manager: Arc::clone(resources_manager),
+                        peer_id,
+                        run,
+                    }).await;
+
+                    if result.is_ok() && is_finished {
+                        executor_logs.close(run_id);
+                    }
+                }
+                Err(cause) => warn!("Received invalid executor run update from peer <{peer_id}>: {cause}"),
+            }
+        },
+        upstream::Message::ExecutorLogs(ExecutorLogsMessage { lines }) => {
+            let lines = lines.into_iter()
+                .map(ExecutorLogLine::try_from)
+                .collect::<Result<Vec<_>, _>>();
+
+            match lines {
+                Ok(lines) => {
+                    let (lines, foreign_lines): (Vec<_>, Vec<_>) = resources_manager.resources(|resources| {
+                        lines.into_iter()
+                            .partition(|line| resources.get::<ExecutorRun>(line.run_id)
+                                .is_some_and(|run| run.peer_id == peer_id)
+                            )
+                    }).await;
+
+                    if foreign_lines.is_empty().not() {
+                        warn!("Ignoring {} log lines from peer <{peer_id}>, which do not belong to one of its executor runs.", foreign_lines.len());
+                    }
+                    executor_logs.append(lines);
+                }
+                Err(cause) => warn!("Received invalid executor logs from peer <{peer_id}>: {cause}"),
+            }
+        },
+        upstream::Message::ResultArtifactUploaded(uploaded) => {
+            let artifact = uploaded.artifact
+                .ok_or_else(|| ConversionErrorBuilder::<ResultArtifactUploaded, ResultArtifact>::field_not_set("artifact"))
+                .and_then(ResultArtifact::try_from);
+
+            match artifact {
+                Ok(artifact) => {
+                    let _ignore_result = crate::actions::store_result_artifact(StoreResultArtifactParams {
+                        resources_manager: Arc::clone(resources_manager),
+                        peer_id,
+                        artifact,
+                    }).await;
+                }
+                Err(cause) => warn!("Received invalid result artifact from peer <{peer_id}>: {cause}"),
+            }
+        },
+        upstream::Message::ClientSecretStored(stored) => {
+            let client_id = stored.client_id
+                .ok_or_else(|| ConversionErrorBuilder::<ClientSecretStored, ClientId>::field_not_set("client_id"))
+                .and_then(ClientId::try_from);
+
+            match client_id {
+                Ok(client_id) => {
+                    if client_secret_confirmations.confirm(peer_id, client_id).not() {
+                        warn!("Peer <{peer_id}> confirmed storing a client secret, which was not expected.");
+                    }
+                }
+                Err(cause) => warn!("Received invalid confirmation of a stored client secret from peer <{peer_id}>: {cause}"),
+            }
+        },
     }
 }
 
@@ -227,6 +432,8 @@ pub enum Error {
     DownstreamSend(SendError<Downstream>),
     #[error("PeerNotFound Error: {0}")]
     PeerNotFound(PeerId),
+    #[error("No run of executor '{executor_name}' found on peer <{peer_id}>.")]
+    ExecutorRunNotFound { peer_id: PeerId, executor_name: String },
     #[error("Other Error: {message}")]
     Other { message: String },
 }
@@ -261,14 +468,24 @@ impl PeerMessagingBrokerOptions {
 #[cfg(test)]
 mod tests {
     use std::str::FromStr;
+    use std::time::SystemTime;
 
     use googletest::prelude::*;
     use tokio::sync::mpsc;
     use tokio::sync::mpsc::Receiver;
+    use uuid::Uuid;
 
     use opendut_carl_api::proto::services::peer_messaging_broker::Ping;
-
+    use opendut_types::peer::configuration::{ParameterId, PeerNetworkConfiguration};
+    use opendut_types::peer::executor::{ExecutorDescriptor, ExecutorKind};
+    use opendut_types::peer::executor::executable::{ExecutableEnvironmentVariable, ExecutableName, ExecutableProgram};
+    use opendut_types::peer::executor::run::{ExecutorLogStream, ExecutorRunId, ExecutorRunState};
+    use opendut_types::secret::{Secret, SecretName};
+    use opendut_types::util::net::NetworkInterfaceName;
+
+    use crate::auth::permission::Role;
     use crate::resources::manager::ResourcesManager;
+    use crate::secrets::{SecretCipher, SecretCipherError};
 
     use super::*;
 
@@ -279,7 +496,7 @@ mod tests {
         let options = PeerMessagingBrokerOptions {
             peer_disconnect_timeout: Duration::from_millis(200),
         };
-        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options.clone());
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), Arc::new(SecretCipher::generate()?), options.clone());
 
         let peer_id = PeerId::random();
         let remote_host = IpAddr::from_str("1.2.3.4")?;
@@ -337,6 +554,30 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn should_close_the_stream_of_a_disconnected_peer() -> Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), Arc::new(SecretCipher::generate()?), PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_secs(10),
+        });
+
+        let peer_id = PeerId::random();
+        let (_sender, mut receiver) = testee.open(peer_id, IpAddr::from_str("1.2.3.4")?).await?;
+
+        testee.disconnect(peer_id).await?;
+
+        let received = tokio::time::timeout(Duration::from_secs(1), receiver.recv()).await?;
+        assert!(received.is_none(), "Stream of the disconnected peer should be closed.");
+
+        assert!(testee.list_peers(Requester::Unrestricted).await.is_empty());
+        let peer_state = resources_manager.get::<PeerState>(peer_id).await;
+        assert!(matches!(peer_state, Some(PeerState::Down)));
+
+        assert!(matches!(testee.disconnect(peer_id).await, Err(Error::PeerNotFound(_))));
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn should_reject_second_connection_for_peer() -> Result<()> {
         let resources_manager = ResourcesManager::new();
@@ -344,7 +585,7 @@ mod tests {
         let options = PeerMessagingBrokerOptions {
             peer_disconnect_timeout: Duration::from_millis(200),
         };
-        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), options.clone());
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), Arc::new(SecretCipher::generate()?), options.clone());
 
         let peer_id = PeerId::random();
         let remote_host = IpAddr::from_str("1.2.3.4")?;
@@ -358,6 +599,175 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn should_buffer_executor_logs_only_for_runs_of_the_reporting_peer() -> Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), Arc::new(SecretCipher::generate()?), PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_millis(200),
+        });
+
+        let peer_id = PeerId::random();
+        let run = executor_run(peer_id, "nmap-test");
+        let foreign_run = executor_run(PeerId::random(), "nmap-test");
+        resources_manager.resources_mut(|resources| {
+            resources.insert(run.id, Clone::clone(&run));
+            resources.insert(foreign_run.id, Clone::clone(&foreign_run));
+        }).await;
+
+        let (tx_outbound, _rx_outbound) = mpsc::channel(1);
+        let message = upstream::Message::ExecutorLogs(ExecutorLogsMessage {
+            lines: vec![
+                log_line(&run, "own line").into(),
+                log_line(&foreign_run, "foreign line").into(),
+            ],
+        });
+        handle_stream_message(message, peer_id, &tx_outbound, &resources_manager, &testee.executor_logs, &testee.client_secret_confirmations).await;
+
+        let subscription = testee.subscribe_executor_logs(peer_id, "nmap-test", Requester::Unrestricted).await?;
+        let lines = subscription.buffered.into_iter().map(|line| line.line).collect::<Vec<_>>();
+        assert_that!(lines, elements_are![eq("own line")]);
+
+        let foreign_lines = testee.executor_logs.subscribe(foreign_run.id).buffered;
+        assert!(foreign_lines.is_empty());
+
+        let result = testee.subscribe_executor_logs(peer_id, "unknown", Requester::Unrestricted).await;
+        assert!(matches!(result, Err(Error::ExecutorRunNotFound { .. })));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_only_expose_peers_and_executor_logs_of_the_own_team() -> Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), Arc::new(SecretCipher::generate()?), PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_millis(200),
+        });
+
+        let peer_a = PeerId::random();
+        let peer_b = PeerId::random();
+        let run_b = executor_run(peer_b, "nmap-test");
+        resources_manager.resources_mut(|resources| {
+            resources.insert(peer_a, Ownership { owner: String::from("alice"), group: Some(String::from("/team-a")) });
+            resources.insert(peer_b, Ownership { owner: String::from("bob"), group: Some(String::from("/team-b")) });
+            resources.insert(run_b.id, Clone::clone(&run_b));
+        }).await;
+
+        let remote_host = IpAddr::from_str("1.2.3.4")?;
+        let _peer_a_stream = testee.open(peer_a, remote_host).await?;
+        let _peer_b_stream = testee.open(peer_b, remote_host).await?;
+
+        let carol = Requester::User { name: String::from("carol"), groups: vec![String::from("/team-a")], role: Some(Role::Viewer) };
+
+        assert_that!(testee.list_peers(Clone::clone(&carol)).await, elements_are![eq(peer_a)]);
+
+        let result = testee.subscribe_executor_logs(peer_b, "nmap-test", carol).await;
+        assert!(matches!(result, Err(Error::ExecutorRunNotFound { .. })));
+
+        let result = testee.subscribe_executor_logs(peer_b, "nmap-test", Requester::Unrestricted).await;
+        assert!(result.is_ok());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_attach_the_referenced_secrets_only_to_the_sent_configuration() -> Result<()> {
+        let resources_manager = ResourcesManager::new();
+        let secret_cipher = Arc::new(SecretCipher::generate()?);
+        let testee = PeerMessagingBroker::new(Arc::clone(&resources_manager), Arc::clone(&secret_cipher), PeerMessagingBrokerOptions {
+            peer_disconnect_timeout: Duration::from_millis(200),
+        });
+
+        let secret = Secret {
+            name: SecretName::try_from("db-password")?,
+            value: String::from("hunter2"),
+        };
+        let unreferenced_secret = Secret {
+            name: SecretName::try_from("unreferenced")?,
+            value: String::from("other"),
+        };
+        let foreign_secret = Secret {
+            name: SecretName::try_from("foreign")?,
+            value: String::from("of-team-b"),
+        };
+
+        let peer_id = PeerId::random();
+        let mut configuration2 = PeerConfiguration2::default();
+        configuration2.insert_executor(ExecutorDescriptor {
+            kind: ExecutorKind::Executable {
+                name: ExecutableName::try_from("tester")?,
+                program: ExecutableProgram::try_from("/usr/bin/tester")?,
+                args: vec![],
+                envs: vec![
+                    ExecutableEnvironmentVariable::new("DB_PASSWORD", secret.name.reference())?,
+                    ExecutableEnvironmentVariable::new("FOREIGN", foreign_secret.name.reference())?,
+                ],
+                working_directory: None,
+                user: None,
+            },
+            results_url: None,
+        }, ParameterTarget::Present);
+
+        resources_manager.resources_mut(|resources| {
+            resources.insert(Clone::clone(&secret.name), secret_cipher.encrypt(&secret)?);
+            resources.insert(Clone::clone(&secret.name), Ownership { owner: String::from("carol"), group: Some(String::from("/team-a")) });
+            resources.insert(Clone::clone(&unreferenced_secret.name), secret_cipher.encrypt(&unreferenced_secret)?);
+            resources.insert(Clone::clone(&foreign_secret.name), secret_cipher.encrypt(&foreign_secret)?);
+            resources.insert(Clone::clone(&foreign_secret.name), Ownership { owner: String::from("bob"), group: Some(String::from("/team-b")) });
+            resources.insert(peer_id, Ownership { owner: String::from("alice"), group: Some(String::from("/team-a")) });
+            resources.insert(peer_id, PeerConfiguration {
+                cluster_assignment: None,
+                network: PeerNetworkConfiguration {
+                    bridge_name: NetworkInterfaceName::try_from("br-opendut-1").unwrap(),
+                },
+            });
+            resources.insert(peer_id, Clone::clone(&configuration2));
+            Ok::<_, SecretCipherError>(())
+        }).await?;
+
+        let (_sender, mut receiver) = testee.open(peer_id, IpAddr::from_str("1.2.3.4")?).await?;
+
+        let received = receiver.recv().await
+            .and_then(|downstream| downstream.message);
+        let Some(downstream::Message::ApplyPeerConfiguration(ApplyPeerConfiguration { configuration2: Some(sent_configuration2), .. })) = received else {
+            panic!("Expected ApplyPeerConfiguration message, but received: {received:?}");
+        };
+        let sent_configuration2 = PeerConfiguration2::try_from(sent_configuration2)?;
+        let sent_secrets = sent_configuration2.secrets.into_iter()
+            .map(|parameter| parameter.value)
+            .collect::<Vec<_>>();
+        assert_that!(sent_secrets, elements_are![eq(&secret)]);
+
+        let stored_configuration2 = resources_manager.get::<PeerConfiguration2>(peer_id).await;
+        assert_that!(stored_configuration2, some(eq(&configuration2)));
+
+        Ok(())
+    }
+
+    fn executor_run(peer_id: PeerId, executor_name: &str) -> ExecutorRun {
+        ExecutorRun {
+            id: ExecutorRunId::random(),
+            peer_id,
+            executor_id: ParameterId(Uuid::new_v4()),
+            executor_name: executor_name.to_owned(),
+            state: ExecutorRunState::Running,
+            exit_code: None,
+            created_at: SystemTime::now(),
+            started_at: None,
+            finished_at: None,
+            results_location: None,
+            image_digest: None,
+        }
+    }
+
+    fn log_line(run: &ExecutorRun, line: &str) -> ExecutorLogLine {
+        ExecutorLogLine {
+            run_id: run.id,
+            stream: ExecutorLogStream::Stdout,
+            timestamp: SystemTime::now(),
+            line: line.to_owned(),
+        }
+    }
+
     async fn do_ping(sender: &mpsc::Sender<upstream::Message>, receiver: &mut Receiver<Downstream>) {
         sender.send(upstream::Message::Ping(Ping {})).await
             .unwrap();
@@ -0,0 +1,54 @@
+use std::collections::HashMap;
+use std::sync::Mutex;
+
+use tokio::sync::oneshot;
+
+use opendut_types::peer::PeerId;
+use opendut_types::util::net::ClientId;
+
+/// Peers, which were sent a rotated client secret, but did not yet confirm, that they stored it.
+/// The previous secret remains valid at the identity provider until the confirmation arrives.
+#[derive(Default)]
+pub struct ClientSecretConfirmations {
+    pending: Mutex<HashMap<PeerId, oneshot::Sender<ClientId>>>,
+}
+
+impl ClientSecretConfirmations {
+
+    /// Has to be called before the secret is sent, so that an early confirmation is not missed.
+    /// Replaces a pending confirmation of the same peer, whose receiver then resolves with an error.
+    pub fn expect(&self, peer_id: PeerId) -> oneshot::Receiver<ClientId> {
+        let (sender, receiver) = oneshot::channel();
+        self.pending.lock().unwrap().insert(peer_id, sender);
+        receiver
+    }
+
+    /// Returns `false`, if no confirmation was expected from the peer.
+    pub fn confirm(&self, peer_id: PeerId, client_id: ClientId) -> bool {
+        let sender = self.pending.lock().unwrap().remove(&peer_id);
+        match sender {
+            Some(sender) => sender.send(client_id).is_ok(),
+            None => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn should_only_accept_expected_confirmations() -> anyhow::Result<()> {
+        let testee = ClientSecretConfirmations::default();
+        let peer_id = PeerId::random();
+
+        assert!(!testee.confirm(peer_id, ClientId::from("edgar-123")));
+
+        let receiver = testee.expect(peer_id);
+        assert!(testee.confirm(peer_id, ClientId::from("edgar-123")));
+        assert_eq!(receiver.await?, ClientId::from("edgar-123"));
+
+        assert!(!testee.confirm(peer_id, ClientId::from("edgar-123")));
+        Ok(())
+    }
+}
@@ -0,0 +1,181 @@
+use std::time::{Duration, SystemTime};
+
+use opendut_types::peer::enrollment::EnrollmentToken;
+
+/// Enrollment token, which was issued with the most recent setup string of a peer.
+/// Generating a new setup string replaces it, which invalidates the previous token.
+#[derive(Clone, Debug)]
+pub struct Enrollment {
+    pub token: EnrollmentToken,
+    /// User, which generated the setup string. Becomes the owner of the OIDC client registered on redemption.
+    pub user_id: String,
+    pub expires_at: SystemTime,
+    pub status: EnrollmentStatus,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EnrollmentStatus {
+    Issued,
+    Redeemed,
+    Expired,
+}
+
+impl Enrollment {
+    pub fn issue(user_id: String, lifetime: Duration, now: SystemTime) -> Self {
+        Self {
+            token: EnrollmentToken::random(),
+            user_id,
+            expires_at: now + lifetime,
+            status: EnrollmentStatus::Issued,
+        }
+    }
+
+    /// Compares the tokens in constant time, so that the duration of a failed attempt does not reveal how much of the token was guessed correctly.
+    fn matches(&self, token: &EnrollmentToken) -> bool {
+        let expected = self.token.value().as_bytes();
+        let actual = token.value().as_bytes();
+
+        expected.len() == actual.len()
+            && std::hint::black_box(
+                expected.iter().zip(actual)
+                    .fold(0u8, |difference, (expected, actual)| difference | (expected ^ actual))
+            ) == 0
+    }
+
+    /// Marks the enrollment as redeemed, if the given token matches and is still valid.
+    pub fn redeem(&mut self, token: &EnrollmentToken, now: SystemTime) -> Result<(), RedeemEnrollmentError> {
+        if !self.matches(token) {
+            return Err(RedeemEnrollmentError::InvalidToken);
+        }
+        match self.status {
+            EnrollmentStatus::Redeemed => Err(RedeemEnrollmentError::AlreadyRedeemed),
+            EnrollmentStatus::Expired => Err(RedeemEnrollmentError::Expired),
+            EnrollmentStatus::Issued if now > self.expires_at => {
+                self.status = EnrollmentStatus::Expired;
+                Err(RedeemEnrollmentError::Expired)
+            }
+            EnrollmentStatus::Issued => {
+                self.status = EnrollmentStatus::Redeemed;
+                Ok(())
+            }
+        }
+    }
+
+    /// Makes the token redeemable again, if issuing the credentials failed after its redemption.
+    /// Has no effect, if the token was replaced by a newer setup string in the meantime.
+    pub fn revert_redemption(&mut self, token: &EnrollmentToken) {
+        if self.matches(token) && self.status == EnrollmentStatus::Redeemed {
+            self.status = EnrollmentStatus::Issued;
+        }
+    }
+}
+
+#[derive(thiserror::Error, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RedeemEnrollmentError {
+    #[error("Enrollment token is not valid.")]
+    InvalidToken,
+    #[error("Enrollment token was already redeemed.")]
+    AlreadyRedeemed,
+    #[error("Enrollment token has expired.")]
+    Expired,
+}
+
+#[derive(Clone, Debug)]
+pub struct EnrollmentOptions {
+    pub token_lifetime: Duration,
+    /// Whether peers receive a client certificate on enrollment, with which they authenticate instead of OIDC.
+    pub mutual_tls: bool,
+}
+impl EnrollmentOptions {
+    pub fn load(config: &config::Config) -> Result<Self, opendut_util::settings::LoadError> {
+        let token_lifetime = Duration::from_millis(
+            config.get::<u64>("peer.enrollment.token.lifetime.ms")?
+        );
+        let mutual_tls = config.get_bool("network.tls.client.auth.enabled")?;
+
+        Ok(EnrollmentOptions {
+            token_lifetime,
+            mutual_tls,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LIFETIME: Duration = Duration::from_secs(900);
+
+    #[test]
+    fn should_redeem_a_token_only_once() {
+        let now = SystemTime::now();
+        let mut enrollment = Enrollment::issue(String::from("alice"), LIFETIME, now);
+        let token = Clone::clone(&enrollment.token);
+
+        assert_eq!(enrollment.redeem(&token, now), Ok(()));
+        assert_eq!(enrollment.status, EnrollmentStatus::Redeemed);
+
+        assert_eq!(enrollment.redeem(&token, now), Err(RedeemEnrollmentError::AlreadyRedeemed));
+    }
+
+    #[test]
+    fn should_reject_an_expired_token() {
+        let now = SystemTime::now();
+        let mut enrollment = Enrollment::issue(String::from("alice"), LIFETIME, now);
+        let token = Clone::clone(&enrollment.token);
+
+        assert_eq!(enrollment.redeem(&token, now + LIFETIME + Duration::from_secs(1)), Err(RedeemEnrollmentError::Expired));
+        assert_eq!(enrollment.status, EnrollmentStatus::Expired);
+
+        assert_eq!(enrollment.redeem(&token, now), Err(RedeemEnrollmentError::Expired));
+    }
+
+    #[test]
+    fn should_allow_redeeming_again_after_reverting_a_redemption() {
+        let now = SystemTime::now();
+        let mut enrollment = Enrollment::issue(String::from("alice"), LIFETIME, now);
+        let token = Clone::clone(&enrollment.token);
+
+        assert_eq!(enrollment.redeem(&token, now), Ok(()));
+        enrollment.revert_redemption(&token);
+        assert_eq!(enrollment.status, EnrollmentStatus::Issued);
+
+        assert_eq!(enrollment.redeem(&token, now), Ok(()));
+    }
+
+    #[test]
+    fn should_not_revert_the_redemption_of_a_replaced_token() {
+        let now = SystemTime::now();
+        let mut enrollment = Enrollment::issue(String::from("alice"), LIFETIME, now);
+        let token = Clone::clone(&enrollment.token);
+        assert_eq!(enrollment.redeem(&token, now), Ok(()));
+
+        enrollment.revert_redemption(&EnrollmentToken::random());
+
+        assert_eq!(enrollment.status, EnrollmentStatus::Redeemed);
+    }
+
+    #[test]
+    fn should_reject_a_foreign_token() {
+        let now = SystemTime::now();
+        let mut enrollment = Enrollment::issue(String::from("alice"), LIFETIME, now);
+
+        assert_eq!(enrollment.redeem(&EnrollmentToken::random(), now), Err(RedeemEnrollmentError::InvalidToken));
+        assert_eq!(enrollment.status, EnrollmentStatus::Issued);
+    }
+
+    #[test]
+    fn should_reject_truncated_and_extended_tokens() {
+        let now = SystemTime::now();
+        let mut enrollment = Enrollment::issue(String::from("alice"), LIFETIME, now);
+        let token = enrollment.token.value().to_owned();
+
+        let truncated = EnrollmentToken::from(&token[..token.len() - 1]);
+        assert_eq!(enrollment.redeem(&truncated, now), Err(RedeemEnrollmentError::InvalidToken));
+
+        let extended = EnrollmentToken::from(format!("{token}0"));
+        assert_eq!(enrollment.redeem(&extended, now), Err(RedeemEnrollmentError::InvalidToken));
+
+        assert_eq!(enrollment.redeem(&EnrollmentToken::from(token), now), Ok(()));
+    }
+}
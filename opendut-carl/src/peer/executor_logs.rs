@@ -0,0 +1,156 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::Mutex;
+
+use tokio::sync::broadcast;
+
+use opendut_types::peer::executor::run::{ExecutorLogLine, ExecutorRunId};
+
+/// Number of log lines, which are buffered per executor run. Older lines are discarded.
+const MAX_BUFFERED_LINES_PER_RUN: usize = 1000;
+/// Number of executor runs, for which logs are buffered. The logs of the oldest runs are discarded.
+const MAX_BUFFERED_RUNS: usize = 100;
+/// Number of lines a live subscriber may lag behind, before it misses lines.
+const LIVE_CHANNEL_CAPACITY: usize = 1024;
+
+/// Buffers the most recent log lines of executor runs and forwards new lines to live subscribers.
+#[derive(Default)]
+pub struct ExecutorLogs {
+    inner: Mutex<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    runs: HashMap<ExecutorRunId, RunLog>,
+    /// Order in which the runs were first seen, used to discard the logs of the oldest runs.
+    order: VecDeque<ExecutorRunId>,
+}
+
+struct RunLog {
+    lines: VecDeque<ExecutorLogLine>,
+    /// Sender for live subscribers. `None`, when the run has finished.
+    live: Option<broadcast::Sender<ExecutorLogLine>>,
+}
+
+impl RunLog {
+    fn new() -> Self {
+        let (live, _) = broadcast::channel(LIVE_CHANNEL_CAPACITY);
+        Self {
+            lines: VecDeque::new(),
+            live: Some(live),
+        }
+    }
+}
+
+/// Buffered lines of an executor run together with a receiver for lines, which arrive later.
+pub struct ExecutorLogSubscription {
+    pub buffered: Vec<ExecutorLogLine>,
+    /// `None`, when the run has already finished.
+    pub live: Option<broadcast::Receiver<ExecutorLogLine>>,
+}
+
+impl ExecutorLogs {
+
+    pub fn append(&self, lines: Vec<ExecutorLogLine>) {
+        let mut inner = self.inner.lock().unwrap();
+
+        for line in lines {
+            let run_log = inner.run_log_mut(line.run_id);
+
+            if let Some(live) = &run_log.live {
+                let _ignore_no_subscribers = live.send(Clone::clone(&line));
+            }
+
+            run_log.lines.push_back(line);
+            if run_log.lines.len() > MAX_BUFFERED_LINES_PER_RUN {
+                run_log.lines.pop_front();
+            }
+        }
+    }
+
+    pub fn subscribe(&self, run_id: ExecutorRunId) -> ExecutorLogSubscription {
+        let mut inner = self.inner.lock().unwrap();
+        let run_log = inner.run_log_mut(run_id);
+
+        ExecutorLogSubscription {
+            buffered: run_log.lines.iter().cloned().collect(),
+            live: run_log.live.as_ref().map(broadcast::Sender::subscribe),
+        }
+    }
+
+    /// Ends the live streams of the run, e.g. when the run has finished. The buffered lines are kept.
+    pub fn close(&self, run_id: ExecutorRunId) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.run_log_mut(run_id).live = None;
+    }
+}
+
+impl Inner {
+    fn run_log_mut(&mut self, run_id: ExecutorRunId) -> &mut RunLog {
+        if !self.runs.contains_key(&run_id) {
+            if self.order.len() >= MAX_BUFFERED_RUNS {
+                if let Some(oldest_run_id) = self.order.pop_front() {
+                    self.runs.remove(&oldest_run_id);
+                }
+            }
+            self.order.push_back(run_id);
+        }
+        self.runs.entry(run_id).or_insert_with(RunLog::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::SystemTime;
+
+    use googletest::prelude::*;
+
+    use opendut_types::peer::executor::run::ExecutorLogStream;
+
+    use super::*;
+
+    fn log_line(run_id: ExecutorRunId, line: &str) -> ExecutorLogLine {
+        ExecutorLogLine {
+            run_id,
+            stream: ExecutorLogStream::Stdout,
+            timestamp: SystemTime::now(),
+            line: String::from(line),
+        }
+    }
+
+    #[tokio::test]
+    async fn should_provide_buffered_and_live_lines() -> anyhow::Result<()> {
+        let testee = ExecutorLogs::default();
+        let run_id = ExecutorRunId::random();
+
+        testee.append(vec![log_line(run_id, "first")]);
+
+        let subscription = testee.subscribe(run_id);
+        let buffered = subscription.buffered.iter().map(|line| line.line.as_str()).collect::<Vec<_>>();
+        assert_that!(buffered, elements_are![eq("first")]);
+        let mut live = subscription.live.expect("Subscription for running executor should be live.");
+
+        testee.append(vec![log_line(run_id, "second")]);
+        assert_that!(live.recv().await?.line, eq("second"));
+
+        testee.close(run_id);
+        assert!(live.recv().await.is_err());
+        assert!(testee.subscribe(run_id).live.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_discard_the_oldest_lines() {
+        let testee = ExecutorLogs::default();
+        let run_id = ExecutorRunId::random();
+
+        let lines = (0..MAX_BUFFERED_LINES_PER_RUN + 5)
+            .map(|index| log_line(run_id, &index.to_string()))
+            .collect::<Vec<_>>();
+        testee.append(lines);
+
+        let buffered = testee.subscribe(run_id).buffered;
+        assert_that!(buffered.len(), eq(MAX_BUFFERED_LINES_PER_RUN));
+        assert_that!(buffered[0].line, eq("5"));
+    }
+}
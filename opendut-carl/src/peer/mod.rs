@@ -1 +1,4 @@
 pub mod broker;
+pub mod client_secrets;
+pub mod enrollment;
+pub mod executor_logs;
@@ -1,11 +1,35 @@
-use opendut_types::cluster::{ClusterConfiguration, ClusterDeployment, ClusterId};
+use uuid::Uuid;
+
+use opendut_types::can::{CanDatabase, CanDatabaseId};
+use opendut_types::cluster::{CanFaultInjectionRule, CanFaultInjectionRuleId, ClusterConfiguration, ClusterDeployment, ClusterId};
 use opendut_types::peer::{PeerDescriptor, PeerId};
 use opendut_types::peer::configuration::{PeerConfiguration, PeerConfiguration2};
+use opendut_types::peer::executor::run::{ExecutorRun, ExecutorRunId, ResultArtifact, ResultArtifactId};
 use opendut_types::peer::state::PeerState;
+use opendut_types::OPENDUT_UUID_NAMESPACE;
 use opendut_types::resources::Id;
+use opendut_types::secret::SecretName;
+use opendut_types::service_account::{ApiTokenId, ServiceAccountDescriptor, ServiceAccountId};
 use opendut_types::topology::{DeviceDescriptor, DeviceId};
 
+use crate::auth::api_token::StoredApiToken;
+use crate::auth::ownership::Ownership;
+use crate::auth::peer_identity::PeerClient;
+use crate::peer::enrollment::Enrollment;
 use crate::resources::IntoId;
+use crate::secrets::EncryptedSecret;
+
+impl IntoId<CanDatabase> for CanDatabaseId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+
+impl IntoId<CanFaultInjectionRule> for CanFaultInjectionRuleId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
 
 impl IntoId<ClusterConfiguration> for ClusterId {
     fn into_id(self) -> Id {
@@ -17,6 +41,11 @@ impl IntoId<ClusterDeployment> for ClusterId {
         Id::from(self.0)
     }
 }
+impl IntoId<Ownership> for ClusterId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
 
 impl IntoId<DeviceDescriptor> for DeviceId {
     fn into_id(self) -> Id {
@@ -24,11 +53,38 @@ impl IntoId<DeviceDescriptor> for DeviceId {
     }
 }
 
+impl IntoId<ExecutorRun> for ExecutorRunId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+
+impl IntoId<ResultArtifact> for ResultArtifactId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+
 impl IntoId<PeerDescriptor> for PeerId {
     fn into_id(self) -> Id {
         Id::from(self.uuid)
     }
 }
+impl IntoId<Enrollment> for PeerId {
+    fn into_id(self) -> Id {
+        Id::from(self.uuid)
+    }
+}
+impl IntoId<Ownership> for PeerId {
+    fn into_id(self) -> Id {
+        Id::from(self.uuid)
+    }
+}
+impl IntoId<PeerClient> for PeerId {
+    fn into_id(self) -> Id {
+        Id::from(self.uuid)
+    }
+}
 impl IntoId<PeerState> for PeerId {
     fn into_id(self) -> Id {
         Id::from(self.uuid)
@@ -45,3 +101,25 @@ impl IntoId<PeerConfiguration2> for PeerId {
         Id::from(self.uuid)
     }
 }
+
+impl IntoId<EncryptedSecret> for SecretName {
+    fn into_id(self) -> Id {
+        Id::from(Uuid::new_v5(&OPENDUT_UUID_NAMESPACE, self.value().as_bytes()))
+    }
+}
+impl IntoId<Ownership> for SecretName {
+    fn into_id(self) -> Id {
+        IntoId::<EncryptedSecret>::into_id(self)
+    }
+}
+
+impl IntoId<ServiceAccountDescriptor> for ServiceAccountId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
+impl IntoId<StoredApiToken> for ApiTokenId {
+    fn into_id(self) -> Id {
+        Id::from(self.0)
+    }
+}
@@ -1,4 +1,5 @@
 use std::any::Any;
+use std::fmt;
 use std::sync::Arc;
 
 use tokio::sync::RwLock;
@@ -15,6 +16,12 @@ struct State {
     resources: Resources
 }
 
+impl fmt::Debug for ResourcesManager {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ResourcesManager").finish_non_exhaustive()
+    }
+}
+
 impl ResourcesManager {
 
     pub fn new() -> ResourcesManagerRef {
@@ -80,7 +87,7 @@ mod test {
 
     use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName};
     use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
-    use opendut_types::peer::executor::{container::{ContainerCommand, ContainerImage, ContainerName, Engine}, ExecutorKind, ExecutorDescriptors, ExecutorDescriptor};
+    use opendut_types::peer::executor::{container::{ContainerCommand, ContainerImage, ContainerImagePullPolicy, ContainerName, Engine}, ExecutorKind, ExecutorDescriptors, ExecutorDescriptor};
     use opendut_types::topology::Topology;
     use opendut_types::util::net::{NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceName};
 
@@ -113,12 +120,23 @@ mod test {
                             engine: Engine::Docker,
                             name: ContainerName::Empty,
                             image: ContainerImage::try_from("testUrl").unwrap(),
+                            pull_policy: ContainerImagePullPolicy::IfNotPresent,
+                            digest: None,
+                            registry_credential: None,
                             volumes: vec![],
                             devices: vec![],
                             envs: vec![],
                             ports: vec![],
                             command: ContainerCommand::Default,
                             args: vec![],
+                            can_databases: vec![],
+                            cpus: None,
+                            memory: None,
+                            pids_limit: None,
+                            network_mode: Default::default(),
+                            cap_add: vec![],
+                            cap_drop: vec![],
+                            read_only_rootfs: false,
                         },
                         results_url: None,
                     }
@@ -132,6 +150,7 @@ mod test {
             name: ClusterName::try_from("ClusterX032").unwrap(),
             leader: peer.id,
             devices: HashSet::new(),
+            can_capture: None,
         };
 
         assert!(testee.is_empty().await);
@@ -0,0 +1,360 @@
+use std::fmt;
+use std::fs;
+use std::io::{self, Write};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use base64::Engine;
+use base64::prelude::BASE64_STANDARD;
+use ring::aead::{Aad, AES_256_GCM, LessSafeKey, Nonce, NONCE_LEN, UnboundKey};
+use ring::rand::{SecureRandom, SystemRandom};
+use serde::{Deserialize, Serialize};
+use tracing::info;
+
+use opendut_types::secret::{Secret, SecretName};
+use opendut_util::project;
+
+use crate::auth::ownership::Ownership;
+use crate::resources::manager::ResourcesManagerRef;
+use crate::resources::Resources;
+
+pub type SecretCipherRef = Arc<SecretCipher>;
+
+/// Secret, as CARL stores it. The value is encrypted with the key of the [`SecretCipher`].
+#[derive(Clone, PartialEq, Eq)]
+pub struct EncryptedSecret {
+    pub name: SecretName,
+    nonce: [u8; NONCE_LEN],
+    ciphertext: Vec<u8>,
+}
+
+impl fmt::Debug for EncryptedSecret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EncryptedSecret")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Encrypts secrets at rest with AES-256-GCM. The name of a secret is authenticated along with its value,
+/// so an encrypted value cannot be passed off as the value of another secret.
+pub struct SecretCipher {
+    key: LessSafeKey,
+    random: SystemRandom,
+}
+
+impl fmt::Debug for SecretCipher {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SecretCipher").finish_non_exhaustive()
+    }
+}
+
+const KEY_LEN: usize = 32;
+
+impl SecretCipher {
+
+    /// Loads the key from the configured file. A new key is generated and written to the file, if it does not exist yet.
+    pub fn load(config: &config::Config) -> anyhow::Result<SecretCipherRef> {
+        let key_file = project::make_path_absolute(config.get_string("secrets.key.file")?)?;
+        let cipher = Self::load_or_generate(&key_file)?;
+        Ok(Arc::new(cipher))
+    }
+
+    fn load_or_generate(key_file: &Path) -> Result<Self, SecretCipherError> {
+        let key = match fs::read_to_string(key_file) {
+            Ok(encoded) => {
+                BASE64_STANDARD.decode(encoded.trim())
+                    .map_err(|cause| SecretCipherError::InvalidKeyFile { path: key_file.to_owned(), cause: cause.to_string() })?
+            }
+            Err(cause) if cause.kind() == io::ErrorKind::NotFound => {
+                let key = Self::generate_key(&SystemRandom::new())?;
+                Self::write_key_file(key_file, &key)
+                    .map_err(|cause| SecretCipherError::WriteKeyFile { path: key_file.to_owned(), cause })?;
+                info!("Generated new key for encrypting secrets at '{}'.", key_file.display());
+                key
+            }
+            Err(cause) => return Err(SecretCipherError::ReadKeyFile { path: key_file.to_owned(), cause }),
+        };
+        Self::new(&key)
+    }
+
+    pub fn new(key: &[u8]) -> Result<Self, SecretCipherError> {
+        let key = UnboundKey::new(&AES_256_GCM, key)
+            .map_err(|_| SecretCipherError::InvalidKeyLength { expected: KEY_LEN, actual: key.len() })?;
+        Ok(Self {
+            key: LessSafeKey::new(key),
+            random: SystemRandom::new(),
+        })
+    }
+
+    /// Cipher with a random key, which is not persisted.
+    pub fn generate() -> Result<Self, SecretCipherError> {
+        let key = Self::generate_key(&SystemRandom::new())?;
+        Self::new(&key)
+    }
+
+    pub fn encrypt(&self, secret: &Secret) -> Result<EncryptedSecret, SecretCipherError> {
+        let mut nonce = [0u8; NONCE_LEN];
+        self.random.fill(&mut nonce)
+            .map_err(|_| SecretCipherError::Random)?;
+
+        let mut ciphertext = Vec::from(secret.value.as_bytes());
+        self.key.seal_in_place_append_tag(Nonce::assume_unique_for_key(nonce), Aad::from(secret.name.value()), &mut ciphertext)
+            .map_err(|_| SecretCipherError::Encryption { secret_name: Clone::clone(&secret.name) })?;
+
+        Ok(EncryptedSecret {
+            name: Clone::clone(&secret.name),
+            nonce,
+            ciphertext,
+        })
+    }
+
+    pub fn decrypt(&self, secret: &EncryptedSecret) -> Result<Secret, SecretCipherError> {
+        let error = || SecretCipherError::Decryption { secret_name: Clone::clone(&secret.name) };
+
+        let mut plaintext = Clone::clone(&secret.ciphertext);
+        let value = self.key.open_in_place(Nonce::assume_unique_for_key(secret.nonce), Aad::from(secret.name.value()), &mut plaintext)
+            .map_err(|_| error())?;
+        let value = String::from_utf8(value.to_vec())
+            .map_err(|_| error())?;
+
+        Ok(Secret {
+            name: Clone::clone(&secret.name),
+            value,
+        })
+    }
+
+    fn generate_key(random: &SystemRandom) -> Result<Vec<u8>, SecretCipherError> {
+        let mut key = vec![0u8; KEY_LEN];
+        random.fill(&mut key)
+            .map_err(|_| SecretCipherError::Random)?;
+        Ok(key)
+    }
+
+    fn write_key_file(key_file: &Path, key: &[u8]) -> io::Result<()> {
+        if let Some(parent) = key_file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = fs::OpenOptions::new()
+            .write(true)
+            .create_new(true)
+            .mode(0o600)
+            .open(key_file)?;
+        file.write_all(BASE64_STANDARD.encode(key).as_bytes())
+    }
+}
+
+/// File, to which the encrypted secrets and their owners are written, so that they survive a restart of CARL.
+/// It only contains encrypted values, the key for decrypting them is kept in its own file.
+#[derive(Clone, Debug, Default)]
+pub struct SecretStore {
+    /// `None`, if the secrets are only kept in memory.
+    file: Option<PathBuf>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredSecret {
+    name: String,
+    nonce: String,
+    ciphertext: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    ownership: Option<StoredOwnership>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct StoredOwnership {
+    owner: String,
+    group: Option<String>,
+}
+
+impl SecretStore {
+    pub fn load(config: &config::Config) -> anyhow::Result<Self> {
+        let file = project::make_path_absolute(config.get_string("secrets.values.file")?)?;
+        Ok(Self::at(file))
+    }
+
+    pub fn at(file: PathBuf) -> Self {
+        Self { file: Some(file) }
+    }
+
+    /// Reads the stored secrets into the resources and returns how many there were.
+    pub async fn restore(&self, resources_manager: &ResourcesManagerRef) -> Result<usize, SecretStoreError> {
+        let Some(file) = &self.file else {
+            return Ok(0);
+        };
+        let content = match fs::read_to_string(file) {
+            Ok(content) => content,
+            Err(cause) if cause.kind() == io::ErrorKind::NotFound => return Ok(0),
+            Err(cause) => return Err(SecretStoreError::Read { path: Clone::clone(file), cause }),
+        };
+        let invalid_file = |cause: String| SecretStoreError::InvalidFile { path: Clone::clone(file), cause };
+
+        let stored_secrets = serde_json::from_str::<Vec<StoredSecret>>(&content)
+            .map_err(|cause| invalid_file(cause.to_string()))?;
+
+        let secrets = stored_secrets.into_iter()
+            .map(|stored| {
+                let name = SecretName::try_from(stored.name)
+                    .map_err(|cause| invalid_file(cause.to_string()))?;
+                let nonce = BASE64_STANDARD.decode(stored.nonce)
+                    .map_err(|cause| invalid_file(cause.to_string()))?
+                    .try_into()
+                    .map_err(|_| invalid_file(format!("Nonce of secret '{name}' must be {NONCE_LEN} bytes long.")))?;
+                let ciphertext = BASE64_STANDARD.decode(stored.ciphertext)
+                    .map_err(|cause| invalid_file(cause.to_string()))?;
+                let ownership = stored.ownership
+                    .map(|StoredOwnership { owner, group }| Ownership { owner, group });
+                Ok((EncryptedSecret { name, nonce, ciphertext }, ownership))
+            })
+            .collect::<Result<Vec<_>, SecretStoreError>>()?;
+
+        let count = secrets.len();
+        resources_manager.resources_mut(|resources| {
+            for (secret, ownership) in secrets {
+                if let Some(ownership) = ownership {
+                    resources.insert(Clone::clone(&secret.name), ownership);
+                }
+                resources.insert(Clone::clone(&secret.name), secret);
+            }
+        }).await;
+
+        info!("Restored {count} secret(s) from '{}'.", file.display());
+        Ok(count)
+    }
+
+    /// Writes all secrets of the resources to the file, replacing its previous content.
+    pub fn save(&self, resources: &Resources) -> Result<(), SecretStoreError> {
+        let Some(file) = &self.file else {
+            return Ok(());
+        };
+
+        let mut stored_secrets = resources.iter::<EncryptedSecret>()
+            .map(|secret| StoredSecret {
+                name: secret.name.value().to_owned(),
+                nonce: BASE64_STANDARD.encode(secret.nonce),
+                ciphertext: BASE64_STANDARD.encode(&secret.ciphertext),
+                ownership: resources.get::<Ownership>(Clone::clone(&secret.name))
+                    .map(|Ownership { owner, group }| StoredOwnership { owner, group }),
+            })
+            .collect::<Vec<_>>();
+        stored_secrets.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let content = serde_json::to_string_pretty(&stored_secrets)
+            .map_err(|cause| SecretStoreError::Write { path: Clone::clone(file), cause: io::Error::other(cause) })?;
+
+        Self::write_file(file, &content)
+            .map_err(|cause| SecretStoreError::Write { path: Clone::clone(file), cause })
+    }
+
+    /// Writes to a temporary file first, so that a failed write does not lose the previously stored secrets.
+    fn write_file(file: &Path, content: &str) -> io::Result<()> {
+        if let Some(parent) = file.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let temporary_file = file.with_extension("tmp");
+        let mut temporary = fs::OpenOptions::new()
+            .write(true)
+            .create(true)
+            .truncate(true)
+            .mode(0o600)
+            .open(&temporary_file)?;
+        temporary.write_all(content.as_bytes())?;
+        temporary.sync_all()?;
+        fs::rename(temporary_file, file)
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SecretStoreError {
+    #[error("Failed to read secrets from '{}':\n  {cause}", path.display())]
+    Read { path: PathBuf, cause: io::Error },
+    #[error("File of secrets at '{}' is not valid:\n  {cause}", path.display())]
+    InvalidFile { path: PathBuf, cause: String },
+    #[error("Failed to write secrets to '{}':\n  {cause}", path.display())]
+    Write { path: PathBuf, cause: io::Error },
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum SecretCipherError {
+    #[error("Failed to read key for secrets from '{}':\n  {cause}", path.display())]
+    ReadKeyFile { path: PathBuf, cause: io::Error },
+    #[error("Failed to write key for secrets to '{}':\n  {cause}", path.display())]
+    WriteKeyFile { path: PathBuf, cause: io::Error },
+    #[error("Key file for secrets at '{}' is not valid base64:\n  {cause}", path.display())]
+    InvalidKeyFile { path: PathBuf, cause: String },
+    #[error("Key for secrets must be {expected} bytes long, but is {actual} bytes long.")]
+    InvalidKeyLength { expected: usize, actual: usize },
+    #[error("Failed to generate random bytes.")]
+    Random,
+    #[error("Failed to encrypt secret '{secret_name}'.")]
+    Encryption { secret_name: SecretName },
+    #[error("Failed to decrypt secret '{secret_name}'. The key for secrets may have changed.")]
+    Decryption { secret_name: SecretName },
+}
+
+#[cfg(test)]
+mod tests {
+    use std::os::unix::fs::PermissionsExt;
+
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn secret(name: &str, value: &str) -> anyhow::Result<Secret> {
+        Ok(Secret {
+            name: SecretName::try_from(name)?,
+            value: value.to_owned(),
+        })
+    }
+
+    #[test]
+    fn should_decrypt_encrypted_secrets() -> anyhow::Result<()> {
+        let cipher = SecretCipher::generate()?;
+        let secret = secret("db-password", "hunter2")?;
+
+        let encrypted = cipher.encrypt(&secret)?;
+        assert_that!(encrypted.ciphertext.windows(7).any(|window| window == b"hunter2"), eq(false));
+        assert_that!(format!("{encrypted:?}"), not(contains_substring("hunter2")));
+
+        let decrypted = cipher.decrypt(&encrypted)?;
+        assert_that!(decrypted, eq(secret));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_not_decrypt_with_another_key_or_name() -> anyhow::Result<()> {
+        let cipher = SecretCipher::generate()?;
+        let encrypted = cipher.encrypt(&secret("db-password", "hunter2")?)?;
+
+        let other_cipher = SecretCipher::generate()?;
+        assert!(matches!(other_cipher.decrypt(&encrypted), Err(SecretCipherError::Decryption { .. })));
+
+        let renamed = EncryptedSecret {
+            name: SecretName::try_from("token")?,
+            ..encrypted
+        };
+        assert!(matches!(cipher.decrypt(&renamed), Err(SecretCipherError::Decryption { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_generate_the_key_file_once_and_reuse_it() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let key_file = temp.path().join("secrets").join("secrets.key");
+
+        let cipher = SecretCipher::load_or_generate(&key_file)?;
+        let encrypted = cipher.encrypt(&secret("db-password", "hunter2")?)?;
+
+        let mode = fs::metadata(&key_file)?.permissions().mode();
+        assert_that!(mode & 0o777, eq(0o600));
+
+        let reloaded = SecretCipher::load_or_generate(&key_file)?;
+        assert_that!(reloaded.decrypt(&encrypted)?.value, eq("hunter2"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,57 @@
+use std::time::Duration;
+
+use chrono::{DateTime, Local, SecondsFormat};
+use serde::Serialize;
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::service_account::{ApiToken, ApiTokenDescriptor, ServiceAccountId};
+
+use crate::CreateOutputFormat;
+
+/// Create an API token for a service account. The token is only shown once.
+#[derive(clap::Parser)]
+pub struct CreateApiTokenCli {
+    ///ID of the service account
+    #[arg(long)]
+    service_account: Uuid,
+    ///Number of days, after which the token expires
+    #[arg(long, default_value_t = 30, value_parser = clap::value_parser!(u64).range(1..))]
+    expires_in_days: u64,
+}
+
+#[derive(Serialize)]
+struct CreatedApiToken {
+    #[serde(flatten)]
+    descriptor: ApiTokenDescriptor,
+    token: ApiToken,
+}
+
+impl CreateApiTokenCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+        let service_account_id = ServiceAccountId::from(self.service_account);
+        let lifetime = Duration::from_secs(self.expires_in_days * 24 * 60 * 60);
+
+        let (descriptor, token) = carl.service_accounts.create_api_token(service_account_id, lifetime).await
+            .map_err(|error| format!("Could not create API token for service account <{service_account_id}>.\n  {error}"))?;
+
+        match output {
+            CreateOutputFormat::Text => {
+                let expires_at = DateTime::<Local>::from(descriptor.expires_at).to_rfc3339_opts(SecondsFormat::Secs, false);
+                println!("Successfully created API token <{}> for service account <{service_account_id}>, which expires at {expires_at}.", descriptor.id);
+                println!("Store the token securely, it cannot be shown again:");
+                println!("{}", token.value());
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&CreatedApiToken { descriptor, token }).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&CreatedApiToken { descriptor, token }).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
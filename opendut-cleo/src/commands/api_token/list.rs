@@ -0,0 +1,65 @@
+use chrono::{DateTime, Local, SecondsFormat};
+use cli_table::{print_stdout, Table, WithTitle};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::service_account::{ApiTokenDescriptor, ApiTokenId, ServiceAccountId};
+
+use crate::ListOutputFormat;
+
+/// List the API tokens of service accounts. The tokens themselves are not shown.
+#[derive(clap::Parser)]
+pub struct ListApiTokensCli {
+    ///Only list the tokens of this service account
+    #[arg(long)]
+    service_account: Option<Uuid>,
+}
+
+#[derive(Table)]
+struct ApiTokenTable {
+    #[table(title = "ApiTokenID")]
+    id: ApiTokenId,
+    #[table(title = "ServiceAccountID")]
+    service_account_id: ServiceAccountId,
+    #[table(title = "Created")]
+    created_at: String,
+    #[table(title = "Expires")]
+    expires_at: String,
+}
+
+impl ListApiTokensCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let service_account_id = self.service_account.map(ServiceAccountId::from);
+
+        let tokens = carl.service_accounts.list_api_tokens(service_account_id).await
+            .map_err(|error| format!("Could not list any API tokens.\n  {error}"))?;
+
+        match output {
+            ListOutputFormat::Table => {
+                let api_token_table = tokens.into_iter()
+                    .map(api_token_table_row)
+                    .collect::<Vec<_>>();
+                print_stdout(api_token_table.with_title())
+                    .expect("List of API tokens should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&tokens).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&tokens).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn api_token_table_row(token: ApiTokenDescriptor) -> ApiTokenTable {
+    ApiTokenTable {
+        id: token.id,
+        service_account_id: token.service_account_id,
+        created_at: DateTime::<Local>::from(token.created_at).to_rfc3339_opts(SecondsFormat::Secs, false),
+        expires_at: DateTime::<Local>::from(token.expires_at).to_rfc3339_opts(SecondsFormat::Secs, false),
+    }
+}
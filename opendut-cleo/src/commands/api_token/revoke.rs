@@ -0,0 +1,25 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::service_account::ApiTokenId;
+
+/// Revoke an API token of a service account
+#[derive(clap::Parser)]
+pub struct RevokeApiTokenCli {
+    ///ID of the API token
+    #[arg()]
+    id: Uuid,
+}
+
+impl RevokeApiTokenCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = ApiTokenId::from(self.id);
+
+        let descriptor = carl.service_accounts.revoke_api_token(id).await
+            .map_err(|error| format!("Failed to revoke API token with id <{id}>.\n  {error}"))?;
+
+        println!("Revoked API token <{}> of service account <{}> successfully.", descriptor.id, descriptor.service_account_id);
+
+        Ok(())
+    }
+}
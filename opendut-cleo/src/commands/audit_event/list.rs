@@ -0,0 +1,96 @@
+use chrono::{DateTime, FixedOffset, Local, SecondsFormat};
+use cli_table::{print_stdout, Table, WithTitle};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::audit::{AuditEvent, AuditEventFilter};
+
+use crate::ListOutputFormat;
+
+/// List the mutating operations recorded in the audit log of CARL, most recent first
+#[derive(clap::Parser)]
+pub struct ListAuditEventsCli {
+    ///Only list operations requested by this user
+    #[arg(long)]
+    user: Option<String>,
+    ///Only list operations with this name, e.g. 'delete_cluster_deployment'
+    #[arg(long)]
+    operation: Option<String>,
+    ///Only list operations on the peer, cluster or other resource with this ID
+    #[arg(long)]
+    resource_id: Option<Uuid>,
+    ///Only list operations at or after this time, e.g. '2024-05-01T12:00:00+02:00'
+    #[arg(long)]
+    since: Option<DateTime<FixedOffset>>,
+    ///Only list operations at or before this time
+    #[arg(long)]
+    until: Option<DateTime<FixedOffset>>,
+    ///Maximum number of operations to list
+    #[arg(long, default_value_t = 100)]
+    limit: u32,
+}
+
+#[derive(Table)]
+struct AuditEventTable {
+    #[table(title = "Timestamp")]
+    timestamp: String,
+    #[table(title = "User")]
+    user: String,
+    #[table(title = "Operation")]
+    operation: String,
+    #[table(title = "ResourceID")]
+    resource_id: String,
+    #[table(title = "Before")]
+    before: String,
+    #[table(title = "After")]
+    after: String,
+    #[table(title = "Outcome")]
+    outcome: String,
+}
+
+impl ListAuditEventsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let filter = AuditEventFilter {
+            user: self.user,
+            operation: self.operation,
+            resource_id: self.resource_id.map(From::from),
+            since: self.since.map(From::from),
+            until: self.until.map(From::from),
+            limit: Some(self.limit),
+        };
+
+        let events = carl.audit.list_audit_events(filter).await
+            .map_err(|error| format!("Could not list audit events.\n  {error}"))?;
+
+        match output {
+            ListOutputFormat::Table => {
+                let audit_event_table = events.into_iter()
+                    .map(audit_event_table_row)
+                    .collect::<Vec<_>>();
+                print_stdout(audit_event_table.with_title())
+                    .expect("List of audit events should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&events).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&events).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn audit_event_table_row(event: AuditEvent) -> AuditEventTable {
+    AuditEventTable {
+        timestamp: DateTime::<Local>::from(event.timestamp).to_rfc3339_opts(SecondsFormat::Secs, false),
+        user: event.user.unwrap_or_default(),
+        operation: event.operation,
+        resource_id: event.resource_id.map(|resource_id| resource_id.to_string()).unwrap_or_default(),
+        before: event.before.unwrap_or_default(),
+        after: event.after.unwrap_or_default(),
+        outcome: event.outcome.to_string(),
+    }
+}
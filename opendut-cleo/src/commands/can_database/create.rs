@@ -0,0 +1,84 @@
+use std::path::PathBuf;
+
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::can::{CanDatabase, CanDatabaseFormat, CanDatabaseId, CanDatabaseName, CanDatabaseVersion};
+
+use crate::{CanDatabaseFormatVariants, CreateOutputFormat};
+
+/// Upload a CAN database (ARXML or DBC file) to CARL
+#[derive(clap::Parser)]
+pub struct CreateCanDatabaseCli {
+    ///Path to the ARXML or DBC file
+    #[arg(short, long)]
+    file: PathBuf,
+    ///Name of the CAN database, defaults to the file name
+    #[arg(short, long)]
+    name: Option<CanDatabaseName>,
+    ///Version of the CAN database
+    #[arg(short, long)]
+    version: CanDatabaseVersion,
+    ///Format of the CAN database, derived from the file extension if not specified
+    #[arg(long)]
+    format: Option<CanDatabaseFormatVariants>,
+    ///ID of the CAN database, specify to replace an existing database
+    #[arg(long)]
+    id: Option<Uuid>,
+}
+
+impl CreateCanDatabaseCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+
+        let format = match self.format {
+            Some(CanDatabaseFormatVariants::Arxml) => CanDatabaseFormat::Arxml,
+            Some(CanDatabaseFormatVariants::Dbc) => CanDatabaseFormat::Dbc,
+            None => {
+                self.file.extension()
+                    .and_then(|extension| CanDatabaseFormat::from_file_extension(&extension.to_string_lossy()))
+                    .ok_or_else(|| format!("Could not determine format of CAN database '{}' from its file extension. Please specify it via --format.", self.file.display()))?
+            }
+        };
+
+        let name = match self.name {
+            Some(name) => name,
+            None => {
+                let file_stem = self.file.file_stem()
+                    .map(|file_stem| file_stem.to_string_lossy().to_string())
+                    .unwrap_or_default();
+                CanDatabaseName::try_from(file_stem)
+                    .map_err(|cause| format!("Could not derive name of CAN database from file '{}'. Please specify it via --name.\n  {cause}", self.file.display()))?
+            }
+        };
+
+        let content = std::fs::read(&self.file)
+            .map_err(|cause| format!("Failed to read CAN database from '{}'.\n  {cause}", self.file.display()))?;
+
+        let id = self.id.map(CanDatabaseId::from).unwrap_or_else(CanDatabaseId::random);
+
+        let can_database = CanDatabase::new(id, name, self.version, format, content)
+            .map_err(|cause| cause.to_string())?;
+        let descriptor = Clone::clone(&can_database.descriptor);
+
+        carl.can_databases.store_can_database(can_database).await
+            .map_err(|error| format!("Could not store CAN database.\n  {error}"))?;
+
+        match output {
+            CreateOutputFormat::Text => {
+                println!("Successfully stored CAN database '{}' in version '{}' <{}>.", descriptor.name, descriptor.version, descriptor.id);
+                println!("Format: {}", descriptor.format);
+                println!("SHA-256: {}", descriptor.hash);
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&descriptor).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&descriptor).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::can::CanDatabaseId;
+
+/// Delete a CAN database
+#[derive(clap::Parser)]
+pub struct DeleteCanDatabaseCli {
+    ///ID of the CAN database
+    #[arg()]
+    id: Uuid,
+}
+
+impl DeleteCanDatabaseCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = CanDatabaseId::from(self.id);
+
+        let descriptor = carl.can_databases.delete_can_database(id).await
+            .map_err(|error| format!("Failed to delete CAN database with id <{id}>.\n  {error}"))?;
+
+        println!("Deleted CAN database '{}' in version '{}' <{}> successfully.", descriptor.name, descriptor.version, descriptor.id);
+
+        Ok(())
+    }
+}
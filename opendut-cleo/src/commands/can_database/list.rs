@@ -0,0 +1,58 @@
+use cli_table::{print_stdout, Table, WithTitle};
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::can::{CanDatabaseFormat, CanDatabaseId, CanDatabaseName, CanDatabaseVersion};
+
+use crate::ListOutputFormat;
+
+/// List all CAN databases
+#[derive(clap::Parser)]
+pub struct ListCanDatabasesCli;
+
+#[derive(Table)]
+struct CanDatabaseTable {
+    #[table(title = "Name")]
+    name: CanDatabaseName,
+    #[table(title = "Version")]
+    version: CanDatabaseVersion,
+    #[table(title = "Format")]
+    format: CanDatabaseFormat,
+    #[table(title = "CanDatabaseID")]
+    id: CanDatabaseId,
+    #[table(title = "SHA-256")]
+    hash: String,
+}
+
+impl ListCanDatabasesCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let descriptors = carl.can_databases.list_can_databases().await
+            .map_err(|error| format!("Could not list any CAN databases.\n  {error}"))?;
+
+        match output {
+            ListOutputFormat::Table => {
+                let can_database_table = descriptors.into_iter()
+                    .map(|descriptor| {
+                        CanDatabaseTable {
+                            name: descriptor.name,
+                            version: descriptor.version,
+                            format: descriptor.format,
+                            id: descriptor.id,
+                            hash: descriptor.hash.to_string(),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                print_stdout(can_database_table.with_title())
+                    .expect("List of CAN databases should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&descriptors).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&descriptors).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
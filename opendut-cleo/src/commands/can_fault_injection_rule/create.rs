@@ -0,0 +1,110 @@
+use std::ops::Not;
+use std::time::Duration;
+
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::cluster::{CanFault, CanFaultInjectionRule, CanFaultInjectionRuleId, ClusterId};
+use opendut_types::util::net::CanIdFilter;
+
+use crate::CreateOutputFormat;
+
+/// Create a rule, which injects faults into the CAN frames of a cluster
+#[derive(clap::Parser)]
+#[command(group(clap::ArgGroup::new("fault").required(true).args(["drop", "flip_bits", "set_dlc", "delay"])))]
+pub struct CreateCanFaultInjectionRuleCli {
+    ///ID of the cluster
+    #[arg(long)]
+    cluster_id: Uuid,
+    ///CAN IDs, which the fault is injected into, in the format <id>:<mask> (hexadecimal, candump notation)
+    #[arg(long)]
+    can_id: CanIdFilter,
+    ///Drop the given percentage of frames at random
+    #[arg(long)]
+    drop: Option<u8>,
+    ///Invert bits of a data byte, in the format <byte>:<mask> (byte index in decimal, mask in hexadecimal)
+    #[arg(long, value_parser = parse_flip_bits)]
+    flip_bits: Option<(u8, u8)>,
+    ///Overwrite the data length code of classic CAN frames
+    #[arg(long)]
+    set_dlc: Option<u8>,
+    ///Delay frames by the given number of milliseconds
+    #[arg(long)]
+    delay: Option<u64>,
+    ///Create the rule without enabling it
+    #[arg(long)]
+    disabled: bool,
+    ///ID of the rule, specify to replace an existing rule
+    #[arg(long)]
+    id: Option<Uuid>,
+}
+
+impl CreateCanFaultInjectionRuleCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+
+        let fault = if let Some(percentage) = self.drop {
+            CanFault::Drop { percentage }
+        } else if let Some((byte, mask)) = self.flip_bits {
+            CanFault::FlipBits { byte, mask }
+        } else if let Some(dlc) = self.set_dlc {
+            CanFault::SetDlc { dlc }
+        } else if let Some(delay) = self.delay {
+            CanFault::Delay { delay: Duration::from_millis(delay) }
+        } else {
+            unreachable!("Clap requires one of the fault arguments.")
+        };
+        fault.verify().map_err(|cause| cause.to_string())?;
+
+        let rule = CanFaultInjectionRule {
+            id: self.id.map(CanFaultInjectionRuleId::from).unwrap_or_else(CanFaultInjectionRuleId::random),
+            cluster_id: ClusterId::from(self.cluster_id),
+            filter: self.can_id,
+            fault,
+            enabled: self.disabled.not(),
+        };
+
+        carl.cluster.store_can_fault_injection_rule(Clone::clone(&rule)).await
+            .map_err(|error| format!("Could not store CAN fault injection rule.\n  {error}"))?;
+
+        match output {
+            CreateOutputFormat::Text => {
+                let state = if rule.enabled { "enabled" } else { "disabled" };
+                println!("Successfully stored {state} CAN fault injection rule <{}> for cluster <{}>.", rule.id, rule.cluster_id);
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&rule).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&rule).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn parse_flip_bits(value: &str) -> Result<(u8, u8), String> {
+    let (byte, mask) = value.split_once(':')
+        .ok_or_else(|| format!("Expected <byte>:<mask>, but got '{value}'."))?;
+    let byte = byte.parse::<u8>()
+        .map_err(|cause| format!("Invalid byte index '{byte}': {cause}"))?;
+    let mask = u8::from_str_radix(mask.trim_start_matches("0x"), 16)
+        .map_err(|cause| format!("Invalid bit mask '{mask}': {cause}"))?;
+    Ok((byte, mask))
+}
+
+#[cfg(test)]
+mod test {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_flip_bits() {
+        assert_that!(parse_flip_bits("3:81"), ok(eq((3, 0x81))));
+        assert_that!(parse_flip_bits("0:0x0F"), ok(eq((0, 0x0F))));
+        assert_that!(parse_flip_bits("3"), err(anything()));
+        assert_that!(parse_flip_bits("3:1FF"), err(anything()));
+    }
+}
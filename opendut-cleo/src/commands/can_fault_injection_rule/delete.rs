@@ -0,0 +1,25 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::cluster::CanFaultInjectionRuleId;
+
+/// Delete a CAN fault injection rule
+#[derive(clap::Parser)]
+pub struct DeleteCanFaultInjectionRuleCli {
+    ///ID of the rule
+    #[arg()]
+    id: Uuid,
+}
+
+impl DeleteCanFaultInjectionRuleCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = CanFaultInjectionRuleId::from(self.id);
+
+        let rule = carl.cluster.delete_can_fault_injection_rule(id).await
+            .map_err(|error| format!("Failed to delete CAN fault injection rule with id <{id}>.\n  {error}"))?;
+
+        println!("Deleted CAN fault injection rule <{}> of cluster <{}> successfully.", rule.id, rule.cluster_id);
+
+        Ok(())
+    }
+}
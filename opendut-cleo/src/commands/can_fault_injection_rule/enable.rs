@@ -0,0 +1,40 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::cluster::CanFaultInjectionRuleId;
+
+/// Enable or disable a CAN fault injection rule, which takes effect in a deployed cluster immediately
+#[derive(clap::Parser)]
+pub struct EnableCanFaultInjectionRuleCli {
+    ///ID of the rule
+    #[arg()]
+    id: Uuid,
+}
+
+impl EnableCanFaultInjectionRuleCli {
+    pub async fn execute(self, carl: &mut CarlClient, enabled: bool) -> crate::Result<()> {
+        let id = CanFaultInjectionRuleId::from(self.id);
+
+        let rules = carl.cluster.list_can_fault_injection_rules(None).await
+            .map_err(|error| format!("Could not list CAN fault injection rules.\n  {error}"))?;
+
+        let mut rule = rules.into_iter()
+            .find(|rule| rule.id == id)
+            .ok_or_else(|| format!("CAN fault injection rule <{id}> does not exist."))?;
+
+        let state = if enabled { "enabled" } else { "disabled" };
+
+        if rule.enabled == enabled {
+            println!("CAN fault injection rule <{id}> is already {state}.");
+            return Ok(());
+        }
+
+        rule.enabled = enabled;
+        carl.cluster.store_can_fault_injection_rule(rule).await
+            .map_err(|error| format!("Could not update CAN fault injection rule <{id}>.\n  {error}"))?;
+
+        println!("CAN fault injection rule <{id}> {state} successfully.");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+use cli_table::{print_stdout, Table, WithTitle};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::cluster::{CanFaultInjectionRuleId, ClusterId};
+use opendut_types::util::net::CanIdFilter;
+
+use crate::ListOutputFormat;
+
+/// List CAN fault injection rules
+#[derive(clap::Parser)]
+pub struct ListCanFaultInjectionRulesCli {
+    ///Only list the rules of this cluster
+    #[arg(long)]
+    cluster_id: Option<Uuid>,
+}
+
+#[derive(Table)]
+struct CanFaultInjectionRuleTable {
+    #[table(title = "RuleID")]
+    id: CanFaultInjectionRuleId,
+    #[table(title = "ClusterID")]
+    cluster_id: ClusterId,
+    #[table(title = "CAN ID")]
+    filter: CanIdFilter,
+    #[table(title = "Fault")]
+    fault: String,
+    #[table(title = "Enabled")]
+    enabled: bool,
+}
+
+impl ListCanFaultInjectionRulesCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let rules = carl.cluster.list_can_fault_injection_rules(self.cluster_id.map(ClusterId::from)).await
+            .map_err(|error| format!("Could not list any CAN fault injection rules.\n  {error}"))?;
+
+        match output {
+            ListOutputFormat::Table => {
+                let rule_table = rules.into_iter()
+                    .map(|rule| {
+                        CanFaultInjectionRuleTable {
+                            id: rule.id,
+                            cluster_id: rule.cluster_id,
+                            filter: rule.filter,
+                            fault: rule.fault.to_string(),
+                            enabled: rule.enabled,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                print_stdout(rule_table.with_title())
+                    .expect("List of CAN fault injection rules should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&rules).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&rules).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
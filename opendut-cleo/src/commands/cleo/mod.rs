@@ -0,0 +1,2 @@
+pub mod revoke;
+pub mod rotate_secret;
@@ -0,0 +1,27 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_carl_api::carl::peer::CredentialsHolder;
+use opendut_types::cleo::CleoId;
+
+/// Revoke the client credentials of a CLEO instance
+#[derive(clap::Parser)]
+pub struct RevokeCleoCli {
+    ///CleoID
+    #[arg()]
+    id: Uuid,
+}
+
+impl RevokeCleoCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = CleoId::from(self.id);
+        let client_ids = carl.peers
+            .revoke_client(CredentialsHolder::Cleo(id))
+            .await
+            .map_err(|error| format!("Failed to revoke client credentials of CLEO with the id '{}'.\n  {}", id, error))?;
+        let client_ids = client_ids.into_iter().map(|client_id| client_id.value()).collect::<Vec<_>>();
+        println!("Revoked client credentials of CLEO with the CleoID {}: {}", id, client_ids.join(", "));
+
+        Ok(())
+    }
+}
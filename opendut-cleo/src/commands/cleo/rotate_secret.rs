@@ -0,0 +1,30 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_carl_api::carl::peer::CredentialsHolder;
+use opendut_types::cleo::CleoId;
+
+/// Rotate the client secret of a CLEO instance and print the new credentials
+#[derive(clap::Parser)]
+pub struct RotateSecretCleoCli {
+    ///CleoID
+    #[arg()]
+    id: Uuid,
+}
+
+impl RotateSecretCleoCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = CleoId::from(self.id);
+        let credentials = carl.peers
+            .rotate_client_secret(CredentialsHolder::Cleo(id))
+            .await
+            .map_err(|error| format!("Failed to rotate client secret of CLEO with the id '{}'.\n  {}", id, error))?
+            .ok_or(format!("CARL did not return the rotated client secret of CLEO with the id '{}'.", id))?;
+
+        println!("client_id = \"{}\"", credentials.client_id.value());
+        println!("client_secret = \"{}\"", credentials.client_secret.value());
+        eprintln!("The previous client secret is no longer valid. Update 'network.oidc.client.secret' in the configuration of this CLEO instance.");
+
+        Ok(())
+    }
+}
@@ -1,12 +1,15 @@
 use std::collections::HashSet;
 use std::ops::Not;
+use std::time::Duration;
 
 use uuid::Uuid;
 
 use opendut_carl_api::carl::CarlClient;
-use opendut_types::cluster::{ClusterConfiguration, ClusterId};
+use opendut_types::cluster::{CanCaptureConfiguration, CanCaptureRotation, CanCaptureSource, ClusterConfiguration, ClusterId};
+use opendut_types::peer::executor::ResultsUrl;
 use opendut_types::peer::PeerId;
 use opendut_types::topology::{DeviceDescriptor, DeviceName};
+use opendut_types::util::net::NetworkInterfaceName;
 
 use crate::{ClusterConfigurationDevices, CreateOutputFormat};
 use crate::parse::cluster::{ParseableClusterId, ParseableClusterName};
@@ -26,6 +29,53 @@ pub struct CreateClusterConfigurationCli {
     ///List of devices in cluster
     #[clap(flatten)]
     devices: ClusterConfigurationDevices,
+    #[clap(flatten)]
+    can_capture: CanCaptureArgs,
+}
+
+#[derive(clap::Args)]
+struct CanCaptureArgs {
+    ///Record the CAN traffic of the cluster into candump log files on each peer
+    #[arg(long)]
+    can_capture: bool,
+    ///Only record the traffic of these device interfaces instead of the whole CAN bridge
+    #[arg(long, num_args = 1.., requires = "can_capture")]
+    can_capture_interfaces: Option<Vec<NetworkInterfaceName>>,
+    ///Maximum size of a single log file in bytes, before a new one is started (0 disables this limit)
+    #[arg(long, default_value_t = CanCaptureRotation::DEFAULT_MAX_FILE_SIZE, requires = "can_capture")]
+    can_capture_max_file_size: u64,
+    ///Maximum duration in seconds a single log file is written to, before a new one is started (0 disables this limit)
+    #[arg(long, default_value_t = CanCaptureRotation::DEFAULT_MAX_FILE_DURATION.as_secs(), requires = "can_capture")]
+    can_capture_max_file_duration: u64,
+    ///Number of log files to keep on each peer (0 keeps all log files)
+    #[arg(long, default_value_t = CanCaptureRotation::DEFAULT_MAX_FILES, requires = "can_capture")]
+    can_capture_max_files: u32,
+    ///URL to which the log files will be uploaded
+    #[arg(long, requires = "can_capture")]
+    can_capture_results_url: Option<ResultsUrl>,
+}
+
+impl CanCaptureArgs {
+    fn into_configuration(self) -> Option<CanCaptureConfiguration> {
+        if self.can_capture.not() {
+            return None;
+        }
+
+        let source = match self.can_capture_interfaces {
+            Some(interfaces) => CanCaptureSource::DeviceInterfaces(interfaces),
+            None => CanCaptureSource::Bridge,
+        };
+
+        Some(CanCaptureConfiguration {
+            source,
+            rotation: CanCaptureRotation {
+                max_file_size: self.can_capture_max_file_size,
+                max_file_duration: Duration::from_secs(self.can_capture_max_file_duration),
+                max_files: self.can_capture_max_files,
+            },
+            results_url: self.can_capture_results_url,
+        })
+    }
 }
 
 impl CreateClusterConfigurationCli {
@@ -69,7 +119,9 @@ impl CreateClusterConfigurationCli {
             Err("Specify at least 2 devices per cluster configuration.".to_string())?
         }
 
-        let configuration = ClusterConfiguration { id: cluster_id, name: Clone::clone(&cluster_name), leader, devices: device_ids };
+        let can_capture = self.can_capture.into_configuration();
+
+        let configuration = ClusterConfiguration { id: cluster_id, name: Clone::clone(&cluster_name), leader, devices: device_ids, can_capture: Clone::clone(&can_capture) };
         carl.cluster.store_cluster_configuration(configuration.clone()).await
             .map_err(|err| format!("Could not store cluster configuration. Make sure the application is running. Error: {}", err))?;
 
@@ -83,6 +135,12 @@ impl CreateClusterConfigurationCli {
                 for device_name in device_names.iter() {
                     println!("\x09{}", device_name);
                 };
+                if let Some(can_capture) = can_capture {
+                    match can_capture.source {
+                        CanCaptureSource::Bridge => println!("CAN traffic on the CAN bridge of each peer is captured."),
+                        CanCaptureSource::DeviceInterfaces(interfaces) => println!("CAN traffic is captured on the interfaces: {}", interfaces.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")),
+                    }
+                }
             }
             CreateOutputFormat::Json => {
                 let json = serde_json::to_string(&configuration).unwrap();
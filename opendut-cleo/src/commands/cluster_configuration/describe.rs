@@ -2,7 +2,7 @@ use indoc::indoc;
 use uuid::Uuid;
 
 use opendut_carl_api::carl::CarlClient;
-use opendut_types::cluster::{ClusterId, ClusterName};
+use opendut_types::cluster::{CanCaptureConfiguration, CanCaptureSource, ClusterId, ClusterName};
 use opendut_types::peer::{PeerId, PeerName};
 use serde::Serialize;
 use opendut_types::topology::DeviceName;
@@ -25,6 +25,7 @@ struct ClusterTable {
     leader: PeerId,
     peers: Vec<PeerName>,
     devices: Vec<DeviceName>,
+    can_capture: Option<CanCaptureConfiguration>,
 }
 
 impl DescribeClusterConfigurationCli {
@@ -64,6 +65,7 @@ impl DescribeClusterConfigurationCli {
             leader: cluster_configuration.leader,
             peers: cluster_peers,
             devices: cluster_devices,
+            can_capture: cluster_configuration.can_capture,
         };
 
         let text = match output {
@@ -74,7 +76,8 @@ impl DescribeClusterConfigurationCli {
                   Leader: {}
                   Peers: [{:?}]
                   Devices: [{:?}]
-            "), table.name, table.id, table.leader, table.peers, table.devices)
+                  CAN Capture: {}
+            "), table.name, table.id, table.leader, table.peers, table.devices, describe_can_capture(&table.can_capture))
             }
             DescribeOutputFormat::Json => {
                 serde_json::to_string(&table).unwrap()
@@ -89,3 +92,19 @@ impl DescribeClusterConfigurationCli {
         Ok(())
     }
 }
+
+fn describe_can_capture(can_capture: &Option<CanCaptureConfiguration>) -> String {
+    match can_capture {
+        None => String::from("disabled"),
+        Some(can_capture) => {
+            let source = match &can_capture.source {
+                CanCaptureSource::Bridge => String::from("CAN bridge"),
+                CanCaptureSource::DeviceInterfaces(interfaces) => interfaces.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "),
+            };
+            match &can_capture.results_url {
+                Some(results_url) => format!("{source} (uploaded to {})", results_url.value()),
+                None => source,
+            }
+        }
+    }
+}
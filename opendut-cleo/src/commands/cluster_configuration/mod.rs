@@ -2,3 +2,4 @@ pub mod create;
 pub mod list;
 pub mod describe;
 pub mod delete;
+pub mod transfer;
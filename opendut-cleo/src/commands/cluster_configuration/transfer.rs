@@ -0,0 +1,31 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::cluster::ClusterId;
+
+/// Transfer the ownership of a cluster configuration to another user and group
+#[derive(clap::Parser)]
+pub struct TransferClusterConfigurationCli {
+    ///ClusterID
+    #[arg()]
+    id: Uuid,
+    ///Name of the user, who becomes the owner
+    #[arg(long)]
+    owner: String,
+    ///Group, whose members may also access the cluster configuration
+    #[arg(long)]
+    group: Option<String>,
+}
+
+impl TransferClusterConfigurationCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = ClusterId::from(self.id);
+
+        carl.cluster.transfer_cluster_configuration_ownership(id, Clone::clone(&self.owner), self.group).await
+            .map_err(|error| format!("Failed to transfer ownership of ClusterConfiguration with id <{id}>.\n  {error}"))?;
+
+        println!("Transferred ClusterConfiguration <{}> to '{}' successfully.", id, self.owner);
+
+        Ok(())
+    }
+}
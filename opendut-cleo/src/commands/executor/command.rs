@@ -0,0 +1,29 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::executor::ExecutorCommand;
+use opendut_types::peer::PeerId;
+
+/// Start, stop or restart an executor on a connected peer, without redeploying the peer
+#[derive(clap::Parser)]
+pub struct ExecutorCommandCli {
+    ///ID of the peer
+    #[arg()]
+    peer_id: Uuid,
+    ///Name of the executor
+    #[arg()]
+    name: String,
+}
+
+impl ExecutorCommandCli {
+    pub async fn execute(self, carl: &mut CarlClient, command: ExecutorCommand) -> crate::Result<()> {
+        let peer_id = PeerId::from(self.peer_id);
+
+        carl.broker.execute_executor_command(peer_id, self.name.clone(), command).await
+            .map_err(|error| format!("Could not {command} executor '{}' on peer <{peer_id}>.\n  {error}", self.name))?;
+
+        println!("Sent command '{command}' to executor '{}' on peer <{peer_id}>.", self.name);
+
+        Ok(())
+    }
+}
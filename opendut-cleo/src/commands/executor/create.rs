@@ -2,8 +2,9 @@ use opendut_types::peer::executor::ExecutorDescriptor;
 use uuid::Uuid;
 
 use opendut_carl_api::carl::CarlClient;
+use opendut_types::can::CanDatabaseId;
 use opendut_types::peer::PeerId;
-use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine}, ExecutorKind, ResultsUrl};
+use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerImageDigest, ContainerImagePullPolicy, ContainerName, ContainerPortSpec, ContainerRegistryCredentialName, ContainerVolume, ContainerCpuLimit, ContainerMemoryLimit, ContainerPidsLimit, ContainerNetworkMode, ContainerCapability, Engine}, ExecutorKind, ResultsUrl};
 
 use crate::{CreateOutputFormat, DescribeOutputFormat, EngineVariants};
 
@@ -22,6 +23,15 @@ pub struct CreateContainerExecutorCli {
     ///Container image
     #[arg(short, long)]
     image: ContainerImage,
+    ///When to pull the container image: always, if-not-present or never
+    #[arg(long, default_value_t)]
+    pull_policy: ContainerImagePullPolicy,
+    ///Digest the container image is pinned to, e.g. sha256:<hash>
+    #[arg(long)]
+    digest: Option<ContainerImageDigest>,
+    ///Name of a registry credential configured in CARL, which is used to pull the container image
+    #[arg(long)]
+    registry_credential: Option<ContainerRegistryCredentialName>,
     ///Container volumes
     #[arg(short, long, num_args = 1..)]
     volumes: Option<Vec<ContainerVolume>>,
@@ -43,6 +53,30 @@ pub struct CreateContainerExecutorCli {
     ///URL to which results will be uploaded
     #[arg(short, long)]
     results_url: Option<ResultsUrl>,
+    ///IDs of CAN databases, which are provided to the container in the directory /can-databases
+    #[arg(long, num_args = 1..)]
+    can_databases: Option<Vec<Uuid>>,
+    ///Number of CPUs the container may use, e.g. 1.5
+    #[arg(long)]
+    cpus: Option<ContainerCpuLimit>,
+    ///Memory the container may use, e.g. 512m or 2g
+    #[arg(long)]
+    memory: Option<ContainerMemoryLimit>,
+    ///Maximum number of processes in the container
+    #[arg(long)]
+    pids_limit: Option<ContainerPidsLimit>,
+    ///Network of the container: host, bridge or cluster
+    #[arg(long, default_value_t)]
+    network_mode: ContainerNetworkMode,
+    ///Capabilities to add to the container, e.g. NET_ADMIN
+    #[arg(long, num_args = 1..)]
+    cap_add: Option<Vec<ContainerCapability>>,
+    ///Capabilities to drop from the container, e.g. ALL
+    #[arg(long, num_args = 1..)]
+    cap_drop: Option<Vec<ContainerCapability>>,
+    ///Mount the root filesystem of the container read-only
+    #[arg(long)]
+    read_only_rootfs: bool,
 }
 
 impl CreateContainerExecutorCli {
@@ -58,6 +92,10 @@ impl CreateContainerExecutorCli {
         let devices = self.devices.unwrap_or_default();
         let ports = self.ports.unwrap_or_default();
         let args = self.args.unwrap_or_default();
+        let can_databases = self.can_databases.unwrap_or_default()
+            .into_iter()
+            .map(CanDatabaseId::from)
+            .collect();
 
         let mut environment_variables = vec![];
 
@@ -74,12 +112,23 @@ impl CreateContainerExecutorCli {
                 engine,
                 name: self.name.unwrap_or_default(),
                 image: self.image,
+                pull_policy: self.pull_policy,
+                digest: self.digest,
+                registry_credential: self.registry_credential,
                 volumes,
                 devices,
                 envs: environment_variables,
                 ports,
                 command: self.command.unwrap_or_default(),
                 args,
+                can_databases,
+                cpus: self.cpus,
+                memory: self.memory,
+                pids_limit: self.pids_limit,
+                network_mode: self.network_mode,
+                cap_add: self.cap_add.unwrap_or_default(),
+                cap_drop: self.cap_drop.unwrap_or_default(),
+                read_only_rootfs: self.read_only_rootfs,
             },
             results_url: self.results_url,
         };
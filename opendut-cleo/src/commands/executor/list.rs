@@ -1,3 +1,5 @@
+use std::collections::HashMap;
+
 use cli_table::{print_stdout, Table, WithTitle};
 use opendut_types::peer::executor::ExecutorDescriptor;
 use serde::Serialize;
@@ -5,7 +7,9 @@ use uuid::Uuid;
 
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::peer::{PeerDescriptor, PeerId};
-use opendut_types::peer::executor::{container::{ContainerImage, Engine}, ExecutorKind};
+use opendut_types::peer::configuration::{ParameterId, ParameterValue};
+use opendut_types::peer::executor::{container::{ContainerCapability, ContainerCpuLimit, ContainerImage, ContainerImagePullPolicy, ContainerMemoryLimit, ContainerNetworkMode, ContainerPidsLimit, Engine}, ExecutorKind};
+use opendut_types::peer::executor::run::ExecutorRun;
 
 use crate::{ListOutputFormat};
 
@@ -24,7 +28,11 @@ impl ListContainerExecutorCli {
             .peers.get_peer_descriptor(peer_id)
             .await
             .map_err(|error| format!("Could not find peer.\n  {}", error))?;
-        let executor_table = filter_connected_peers(&peer);
+        let runs = carl
+            .peers.list_executor_runs(Some(peer_id))
+            .await
+            .map_err(|error| format!("Could not list executor runs.\n  {}", error))?;
+        let executor_table = filter_connected_peers(&peer, latest_runs(runs));
 
         match output {
             ListOutputFormat::Table => {
@@ -44,47 +52,108 @@ impl ListContainerExecutorCli {
     }
 }
 
+/// Returns the most recent run of each executor.
+fn latest_runs(runs: Vec<ExecutorRun>) -> HashMap<ParameterId, ExecutorRun> {
+    let mut latest_runs = HashMap::<ParameterId, ExecutorRun>::new();
+    for run in runs {
+        match latest_runs.get(&run.executor_id) {
+            Some(latest_run) if latest_run.created_at >= run.created_at => {}
+            _ => {
+                latest_runs.insert(run.executor_id, run);
+            }
+        }
+    }
+    latest_runs
+}
+
 fn filter_connected_peers(
     peer: &PeerDescriptor,
+    latest_runs: HashMap<ParameterId, ExecutorRun>,
 ) -> Vec<ContainerExecutorTable> {
     let mut executor_table = vec![];
     for executor in &peer.executors.executors {
         let ExecutorDescriptor {kind, results_url} = executor;
+        let latest_run = latest_runs.get(&executor.parameter_identifier());
         
         if let ExecutorKind::Container {
             engine,
             name,
             image,
+            pull_policy,
+            digest,
+            registry_credential,
             volumes,
             devices,
             envs,
             ports,
             command,
             args,
+            can_databases,
+            cpus,
+            memory,
+            pids_limit,
+            network_mode,
+            cap_add,
+            cap_drop,
+            read_only_rootfs,
         } = kind {
             let args = args.iter().map(|arg| arg.to_string()).collect::<Vec<_>>();
             let volumes = volumes.iter().map(|volume| volume.to_string()).collect::<Vec<_>>();
             let devices = devices.iter().map(|device| device.to_string()).collect::<Vec<_>>();
             let ports = ports.iter().map(|port| port.to_string()).collect::<Vec<_>>();
+            let can_databases = can_databases.iter().map(|can_database| can_database.to_string()).collect::<Vec<_>>();
             let envs = envs.iter().map(|env|
                 format!("{}={}", env.name(), env.value())).collect::<Vec<_>>();
             executor_table.push(ContainerExecutorTable {
                 engine: Clone::clone(engine),
                 name: name.into(),
                 image: Clone::clone(image),
+                pull_policy: *pull_policy,
+                digest: digest.as_ref().map_or("None".to_string(), ToString::to_string),
+                registry_credential: registry_credential.as_ref().map_or("None".to_string(), ToString::to_string),
                 volumes: volumes.join(", "),
                 devices: devices.join(", "),
                 envs: envs.join(", "),
                 ports: ports.join(", "),
                 command: command.into(),
                 args: args.join(", "),
+                can_databases: can_databases.join(", "),
+                limits: format_limits(cpus, memory, pids_limit),
+                network_mode: *network_mode,
+                capabilities: format_capabilities(cap_add, cap_drop),
+                read_only_rootfs: *read_only_rootfs,
                 results_url: results_url.clone().map_or("None".to_string(), |results_url| results_url.into()),
+                run_state: latest_run.map_or("None".to_string(), |run| run.state.to_string()),
+                exit_code: latest_run.and_then(|run| run.exit_code).map_or("None".to_string(), |exit_code| exit_code.to_string()),
+                results_location: latest_run.and_then(|run| run.results_location.clone()).map_or("None".to_string(), String::from),
+                image_digest: latest_run.and_then(|run| run.image_digest.as_ref()).map_or("None".to_string(), ToString::to_string),
             });
         }
     };
     executor_table
 }
 
+fn format_limits(cpus: &Option<ContainerCpuLimit>, memory: &Option<ContainerMemoryLimit>, pids_limit: &Option<ContainerPidsLimit>) -> String {
+    let limits = [
+        cpus.map(|cpus| format!("cpus={cpus}")),
+        memory.map(|memory| format!("memory={memory}")),
+        pids_limit.map(|pids_limit| format!("pids={pids_limit}")),
+    ];
+    let limits = limits.into_iter().flatten().collect::<Vec<_>>();
+    if limits.is_empty() {
+        String::from("None")
+    } else {
+        limits.join(", ")
+    }
+}
+
+fn format_capabilities(cap_add: &[ContainerCapability], cap_drop: &[ContainerCapability]) -> String {
+    cap_add.iter().map(|capability| format!("+{capability}"))
+        .chain(cap_drop.iter().map(|capability| format!("-{capability}")))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
 #[derive(Table, Debug, Serialize)]
 struct ContainerExecutorTable {
     #[table(title = "Engine")]
@@ -93,6 +162,12 @@ struct ContainerExecutorTable {
     name: String,
     #[table(title = "Image")]
     image: ContainerImage,
+    #[table(title = "Pull Policy")]
+    pull_policy: ContainerImagePullPolicy,
+    #[table(title = "Digest")]
+    digest: String,
+    #[table(title = "Registry Credential")]
+    registry_credential: String,
     #[table(title = "Volume")]
     volumes: String,
     #[table(title = "Devices")]
@@ -105,6 +180,81 @@ struct ContainerExecutorTable {
     command: String,
     #[table(title = "Args")]
     args: String,
+    #[table(title = "CAN Databases")]
+    can_databases: String,
+    #[table(title = "Limits")]
+    limits: String,
+    #[table(title = "Network")]
+    network_mode: ContainerNetworkMode,
+    #[table(title = "Capabilities")]
+    capabilities: String,
+    #[table(title = "Read-only Rootfs")]
+    read_only_rootfs: bool,
     #[table(title = "Results URL")]
     results_url: String,
+    #[table(title = "Last Run")]
+    run_state: String,
+    #[table(title = "Exit Code")]
+    exit_code: String,
+    #[table(title = "Last Results")]
+    results_location: String,
+    #[table(title = "Last Image Digest")]
+    image_digest: String,
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use googletest::prelude::*;
+
+    use opendut_types::peer::executor::run::{ExecutorRunId, ExecutorRunState};
+
+    use super::*;
+
+    fn executor_run(executor_id: ParameterId, created_at_secs: u64, state: ExecutorRunState) -> ExecutorRun {
+        ExecutorRun {
+            id: ExecutorRunId::random(),
+            peer_id: PeerId::random(),
+            executor_id,
+            executor_name: String::from("nmap-test"),
+            state,
+            exit_code: None,
+            created_at: UNIX_EPOCH + Duration::from_secs(created_at_secs),
+            started_at: None,
+            finished_at: None,
+            results_location: None,
+            image_digest: None,
+        }
+    }
+
+    #[test]
+    fn should_format_resource_limits_and_capabilities() -> anyhow::Result<()> {
+        assert_that!(format_limits(&None, &None, &None), eq("None"));
+        assert_that!(
+            format_limits(&Some(ContainerCpuLimit::try_from("1.5")?), &None, &Some(ContainerPidsLimit::try_from(256)?)),
+            eq("cpus=1.5, pids=256")
+        );
+        assert_that!(
+            format_capabilities(&[ContainerCapability::try_from("NET_ADMIN")?], &[ContainerCapability::try_from("ALL")?]),
+            eq("+NET_ADMIN, -ALL")
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn should_determine_the_latest_run_of_each_executor() {
+        let executor_a = ParameterId(Uuid::new_v4());
+        let executor_b = ParameterId(Uuid::new_v4());
+
+        let latest_runs = latest_runs(vec![
+            executor_run(executor_a, 2, ExecutorRunState::Running),
+            executor_run(executor_a, 1, ExecutorRunState::Failed),
+            executor_run(executor_b, 1, ExecutorRunState::Succeeded),
+        ]);
+
+        assert_that!(latest_runs.len(), eq(2));
+        assert_that!(latest_runs.get(&executor_a).map(|run| run.state), some(eq(ExecutorRunState::Running)));
+        assert_that!(latest_runs.get(&executor_b).map(|run| run.state), some(eq(ExecutorRunState::Succeeded)));
+    }
 }
@@ -0,0 +1,48 @@
+use chrono::{DateTime, Local, SecondsFormat};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::executor::run::ExecutorLogLine;
+use opendut_types::peer::PeerId;
+
+/// Show the log of the latest run of an executor
+#[derive(clap::Parser)]
+pub struct ExecutorLogsCli {
+    ///ID of the peer
+    #[arg()]
+    peer_id: Uuid,
+    ///Name of the executor
+    #[arg()]
+    name: String,
+    ///Keep printing new lines until the run finishes
+    #[arg(short, long)]
+    follow: bool,
+}
+
+impl ExecutorLogsCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let peer_id = PeerId::from(self.peer_id);
+
+        let mut receiver = carl.broker.stream_executor_logs(peer_id, self.name.clone(), self.follow).await
+            .map_err(|error| format!("Could not retrieve log of executor '{}' on peer <{peer_id}>.\n  {error}", self.name))?;
+
+        loop {
+            let lines = receiver.next().await
+                .map_err(|error| format!("Error while streaming log of executor '{}'.\n  {error}", self.name))?;
+
+            let Some(lines) = lines else {
+                break;
+            };
+            for line in lines {
+                println!("{}", format_log_line(&line));
+            }
+        }
+
+        Ok(())
+    }
+}
+
+fn format_log_line(line: &ExecutorLogLine) -> String {
+    let timestamp = DateTime::<Local>::from(line.timestamp).to_rfc3339_opts(SecondsFormat::Millis, false);
+    format!("{timestamp} [{}] {}", line.stream, line.line)
+}
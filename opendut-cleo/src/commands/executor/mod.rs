@@ -1,4 +1,6 @@
 pub mod create;
 pub mod delete;
 pub mod list;
-pub mod apply;
\ No newline at end of file
+pub mod logs;
+pub mod apply;
+pub mod command;
\ No newline at end of file
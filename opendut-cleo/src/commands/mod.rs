@@ -1,9 +1,17 @@
+pub mod api_token;
+pub mod audit_event;
+pub mod can_database;
+pub mod can_fault_injection_rule;
 pub mod cluster_configuration;
 pub mod cluster_deployment;
+pub mod cleo;
 pub mod device;
 pub mod peer;
 pub mod network_interface;
 pub mod executor;
+pub mod result_artifact;
+pub mod secret;
+pub mod service_account;
 pub mod decode_setup_string;
 pub mod generate_setup_string;
 pub mod completions;
@@ -1,8 +1,10 @@
+use std::ops::Not;
+
 use uuid::Uuid;
 
 use opendut_carl_api::carl::CarlClient;
 use opendut_types::peer::PeerId;
-use opendut_types::util::net::{CanSamplePoint, NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceName};
+use opendut_types::util::net::{CanFrameFilter, CanIdFilter, CanRateLimit, CanSamplePoint, NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceName};
 
 use crate::{CreateOutputFormat, DescribeOutputFormat, NetworkInterfaceType};
 
@@ -18,6 +20,18 @@ pub struct CreateNetworkInterfaceCli {
     ///Name of the network interface
     #[arg(long("name"))]
     interface_name: String,
+    ///CAN IDs, which are routed into the cluster, in the format <id>:<mask> (hexadecimal, candump notation). Routes all CAN IDs, if not specified.
+    #[arg(long, num_args = 1..)]
+    can_accept: Vec<CanIdFilter>,
+    ///CAN IDs, which are not routed into the cluster, in the format <id>:<mask> (hexadecimal, candump notation)
+    #[arg(long, num_args = 1..)]
+    can_reject: Vec<CanIdFilter>,
+    ///Maximum number of CAN frames per second, which are routed into the cluster
+    #[arg(long)]
+    can_rate_limit: Option<u32>,
+    ///Number of CAN frames, which may exceed the rate limit for a short time
+    #[arg(long, default_value_t = 10, requires = "can_rate_limit")]
+    can_rate_limit_burst: u32,
 }
 impl CreateNetworkInterfaceCli {
     pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
@@ -31,15 +45,31 @@ impl CreateNetworkInterfaceCli {
 
         let interface_name = NetworkInterfaceName::try_from(self.interface_name).map_err(|error| error.to_string())?;
 
+        let can_filter = CanFrameFilter {
+            accept: self.can_accept,
+            reject: self.can_reject,
+        };
+        let can_rate_limit = self.can_rate_limit.map(|frames_per_second| CanRateLimit {
+            frames_per_second,
+            burst: self.can_rate_limit_burst,
+        });
+
         // TODO: Properly implement CAN parameter configuration
         let interface_configuration = match self.interface_type {
-            NetworkInterfaceType::Ethernet => NetworkInterfaceConfiguration::Ethernet,
+            NetworkInterfaceType::Ethernet => {
+                if can_filter.is_empty().not() || can_rate_limit.is_some() {
+                    Err(String::from("CAN filters and rate limits can only be specified for CAN interfaces."))?
+                }
+                NetworkInterfaceConfiguration::Ethernet
+            },
             NetworkInterfaceType::Can => NetworkInterfaceConfiguration::Can {
                 bitrate: 500000,
                 sample_point: CanSamplePoint::try_from(0.7).unwrap(),
                 fd: true,
                 data_bitrate: 2000000,
                 data_sample_point: CanSamplePoint::try_from(0.7).unwrap(),
+                filter: can_filter,
+                rate_limit: can_rate_limit,
             },
         };
 
@@ -2,6 +2,6 @@ pub mod list;
 pub mod describe;
 pub mod create;
 pub mod delete;
-
-
-
+pub mod transfer;
+pub mod rotate_secret;
+pub mod revoke;
@@ -0,0 +1,31 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_carl_api::carl::peer::CredentialsHolder;
+use opendut_types::peer::PeerId;
+
+/// Revoke the client credentials of a peer, which then has to be set up again
+#[derive(clap::Parser)]
+pub struct RevokePeerCli {
+    ///PeerID
+    #[arg()]
+    id: Uuid,
+}
+
+impl RevokePeerCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = PeerId::from(self.id);
+        let client_ids = carl.peers
+            .revoke_client(CredentialsHolder::Peer(id))
+            .await
+            .map_err(|error| format!("Failed to revoke client credentials of peer with the id '{}'.\n  {}", id, error))?;
+        if client_ids.is_empty() {
+            println!("Revoked the client certificate of peer with the PeerID {}.", id);
+        } else {
+            let client_ids = client_ids.into_iter().map(|client_id| client_id.value()).collect::<Vec<_>>();
+            println!("Revoked client credentials of peer with the PeerID {}: {}", id, client_ids.join(", "));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,26 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_carl_api::carl::peer::CredentialsHolder;
+use opendut_types::peer::PeerId;
+
+/// Rotate the client secret of a connected peer, which stores the new secret and restarts
+#[derive(clap::Parser)]
+pub struct RotateSecretPeerCli {
+    ///PeerID
+    #[arg()]
+    id: Uuid,
+}
+
+impl RotateSecretPeerCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = PeerId::from(self.id);
+        carl.peers
+            .rotate_client_secret(CredentialsHolder::Peer(id))
+            .await
+            .map_err(|error| format!("Failed to rotate client secret of peer with the id '{}'.\n  {}", id, error))?;
+        println!("Rotated client secret of peer with the PeerID {}. The peer reconnects with its new secret.", id);
+
+        Ok(())
+    }
+}
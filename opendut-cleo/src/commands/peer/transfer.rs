@@ -0,0 +1,31 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::PeerId;
+
+/// Transfer the ownership of a peer to another user and group
+#[derive(clap::Parser)]
+pub struct TransferPeerCli {
+    ///PeerID
+    #[arg()]
+    id: Uuid,
+    ///Name of the user, who becomes the owner
+    #[arg(long)]
+    owner: String,
+    ///Group, whose members may also access the peer
+    #[arg(long)]
+    group: Option<String>,
+}
+
+impl TransferPeerCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = PeerId::from(self.id);
+        carl.peers
+            .transfer_peer_ownership(id, Clone::clone(&self.owner), self.group)
+            .await
+            .map_err(|error| format!("Failed to transfer ownership of peer with the id '{}'.\n  {}", id, error))?;
+        println!("Transferred peer with the PeerID {} to '{}'.", id, self.owner);
+
+        Ok(())
+    }
+}
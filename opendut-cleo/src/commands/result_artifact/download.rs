@@ -0,0 +1,128 @@
+use std::ops::Not;
+use std::path::PathBuf;
+
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+use opendut_auth::confidential::client::ConfidentialClient;
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::peer::executor::run::{ResultArtifact, ResultArtifactId};
+
+/// Download a result artifact uploaded by an executor
+#[derive(clap::Parser)]
+pub struct DownloadResultArtifactCli {
+    ///ID of the result artifact
+    #[arg()]
+    id: Uuid,
+    ///File to write the artifact to. Defaults to the file name of the artifact in the current directory.
+    #[arg(short, long)]
+    output: Option<PathBuf>,
+}
+
+impl DownloadResultArtifactCli {
+    pub async fn execute(self, carl: &mut CarlClient, config: &config::Config) -> crate::Result<()> {
+        let artifact_id = ResultArtifactId::from(self.id);
+
+        let artifact = carl.peers.list_result_artifacts(None, None, None).await
+            .map_err(|error| format!("Could not list result artifacts.\n  {error}"))?
+            .into_iter()
+            .find(|artifact| artifact.id == artifact_id)
+            .ok_or(format!("Result artifact <{artifact_id}> could not be found."))?;
+
+        let location = &artifact.location;
+        if ["http", "https"].contains(&location.scheme()).not() {
+            return Err(format!("Result artifact <{artifact_id}> is stored at '{location}'. Only artifacts stored at 'http://' or 'https://' locations can be downloaded with CLEO."));
+        }
+
+        let mut request = reqwest::Client::new().get(Clone::clone(location));
+
+        let oidc_client = ConfidentialClient::from_settings(config).await
+            .map_err(|error| format!("Failed to create OIDC client for downloading result artifact.\n  {error}"))?;
+        if let Some(oidc_client) = oidc_client {
+            let token = oidc_client.get_token().await
+                .map_err(|error| format!("Failed to obtain token for downloading result artifact.\n  {error}"))?;
+            request = request.bearer_auth(token.value);
+        }
+
+        let response = request.send().await
+            .and_then(|response| response.error_for_status())
+            .map_err(|error| format!("Could not download result artifact <{artifact_id}> from '{location}'.\n  {error}"))?;
+        let content = response.bytes().await
+            .map_err(|error| format!("Could not download result artifact <{artifact_id}> from '{location}'.\n  {error}"))?;
+
+        verify_checksum(&artifact, &content)?;
+
+        let output = match self.output {
+            Some(output) => output,
+            None => PathBuf::from(file_name(&artifact)),
+        };
+        std::fs::write(&output, &content)
+            .map_err(|error| format!("Could not write result artifact <{artifact_id}> to '{}'.\n  {error}", output.display()))?;
+
+        println!("Downloaded result artifact <{artifact_id}> to '{}'.", output.display());
+        Ok(())
+    }
+}
+
+fn verify_checksum(artifact: &ResultArtifact, content: &[u8]) -> crate::Result<()> {
+    let actual = hex::encode(Sha256::digest(content));
+    if artifact.checksum.is_empty() || artifact.checksum.eq_ignore_ascii_case(&actual) {
+        Ok(())
+    } else {
+        Err(format!("Downloaded content of result artifact <{}> has the SHA-256 checksum '{actual}', but '{}' was expected.", artifact.id, artifact.checksum))
+    }
+}
+
+fn file_name(artifact: &ResultArtifact) -> String {
+    artifact.location.path_segments()
+        .and_then(|mut segments| segments.next_back())
+        .filter(|file_name| file_name.is_empty().not())
+        .map(String::from)
+        .unwrap_or_else(|| format!("{}.zip", artifact.id))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::UNIX_EPOCH;
+
+    use googletest::prelude::*;
+    use url::Url;
+
+    use opendut_types::peer::configuration::ParameterId;
+    use opendut_types::peer::executor::run::ExecutorRunId;
+    use opendut_types::peer::PeerId;
+
+    use super::*;
+
+    fn artifact(location: &str, checksum: &str) -> ResultArtifact {
+        ResultArtifact {
+            id: ResultArtifactId::random(),
+            peer_id: PeerId::random(),
+            cluster_id: None,
+            run_id: ExecutorRunId::random(),
+            executor_id: ParameterId(Uuid::new_v4()),
+            executor_name: String::from("nmap-test"),
+            uploaded_at: UNIX_EPOCH,
+            size: 0,
+            location: Url::parse(location).unwrap(),
+            checksum: String::from(checksum),
+        }
+    }
+
+    #[test]
+    fn should_verify_the_checksum_of_downloaded_artifacts() {
+        let empty_checksum = "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+
+        assert!(verify_checksum(&artifact("http://nginx-webdav/results.zip", empty_checksum), b"").is_ok());
+        assert!(verify_checksum(&artifact("http://nginx-webdav/results.zip", empty_checksum), b"modified").is_err());
+        assert!(verify_checksum(&artifact("http://nginx-webdav/results.zip", ""), b"modified").is_ok());
+    }
+
+    #[test]
+    fn should_name_downloaded_files_after_the_artifact_location() {
+        assert_that!(file_name(&artifact("http://nginx-webdav/2024-01-01_12-00-00_nmap-test.zip", "")), eq("2024-01-01_12-00-00_nmap-test.zip"));
+
+        let artifact = artifact("https://example.com/upload/", "");
+        assert_that!(file_name(&artifact), eq(format!("{}.zip", artifact.id)));
+    }
+}
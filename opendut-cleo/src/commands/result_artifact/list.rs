@@ -0,0 +1,84 @@
+use chrono::{DateTime, Local, SecondsFormat};
+use cli_table::{print_stdout, Table, WithTitle};
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::cluster::ClusterId;
+use opendut_types::peer::executor::run::{ExecutorRunId, ResultArtifact, ResultArtifactId};
+use opendut_types::peer::PeerId;
+
+use crate::ListOutputFormat;
+
+/// List the result artifacts uploaded by executors
+#[derive(clap::Parser)]
+pub struct ListResultArtifactsCli {
+    ///Only list artifacts of the peer with this ID
+    #[arg(long)]
+    peer_id: Option<Uuid>,
+    ///Only list artifacts uploaded while the peer was assigned to the cluster with this ID
+    #[arg(long)]
+    cluster_id: Option<Uuid>,
+    ///Only list artifacts of the executor run with this ID
+    #[arg(long)]
+    run_id: Option<Uuid>,
+}
+
+#[derive(Table)]
+struct ResultArtifactTable {
+    #[table(title = "ResultArtifactID")]
+    id: ResultArtifactId,
+    #[table(title = "Executor")]
+    executor_name: String,
+    #[table(title = "PeerID")]
+    peer_id: PeerId,
+    #[table(title = "RunID")]
+    run_id: ExecutorRunId,
+    #[table(title = "Uploaded")]
+    uploaded_at: String,
+    #[table(title = "Size")]
+    size: u64,
+    #[table(title = "Location")]
+    location: String,
+}
+
+impl ListResultArtifactsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let artifacts = carl.peers.list_result_artifacts(
+            self.peer_id.map(PeerId::from),
+            self.cluster_id.map(ClusterId::from),
+            self.run_id.map(ExecutorRunId::from),
+        ).await
+            .map_err(|error| format!("Could not list result artifacts.\n  {error}"))?;
+
+        match output {
+            ListOutputFormat::Table => {
+                let result_artifact_table = artifacts.into_iter()
+                    .map(result_artifact_table_row)
+                    .collect::<Vec<_>>();
+                print_stdout(result_artifact_table.with_title())
+                    .expect("List of result artifacts should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&artifacts).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&artifacts).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
+
+fn result_artifact_table_row(artifact: ResultArtifact) -> ResultArtifactTable {
+    ResultArtifactTable {
+        id: artifact.id,
+        executor_name: artifact.executor_name,
+        peer_id: artifact.peer_id,
+        run_id: artifact.run_id,
+        uploaded_at: DateTime::<Local>::from(artifact.uploaded_at).to_rfc3339_opts(SecondsFormat::Secs, false),
+        size: artifact.size,
+        location: artifact.location.to_string(),
+    }
+}
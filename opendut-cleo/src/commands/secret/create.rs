@@ -0,0 +1,63 @@
+use std::io::Read;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::secret::{Secret, SecretName};
+
+use crate::CreateOutputFormat;
+
+/// Store a secret in CARL, which executors can reference via `${secret:NAME}`
+#[derive(clap::Parser)]
+pub struct CreateSecretCli {
+    ///Name of the secret
+    #[arg(short, long)]
+    name: SecretName,
+    ///Value of the secret, read from stdin if not specified
+    #[arg(long)]
+    value: Option<String>,
+    ///Name of the user, whose peers may reference the secret (defaults to yourself)
+    #[arg(long)]
+    owner: Option<String>,
+    ///Group, whose members' peers may also reference the secret
+    #[arg(long, requires = "owner")]
+    group: Option<String>,
+}
+
+impl CreateSecretCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+
+        let value = match self.value {
+            Some(value) => value,
+            None => {
+                let mut value = String::new();
+                std::io::stdin().read_to_string(&mut value)
+                    .map_err(|cause| format!("Failed to read value of secret '{}' from stdin.\n  {cause}", self.name))?;
+                value.trim_end_matches(['\r', '\n']).to_owned()
+            }
+        };
+
+        let secret = Secret {
+            name: self.name,
+            value,
+        };
+
+        let secret_name = carl.secrets.store_secret(secret, self.owner, self.group).await
+            .map_err(|error| format!("Could not store secret.\n  {error}"))?;
+
+        match output {
+            CreateOutputFormat::Text => {
+                println!("Successfully stored secret '{secret_name}'.");
+                println!("Reference it via: {}", secret_name.reference());
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&secret_name).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&secret_name).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
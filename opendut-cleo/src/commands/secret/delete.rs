@@ -0,0 +1,23 @@
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::secret::SecretName;
+
+/// Delete a secret, which is not referenced by any peer
+#[derive(clap::Parser)]
+pub struct DeleteSecretCli {
+    ///Name of the secret
+    #[arg()]
+    name: SecretName,
+}
+
+impl DeleteSecretCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let name = self.name;
+
+        let secret_name = carl.secrets.delete_secret(Clone::clone(&name)).await
+            .map_err(|error| format!("Failed to delete secret '{name}'.\n  {error}"))?;
+
+        println!("Deleted secret '{secret_name}' successfully.");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,49 @@
+use cli_table::{print_stdout, Table, WithTitle};
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::secret::SecretName;
+
+use crate::ListOutputFormat;
+
+/// List the names of all secrets
+#[derive(clap::Parser)]
+pub struct ListSecretsCli;
+
+#[derive(Table)]
+struct SecretTable {
+    #[table(title = "Name")]
+    name: SecretName,
+    #[table(title = "Reference")]
+    reference: String,
+}
+
+impl ListSecretsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let secret_names = carl.secrets.list_secrets().await
+            .map_err(|error| format!("Could not list any secrets.\n  {error}"))?;
+
+        match output {
+            ListOutputFormat::Table => {
+                let secret_table = secret_names.into_iter()
+                    .map(|name| {
+                        SecretTable {
+                            reference: name.reference(),
+                            name,
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                print_stdout(secret_table.with_title())
+                    .expect("List of secrets should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&secret_names).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&secret_names).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,48 @@
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::service_account::{ServiceAccountName, ServiceAccountRole};
+
+use crate::{CreateOutputFormat, ServiceAccountRoleVariants};
+
+/// Create a service account, e.g. for a CI pipeline, which authenticates with API tokens
+#[derive(clap::Parser)]
+pub struct CreateServiceAccountCli {
+    ///Name of the service account
+    #[arg(short, long)]
+    name: ServiceAccountName,
+    ///Role of the service account, which determines its permissions
+    #[arg(short, long)]
+    role: ServiceAccountRoleVariants,
+    ///Group, e.g. team, of the service account. Resources created by it belong to the group, and it may access the resources of the group
+    #[arg(short, long)]
+    group: Option<String>,
+}
+
+impl CreateServiceAccountCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: CreateOutputFormat) -> crate::Result<()> {
+
+        let role = match self.role {
+            ServiceAccountRoleVariants::Viewer => ServiceAccountRole::Viewer,
+            ServiceAccountRoleVariants::Operator => ServiceAccountRole::Operator,
+            ServiceAccountRoleVariants::Admin => ServiceAccountRole::Admin,
+        };
+
+        let descriptor = carl.service_accounts.create_service_account(self.name, role, self.group).await
+            .map_err(|error| format!("Could not create service account.\n  {error}"))?;
+
+        match output {
+            CreateOutputFormat::Text => {
+                println!("Successfully created service account '{}' with role '{}' <{}>.", descriptor.name, descriptor.role, descriptor.id);
+            }
+            CreateOutputFormat::Json => {
+                let json = serde_json::to_string(&descriptor).unwrap();
+                println!("{}", json);
+            }
+            CreateOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&descriptor).unwrap();
+                println!("{}", json);
+            }
+        }
+
+        Ok(())
+    }
+}
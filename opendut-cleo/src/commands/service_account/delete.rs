@@ -0,0 +1,25 @@
+use uuid::Uuid;
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::service_account::ServiceAccountId;
+
+/// Delete a service account and revoke all of its API tokens
+#[derive(clap::Parser)]
+pub struct DeleteServiceAccountCli {
+    ///ID of the service account
+    #[arg()]
+    id: Uuid,
+}
+
+impl DeleteServiceAccountCli {
+    pub async fn execute(self, carl: &mut CarlClient) -> crate::Result<()> {
+        let id = ServiceAccountId::from(self.id);
+
+        let descriptor = carl.service_accounts.delete_service_account(id).await
+            .map_err(|error| format!("Failed to delete service account with id <{id}>.\n  {error}"))?;
+
+        println!("Deleted service account '{}' <{}> and revoked its API tokens successfully.", descriptor.name, descriptor.id);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,55 @@
+use cli_table::{print_stdout, Table, WithTitle};
+
+use opendut_carl_api::carl::CarlClient;
+use opendut_types::service_account::{ServiceAccountId, ServiceAccountName, ServiceAccountRole};
+
+use crate::ListOutputFormat;
+
+/// List all service accounts
+#[derive(clap::Parser)]
+pub struct ListServiceAccountsCli;
+
+#[derive(Table)]
+struct ServiceAccountTable {
+    #[table(title = "Name")]
+    name: ServiceAccountName,
+    #[table(title = "ServiceAccountID")]
+    id: ServiceAccountId,
+    #[table(title = "Role")]
+    role: ServiceAccountRole,
+    #[table(title = "Group")]
+    group: String,
+}
+
+impl ListServiceAccountsCli {
+    pub async fn execute(self, carl: &mut CarlClient, output: ListOutputFormat) -> crate::Result<()> {
+        let service_accounts = carl.service_accounts.list_service_accounts().await
+            .map_err(|error| format!("Could not list any service accounts.\n  {error}"))?;
+
+        match output {
+            ListOutputFormat::Table => {
+                let service_account_table = service_accounts.into_iter()
+                    .map(|service_account| {
+                        ServiceAccountTable {
+                            name: service_account.name,
+                            id: service_account.id,
+                            role: service_account.role,
+                            group: service_account.group.unwrap_or_default(),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                print_stdout(service_account_table.with_title())
+                    .expect("List of service accounts should be printable as table.");
+            }
+            ListOutputFormat::Json => {
+                let json = serde_json::to_string(&service_accounts).unwrap();
+                println!("{}", json);
+            }
+            ListOutputFormat::PrettyJson => {
+                let json = serde_json::to_string_pretty(&service_accounts).unwrap();
+                println!("{}", json);
+            }
+        }
+        Ok(())
+    }
+}
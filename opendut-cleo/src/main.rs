@@ -7,6 +7,7 @@ use clap_complete::Shell;
 use console::Style;
 
 use opendut_carl_api::carl::{CaCertInfo, CarlClient};
+use opendut_types::peer::executor::ExecutorCommand;
 use opendut_types::topology::DeviceName;
 use opendut_util::settings::{FileFormat, load_config, LoadedConfig};
 
@@ -81,6 +82,71 @@ enum Commands {
         #[command(subcommand)]
         resource: DeleteResource,
     },
+    ///Enable openDuT resource
+    Enable {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: EnableResource,
+    },
+    ///Disable openDuT resource
+    Disable {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: EnableResource,
+    },
+    ///Start openDuT resource on its peer
+    Start {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: ExecutorCommandResource,
+    },
+    ///Stop openDuT resource on its peer
+    Stop {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: ExecutorCommandResource,
+    },
+    ///Restart openDuT resource on its peer
+    Restart {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: ExecutorCommandResource,
+    },
+    ///Run openDuT resource once as a job, which is not stopped by redeploying its peer
+    Run {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: ExecutorCommandResource,
+    },
+    ///Download openDuT resource
+    Download {
+        #[command(subcommand)]
+        resource: DownloadResource,
+    },
+    ///Show logs of openDuT resource
+    Logs {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: LogsResource,
+    },
+    ///Transfer the ownership of openDuT resource (requires the admin role)
+    Transfer {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: TransferResource,
+    },
+    ///Rotate the client secret of openDuT resource (requires the admin role)
+    RotateSecret {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: RotateSecretResource,
+    },
+    ///Revoke the client credentials of openDuT resource (requires the admin role)
+    Revoke {
+        ///Name of openDuT resource
+        #[command(subcommand)]
+        resource: RevokeResource,
+    },
     Config,
     /// Generates shell completion
     Completions {
@@ -92,11 +158,18 @@ enum Commands {
 
 #[derive(Subcommand)]
 enum ListResource {
+    ApiTokens(commands::api_token::list::ListApiTokensCli),
+    AuditEvents(commands::audit_event::list::ListAuditEventsCli),
+    CanDatabases(commands::can_database::list::ListCanDatabasesCli),
+    CanFaultInjectionRules(commands::can_fault_injection_rule::list::ListCanFaultInjectionRulesCli),
     ClusterConfigurations(commands::cluster_configuration::list::ListClusterConfigurationsCli),
     ClusterDeployments(commands::cluster_deployment::list::ListClusterDeploymentsCli),
     Peers(commands::peer::list::ListPeersCli),
     Devices(commands::device::list::ListDevicesCli),
     ContainerExecutor(commands::executor::list::ListContainerExecutorCli),
+    ResultArtifacts(commands::result_artifact::list::ListResultArtifactsCli),
+    Secrets(commands::secret::list::ListSecretsCli),
+    ServiceAccounts(commands::service_account::list::ListServiceAccountsCli),
 }
 
 #[derive(clap::Args)]
@@ -114,6 +187,19 @@ pub enum EngineVariants {
     Podman,
 }
 
+#[derive(ValueEnum, Clone)]
+pub enum CanDatabaseFormatVariants {
+    Arxml,
+    Dbc,
+}
+
+#[derive(ValueEnum, Clone)]
+pub enum ServiceAccountRoleVariants {
+    Viewer,
+    Operator,
+    Admin,
+}
+
 #[derive(ValueEnum, Clone)]
 pub enum NetworkInterfaceType {
     Ethernet,
@@ -127,12 +213,17 @@ enum ApplyResource {
 
 #[derive(Subcommand)]
 enum CreateResource {
+    ApiToken(commands::api_token::create::CreateApiTokenCli),
+    CanDatabase(commands::can_database::create::CreateCanDatabaseCli),
+    CanFaultInjectionRule(commands::can_fault_injection_rule::create::CreateCanFaultInjectionRuleCli),
     ClusterConfiguration(commands::cluster_configuration::create::CreateClusterConfigurationCli),
     ClusterDeployment(commands::cluster_deployment::create::CreateClusterDeploymentCli),
     Peer(commands::peer::create::CreatePeerCli),
     ContainerExecutor(commands::executor::create::CreateContainerExecutorCli),
     NetworkInterface(commands::network_interface::create::CreateNetworkInterfaceCli),
-    Device(commands::device::create::CreateDeviceCli)
+    Device(commands::device::create::CreateDeviceCli),
+    Secret(commands::secret::create::CreateSecretCli),
+    ServiceAccount(commands::service_account::create::CreateServiceAccountCli),
 }
 
 #[derive(Subcommand)]
@@ -149,12 +240,55 @@ enum FindResource {
 
 #[derive(Subcommand)]
 enum DeleteResource {
+    CanDatabase(commands::can_database::delete::DeleteCanDatabaseCli),
+    CanFaultInjectionRule(commands::can_fault_injection_rule::delete::DeleteCanFaultInjectionRuleCli),
     ClusterConfiguration(commands::cluster_configuration::delete::DeleteClusterConfigurationCli),
     ClusterDeployment(commands::cluster_deployment::delete::DeleteClusterDeploymentCli),
     Peer(commands::peer::delete::DeletePeerCli),
     ContainerExecutor(commands::executor::delete::DeleteContainerExecutorCli),
     NetworkInterface(commands::network_interface::delete::DeleteNetworkInterfaceCli),
     Device(commands::device::delete::DeleteDeviceCli),
+    Secret(commands::secret::delete::DeleteSecretCli),
+    ServiceAccount(commands::service_account::delete::DeleteServiceAccountCli),
+}
+
+#[derive(Subcommand)]
+enum DownloadResource {
+    ResultArtifact(commands::result_artifact::download::DownloadResultArtifactCli),
+}
+
+#[derive(Subcommand)]
+enum LogsResource {
+    Executor(commands::executor::logs::ExecutorLogsCli),
+}
+
+#[derive(Subcommand)]
+enum TransferResource {
+    ClusterConfiguration(commands::cluster_configuration::transfer::TransferClusterConfigurationCli),
+    Peer(commands::peer::transfer::TransferPeerCli),
+}
+
+#[derive(Subcommand)]
+enum RotateSecretResource {
+    Cleo(commands::cleo::rotate_secret::RotateSecretCleoCli),
+    Peer(commands::peer::rotate_secret::RotateSecretPeerCli),
+}
+
+#[derive(Subcommand)]
+enum RevokeResource {
+    ApiToken(commands::api_token::revoke::RevokeApiTokenCli),
+    Cleo(commands::cleo::revoke::RevokeCleoCli),
+    Peer(commands::peer::revoke::RevokePeerCli),
+}
+
+#[derive(Subcommand)]
+enum ExecutorCommandResource {
+    Executor(commands::executor::command::ExecutorCommandCli),
+}
+
+#[derive(Subcommand)]
+enum EnableResource {
+    CanFaultInjectionRule(commands::can_fault_injection_rule::enable::EnableCanFaultInjectionRuleCli),
 }
 
 #[derive(ValueEnum, Clone)]
@@ -232,6 +366,18 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
         Commands::List { resource, output } => {
             let mut carl = create_carl_client(&settings.config).await;
             match resource {
+                ListResource::ApiTokens(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                ListResource::AuditEvents(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                ListResource::CanDatabases(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                ListResource::CanFaultInjectionRules(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
                 ListResource::ClusterConfigurations(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
@@ -247,6 +393,15 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 ListResource::Devices(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
+                ListResource::ResultArtifacts(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                ListResource::Secrets(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                ListResource::ServiceAccounts(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
             }
         }
         Commands::Apply { resource, output } => {
@@ -260,6 +415,15 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
         Commands::Create { resource, output } => {
             let mut carl = create_carl_client(&settings.config).await;
             match *resource {
+                CreateResource::ApiToken(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                CreateResource::CanDatabase(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                CreateResource::CanFaultInjectionRule(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
                 CreateResource::ClusterConfiguration(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
@@ -278,6 +442,12 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 CreateResource::Device(implementation) => {
                     implementation.execute(&mut carl, output).await?;
                 }
+                CreateResource::Secret(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
+                CreateResource::ServiceAccount(implementation) => {
+                    implementation.execute(&mut carl, output).await?;
+                }
             }
         }
         Commands::GenerateSetupString(implementation) => {
@@ -305,6 +475,12 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
         Commands::Delete { resource} => {
             let mut carl = create_carl_client(&settings.config).await;
             match resource {
+                DeleteResource::CanDatabase(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+                DeleteResource::CanFaultInjectionRule(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
                 DeleteResource::ClusterConfiguration(implementation) => {
                     implementation.execute(&mut carl).await?;
                 }
@@ -323,6 +499,92 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
                 DeleteResource::Device(implementation) => {
                     implementation.execute(&mut carl).await?;
                 }
+                DeleteResource::Secret(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+                DeleteResource::ServiceAccount(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+            }
+        }
+        Commands::Enable { resource } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                EnableResource::CanFaultInjectionRule(implementation) => {
+                    implementation.execute(&mut carl, true).await?;
+                }
+            }
+        }
+        Commands::Disable { resource } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                EnableResource::CanFaultInjectionRule(implementation) => {
+                    implementation.execute(&mut carl, false).await?;
+                }
+            }
+        }
+        Commands::Start { resource } => {
+            execute_executor_command(resource, ExecutorCommand::Start, settings).await?;
+        }
+        Commands::Stop { resource } => {
+            execute_executor_command(resource, ExecutorCommand::Stop, settings).await?;
+        }
+        Commands::Restart { resource } => {
+            execute_executor_command(resource, ExecutorCommand::Restart, settings).await?;
+        }
+        Commands::Run { resource } => {
+            execute_executor_command(resource, ExecutorCommand::RunJob, settings).await?;
+        }
+        Commands::Download { resource } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                DownloadResource::ResultArtifact(implementation) => {
+                    implementation.execute(&mut carl, &settings.config).await?;
+                }
+            }
+        }
+        Commands::Logs { resource } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                LogsResource::Executor(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+            }
+        }
+        Commands::Transfer { resource } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                TransferResource::ClusterConfiguration(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+                TransferResource::Peer(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+            }
+        }
+        Commands::RotateSecret { resource } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                RotateSecretResource::Cleo(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+                RotateSecretResource::Peer(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+            }
+        }
+        Commands::Revoke { resource } => {
+            let mut carl = create_carl_client(&settings.config).await;
+            match resource {
+                RevokeResource::ApiToken(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+                RevokeResource::Cleo(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
+                RevokeResource::Peer(implementation) => {
+                    implementation.execute(&mut carl).await?;
+                }
             }
         }
         Commands::Find { resource, output } => {
@@ -344,6 +606,16 @@ async fn execute_command(commands: Commands, settings: &LoadedConfig) -> Result<
     Ok(())
 }
 
+async fn execute_executor_command(resource: ExecutorCommandResource, command: ExecutorCommand, settings: &LoadedConfig) -> Result<()> {
+    let mut carl = create_carl_client(&settings.config).await;
+    match resource {
+        ExecutorCommandResource::Executor(implementation) => {
+            implementation.execute(&mut carl, command).await?;
+        }
+    }
+    Ok(())
+}
+
 pub async fn create_carl_client(config: &config::Config) -> CarlClient {
     let host = config.get_string("network.carl.host")
         .expect("Configuration should contain a valid host name to connect to CARL");
@@ -4,8 +4,8 @@ use std::collections::HashMap;
 
 use autosar_data::{AutosarModel, CharacterData, Element, ElementName, EnumItem};
 
-use crate::arxml_structs::*;
-use crate::arxml_utils::*;
+use super::arxml_structs::*;
+use super::arxml_utils::*;
 
 /*
 - Arxml parser that is able to extract all values necessary for a restbus simulation
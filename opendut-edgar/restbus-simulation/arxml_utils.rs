@@ -5,7 +5,7 @@ use autosar_data::{CharacterData, Element, ElementName, EnumItem};
 
 use std::collections::HashMap;
 
-use crate::arxml_structs::*;
+use super::arxml_structs::*;
 
 pub fn decode_integer(cdata: &CharacterData) -> Option<i64> {
     if let CharacterData::String(text) = cdata {
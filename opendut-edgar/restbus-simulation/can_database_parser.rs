@@ -0,0 +1,95 @@
+use std::collections::HashMap;
+use std::path::Path;
+
+#[cfg(feature = "arxml")]
+use super::arxml_parser::ArxmlParser;
+use super::arxml_structs::CanCluster;
+use super::dbc_parser::DbcParser;
+
+/*
+- Entry point for loading CAN communication matrices independent of their source format.
+- The format is determined by the file extension, so that the restbus simulation and the signal codec
+  only work on the CanCluster structures.
+- ARXML files can only be loaded, if EDGAR is built with the "arxml" feature.
+*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CanDatabaseFormat {
+    Arxml,
+    Dbc,
+}
+
+impl CanDatabaseFormat {
+    pub fn from_file_name(file_name: &str) -> Option<CanDatabaseFormat> {
+        let extension = Path::new(file_name)
+            .extension()?
+            .to_string_lossy()
+            .to_lowercase();
+
+        match extension.as_str() {
+            "arxml" | "xml" => Some(CanDatabaseFormat::Arxml),
+            "dbc" => Some(CanDatabaseFormat::Dbc),
+            _ => None,
+        }
+    }
+}
+
+pub fn parse_can_database_file(file_name: String) -> Option<HashMap<String, CanCluster>> {
+    match CanDatabaseFormat::from_file_name(&file_name) {
+        #[cfg(feature = "arxml")]
+        Some(CanDatabaseFormat::Arxml) => ArxmlParser {}.parse_file(file_name),
+        #[cfg(not(feature = "arxml"))]
+        Some(CanDatabaseFormat::Arxml) => {
+            println!("[-] WARNING: Cannot load CAN database {}, since ARXML support is not included. Build with the \"arxml\" feature.", file_name);
+            None
+        }
+        Some(CanDatabaseFormat::Dbc) => DbcParser {}.parse_file(file_name),
+        None => {
+            println!("[-] WARNING: Cannot determine format of CAN database {}. Expected an .arxml or .dbc file.", file_name);
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_data(file_name: &str) -> String {
+        format!("{}/restbus-simulation/test-data/{}", env!("CARGO_MANIFEST_DIR"), file_name)
+    }
+
+    #[test]
+    fn should_determine_format_from_file_extension() {
+        assert_eq!(CanDatabaseFormat::from_file_name("Powertrain.DBC"), Some(CanDatabaseFormat::Dbc));
+        assert_eq!(CanDatabaseFormat::from_file_name("Chassis.arxml"), Some(CanDatabaseFormat::Arxml));
+        assert_eq!(CanDatabaseFormat::from_file_name("Chassis.xml"), Some(CanDatabaseFormat::Arxml));
+        assert_eq!(CanDatabaseFormat::from_file_name("Powertrain.txt"), None);
+        assert_eq!(CanDatabaseFormat::from_file_name("Powertrain"), None);
+    }
+
+    #[test]
+    fn should_load_dbc_file() {
+        let can_clusters = parse_can_database_file(test_data("Powertrain.dbc")).unwrap();
+
+        let cluster = can_clusters.get("Powertrain").unwrap();
+        assert_eq!(cluster.baudrate, 500_000);
+        assert!(cluster.can_frame_triggerings.contains_key(&256));
+    }
+
+    #[cfg(feature = "arxml")]
+    #[test]
+    fn should_load_arxml_file() {
+        let can_clusters = parse_can_database_file(test_data("Chassis.arxml")).unwrap();
+
+        let cluster = can_clusters.get("Chassis").unwrap();
+        assert_eq!(cluster.baudrate, 500_000);
+        assert!(cluster.can_frame_triggerings.is_empty());
+    }
+
+    #[cfg(not(feature = "arxml"))]
+    #[test]
+    fn should_not_load_arxml_file_without_arxml_support() {
+        assert!(parse_can_database_file(test_data("Chassis.arxml")).is_none());
+    }
+}
@@ -0,0 +1,394 @@
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+use std::time::Instant;
+
+use super::arxml_structs::*;
+use super::dbc_utils::*;
+
+/*
+- DBC parser that extracts the same values as the ArxmlParser, so that a restbus simulation can be set up
+  regardless of whether a supplier delivers an ARXML or a DBC communication matrix.
+- Each DBC file describes exactly one CAN network, which is returned as a single CanCluster.
+- Vector attribute conventions are used for timings and initial values:
+    - GenMsgCycleTime, GenMsgStartDelayTime, GenMsgNrOfRepetition, GenMsgCycleTimeFast (milliseconds)
+    - GenSigStartValue (raw value)
+    - VFrameFormat, NmMessage/NmAsrMessage, Baudrate/BaudrateCANFD, DBName
+*/
+
+// Used when the DBC file does not specify a baudrate, since most CAN networks run with 500 kbit/s
+pub const DEFAULT_BAUDRATE: i64 = 500_000;
+
+// Pseudo message used by Vector tools to hold signals which are not mapped to any message
+const VECTOR_INDEPENDENT_SIGNALS_MESSAGE: &str = "VECTOR__INDEPENDENT_SIG_MSG";
+
+// Parser structure
+pub struct DbcParser {
+}
+
+impl DbcParser {
+    fn handle_signal(&self, database: &DbcDatabase, message: &DbcMessage, signal: &DbcSignal) -> ISignal {
+        let init_values = match database.signal_attribute(message.id, &signal.name, "GenSigStartValue") {
+            Some(DbcAttributeValue::Number(value)) => InitValues::Single(value.round() as i64),
+            _ => InitValues::NotExist(true),
+        };
+
+        ISignal {
+            name: signal.name.clone(),
+            byte_order: signal.big_endian,
+            start_pos: signal.start_bit,
+            length: signal.length,
+            init_values,
+        }
+    }
+
+    fn handle_signals(&self, database: &DbcDatabase, message: &DbcMessage, grouped_signals: &mut Vec<ISignalGroup>, ungrouped_signals: &mut Vec<ISignal>) {
+        let mut grouped_signal_names: HashSet<&str> = HashSet::new();
+
+        for signal_group in database.signal_groups.iter().filter(|group| group.message_id == message.id) {
+            let mut isignals: Vec<ISignal> = Vec::new();
+
+            for signal_name in &signal_group.signal_names {
+                match message.signals.iter().find(|signal| &signal.name == signal_name) {
+                    Some(signal) => {
+                        isignals.push(self.handle_signal(database, message, signal));
+                        grouped_signal_names.insert(signal_name.as_str());
+                    }
+                    None => println!("[-] WARNING: Signal {} of SignalGroup {} does not exist in message {}. Will skip it.", signal_name, signal_group.name, message.name),
+                }
+            }
+
+            isignals.sort_by_key(|signal| signal.start_pos);
+
+            grouped_signals.push(ISignalGroup {
+                name: signal_group.name.clone(),
+                isignals,
+                data_transformations: Vec::new(),
+                transformation_props: Vec::new(),
+            });
+        }
+
+        for signal in &message.signals {
+            if grouped_signal_names.contains(signal.name.as_str()) {
+                continue;
+            }
+            if signal.multiplexed {
+                println!("[-] WARNING: Multiplexed signal {} of message {} is not supported. Will skip it.", signal.name, message.name);
+                continue;
+            }
+            ungrouped_signals.push(self.handle_signal(database, message, signal));
+        }
+
+        ungrouped_signals.sort_by_key(|signal| signal.start_pos);
+    }
+
+    fn handle_isignal_ipdu(&self, database: &DbcDatabase, message: &DbcMessage) -> ISignalIPDU {
+        let cyclic_timing_period_value = database.message_attribute_millis_as_seconds(message.id, "GenMsgCycleTime");
+        let cyclic_timing_offset_value = database.message_attribute_millis_as_seconds(message.id, "GenMsgStartDelayTime");
+        let repetition_period_value = database.message_attribute_millis_as_seconds(message.id, "GenMsgCycleTimeFast");
+
+        let number_of_repetitions = match database.message_attribute(message.id, "GenMsgNrOfRepetition") {
+            Some(DbcAttributeValue::Number(value)) => value.round() as i64,
+            _ => 0,
+        };
+
+        let mut grouped_signals: Vec<ISignalGroup> = Vec::new();
+        let mut ungrouped_signals: Vec<ISignal> = Vec::new();
+
+        self.handle_signals(database, message, &mut grouped_signals, &mut ungrouped_signals);
+
+        ISignalIPDU {
+            cyclic_timing_period_value,
+            cyclic_timing_period_tolerance: None,
+            cyclic_timing_offset_value,
+            cyclic_timing_offset_tolerance: None,
+            number_of_repetitions,
+            repetition_period_value,
+            repetition_period_tolerance: None,
+            unused_bit_pattern: false,
+            ungrouped_signals,
+            grouped_signals,
+        }
+    }
+
+    fn handle_nm_pdu(&self, database: &DbcDatabase, message: &DbcMessage) -> NMPDU {
+        let mut grouped_signals: Vec<ISignalGroup> = Vec::new();
+        let mut ungrouped_signals: Vec<ISignal> = Vec::new();
+
+        self.handle_signals(database, message, &mut grouped_signals, &mut ungrouped_signals);
+
+        NMPDU {
+            unused_bit_pattern: false,
+            ungrouped_signals,
+            grouped_signals,
+        }
+    }
+
+    fn handle_message(&self, database: &DbcDatabase, message: &DbcMessage) -> Result<CanFrameTriggering, String> {
+        if message.dlc <= 0 {
+            return Err(format!("Message {} has a DLC of {}. Skipping this message.", message.name, message.dlc));
+        }
+
+        let is_extended = message.id & DBC_EXTENDED_ID_FLAG != 0;
+        let can_id = message.id & DBC_CAN_ID_MASK;
+
+        let addressing_mode = if is_extended {
+            String::from("EXTENDED")
+        } else {
+            String::from("STANDARD")
+        };
+
+        let is_can_fd = matches!(
+            database.message_attribute(message.id, "VFrameFormat"),
+            Some(DbcAttributeValue::String(format)) if format.ends_with("CAN_FD")
+        );
+        let frame_behavior = if is_can_fd {
+            String::from("CAN-FD")
+        } else {
+            String::from("CAN-20")
+        };
+
+        let mut sender_ecus: Vec<String> = Vec::new();
+        for transmitter in message.transmitters.iter() {
+            if !is_vector_placeholder_node(transmitter) && !sender_ecus.contains(transmitter) {
+                sender_ecus.push(transmitter.clone());
+            }
+        }
+
+        let mut receiver_ecus: Vec<String> = Vec::new();
+        for receiver in message.signals.iter().flat_map(|signal| signal.receivers.iter()) {
+            if !is_vector_placeholder_node(receiver) && !receiver_ecus.contains(receiver) {
+                receiver_ecus.push(receiver.clone());
+            }
+        }
+
+        let is_nm_message = ["NmMessage", "NmAsrMessage"].iter()
+            .any(|attribute| match database.message_attribute(message.id, attribute) {
+                Some(DbcAttributeValue::String(value)) => value.eq_ignore_ascii_case("yes"),
+                Some(DbcAttributeValue::Number(value)) => value != 0_f64,
+                None => false,
+            });
+
+        let pdu = if is_nm_message {
+            PDU::NMPDU(self.handle_nm_pdu(database, message))
+        } else {
+            PDU::ISignalIPDU(self.handle_isignal_ipdu(database, message))
+        };
+
+        // DBC does not distinguish between frames and PDUs, so every message is mapped to exactly one PDU
+        let pdu_mapping = PDUMapping {
+            name: message.name.clone(),
+            byte_order: false,
+            start_position: 0,
+            length: message.dlc,
+            dynamic_length: String::from(""),
+            category: String::from(""),
+            contained_header_id_short: String::from(""),
+            contained_header_id_long: String::from(""),
+            pdu,
+        };
+
+        Ok(CanFrameTriggering {
+            frame_triggering_name: message.name.clone(),
+            frame_name: message.name.clone(),
+            can_id,
+            addressing_mode,
+            frame_rx_behavior: frame_behavior.clone(),
+            frame_tx_behavior: frame_behavior,
+            rx_range_lower: 0,
+            rx_range_upper: 0,
+            sender_ecus,
+            receiver_ecus,
+            frame_length: message.dlc,
+            pdu_mappings: vec![pdu_mapping],
+        })
+    }
+
+    fn handle_database(&self, default_cluster_name: String, database: &DbcDatabase) -> CanCluster {
+        let name = match database.network_attribute("DBName") {
+            Some(DbcAttributeValue::String(name)) if !name.is_empty() => name,
+            _ => default_cluster_name,
+        };
+
+        let mut baudrate = match database.network_attribute("Baudrate") {
+            Some(DbcAttributeValue::Number(value)) => value.round() as i64,
+            _ => 0,
+        };
+        let canfd_baudrate = match database.network_attribute("BaudrateCANFD") {
+            Some(DbcAttributeValue::Number(value)) => value.round() as i64,
+            _ => 0,
+        };
+
+        if baudrate == 0 && canfd_baudrate == 0 {
+            println!("[-] WARNING: DBC file of CanCluster {} does not define a baudrate. Using default of {}.", name, DEFAULT_BAUDRATE);
+            baudrate = DEFAULT_BAUDRATE;
+        }
+
+        let mut can_frame_triggerings: HashMap<i64, CanFrameTriggering> = HashMap::new();
+
+        for message in database.messages.iter().filter(|message| message.name != VECTOR_INDEPENDENT_SIGNALS_MESSAGE) {
+            match self.handle_message(database, message) {
+                Ok(value) => {
+                    can_frame_triggerings.insert(value.can_id, value);
+                }
+                Err(error) => println!("[-] WARNING: {}", error),
+            }
+        }
+
+        CanCluster {
+            name,
+            baudrate,
+            canfd_baudrate,
+            can_frame_triggerings,
+        }
+    }
+
+    // Parses the content of a DBC file. The cluster name is used if the file does not contain a DBName attribute.
+    pub fn parse_string(&self, cluster_name: String, content: &str) -> Result<CanCluster, String> {
+        let database = DbcDatabase::parse(content)?;
+
+        Ok(self.handle_database(cluster_name, &database))
+    }
+
+    // Main parsing method. Returns a map with a single CanCluster to be interchangeable with the ArxmlParser.
+    pub fn parse_file(&self, file_name: String) -> Option<HashMap<String, CanCluster>> {
+        let start = Instant::now();
+
+        // DBC files are traditionally Windows-1252 encoded, so invalid UTF-8 must not abort the parsing
+        let content = match std::fs::read(&file_name) {
+            Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+            Err(err) => panic!("Reading DBC file {} failed. Error: {}", file_name, err),
+        };
+
+        let default_cluster_name = Path::new(&file_name)
+            .file_stem()
+            .map(|stem| stem.to_string_lossy().into_owned())
+            .unwrap_or(file_name.clone());
+
+        let mut can_clusters: HashMap<String, CanCluster> = HashMap::new();
+
+        match self.parse_string(default_cluster_name, &content) {
+            Ok(value) => {
+                can_clusters.insert(value.name.clone(), value);
+            }
+            Err(error) => panic!("Parsing failed. Error: {}", error),
+        }
+
+        println!("[+] Duration of parsing: {:?}", start.elapsed());
+
+        Some(can_clusters)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DBC_CONTENT: &str = r#"
+VERSION ""
+
+NS_ :
+    NS_DESC_
+    CM_
+    BA_DEF_
+    BA_
+    SIG_GROUP_
+
+BS_:
+
+BU_: Gateway Dashboard Engine
+
+BO_ 256 EngineData: 8 Engine
+ SG_ EngineSpeed : 0|16@1+ (0.25,0) [0|16383.75] "rpm" Dashboard,Gateway
+ SG_ EngineTemp : 16|8@1- (1,-40) [-40|215] "degC" Dashboard
+
+BO_ 2147484160 ExtendedStatus: 4 Gateway
+ SG_ Status : 7|8@0+ (1,0) [0|255] "" Vector__XXX
+ SG_ Counter : 8|4@1+ (1,0) [0|15] "" Engine
+
+BO_ 1280 NmEngine: 8 Engine
+ SG_ NmSourceNode : 0|8@1+ (1,0) [0|255] "" Gateway
+
+CM_ SG_ 256 EngineSpeed "Speed; measured at the crankshaft";
+BA_DEF_ "DBName" STRING ;
+BA_DEF_ "Baudrate" INT 0 1000000;
+BA_DEF_ BO_ "GenMsgCycleTime" INT 0 65535;
+BA_DEF_ BO_ "NmAsrMessage" ENUM "No","Yes";
+BA_DEF_ BO_ "VFrameFormat" ENUM "StandardCAN","ExtendedCAN","reserved","StandardCAN_FD","ExtendedCAN_FD";
+BA_DEF_ SG_ "GenSigStartValue" INT 0 65535;
+BA_DEF_DEF_ "DBName" "";
+BA_DEF_DEF_ "Baudrate" 500000;
+BA_DEF_DEF_ "GenMsgCycleTime" 0;
+BA_DEF_DEF_ "NmAsrMessage" "No";
+BA_DEF_DEF_ "VFrameFormat" "StandardCAN";
+BA_DEF_DEF_ "GenSigStartValue" 0;
+BA_ "DBName" "Powertrain";
+BA_ "Baudrate" 250000;
+BA_ "GenMsgCycleTime" BO_ 256 100;
+BA_ "VFrameFormat" BO_ 2147484160 1;
+BA_ "NmAsrMessage" BO_ 1280 1;
+BA_ "GenSigStartValue" SG_ 256 EngineTemp 40;
+SIG_GROUP_ 2147484160 StatusGroup 1 : Status Counter;
+"#;
+
+    #[test]
+    fn should_parse_cluster_from_dbc() {
+        let cluster = DbcParser {}.parse_string(String::from("fallback"), DBC_CONTENT).unwrap();
+
+        assert_eq!(cluster.name, "Powertrain");
+        assert_eq!(cluster.baudrate, 250_000);
+        assert_eq!(cluster.can_frame_triggerings.len(), 3);
+    }
+
+    #[test]
+    fn should_parse_cycle_time_and_init_values() {
+        let cluster = DbcParser {}.parse_string(String::from("fallback"), DBC_CONTENT).unwrap();
+
+        let engine_data = cluster.can_frame_triggerings.get(&256).unwrap();
+        assert_eq!(engine_data.addressing_mode, "STANDARD");
+        assert_eq!(engine_data.frame_length, 8);
+        assert_eq!(engine_data.sender_ecus, vec![String::from("Engine")]);
+        assert_eq!(engine_data.receiver_ecus, vec![String::from("Dashboard"), String::from("Gateway")]);
+
+        let PDU::ISignalIPDU(ipdu) = &engine_data.pdu_mappings[0].pdu else {
+            panic!("Expected an ISignalIPDU");
+        };
+        assert_eq!(ipdu.cyclic_timing_period_value, 0.1);
+        assert_eq!(ipdu.ungrouped_signals.len(), 2);
+
+        let engine_temp = &ipdu.ungrouped_signals[1];
+        assert_eq!(engine_temp.name, "EngineTemp");
+        assert_eq!(engine_temp.start_pos, 16);
+        assert_eq!(engine_temp.length, 8);
+        assert!(!engine_temp.byte_order);
+        assert!(matches!(engine_temp.init_values, InitValues::Single(40)));
+
+        let engine_speed = &ipdu.ungrouped_signals[0];
+        assert!(matches!(engine_speed.init_values, InitValues::Single(0)));
+    }
+
+    #[test]
+    fn should_parse_extended_frames_with_signal_groups() {
+        let cluster = DbcParser {}.parse_string(String::from("fallback"), DBC_CONTENT).unwrap();
+
+        let extended_status = cluster.can_frame_triggerings.get(&0x200).unwrap();
+        assert_eq!(extended_status.addressing_mode, "EXTENDED");
+        assert_eq!(extended_status.frame_rx_behavior, "CAN-20");
+        assert_eq!(extended_status.receiver_ecus, vec![String::from("Engine")]);
+
+        let PDU::ISignalIPDU(ipdu) = &extended_status.pdu_mappings[0].pdu else {
+            panic!("Expected an ISignalIPDU");
+        };
+        assert!(ipdu.ungrouped_signals.is_empty());
+        assert_eq!(ipdu.grouped_signals.len(), 1);
+        assert_eq!(ipdu.grouped_signals[0].isignals.len(), 2);
+        assert!(ipdu.grouped_signals[0].isignals[0].byte_order);
+    }
+
+    #[test]
+    fn should_parse_nm_messages_as_nm_pdu() {
+        let cluster = DbcParser {}.parse_string(String::from("fallback"), DBC_CONTENT).unwrap();
+
+        let nm_engine = cluster.can_frame_triggerings.get(&1280).unwrap();
+        assert!(matches!(nm_engine.pdu_mappings[0].pdu, PDU::NMPDU(_)));
+    }
+}
@@ -0,0 +1,483 @@
+/*
+    HELPER METHODS AND STRUCTURES FOR THE DBC PARSER
+*/
+use std::collections::HashMap;
+
+// Bit 31 of a message ID marks an extended (29 bit) CAN ID in DBC files
+pub const DBC_EXTENDED_ID_FLAG: i64 = 0x8000_0000;
+pub const DBC_CAN_ID_MASK: i64 = 0x1FFF_FFFF;
+
+// Keywords which start a new statement on the top level of a DBC file
+const DBC_KEYWORDS: [&str; 27] = [
+    "VERSION", "NS_", "BS_", "BU_", "VAL_TABLE_", "BO_", "SG_", "BO_TX_BU_", "EV_", "ENVVAR_DATA_",
+    "SGTYPE_", "CM_", "BA_DEF_", "BA_DEF_DEF_", "BA_", "VAL_", "SIG_GROUP_", "SIG_VALTYPE_",
+    "SIG_TYPE_REF_", "SG_MUL_VAL_", "BA_DEF_REL_", "BA_REL_", "BA_DEF_DEF_REL_", "BU_SG_REL_",
+    "BU_EV_REL_", "BU_BO_REL_", "CAT_DEF_",
+];
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DbcToken {
+    Identifier(String),
+    Number(f64),
+    String(String),
+    Symbol(char),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum DbcAttributeValue {
+    Number(f64),
+    String(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum DbcAttributeTarget {
+    Network,
+    Node(String),
+    Message(i64),
+    Signal(i64, String),
+}
+
+#[derive(Debug)]
+pub struct DbcSignal {
+    pub name: String,
+    pub multiplexed: bool,
+    pub start_bit: i64,
+    pub length: i64,
+    pub big_endian: bool,
+    pub signed: bool,
+    pub factor: f64,
+    pub offset: f64,
+    pub receivers: Vec<String>,
+}
+
+#[derive(Debug)]
+pub struct DbcMessage {
+    pub id: i64,
+    pub name: String,
+    pub dlc: i64,
+    pub transmitters: Vec<String>,
+    pub signals: Vec<DbcSignal>,
+}
+
+#[derive(Debug)]
+pub struct DbcSignalGroup {
+    pub message_id: i64,
+    pub name: String,
+    pub signal_names: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct DbcDatabase {
+    pub nodes: Vec<String>,
+    pub messages: Vec<DbcMessage>,
+    pub signal_groups: Vec<DbcSignalGroup>,
+    attribute_enums: HashMap<String, Vec<String>>,
+    attribute_defaults: HashMap<String, DbcAttributeValue>,
+    attributes: HashMap<(String, DbcAttributeTarget), DbcAttributeValue>,
+}
+
+pub fn is_vector_placeholder_node(name: &str) -> bool {
+    name == "Vector__XXX"
+}
+
+pub fn tokenize(content: &str) -> Result<Vec<DbcToken>, String> {
+    let mut tokens: Vec<DbcToken> = Vec::new();
+    let chars: Vec<char> = content.chars().collect();
+    let mut index: usize = 0;
+
+    while index < chars.len() {
+        let c = chars[index];
+
+        if c.is_whitespace() {
+            index += 1;
+        } else if c == '"' {
+            let mut value = String::new();
+            index += 1;
+            loop {
+                match chars.get(index) {
+                    None => return Err(String::from("Unterminated string literal in DBC file")),
+                    Some('\\') if chars.get(index + 1) == Some(&'"') => {
+                        value.push('"');
+                        index += 2;
+                    }
+                    Some('"') => {
+                        index += 1;
+                        break;
+                    }
+                    Some(other) => {
+                        value.push(*other);
+                        index += 1;
+                    }
+                }
+            }
+            tokens.push(DbcToken::String(value));
+        } else if c.is_ascii_digit() || (c == '-' && chars.get(index + 1).is_some_and(|next| next.is_ascii_digit())) {
+            let start = index;
+            index += 1;
+            while let Some(next) = chars.get(index) {
+                let is_exponent_sign = (*next == '+' || *next == '-') && matches!(chars[index - 1], 'e' | 'E');
+                if next.is_ascii_digit() || *next == '.' || *next == 'e' || *next == 'E' || is_exponent_sign {
+                    index += 1;
+                } else {
+                    break;
+                }
+            }
+            let text: String = chars[start..index].iter().collect();
+            let value = text.parse::<f64>()
+                .map_err(|_| format!("Invalid number '{}' in DBC file", text))?;
+            tokens.push(DbcToken::Number(value));
+        } else if c.is_alphabetic() || c == '_' {
+            let start = index;
+            while chars.get(index).is_some_and(|next| next.is_alphanumeric() || *next == '_') {
+                index += 1;
+            }
+            tokens.push(DbcToken::Identifier(chars[start..index].iter().collect()));
+        } else {
+            tokens.push(DbcToken::Symbol(c));
+            index += 1;
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct TokenStream {
+    tokens: Vec<DbcToken>,
+    position: usize,
+}
+
+impl TokenStream {
+    fn peek(&self) -> Option<&DbcToken> {
+        self.tokens.get(self.position)
+    }
+
+    fn next(&mut self) -> Option<DbcToken> {
+        let token = self.tokens.get(self.position).cloned();
+        self.position += 1;
+        token
+    }
+
+    fn peek_is_keyword(&self) -> bool {
+        matches!(self.peek(), Some(DbcToken::Identifier(ident)) if DBC_KEYWORDS.contains(&ident.as_str()))
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> Result<(), String> {
+        match self.next() {
+            Some(DbcToken::Symbol(value)) if value == symbol => Ok(()),
+            other => Err(format!("Expected '{}' in DBC file, found {:?}", symbol, other)),
+        }
+    }
+
+    fn expect_identifier(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(DbcToken::Identifier(value)) => Ok(value),
+            other => Err(format!("Expected identifier in DBC file, found {:?}", other)),
+        }
+    }
+
+    fn expect_number(&mut self) -> Result<f64, String> {
+        match self.next() {
+            Some(DbcToken::Number(value)) => Ok(value),
+            other => Err(format!("Expected number in DBC file, found {:?}", other)),
+        }
+    }
+
+    fn expect_integer(&mut self) -> Result<i64, String> {
+        self.expect_number().map(|value| value as i64)
+    }
+
+    fn expect_string(&mut self) -> Result<String, String> {
+        match self.next() {
+            Some(DbcToken::String(value)) => Ok(value),
+            other => Err(format!("Expected string in DBC file, found {:?}", other)),
+        }
+    }
+
+    fn expect_attribute_value(&mut self) -> Result<DbcAttributeValue, String> {
+        match self.next() {
+            Some(DbcToken::Number(value)) => Ok(DbcAttributeValue::Number(value)),
+            Some(DbcToken::String(value)) => Ok(DbcAttributeValue::String(value)),
+            other => Err(format!("Expected attribute value in DBC file, found {:?}", other)),
+        }
+    }
+
+    fn skip_statement(&mut self) {
+        while let Some(token) = self.next() {
+            if token == DbcToken::Symbol(';') {
+                break;
+            }
+        }
+    }
+
+    fn skip_until_keyword(&mut self) {
+        while self.peek().is_some() && !self.peek_is_keyword() {
+            self.position += 1;
+        }
+    }
+}
+
+impl DbcDatabase {
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut stream = TokenStream { tokens: tokenize(content)?, position: 0 };
+        let mut database = DbcDatabase::default();
+
+        while let Some(token) = stream.next() {
+            let keyword = match token {
+                DbcToken::Identifier(keyword) => keyword,
+                _ => continue,
+            };
+
+            match keyword.as_str() {
+                "NS_" => {
+                    // The new symbols section lists keywords, so it can only be terminated by the bit timing section
+                    while let Some(token) = stream.peek() {
+                        if *token == DbcToken::Identifier(String::from("BS_")) {
+                            break;
+                        }
+                        stream.position += 1;
+                    }
+                }
+                "BU_" => {
+                    stream.expect_symbol(':')?;
+                    while !stream.peek_is_keyword() {
+                        match stream.next() {
+                            Some(DbcToken::Identifier(node)) => database.nodes.push(node),
+                            Some(_) => {}
+                            None => break,
+                        }
+                    }
+                }
+                "BO_" => database.parse_message(&mut stream)?,
+                "SG_" => database.parse_signal(&mut stream)?,
+                "BO_TX_BU_" => database.parse_message_transmitters(&mut stream)?,
+                "BA_DEF_" => database.parse_attribute_definition(&mut stream)?,
+                "BA_DEF_DEF_" => {
+                    let name = stream.expect_string()?;
+                    let value = stream.expect_attribute_value()?;
+                    database.attribute_defaults.insert(name, value);
+                    stream.skip_statement();
+                }
+                "BA_" => database.parse_attribute(&mut stream)?,
+                "SIG_GROUP_" => database.parse_signal_group(&mut stream)?,
+                "VERSION" | "BS_" => stream.skip_until_keyword(),
+                _ => stream.skip_statement(),
+            }
+        }
+
+        Ok(database)
+    }
+
+    fn parse_message(&mut self, stream: &mut TokenStream) -> Result<(), String> {
+        let id = stream.expect_integer()?;
+        let name = stream.expect_identifier()?;
+        stream.expect_symbol(':')?;
+        let dlc = stream.expect_integer()?;
+        let transmitter = stream.expect_identifier()?;
+
+        self.messages.push(DbcMessage {
+            id,
+            name,
+            dlc,
+            transmitters: vec![transmitter],
+            signals: Vec::new(),
+        });
+
+        Ok(())
+    }
+
+    fn parse_signal(&mut self, stream: &mut TokenStream) -> Result<(), String> {
+        let name = stream.expect_identifier()?;
+
+        // Optional multiplexer indicator: 'M' for the multiplexor, 'm<value>' for multiplexed signals
+        let mut multiplexed = false;
+        if let Some(DbcToken::Identifier(indicator)) = stream.peek() {
+            multiplexed = indicator.starts_with('m');
+            stream.position += 1;
+        }
+
+        stream.expect_symbol(':')?;
+        let start_bit = stream.expect_integer()?;
+        stream.expect_symbol('|')?;
+        let length = stream.expect_integer()?;
+        stream.expect_symbol('@')?;
+        // '@0' is Motorola (big endian), '@1' is Intel (little endian)
+        let big_endian = stream.expect_integer()? == 0;
+        let signed = match stream.next() {
+            Some(DbcToken::Symbol('-')) => true,
+            Some(DbcToken::Symbol('+')) => false,
+            other => return Err(format!("Expected value type of signal {} in DBC file, found {:?}", name, other)),
+        };
+
+        stream.expect_symbol('(')?;
+        let factor = stream.expect_number()?;
+        stream.expect_symbol(',')?;
+        let offset = stream.expect_number()?;
+        stream.expect_symbol(')')?;
+
+        stream.expect_symbol('[')?;
+        stream.expect_number()?;
+        stream.expect_symbol('|')?;
+        stream.expect_number()?;
+        stream.expect_symbol(']')?;
+
+        let _unit = stream.expect_string()?;
+
+        let mut receivers: Vec<String> = vec![stream.expect_identifier()?];
+        while stream.peek() == Some(&DbcToken::Symbol(',')) {
+            stream.position += 1;
+            receivers.push(stream.expect_identifier()?);
+        }
+
+        let message = self.messages.last_mut()
+            .ok_or(format!("Signal {} is not located inside a message in DBC file", name))?;
+
+        message.signals.push(DbcSignal {
+            name,
+            multiplexed,
+            start_bit,
+            length,
+            big_endian,
+            signed,
+            factor,
+            offset,
+            receivers,
+        });
+
+        Ok(())
+    }
+
+    fn parse_message_transmitters(&mut self, stream: &mut TokenStream) -> Result<(), String> {
+        let id = stream.expect_integer()?;
+        stream.expect_symbol(':')?;
+
+        let mut transmitters: Vec<String> = Vec::new();
+        loop {
+            match stream.next() {
+                Some(DbcToken::Identifier(transmitter)) => transmitters.push(transmitter),
+                Some(DbcToken::Symbol(',')) => {}
+                Some(DbcToken::Symbol(';')) | None => break,
+                other => return Err(format!("Unexpected token {:?} in BO_TX_BU_ of message {}", other, id)),
+            }
+        }
+
+        if let Some(message) = self.messages.iter_mut().find(|message| message.id == id) {
+            message.transmitters.extend(transmitters);
+        }
+
+        Ok(())
+    }
+
+    fn parse_attribute_definition(&mut self, stream: &mut TokenStream) -> Result<(), String> {
+        if let Some(DbcToken::Identifier(_)) = stream.peek() {
+            stream.position += 1; // object type: BU_, BO_, SG_ or EV_
+        }
+        let name = stream.expect_string()?;
+        let value_type = stream.expect_identifier()?;
+
+        if value_type == "ENUM" {
+            let mut values: Vec<String> = Vec::new();
+            loop {
+                match stream.next() {
+                    Some(DbcToken::String(value)) => values.push(value),
+                    Some(DbcToken::Symbol(',')) => {}
+                    Some(DbcToken::Symbol(';')) | None => break,
+                    other => return Err(format!("Unexpected token {:?} in ENUM definition of attribute {}", other, name)),
+                }
+            }
+            self.attribute_enums.insert(name, values);
+        } else {
+            stream.skip_statement();
+        }
+
+        Ok(())
+    }
+
+    fn parse_attribute(&mut self, stream: &mut TokenStream) -> Result<(), String> {
+        let name = stream.expect_string()?;
+
+        let target = match stream.peek() {
+            Some(DbcToken::Identifier(object_type)) => {
+                let object_type = object_type.clone();
+                stream.position += 1;
+                match object_type.as_str() {
+                    "BU_" => DbcAttributeTarget::Node(stream.expect_identifier()?),
+                    "BO_" => DbcAttributeTarget::Message(stream.expect_integer()?),
+                    "SG_" => {
+                        let message_id = stream.expect_integer()?;
+                        DbcAttributeTarget::Signal(message_id, stream.expect_identifier()?)
+                    }
+                    _ => {
+                        // Attributes of environment variables are not relevant for the restbus simulation
+                        stream.skip_statement();
+                        return Ok(());
+                    }
+                }
+            }
+            _ => DbcAttributeTarget::Network,
+        };
+
+        let value = stream.expect_attribute_value()?;
+        stream.skip_statement();
+
+        self.attributes.insert((name, target), value);
+
+        Ok(())
+    }
+
+    fn parse_signal_group(&mut self, stream: &mut TokenStream) -> Result<(), String> {
+        let message_id = stream.expect_integer()?;
+        let name = stream.expect_identifier()?;
+        let _repetitions = stream.expect_integer()?;
+        stream.expect_symbol(':')?;
+
+        let mut signal_names: Vec<String> = Vec::new();
+        loop {
+            match stream.next() {
+                Some(DbcToken::Identifier(signal_name)) => signal_names.push(signal_name),
+                Some(DbcToken::Symbol(',')) => {}
+                Some(DbcToken::Symbol(';')) | None => break,
+                other => return Err(format!("Unexpected token {:?} in SIG_GROUP_ {}", other, name)),
+            }
+        }
+
+        self.signal_groups.push(DbcSignalGroup { message_id, name, signal_names });
+
+        Ok(())
+    }
+
+    // Resolves the value of an attribute, falling back to its default and mapping ENUM indices to their names
+    fn attribute(&self, name: &str, target: DbcAttributeTarget) -> Option<DbcAttributeValue> {
+        let value = self.attributes.get(&(String::from(name), target))
+            .or_else(|| self.attribute_defaults.get(name))
+            .cloned()?;
+
+        match (value, self.attribute_enums.get(name)) {
+            (DbcAttributeValue::Number(index), Some(enum_values)) => {
+                enum_values.get(index as usize)
+                    .cloned()
+                    .map(DbcAttributeValue::String)
+            }
+            (value, _) => Some(value),
+        }
+    }
+
+    pub fn network_attribute(&self, name: &str) -> Option<DbcAttributeValue> {
+        self.attribute(name, DbcAttributeTarget::Network)
+    }
+
+    pub fn message_attribute(&self, message_id: i64, name: &str) -> Option<DbcAttributeValue> {
+        self.attribute(name, DbcAttributeTarget::Message(message_id))
+    }
+
+    pub fn signal_attribute(&self, message_id: i64, signal_name: &str, name: &str) -> Option<DbcAttributeValue> {
+        self.attribute(name, DbcAttributeTarget::Signal(message_id, String::from(signal_name)))
+    }
+
+    // DBC timing attributes are given in milliseconds, while the ARXML structures use seconds
+    pub fn message_attribute_millis_as_seconds(&self, message_id: i64, name: &str) -> f64 {
+        match self.message_attribute(message_id, name) {
+            Some(DbcAttributeValue::Number(value)) => value / 1000_f64,
+            _ => 0_f64,
+        }
+    }
+}
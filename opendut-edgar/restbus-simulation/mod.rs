@@ -0,0 +1,12 @@
+/*
+- Parsers for CAN communication matrices, which the restbus simulation is set up from.
+- The ARXML parser depends on autosar-data and is only included with the "arxml" feature.
+*/
+#[cfg(feature = "arxml")]
+pub mod arxml_parser;
+pub mod arxml_structs;
+#[cfg(feature = "arxml")]
+pub mod arxml_utils;
+pub mod can_database_parser;
+pub mod dbc_parser;
+pub mod dbc_utils;
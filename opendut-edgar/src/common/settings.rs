@@ -25,7 +25,10 @@ pub fn default_config_file_path() -> PathBuf {
 }
 
 pub fn load_with_overrides(overrides: config::Config) -> anyhow::Result<LoadedConfig> {
-    let edgar_config_hide_secrets_override = opendut_util::settings::Config::default();
+    let edgar_config_hide_secrets_override = config::Config::builder()
+        .set_override("network.oidc.client.secret", "redacted")?
+        .set_override("results.s3.secret.access.key", "redacted")?
+        .build()?;
 
     Ok(opendut_util::settings::load_config("edgar", include_str!("../../edgar.toml"), config::FileFormat::Toml, overrides, edgar_config_hide_secrets_override)?)
 }
@@ -2,5 +2,9 @@ opendut_util::app_info!();
 pub mod common;
 pub mod service;
 pub mod setup;
+pub mod test_case;
+
+#[path = "../restbus-simulation/mod.rs"]
+pub mod restbus_simulation;
 
 pub use fs_err as fs;
@@ -1,14 +1,17 @@
 use std::collections::HashSet;
 use std::net::Ipv4Addr;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
 use clap::{Parser, Subcommand};
 use tracing::info;
 use url::Url;
 use uuid::Uuid;
 
-use opendut_edgar::setup;
+use opendut_edgar::{setup, test_case};
 use opendut_types::peer::PeerId;
+use opendut_types::test_case::TestVerdict;
 use opendut_types::util::net::NetworkInterfaceName;
 use opendut_types::vpn::netbird::SetupKey;
 
@@ -46,6 +49,24 @@ enum Commands {
         #[arg(long, global=true, default_value="1538")]
         mtu: u16,
     },
+    /// Run a test case of the ALIA/SKyBT test case DSL (.csa or .json)
+    TestCase {
+        /// Path to the test case
+        #[arg()]
+        file: PathBuf,
+
+        /// Value for a placeholder in the test case, e.g. `--parameter canIF=vcan0`. Takes precedence over environment variables and defaults.
+        #[arg(long="parameter", value_name="NAME=VALUE", value_parser=parse_parameter)]
+        parameters: Vec<(String, String)>,
+
+        /// File to write the verdict to as JSON. Defaults to `verdict.json` in the results directory, when run as executor.
+        #[arg(long)]
+        verdict: Option<PathBuf>,
+
+        /// Seconds after which steps without `duration:long` are cancelled.
+        #[arg(long, default_value="60")]
+        short_step_timeout: u64,
+    },
 }
 
 #[derive(Debug, Subcommand)]
@@ -117,9 +138,31 @@ async fn main() -> anyhow::Result<()> {
             info!("EDGAR Setup finished!\n");
             Ok(())
         },
+        Commands::TestCase { file, parameters, verdict, short_step_timeout } => {
+            let options = test_case::TestCaseOptions {
+                file,
+                parameters,
+                verdict_file: verdict,
+                short_step_timeout: Duration::from_secs(short_step_timeout),
+            };
+            let exit_code = match test_case::run(options).await {
+                Ok(verdict) => test_case::exit_code(verdict),
+                Err(cause) => {
+                    eprintln!("Failed to run test case: {cause:#}");
+                    test_case::exit_code(TestVerdict::Error)
+                }
+            };
+            std::process::exit(exit_code);
+        },
     }
 }
 
+fn parse_parameter(string: &str) -> Result<(String, String), String> {
+    let (name, value) = string.split_once('=')
+        .ok_or_else(|| format!("Expected a parameter in the form NAME=VALUE, but got '{string}'."))?;
+    Ok((name.to_owned(), value.to_owned()))
+}
+
 #[derive(Clone, Debug)]
 struct ParseableLeader(setup::Leader);
 impl FromStr for ParseableLeader {
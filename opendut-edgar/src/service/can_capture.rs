@@ -0,0 +1,374 @@
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use tokio::fs::{self, File};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::process::{Child, ChildStdout, Command};
+use tracing::{debug, error, info, warn};
+use url::Url;
+
+use opendut_types::cluster::{CanCaptureConfiguration, CanCaptureRotation, CanCaptureSource, ClusterId};
+use opendut_types::peer::executor::ResultsUrl;
+use opendut_types::peer::PeerId;
+use opendut_types::util::net::NetworkInterfaceName;
+
+use crate::service::test_execution::result_sink::{self, ResultSinksRef};
+
+pub type CanCaptureManagerRef = Arc<CanCaptureManager>;
+
+pub struct CanCaptureManager {
+    /*
+        Works like the cannelloni_termination_token of the CanManager: Every ClusterAssignment starts a new generation of
+        CanCaptureRecorders, which share a fresh AtomicBool. Setting it to 'true' makes the previous generation terminate.
+     */
+    termination_token: Mutex<Arc<AtomicBool>>,
+    capture_directory: PathBuf,
+    result_sinks: ResultSinksRef,
+}
+
+const MONITOR_INTERVAL_MS: u64 = 100;
+const CANDUMP_RESTART_DELAY_MS: u64 = 1000;
+const LOG_FILE_EXTENSION: &str = "log";
+
+impl CanCaptureManager {
+    pub fn create(capture_directory: PathBuf, result_sinks: ResultSinksRef) -> CanCaptureManagerRef {
+        Arc::new(Self {
+            termination_token: Mutex::new(Arc::new(AtomicBool::new(false))),
+            capture_directory,
+            result_sinks,
+        })
+    }
+
+    /// Stops the currently running capture, if any, and starts recording according to the given configuration.
+    pub async fn start_capture(
+        &self,
+        cluster_id: ClusterId,
+        self_id: PeerId,
+        configuration: &Option<CanCaptureConfiguration>,
+        bridge_name: &NetworkInterfaceName,
+        own_can_interfaces: &[NetworkInterfaceName],
+    ) -> Result<(), Error> {
+
+        let termination_token = self.renew_termination_token();
+
+        let configuration = match configuration {
+            Some(configuration) => configuration,
+            None => {
+                debug!("CAN capture is not enabled for cluster <{cluster_id}>.");
+                return Ok(());
+            }
+        };
+
+        let interfaces = determine_capture_interfaces(&configuration.source, bridge_name, own_can_interfaces);
+        if interfaces.is_empty() {
+            info!("None of the CAN interfaces selected for capturing in cluster <{cluster_id}> are present on this peer. Not capturing CAN traffic.");
+            return Ok(());
+        }
+
+        let directory = self.capture_directory.join(cluster_id.to_string());
+        fs::create_dir_all(&directory).await
+            .map_err(|cause| Error::LogFileWriting { path: directory.clone(), cause })?;
+
+        info!("Capturing CAN traffic of cluster <{cluster_id}> on interfaces {} into '{}'.",
+            interfaces.iter().map(ToString::to_string).collect::<Vec<_>>().join(", "),
+            directory.display(),
+        );
+
+        let mut recorder = CanCaptureRecorder {
+            interfaces,
+            directory,
+            file_prefix: format!("can-capture_{self_id}"),
+            rotation: Clone::clone(&configuration.rotation),
+            results_url: Clone::clone(&configuration.results_url),
+            result_sinks: Arc::clone(&self.result_sinks),
+            termination_token,
+            file_index: 0,
+        };
+
+        tokio::spawn(async move {
+            recorder.run().await;
+        });
+
+        Ok(())
+    }
+
+    /// Stops the currently running capture, if any. The last log file is finalized and uploaded by the recorder.
+    pub fn stop_capture(&self) {
+        self.renew_termination_token();
+    }
+
+    fn renew_termination_token(&self) -> Arc<AtomicBool> {
+        let mut guarded_termination_token = self.termination_token.lock().unwrap();
+        guarded_termination_token.store(true, Ordering::Relaxed);
+        *guarded_termination_token = Arc::new(AtomicBool::new(false));
+        Arc::clone(&guarded_termination_token)
+    }
+}
+
+fn determine_capture_interfaces(
+    source: &CanCaptureSource,
+    bridge_name: &NetworkInterfaceName,
+    own_can_interfaces: &[NetworkInterfaceName],
+) -> Vec<NetworkInterfaceName> {
+    match source {
+        CanCaptureSource::Bridge => vec![Clone::clone(bridge_name)],
+        CanCaptureSource::DeviceInterfaces(interfaces) => {
+            interfaces.iter()
+                .filter(|interface| own_can_interfaces.contains(interface))
+                .cloned()
+                .collect()
+        }
+    }
+}
+
+struct CanCaptureRecorder {
+    interfaces: Vec<NetworkInterfaceName>,
+    directory: PathBuf,
+    file_prefix: String,
+    rotation: CanCaptureRotation,
+    results_url: Option<ResultsUrl>,
+    result_sinks: ResultSinksRef,
+    termination_token: Arc<AtomicBool>,
+    file_index: u32,
+}
+
+enum RecordingResult {
+    Rotate,
+    RestartCandump,
+    Terminate,
+}
+
+struct LogFile {
+    path: PathBuf,
+    file: File,
+    size: u64,
+    opened_at: Instant,
+}
+
+impl CanCaptureRecorder {
+
+    async fn run(&mut self) {
+        match self.run_internal().await {
+            Ok(_) => (),
+            Err(cause) => error!("{cause}"),
+        }
+    }
+
+    async fn run_internal(&mut self) -> Result<(), Error> {
+        let (mut candump, mut lines) = self.spawn_candump()?;
+        let mut log_file = self.open_log_file().await?;
+
+        loop {
+            match self.record(&mut lines, &mut log_file).await? {
+                RecordingResult::Rotate => {
+                    let finished_log_file = std::mem::replace(&mut log_file, self.open_log_file().await?);
+                    self.finish_log_file(finished_log_file).await;
+                }
+                RecordingResult::RestartCandump => {
+                    warn!("candump terminated unexpectedly while capturing on {}. Restarting it.", self.interfaces_string());
+                    tokio::time::sleep(Duration::from_millis(CANDUMP_RESTART_DELAY_MS)).await;
+                    (candump, lines) = self.spawn_candump()?;
+                }
+                RecordingResult::Terminate => {
+                    candump.kill().await
+                        .map_err(|cause| Error::Other { message: format!("Failed to kill candump process capturing on {}: {cause}", self.interfaces_string()) })?;
+                    self.finish_log_file(log_file).await;
+                    info!("Stopped capturing CAN traffic on {}.", self.interfaces_string());
+                    return Ok(());
+                }
+            }
+        }
+    }
+
+    async fn record(&self, lines: &mut Lines<BufReader<ChildStdout>>, log_file: &mut LogFile) -> Result<RecordingResult, Error> {
+        loop {
+            if self.termination_token.load(Ordering::Relaxed) {
+                return Ok(RecordingResult::Terminate);
+            }
+            if self.rotation_due(log_file) {
+                return Ok(RecordingResult::Rotate);
+            }
+
+            let line = match tokio::time::timeout(Duration::from_millis(MONITOR_INTERVAL_MS), lines.next_line()).await {
+                Err(_elapsed) => continue,
+                Ok(Ok(Some(line))) => line,
+                Ok(Ok(None)) => return Ok(RecordingResult::RestartCandump),
+                Ok(Err(cause)) => {
+                    error!("Failed to read output of candump capturing on {}: {cause}", self.interfaces_string());
+                    return Ok(RecordingResult::RestartCandump);
+                }
+            };
+
+            let line = format!("{line}\n");
+            log_file.file.write_all(line.as_bytes()).await
+                .map_err(|cause| Error::LogFileWriting { path: log_file.path.clone(), cause })?;
+            log_file.size += line.len() as u64;
+        }
+    }
+
+    fn rotation_due(&self, log_file: &LogFile) -> bool {
+        let size_exceeded = self.rotation.max_file_size > 0
+            && log_file.size >= self.rotation.max_file_size;
+        let duration_exceeded = self.rotation.max_file_duration.is_zero().not()
+            && log_file.opened_at.elapsed() >= self.rotation.max_file_duration;
+
+        size_exceeded || duration_exceeded
+    }
+
+    fn spawn_candump(&self) -> Result<(Child, Lines<BufReader<ChildStdout>>), Error> {
+        // '-L' prints the frames in the log file format, which can be replayed with `canplayer`.
+        let mut candump = Command::new("candump")
+            .arg("-L")
+            .args(self.interfaces.iter().map(NetworkInterfaceName::name))
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .kill_on_drop(true)
+            .spawn()
+            .map_err(|cause| Error::CommandLineProgramExecution { command: "candump".to_string(), cause })?;
+
+        let stdout = candump.stdout.take()
+            .ok_or(Error::Other { message: format!("Failed to get stdout of candump process capturing on {}.", self.interfaces_string()) })?;
+
+        Ok((candump, BufReader::new(stdout).lines()))
+    }
+
+    async fn open_log_file(&mut self) -> Result<LogFile, Error> {
+        let file_name = format!("{}_{}_{:05}.{LOG_FILE_EXTENSION}",
+            self.file_prefix,
+            chrono::offset::Local::now().format("%Y-%m-%d_%H-%M-%S"),
+            self.file_index,
+        );
+        self.file_index += 1;
+
+        let path = self.directory.join(file_name);
+        let file = File::create(&path).await
+            .map_err(|cause| Error::LogFileWriting { path: path.clone(), cause })?;
+
+        debug!("Writing captured CAN traffic to '{}'.", path.display());
+
+        Ok(LogFile { path, file, size: 0, opened_at: Instant::now() })
+    }
+
+    async fn finish_log_file(&self, mut log_file: LogFile) {
+        if let Err(cause) = log_file.file.flush().await {
+            error!("Failed to flush CAN capture log file '{}': {cause}", log_file.path.display());
+        }
+
+        if let Err(cause) = self.upload_log_file(&log_file.path).await {
+            error!("{cause}");
+        }
+
+        if let Err(cause) = self.remove_old_log_files().await {
+            error!("{cause}");
+        }
+    }
+
+    async fn upload_log_file(&self, path: &Path) -> Result<(), Error> {
+        let results_url = match &self.results_url {
+            Some(results_url) => results_url,
+            None => return Ok(()),
+        };
+
+        let content = fs::read(path).await
+            .map_err(|cause| Error::LogFileWriting { path: path.to_path_buf(), cause })?;
+
+        let file_name = path.file_name()
+            .map(|file_name| file_name.to_string_lossy())
+            .ok_or(Error::Other { message: format!("CAN capture log file '{}' has no file name.", path.display()) })?;
+
+        let uploaded_file = self.result_sinks.upload(results_url, &file_name, content)
+            .await
+            .map_err(|cause| Error::Uploading { url: Clone::clone(results_url.value()), cause: Box::new(cause) })?;
+
+        info!("Successfully uploaded CAN capture log file '{}' to '{}'.", path.display(), uploaded_file.location);
+        Ok(())
+    }
+
+    async fn remove_old_log_files(&self) -> Result<(), Error> {
+        if self.rotation.max_files == 0 {
+            return Ok(());
+        }
+
+        let mut log_files = Vec::new();
+        let mut entries = fs::read_dir(&self.directory).await
+            .map_err(|cause| Error::LogFileWriting { path: self.directory.clone(), cause })?;
+        while let Some(entry) = entries.next_entry().await
+            .map_err(|cause| Error::LogFileWriting { path: self.directory.clone(), cause })? {
+
+            let file_name = entry.file_name().to_string_lossy().to_string();
+            if file_name.starts_with(&self.file_prefix) && file_name.ends_with(LOG_FILE_EXTENSION) {
+                log_files.push(entry.path());
+            }
+        }
+
+        // The file names contain a timestamp followed by a running index, so they sort chronologically.
+        // The log file currently being written is the newest one and is therefore never removed.
+        log_files.sort();
+
+        let max_files = usize::try_from(self.rotation.max_files).unwrap_or(usize::MAX);
+        let excess = log_files.len().saturating_sub(max_files);
+
+        for path in log_files.into_iter().take(excess) {
+            debug!("Removing old CAN capture log file '{}'.", path.display());
+            fs::remove_file(&path).await
+                .map_err(|cause| Error::LogFileWriting { path, cause })?;
+        }
+        Ok(())
+    }
+
+    fn interfaces_string(&self) -> String {
+        self.interfaces.iter().map(ToString::to_string).collect::<Vec<_>>().join(", ")
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failure while invoking command line program '{command}': {cause}")]
+    CommandLineProgramExecution { command: String, cause: std::io::Error },
+    #[error("Failure while writing CAN capture log file '{path}': {cause}")]
+    LogFileWriting { path: PathBuf, cause: std::io::Error },
+    #[error("Failure while uploading CAN capture log file to '{url}': {cause}")]
+    Uploading { url: Url, cause: Box<result_sink::Error> },
+    #[error("{message}")]
+    Other { message: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_capture_on_the_bridge() -> anyhow::Result<()> {
+        let bridge_name = NetworkInterfaceName::try_from("br-vcan-opendut")?;
+        let own_can_interfaces = vec![NetworkInterfaceName::try_from("can0")?];
+
+        let interfaces = determine_capture_interfaces(&CanCaptureSource::Bridge, &bridge_name, &own_can_interfaces);
+
+        assert_that!(interfaces, elements_are![eq(bridge_name)]);
+        Ok(())
+    }
+
+    #[test]
+    fn should_only_capture_on_selected_interfaces_present_on_the_peer() -> anyhow::Result<()> {
+        let bridge_name = NetworkInterfaceName::try_from("br-vcan-opendut")?;
+        let own_can_interfaces = vec![
+            NetworkInterfaceName::try_from("can0")?,
+            NetworkInterfaceName::try_from("can1")?,
+        ];
+        let source = CanCaptureSource::DeviceInterfaces(vec![
+            NetworkInterfaceName::try_from("can1")?,
+            NetworkInterfaceName::try_from("vcan2")?,
+        ]);
+
+        let interfaces = determine_capture_interfaces(&source, &bridge_name, &own_can_interfaces);
+
+        assert_that!(interfaces, elements_are![eq(NetworkInterfaceName::try_from("can1")?)]);
+        Ok(())
+    }
+}
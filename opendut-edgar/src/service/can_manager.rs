@@ -1,21 +1,31 @@
+use std::collections::HashSet;
 use std::net::IpAddr;
+use std::ops::Not;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
 use std::time::Duration;
-use opendut_types::cluster::PeerClusterAssignment;
+use opendut_types::cluster::{CanFault, CanFaultInjectionRule, ClusterId, PeerClusterAssignment};
 use opendut_types::util::Port;
-use regex::Regex;
 
 use tokio::process::Command;
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
-use opendut_types::util::net::{NetworkInterfaceDescriptor, NetworkInterfaceName};
+use opendut_types::util::net::{CanFrameFilter, CanIdFilter, CanRateLimit, NetworkInterfaceConfiguration, NetworkInterfaceDescriptor, NetworkInterfaceName};
 
 use crate::service::cannelloni_manager::CannelloniManager;
 use crate::service::network_interface::manager::NetworkInterfaceManagerRef;
 
 pub type CanManagerRef = Arc<CanManager>;
 
+const CAN_EFF_FLAG: u32 = 0x8000_0000;
+
+/// The `prio` qdisc supports at most 16 bands, of which the first three are used for the unaffected frames.
+const MAX_FAULT_INJECTION_BANDS: usize = 13;
+
+/// Offsets within `struct can_frame` and `struct canfd_frame`, as seen by `pedit`.
+const CAN_FRAME_LEN_OFFSET: u8 = 4;
+const CAN_FRAME_DATA_OFFSET: u8 = 8;
+
 pub struct CanManager{
     /*
         The cannelloni_termination_token is used to signal the CannelloniManagers, running in separate threads, to terminate. Once it is read as 'true' 
@@ -28,17 +38,52 @@ pub struct CanManager{
      */
     cannelloni_termination_token: Mutex<Arc<AtomicBool>>,
     network_interface_manager: NetworkInterfaceManagerRef,
+    fault_injection: Mutex<FaultInjectionState>,
+    traffic_control: Mutex<TrafficControlState>,
+}
+
+/// Interfaces, on which qdiscs were added with `tc`. Nothing needs to be removed from the other interfaces,
+/// so `tc` is only required when filters, rate limits or faults are configured.
+#[derive(Default)]
+struct TrafficControlState {
+    ingress: HashSet<NetworkInterfaceName>,
+    egress: HashSet<NetworkInterfaceName>,
+}
+
+/// The fault injection rules are received independently of the cluster assignment,
+/// so they are kept here to re-apply them, when the local routing is set up again.
+#[derive(Default)]
+struct FaultInjectionState {
+    assigned_cluster: Option<ClusterId>,
+    device_interfaces: Vec<NetworkInterfaceName>,
+    rules_cluster: Option<ClusterId>,
+    rules: Vec<CanFaultInjectionRule>,
+}
+
+impl FaultInjectionState {
+    fn active_rules(&self) -> Vec<CanFaultInjectionRule> {
+        if self.assigned_cluster.is_some() && self.assigned_cluster == self.rules_cluster {
+            self.rules.iter()
+                .filter(|rule| rule.enabled)
+                .cloned()
+                .collect()
+        } else {
+            Vec::new()
+        }
+    }
 }
 
 impl CanManager {
     pub fn create(network_interface_manager: NetworkInterfaceManagerRef) -> CanManagerRef {
         Arc::new(Self {
             cannelloni_termination_token: Mutex::new(Arc::new(AtomicBool::new(false))),
-            network_interface_manager
+            network_interface_manager,
+            fault_injection: Mutex::new(FaultInjectionState::default()),
+            traffic_control: Mutex::new(TrafficControlState::default()),
         })
     }
 
-    async fn check_can_route_exists(&self, src: &NetworkInterfaceName, dst: &NetworkInterfaceName, can_fd: bool, max_hops: u8) -> Result<bool, Error> {
+    async fn check_can_route_exists(&self, src: &NetworkInterfaceName, dst: &NetworkInterfaceName, can_fd: bool, max_hops: u8, filter: Option<&CanIdFilter>) -> Result<bool, Error> {
         let output = Command::new("cangw")
                 .arg("-L")
                 .output()
@@ -49,20 +94,22 @@ impl CanManager {
         
         let output_str = String::from_utf8_lossy(&output.stdout);
 
-        let re = Regex::new(r"(?m)^cangw -A -s ([^\n ]+) -d ([^\n ]+) ((?:-X )?)-e -l ([0-9[^\n ]]+) #.*$").unwrap();
+        let expected_route = CanRoute {
+            src: src.name(),
+            dst: dst.name(),
+            can_fd,
+            max_hops,
+            filter: filter.map(cangw_filter_values),
+        };
 
-        for (_, [exist_src, exist_dst, can_fd_flag, exist_max_hops]) in re.captures_iter(&output_str).map(|c| c.extract()) {
-            let exist_can_fd = can_fd_flag.trim() == "-X";
-            if exist_src == src.to_string() && exist_dst == dst.to_string() && exist_can_fd == can_fd && exist_max_hops == max_hops.to_string(){
-                return Ok(true)
-            }
-
-        }
+        let exists = output_str.lines()
+            .filter_map(parse_can_route)
+            .any(|route| route == expected_route);
 
-        Ok(false)
+        Ok(exists)
     }
 
-    async fn create_can_route(&self, src: &NetworkInterfaceName, dst: &NetworkInterfaceName, can_fd: bool, max_hops: u8) -> Result<(), Error> {
+    async fn create_can_route(&self, src: &NetworkInterfaceName, dst: &NetworkInterfaceName, can_fd: bool, max_hops: u8, filter: Option<&CanIdFilter>) -> Result<(), Error> {
         let mut cmd = Command::new("cangw");
         cmd.arg("-A")
             .arg("-s")
@@ -77,6 +124,12 @@ impl CanManager {
             cmd.arg("-X");
         } 
 
+        if let Some(filter) = filter {
+            let (id, mask) = cangw_filter_values(filter);
+            cmd.arg("-f")
+                .arg(format!("{id:X}:{mask:X}"));
+        }
+
         let output= cmd.output().await
                 .map_err(|cause| Error::CommandLineProgramExecution { command: "cangw".to_string(), cause })?;
 
@@ -87,13 +140,185 @@ impl CanManager {
                 cause: format!("{:?}", String::from_utf8_lossy(&output.stderr).trim()) });
         }
 
-        if self.check_can_route_exists(src, dst, can_fd, max_hops).await? {
+        if self.check_can_route_exists(src, dst, can_fd, max_hops, filter).await? {
             Ok(())
         } else {
             Err(Error::CanRouteCreationNoCause { src: src.clone(), dst: dst.clone() })
         }
     }
 
+    /// Creates the routes for frames received on the device interface into the bridge.
+    /// Without accept filters, a single unfiltered route is created, otherwise one route per accept filter.
+    async fn create_can_routes_into_bridge(&self, interface: &NetworkInterfaceName, bridge_name: &NetworkInterfaceName, filter: &CanFrameFilter) -> Result<(), Error> {
+        if filter.accept.is_empty() {
+            self.create_can_route(interface, bridge_name, true, 2, None).await?;
+            self.create_can_route(interface, bridge_name, false, 2, None).await?;
+        } else {
+            for accept_filter in &filter.accept {
+                self.create_can_route(interface, bridge_name, true, 2, Some(accept_filter)).await?;
+                self.create_can_route(interface, bridge_name, false, 2, Some(accept_filter)).await?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Drops rejected frames and limits the rate of frames received on the device interface, before they reach the routes into the bridge.
+    /// `cangw` only supports a single filter per route, so these are realized with an ingress qdisc instead.
+    async fn setup_ingress_filters(&self, interface: &NetworkInterfaceName, filter: &CanFrameFilter, rate_limit: &Option<CanRateLimit>) -> Result<(), Error> {
+
+        let nothing_to_apply = filter.reject.is_empty() && rate_limit.is_none();
+        let previously_applied = self.traffic_control.lock().unwrap().ingress.contains(interface);
+
+        if nothing_to_apply && previously_applied.not() {
+            return Ok(());
+        }
+
+        // Deleting the ingress qdisc removes all filters from previous cluster assignments. It fails, if there is none, which is fine.
+        let _ = Command::new("tc")
+            .args(["qdisc", "del", "dev", &interface.name(), "ingress"])
+            .output()
+            .await
+            .map_err(|cause| Error::CommandLineProgramExecution { command: "tc".to_string(), cause })?;
+
+        self.traffic_control.lock().unwrap().ingress.remove(interface);
+
+        if nothing_to_apply {
+            return Ok(());
+        }
+
+        run_tc(interface, &["qdisc", "add", "dev", &interface.name(), "ingress"]).await?;
+        self.traffic_control.lock().unwrap().ingress.insert(Clone::clone(interface));
+
+        if filter.reject.is_empty().not() {
+            let can_ids = filter.reject.iter()
+                .map(tc_can_id)
+                .collect::<Vec<_>>()
+                .join(" ");
+
+            run_tc(interface, &[
+                "filter", "add", "dev", &interface.name(), "ingress", "prio", "1", "protocol", "can",
+                "basic", "match", &format!("canid({can_ids})"),
+                "action", "drop",
+            ]).await?;
+        }
+
+        if let Some(rate_limit) = rate_limit {
+            run_tc(interface, &[
+                "filter", "add", "dev", &interface.name(), "ingress", "prio", "2", "protocol", "can",
+                "matchall",
+                "action", "police", "pkts_rate", &rate_limit.frames_per_second.to_string(), "pkts_burst", &rate_limit.burst.to_string(), "conform-exceed", "drop",
+            ]).await?;
+        }
+
+        debug!("Applied CAN filters to interface '{interface}'.");
+
+        Ok(())
+    }
+
+    /// Injects faults into the frames, which are sent from the bridge to the device interface.
+    /// Frames are modified with `pedit` and then passed on to the next filter. Dropped and delayed frames are
+    /// classified into a band of a `prio` qdisc with its own `netem` qdisc, so only the first of these rules matching a frame applies.
+    async fn setup_egress_faults(&self, interface: &NetworkInterfaceName, rules: &[CanFaultInjectionRule]) -> Result<(), Error> {
+
+        let previously_applied = self.traffic_control.lock().unwrap().egress.contains(interface);
+
+        if rules.is_empty() && previously_applied.not() {
+            return Ok(());
+        }
+
+        // Deleting the root qdisc removes all previously injected faults. It fails, if there is none, which is fine.
+        let _ = Command::new("tc")
+            .args(["qdisc", "del", "dev", &interface.name(), "root"])
+            .output()
+            .await
+            .map_err(|cause| Error::CommandLineProgramExecution { command: "tc".to_string(), cause })?;
+
+        self.traffic_control.lock().unwrap().egress.remove(interface);
+
+        if rules.is_empty() {
+            return Ok(());
+        }
+
+        let (modifying_rules, band_rules): (Vec<&CanFaultInjectionRule>, Vec<&CanFaultInjectionRule>) = rules.iter()
+            .partition(|rule| matches!(rule.fault, CanFault::FlipBits { .. } | CanFault::SetDlc { .. }));
+
+        if band_rules.len() > MAX_FAULT_INJECTION_BANDS {
+            return Err(Error::CanFaultInjectionSetup {
+                interface: interface.clone(),
+                cause: format!("At most {MAX_FAULT_INJECTION_BANDS} rules for dropping or delaying frames are supported, but {} are enabled.", band_rules.len()),
+            });
+        }
+
+        let bands = (3 + band_rules.len()).to_string();
+        run_tc(interface, &[
+            "qdisc", "add", "dev", &interface.name(), "root", "handle", "1:", "prio", "bands", &bands,
+            "priomap", "1", "1", "1", "1", "1", "1", "1", "1", "1", "1", "1", "1", "1", "1", "1", "1",
+        ]).await?;
+        self.traffic_control.lock().unwrap().egress.insert(Clone::clone(interface));
+
+        let mut filter_priority = 0;
+
+        for rule in modifying_rules {
+            filter_priority += 1;
+            let munge = tc_pedit_munge(&rule.fault);
+            let mut args = vec![
+                String::from("filter"), String::from("add"), String::from("dev"), interface.name(), String::from("parent"), String::from("1:"),
+                String::from("prio"), filter_priority.to_string(), String::from("protocol"), String::from("can"),
+                String::from("basic"), String::from("match"), format!("canid({})", tc_can_id(&rule.filter)),
+                String::from("action"), String::from("pedit"), String::from("munge"),
+            ];
+            args.extend(munge);
+            args.push(String::from("continue"));
+
+            run_tc(interface, &args.iter().map(String::as_str).collect::<Vec<_>>()).await?;
+        }
+
+        for (index, rule) in band_rules.into_iter().enumerate() {
+            filter_priority += 1;
+            let class_id = format!("1:{}", 4 + index);
+            let netem = match &rule.fault {
+                CanFault::Drop { percentage } => vec![String::from("loss"), String::from("random"), format!("{percentage}%")],
+                CanFault::Delay { delay } => vec![String::from("delay"), format!("{}ms", delay.as_millis())],
+                CanFault::FlipBits { .. } | CanFault::SetDlc { .. } => unreachable!("Modifying rules are handled above."),
+            };
+
+            let mut args = vec![
+                String::from("qdisc"), String::from("add"), String::from("dev"), interface.name(),
+                String::from("parent"), Clone::clone(&class_id), String::from("handle"), format!("{}:", 10 + index), String::from("netem"),
+            ];
+            args.extend(netem);
+            run_tc(interface, &args.iter().map(String::as_str).collect::<Vec<_>>()).await?;
+
+            run_tc(interface, &[
+                "filter", "add", "dev", &interface.name(), "parent", "1:", "prio", &filter_priority.to_string(), "protocol", "can",
+                "basic", "match", &format!("canid({})", tc_can_id(&rule.filter)),
+                "classid", &class_id,
+            ]).await?;
+        }
+
+        debug!("Injected {} CAN faults into interface '{interface}'.", rules.len());
+
+        Ok(())
+    }
+
+    /// Replaces the fault injection rules of the given cluster. They are applied immediately, if this peer is assigned to that cluster.
+    pub async fn update_fault_injection_rules(&self, cluster_id: ClusterId, rules: Vec<CanFaultInjectionRule>) -> Result<(), Error> {
+        let (device_interfaces, active_rules) = {
+            let mut state = self.fault_injection.lock().unwrap();
+            state.rules_cluster = Some(cluster_id);
+            state.rules = rules;
+            (Clone::clone(&state.device_interfaces), state.active_rules())
+        };
+
+        for interface in &device_interfaces {
+            self.setup_egress_faults(interface, &active_rules).await?;
+        }
+
+        info!("Applied {} active CAN fault injection rules of cluster <{cluster_id}>.", active_rules.len());
+
+        Ok(())
+    }
+
     async fn remove_all_can_routes(&self) -> Result<(), Error> {
         let output = Command::new("cangw")
                     .arg("-F")
@@ -109,6 +334,7 @@ impl CanManager {
 
     pub async fn setup_local_routing(
         &self,
+        cluster_id: ClusterId,
         bridge_name: &NetworkInterfaceName,
         local_can_interfaces: Vec<NetworkInterfaceDescriptor>,
     ) -> Result<(), Error> {
@@ -118,12 +344,35 @@ impl CanManager {
             .map_err(|cause| Error::Other { message: format!("Error while creating CAN bridge: {cause}") })?;
     
         self.remove_all_can_routes().await?;
+
+        let (previous_interfaces, active_rules) = {
+            let mut state = self.fault_injection.lock().unwrap();
+            state.assigned_cluster = Some(cluster_id);
+            let interfaces = local_can_interfaces.iter()
+                .map(|interface| Clone::clone(&interface.name))
+                .collect();
+            (std::mem::replace(&mut state.device_interfaces, interfaces), state.active_rules())
+        };
+
+        for interface in previous_interfaces {
+            if local_can_interfaces.iter().any(|local| local.name == interface).not() {
+                let _ = self.setup_egress_faults(&interface, &[]).await
+                    .inspect_err(|cause| warn!("Failed to remove CAN faults from interface '{interface}', which is no longer part of the cluster: {cause}"));
+            }
+        }
     
         for interface in local_can_interfaces {
-            self.create_can_route(bridge_name, &interface.name, true, 2).await?;
-            self.create_can_route(bridge_name, &interface.name, false, 2).await?;
-            self.create_can_route(&interface.name, bridge_name, true, 2).await?;
-            self.create_can_route(&interface.name, bridge_name, false, 2).await?;
+            let (filter, rate_limit) = match interface.configuration {
+                NetworkInterfaceConfiguration::Can { filter, rate_limit, .. } => (filter, rate_limit),
+                NetworkInterfaceConfiguration::Ethernet => (CanFrameFilter::default(), None),
+            };
+
+            self.setup_ingress_filters(&interface.name, &filter, &rate_limit).await?;
+            self.setup_egress_faults(&interface.name, &active_rules).await?;
+
+            self.create_can_route(bridge_name, &interface.name, true, 2, None).await?;
+            self.create_can_route(bridge_name, &interface.name, false, 2, None).await?;
+            self.create_can_routes_into_bridge(&interface.name, bridge_name, &filter).await?;
         }
     
         Ok(())
@@ -202,6 +451,96 @@ impl CanManager {
 
 }
 
+async fn run_tc(interface: &NetworkInterfaceName, args: &[&str]) -> Result<(), Error> {
+    let output = Command::new("tc")
+        .args(args)
+        .output()
+        .await
+        .map_err(|cause| Error::CommandLineProgramExecution { command: "tc".to_string(), cause })?;
+
+    if ! output.status.success() {
+        return Err(Error::CanFilterSetup {
+            interface: interface.clone(),
+            cause: format!("{:?}", String::from_utf8_lossy(&output.stderr).trim()) });
+    }
+    Ok(())
+}
+
+/// A CAN ID filter as expected by the `canid` ematch of `tc`.
+fn tc_can_id(filter: &CanIdFilter) -> String {
+    let frame_format = if filter.is_extended() { "eff" } else { "sff" };
+    format!("{frame_format} {:#X}:{:#X}", filter.id(), filter.mask())
+}
+
+/// The arguments of `pedit munge` for a fault, which modifies frames.
+fn tc_pedit_munge(fault: &CanFault) -> Vec<String> {
+    match fault {
+        CanFault::FlipBits { byte, mask } => vec![
+            String::from("offset"), (CAN_FRAME_DATA_OFFSET + byte).to_string(), String::from("u8"),
+            String::from("invert"), String::from("retain"), format!("{mask:#04X}"),
+        ],
+        CanFault::SetDlc { dlc } => vec![
+            String::from("offset"), CAN_FRAME_LEN_OFFSET.to_string(), String::from("u8"),
+            String::from("set"), dlc.to_string(),
+        ],
+        CanFault::Drop { .. } | CanFault::Delay { .. } => Vec::new(),
+    }
+}
+
+/// The ID and mask as expected by `cangw`. The EFF flag is always part of the mask, so that a filter only matches its own frame format.
+fn cangw_filter_values(filter: &CanIdFilter) -> (u32, u32) {
+    if filter.is_extended() {
+        (filter.id() | CAN_EFF_FLAG, filter.mask() | CAN_EFF_FLAG)
+    } else {
+        (filter.id(), filter.mask() | CAN_EFF_FLAG)
+    }
+}
+
+#[derive(Debug, PartialEq)]
+struct CanRoute {
+    src: String,
+    dst: String,
+    can_fd: bool,
+    max_hops: u8,
+    filter: Option<(u32, u32)>,
+}
+
+/// Parses a route from the output of `cangw -L`, e.g. `cangw -A -s can0 -d br-vcan-opendut -X -e -f 123:800007FF -l 2 # 0 handled 0 dropped 0 deleted`.
+fn parse_can_route(line: &str) -> Option<CanRoute> {
+    let options = line.split('#').next()?
+        .strip_prefix("cangw -A")?;
+
+    let mut src = None;
+    let mut dst = None;
+    let mut can_fd = false;
+    let mut max_hops = None;
+    let mut filter = None;
+
+    let mut tokens = options.split_whitespace();
+    while let Some(token) = tokens.next() {
+        match token {
+            "-s" => src = Some(tokens.next()?.to_owned()),
+            "-d" => dst = Some(tokens.next()?.to_owned()),
+            "-X" => can_fd = true,
+            "-l" => max_hops = Some(tokens.next()?.parse().ok()?),
+            "-f" => {
+                // Inverted filters ('~') are never created by EDGAR, so they are not parsed.
+                let (id, mask) = tokens.next()?.split_once(':')?;
+                filter = Some((u32::from_str_radix(id, 16).ok()?, u32::from_str_radix(mask, 16).ok()?));
+            }
+            _ => {}
+        }
+    }
+
+    Some(CanRoute {
+        src: src?,
+        dst: dst?,
+        can_fd,
+        max_hops: max_hops?,
+        filter,
+    })
+}
+
 #[derive(Debug, thiserror::Error)]
 pub enum Error {
     #[error("Failure while invoking command line program '{command}': {cause}")]
@@ -212,6 +551,55 @@ pub enum Error {
     CanRouteCreationNoCause { src: NetworkInterfaceName, dst: NetworkInterfaceName},
     #[error("Failure while flushing existing CAN routes: {cause}")]
     CanRouteFlushing { cause: String },
+    #[error("Failure while setting up CAN filters for interface '{interface}': {cause}")]
+    CanFilterSetup { interface: NetworkInterfaceName, cause: String },
+    #[error("Failure while injecting CAN faults into interface '{interface}': {cause}")]
+    CanFaultInjectionSetup { interface: NetworkInterfaceName, cause: String },
     #[error("{message}")]
     Other { message: String },
 }
+
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_can_routes_listed_by_cangw() {
+        let route = parse_can_route("cangw -A -s can0 -d br-vcan-opendut -X -e -l 2 # 0 handled 0 dropped 0 deleted");
+        assert_that!(route, some(eq(CanRoute {
+            src: String::from("can0"),
+            dst: String::from("br-vcan-opendut"),
+            can_fd: true,
+            max_hops: 2,
+            filter: None,
+        })));
+
+        let route = parse_can_route("cangw -A -s can0 -d br-vcan-opendut -e -f 123:800007FF -l 2 # 12 handled 0 dropped 0 deleted");
+        assert_that!(route, some(eq(CanRoute {
+            src: String::from("can0"),
+            dst: String::from("br-vcan-opendut"),
+            can_fd: false,
+            max_hops: 2,
+            filter: Some((0x123, 0x8000_07FF)),
+        })));
+
+        assert_that!(parse_can_route("Routing information is not available."), none());
+    }
+
+    #[test]
+    fn should_determine_pedit_arguments_for_modifying_faults() {
+        assert_that!(tc_pedit_munge(&CanFault::FlipBits { byte: 2, mask: 0x81 }), elements_are![eq("offset"), eq("10"), eq("u8"), eq("invert"), eq("retain"), eq("0x81")]);
+        assert_that!(tc_pedit_munge(&CanFault::SetDlc { dlc: 3 }), elements_are![eq("offset"), eq("4"), eq("u8"), eq("set"), eq("3")]);
+        assert!(tc_pedit_munge(&CanFault::Drop { percentage: 10 }).is_empty());
+    }
+
+    #[test]
+    fn should_restrict_cangw_filters_to_their_frame_format() -> anyhow::Result<()> {
+        assert_that!(cangw_filter_values(&CanIdFilter::new(0x123, 0x7F0, false)?), eq((0x123, 0x8000_07F0)));
+        assert_that!(cangw_filter_values(&CanIdFilter::new(0x123, 0x1FFF_FFFF, true)?), eq((0x8000_0123, 0x9FFF_FFFF)));
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+use std::fs;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context};
+use tracing::info;
+
+use opendut_carl_api::proto::services::peer_messaging_broker::RotateClientSecret;
+use opendut_types::util::net::{ClientId, ClientSecret};
+
+/// Stores the client secret, which CARL rotated, in the configuration file.
+/// EDGAR has to be restarted afterwards to authenticate with it.
+pub fn persist_rotated_client_secret(message: RotateClientSecret, config_file: &Path) -> anyhow::Result<ClientId> {
+    let RotateClientSecret { client_id, client_secret } = message;
+
+    let client_id = ClientId::try_from(client_id.ok_or_else(|| anyhow!("CARL sent rotated client secret without client ID."))?)?;
+    let client_secret = ClientSecret::try_from(client_secret.ok_or_else(|| anyhow!("CARL sent rotated client secret without secret."))?)?
+        .value();
+
+    let content = fs::read_to_string(config_file)
+        .context(format!("Failed to read configuration file at '{}'.", config_file.display()))?;
+    let mut settings = toml_edit::DocumentMut::from_str(&content)
+        .context("Failed to parse configuration as TOML.")?;
+
+    let configured_client_id = settings.get("network")
+        .and_then(|network| network.get("oidc"))
+        .and_then(|oidc| oidc.get("client"))
+        .and_then(|client| client.get("id"))
+        .and_then(|id| id.as_str());
+
+    if configured_client_id != Some(client_id.0.as_str()) {
+        return Err(anyhow!("Rotated client secret is meant for client '{}', which is not configured in '{}'.", client_id.0, config_file.display()));
+    }
+
+    settings["network"]["oidc"]["client"]["secret"] = toml_edit::value(client_secret);
+
+    fs::write(config_file, settings.to_string())
+        .context(format!("Failed to write configuration file at '{}'.", config_file.display()))?;
+
+    info!("Stored rotated client secret of client '{}' in '{}'.", client_id.0, config_file.display());
+    Ok(client_id)
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+
+    use super::*;
+
+    #[test]
+    fn should_replace_the_client_secret_in_the_configuration_file() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let config_file = temp.child("edgar.toml");
+        config_file.write_str(r#"
+[network.oidc.client]
+id = "edgar-123"
+secret = "old-secret"
+"#)?;
+
+        let client_id = persist_rotated_client_secret(RotateClientSecret {
+            client_id: Some(ClientId::from("edgar-123").into()),
+            client_secret: Some(ClientSecret::from("new-secret-with-sufficient-length").into()),
+        }, config_file.path())?;
+        assert_eq!(client_id, ClientId::from("edgar-123"));
+
+        let content = fs::read_to_string(config_file.path())?;
+        assert!(content.contains(r#"secret = "new-secret-with-sufficient-length""#));
+        assert!(content.contains(r#"id = "edgar-123""#));
+
+        let result = persist_rotated_client_secret(RotateClientSecret {
+            client_id: Some(ClientId::from("edgar-456").into()),
+            client_secret: Some(ClientSecret::from("other-secret-with-sufficient-length").into()),
+        }, config_file.path());
+        assert!(result.is_err());
+
+        Ok(())
+    }
+}
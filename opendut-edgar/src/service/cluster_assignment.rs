@@ -10,14 +10,16 @@ use crate::service::network_interface;
 use crate::service::network_interface::{bridge, gre};
 use crate::service::network_interface::manager::NetworkInterfaceManagerRef;
 use crate::service::can_manager::CanManagerRef;
+use crate::service::can_capture::CanCaptureManagerRef;
 
-#[tracing::instrument(skip(cluster_assignment, can_manager, network_interface_manager), level="trace")]
+#[tracing::instrument(skip(cluster_assignment, can_manager, can_capture_manager, network_interface_manager), level="trace")]
 pub async fn network_interfaces_setup(
     cluster_assignment: &ClusterAssignment,
     self_id: PeerId,
     bridge_name: &NetworkInterfaceName,
     network_interface_manager: NetworkInterfaceManagerRef,
     can_manager: CanManagerRef,
+    can_capture_manager: CanCaptureManagerRef,
 ) -> Result<(), Error> {
 
     bridge::recreate(bridge_name, Arc::clone(&network_interface_manager)).await
@@ -48,7 +50,7 @@ pub async fn network_interfaces_setup(
     join_device_interfaces_to_bridge(&own_ethernet_interfaces, bridge_name, Arc::clone(&network_interface_manager)).await
         .map_err(Error::JoinDeviceInterfaceToBridgeFailed)?;
 
-    setup_can(cluster_assignment, self_id, can_manager).await?;
+    setup_can(cluster_assignment, self_id, can_manager, can_capture_manager).await?;
 
     Ok(())
 }
@@ -56,12 +58,17 @@ pub async fn network_interfaces_setup(
 pub async fn setup_can(
     cluster_assignment: &ClusterAssignment,
     self_id: PeerId,
-    can_manager: CanManagerRef
+    can_manager: CanManagerRef,
+    can_capture_manager: CanCaptureManagerRef,
 ) -> Result<(), Error> {
 
     let can_bridge_name = crate::common::default_can_bridge_name();
     let own_can_interfaces = get_own_can_interfaces(cluster_assignment, self_id)?;
+    let own_can_interface_names = own_can_interfaces.iter()
+        .map(|interface| Clone::clone(&interface.name))
+        .collect::<Vec<_>>();
     can_manager.setup_local_routing(
+        cluster_assignment.id,
         &can_bridge_name, 
         own_can_interfaces,
     ).await
@@ -95,6 +102,15 @@ pub async fn setup_can(
         .map_err(Error::RemoteCanRoutingSetupFailed)?;
     }
 
+    can_capture_manager.start_capture(
+        cluster_assignment.id,
+        self_id,
+        &cluster_assignment.can_capture,
+        &can_bridge_name,
+        &own_can_interface_names,
+    ).await
+    .map_err(Error::CanCaptureSetupFailed)?;
+
     Ok(())
 }
 
@@ -199,6 +215,8 @@ pub enum Error {
     LocalCanRoutingSetupFailed(crate::service::can_manager::Error),
     #[error("Remote CAN routing setup failed: {0}")]
     RemoteCanRoutingSetupFailed(crate::service::can_manager::Error),
+    #[error("CAN capture setup failed: {0}")]
+    CanCaptureSetupFailed(crate::service::can_capture::Error),
     #[error("Joining device interface to bridge failed: {0}")]
     JoinDeviceInterfaceToBridgeFailed(network_interface::manager::Error),
 }
@@ -1,9 +1,11 @@
 pub mod start;
 pub mod network_interface;
 
+mod client_secret;
 mod cluster_assignment;
 mod cannelloni_manager;
 mod can_manager;
+mod can_capture;
 mod vpn;
 mod test_execution;
 mod network_metrics;
@@ -1,6 +1,7 @@
 use std::any::Any;
 use std::fmt::Debug;
 use std::ops::Not;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::Duration;
 
@@ -15,11 +16,12 @@ use tracing::{debug, error, info, Span, trace, warn};
 use tracing_opentelemetry::OpenTelemetrySpanExt;
 
 use opendut_carl_api::proto::services::peer_messaging_broker;
-use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyPeerConfiguration, TracingContext};
+use opendut_carl_api::proto::services::peer_messaging_broker::{ApplyCanFaultInjectionRules, ApplyPeerConfiguration, ClientSecretStored, ExecuteExecutorCommand, RotateClientSecret, TracingContext};
 use opendut_carl_api::proto::services::peer_messaging_broker::downstream::Message;
-use opendut_types::cluster::{ClusterAssignment, PeerClusterAssignment};
+use opendut_types::cluster::{CanFaultInjectionRule, ClusterAssignment, ClusterId, PeerClusterAssignment};
 use opendut_types::peer::configuration::{PeerConfiguration, PeerConfiguration2};
 use opendut_types::peer::PeerId;
+use opendut_types::peer::executor::ExecutorCommand;
 use opendut_types::util::net::NetworkInterfaceName;
 use opendut_util::telemetry;
 use opendut_util::telemetry::logging::LoggingConfig;
@@ -28,9 +30,12 @@ use opendut_util::project;
 use opendut_util::settings::LoadedConfig;
 
 use crate::common::{carl, settings};
+use crate::service::test_execution::container_engine::ContainerEngineSockets;
 use crate::service::test_execution::executor_manager::{ExecutorManager, ExecutorManagerRef};
-use crate::service::{cluster_assignment, vpn};
+use crate::service::test_execution::result_sink::{ResultSinks, ResultSinksRef};
+use crate::service::{client_secret, cluster_assignment, vpn};
 use crate::service::can_manager::{CanManager, CanManagerRef};
+use crate::service::can_capture::{CanCaptureManager, CanCaptureManagerRef};
 use crate::service::cluster_assignment::Error;
 use crate::service::network_metrics;
 use crate::service::network_interface::manager::{NetworkInterfaceManager, NetworkInterfaceManagerRef};
@@ -90,7 +95,9 @@ pub async fn create(self_id: PeerId, settings: LoadedConfig) -> anyhow::Result<(
 
     let network_interface_manager: NetworkInterfaceManagerRef = NetworkInterfaceManager::create()?;
     let can_manager: CanManagerRef = CanManager::create(Arc::clone(&network_interface_manager));
-    let executor_manager: ExecutorManagerRef = ExecutorManager::create();
+    let result_sinks: ResultSinksRef = ResultSinks::load(&settings.config).await?;
+    let can_capture_manager: CanCaptureManagerRef = CanCaptureManager::create(PathBuf::from(settings.config.get::<String>("can.capture.directory")?), Arc::clone(&result_sinks));
+    let executor_manager: ExecutorManagerRef = ExecutorManager::create(self_id, ContainerEngineSockets::load(&settings.config)?, result_sinks);
 
     let network_interface_management_enabled = settings.config.get::<bool>("network.interface.management.enabled")?;
 
@@ -105,6 +112,7 @@ pub async fn create(self_id: PeerId, settings: LoadedConfig) -> anyhow::Result<(
         network_interface_management_enabled,
         network_interface_manager,
         can_manager,
+        can_capture_manager,
         executor_manager,
         ping_interval,
         target_bandwidth_kbit_per_second,
@@ -130,7 +138,7 @@ pub async fn create(self_id: PeerId, settings: LoadedConfig) -> anyhow::Result<(
                         message,
                         &setup_cluster_info,
                         &tx_outbound,
-                    ).await?
+                    ).await?;
                 }
                 Err(status) => {
                     warn!("CARL sent a gRPC error status: {status}");
@@ -197,7 +205,10 @@ async fn handle_stream_message(
                     tx_outbound.send(message).await
                         .inspect_err(|cause| debug!("Failed to send ping to CARL: {cause}"));
             }
-            Message::ApplyPeerConfiguration(message) => { apply_peer_configuration(message, context, setup_cluster_info).await? }
+            Message::ApplyPeerConfiguration(message) => { apply_peer_configuration(message, context, setup_cluster_info, tx_outbound).await? }
+            Message::ApplyCanFaultInjectionRules(message) => { apply_can_fault_injection_rules(message, context, setup_cluster_info).await }
+            Message::ExecuteExecutorCommand(message) => { execute_executor_command(message, context, setup_cluster_info, tx_outbound) }
+            Message::RotateClientSecret(message) => { rotate_client_secret(message, tx_outbound).await? }
         }
     } else {
         ignore(message)
@@ -207,7 +218,7 @@ async fn handle_stream_message(
 }
 
 #[tracing::instrument(skip_all, level="trace")]
-async fn apply_peer_configuration(message: ApplyPeerConfiguration, context: Option<TracingContext>, setup_cluster_info: &SetupClusterInfo) -> anyhow::Result<()> {
+async fn apply_peer_configuration(message: ApplyPeerConfiguration, context: Option<TracingContext>, setup_cluster_info: &SetupClusterInfo, tx_outbound: &Sender<peer_messaging_broker::Upstream>) -> anyhow::Result<()> {
 
     match message.clone() {
         ApplyPeerConfiguration {
@@ -226,6 +237,9 @@ async fn apply_peer_configuration(message: ApplyPeerConfiguration, context: Opti
                     match PeerConfiguration2::try_from(configuration2) {
                         Err(error) => error!("Illegal PeerConfiguration2: {error}"),
                         Ok(configuration2) => {
+                            let cluster_bridge = configuration.cluster_assignment.is_some()
+                                .then(|| Clone::clone(&configuration.network.bridge_name));
+
                             let _ = setup_cluster(
                                 &configuration.cluster_assignment,
                                 setup_cluster_info,
@@ -234,7 +248,7 @@ async fn apply_peer_configuration(message: ApplyPeerConfiguration, context: Opti
 
                             let mut executor_manager = setup_cluster_info.executor_manager.lock().unwrap();
                             executor_manager.terminate_executors();
-                            executor_manager.create_new_executors(configuration2.executors);
+                            executor_manager.create_new_executors(configuration2.executors, configuration2.can_databases, configuration2.registry_credentials, configuration2.secrets, cluster_bridge, tx_outbound);
 
                             setup_cluster_metrics(
                                 &configuration.cluster_assignment,
@@ -250,11 +264,93 @@ async fn apply_peer_configuration(message: ApplyPeerConfiguration, context: Opti
     Ok(())
 }
 
+#[tracing::instrument(skip_all, level="trace")]
+async fn apply_can_fault_injection_rules(message: ApplyCanFaultInjectionRules, context: Option<TracingContext>, setup_cluster_info: &SetupClusterInfo) {
+
+    let span = Span::current();
+    set_parent_context(&span, context);
+    let _span = span.enter();
+
+    let ApplyCanFaultInjectionRules { cluster_id, rules } = message;
+
+    let cluster_id = match cluster_id.map(ClusterId::try_from) {
+        Some(Ok(cluster_id)) => cluster_id,
+        Some(Err(error)) => {
+            error!("Illegal ClusterId in CAN fault injection rules: {error}");
+            return;
+        }
+        None => {
+            error!("Received CAN fault injection rules without ClusterId.");
+            return;
+        }
+    };
+
+    let rules = match rules.into_iter().map(CanFaultInjectionRule::try_from).collect::<Result<Vec<_>, _>>() {
+        Ok(rules) => rules,
+        Err(error) => {
+            error!("Illegal CanFaultInjectionRule: {error}");
+            return;
+        }
+    };
+
+    info!("Received {} CAN fault injection rules for cluster <{cluster_id}>.", rules.len());
+
+    if setup_cluster_info.network_interface_management_enabled {
+        let _ = setup_cluster_info.can_manager.update_fault_injection_rules(cluster_id, rules).await
+            .inspect_err(|error| error!("Failed to apply CAN fault injection rules: {error}"));
+    } else {
+        debug!("Skipping CAN fault injection, as changes to network interfaces are disabled via configuration.");
+    }
+}
+
+#[tracing::instrument(skip_all, level="trace")]
+fn execute_executor_command(message: ExecuteExecutorCommand, context: Option<TracingContext>, setup_cluster_info: &SetupClusterInfo, tx_outbound: &Sender<peer_messaging_broker::Upstream>) {
+
+    let span = Span::current();
+    set_parent_context(&span, context);
+    let _span = span.enter();
+
+    let ExecuteExecutorCommand { executor_name, command } = message;
+
+    let command = match command.map(ExecutorCommand::try_from) {
+        Some(Ok(command)) => command,
+        Some(Err(error)) => {
+            error!("Illegal ExecutorCommand for executor '{executor_name}': {error}");
+            return;
+        }
+        None => {
+            error!("Received command for executor '{executor_name}' without ExecutorCommand.");
+            return;
+        }
+    };
+
+    let mut executor_manager = setup_cluster_info.executor_manager.lock().unwrap();
+    let _ = executor_manager.execute_command(&executor_name, command, tx_outbound)
+        .inspect_err(|error| error!("Failed to execute command '{command}': {error}"));
+}
+
+/// Stores the rotated client secret and confirms it to CARL, which then activates it and disconnects EDGAR.
+/// EDGAR is restarted afterwards and authenticates with the new secret.
+async fn rotate_client_secret(message: RotateClientSecret, tx_outbound: &Sender<peer_messaging_broker::Upstream>) -> anyhow::Result<()> {
+    let client_id = client_secret::persist_rotated_client_secret(message, &settings::default_config_file_path())?;
+
+    let message = peer_messaging_broker::Upstream {
+        message: Some(peer_messaging_broker::upstream::Message::ClientSecretStored(ClientSecretStored {
+            client_id: Some(client_id.into()),
+        })),
+        context: None,
+    };
+    tx_outbound.send(message).await
+        .context("Failed to confirm the stored client secret to CARL.")?;
+    Ok(())
+}
+
 struct SetupClusterInfo {
     self_id: PeerId,
     network_interface_management_enabled: bool,
     network_interface_manager: NetworkInterfaceManagerRef,
     can_manager: CanManagerRef,
+    can_capture_manager: CanCaptureManagerRef,
     executor_manager: ExecutorManagerRef,
     ping_interval: Duration,
     target_bandwidth_kbit_per_second: u64,
@@ -278,7 +374,8 @@ async fn setup_cluster(
                     info.self_id,
                     &bridge_name,
                     Arc::clone(&info.network_interface_manager),
-                    Arc::clone(&info.can_manager)
+                    Arc::clone(&info.can_manager),
+                    Arc::clone(&info.can_capture_manager),
                 ).await
                     .inspect_err(|error| {
                         error!("Failed to configure network interfaces: {error}")
@@ -289,6 +386,7 @@ async fn setup_cluster(
         }
         None => {
             debug!("No ClusterAssignment in peer configuration.");
+            info.can_capture_manager.stop_capture();
             //TODO teardown cluster, if configuration changed
         }
     }
@@ -0,0 +1,545 @@
+use std::collections::{HashMap, VecDeque};
+use std::path::PathBuf;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use base64::Engine;
+use base64::prelude::BASE64_URL_SAFE;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use serde::Deserialize;
+use serde_json::json;
+
+use opendut_types::peer::executor::container::ContainerRegistryCredential;
+use opendut_types::peer::executor::run::ExecutorLogStream;
+
+use crate::service::test_execution::container_engine::{ContainerEngine, ContainerId, ContainerLogLine, ContainerSpec, ContainerState, ContainerStatus, Error, ImageInfo};
+use crate::service::test_execution::container_engine::http::UnixSocketClient;
+
+/// Talks to the Docker Engine API, which Podman provides as well, on the given Unix socket.
+pub struct ApiContainerEngine {
+    client: UnixSocketClient,
+}
+
+impl ApiContainerEngine {
+    pub fn new(socket: impl Into<PathBuf>) -> Self {
+        Self { client: UnixSocketClient::new(socket.into()) }
+    }
+}
+
+#[derive(Deserialize)]
+struct CreateContainerResponse {
+    #[serde(rename = "Id")]
+    id: String,
+}
+
+#[derive(Deserialize)]
+struct InspectContainerResponse {
+    #[serde(rename = "State")]
+    state: InspectContainerState,
+}
+
+#[derive(Deserialize)]
+struct InspectContainerState {
+    #[serde(rename = "Status")]
+    status: String,
+    #[serde(rename = "ExitCode")]
+    exit_code: Option<i32>,
+    #[serde(rename = "OOMKilled", default)]
+    oom_killed: bool,
+}
+
+#[derive(Deserialize)]
+struct InspectImageResponse {
+    #[serde(rename = "Id")]
+    id: String,
+    #[serde(rename = "RepoDigests", default)]
+    repo_digests: Option<Vec<String>>,
+}
+
+#[async_trait]
+impl ContainerEngine for ApiContainerEngine {
+    async fn create_container(&self, name: &str, spec: &ContainerSpec) -> Result<Option<ContainerId>, Error> {
+        let body = create_container_body(spec);
+        let response = self.client.request("POST", &format!("/containers/create?name={}", encode(name)), Some(&body)).await?;
+
+        match response.status {
+            200 | 201 => {
+                let response = response.json::<CreateContainerResponse>().await?;
+                Ok(Some(ContainerId(response.id)))
+            }
+            404 => Err(Error::ImageNotFound { image: Clone::clone(&spec.image) }),
+            409 => Ok(None),
+            _ => Err(response.into_error("create container").await),
+        }
+    }
+
+    async fn start_container(&self, id: &ContainerId) -> Result<(), Error> {
+        let response = self.client.request("POST", &format!("/containers/{id}/start"), None).await?;
+
+        match response.status {
+            204 | 304 => Ok(()),
+            _ => Err(response.into_error("start container").await),
+        }
+    }
+
+    async fn inspect_container(&self, id: &ContainerId) -> Result<ContainerStatus, Error> {
+        let response = self.client.request("GET", &format!("/containers/{id}/json"), None).await?;
+
+        if !response.is_success() {
+            return Err(response.into_error("inspect container").await);
+        }
+        let InspectContainerState { status, exit_code, oom_killed } = response.json::<InspectContainerResponse>().await?.state;
+
+        Ok(ContainerStatus {
+            state: ContainerState::try_from(status.as_str())?,
+            exit_code,
+            oom_killed,
+        })
+    }
+
+    async fn stop_container(&self, id: &ContainerId, timeout: Duration) -> Result<(), Error> {
+        let response = self.client.request("POST", &format!("/containers/{id}/stop?t={}", timeout.as_secs()), None).await?;
+
+        match response.status {
+            204 | 304 => Ok(()),
+            _ => Err(response.into_error("stop container").await),
+        }
+    }
+
+    async fn inspect_image(&self, image: &str) -> Result<Option<ImageInfo>, Error> {
+        let response = self.client.request("GET", &format!("/images/{image}/json"), None).await?;
+
+        match response.status {
+            200 => {
+                let InspectImageResponse { id, repo_digests } = response.json::<InspectImageResponse>().await?;
+                Ok(Some(ImageInfo { id, repo_digests: repo_digests.unwrap_or_default() }))
+            }
+            404 => Ok(None),
+            _ => Err(response.into_error("inspect image").await),
+        }
+    }
+
+    async fn pull_image(&self, image: &str, credential: Option<&ContainerRegistryCredential>) -> Result<(), Error> {
+        let path_and_query = format!("/images/create?fromImage={}", encode(&with_default_tag(image)));
+        let response = match credential {
+            Some(credential) => {
+                let auth = registry_auth(credential);
+                self.client.request_with_headers("POST", &path_and_query, &[("X-Registry-Auth", auth.as_str())], None).await?
+            }
+            None => self.client.request("POST", &path_and_query, None).await?,
+        };
+
+        if !response.is_success() {
+            return Err(response.into_error("pull image").await);
+        }
+        let status = response.status;
+
+        // The progress is reported as a sequence of JSON objects. Failures during the pull are reported in there, too.
+        let progress = response.bytes().await?;
+        let failure = serde_json::Deserializer::from_slice(&progress)
+            .into_iter::<serde_json::Value>()
+            .filter_map(Result::ok)
+            .find_map(|progress| progress.get("error").and_then(|error| error.as_str()).map(ToOwned::to_owned));
+
+        match failure {
+            None => Ok(()),
+            Some(message) => Err(Error::Api { request: String::from("pull image"), status, message }),
+        }
+    }
+
+    async fn ensure_bridge_network(&self, name: &str, bridge: &str) -> Result<(), Error> {
+        let response = self.client.request("GET", &format!("/networks/{}", encode(name)), None).await?;
+        match response.status {
+            200 => return Ok(()),
+            404 => (),
+            _ => return Err(response.into_error("inspect network").await),
+        }
+
+        let body = json!({
+            "Name": name,
+            "Driver": "bridge",
+            "Options": {
+                "com.docker.network.bridge.name": bridge,
+            },
+        });
+        let response = self.client.request("POST", "/networks/create", Some(&body)).await?;
+
+        match response.status {
+            // 409: The network was created concurrently, e.g. by another executor.
+            200 | 201 | 409 => Ok(()),
+            _ => Err(response.into_error("create network").await),
+        }
+    }
+
+    async fn container_logs(&self, id: &ContainerId) -> Result<BoxStream<'static, Result<ContainerLogLine, Error>>, Error> {
+        let response = self.client.request("GET", &format!("/containers/{id}/logs?follow=1&stdout=1&stderr=1&timestamps=1"), None).await?;
+
+        if !response.is_success() {
+            return Err(response.into_error("follow container logs").await);
+        }
+
+        let lines = futures::stream::unfold(Some((response, LogFrameDecoder::default(), VecDeque::new())), |state| async move {
+            let (mut response, mut decoder, mut pending) = state?;
+            loop {
+                if let Some(line) = pending.pop_front() {
+                    return Some((Ok(line), Some((response, decoder, pending))));
+                }
+                match response.chunk().await {
+                    Ok(Some(data)) => pending.extend(decoder.push(&data)),
+                    Ok(None) => {
+                        pending.extend(decoder.finish());
+                        if pending.is_empty() {
+                            return None;
+                        }
+                    }
+                    Err(cause) => return Some((Err(cause), None)),
+                }
+            }
+        });
+
+        Ok(lines.boxed())
+    }
+}
+
+fn create_container_body(spec: &ContainerSpec) -> serde_json::Value {
+    let mounts = spec.mounts.iter()
+        .map(|mount| json!({
+            "Type": "bind",
+            "Source": mount.source.to_string_lossy(),
+            "Target": mount.target.to_string_lossy(),
+            "ReadOnly": mount.read_only,
+        }))
+        .collect::<Vec<_>>();
+
+    let devices = spec.devices.iter()
+        .map(|device| json!({
+            "PathOnHost": device.path_on_host,
+            "PathInContainer": device.path_in_container,
+            "CgroupPermissions": device.cgroup_permissions,
+        }))
+        .collect::<Vec<_>>();
+
+    let mut exposed_ports = HashMap::new();
+    let mut port_bindings = HashMap::<&str, Vec<serde_json::Value>>::new();
+    for port in &spec.ports {
+        exposed_ports.insert(port.container_port.as_str(), json!({}));
+        port_bindings.entry(port.container_port.as_str()).or_default().push(json!({
+            "HostIp": port.host_ip.as_deref().unwrap_or_default(),
+            "HostPort": port.host_port.as_deref().unwrap_or_default(),
+        }));
+    }
+
+    let command = (!spec.command.is_empty()).then_some(&spec.command);
+
+    let tmpfs = if spec.read_only_rootfs {
+        HashMap::from([("/tmp", "")])
+    } else {
+        HashMap::new()
+    };
+
+    json!({
+        "Image": spec.image,
+        "Cmd": command,
+        "Env": spec.envs,
+        "ExposedPorts": exposed_ports,
+        "HostConfig": {
+            "NetworkMode": spec.network.mode(),
+            "Binds": spec.binds,
+            "Mounts": mounts,
+            "Devices": devices,
+            "PortBindings": port_bindings,
+            "NanoCpus": spec.nano_cpus.unwrap_or_default(),
+            "Memory": spec.memory_bytes.unwrap_or_default(),
+            "PidsLimit": spec.pids_limit,
+            "CapAdd": spec.cap_add,
+            "CapDrop": spec.cap_drop,
+            "ReadonlyRootfs": spec.read_only_rootfs,
+            "Tmpfs": tmpfs,
+        },
+    })
+}
+
+/// Encodes the credential as expected in the `X-Registry-Auth` header.
+fn registry_auth(credential: &ContainerRegistryCredential) -> String {
+    let auth = json!({
+        "username": credential.username,
+        "password": credential.password,
+        "serveraddress": credential.server,
+    });
+    BASE64_URL_SAFE.encode(auth.to_string())
+}
+
+fn encode(value: &str) -> String {
+    url::form_urlencoded::byte_serialize(value.as_bytes()).collect()
+}
+
+/// Without a tag, the engine would pull all tags of the image.
+fn with_default_tag(image: &str) -> String {
+    let last_component = image.rsplit('/').next().unwrap_or(image);
+    if image.contains('@') || last_component.contains(':') {
+        image.to_owned()
+    } else {
+        format!("{image}:latest")
+    }
+}
+
+/// Decodes the log stream of a container without TTY, in which each frame is prefixed with an 8 byte header
+/// containing the stream type (1 = stdout, 2 = stderr) and the big-endian length of the payload.
+#[derive(Default)]
+struct LogFrameDecoder {
+    buffer: Vec<u8>,
+    partial_stdout: Vec<u8>,
+    partial_stderr: Vec<u8>,
+}
+
+const LOG_FRAME_HEADER_SIZE: usize = 8;
+
+impl LogFrameDecoder {
+    fn push(&mut self, data: &[u8]) -> Vec<ContainerLogLine> {
+        self.buffer.extend_from_slice(data);
+
+        let mut lines = Vec::new();
+        while self.buffer.len() >= LOG_FRAME_HEADER_SIZE {
+            let length = u32::from_be_bytes([self.buffer[4], self.buffer[5], self.buffer[6], self.buffer[7]]) as usize;
+            if self.buffer.len() < LOG_FRAME_HEADER_SIZE + length {
+                break;
+            }
+            let stream = match self.buffer[0] {
+                2 => ExecutorLogStream::Stderr,
+                _ => ExecutorLogStream::Stdout,
+            };
+            let payload = self.buffer.drain(..LOG_FRAME_HEADER_SIZE + length).skip(LOG_FRAME_HEADER_SIZE).collect::<Vec<_>>();
+
+            let partial = self.partial(stream);
+            partial.extend(payload);
+            while let Some(position) = partial.iter().position(|byte| *byte == b'\n') {
+                let line = partial.drain(..=position).collect::<Vec<_>>();
+                lines.push(to_log_line(stream, &line));
+            }
+        }
+        lines
+    }
+
+    /// Returns the lines, which were not terminated by a newline.
+    fn finish(&mut self) -> Vec<ContainerLogLine> {
+        [ExecutorLogStream::Stdout, ExecutorLogStream::Stderr].into_iter()
+            .filter_map(|stream| {
+                let partial = std::mem::take(self.partial(stream));
+                (!partial.is_empty()).then(|| to_log_line(stream, &partial))
+            })
+            .collect()
+    }
+
+    fn partial(&mut self, stream: ExecutorLogStream) -> &mut Vec<u8> {
+        match stream {
+            ExecutorLogStream::Stdout => &mut self.partial_stdout,
+            ExecutorLogStream::Stderr => &mut self.partial_stderr,
+        }
+    }
+}
+
+fn to_log_line(stream: ExecutorLogStream, line: &[u8]) -> ContainerLogLine {
+    let line = String::from_utf8_lossy(line);
+    ContainerLogLine {
+        stream,
+        line: line.trim_end_matches(['\n', '\r']).to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::UnixListener;
+
+    use super::*;
+    use crate::service::test_execution::container_engine::ContainerNetwork;
+
+    fn frame(stream_type: u8, payload: &str) -> Vec<u8> {
+        let mut frame = vec![stream_type, 0, 0, 0];
+        frame.extend((payload.len() as u32).to_be_bytes());
+        frame.extend(payload.as_bytes());
+        frame
+    }
+
+    fn log_line(stream: ExecutorLogStream, line: &str) -> ContainerLogLine {
+        ContainerLogLine { stream, line: String::from(line) }
+    }
+
+    #[test]
+    fn should_decode_log_frames_split_at_arbitrary_positions() {
+        let mut data = frame(1, "first\nsec");
+        data.extend(frame(2, "error\n"));
+        data.extend(frame(1, "ond\nunterminated"));
+
+        let mut decoder = LogFrameDecoder::default();
+        let (head, tail) = data.split_at(5);
+        let mut lines = decoder.push(head);
+        lines.extend(decoder.push(tail));
+        lines.extend(decoder.finish());
+
+        assert_that!(lines, elements_are![
+            eq(log_line(ExecutorLogStream::Stdout, "first")),
+            eq(log_line(ExecutorLogStream::Stderr, "error")),
+            eq(log_line(ExecutorLogStream::Stdout, "second")),
+            eq(log_line(ExecutorLogStream::Stdout, "unterminated")),
+        ]);
+    }
+
+    #[test]
+    fn should_only_add_the_default_tag_to_images_without_tag_or_digest() {
+        assert_that!(with_default_tag("nmap"), eq("nmap:latest"));
+        assert_that!(with_default_tag("registry:5000/team/nmap"), eq("registry:5000/team/nmap:latest"));
+        assert_that!(with_default_tag("registry:5000/team/nmap:1.2"), eq("registry:5000/team/nmap:1.2"));
+        assert_that!(with_default_tag("nmap@sha256:abcd"), eq("nmap@sha256:abcd"));
+    }
+
+    /// Serves a single request on a Unix socket with the given response and returns the received request.
+    async fn serve_once(socket: PathBuf, response: String) -> tokio::task::JoinHandle<String> {
+        let listener = UnixListener::bind(socket).unwrap();
+        tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.unwrap();
+            let mut request = Vec::new();
+            loop {
+                let mut buffer = [0; 4096];
+                let read = stream.read(&mut buffer).await.unwrap();
+                request.extend_from_slice(&buffer[..read]);
+                let text = String::from_utf8_lossy(&request);
+                if let Some((head, body)) = text.split_once("\r\n\r\n") {
+                    let content_length = head.lines()
+                        .find_map(|line| line.strip_prefix("Content-Length: "))
+                        .and_then(|length| length.parse::<usize>().ok())
+                        .unwrap_or_default();
+                    if body.len() >= content_length {
+                        break;
+                    }
+                }
+                if read == 0 {
+                    break;
+                }
+            }
+            stream.write_all(response.as_bytes()).await.unwrap();
+            String::from_utf8_lossy(&request).into_owned()
+        })
+    }
+
+    #[tokio::test]
+    async fn should_report_exit_code_and_oom_kill_of_a_container() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let socket = temp.path().join("engine.sock");
+        let body = r#"{"Id":"abc","State":{"Status":"exited","ExitCode":137,"OOMKilled":true}}"#;
+        let response = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{body}", body.len());
+        let server = serve_once(Clone::clone(&socket), response).await;
+
+        let engine = ApiContainerEngine::new(socket);
+        let status = engine.inspect_container(&ContainerId(String::from("abc"))).await?;
+
+        assert_that!(status, eq(ContainerStatus {
+            state: ContainerState::Exited,
+            exit_code: Some(137),
+            oom_killed: true,
+        }));
+        let request = server.await?;
+        assert!(request.starts_with("GET /containers/abc/json HTTP/1.1\r\n"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_report_the_digests_of_an_image() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let socket = temp.path().join("engine.sock");
+        let body = r#"{"Id":"sha256:1111","RepoDigests":["registry:5000/team/nmap@sha256:bbbb"]}"#;
+        let response = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{body}", body.len());
+        let server = serve_once(Clone::clone(&socket), response).await;
+
+        let engine = ApiContainerEngine::new(socket);
+        let image = engine.inspect_image("registry:5000/team/nmap:1.2").await?;
+
+        assert_that!(image, some(eq(ImageInfo {
+            id: String::from("sha256:1111"),
+            repo_digests: vec![String::from("registry:5000/team/nmap@sha256:bbbb")],
+        })));
+        let request = server.await?;
+        assert!(request.starts_with("GET /images/registry:5000/team/nmap:1.2/json HTTP/1.1\r\n"));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_authenticate_at_the_registry_when_pulling_with_a_credential() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let socket = temp.path().join("engine.sock");
+        let body = r#"{"status":"Pulling from team/nmap"}"#;
+        let response = format!("HTTP/1.1 200 OK\r\nContent-Length: {}\r\n\r\n{body}", body.len());
+        let server = serve_once(Clone::clone(&socket), response).await;
+
+        let engine = ApiContainerEngine::new(socket);
+        let credential = ContainerRegistryCredential {
+            name: opendut_types::peer::executor::container::ContainerRegistryCredentialName::try_from("ci-registry")?,
+            server: String::from("registry:5000"),
+            username: String::from("ci"),
+            password: String::from("secret"),
+        };
+        engine.pull_image("registry:5000/team/nmap", Some(&credential)).await?;
+
+        let request = server.await?;
+        let auth = request.lines()
+            .find_map(|line| line.strip_prefix("X-Registry-Auth: "))
+            .expect("X-Registry-Auth header should be sent");
+        let auth: serde_json::Value = serde_json::from_slice(&BASE64_URL_SAFE.decode(auth)?)?;
+        assert_that!(auth, eq(json!({ "username": "ci", "password": "secret", "serveraddress": "registry:5000" })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_report_an_existing_container_name_as_conflict() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let socket = temp.path().join("engine.sock");
+        let server = serve_once(Clone::clone(&socket), String::from("HTTP/1.1 409 Conflict\r\nContent-Length: 2\r\n\r\n{}")).await;
+
+        let engine = ApiContainerEngine::new(socket);
+        let spec = ContainerSpec {
+            image: String::from("nmap"),
+            command: vec![String::from("nmap"), String::from("-A")],
+            ..Default::default()
+        };
+        let container_id = engine.create_container("nmap-scan-1", &spec).await?;
+
+        assert_that!(container_id, none());
+        let request = server.await?;
+        assert!(request.starts_with("POST /containers/create?name=nmap-scan-1 HTTP/1.1\r\n"));
+        let (_, body) = request.split_once("\r\n\r\n").unwrap();
+        let body: serde_json::Value = serde_json::from_str(body)?;
+        assert_that!(body["Cmd"], eq(json!(["nmap", "-A"])));
+        assert_that!(body["HostConfig"]["NetworkMode"], eq(json!("host")));
+        Ok(())
+    }
+
+    #[test]
+    fn should_apply_resource_limits_and_isolation_options() {
+        let spec = ContainerSpec {
+            image: String::from("nmap"),
+            network: ContainerNetwork::Named(String::from("opendut-br-opendut")),
+            nano_cpus: Some(1_500_000_000),
+            memory_bytes: Some(512 * 1024 * 1024),
+            pids_limit: Some(256),
+            cap_add: vec![String::from("NET_ADMIN")],
+            cap_drop: vec![String::from("ALL")],
+            read_only_rootfs: true,
+            ..Default::default()
+        };
+
+        let body = create_container_body(&spec);
+
+        let host_config = &body["HostConfig"];
+        assert_that!(host_config["NetworkMode"], eq(json!("opendut-br-opendut")));
+        assert_that!(host_config["NanoCpus"], eq(json!(1_500_000_000)));
+        assert_that!(host_config["Memory"], eq(json!(536_870_912)));
+        assert_that!(host_config["PidsLimit"], eq(json!(256)));
+        assert_that!(host_config["CapAdd"], eq(json!(["NET_ADMIN"])));
+        assert_that!(host_config["CapDrop"], eq(json!(["ALL"])));
+        assert_that!(host_config["ReadonlyRootfs"], eq(json!(true)));
+        assert_that!(host_config["Tmpfs"], eq(json!({ "/tmp": "" })));
+    }
+}
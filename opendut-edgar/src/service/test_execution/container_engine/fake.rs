@@ -0,0 +1,203 @@
+use std::collections::{HashMap, HashSet};
+use std::sync::Mutex;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+use futures::StreamExt;
+
+use opendut_types::peer::executor::container::{ContainerRegistryCredential, ContainerRegistryCredentialName};
+
+use crate::service::test_execution::container_engine::{ContainerEngine, ContainerId, ContainerLogLine, ContainerSpec, ContainerState, ContainerStatus, Error, ImageInfo};
+
+/// Exit code of a container, which was stopped by the engine.
+const STOPPED_EXIT_CODE: i32 = 143;
+
+/// In-memory container engine for unit tests. Containers run for a configurable number of inspections and then exit.
+#[derive(Default)]
+pub struct FakeContainerEngine {
+    state: Mutex<FakeState>,
+}
+
+#[derive(Default)]
+struct FakeState {
+    existing_names: HashSet<String>,
+    missing_images: HashSet<String>,
+    image_digests: HashMap<String, String>,
+    pulled_images: Vec<(String, Option<ContainerRegistryCredentialName>)>,
+    networks: HashMap<String, String>,
+    containers: HashMap<ContainerId, FakeContainer>,
+    running_inspections: usize,
+    exit_code: i32,
+    oom_killed: bool,
+    logs: Vec<ContainerLogLine>,
+}
+
+#[derive(Clone, Debug)]
+pub struct FakeContainer {
+    pub name: String,
+    pub spec: ContainerSpec,
+    pub started: bool,
+    pub stopped: bool,
+    inspections: usize,
+}
+
+impl FakeContainerEngine {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_existing_container(self, name: &str) -> Self {
+        self.state.lock().unwrap().existing_names.insert(name.to_owned());
+        self
+    }
+
+    /// The image has to be pulled, before a container can be created from it.
+    pub fn with_missing_image(self, image: &str) -> Self {
+        self.state.lock().unwrap().missing_images.insert(image.to_owned());
+        self
+    }
+
+    /// Digest, which the registry reports for the image.
+    pub fn with_image_digest(self, image: &str, digest: &str) -> Self {
+        self.state.lock().unwrap().image_digests.insert(image.to_owned(), digest.to_owned());
+        self
+    }
+
+    /// Number of inspections, for which a container is reported as running, before it exits.
+    pub fn running_for(self, inspections: usize) -> Self {
+        self.state.lock().unwrap().running_inspections = inspections;
+        self
+    }
+
+    pub fn exiting_with(self, exit_code: i32) -> Self {
+        self.state.lock().unwrap().exit_code = exit_code;
+        self
+    }
+
+    pub fn oom_killed(self) -> Self {
+        self.state.lock().unwrap().oom_killed = true;
+        self
+    }
+
+    pub fn with_logs(self, logs: Vec<ContainerLogLine>) -> Self {
+        self.state.lock().unwrap().logs = logs;
+        self
+    }
+
+    pub fn containers(&self) -> Vec<FakeContainer> {
+        self.state.lock().unwrap().containers.values().cloned().collect()
+    }
+
+    pub fn pulled_images(&self) -> Vec<String> {
+        self.state.lock().unwrap().pulled_images.iter()
+            .map(|(image, _)| Clone::clone(image))
+            .collect()
+    }
+
+    /// Names of the credentials, which were used for each pull.
+    pub fn pull_credentials(&self) -> Vec<Option<ContainerRegistryCredentialName>> {
+        self.state.lock().unwrap().pulled_images.iter()
+            .map(|(_, credential)| Clone::clone(credential))
+            .collect()
+    }
+
+    /// Networks created by EDGAR, by name, with the bridge they were created on.
+    pub fn networks(&self) -> HashMap<String, String> {
+        Clone::clone(&self.state.lock().unwrap().networks)
+    }
+
+    pub fn image_id(image: &str) -> String {
+        format!("fake-image-{image}")
+    }
+}
+
+#[async_trait]
+impl ContainerEngine for FakeContainerEngine {
+    async fn create_container(&self, name: &str, spec: &ContainerSpec) -> Result<Option<ContainerId>, Error> {
+        let mut state = self.state.lock().unwrap();
+        if state.missing_images.iter().any(|image| Self::image_id(image) == spec.image) {
+            return Err(Error::ImageNotFound { image: Clone::clone(&spec.image) });
+        }
+        if !state.existing_names.insert(name.to_owned()) {
+            return Ok(None);
+        }
+        let id = ContainerId(format!("fake-{name}"));
+        state.containers.insert(Clone::clone(&id), FakeContainer {
+            name: name.to_owned(),
+            spec: Clone::clone(spec),
+            started: false,
+            stopped: false,
+            inspections: 0,
+        });
+        Ok(Some(id))
+    }
+
+    async fn start_container(&self, id: &ContainerId) -> Result<(), Error> {
+        self.container(id, |container| container.started = true)
+    }
+
+    async fn inspect_container(&self, id: &ContainerId) -> Result<ContainerStatus, Error> {
+        let (running_inspections, exit_code, oom_killed) = {
+            let state = self.state.lock().unwrap();
+            (state.running_inspections, state.exit_code, state.oom_killed)
+        };
+        self.container(id, |container| {
+            if !container.started {
+                ContainerStatus { state: ContainerState::Created, exit_code: None, oom_killed: false }
+            } else if container.stopped {
+                ContainerStatus { state: ContainerState::Exited, exit_code: Some(STOPPED_EXIT_CODE), oom_killed: false }
+            } else if container.inspections < running_inspections {
+                container.inspections += 1;
+                ContainerStatus { state: ContainerState::Running, exit_code: None, oom_killed: false }
+            } else {
+                ContainerStatus { state: ContainerState::Exited, exit_code: Some(exit_code), oom_killed }
+            }
+        })
+    }
+
+    async fn stop_container(&self, id: &ContainerId, _timeout: Duration) -> Result<(), Error> {
+        self.container(id, |container| container.stopped = true)
+    }
+
+    async fn inspect_image(&self, image: &str) -> Result<Option<ImageInfo>, Error> {
+        let state = self.state.lock().unwrap();
+        if state.missing_images.contains(image) {
+            return Ok(None);
+        }
+        Ok(Some(ImageInfo {
+            id: Self::image_id(image),
+            repo_digests: state.image_digests.get(image)
+                .map(|digest| format!("{image}@{digest}"))
+                .into_iter()
+                .collect(),
+        }))
+    }
+
+    async fn pull_image(&self, image: &str, credential: Option<&ContainerRegistryCredential>) -> Result<(), Error> {
+        let mut state = self.state.lock().unwrap();
+        state.missing_images.remove(image);
+        state.pulled_images.push((image.to_owned(), credential.map(|credential| Clone::clone(&credential.name))));
+        Ok(())
+    }
+
+    async fn ensure_bridge_network(&self, name: &str, bridge: &str) -> Result<(), Error> {
+        self.state.lock().unwrap().networks.entry(name.to_owned()).or_insert_with(|| bridge.to_owned());
+        Ok(())
+    }
+
+    async fn container_logs(&self, id: &ContainerId) -> Result<BoxStream<'static, Result<ContainerLogLine, Error>>, Error> {
+        self.container(id, |_| ())?;
+        let logs = Clone::clone(&self.state.lock().unwrap().logs);
+        Ok(futures::stream::iter(logs.into_iter().map(Ok)).boxed())
+    }
+}
+
+impl FakeContainerEngine {
+    fn container<T>(&self, id: &ContainerId, f: impl FnOnce(&mut FakeContainer) -> T) -> Result<T, Error> {
+        let mut state = self.state.lock().unwrap();
+        let container = state.containers.get_mut(id)
+            .ok_or_else(|| Error::Api { request: String::from("fake"), status: 404, message: format!("No such container: {id}") })?;
+        Ok(f(container))
+    }
+}
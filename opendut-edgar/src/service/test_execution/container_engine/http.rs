@@ -0,0 +1,249 @@
+use std::path::PathBuf;
+
+use serde::de::DeserializeOwned;
+use tokio::io::{AsyncBufRead, AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader};
+use tokio::net::UnixStream;
+
+use crate::service::test_execution::container_engine::Error;
+
+const READ_BUFFER_SIZE: usize = 8192;
+
+/// Minimal HTTP/1.1 client for the REST APIs, which Docker and Podman serve on a Unix socket.
+/// Each request uses a new connection, which is closed after the response.
+pub struct UnixSocketClient {
+    socket: PathBuf,
+}
+
+impl UnixSocketClient {
+    pub fn new(socket: PathBuf) -> Self {
+        Self { socket }
+    }
+
+    pub async fn request(&self, method: &str, path_and_query: &str, body: Option<&serde_json::Value>) -> Result<Response<BufReader<UnixStream>>, Error> {
+        self.request_with_headers(method, path_and_query, &[], body).await
+    }
+
+    /// Like [`Self::request`], but sends additional headers, e.g. to authenticate at a registry.
+    pub async fn request_with_headers(&self, method: &str, path_and_query: &str, headers: &[(&str, &str)], body: Option<&serde_json::Value>) -> Result<Response<BufReader<UnixStream>>, Error> {
+        let mut stream = UnixStream::connect(&self.socket).await
+            .map_err(|cause| Error::Connect { socket: Clone::clone(&self.socket), cause })?;
+
+        let body = body.map(|body| body.to_string().into_bytes()).unwrap_or_default();
+
+        let mut head = format!("{method} {path_and_query} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n");
+        for (name, value) in headers {
+            head.push_str(&format!("{name}: {value}\r\n"));
+        }
+        if !body.is_empty() {
+            head.push_str("Content-Type: application/json\r\n");
+        }
+        head.push_str(&format!("Content-Length: {}\r\n\r\n", body.len()));
+
+        stream.write_all(head.as_bytes()).await?;
+        stream.write_all(&body).await?;
+        stream.flush().await?;
+
+        Response::read(BufReader::new(stream)).await
+    }
+}
+
+pub struct Response<R> {
+    pub status: u16,
+    reader: R,
+    framing: Framing,
+    finished: bool,
+}
+
+enum Framing {
+    Length { remaining: u64 },
+    Chunked { remaining_in_chunk: u64 },
+    UntilClose,
+}
+
+impl<R: AsyncBufRead + Unpin> Response<R> {
+    pub async fn read(mut reader: R) -> Result<Self, Error> {
+        let mut line = String::new();
+        reader.read_line(&mut line).await?;
+        let status = line.split_whitespace()
+            .nth(1)
+            .and_then(|status| status.parse::<u16>().ok())
+            .ok_or_else(|| Error::InvalidResponse { message: format!("Malformed status line '{}'", line.trim_end()) })?;
+
+        let mut content_length = None;
+        let mut chunked = false;
+        loop {
+            line.clear();
+            if reader.read_line(&mut line).await? == 0 {
+                return Err(Error::InvalidResponse { message: String::from("Connection closed before the end of the headers") });
+            }
+            let header = line.trim_end();
+            if header.is_empty() {
+                break;
+            }
+            if let Some((name, value)) = header.split_once(':') {
+                let value = value.trim();
+                if name.eq_ignore_ascii_case("content-length") {
+                    content_length = Some(value.parse::<u64>()
+                        .map_err(|_| Error::InvalidResponse { message: format!("Malformed Content-Length '{value}'") })?);
+                } else if name.eq_ignore_ascii_case("transfer-encoding") && value.eq_ignore_ascii_case("chunked") {
+                    chunked = true;
+                }
+            }
+        }
+
+        let framing = match (chunked, content_length) {
+            (true, _) => Framing::Chunked { remaining_in_chunk: 0 },
+            (false, Some(remaining)) => Framing::Length { remaining },
+            (false, None) => Framing::UntilClose,
+        };
+
+        Ok(Self { status, reader, framing, finished: false })
+    }
+
+    pub fn is_success(&self) -> bool {
+        (200..300).contains(&self.status)
+    }
+
+    /// Returns the next part of the body as soon as it arrives, or `None`, when the body is complete.
+    pub async fn chunk(&mut self) -> Result<Option<Vec<u8>>, Error> {
+        if self.finished {
+            return Ok(None);
+        }
+
+        let to_read = match &mut self.framing {
+            Framing::Length { remaining } => *remaining,
+            Framing::UntilClose => READ_BUFFER_SIZE as u64,
+            Framing::Chunked { remaining_in_chunk } => {
+                if *remaining_in_chunk == 0 {
+                    let mut line = String::new();
+                    self.reader.read_line(&mut line).await?;
+                    let size = line.trim_end().split(';').next().unwrap_or_default();
+                    let size = u64::from_str_radix(size, 16)
+                        .map_err(|_| Error::InvalidResponse { message: format!("Malformed chunk size '{size}'") })?;
+                    if size == 0 {
+                        self.skip_trailers().await?;
+                        self.finished = true;
+                        return Ok(None);
+                    }
+                    *remaining_in_chunk = size;
+                }
+                *remaining_in_chunk
+            }
+        };
+
+        if to_read == 0 {
+            self.finished = true;
+            return Ok(None);
+        }
+
+        let mut buffer = vec![0; to_read.min(READ_BUFFER_SIZE as u64) as usize];
+        let read = self.reader.read(&mut buffer).await?;
+        buffer.truncate(read);
+
+        match &mut self.framing {
+            Framing::UntilClose => {
+                if read == 0 {
+                    self.finished = true;
+                    return Ok(None);
+                }
+            }
+            Framing::Length { remaining } => {
+                if read == 0 {
+                    return Err(Error::InvalidResponse { message: String::from("Connection closed before the end of the body") });
+                }
+                *remaining -= read as u64;
+            }
+            Framing::Chunked { remaining_in_chunk } => {
+                if read == 0 {
+                    return Err(Error::InvalidResponse { message: String::from("Connection closed within a chunk") });
+                }
+                *remaining_in_chunk -= read as u64;
+                if *remaining_in_chunk == 0 {
+                    let mut line = String::new();
+                    self.reader.read_line(&mut line).await?;
+                }
+            }
+        }
+        Ok(Some(buffer))
+    }
+
+    pub async fn bytes(mut self) -> Result<Vec<u8>, Error> {
+        let mut body = Vec::new();
+        while let Some(chunk) = self.chunk().await? {
+            body.extend(chunk);
+        }
+        Ok(body)
+    }
+
+    pub async fn json<T: DeserializeOwned>(self) -> Result<T, Error> {
+        let body = self.bytes().await?;
+        serde_json::from_slice(&body)
+            .map_err(|cause| Error::InvalidResponse { message: format!("Malformed JSON body: {cause}") })
+    }
+
+    /// Turns an unsuccessful response into an error. The engines describe the error as JSON object with a `message` field.
+    pub async fn into_error(self, request: &str) -> Error {
+        let status = self.status;
+        let message = match self.bytes().await {
+            Ok(body) => serde_json::from_slice::<serde_json::Value>(&body).ok()
+                .and_then(|body| body.get("message").and_then(|message| message.as_str()).map(ToOwned::to_owned))
+                .unwrap_or_else(|| String::from_utf8_lossy(&body).trim().to_owned()),
+            Err(cause) => cause.to_string(),
+        };
+        Error::Api { request: request.to_owned(), status, message }
+    }
+
+    async fn skip_trailers(&mut self) -> Result<(), Error> {
+        let mut line = String::new();
+        loop {
+            line.clear();
+            if self.reader.read_line(&mut line).await? == 0 || line.trim_end().is_empty() {
+                return Ok(());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn should_read_a_response_with_content_length() -> anyhow::Result<()> {
+        let raw = b"HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: 13\r\n\r\n{\"Id\":\"1234\"}";
+
+        let response = Response::read(&raw[..]).await?;
+        assert_that!(response.status, eq(200));
+
+        let body = response.json::<serde_json::Value>().await?;
+        assert_that!(body, eq(serde_json::json!({ "Id": "1234" })));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_read_a_chunked_response_chunk_by_chunk() -> anyhow::Result<()> {
+        let raw = b"HTTP/1.1 200 OK\r\nTransfer-Encoding: chunked\r\n\r\n5\r\nhello\r\n7;ext=1\r\n, world\r\n0\r\n\r\n";
+
+        let mut response = Response::read(&raw[..]).await?;
+
+        assert_that!(response.chunk().await?, some(eq(b"hello".to_vec())));
+        assert_that!(response.chunk().await?, some(eq(b", world".to_vec())));
+        assert_that!(response.chunk().await?, none());
+        assert_that!(response.chunk().await?, none());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_extract_the_message_of_an_error_response() -> anyhow::Result<()> {
+        let raw = b"HTTP/1.1 404 Not Found\r\nContent-Length: 39\r\n\r\n{\"message\":\"No such container: nmap-1\"}";
+
+        let response = Response::read(&raw[..]).await?;
+        assert!(!response.is_success());
+
+        let error = response.into_error("inspect container").await;
+        assert!(matches!(error, Error::Api { status: 404, ref message, .. } if message == "No such container: nmap-1"));
+        Ok(())
+    }
+}
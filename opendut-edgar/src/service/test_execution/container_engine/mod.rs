@@ -0,0 +1,309 @@
+use std::fmt;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use futures::stream::BoxStream;
+
+use opendut_types::peer::executor::container::{ContainerRegistryCredential, Engine};
+use opendut_types::peer::executor::run::ExecutorLogStream;
+
+pub use api::ApiContainerEngine;
+
+mod api;
+mod http;
+#[cfg(test)]
+pub mod fake;
+
+pub type ContainerEngineRef = Arc<dyn ContainerEngine>;
+
+/// Access to a container engine, independent of whether it is Docker or Podman.
+#[async_trait]
+pub trait ContainerEngine: Send + Sync {
+    /// Creates a container with the given name. Returns `None`, if a container with this name already exists.
+    async fn create_container(&self, name: &str, spec: &ContainerSpec) -> Result<Option<ContainerId>, Error>;
+
+    async fn start_container(&self, id: &ContainerId) -> Result<(), Error>;
+
+    async fn inspect_container(&self, id: &ContainerId) -> Result<ContainerStatus, Error>;
+
+    /// Stops the container, killing it if it does not exit within the timeout. Succeeds, if the container is already stopped.
+    async fn stop_container(&self, id: &ContainerId, timeout: Duration) -> Result<(), Error>;
+
+    /// Returns information about the image, if it is available on this peer.
+    async fn inspect_image(&self, image: &str) -> Result<Option<ImageInfo>, Error>;
+
+    /// Pulls the image from its registry, authenticating with the credential, if given.
+    async fn pull_image(&self, image: &str, credential: Option<&ContainerRegistryCredential>) -> Result<(), Error>;
+
+    /// Creates a bridge network with the given name on top of an existing Linux bridge, unless it exists already.
+    async fn ensure_bridge_network(&self, name: &str, bridge: &str) -> Result<(), Error>;
+
+    /// Follows the output of the container, until it exits.
+    async fn container_logs(&self, id: &ContainerId) -> Result<BoxStream<'static, Result<ContainerLogLine, Error>>, Error>;
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct ContainerId(pub String);
+
+impl fmt::Display for ContainerId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Everything needed to create a container, in the terms of the container engine.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct ContainerSpec {
+    pub image: String,
+    /// Replaces the default command of the image, if not empty.
+    pub command: Vec<String>,
+    /// Environment variables in the form `NAME=value`.
+    pub envs: Vec<String>,
+    /// Bind mounts in the form `source:target[:options]`, as passed to `--volume`.
+    pub binds: Vec<String>,
+    pub mounts: Vec<ContainerMount>,
+    pub devices: Vec<ContainerDeviceMapping>,
+    pub ports: Vec<ContainerPortMapping>,
+    pub network: ContainerNetwork,
+    /// CPU limit in billionths of a CPU.
+    pub nano_cpus: Option<u64>,
+    /// Memory limit in bytes.
+    pub memory_bytes: Option<u64>,
+    pub pids_limit: Option<u64>,
+    /// Capabilities without the `CAP_` prefix, e.g. `NET_ADMIN`.
+    pub cap_add: Vec<String>,
+    pub cap_drop: Vec<String>,
+    /// Mounts the root filesystem read-only. A tmpfs is mounted at `/tmp`, so the container can still write temporary files.
+    pub read_only_rootfs: bool,
+}
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum ContainerNetwork {
+    #[default]
+    Host,
+    /// The default bridge network of the engine.
+    Bridge,
+    /// A network created by EDGAR, e.g. on top of the bridge of the cluster.
+    Named(String),
+}
+
+impl ContainerNetwork {
+    /// Name of the network, as expected for the `NetworkMode` of a container.
+    pub fn mode(&self) -> &str {
+        match self {
+            ContainerNetwork::Host => "host",
+            ContainerNetwork::Bridge => "bridge",
+            ContainerNetwork::Named(name) => name,
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContainerMount {
+    pub source: PathBuf,
+    pub target: PathBuf,
+    pub read_only: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContainerDeviceMapping {
+    pub path_on_host: String,
+    pub path_in_container: String,
+    pub cgroup_permissions: String,
+}
+
+impl ContainerDeviceMapping {
+    /// Parses a device in the form `host[:container[:permissions]]`, as passed to `--device`.
+    pub fn parse(value: &str) -> Self {
+        let mut parts = value.splitn(3, ':');
+        let path_on_host = parts.next().unwrap_or_default().to_owned();
+        let path_in_container = parts.next().map(ToOwned::to_owned).unwrap_or_else(|| Clone::clone(&path_on_host));
+        let cgroup_permissions = parts.next().unwrap_or("rwm").to_owned();
+        Self { path_on_host, path_in_container, cgroup_permissions }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContainerPortMapping {
+    pub host_ip: Option<String>,
+    pub host_port: Option<String>,
+    /// Port in the container including the protocol, e.g. `80/tcp`.
+    pub container_port: String,
+}
+
+impl ContainerPortMapping {
+    /// Parses a port in the form `[[host_ip:]host_port:]container_port[/protocol]`, as passed to `--publish`.
+    pub fn parse(value: &str) -> Self {
+        let (ports, protocol) = value.split_once('/').unwrap_or((value, "tcp"));
+        let mut parts = ports.rsplitn(3, ':');
+        let container_port = parts.next().unwrap_or_default();
+        let host_port = parts.next().filter(|port| !port.is_empty()).map(ToOwned::to_owned);
+        let host_ip = parts.next().filter(|ip| !ip.is_empty()).map(ToOwned::to_owned);
+        Self {
+            host_ip,
+            host_port,
+            container_port: format!("{container_port}/{protocol}"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ContainerState {
+    Created,
+    Running,
+    Restarting,
+    Exited,
+    Paused,
+    Dead,
+}
+
+impl TryFrom<&str> for ContainerState {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "created" | "configured" => Ok(ContainerState::Created),
+            "running" => Ok(ContainerState::Running),
+            "restarting" => Ok(ContainerState::Restarting),
+            "exited" | "stopped" => Ok(ContainerState::Exited),
+            "paused" => Ok(ContainerState::Paused),
+            "dead" => Ok(ContainerState::Dead),
+            unknown_state => Err(Error::InvalidResponse { message: format!("Unknown container state '{unknown_state}'") }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContainerStatus {
+    pub state: ContainerState,
+    /// Only meaningful, once the container exited.
+    pub exit_code: Option<i32>,
+    /// Whether the container was killed, because it exceeded its memory limit.
+    pub oom_killed: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImageInfo {
+    /// Local ID of the image, from which containers can be created.
+    pub id: String,
+    /// References of the image in the registries it was pulled from, in the form `repository@digest`.
+    /// Empty for images, which were built locally.
+    pub repo_digests: Vec<String>,
+}
+
+impl ImageInfo {
+    pub fn digests(&self) -> impl Iterator<Item=&str> {
+        self.repo_digests.iter()
+            .filter_map(|repo_digest| repo_digest.split_once('@'))
+            .map(|(_, digest)| digest)
+    }
+
+    /// Returns the digest of the image in the repository of the given image reference,
+    /// falling back to any digest, if the engine reports the repository under a different name.
+    pub fn digest_for(&self, image: &str) -> Option<&str> {
+        let repository = repository_of(image);
+        self.repo_digests.iter()
+            .filter_map(|repo_digest| repo_digest.split_once('@'))
+            .find(|(repo, _)| *repo == repository || repo.ends_with(&format!("/{repository}")))
+            .map(|(_, digest)| digest)
+            .or_else(|| self.digests().next())
+    }
+}
+
+/// Strips the tag and digest off an image reference, e.g. `registry:5000/team/nmap:1.2` becomes `registry:5000/team/nmap`.
+fn repository_of(image: &str) -> &str {
+    let image = image.split_once('@').map_or(image, |(repository, _)| repository);
+    match image.rsplit_once(':') {
+        Some((repository, tag)) if !tag.contains('/') => repository,
+        _ => image,
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ContainerLogLine {
+    pub stream: ExecutorLogStream,
+    pub line: String,
+}
+
+/// Locations of the API sockets of the supported container engines.
+#[derive(Clone, Debug)]
+pub struct ContainerEngineSockets {
+    pub docker: PathBuf,
+    pub podman: PathBuf,
+}
+
+impl ContainerEngineSockets {
+    pub fn load(config: &config::Config) -> Result<Self, config::ConfigError> {
+        Ok(Self {
+            docker: PathBuf::from(config.get_string("executor.engine.docker.socket")?),
+            podman: PathBuf::from(config.get_string("executor.engine.podman.socket")?),
+        })
+    }
+
+    pub fn socket(&self, engine: &Engine) -> &Path {
+        match engine {
+            Engine::Docker => &self.docker,
+            Engine::Podman => &self.podman,
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failed to connect to container engine at '{}': {cause}", socket.to_string_lossy())]
+    Connect { socket: PathBuf, cause: std::io::Error },
+    #[error("Failed to communicate with container engine: {0}")]
+    Io(#[from] std::io::Error),
+    #[error("Image '{image}' is not available on this peer")]
+    ImageNotFound { image: String },
+    #[error("Container engine responded to '{request}' with status {status}: {message}")]
+    Api { request: String, status: u16, message: String },
+    #[error("Invalid response from container engine: {message}")]
+    InvalidResponse { message: String },
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_port_mappings() {
+        assert_that!(ContainerPortMapping::parse("80"), eq(ContainerPortMapping { host_ip: None, host_port: None, container_port: String::from("80/tcp") }));
+        assert_that!(ContainerPortMapping::parse("8080:80/udp"), eq(ContainerPortMapping { host_ip: None, host_port: Some(String::from("8080")), container_port: String::from("80/udp") }));
+        assert_that!(ContainerPortMapping::parse("127.0.0.1:8080:80"), eq(ContainerPortMapping { host_ip: Some(String::from("127.0.0.1")), host_port: Some(String::from("8080")), container_port: String::from("80/tcp") }));
+    }
+
+    #[test]
+    fn should_determine_the_digest_of_an_image_in_its_repository() {
+        let image = ImageInfo {
+            id: String::from("sha256:1111"),
+            repo_digests: vec![
+                String::from("mirror.example.com/team/nmap@sha256:aaaa"),
+                String::from("registry:5000/team/nmap@sha256:bbbb"),
+            ],
+        };
+
+        assert_that!(image.digest_for("registry:5000/team/nmap:1.2"), some(eq("sha256:bbbb")));
+        assert_that!(image.digest_for("team/nmap"), some(eq("sha256:aaaa")));
+        assert_that!(image.digest_for("other"), some(eq("sha256:aaaa")));
+        assert_that!(image.digests().collect::<Vec<_>>(), elements_are![eq("sha256:aaaa"), eq("sha256:bbbb")]);
+    }
+
+    #[test]
+    fn should_parse_device_mappings() {
+        assert_that!(ContainerDeviceMapping::parse("/dev/ttyUSB0"), eq(ContainerDeviceMapping {
+            path_on_host: String::from("/dev/ttyUSB0"),
+            path_in_container: String::from("/dev/ttyUSB0"),
+            cgroup_permissions: String::from("rwm"),
+        }));
+        assert_that!(ContainerDeviceMapping::parse("/dev/ttyUSB0:/dev/serial:r"), eq(ContainerDeviceMapping {
+            path_on_host: String::from("/dev/ttyUSB0"),
+            path_in_container: String::from("/dev/serial"),
+            cgroup_permissions: String::from("r"),
+        }));
+    }
+}
@@ -1,31 +1,31 @@
-use std::{env, io::{Cursor, ErrorKind, Write}, path::PathBuf, process::Stdio};
+use std::{env, path::PathBuf, time::{Duration, SystemTime}};
+use std::os::unix::fs::PermissionsExt;
 
-use anyhow::Result;
-use tokio::{fs::{self, File}, io::{AsyncBufReadExt, AsyncReadExt, BufReader}, process::{Child, Command}, sync::{mpsc, watch}};
+use futures::stream::BoxStream;
+use futures::StreamExt;
+use tokio::{fs, sync::watch, task::JoinHandle};
 use tracing::{error, info, warn};
-use url::Url;
 use uuid::Uuid;
-use walkdir::WalkDir;
-use zip::{CompressionMethod, write::{FileOptionExtension, FileOptions, SimpleFileOptions}, ZipWriter};
 
-use opendut_types::peer::executor::{container::{CommandName, ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine}, ResultsUrl};
+use opendut_types::can::CanDatabase;
+use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerImageDigest, ContainerImagePullPolicy, ContainerName, ContainerPortSpec, ContainerRegistryCredential, ContainerVolume, ContainerCpuLimit, ContainerMemoryLimit, ContainerPidsLimit, ContainerNetworkMode, ContainerCapability, Engine}, ResultsUrl};
+use opendut_types::util::net::NetworkInterfaceName;
+use opendut_types::peer::executor::run::ExecutorLogStream;
+use opendut_types::secret::Secret;
 
-use crate::service::test_execution::webdav_client::{self, WebdavClient};
-
-#[derive(Debug)]
-enum ContainerState {
-    Created,
-    Running,
-    Restarting,
-    Exited,
-    Paused,
-    Dead,
-}
+use crate::service::test_execution::container_engine::{self, ContainerDeviceMapping, ContainerEngineRef, ContainerId, ContainerLogLine, ContainerMount, ContainerNetwork, ContainerPortMapping, ContainerSpec, ContainerState, ImageInfo};
+use crate::service::test_execution::executor_logs::ExecutorLogSender;
+use crate::service::test_execution::executor_run::ExecutorRunReporter;
+use crate::service::test_execution::result_sink::ResultSinksRef;
+use crate::service::test_execution::results::{self, ResultsDirectory};
 
 pub struct ContainerConfiguration {
     pub name: ContainerName,
     pub engine: Engine,
     pub image: ContainerImage,
+    pub pull_policy: ContainerImagePullPolicy,
+    pub digest: Option<ContainerImageDigest>,
+    pub registry_credential: Option<ContainerRegistryCredential>,
     pub command: ContainerCommand,
     pub args: Vec<ContainerCommandArgument>,
     pub envs: Vec<ContainerEnvironmentVariable>,
@@ -33,70 +33,122 @@ pub struct ContainerConfiguration {
     pub devices: Vec<ContainerDevice>,
     pub volumes: Vec<ContainerVolume>,
     pub results_url: Option<ResultsUrl>,
+    pub can_databases: Vec<CanDatabase>,
+    /// Secrets, which volumes reference as their source. Each is provided to the container as a file.
+    pub secrets: Vec<Secret>,
+    pub cpus: Option<ContainerCpuLimit>,
+    pub memory: Option<ContainerMemoryLimit>,
+    pub pids_limit: Option<ContainerPidsLimit>,
+    pub network_mode: ContainerNetworkMode,
+    pub cap_add: Vec<ContainerCapability>,
+    pub cap_drop: Vec<ContainerCapability>,
+    pub read_only_rootfs: bool,
+    /// Bridge of the cluster, which the peer is currently assigned to. Required for the network mode `cluster`.
+    pub cluster_bridge: Option<NetworkInterfaceName>,
 }
 
 pub struct ContainerManager{
     config: ContainerConfiguration,
-    results_dir: PathBuf,
-    webdav_client: WebdavClient,
+    engine: ContainerEngineRef,
+    results_dir: ResultsDirectory,
+    can_databases_dir: PathBuf,
+    secrets_dir: PathBuf,
+    result_sinks: ResultSinksRef,
+    reporter: ExecutorRunReporter,
     termination_channel_rx: watch::Receiver<bool>,
 }
 
 const MONITOR_INTERVAL_MS: u64 = 1000;
-const RESULTS_READY_FILE: &str = ".results_ready";
 const CONTAINER_RESULTS_DIRECTORY: &str = "/results";
+const CONTAINER_CAN_DATABASES_DIRECTORY: &str = "/can-databases";
+/// Time the log reader has to forward the remaining output after the container exited.
+const LOG_READER_FINISH_TIMEOUT: Duration = Duration::from_secs(5);
+/// Time a container has to exit after being asked to stop, before it is killed.
+const STOP_TIMEOUT: Duration = Duration::from_secs(10);
 
 impl ContainerManager {
 
-    pub fn new(container_configuration: ContainerConfiguration, termination_channel_rx: watch::Receiver<bool>) -> Self {
+    pub fn new(container_configuration: ContainerConfiguration, engine: ContainerEngineRef, result_sinks: ResultSinksRef, reporter: ExecutorRunReporter, termination_channel_rx: watch::Receiver<bool>) -> Self {
+        let id = Uuid::new_v4();
         Self { 
             config: container_configuration,
-            results_dir: env::temp_dir().join(format!("opendut-edgar-results_{}", Uuid::new_v4())),
-            webdav_client: WebdavClient::new("some_dummy_token".to_string()), // TODO: Authenticate with actual token
+            engine,
+            results_dir: ResultsDirectory::new(id),
+            can_databases_dir: env::temp_dir().join(format!("opendut-edgar-can-databases_{}", id)),
+            secrets_dir: env::temp_dir().join(format!("opendut-edgar-secrets_{}", id)),
+            result_sinks,
+            reporter,
             termination_channel_rx
         }
     }
 
     pub async fn start(&mut self) {
-        match self.run().await {
+        let result = self.run().await;
+
+        // secrets must not outlive the container, not even when it failed
+        if let Err(cause) = self.cleanup_secrets_dir().await {
+            error!("{cause}");
+        }
+
+        match result {
             Ok(_) => (),
-            Err(cause) => error!("{}", cause.to_string()),
+            Err(cause) => {
+                error!("{}", cause.to_string());
+                self.reporter.failed().await;
+            }
         }
     }
 
     async fn run(&mut self) -> Result<(), Error> {
         let mut results_uploaded = false;
+        let mut cancelled = false;
 
-        self.create_results_dir().await?;
-        let container_name = self.start_container().await?;
-        let mut log_reader = 
-            ContainerLogReader::create(
-                self.config.engine.command_name(), 
-                container_name.clone()
-            )?;
+        self.reporter.pending().await;
 
-        loop {
-            log_reader.read().await;
+        let image = self.prepare_image().await?;
+        if let Some(digest) = image.digest_for(self.config.image.value()) {
+            match ContainerImageDigest::try_from(digest) {
+                Ok(digest) => self.reporter.image_resolved(digest).await,
+                Err(cause) => warn!("Not reporting digest of image '{}': {cause}", self.config.image),
+            }
+        }
 
+        self.results_dir.create().await?;
+        self.create_can_databases_dir().await?;
+        self.create_secrets_dir().await?;
+        let network = self.prepare_network().await?;
+        let container_id = self.start_container(&image, network).await?;
+        let log_forwarder = self.reporter.log_forwarder();
+        let log_reader =
+            ContainerLogReader::create(
+                self.engine.container_logs(&container_id).await?,
+                self.config.name.to_string(),
+                log_forwarder.sender(),
+            );
+        self.reporter.running().await;
+
+        let status = loop {
             // If the value in the channel has changed or the channel has been closed, we terminate
-            if self.termination_channel_rx.has_changed().unwrap_or(true) {
-                self.stop_container(&container_name).await?;
+            if !cancelled && self.termination_channel_rx.has_changed().unwrap_or(true) {
+                self.engine.stop_container(&container_id, STOP_TIMEOUT).await?;
+                cancelled = true;
             }
 
-            if self.are_results_ready().await? {
-                self.remove_result_ready_indicator().await?;
+            if self.results_dir.are_results_ready() {
+                self.results_dir.remove_results_ready_indicator().await?;
                 self.upload_results().await?;
                 results_uploaded = true;
             }
 
-            match self.get_container_state(&container_name).await? {
+            let status = self.engine.inspect_container(&container_id).await?;
+            match status.state {
                 ContainerState::Running => (),
-                ContainerState::Exited => {
+                ContainerState::Exited | ContainerState::Dead => {
                     if ! results_uploaded {
-                        self.remove_result_ready_indicator().await?;
+                        self.results_dir.remove_results_ready_indicator().await?;
                         self.upload_results().await?;
                     }
-                    break
+                    break status
                 },
                 state => {
                     warn!("Unexpected container state of '{}': {:?}", self.config.name, state)
@@ -104,281 +156,590 @@ impl ContainerManager {
             }
 
             tokio::time::sleep(std::time::Duration::from_millis(MONITOR_INTERVAL_MS)).await;
+        };
+
+        log_reader.finish().await;
+        if status.oom_killed {
+            let message = "Container was killed, because it ran out of memory.";
+            warn!("[{}] {message}", self.config.name);
+            log_forwarder.sender().send(ExecutorLogStream::Stderr, SystemTime::now(), String::from(message));
         }
+        log_forwarder.close().await;
 
-        self.cleanup_results_dir().await?;
+        if status.exit_code.is_none() {
+            warn!("Could not determine exit code of container '{}'.", self.config.name);
+        }
 
-        Ok(())
-    }
+        self.results_dir.cleanup().await?;
+        self.cleanup_can_databases_dir().await?;
 
-    async fn get_container_state(&self, container_name: &String) -> Result<ContainerState, Error> {
-        let output = Command::new(self.config.engine.command_name())
-            .args(["inspect", "-f", "'{{.State.Status}}'", container_name])
-            .output()
-            .await
-            .map_err(|cause| Error::CommandLineProgramExecution { command: format!("{} inspect", &self.config.engine.command_name()), cause })?;
-        
-        match String::from_utf8_lossy(&output.stdout).into_owned().replace('\'', "").trim() {
-            "created" => Ok(ContainerState::Created),
-            "running" => Ok(ContainerState::Running),
-            "restarting" => Ok(ContainerState::Restarting),
-            "exited" => Ok(ContainerState::Exited),
-            "paused" => Ok(ContainerState::Paused),
-            "dead" => Ok(ContainerState::Dead),
-            unknown_state => Err(Error::Other { message: format!("Unknown container state returned by {} inspect: '{}'", &self.config.engine.command_name(), unknown_state) } ),
+        if cancelled {
+            self.reporter.cancelled(status.exit_code).await;
+        } else {
+            self.reporter.exited(status.exit_code).await;
         }
-        
+
+        Ok(())
     }
 
-    async fn start_container(&mut self) -> Result<String, Error>{
+    /// Makes the image available as demanded by the pull policy and verifies, that it matches the pinned digest.
+    async fn prepare_image(&self) -> Result<ImageInfo, Error> {
+        let image = self.config.image.value();
+        let local_image = self.engine.inspect_image(image).await?;
 
-        let mut cmd = Command::new(self.config.engine.command_name());
-        cmd.arg("run");
-        cmd.arg("--detach");
-        cmd.arg("--net=host");
+        let pull = match self.config.pull_policy {
+            ContainerImagePullPolicy::Always => true,
+            ContainerImagePullPolicy::IfNotPresent => local_image.is_none(),
+            ContainerImagePullPolicy::Never => false,
+        };
 
-        // TODO: Determining the name like this and then creating the container is theoretically susceptible to race conditions
-        let mut container_name = String::new();
-        for ctr in 1..i32::MAX {
-            container_name = format!("{}-{}", self.config.name, ctr);
-            if ! self.check_container_name_exists(&container_name).await? {
-                break;
+        let image_info = if pull {
+            info!("Pulling image '{image}' for container {}.", self.config.name);
+            self.engine.pull_image(image, self.config.registry_credential.as_ref()).await?;
+            self.engine.inspect_image(image).await?
+        } else {
+            local_image
+        };
+        let image_info = image_info
+            .ok_or_else(|| container_engine::Error::ImageNotFound { image: image.to_owned() })?;
+
+        if let Some(expected) = &self.config.digest {
+            if !image_info.digests().any(|digest| digest == expected.value()) {
+                return Err(Error::ImageDigestMismatch {
+                    image: Clone::clone(&self.config.image),
+                    expected: Clone::clone(expected),
+                    actual: image_info.digests().map(ToOwned::to_owned).collect(),
+                });
             }
         }
-        cmd.args(["--name", container_name.as_str()]);
+        Ok(image_info)
+    }
 
-        cmd.args(["--mount", format!("type=bind,source={},target={}", self.results_dir.to_string_lossy(), CONTAINER_RESULTS_DIRECTORY).as_str()]);
-        
-        for env in &self.config.envs {
-            cmd.args(["--env", &format!("{}={}", env.name(), env.value())]);
-        }
-        for port in &self.config.ports {
-            cmd.args(["--publish", port.value()]);
+    /// Determines the network of the container. For the network mode `cluster`, a network on top of the cluster bridge is created, if necessary.
+    async fn prepare_network(&self) -> Result<ContainerNetwork, Error> {
+        match self.config.network_mode {
+            ContainerNetworkMode::Host => Ok(ContainerNetwork::Host),
+            ContainerNetworkMode::Bridge => Ok(ContainerNetwork::Bridge),
+            ContainerNetworkMode::Cluster => {
+                let bridge = self.config.cluster_bridge.as_ref()
+                    .ok_or_else(|| Error::Other { message: format!("Container '{}' should be attached to the cluster network, but the peer is not configured with a cluster bridge.", self.config.name) })?;
+                let network = cluster_network_name(bridge);
+                self.engine.ensure_bridge_network(&network, &bridge.name()).await?;
+                Ok(ContainerNetwork::Named(network))
+            }
         }
-        for volume in &self.config.volumes {
-            cmd.args(["--volume", volume.value()]);
+    }
+
+    /// Creates and starts the container under the first free name of the form `<name>-<counter>`.
+    /// The container is created from the ID of the prepared image, so that it runs exactly the verified image.
+    async fn start_container(&mut self, image: &ImageInfo, network: ContainerNetwork) -> Result<ContainerId, Error> {
+        let spec = self.container_spec(&image.id, network);
+
+        let mut counter = 1;
+        loop {
+            let container_name = format!("{}-{}", self.config.name, counter);
+            match self.engine.create_container(&container_name, &spec).await {
+                Ok(Some(container_id)) => {
+                    self.engine.start_container(&container_id).await
+                        .map_err(|cause| Error::Other { message: format!("Starting container '{}' failed: {}", self.config.name, cause) })?;
+                    info!("Started container {} as '{}'.", self.config.name, container_name);
+                    return Ok(container_id);
+                }
+                Ok(None) => counter += 1,
+                Err(cause) => return Err(Error::Other { message: format!("Creating container '{}' failed: {}", self.config.name, cause) }),
+            }
         }
-        for device in &self.config.devices {
-            cmd.args(["--devices", device.value()]);
+    }
+
+    fn container_spec(&self, image_id: &str, network: ContainerNetwork) -> ContainerSpec {
+        let mut mounts = vec![ContainerMount {
+            source: self.results_dir.path().to_path_buf(),
+            target: PathBuf::from(CONTAINER_RESULTS_DIRECTORY),
+            read_only: false,
+        }];
+        if ! self.config.can_databases.is_empty() {
+            mounts.push(ContainerMount {
+                source: Clone::clone(&self.can_databases_dir),
+                target: PathBuf::from(CONTAINER_CAN_DATABASES_DIRECTORY),
+                read_only: true,
+            });
         }
 
-        cmd.arg(self.config.image.to_string());
+        let command = match &self.config.command {
+            ContainerCommand::Value(command) => Some(Clone::clone(command)),
+            ContainerCommand::Default => None,
+        };
 
-        if let ContainerCommand::Value(command) = &self.config.command {
-            cmd.arg(command.as_str());
+        ContainerSpec {
+            image: image_id.to_owned(),
+            command: command.into_iter()
+                .chain(self.config.args.iter().map(ToString::to_string))
+                .collect(),
+            envs: self.config.envs.iter()
+                .map(|env| format!("{}={}", env.name(), env.value()))
+                .collect(),
+            binds: self.config.volumes.iter()
+                .map(|volume| match volume.secret_source() {
+                    Some((secret_name, remainder)) => format!("{}:{remainder}", self.secrets_dir.join(secret_name.value()).display()),
+                    None => volume.value().to_owned(),
+                })
+                .collect(),
+            mounts,
+            devices: self.config.devices.iter()
+                .map(|device| ContainerDeviceMapping::parse(device.value()))
+                .collect(),
+            ports: self.config.ports.iter()
+                .map(|port| ContainerPortMapping::parse(port.value()))
+                .collect(),
+            network,
+            nano_cpus: self.config.cpus.map(|cpus| cpus.nano_cpus()),
+            memory_bytes: self.config.memory.map(|memory| memory.bytes()),
+            pids_limit: self.config.pids_limit.map(|pids_limit| pids_limit.value()),
+            cap_add: self.config.cap_add.iter().map(ToString::to_string).collect(),
+            cap_drop: self.config.cap_drop.iter().map(ToString::to_string).collect(),
+            read_only_rootfs: self.config.read_only_rootfs,
         }
+    }
 
-        for arg in &self.config.args {
-            cmd.arg(arg.to_string());
+    async fn upload_results(&mut self) -> Result<(), Error> {
+        let results_file = self.results_dir.upload(&self.result_sinks, &self.config.results_url, &String::from(&self.config.name)).await?;
+        if let Some(results_file) = results_file {
+            self.reporter.results_uploaded(results_file).await;
         }
-        let output = cmd.output()
+        Ok(())
+    }
+
+    async fn create_can_databases_dir(&self) -> Result<(), Error> {
+        if self.config.can_databases.is_empty() {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.can_databases_dir)
             .await
-            .map_err(|cause| Error::CommandLineProgramExecution { command: format!("{} run", &self.config.engine.command_name()), cause })?;
+            .map_err(|cause| Error::Other { message: format!("Failed to create CAN databases directory '{}': {}", self.can_databases_dir.to_string_lossy(), cause) })?;
+
+        for can_database in &self.config.can_databases {
+            let file = self.can_databases_dir.join(can_database.descriptor.file_name());
+            fs::write(&file, &can_database.content)
+                .await
+                .map_err(|cause| Error::Other { message: format!("Failed to write CAN database '{}' to '{}': {}", can_database.descriptor.name, file.to_string_lossy(), cause) })?;
+            info!("Provided CAN database '{}' <{}> to container {} as '{}'.", can_database.descriptor.name, can_database.descriptor.id, self.config.name, can_database.descriptor.file_name());
+        }
+        Ok(())
+    }
 
-        if output.status.success() {
-            info!("Started container {}", self.config.name);
-            Ok(container_name)
-        } else {
-            Err(Error::Other { message: format!("Starting container '{}' failed: {}", self.config.name, String::from_utf8_lossy(&output.stderr)).to_string()})
+    async fn cleanup_can_databases_dir(&self) -> Result<(), Error> {
+        if self.config.can_databases.is_empty() {
+            return Ok(());
         }
+        fs::remove_dir_all(&self.can_databases_dir)
+            .await
+            .map_err(|cause| Error::Other { message: format!("Failed to remove CAN databases directory '{}': {}", self.can_databases_dir.to_string_lossy(), cause) })?;
+        Ok(())
+    }
 
+    /// Writes the secrets into a directory, which only EDGAR may access. The files themselves are readable by everyone,
+    /// so that the container can read them with any user, since it only sees the files bound into it.
+    async fn create_secrets_dir(&self) -> Result<(), Error> {
+        if self.config.secrets.is_empty() {
+            return Ok(());
+        }
+        fs::create_dir_all(&self.secrets_dir)
+            .await
+            .map_err(|cause| Error::Other { message: format!("Failed to create secrets directory '{}': {}", self.secrets_dir.to_string_lossy(), cause) })?;
+        fs::set_permissions(&self.secrets_dir, std::fs::Permissions::from_mode(0o700))
+            .await
+            .map_err(|cause| Error::Other { message: format!("Failed to restrict permissions of secrets directory '{}': {}", self.secrets_dir.to_string_lossy(), cause) })?;
+
+        for secret in &self.config.secrets {
+            let file = self.secrets_dir.join(secret.name.value());
+            fs::write(&file, &secret.value)
+                .await
+                .map_err(|cause| Error::Other { message: format!("Failed to write secret '{}' to '{}': {}", secret.name, file.to_string_lossy(), cause) })?;
+            fs::set_permissions(&file, std::fs::Permissions::from_mode(0o444))
+                .await
+                .map_err(|cause| Error::Other { message: format!("Failed to set permissions of secret file '{}': {}", file.to_string_lossy(), cause) })?;
+            info!("Provided secret '{}' to container {}.", secret.name, self.config.name);
+        }
+        Ok(())
     }
 
-    async fn check_container_name_exists(&self, name: &str) -> Result<bool, Error>{
-        let output = Command::new(self.config.engine.command_name())
-            .args(["container", "inspect", name])
-            .output()
+    async fn cleanup_secrets_dir(&self) -> Result<(), Error> {
+        if self.config.secrets.is_empty() || !self.secrets_dir.exists() {
+            return Ok(());
+        }
+        fs::remove_dir_all(&self.secrets_dir)
             .await
-            .map_err(|cause| Error::CommandLineProgramExecution { command: format!("{} inspect", &self.config.engine.command_name()), cause })?;
+            .map_err(|cause| Error::Other { message: format!("Failed to remove secrets directory '{}': {}", self.secrets_dir.to_string_lossy(), cause) })?;
+        Ok(())
+    }
+
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("{0}")]
+    Engine(#[from] container_engine::Error),
+    #[error("{0}")]
+    Results(#[from] results::Error),
+    #[error("Image '{image}' does not match the pinned digest '{expected}'. Digests of the image: [{}]", actual.join(", "))]
+    ImageDigestMismatch { image: ContainerImage, expected: ContainerImageDigest, actual: Vec<String> },
+    #[error("{message}")]
+    Other { message: String },
+}
+
+/// Name of the network, which EDGAR creates on top of the cluster bridge for containers with the network mode `cluster`.
+fn cluster_network_name(bridge: &NetworkInterfaceName) -> String {
+    format!("opendut-{bridge}")
+}
+
+/// Forwards the output of the container into the log of EDGAR and to CARL.
+struct ContainerLogReader {
+    reader: JoinHandle<()>,
+}
+
+impl ContainerLogReader {
+    pub fn create(mut logs: BoxStream<'static, Result<ContainerLogLine, container_engine::Error>>, container_name: String, log_sender: ExecutorLogSender) -> Self {
+        let reader = tokio::spawn(async move {
+            while let Some(line) = logs.next().await {
+                match line {
+                    Ok(ContainerLogLine { stream, line }) => {
+                        let (timestamp, line) = split_log_timestamp(&line);
+                        info!("[{container_name}/{stream}] {line}");
+                        log_sender.send(stream, timestamp, line.to_owned());
+                    }
+                    Err(cause) => {
+                        error!("Error reading output of container {container_name}: {cause}");
+                        break;
+                    }
+                }
+            }
+        });
 
-        Ok(output.status.success())
+        Self { reader }
     }
 
-    async fn stop_container(&self, container_name: &String) -> Result<(), Error>{
-        let output = Command::new(self.config.engine.command_name())
-            .args(["stop", container_name])
-            .output()
-            .await
-            .map_err(|cause| Error::CommandLineProgramExecution { command: format!("{} stop", &self.config.engine.command_name()), cause })?;
+    /// Waits until the remaining output has been forwarded, which happens when the engine notices that the container exited.
+    async fn finish(mut self) {
+        if tokio::time::timeout(LOG_READER_FINISH_TIMEOUT, &mut self.reader).await.is_err() {
+            warn!("Log output of container did not end within {} seconds after it exited.", LOG_READER_FINISH_TIMEOUT.as_secs());
+            self.reader.abort();
+        }
+    }
+}
+
+/// Splits off the RFC 3339 timestamp, which the container engine prefixes each line with, when called with `--timestamps`.
+/// Falls back to the current time, if the line has no such prefix.
+fn split_log_timestamp(line: &str) -> (SystemTime, &str) {
+    line.split_once(' ')
+        .and_then(|(timestamp, rest)| {
+            chrono::DateTime::parse_from_rfc3339(timestamp).ok()
+                .map(|timestamp| (SystemTime::from(timestamp), rest))
+        })
+        .unwrap_or((SystemTime::now(), line))
+}
 
-        match output.status.success() {
-            true => Ok(()),
-            false => Err(Error::Other { message: format!("Stopping container failed: {}", String::from_utf8_lossy(&output.stderr)).to_string()})
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+    use std::time::{Duration, UNIX_EPOCH};
+
+    use googletest::prelude::*;
+    use tokio::sync::mpsc;
+
+    use opendut_carl_api::proto::services::peer_messaging_broker;
+    use opendut_types::peer::configuration::ParameterId;
+    use opendut_types::peer::executor::container::ContainerRegistryCredentialName;
+    use opendut_types::peer::executor::run::{ExecutorLogLine, ExecutorRun, ExecutorRunState};
+    use opendut_types::peer::PeerId;
+    use opendut_types::secret::SecretName;
+
+    use crate::service::test_execution::container_engine::fake::FakeContainerEngine;
+    use crate::service::test_execution::result_sink::ResultSinks;
+
+    use super::*;
+
+    fn container_configuration() -> ContainerConfiguration {
+        ContainerConfiguration {
+            name: ContainerName::Value(String::from("nmap-scan")),
+            engine: Engine::Docker,
+            image: ContainerImage::try_from("testenv-nmap").unwrap(),
+            pull_policy: ContainerImagePullPolicy::IfNotPresent,
+            digest: None,
+            registry_credential: None,
+            command: ContainerCommand::Value(String::from("nmap")),
+            args: vec![ContainerCommandArgument::try_from("-A").unwrap()],
+            envs: vec![ContainerEnvironmentVariable::new("TARGET", "10.0.0.1").unwrap()],
+            ports: vec![],
+            devices: vec![],
+            volumes: vec![],
+            results_url: None,
+            can_databases: vec![],
+            secrets: vec![],
+            cpus: None,
+            memory: None,
+            pids_limit: None,
+            network_mode: ContainerNetworkMode::Host,
+            cap_add: vec![],
+            cap_drop: vec![],
+            read_only_rootfs: false,
+            cluster_bridge: None,
         }
+    }
 
+    async fn run_container(engine: &Arc<FakeContainerEngine>, terminate: bool) -> (Vec<ExecutorRun>, Vec<ExecutorLogLine>) {
+        run_container_with(container_configuration(), engine, terminate).await
     }
 
-    async fn remove_result_ready_indicator(&self) -> Result<(), Error>{ 
-        let indicator_file = self.results_dir.join(RESULTS_READY_FILE);
-        match fs::remove_file(&indicator_file).await {
-            Ok(_) => Ok(()),
-            Err(err) => match err.kind() {
-                ErrorKind::NotFound => Ok(()),
-                _ => Err(Error::Other { message: format!("Failed to remove result indicator file '{}': {}", indicator_file.to_string_lossy(), err) }),
-            },
+    async fn run_container_with(config: ContainerConfiguration, engine: &Arc<FakeContainerEngine>, terminate: bool) -> (Vec<ExecutorRun>, Vec<ExecutorLogLine>) {
+        let (tx_outbound, mut rx_outbound) = mpsc::channel(64);
+        let reporter = ExecutorRunReporter::new(PeerId::random(), ParameterId(Uuid::new_v4()), String::from("nmap-scan"), tx_outbound);
+        let (tx_termination, rx_termination) = watch::channel(false);
+        if terminate {
+            tx_termination.send_replace(true);
+        }
+
+        let engine: ContainerEngineRef = Arc::clone(engine) as _;
+        ContainerManager::new(config, engine, ResultSinks::unauthenticated(), reporter, rx_termination).start().await;
+
+        let mut runs = Vec::new();
+        let mut log_lines = Vec::new();
+        while let Ok(upstream) = rx_outbound.try_recv() {
+            match upstream.message {
+                Some(peer_messaging_broker::upstream::Message::ExecutorRunUpdate(update)) => {
+                    runs.push(ExecutorRun::try_from(update.run.unwrap()).unwrap());
+                }
+                Some(peer_messaging_broker::upstream::Message::ExecutorLogs(logs)) => {
+                    log_lines.extend(logs.lines.into_iter().map(|line| ExecutorLogLine::try_from(line).unwrap()));
+                }
+                _ => {}
+            }
         }
+        (runs, log_lines)
+    }
+
+    #[tokio::test]
+    async fn should_create_the_container_under_a_free_name_and_report_its_exit() -> anyhow::Result<()> {
+        let engine = Arc::new(FakeContainerEngine::new()
+            .with_existing_container("nmap-scan-1")
+            .with_missing_image("testenv-nmap")
+            .running_for(1)
+            .exiting_with(3)
+            .with_logs(vec![ContainerLogLine {
+                stream: ExecutorLogStream::Stdout,
+                line: String::from("2024-05-01T12:00:00.500000000Z Starting scan"),
+            }]));
+
+        let (runs, log_lines) = run_container(&engine, false).await;
+
+        let states = runs.iter().map(|run| run.state).collect::<Vec<_>>();
+        assert_that!(states, elements_are![eq(ExecutorRunState::Pending), eq(ExecutorRunState::Running), eq(ExecutorRunState::Failed)]);
+        assert_that!(runs.last().unwrap().exit_code, some(eq(3)));
+
+        assert_that!(engine.pulled_images(), elements_are![eq("testenv-nmap")]);
+        let containers = engine.containers();
+        assert_that!(containers.len(), eq(1));
+        let container = &containers[0];
+        assert_that!(container.name, eq("nmap-scan-2"));
+        assert_that!(container.spec.command, elements_are![eq("nmap"), eq("-A")]);
+        assert_that!(container.spec.envs, elements_are![eq("TARGET=10.0.0.1")]);
+        assert!(container.spec.mounts.iter().any(|mount| mount.target.as_path() == std::path::Path::new(CONTAINER_RESULTS_DIRECTORY)));
+
+        let lines = log_lines.iter().map(|line| line.line.as_str()).collect::<Vec<_>>();
+        assert_that!(lines, elements_are![eq("Starting scan")]);
+        assert_that!(log_lines[0].timestamp, eq(UNIX_EPOCH + Duration::from_millis(1_714_564_800_500)));
 
+        Ok(())
     }
 
-    async fn upload_results(&self) -> Result<(), Error>{
-        info!("Starting upload for results of {}", self.config.name);
-        let results_url = match &self.config.results_url {
-            Some(results_url) => results_url.value(),
-            None => {
-                info!("Container {} has no results URL, won't upload results.", self.config.name);
-                return Ok(());
-            },
+    #[tokio::test]
+    async fn should_pull_the_pinned_image_with_the_registry_credential_and_report_its_digest() -> anyhow::Result<()> {
+        let digest = ContainerImageDigest::try_from(format!("sha256:{}", "0123456789abcdef".repeat(4)))?;
+        let engine = Arc::new(FakeContainerEngine::new()
+            .with_image_digest("testenv-nmap", digest.value()));
+        let config = ContainerConfiguration {
+            pull_policy: ContainerImagePullPolicy::Always,
+            digest: Some(Clone::clone(&digest)),
+            registry_credential: Some(ContainerRegistryCredential {
+                name: ContainerRegistryCredentialName::try_from("ci-registry")?,
+                server: String::from("registry.example.com"),
+                username: String::from("ci"),
+                password: String::from("secret"),
+            }),
+            ..container_configuration()
         };
-        
-        let mut zipped_data = Vec::new();
-        // https://github.com/zip-rs/zip2/issues/195 large_file(true) produces invalid zip file with crate version 2.1.3
-        let zip_options = SimpleFileOptions::default().compression_method(CompressionMethod::BZIP2).large_file(false);
-        create_zip_from_directory(&mut zipped_data, &self.results_dir, zip_options).await.map_err(|cause| Error::ResultZipping { path: self.results_dir.clone(), cause })?;
 
-        self.webdav_client.create_collection_path(results_url.clone())
-            .await
-            .map_err(|cause| Error::ResultUploadingInternal { url: results_url.clone(), cause })?;
+        let (runs, _) = run_container_with(config, &engine, false).await;
 
-        let results_file_url = results_url.join(
-            format!("{}_{}.zip", chrono::offset::Local::now().format("%Y-%m-%d_%H-%M-%S"), self.config.name).as_str()
-        ).map_err(|cause| Error::Other { message: format!("Failed to construct URL for results directory: {}", cause) })?;
+        let finished_run = runs.last().unwrap();
+        assert_that!(finished_run.state, eq(ExecutorRunState::Succeeded));
+        assert_that!(finished_run.image_digest, some(eq(digest)));
+        assert_that!(engine.pull_credentials(), eq(vec![Some(ContainerRegistryCredentialName::try_from("ci-registry")?)]));
+        assert_that!(engine.containers()[0].spec.image, eq(FakeContainerEngine::image_id("testenv-nmap")));
 
-        let response = self.webdav_client.put(zipped_data, results_file_url.clone())
-            .await
-            .map_err(|cause| Error::ResultUploadingInternal { url: results_file_url.clone(), cause })?;
-
-        match response.status().is_success() {
-            true => {
-                info!("Successfully uploaded results of {}", self.config.name);
-                Ok(())
-            },
-            false => Err(Error::ResultUploadingServer { container_name: self.config.name.clone(), url: results_file_url.clone(), status: response.status() }),
-        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_not_start_an_image_which_does_not_match_the_pinned_digest() -> anyhow::Result<()> {
+        let engine = Arc::new(FakeContainerEngine::new()
+            .with_image_digest("testenv-nmap", &format!("sha256:{}", "f".repeat(64))));
+        let config = ContainerConfiguration {
+            digest: Some(ContainerImageDigest::try_from(format!("sha256:{}", "0123456789abcdef".repeat(4)))?),
+            ..container_configuration()
+        };
+
+        let (runs, _) = run_container_with(config, &engine, false).await;
+
+        let states = runs.iter().map(|run| run.state).collect::<Vec<_>>();
+        assert_that!(states, elements_are![eq(ExecutorRunState::Pending), eq(ExecutorRunState::Failed)]);
+        assert!(engine.containers().is_empty());
 
+        Ok(())
     }
 
-    async fn create_results_dir(&mut self) -> Result<(), Error>{
-        fs::create_dir_all(&self.results_dir)
-            .await
-            .map_err(|cause| Error::Other { message: format!("Failed to create results directory '{}': {}", self.results_dir.to_string_lossy(), cause) })?;
+    #[tokio::test]
+    async fn should_not_pull_a_missing_image_with_pull_policy_never() -> anyhow::Result<()> {
+        let engine = Arc::new(FakeContainerEngine::new()
+            .with_missing_image("testenv-nmap"));
+        let config = ContainerConfiguration {
+            pull_policy: ContainerImagePullPolicy::Never,
+            ..container_configuration()
+        };
+
+        let (runs, _) = run_container_with(config, &engine, false).await;
+
+        assert_that!(runs.last().unwrap().state, eq(ExecutorRunState::Failed));
+        assert!(engine.pulled_images().is_empty());
+        assert!(engine.containers().is_empty());
+
         Ok(())
     }
 
-    async fn cleanup_results_dir(&self) -> Result<(), Error> {
-        fs::remove_dir_all(&self.results_dir)
-            .await
-            .map_err(|cause| Error::Other { message: format!("Failed to remove results directory '{}': {}", self.results_dir.to_string_lossy(), cause) })?;
+    #[tokio::test]
+    async fn should_attach_the_container_to_the_cluster_network_with_its_limits() -> anyhow::Result<()> {
+        let engine = Arc::new(FakeContainerEngine::new());
+        let config = ContainerConfiguration {
+            cpus: Some(ContainerCpuLimit::try_from("0.5")?),
+            memory: Some(ContainerMemoryLimit::try_from("256m")?),
+            pids_limit: Some(ContainerPidsLimit::try_from(64)?),
+            network_mode: ContainerNetworkMode::Cluster,
+            cap_drop: vec![ContainerCapability::try_from("ALL")?],
+            read_only_rootfs: true,
+            cluster_bridge: Some(NetworkInterfaceName::try_from("br-opendut")?),
+            ..container_configuration()
+        };
+
+        let (runs, _) = run_container_with(config, &engine, false).await;
+
+        assert_that!(runs.last().unwrap().state, eq(ExecutorRunState::Succeeded));
+        assert_that!(engine.networks().get("opendut-br-opendut").map(String::as_str), some(eq("br-opendut")));
+        let spec = &engine.containers()[0].spec;
+        assert_that!(spec.network, eq(ContainerNetwork::Named(String::from("opendut-br-opendut"))));
+        assert_that!(spec.nano_cpus, some(eq(500_000_000)));
+        assert_that!(spec.memory_bytes, some(eq(256 * 1024 * 1024)));
+        assert_that!(spec.pids_limit, some(eq(64)));
+        assert_that!(spec.cap_drop, eq(vec![String::from("ALL")]));
+        assert!(spec.read_only_rootfs);
+
         Ok(())
     }
 
-    async fn are_results_ready(&self) -> Result<bool, Error> {
-        let indicator_file = self.results_dir.join(RESULTS_READY_FILE);
-        Ok(indicator_file.is_file())
+    #[tokio::test]
+    async fn should_fail_to_attach_the_container_to_the_cluster_network_without_a_cluster_bridge() -> anyhow::Result<()> {
+        let engine = Arc::new(FakeContainerEngine::new());
+        let config = ContainerConfiguration {
+            network_mode: ContainerNetworkMode::Cluster,
+            ..container_configuration()
+        };
+
+        let (runs, _) = run_container_with(config, &engine, false).await;
+
+        assert_that!(runs.last().unwrap().state, eq(ExecutorRunState::Failed));
+        assert!(engine.containers().is_empty());
+
+        Ok(())
     }
 
-}
+    #[tokio::test]
+    async fn should_report_an_out_of_memory_kill_in_the_log() -> anyhow::Result<()> {
+        let engine = Arc::new(FakeContainerEngine::new()
+            .exiting_with(137)
+            .oom_killed());
 
-async fn create_zip_from_directory<T>(data: &mut Vec<u8>, directory: &PathBuf, file_options: FileOptions<'_, T>) -> Result<()> 
-    where
-        T: FileOptionExtension + std::marker::Copy,
-    {
-        let mut file_buffer = Vec::new();
-        let zip_buffer = Cursor::new(data);
-        let mut zip = ZipWriter::new(zip_buffer);
-
-        for entry_res in WalkDir::new(directory) {
-            let entry = entry_res?;
-            let entry_path = entry.path();
-            let entry_metadata = entry.metadata()?;
-
-            if entry_metadata.is_file() {
-                let mut f = File::open(&entry_path).await?;
-                f.read_to_end(&mut file_buffer).await?;
-                let relative_path = entry_path.strip_prefix(directory)?;
-                zip.start_file(relative_path.to_string_lossy(), file_options)?;
-                zip.write_all(file_buffer.as_ref())?;
-                file_buffer.clear();
-            } else if entry_metadata.is_dir() {
-                let relative_path = entry_path.strip_prefix(directory)?;
-                zip.add_directory(relative_path.to_string_lossy(), file_options)?;
-            }
-        }
+        let (runs, log_lines) = run_container(&engine, false).await;
+
+        let finished_run = runs.last().unwrap();
+        assert_that!(finished_run.state, eq(ExecutorRunState::Failed));
+        assert_that!(finished_run.exit_code, some(eq(137)));
+        assert!(log_lines.iter().any(|line| line.stream == ExecutorLogStream::Stderr && line.line.contains("ran out of memory")));
 
         Ok(())
     }
 
-#[derive(Debug, thiserror::Error)]
-pub enum Error {
-    #[error("Failure while invoking command line program '{command}': {cause}")]
-    CommandLineProgramExecution { command: String, cause: std::io::Error },
-    #[error("Failure while creating a ZIP archive of the test results at '{path}' : {cause}")]
-    ResultZipping { path: PathBuf, cause: anyhow::Error },
-    #[error("Failure while uploading test results to '{url}': {cause}")]
-    ResultUploadingInternal { url: Url, cause: webdav_client::Error },
-    #[error("Failure while uploading test results for '{container_name}' to '{url}' (HTTP status {status})")]
-    ResultUploadingServer { container_name: ContainerName, url: Url, status: reqwest::StatusCode },
-    #[error("{message}")]
-    Other { message: String },
-}
+    #[tokio::test]
+    async fn should_stop_the_container_when_terminated() -> anyhow::Result<()> {
+        let engine = Arc::new(FakeContainerEngine::new()
+            .running_for(usize::MAX));
 
-struct ContainerLogReader {
-    _log_proc: Child,
-    receiver: mpsc::Receiver<Vec<u8>>,
-}
+        let (runs, _) = run_container(&engine, true).await;
 
-impl ContainerLogReader {
-    pub fn create(engine: &str, container_name: String) -> Result<Self, Error> {
-        let mut cmd = Command::new(engine);
-        cmd.args(["logs", "--timestamps", "--follow"]);
-        cmd.arg(container_name);
-        cmd.stdout(Stdio::piped());
-        cmd.stderr(Stdio::piped());
-        cmd.kill_on_drop(true);
-
-        let mut child = cmd.spawn()
-            .map_err(|cause| Error::CommandLineProgramExecution { command: format!("{engine} logs"), cause })?;
-
-        let stdout = child.stdout.take().ok_or(Error::Other { message: format!("Failed to get stdout of '{engine} logs' process")})?;
-
-        let mut stdout_reader = BufReader::new(stdout);
-
-        let (tx, rx) = tokio::sync::mpsc::channel::<Vec<u8>>(100);
-
-        tokio::spawn(async move {
-            let mut buffer = Vec::new();
-    
-            loop {
-                match stdout_reader.read_until(b'\n', &mut buffer).await {
-                    Ok(0) => {
-                        // EOF reached
-                        break;
-                    }
-                    Ok(_) => {
-                        let _ = tx.send(buffer.clone()).await;
-                        buffer.clear();
-                    }
-                    Err(e) => {
-                        error!("Error reading from logs stdout stream: {}", e);
-                        break;
-                    }
-                }
-            }
-        });
-        
+        let finished_run = runs.last().unwrap();
+        assert_that!(finished_run.state, eq(ExecutorRunState::Cancelled));
+        assert!(engine.containers()[0].stopped);
 
-        Ok(
-            Self {
-                _log_proc: child,
-                receiver: rx,
-            }
-        )
+        Ok(())
     }
 
+    #[tokio::test]
+    async fn should_bind_secrets_as_files_and_remove_them_afterwards() -> anyhow::Result<()> {
+        let secret = Secret {
+            name: SecretName::try_from("ca-cert")?,
+            value: String::from("-----BEGIN CERTIFICATE-----"),
+        };
+        let config = ContainerConfiguration {
+            volumes: vec![
+                ContainerVolume::try_from("${secret:ca-cert}:/etc/ssl/ca.pem:ro")?,
+                ContainerVolume::try_from("/data:/data")?,
+            ],
+            secrets: vec![Clone::clone(&secret)],
+            ..container_configuration()
+        };
+        let (tx_outbound, _rx_outbound) = mpsc::channel(64);
+        let reporter = ExecutorRunReporter::new(PeerId::random(), ParameterId(Uuid::new_v4()), String::from("nmap-scan"), tx_outbound);
+        let (_tx_termination, rx_termination) = watch::channel(false);
+        let engine: ContainerEngineRef = Arc::new(FakeContainerEngine::new());
+        let mut manager = ContainerManager::new(config, engine, ResultSinks::unauthenticated(), reporter, rx_termination);
 
-    async fn read(&mut self) {
-        while let Ok(line) = self.receiver.try_recv() {
-            info!("Received line: {:?}", String::from_utf8_lossy(&line))
-        }
+        manager.create_secrets_dir().await?;
+
+        let secret_file = manager.secrets_dir.join("ca-cert");
+        assert_that!(std::fs::read_to_string(&secret_file)?, eq(&secret.value));
+        assert_that!(std::fs::metadata(&manager.secrets_dir)?.permissions().mode() & 0o777, eq(0o700));
+
+        let spec = manager.container_spec("image", ContainerNetwork::Host);
+        assert_that!(spec.binds, elements_are![
+            eq(&format!("{}:/etc/ssl/ca.pem:ro", secret_file.display())),
+            eq("/data:/data"),
+        ]);
+
+        manager.start().await;
+        assert!(!manager.secrets_dir.exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_split_the_timestamp_off_a_log_line() {
+        let (timestamp, line) = split_log_timestamp("2024-05-01T12:00:00.500000000Z Starting scan: 10.0.0.1");
+
+        assert_that!(line, eq("Starting scan: 10.0.0.1"));
+        assert_that!(timestamp, eq(UNIX_EPOCH + Duration::from_millis(1_714_564_800_500)));
+    }
+
+    #[test]
+    fn should_keep_log_lines_without_timestamp() {
+        let (_, line) = split_log_timestamp("Starting scan: 10.0.0.1");
+
+        assert_that!(line, eq("Starting scan: 10.0.0.1"));
     }
-    
 }
@@ -0,0 +1,405 @@
+use std::process::{ExitStatus, Stdio};
+use std::time::{Duration, SystemTime};
+
+use nix::sys::signal::{killpg, Signal};
+use nix::unistd::{chown, Pid, User};
+use tokio::{io::{AsyncBufReadExt, AsyncRead, BufReader}, process::{Child, Command}, sync::watch, task::JoinHandle};
+use tracing::{error, info, warn};
+use uuid::Uuid;
+
+use opendut_types::peer::executor::{executable::{ExecutableArgument, ExecutableEnvironmentVariable, ExecutableName, ExecutableProgram, ExecutableUser, ExecutableWorkingDirectory}, ResultsUrl};
+use opendut_types::peer::executor::run::ExecutorLogStream;
+
+use crate::service::test_execution::executor_logs::ExecutorLogSender;
+use crate::service::test_execution::executor_run::ExecutorRunReporter;
+use crate::service::test_execution::result_sink::ResultSinksRef;
+use crate::service::test_execution::results::{self, ResultsDirectory};
+
+pub struct ExecutableConfiguration {
+    pub name: ExecutableName,
+    pub program: ExecutableProgram,
+    pub args: Vec<ExecutableArgument>,
+    pub envs: Vec<ExecutableEnvironmentVariable>,
+    pub working_directory: Option<ExecutableWorkingDirectory>,
+    pub user: Option<ExecutableUser>,
+    pub results_url: Option<ResultsUrl>,
+}
+
+/// Runs an executable as a child process of EDGAR and supervises it until it exits or is terminated.
+pub struct ExecutableManager {
+    config: ExecutableConfiguration,
+    results_dir: ResultsDirectory,
+    result_sinks: ResultSinksRef,
+    reporter: ExecutorRunReporter,
+    termination_channel_rx: watch::Receiver<bool>,
+}
+
+const MONITOR_INTERVAL_MS: u64 = 1000;
+/// Time the executable has to exit after receiving SIGTERM, before it is killed.
+const TERMINATION_GRACE_PERIOD: Duration = Duration::from_secs(10);
+/// Environment variable, which tells the executable where to put its results.
+const RESULTS_DIRECTORY_ENV: &str = "OPENDUT_RESULTS_DIR";
+
+impl ExecutableManager {
+
+    pub fn new(executable_configuration: ExecutableConfiguration, result_sinks: ResultSinksRef, reporter: ExecutorRunReporter, termination_channel_rx: watch::Receiver<bool>) -> Self {
+        Self {
+            config: executable_configuration,
+            results_dir: ResultsDirectory::new(Uuid::new_v4()),
+            result_sinks,
+            reporter,
+            termination_channel_rx,
+        }
+    }
+
+    pub async fn start(&mut self) {
+        match self.run().await {
+            Ok(_) => (),
+            Err(cause) => {
+                error!("{}", cause.to_string());
+                self.reporter.failed().await;
+            }
+        }
+    }
+
+    async fn run(&mut self) -> Result<(), Error> {
+        let mut results_uploaded = false;
+        let mut cancelled = false;
+
+        self.reporter.pending().await;
+
+        let user = self.resolve_user()?;
+
+        self.results_dir.create().await?;
+        if let Some(user) = &user {
+            chown(self.results_dir.path(), Some(user.uid), Some(user.gid))
+                .map_err(|cause| Error::Other { message: format!("Failed to hand over results directory '{}' to user '{}': {}", self.results_dir.path().to_string_lossy(), user.name, cause) })?;
+        }
+
+        let mut child = self.start_executable(user.as_ref())?;
+        let log_forwarder = self.reporter.log_forwarder();
+        let log_readers = ExecutableLogReader::create(&mut child, &self.config.name, log_forwarder.sender());
+        self.reporter.running().await;
+
+        let exit_status = loop {
+            tokio::select! {
+                exit_status = child.wait() => {
+                    break exit_status
+                        .map_err(|cause| Error::CommandLineProgramExecution { command: self.config.program.to_string(), cause })?;
+                }
+                // If the value in the channel has changed or the channel has been closed, we terminate
+                _ = self.termination_channel_rx.changed() => {
+                    cancelled = true;
+                    break self.stop_executable(&mut child).await?;
+                }
+                _ = tokio::time::sleep(Duration::from_millis(MONITOR_INTERVAL_MS)) => {
+                    if self.results_dir.are_results_ready() {
+                        self.results_dir.remove_results_ready_indicator().await?;
+                        self.upload_results().await?;
+                        results_uploaded = true;
+                    }
+                }
+            }
+        };
+
+        log_readers.finish().await;
+        log_forwarder.close().await;
+
+        if exit_status.success() {
+            info!("Executable {} exited successfully.", self.config.name);
+        } else {
+            warn!("Executable {} exited with {}.", self.config.name, exit_status);
+        }
+
+        if ! results_uploaded {
+            self.results_dir.remove_results_ready_indicator().await?;
+            self.upload_results().await?;
+        }
+
+        self.results_dir.cleanup().await?;
+
+        if cancelled {
+            self.reporter.cancelled(exit_status.code()).await;
+        } else {
+            self.reporter.exited(exit_status.code()).await;
+        }
+
+        Ok(())
+    }
+
+    fn resolve_user(&self) -> Result<Option<User>, Error> {
+        match &self.config.user {
+            None => Ok(None),
+            Some(user) => {
+                let resolved = User::from_name(user.value())
+                    .map_err(|cause| Error::Other { message: format!("Failed to look up user '{}' for executable {}: {}", user, self.config.name, cause) })?
+                    .ok_or_else(|| Error::Other { message: format!("User '{}' for executable {} does not exist on this peer.", user, self.config.name) })?;
+                Ok(Some(resolved))
+            }
+        }
+    }
+
+    fn start_executable(&self, user: Option<&User>) -> Result<Child, Error> {
+
+        let mut cmd = Command::new(self.config.program.value());
+        cmd.args(self.config.args.iter().map(ExecutableArgument::value));
+
+        if let Some(user) = user {
+            cmd.uid(user.uid.as_raw());
+            cmd.gid(user.gid.as_raw());
+            cmd.env("USER", &user.name);
+            cmd.env("HOME", &user.dir);
+        }
+        for env in &self.config.envs {
+            cmd.env(env.name(), env.value());
+        }
+        cmd.env(RESULTS_DIRECTORY_ENV, self.results_dir.path());
+
+        if let Some(working_directory) = &self.config.working_directory {
+            cmd.current_dir(working_directory.value());
+        }
+
+        // Run in a separate process group, so that termination also reaches processes spawned by the executable.
+        cmd.process_group(0);
+        cmd.stdin(Stdio::null());
+        cmd.stdout(Stdio::piped());
+        cmd.stderr(Stdio::piped());
+        cmd.kill_on_drop(true);
+
+        let child = cmd.spawn()
+            .map_err(|cause| Error::CommandLineProgramExecution { command: self.config.program.to_string(), cause })?;
+
+        info!("Started executable {}", self.config.name);
+        Ok(child)
+    }
+
+    async fn stop_executable(&self, child: &mut Child) -> Result<ExitStatus, Error> {
+        info!("Stopping executable {}", self.config.name);
+
+        if let Some(process_group) = child.id() {
+            if let Err(cause) = killpg(Pid::from_raw(process_group as i32), Signal::SIGTERM) {
+                warn!("Failed to send SIGTERM to executable {}: {}", self.config.name, cause);
+            }
+        }
+
+        match tokio::time::timeout(TERMINATION_GRACE_PERIOD, child.wait()).await {
+            Ok(exit_status) => exit_status
+                .map_err(|cause| Error::CommandLineProgramExecution { command: self.config.program.to_string(), cause }),
+            Err(_) => {
+                warn!("Executable {} did not exit within {} seconds after SIGTERM. Killing it.", self.config.name, TERMINATION_GRACE_PERIOD.as_secs());
+                if let Some(process_group) = child.id() {
+                    let _ = killpg(Pid::from_raw(process_group as i32), Signal::SIGKILL);
+                }
+                child.kill().await
+                    .map_err(|cause| Error::Other { message: format!("Failed to kill executable {}: {}", self.config.name, cause) })?;
+                child.wait().await
+                    .map_err(|cause| Error::CommandLineProgramExecution { command: self.config.program.to_string(), cause })
+            }
+        }
+    }
+
+    async fn upload_results(&mut self) -> Result<(), Error> {
+        let results_file = self.results_dir.upload(&self.result_sinks, &self.config.results_url, self.config.name.value()).await?;
+        if let Some(results_file) = results_file {
+            self.reporter.results_uploaded(results_file).await;
+        }
+        Ok(())
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failure while invoking command line program '{command}': {cause}")]
+    CommandLineProgramExecution { command: String, cause: std::io::Error },
+    #[error("{0}")]
+    Results(#[from] results::Error),
+    #[error("{message}")]
+    Other { message: String },
+}
+
+/// Forwards the output of the executable into the log of EDGAR and to CARL.
+struct ExecutableLogReader {
+    readers: Vec<JoinHandle<()>>,
+}
+
+impl ExecutableLogReader {
+    fn create(child: &mut Child, name: &ExecutableName, log_sender: ExecutorLogSender) -> Self {
+        let mut readers = Vec::new();
+        if let Some(stdout) = child.stdout.take() {
+            readers.push(Self::forward(stdout, name.clone(), ExecutorLogStream::Stdout, log_sender.clone()));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            readers.push(Self::forward(stderr, name.clone(), ExecutorLogStream::Stderr, log_sender));
+        }
+        Self { readers }
+    }
+
+    fn forward(stream: impl AsyncRead + Unpin + Send + 'static, name: ExecutableName, log_stream: ExecutorLogStream, log_sender: ExecutorLogSender) -> JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut lines = BufReader::new(stream).lines();
+            loop {
+                match lines.next_line().await {
+                    Ok(Some(line)) => {
+                        info!("[{name}/{log_stream}] {line}");
+                        log_sender.send(log_stream, SystemTime::now(), line);
+                    }
+                    Ok(None) => break,
+                    Err(cause) => {
+                        error!("Error reading from {log_stream} of executable {name}: {cause}");
+                        break;
+                    }
+                }
+            }
+        })
+    }
+
+    /// Waits until the remaining output has been forwarded.
+    async fn finish(self) {
+        for reader in self.readers {
+            let _ = reader.await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::ops::Not;
+
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+    use tokio::sync::mpsc;
+
+    use opendut_carl_api::proto::services::peer_messaging_broker;
+    use opendut_types::peer::configuration::ParameterId;
+    use opendut_types::peer::executor::run::{ExecutorLogLine, ExecutorRun, ExecutorRunState};
+    use opendut_types::peer::PeerId;
+
+    use crate::service::test_execution::result_sink::ResultSinks;
+
+    use super::*;
+
+    fn reporter() -> (ExecutorRunReporter, mpsc::Receiver<peer_messaging_broker::Upstream>) {
+        let (tx, rx) = mpsc::channel(16);
+        let reporter = ExecutorRunReporter::new(PeerId::random(), ParameterId(Uuid::new_v4()), String::from("test-executable"), tx);
+        (reporter, rx)
+    }
+
+    fn reported_runs(mut rx: mpsc::Receiver<peer_messaging_broker::Upstream>) -> Vec<ExecutorRun> {
+        let mut runs = Vec::new();
+        while let Ok(upstream) = rx.try_recv() {
+            if let Some(peer_messaging_broker::upstream::Message::ExecutorRunUpdate(update)) = upstream.message {
+                runs.push(ExecutorRun::try_from(update.run.unwrap()).unwrap());
+            }
+        }
+        runs
+    }
+
+    fn reported_log_lines(reports: &[peer_messaging_broker::Upstream]) -> Vec<ExecutorLogLine> {
+        reports.iter()
+            .filter_map(|upstream| match &upstream.message {
+                Some(peer_messaging_broker::upstream::Message::ExecutorLogs(logs)) => Some(logs.lines.clone()),
+                _ => None,
+            })
+            .flatten()
+            .map(|line| ExecutorLogLine::try_from(line).unwrap())
+            .collect()
+    }
+
+    fn executable_configuration(script: &str, working_directory: &TempDir) -> ExecutableConfiguration {
+        ExecutableConfiguration {
+            name: ExecutableName::try_from("test-executable").unwrap(),
+            program: ExecutableProgram::try_from("sh").unwrap(),
+            args: vec![
+                ExecutableArgument::try_from("-c").unwrap(),
+                ExecutableArgument::try_from(script).unwrap(),
+            ],
+            envs: vec![ExecutableEnvironmentVariable::new("GREETING", "hello").unwrap()],
+            working_directory: Some(ExecutableWorkingDirectory::try_from(working_directory.path().to_string_lossy().as_ref()).unwrap()),
+            user: None,
+            results_url: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn should_run_executable_with_environment_and_working_directory() -> anyhow::Result<()> {
+        let working_directory = TempDir::new()?;
+        let config = executable_configuration(r#"echo "$GREETING" > out.txt && test -d "$OPENDUT_RESULTS_DIR" && echo results >> out.txt"#, &working_directory);
+        let (_tx, rx) = watch::channel(false);
+        let (reporter, reports) = reporter();
+
+        let mut manager = ExecutableManager::new(config, ResultSinks::unauthenticated(), reporter, rx);
+        manager.run().await?;
+
+        let output = fs::read_to_string(working_directory.path().join("out.txt"))?;
+        assert_that!(output, eq("hello\nresults\n"));
+        assert!(manager.results_dir.path().exists().not());
+
+        let runs = reported_runs(reports);
+        let states = runs.iter().map(|run| run.state).collect::<Vec<_>>();
+        assert_that!(states, elements_are![eq(ExecutorRunState::Pending), eq(ExecutorRunState::Running), eq(ExecutorRunState::Succeeded)]);
+        let finished_run = runs.last().unwrap();
+        assert_that!(finished_run.exit_code, some(eq(0)));
+        assert!(finished_run.started_at.is_some());
+        assert!(finished_run.finished_at.is_some());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_forward_output_to_carl_before_reporting_the_exit() -> anyhow::Result<()> {
+        let working_directory = TempDir::new()?;
+        let config = executable_configuration("echo first; echo failure >&2; echo second", &working_directory);
+        let (_tx, rx) = watch::channel(false);
+        let (reporter, mut reports) = reporter();
+
+        let mut manager = ExecutableManager::new(config, ResultSinks::unauthenticated(), reporter, rx);
+        manager.run().await?;
+
+        let mut messages = Vec::new();
+        while let Ok(upstream) = reports.try_recv() {
+            messages.push(upstream);
+        }
+
+        let lines = reported_log_lines(&messages);
+        let stdout = lines.iter()
+            .filter(|line| line.stream == ExecutorLogStream::Stdout)
+            .map(|line| line.line.as_str())
+            .collect::<Vec<_>>();
+        assert_that!(stdout, elements_are![eq("first"), eq("second")]);
+        let stderr = lines.iter()
+            .filter(|line| line.stream == ExecutorLogStream::Stderr)
+            .map(|line| line.line.as_str())
+            .collect::<Vec<_>>();
+        assert_that!(stderr, elements_are![eq("failure")]);
+
+        let last_message = messages.last().and_then(|upstream| upstream.message.as_ref());
+        assert!(matches!(last_message, Some(peer_messaging_broker::upstream::Message::ExecutorRunUpdate(_))));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_terminate_executable() -> anyhow::Result<()> {
+        let working_directory = TempDir::new()?;
+        let config = executable_configuration("trap 'echo terminated > out.txt; exit 0' TERM; while true; do sleep 0.1; done", &working_directory);
+        let (tx, rx) = watch::channel(false);
+        let (reporter, reports) = reporter();
+
+        let mut manager = ExecutableManager::new(config, ResultSinks::unauthenticated(), reporter, rx);
+        let run = tokio::spawn(async move { manager.run().await });
+
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        tx.send(true)?;
+
+        tokio::time::timeout(Duration::from_secs(5), run).await???;
+
+        let output = fs::read_to_string(working_directory.path().join("out.txt"))?;
+        assert_that!(output, eq("terminated\n"));
+
+        let runs = reported_runs(reports);
+        assert_that!(runs.last().map(|run| run.state), some(eq(ExecutorRunState::Cancelled)));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,86 @@
+use std::time::SystemTime;
+
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::task::JoinHandle;
+use tracing::warn;
+
+use opendut_carl_api::proto::services::peer_messaging_broker;
+use opendut_types::peer::executor::run::{ExecutorLogLine, ExecutorLogStream, ExecutorRunId};
+
+/// Number of lines, which may queue up while the connection to CARL is slow. Further lines are dropped.
+const LOG_LINE_BUFFER_SIZE: usize = 1000;
+/// Maximum number of lines sent to CARL in a single message.
+const MAX_LINES_PER_MESSAGE: usize = 100;
+
+/// Forwards the output of an executor run to CARL in batches.
+pub struct ExecutorLogForwarder {
+    sender: ExecutorLogSender,
+    task: JoinHandle<()>,
+}
+
+/// Handle for submitting output lines of an executor run, which can be passed to the tasks reading the output.
+#[derive(Clone)]
+pub struct ExecutorLogSender {
+    run_id: ExecutorRunId,
+    tx: mpsc::Sender<ExecutorLogLine>,
+}
+
+impl ExecutorLogForwarder {
+    pub fn new(run_id: ExecutorRunId, tx_outbound: mpsc::Sender<peer_messaging_broker::Upstream>) -> Self {
+        let (tx, mut rx) = mpsc::channel::<ExecutorLogLine>(LOG_LINE_BUFFER_SIZE);
+
+        let task = tokio::spawn(async move {
+            let mut lines = Vec::with_capacity(MAX_LINES_PER_MESSAGE);
+            while rx.recv_many(&mut lines, MAX_LINES_PER_MESSAGE).await > 0 {
+                let message = peer_messaging_broker::Upstream {
+                    context: None,
+                    message: Some(peer_messaging_broker::upstream::Message::ExecutorLogs(
+                        peer_messaging_broker::ExecutorLogs {
+                            lines: lines.drain(..).map(From::from).collect(),
+                        }
+                    )),
+                };
+
+                if let Err(cause) = tx_outbound.send(message).await {
+                    warn!("Failed to forward log of executor run <{run_id}> to CARL: {cause}");
+                    break;
+                }
+            }
+        });
+
+        Self {
+            sender: ExecutorLogSender { run_id, tx },
+            task,
+        }
+    }
+
+    pub fn sender(&self) -> ExecutorLogSender {
+        Clone::clone(&self.sender)
+    }
+
+    /// Waits until all lines submitted so far have been handed over to the connection to CARL.
+    /// All senders obtained from this forwarder must have been dropped beforehand.
+    pub async fn close(self) {
+        let Self { sender, task } = self;
+        drop(sender);
+        let _ = task.await;
+    }
+}
+
+impl ExecutorLogSender {
+    /// Submits a line for forwarding. Never blocks, so that a slow connection to CARL cannot stall the executor.
+    pub fn send(&self, stream: ExecutorLogStream, timestamp: SystemTime, line: String) {
+        let line = ExecutorLogLine {
+            run_id: self.run_id,
+            stream,
+            timestamp,
+            line,
+        };
+        match self.tx.try_send(line) {
+            Ok(()) => {}
+            Err(TrySendError::Full(_)) => warn!("Dropping log line of executor run <{}>, because the connection to CARL is too slow.", self.run_id),
+            Err(TrySendError::Closed(_)) => {}
+        }
+    }
+}
@@ -1,84 +1,493 @@
+use std::collections::HashMap;
 use std::sync::{Arc, Mutex};
+use std::time::SystemTime;
 
-use opendut_types::peer::{self, executor::{ExecutorDescriptor, ExecutorKind}};
+use opendut_carl_api::proto::services::peer_messaging_broker;
+use opendut_types::can::{CanDatabase, CanDatabaseId};
+use opendut_types::peer::{self, PeerId, executor::{ExecutorCommand, ExecutorDescriptor, ExecutorKind}};
+use opendut_types::peer::configuration::ParameterId;
+use opendut_types::peer::executor::container::{ContainerEnvironmentVariable, ContainerRegistryCredential, ContainerRegistryCredentialName, ContainerVolume};
+use opendut_types::peer::executor::run::ExecutorLogStream;
+use opendut_types::secret::{ResolveSecretReferenceError, Secret, SecretName};
+use opendut_types::util::net::NetworkInterfaceName;
+use tokio::sync::mpsc;
 use tokio::sync::watch::{self, Sender};
-use tracing::warn;
+use tokio::task::JoinHandle;
+use tracing::{error, info, warn};
 
+use crate::service::test_execution::container_engine::{ApiContainerEngine, ContainerEngineRef, ContainerEngineSockets};
 use crate::service::test_execution::container_manager::{ContainerManager, ContainerConfiguration};
+use crate::service::test_execution::executable_manager::{ExecutableConfiguration, ExecutableManager};
+use crate::service::test_execution::executor_run::ExecutorRunReporter;
+use crate::service::test_execution::result_sink::ResultSinksRef;
 
 pub type ExecutorManagerRef = Arc<Mutex<ExecutorManager>>;
 
 pub struct ExecutorManager {
-    tx_termination_channels: Vec<Sender<bool>>,
+    self_id: PeerId,
+    container_engine_sockets: ContainerEngineSockets,
+    result_sinks: ResultSinksRef,
+    executors: HashMap<String, (ParameterId, ExecutorDescriptor)>,
+    can_databases: HashMap<CanDatabaseId, CanDatabase>,
+    registry_credentials: HashMap<ContainerRegistryCredentialName, ContainerRegistryCredential>,
+    secrets: HashMap<SecretName, Secret>,
+    cluster_bridge: Option<NetworkInterfaceName>,
+    running: HashMap<String, RunningExecutor>,
+}
+
+struct RunningExecutor {
+    tx_termination: Sender<bool>,
+    handle: JoinHandle<()>,
+    is_job: bool,
+}
+
+impl RunningExecutor {
+    fn is_active(&self) -> bool {
+        !self.handle.is_finished() && !*self.tx_termination.borrow()
+    }
+
+    fn terminate(&self) {
+        self.tx_termination.send_replace(true);
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ExecutorCommandError {
+    #[error("No executor named '{executor_name}' is configured on this peer.")]
+    ExecutorNotFound { executor_name: String },
 }
 
 impl ExecutorManager {
-    pub fn create() -> ExecutorManagerRef {
+    pub fn create(self_id: PeerId, container_engine_sockets: ContainerEngineSockets, result_sinks: ResultSinksRef) -> ExecutorManagerRef {
         Arc::new(Mutex::new(Self {
-            tx_termination_channels: Vec::new(),
+            self_id,
+            container_engine_sockets,
+            result_sinks,
+            executors: HashMap::new(),
+            can_databases: HashMap::new(),
+            registry_credentials: HashMap::new(),
+            secrets: HashMap::new(),
+            cluster_bridge: None,
+            running: HashMap::new(),
         }))
     }
 
-    pub fn create_new_executors(&mut self, executors: Vec<peer::configuration::Parameter<ExecutorDescriptor>>) {
+    /// Replaces the configured executors and starts them. The lifecycle of each run is reported to CARL via `tx_outbound`.
+    /// Executors, which are currently running as a job, are not restarted.
+    pub fn create_new_executors(
+        &mut self,
+        executors: Vec<peer::configuration::Parameter<ExecutorDescriptor>>,
+        can_databases: Vec<peer::configuration::Parameter<CanDatabase>>,
+        registry_credentials: Vec<peer::configuration::Parameter<ContainerRegistryCredential>>,
+        secrets: Vec<peer::configuration::Parameter<Secret>>,
+        cluster_bridge: Option<NetworkInterfaceName>,
+        tx_outbound: &mpsc::Sender<peer_messaging_broker::Upstream>,
+    ) {
+
+        self.cluster_bridge = cluster_bridge;
+
+        self.can_databases = can_databases.into_iter()
+            .filter(|can_database| matches!(can_database.target, peer::configuration::ParameterTarget::Present))
+            .map(|can_database| (can_database.value.descriptor.id, can_database.value))
+            .collect::<HashMap<CanDatabaseId, CanDatabase>>();
+
+        self.registry_credentials = registry_credentials.into_iter()
+            .filter(|registry_credential| matches!(registry_credential.target, peer::configuration::ParameterTarget::Present))
+            .map(|registry_credential| (Clone::clone(&registry_credential.value.name), registry_credential.value))
+            .collect();
+
+        self.secrets = secrets.into_iter()
+            .filter(|secret| matches!(secret.target, peer::configuration::ParameterTarget::Present))
+            .map(|secret| (Clone::clone(&secret.value.name), secret.value))
+            .collect();
+
+        self.executors = executors.into_iter()
+            .filter(|executor| matches!(executor.target, peer::configuration::ParameterTarget::Present)) //TODO properly handle Present vs. Absent
+            .map(|executor| (executor.value.name(), (executor.id, executor.value)))
+            .collect();
+
+        let names = self.executors.keys().cloned().collect::<Vec<_>>();
+        for name in names {
+            let is_running_job = self.running.get(&name)
+                .map(|running| running.is_job && running.is_active())
+                .unwrap_or(false);
+
+            if is_running_job {
+                info!("Not starting executor '{name}', because it is currently running as a job.");
+            } else {
+                self.spawn_executor(&name, false, tx_outbound);
+            }
+        }
+    }
+
+    /// Terminates all running executors, except for those running as a job.
+    pub fn terminate_executors(&mut self) {
+        self.running.retain(|_, running| {
+            if running.is_job && running.is_active() {
+                true
+            } else {
+                running.terminate();
+                false
+            }
+        });
+    }
 
-        let executors = executors.into_iter()
-            .filter_map(|executor| { //TODO properly handle Present vs. Absent
-                if matches!(executor.target, peer::configuration::ParameterTarget::Present) {
-                    Some(executor.value)
+    /// Applies a command to a single configured executor, independent of deploying the peer.
+    pub fn execute_command(
+        &mut self,
+        executor_name: &str,
+        command: ExecutorCommand,
+        tx_outbound: &mpsc::Sender<peer_messaging_broker::Upstream>,
+    ) -> Result<(), ExecutorCommandError> {
+
+        if !self.executors.contains_key(executor_name) {
+            return Err(ExecutorCommandError::ExecutorNotFound { executor_name: executor_name.to_owned() });
+        }
+
+        info!("Executing command '{command}' for executor '{executor_name}'.");
+
+        match command {
+            ExecutorCommand::Start => {
+                if self.is_active(executor_name) {
+                    warn!("Executor '{executor_name}' is already running. Use 'restart' to start it anew.");
                 } else {
-                    None
+                    self.spawn_executor(executor_name, false, tx_outbound);
                 }
-            });
-
-        for executor in executors {
-
-            let (tx, rx) = watch::channel(false);
-
-            let ExecutorDescriptor {kind, results_url} = executor;
-
-            match kind {
-                ExecutorKind::Executable => warn!("Executing Executable not yet implemented."),
-                ExecutorKind::Container {
-                    engine,
-                    name,
-                    image,
-                    volumes,
-                    devices,
-                    envs,
-                    ports,
-                    command,
-                    args,
-                } => {
-                    let container_config = ContainerConfiguration{
-                        name,
-                        engine,
-                        image,
-                        command,
-                        args,
-                        envs,
-                        results_url,
-                        ports,
-                        devices,
-                        volumes,
-                    };
-                    tokio::spawn(async move {
-                        ContainerManager::new(container_config, rx).start().await;
-                    });
+            }
+            ExecutorCommand::Stop => {
+                if let Some(running) = self.running.remove(executor_name) {
+                    running.terminate();
                 }
             }
-            self.tx_termination_channels.push(tx);
+            ExecutorCommand::Restart => {
+                self.spawn_executor(executor_name, false, tx_outbound);
+            }
+            ExecutorCommand::RunJob => {
+                self.spawn_executor(executor_name, true, tx_outbound);
+            }
         }
+        Ok(())
     }
 
-    pub fn terminate_executors(&mut self) {
-        for tx_termination_channel in &self.tx_termination_channels {
-            if let Err(cause) = tx_termination_channel.send(true) {
-                warn!("Failed to send termination signal to executor, perhaps it already terminated? Cause: {cause}");
+    fn is_active(&self, executor_name: &str) -> bool {
+        self.running.get(executor_name)
+            .map(RunningExecutor::is_active)
+            .unwrap_or(false)
+    }
+
+    /// Starts a new instance of the named executor. A previous instance is terminated and awaited beforehand,
+    /// so that both never run at the same time, e.g. with the same container name.
+    fn spawn_executor(
+        &mut self,
+        executor_name: &str,
+        is_job: bool,
+        tx_outbound: &mpsc::Sender<peer_messaging_broker::Upstream>,
+    ) {
+        let Some((executor_id, executor)) = self.executors.get(executor_name).cloned() else {
+            return;
+        };
+
+        let previous = self.running.remove(executor_name);
+        if let Some(previous) = &previous {
+            previous.terminate();
+        }
+
+        let (tx, rx) = watch::channel(false);
+
+        let ExecutorDescriptor {kind, results_url} = executor;
+
+        let result_sinks = Arc::clone(&self.result_sinks);
+        let reporter = ExecutorRunReporter::new(self.self_id, executor_id, executor_name.to_owned(), Clone::clone(tx_outbound));
+
+        let handle = match kind {
+            ExecutorKind::Executable {
+                name,
+                program,
+                args,
+                envs,
+                working_directory,
+                user,
+            } => {
+                let envs = envs.iter()
+                    .map(|env| env.resolve_secret_references(&self.secrets))
+                    .collect::<Result<Vec<_>, _>>();
+
+                match envs {
+                    Ok(envs) => {
+                        let executable_config = ExecutableConfiguration {
+                            name,
+                            program,
+                            args,
+                            envs,
+                            working_directory,
+                            user,
+                            results_url,
+                        };
+                        tokio::spawn(async move {
+                            await_previous(previous).await;
+                            ExecutableManager::new(executable_config, result_sinks, reporter, rx).start().await;
+                        })
+                    }
+                    Err(cause) => tokio::spawn(report_unresolved_secret(previous, reporter, cause)),
+                }
             }
+            ExecutorKind::Container {
+                engine,
+                name,
+                image,
+                pull_policy,
+                digest,
+                registry_credential: registry_credential_name,
+                volumes,
+                devices,
+                envs,
+                ports,
+                command,
+                args,
+                can_databases: can_database_ids,
+                cpus,
+                memory,
+                pids_limit,
+                network_mode,
+                cap_add,
+                cap_drop,
+                read_only_rootfs,
+            } => {
+                let can_databases = can_database_ids.into_iter()
+                    .filter_map(|can_database_id| {
+                        let can_database = self.can_databases.get(&can_database_id).cloned();
+                        if can_database.is_none() {
+                            warn!("CAN database <{can_database_id}> referenced by container '{name}' is not part of the peer configuration and will not be provided.");
+                        }
+                        can_database
+                    })
+                    .collect();
+
+                let registry_credential = registry_credential_name.and_then(|registry_credential_name| {
+                    let registry_credential = self.registry_credentials.get(&registry_credential_name).cloned();
+                    if registry_credential.is_none() {
+                        warn!("Registry credential '{registry_credential_name}' referenced by container '{name}' is not part of the peer configuration. Pulling the image without authentication.");
+                    }
+                    registry_credential
+                });
+
+                let secrets = self.resolve_container_secrets(&envs, &volumes);
+
+                match secrets {
+                    Ok((envs, volumes, secrets)) => {
+                        let container_engine: ContainerEngineRef = Arc::new(ApiContainerEngine::new(self.container_engine_sockets.socket(&engine)));
+
+                        let container_config = ContainerConfiguration{
+                            name,
+                            engine,
+                            image,
+                            pull_policy,
+                            digest,
+                            registry_credential,
+                            command,
+                            args,
+                            envs,
+                            results_url,
+                            ports,
+                            devices,
+                            volumes,
+                            can_databases,
+                            secrets,
+                            cpus,
+                            memory,
+                            pids_limit,
+                            network_mode,
+                            cap_add,
+                            cap_drop,
+                            read_only_rootfs,
+                            cluster_bridge: Clone::clone(&self.cluster_bridge),
+                        };
+                        tokio::spawn(async move {
+                            await_previous(previous).await;
+                            ContainerManager::new(container_config, container_engine, result_sinks, reporter, rx).start().await;
+                        })
+                    }
+                    Err(cause) => tokio::spawn(report_unresolved_secret(previous, reporter, cause)),
+                }
+            }
+        };
+
+        self.running.insert(executor_name.to_owned(), RunningExecutor { tx_termination: tx, handle, is_job });
+    }
+
+    /// Replaces the references to secrets in the environment variables and volumes of a container with the values of the secrets.
+    /// Volumes, whose source is a secret, are kept as they are. The referenced secrets are returned instead, to be provided as files.
+    fn resolve_container_secrets(
+        &self,
+        envs: &[ContainerEnvironmentVariable],
+        volumes: &[ContainerVolume],
+    ) -> Result<(Vec<ContainerEnvironmentVariable>, Vec<ContainerVolume>, Vec<Secret>), ResolveSecretReferenceError> {
+
+        let envs = envs.iter()
+            .map(|env| env.resolve_secret_references(&self.secrets))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut secrets = Vec::new();
+        let mut resolved_volumes = Vec::new();
+        for volume in volumes {
+            match volume.secret_source() {
+                Some((name, _)) => {
+                    let secret = self.secrets.get(&name)
+                        .ok_or(ResolveSecretReferenceError::SecretNotFound { name })?;
+                    secrets.push(Clone::clone(secret));
+                    resolved_volumes.push(Clone::clone(volume));
+                }
+                None => resolved_volumes.push(volume.resolve_secret_references(&self.secrets)?),
+            }
+        }
+
+        Ok((envs, resolved_volumes, secrets))
+    }
+}
+
+/// Fails the run of an executor, whose configuration references a secret, which cannot be resolved.
+async fn report_unresolved_secret(previous: Option<RunningExecutor>, mut reporter: ExecutorRunReporter, cause: ResolveSecretReferenceError) {
+    await_previous(previous).await;
+
+    let message = format!("Executor cannot be started, because its configuration references a secret, which cannot be resolved: {cause}");
+    error!("{message}");
+
+    let log_forwarder = reporter.log_forwarder();
+    log_forwarder.sender().send(ExecutorLogStream::Stderr, SystemTime::now(), message);
+    log_forwarder.close().await;
+
+    reporter.failed().await;
+}
+
+async fn await_previous(previous: Option<RunningExecutor>) {
+    if let Some(RunningExecutor { tx_termination, handle, .. }) = previous {
+        let _ = handle.await;
+        drop(tx_termination);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::PathBuf;
+    use std::time::Duration;
+
+    use googletest::prelude::*;
+    use uuid::Uuid;
+
+    use opendut_types::peer::configuration::{Parameter, ParameterTarget};
+    use opendut_types::peer::executor::executable::{ExecutableArgument, ExecutableName, ExecutableProgram};
+    use opendut_types::peer::executor::run::{ExecutorRun, ExecutorRunState};
+
+    use crate::service::test_execution::result_sink::ResultSinks;
+
+    use super::*;
+
+    fn long_running_executor(name: &str) -> Parameter<ExecutorDescriptor> {
+        Parameter {
+            id: ParameterId(Uuid::new_v4()),
+            dependencies: vec![],
+            target: ParameterTarget::Present,
+            value: ExecutorDescriptor {
+                kind: ExecutorKind::Executable {
+                    name: ExecutableName::try_from(name).unwrap(),
+                    program: ExecutableProgram::try_from("sh").unwrap(),
+                    args: vec![
+                        ExecutableArgument::try_from("-c").unwrap(),
+                        ExecutableArgument::try_from("sleep 30").unwrap(),
+                    ],
+                    envs: vec![],
+                    working_directory: None,
+                    user: None,
+                },
+                results_url: None,
+            },
         }
-        self.tx_termination_channels.clear();
+    }
+
+    async fn wait_for_cancelled_runs(rx: &mut mpsc::Receiver<peer_messaging_broker::Upstream>, expected: usize) -> usize {
+        let mut cancelled = 0;
+        let _ = tokio::time::timeout(Duration::from_secs(15), async {
+            while cancelled < expected {
+                let Some(upstream) = rx.recv().await else { break };
+                if let Some(peer_messaging_broker::upstream::Message::ExecutorRunUpdate(update)) = upstream.message {
+                    if ExecutorRun::try_from(update.run.unwrap()).unwrap().state == ExecutorRunState::Cancelled {
+                        cancelled += 1;
+                    }
+                }
+            }
+        }).await;
+        cancelled
+    }
+
+    #[tokio::test]
+    async fn should_resolve_the_secrets_referenced_by_a_container() -> anyhow::Result<()> {
+        let (tx_outbound, _rx_outbound) = mpsc::channel(64);
+        let manager = ExecutorManager::create(PeerId::random(), ContainerEngineSockets {
+            docker: PathBuf::from("/var/run/docker.sock"),
+            podman: PathBuf::from("/run/podman/podman.sock"),
+        }, ResultSinks::unauthenticated());
+        let mut manager = manager.lock().unwrap();
 
+        let secret = |name: &str, value: &str| -> anyhow::Result<Parameter<Secret>> {
+            Ok(Parameter {
+                id: ParameterId(Uuid::new_v4()),
+                dependencies: vec![],
+                target: ParameterTarget::Present,
+                value: Secret { name: SecretName::try_from(name)?, value: value.to_owned() },
+            })
+        };
+        manager.create_new_executors(vec![], vec![], vec![], vec![secret("db-password", "hunter2")?, secret("ca-cert", "CERT")?], None, &tx_outbound);
+
+        let (envs, volumes, secrets) = manager.resolve_container_secrets(
+            &[ContainerEnvironmentVariable::new("DB_URL", "postgres://app:${secret:db-password}@db/app")?],
+            &[ContainerVolume::try_from("${secret:ca-cert}:/etc/ssl/ca.pem:ro")?, ContainerVolume::try_from("/data/${secret:db-password}:/data")?],
+        )?;
+        assert_that!(envs, elements_are![eq(&ContainerEnvironmentVariable::new("DB_URL", "postgres://app:hunter2@db/app")?)]);
+        assert_that!(volumes, elements_are![
+            eq(&ContainerVolume::try_from("${secret:ca-cert}:/etc/ssl/ca.pem:ro")?),
+            eq(&ContainerVolume::try_from("/data/hunter2:/data")?),
+        ]);
+        let secret_names = secrets.into_iter().map(|secret| secret.name).collect::<Vec<_>>();
+        assert_that!(secret_names, elements_are![eq(&SecretName::try_from("ca-cert")?)]);
+
+        let result = manager.resolve_container_secrets(&[ContainerEnvironmentVariable::new("TOKEN", "${secret:unknown}")?], &[]);
+        assert!(matches!(result, Err(ResolveSecretReferenceError::SecretNotFound { .. })));
+
+        Ok(())
     }
 
+    #[tokio::test]
+    async fn should_keep_jobs_running_when_executors_are_redeployed_until_stopped() -> anyhow::Result<()> {
+        let (tx_outbound, mut rx_outbound) = mpsc::channel(64);
+        let manager = ExecutorManager::create(PeerId::random(), ContainerEngineSockets {
+            docker: PathBuf::from("/var/run/docker.sock"),
+            podman: PathBuf::from("/run/podman/podman.sock"),
+        }, ResultSinks::unauthenticated());
+        {
+            let mut manager = manager.lock().unwrap();
+
+            manager.create_new_executors(vec![long_running_executor("sleeper")], vec![], vec![], vec![], None, &tx_outbound);
+            assert!(manager.is_active("sleeper"));
+
+            let result = manager.execute_command("unknown", ExecutorCommand::Start, &tx_outbound);
+            assert!(matches!(result, Err(ExecutorCommandError::ExecutorNotFound { .. })));
+
+            manager.execute_command("sleeper", ExecutorCommand::RunJob, &tx_outbound)?;
+            manager.terminate_executors();
+            assert!(manager.is_active("sleeper"));
 
+            manager.execute_command("sleeper", ExecutorCommand::Stop, &tx_outbound)?;
+            assert!(!manager.is_active("sleeper"));
+
+            manager.execute_command("sleeper", ExecutorCommand::Start, &tx_outbound)?;
+            manager.execute_command("sleeper", ExecutorCommand::Restart, &tx_outbound)?;
+            manager.terminate_executors();
+            assert!(!manager.is_active("sleeper"));
+        }
+
+        let cancelled = wait_for_cancelled_runs(&mut rx_outbound, 4).await;
+        assert_that!(cancelled, eq(4));
+
+        Ok(())
+    }
 }
@@ -0,0 +1,139 @@
+use std::time::SystemTime;
+
+use tokio::sync::mpsc;
+use tracing::{debug, warn};
+
+use opendut_carl_api::proto::services::peer_messaging_broker;
+use opendut_types::peer::configuration::ParameterId;
+use opendut_types::peer::executor::container::ContainerImageDigest;
+use opendut_types::peer::executor::run::{ExecutorRun, ExecutorRunId, ExecutorRunState, ResultArtifact, ResultArtifactId};
+use opendut_types::peer::PeerId;
+
+use crate::service::test_execution::executor_logs::ExecutorLogForwarder;
+use crate::service::test_execution::result_sink::UploadedFile;
+
+/// Tracks the lifecycle of a single run of an executor and reports each change to CARL.
+pub struct ExecutorRunReporter {
+    run: ExecutorRun,
+    tx_outbound: mpsc::Sender<peer_messaging_broker::Upstream>,
+}
+
+impl ExecutorRunReporter {
+    pub fn new(peer_id: PeerId, executor_id: ParameterId, executor_name: String, tx_outbound: mpsc::Sender<peer_messaging_broker::Upstream>) -> Self {
+        Self {
+            run: ExecutorRun {
+                id: ExecutorRunId::random(),
+                peer_id,
+                executor_id,
+                executor_name,
+                state: ExecutorRunState::Pending,
+                exit_code: None,
+                created_at: SystemTime::now(),
+                started_at: None,
+                finished_at: None,
+                results_location: None,
+                image_digest: None,
+            },
+            tx_outbound,
+        }
+    }
+
+    /// Creates a forwarder for the output of this run.
+    pub fn log_forwarder(&self) -> ExecutorLogForwarder {
+        ExecutorLogForwarder::new(self.run.id, Clone::clone(&self.tx_outbound))
+    }
+
+    pub async fn pending(&mut self) {
+        self.run.state = ExecutorRunState::Pending;
+        self.report().await;
+    }
+
+    pub async fn running(&mut self) {
+        self.run.state = ExecutorRunState::Running;
+        self.run.started_at = Some(SystemTime::now());
+        self.report().await;
+    }
+
+    /// Records the digest of the image, from which the container is created.
+    pub async fn image_resolved(&mut self, image_digest: ContainerImageDigest) {
+        self.run.image_digest = Some(image_digest);
+        self.report().await;
+    }
+
+    /// Records the location of the uploaded results and reports them to CARL as a result artifact of this run.
+    pub async fn results_uploaded(&mut self, results_file: UploadedFile) {
+        self.run.results_location = Some(Clone::clone(&results_file.location));
+        self.report().await;
+
+        let artifact = ResultArtifact {
+            id: ResultArtifactId::random(),
+            peer_id: self.run.peer_id,
+            cluster_id: None,
+            run_id: self.run.id,
+            executor_id: self.run.executor_id,
+            executor_name: Clone::clone(&self.run.executor_name),
+            uploaded_at: SystemTime::now(),
+            size: results_file.size,
+            location: results_file.location,
+            checksum: results_file.checksum.hex(),
+        };
+        debug!("Reporting result artifact <{}> of executor run <{}> to CARL.", artifact.id, self.run.id);
+
+        let message = peer_messaging_broker::Upstream {
+            context: None,
+            message: Some(peer_messaging_broker::upstream::Message::ResultArtifactUploaded(
+                peer_messaging_broker::ResultArtifactUploaded {
+                    artifact: Some(artifact.into()),
+                }
+            )),
+        };
+
+        let _ignore_error =
+            self.tx_outbound.send(message).await
+                .inspect_err(|cause| warn!("Failed to report result artifact of executor run <{}> to CARL: {cause}", self.run.id));
+    }
+
+    /// Marks the run as finished, successfully if the executor exited with exit code 0.
+    pub async fn exited(&mut self, exit_code: Option<i32>) {
+        let state = match exit_code {
+            Some(0) => ExecutorRunState::Succeeded,
+            _ => ExecutorRunState::Failed,
+        };
+        self.finish(state, exit_code).await;
+    }
+
+    pub async fn failed(&mut self) {
+        self.finish(ExecutorRunState::Failed, None).await;
+    }
+
+    pub async fn cancelled(&mut self, exit_code: Option<i32>) {
+        self.finish(ExecutorRunState::Cancelled, exit_code).await;
+    }
+
+    async fn finish(&mut self, state: ExecutorRunState, exit_code: Option<i32>) {
+        if self.run.state.is_finished() {
+            return;
+        }
+        self.run.state = state;
+        self.run.exit_code = exit_code;
+        self.run.finished_at = Some(SystemTime::now());
+        self.report().await;
+    }
+
+    async fn report(&self) {
+        debug!("Reporting state '{}' of executor run <{}> of '{}' to CARL.", self.run.state, self.run.id, self.run.executor_name);
+
+        let message = peer_messaging_broker::Upstream {
+            context: None,
+            message: Some(peer_messaging_broker::upstream::Message::ExecutorRunUpdate(
+                peer_messaging_broker::ExecutorRunUpdate {
+                    run: Some(Clone::clone(&self.run).into()),
+                }
+            )),
+        };
+
+        let _ignore_error =
+            self.tx_outbound.send(message).await
+                .inspect_err(|cause| warn!("Failed to report executor run <{}> to CARL: {cause}", self.run.id));
+    }
+}
@@ -1,3 +1,9 @@
+pub mod container_engine;
 pub mod container_manager;
-mod webdav_client;
+pub mod executable_manager;
+pub mod executor_logs;
+pub mod executor_run;
+pub mod result_sink;
+pub mod results;
+pub mod webdav_client;
 pub mod executor_manager;
\ No newline at end of file
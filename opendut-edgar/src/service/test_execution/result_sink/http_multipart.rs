@@ -0,0 +1,64 @@
+use async_trait::async_trait;
+use reqwest::multipart::{Form, Part};
+use tracing::info;
+use url::Url;
+
+use crate::service::test_execution::result_sink::{with_scheme, Authentication, Checksum, Error, ResultSink};
+
+const FILE_FIELD: &str = "file";
+const CHECKSUM_FIELD: &str = "sha256";
+
+/// Posts result files as `multipart/form-data` to a plain HTTP endpoint.
+/// The results URL uses the scheme `http+multipart` or `https+multipart`, which is replaced by `http` or `https` respectively.
+///
+/// The form contains the file in the field `file` and its hex-encoded SHA-256 checksum in the field `sha256`,
+/// which the endpoint is expected to verify. If the endpoint responds with a `Location` header, its value is returned
+/// as the location of the uploaded file.
+pub struct HttpMultipartResultSink {
+    client: reqwest::Client,
+    authentication: Authentication,
+}
+
+impl HttpMultipartResultSink {
+    pub fn new(client: reqwest::Client, authentication: Authentication) -> Self {
+        Self { client, authentication }
+    }
+}
+
+#[async_trait]
+impl ResultSink for HttpMultipartResultSink {
+    async fn upload(&self, url: &Url, file_name: &str, content: &[u8], checksum: &Checksum) -> Result<Url, Error> {
+        let scheme = url.scheme().trim_end_matches("+multipart");
+        let endpoint = with_scheme(url, scheme)?;
+
+        let file = Part::bytes(content.to_vec())
+            .file_name(file_name.to_owned())
+            .mime_str("application/octet-stream")
+            .map_err(|cause| Error::Other { message: format!("Failed to create multipart form for '{file_name}': {cause}") })?;
+
+        let form = Form::new()
+            .part(FILE_FIELD, file)
+            .text(CHECKSUM_FIELD, checksum.hex());
+
+        let mut request = self.client.post(Clone::clone(&endpoint))
+            .multipart(form);
+        if let Some(bearer_token) = self.authentication.bearer_token().await? {
+            request = request.bearer_auth(bearer_token);
+        }
+
+        let response = request.send().await
+            .map_err(|cause| Error::Request { method: "POST", url: Clone::clone(&endpoint), cause })?;
+
+        if !response.status().is_success() {
+            return Err(Error::Server { method: "POST", url: endpoint, status: response.status() });
+        }
+
+        let location = response.headers().get(reqwest::header::LOCATION)
+            .and_then(|location| location.to_str().ok())
+            .and_then(|location| endpoint.join(location).ok())
+            .unwrap_or(endpoint);
+
+        info!("Uploaded '{file_name}' to '{location}'.");
+        Ok(location)
+    }
+}
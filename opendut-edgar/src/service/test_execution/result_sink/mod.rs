@@ -0,0 +1,306 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use async_trait::async_trait;
+use backoff::ExponentialBackoffBuilder;
+use base64::Engine;
+use sha2::{Digest, Sha256};
+use tracing::{debug, warn};
+use url::Url;
+
+use opendut_auth::confidential::client::{AuthError, ConfidentialClient, ConfidentialClientRef};
+use opendut_auth::confidential::error::ConfidentialClientError;
+use opendut_types::peer::executor::ResultsUrl;
+
+use crate::service::test_execution::result_sink::http_multipart::HttpMultipartResultSink;
+use crate::service::test_execution::result_sink::s3::{S3Configuration, S3ResultSink};
+use crate::service::test_execution::result_sink::webdav::WebdavResultSink;
+
+pub mod http_multipart;
+pub mod s3;
+pub mod webdav;
+
+pub type ResultSinksRef = Arc<ResultSinks>;
+
+/// A location, which EDGAR uploads result files to, e.g. the results of an executor or CAN capture log files.
+#[async_trait]
+pub trait ResultSink: Send + Sync {
+    /// Uploads `content` as a file named `file_name` into the location `url`.
+    /// Returns the URL, under which the uploaded file is available.
+    async fn upload(&self, url: &Url, file_name: &str, content: &[u8], checksum: &Checksum) -> Result<Url, Error>;
+}
+
+/// Selects the [`ResultSink`] by the scheme of a [`ResultsUrl`]:
+/// - `http://` and `https://` upload into a WebDAV directory,
+/// - `http+multipart://` and `https+multipart://` post a multipart form to a plain HTTP endpoint,
+/// - `s3://<bucket>/<prefix>` upload into an S3-compatible object storage.
+///
+/// Failed uploads are retried with an exponential backoff.
+pub struct ResultSinks {
+    webdav: WebdavResultSink,
+    http_multipart: HttpMultipartResultSink,
+    s3: S3ResultSink,
+    retry_max_elapsed_time: Duration,
+}
+
+pub const WEBDAV_SCHEMES: [&str; 2] = ["http", "https"];
+pub const HTTP_MULTIPART_SCHEMES: [&str; 2] = ["http+multipart", "https+multipart"];
+pub const S3_SCHEME: &str = "s3";
+
+impl ResultSinks {
+    pub async fn load(config: &config::Config) -> Result<ResultSinksRef, LoadError> {
+        let authentication = ConfidentialClient::from_settings(config).await?;
+        let verify_checksum = config.get_bool("results.upload.verify.checksum")?;
+        let retry_max_elapsed_time = Duration::from_millis(config.get::<u64>("results.upload.retry.max.elapsed.time.ms")?);
+        let s3_configuration = S3Configuration::load(config)?;
+
+        Ok(Arc::new(Self::new(authentication, verify_checksum, s3_configuration, retry_max_elapsed_time)))
+    }
+
+    pub fn new(
+        authentication: Option<ConfidentialClientRef>,
+        verify_checksum: bool,
+        s3_configuration: S3Configuration,
+        retry_max_elapsed_time: Duration,
+    ) -> Self {
+        let client = reqwest::Client::new();
+        let authentication = Authentication(authentication);
+
+        Self {
+            webdav: WebdavResultSink::new(Clone::clone(&client), Clone::clone(&authentication), verify_checksum),
+            http_multipart: HttpMultipartResultSink::new(Clone::clone(&client), authentication),
+            s3: S3ResultSink::new(client, s3_configuration),
+            retry_max_elapsed_time,
+        }
+    }
+
+    /// Result sinks without authentication and without retrying failed uploads.
+    #[cfg(test)]
+    pub fn unauthenticated() -> ResultSinksRef {
+        Arc::new(Self::new(None, false, S3Configuration::default(), Duration::ZERO))
+    }
+
+    fn sink_for(&self, url: &Url) -> Result<&dyn ResultSink, Error> {
+        let scheme = url.scheme();
+        if WEBDAV_SCHEMES.contains(&scheme) {
+            Ok(&self.webdav)
+        } else if HTTP_MULTIPART_SCHEMES.contains(&scheme) {
+            Ok(&self.http_multipart)
+        } else if scheme == S3_SCHEME {
+            Ok(&self.s3)
+        } else {
+            Err(Error::UnsupportedScheme { url: Clone::clone(url) })
+        }
+    }
+
+    /// Uploads `content` as a file named `file_name` into the location `results_url`.
+    pub async fn upload(&self, results_url: &ResultsUrl, file_name: &str, content: Vec<u8>) -> Result<UploadedFile, Error> {
+        let url = results_url.value();
+        let sink = self.sink_for(url)?;
+        let checksum = &Checksum::of(&content);
+        let content = &content;
+        debug!("Uploading '{file_name}' with SHA-256 checksum '{}' to '{url}'.", checksum.hex());
+
+        let exponential_backoff = ExponentialBackoffBuilder::default()
+            .with_max_elapsed_time(Some(self.retry_max_elapsed_time))
+            .build();
+
+        let location = backoff::future::retry_notify(
+            exponential_backoff,
+            || async move {
+                sink.upload(url, file_name, content, checksum).await
+                    .map_err(|cause| {
+                        if cause.is_transient() {
+                            backoff::Error::transient(cause)
+                        } else {
+                            backoff::Error::permanent(cause)
+                        }
+                    })
+            },
+            |cause: Error, delay: Duration| warn!("Upload of '{file_name}' to '{url}' failed. Retrying in {} ms.\n  {cause}", delay.as_millis()),
+        ).await?;
+
+        Ok(UploadedFile {
+            location,
+            size: u64::try_from(content.len()).unwrap_or(u64::MAX),
+            checksum: Clone::clone(checksum),
+        })
+    }
+}
+
+/// A file, which was uploaded into a [`ResultSink`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UploadedFile {
+    /// URL, under which the uploaded file is available.
+    pub location: Url,
+    /// Size of the uploaded file in bytes.
+    pub size: u64,
+    pub checksum: Checksum,
+}
+
+/// SHA-256 checksum of an uploaded file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Checksum([u8; 32]);
+
+impl Checksum {
+    pub fn of(content: &[u8]) -> Self {
+        Self(Sha256::digest(content).into())
+    }
+
+    pub fn hex(&self) -> String {
+        hex::encode(self.0)
+    }
+
+    pub fn base64(&self) -> String {
+        base64::prelude::BASE64_STANDARD.encode(self.0)
+    }
+
+    fn verify(&self, url: &Url, content: &[u8]) -> Result<(), Error> {
+        let actual = Checksum::of(content);
+        if actual == *self {
+            debug!("Verified checksum of '{url}'.");
+            Ok(())
+        } else {
+            Err(Error::ChecksumMismatch { url: Clone::clone(url), actual: actual.hex() })
+        }
+    }
+}
+
+/// Provides the access token of EDGAR's confidential OIDC client, if authentication is enabled.
+#[derive(Clone)]
+pub struct Authentication(Option<ConfidentialClientRef>);
+
+impl Authentication {
+    pub async fn bearer_token(&self) -> Result<Option<String>, Error> {
+        match &self.0 {
+            Some(client) => {
+                let token = client.get_token().await
+                    .map_err(|cause| Error::Authentication { cause })?;
+                Ok(Some(token.value))
+            }
+            None => Ok(None),
+        }
+    }
+}
+
+/// Replaces the scheme of a URL, which `Url::set_scheme()` refuses for switching between special and non-special schemes.
+fn with_scheme(url: &Url, scheme: &str) -> Result<Url, Error> {
+    let without_scheme = &url.as_str()[url.scheme().len()..];
+    Url::parse(&format!("{scheme}{without_scheme}"))
+        .map_err(|cause| Error::Other { message: format!("Failed to change scheme of URL '{url}' to '{scheme}': {cause}") })
+}
+
+/// Returns the URL of the file `file_name` within the directory `url`.
+fn file_url(url: &Url, file_name: &str) -> Result<Url, Error> {
+    let mut directory = Clone::clone(url);
+    if !directory.path().ends_with('/') {
+        directory.set_path(&format!("{}/", directory.path()));
+    }
+    directory.join(file_name)
+        .map_err(|cause| Error::Other { message: format!("Failed to construct URL for file '{file_name}' in '{url}': {cause}") })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum LoadError {
+    #[error("Failed to load result sink configuration: {0}")]
+    Configuration(#[from] config::ConfigError),
+    #[error("Failed to create OIDC client for uploading results: {0}")]
+    Authentication(#[from] ConfidentialClientError),
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("No result sink available for the scheme of '{url}'. Supported are: {}, {} and {S3_SCHEME}.", WEBDAV_SCHEMES.join(", "), HTTP_MULTIPART_SCHEMES.join(", "))]
+    UnsupportedScheme { url: Url },
+    #[error("Failed to obtain token for uploading results: {cause}")]
+    Authentication { cause: AuthError },
+    #[error("Failure while sending '{method}' request to '{url}': {cause}")]
+    Request { method: &'static str, url: Url, cause: reqwest::Error },
+    #[error("Request '{method}' to '{url}' failed with HTTP status {status}")]
+    Server { method: &'static str, url: Url, status: reqwest::StatusCode },
+    #[error("Checksum of '{url}' does not match the uploaded content. The stored file has the SHA-256 checksum '{actual}'.")]
+    ChecksumMismatch { url: Url, actual: String },
+    #[error("{message}")]
+    Configuration { message: String },
+    #[error("{message}")]
+    Other { message: String },
+}
+
+impl Error {
+    /// Whether repeating the upload may succeed.
+    pub fn is_transient(&self) -> bool {
+        match self {
+            Error::Authentication { .. } => true,
+            Error::Request { .. } => true,
+            Error::Server { status, .. } => {
+                status.is_server_error()
+                    || *status == reqwest::StatusCode::REQUEST_TIMEOUT
+                    || *status == reqwest::StatusCode::TOO_MANY_REQUESTS
+            }
+            Error::ChecksumMismatch { .. } => true,
+            Error::UnsupportedScheme { .. }
+            | Error::Configuration { .. }
+            | Error::Other { .. } => false,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_select_result_sink_by_scheme() -> anyhow::Result<()> {
+        let sinks = ResultSinks::unauthenticated();
+
+        assert!(sinks.sink_for(&Url::parse("https://example.com/results/")?).is_ok());
+        assert!(sinks.sink_for(&Url::parse("https+multipart://example.com/upload")?).is_ok());
+        assert!(sinks.sink_for(&Url::parse("s3://bucket/results/")?).is_ok());
+
+        let result = sinks.sink_for(&Url::parse("ftp://example.com/results/")?);
+        assert!(matches!(result, Err(Error::UnsupportedScheme { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_only_retry_transient_errors() -> anyhow::Result<()> {
+        let url = Url::parse("https://example.com/results/")?;
+        let server_error = |status| Error::Server { method: "PUT", url: Clone::clone(&url), status };
+
+        assert!(server_error(reqwest::StatusCode::SERVICE_UNAVAILABLE).is_transient());
+        assert!(server_error(reqwest::StatusCode::TOO_MANY_REQUESTS).is_transient());
+        assert!(!server_error(reqwest::StatusCode::UNAUTHORIZED).is_transient());
+        assert!(!server_error(reqwest::StatusCode::NOT_FOUND).is_transient());
+        assert!(Error::ChecksumMismatch { url: Clone::clone(&url), actual: String::new() }.is_transient());
+        assert!(!Error::UnsupportedScheme { url }.is_transient());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_verify_checksum() -> anyhow::Result<()> {
+        let url = Url::parse("https://example.com/results/file.zip")?;
+        let checksum = Checksum::of(b"");
+
+        assert_that!(checksum.hex(), eq("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"));
+        assert_that!(checksum.base64(), eq("47DEQpj8HBSa+/TImW+5JCeuQeRkm5NMpJWZG3hSuFU="));
+        assert!(checksum.verify(&url, b"").is_ok());
+        assert!(matches!(checksum.verify(&url, b"modified"), Err(Error::ChecksumMismatch { .. })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_construct_file_urls() -> anyhow::Result<()> {
+        let file_url = file_url(&Url::parse("https://example.com/results")?, "file.zip")?;
+        assert_that!(file_url.as_str(), eq("https://example.com/results/file.zip"));
+
+        let multipart_url = with_scheme(&Url::parse("https+multipart://example.com:8443/upload?project=demo")?, "https")?;
+        assert_that!(multipart_url.as_str(), eq("https://example.com:8443/upload?project=demo"));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use async_trait::async_trait;
+use tracing::{debug, info};
+use url::Url;
+
+use crate::service::test_execution::result_sink::{file_url, Authentication, Checksum, Error, ResultSink};
+use crate::service::test_execution::webdav_client::{self, WebdavClient};
+
+/// Uploads result files into a WebDAV directory, creating the directory if necessary.
+/// If `verify_checksum` is set, the file is downloaded again after uploading and compared to the uploaded content.
+pub struct WebdavResultSink {
+    client: reqwest::Client,
+    authentication: Authentication,
+    verify_checksum: bool,
+}
+
+impl WebdavResultSink {
+    pub fn new(client: reqwest::Client, authentication: Authentication, verify_checksum: bool) -> Self {
+        Self { client, authentication, verify_checksum }
+    }
+}
+
+#[async_trait]
+impl ResultSink for WebdavResultSink {
+    async fn upload(&self, url: &Url, file_name: &str, content: &[u8], checksum: &Checksum) -> Result<Url, Error> {
+        let bearer_token = self.authentication.bearer_token().await?;
+        let webdav_client = WebdavClient::new(Clone::clone(&self.client), bearer_token);
+
+        webdav_client.create_collection_path(Clone::clone(url))
+            .await
+            .map_err(|cause| map_webdav_error("MKCOL", url, cause))?;
+
+        let file_url = file_url(url, file_name)?;
+
+        let response = webdav_client.put(content.to_vec(), Clone::clone(&file_url), &format!("sha-256={}", checksum.base64()))
+            .await
+            .map_err(|cause| map_webdav_error("PUT", &file_url, cause))?;
+
+        if !response.status().is_success() {
+            return Err(Error::Server { method: "PUT", url: file_url, status: response.status() });
+        }
+
+        if self.verify_checksum {
+            debug!("Verifying checksum of uploaded file '{file_url}'.");
+            let response = webdav_client.get(Clone::clone(&file_url))
+                .await
+                .map_err(|cause| map_webdav_error("GET", &file_url, cause))?;
+
+            if !response.status().is_success() {
+                return Err(Error::Server { method: "GET", url: file_url, status: response.status() });
+            }
+
+            let uploaded_content = response.bytes().await
+                .map_err(|cause| Error::Request { method: "GET", url: Clone::clone(&file_url), cause })?;
+
+            checksum.verify(&file_url, &uploaded_content)?;
+        }
+
+        info!("Uploaded '{file_name}' to WebDAV directory '{url}'.");
+        Ok(file_url)
+    }
+}
+
+fn map_webdav_error(method: &'static str, url: &Url, cause: webdav_client::Error) -> Error {
+    match cause {
+        webdav_client::Error::Request { cause, .. } => Error::Request { method, url: Clone::clone(url), cause },
+        webdav_client::Error::Other { message } => Error::Other { message },
+    }
+}
@@ -0,0 +1,127 @@
+use std::{env, io::{Cursor, ErrorKind, Write}, path::{Path, PathBuf}};
+
+use tokio::{fs::{self, File}, io::AsyncReadExt};
+use tracing::info;
+use uuid::Uuid;
+use walkdir::WalkDir;
+use zip::{CompressionMethod, write::{FileOptionExtension, FileOptions, SimpleFileOptions}, ZipWriter};
+
+use opendut_types::peer::executor::ResultsUrl;
+
+use crate::service::test_execution::result_sink::{self, ResultSinks, UploadedFile};
+
+const RESULTS_READY_FILE: &str = ".results_ready";
+
+/// Directory on the peer, which an executor writes its results to.
+/// The results are uploaded when the executor creates the file `.results_ready` or when it exits.
+pub struct ResultsDirectory {
+    path: PathBuf,
+}
+
+impl ResultsDirectory {
+
+    pub fn new(id: Uuid) -> Self {
+        Self {
+            path: env::temp_dir().join(format!("opendut-edgar-results_{}", id)),
+        }
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub async fn create(&self) -> Result<(), Error> {
+        fs::create_dir_all(&self.path)
+            .await
+            .map_err(|cause| Error::Other { message: format!("Failed to create results directory '{}': {}", self.path.to_string_lossy(), cause) })?;
+        Ok(())
+    }
+
+    pub async fn cleanup(&self) -> Result<(), Error> {
+        fs::remove_dir_all(&self.path)
+            .await
+            .map_err(|cause| Error::Other { message: format!("Failed to remove results directory '{}': {}", self.path.to_string_lossy(), cause) })?;
+        Ok(())
+    }
+
+    pub fn are_results_ready(&self) -> bool {
+        self.path.join(RESULTS_READY_FILE).is_file()
+    }
+
+    pub async fn remove_results_ready_indicator(&self) -> Result<(), Error> {
+        let indicator_file = self.path.join(RESULTS_READY_FILE);
+        match fs::remove_file(&indicator_file).await {
+            Ok(_) => Ok(()),
+            Err(err) => match err.kind() {
+                ErrorKind::NotFound => Ok(()),
+                _ => Err(Error::Other { message: format!("Failed to remove result indicator file '{}': {}", indicator_file.to_string_lossy(), err) }),
+            },
+        }
+    }
+
+    /// Uploads the contents of the directory as a ZIP archive, named after the executor, into the result sink at `results_url`.
+    /// Returns the uploaded archive, or `None` if no `results_url` is configured.
+    pub async fn upload(&self, result_sinks: &ResultSinks, results_url: &Option<ResultsUrl>, executor_name: &str) -> Result<Option<UploadedFile>, Error> {
+        info!("Starting upload for results of {}", executor_name);
+        let results_url = match results_url {
+            Some(results_url) => results_url,
+            None => {
+                info!("Executor {} has no results URL, won't upload results.", executor_name);
+                return Ok(None);
+            },
+        };
+
+        let mut zipped_data = Vec::new();
+        // https://github.com/zip-rs/zip2/issues/195 large_file(true) produces invalid zip file with crate version 2.1.3
+        let zip_options = SimpleFileOptions::default().compression_method(CompressionMethod::BZIP2).large_file(false);
+        create_zip_from_directory(&mut zipped_data, &self.path, zip_options).await.map_err(|cause| Error::ResultZipping { path: self.path.clone(), cause })?;
+
+        let results_file_name = format!("{}_{}.zip", chrono::offset::Local::now().format("%Y-%m-%d_%H-%M-%S"), executor_name);
+
+        let results_file = result_sinks.upload(results_url, &results_file_name, zipped_data)
+            .await
+            .map_err(|cause| Error::ResultUploading { executor_name: executor_name.to_owned(), cause: Box::new(cause) })?;
+
+        info!("Successfully uploaded results of {}", executor_name);
+        Ok(Some(results_file))
+    }
+}
+
+async fn create_zip_from_directory<T>(data: &mut Vec<u8>, directory: &PathBuf, file_options: FileOptions<'_, T>) -> anyhow::Result<()>
+    where
+        T: FileOptionExtension + std::marker::Copy,
+    {
+        let mut file_buffer = Vec::new();
+        let zip_buffer = Cursor::new(data);
+        let mut zip = ZipWriter::new(zip_buffer);
+
+        for entry_res in WalkDir::new(directory) {
+            let entry = entry_res?;
+            let entry_path = entry.path();
+            let entry_metadata = entry.metadata()?;
+
+            if entry_metadata.is_file() {
+                let mut f = File::open(&entry_path).await?;
+                f.read_to_end(&mut file_buffer).await?;
+                let relative_path = entry_path.strip_prefix(directory)?;
+                zip.start_file(relative_path.to_string_lossy(), file_options)?;
+                zip.write_all(file_buffer.as_ref())?;
+                file_buffer.clear();
+            } else if entry_metadata.is_dir() {
+                let relative_path = entry_path.strip_prefix(directory)?;
+                zip.add_directory(relative_path.to_string_lossy(), file_options)?;
+            }
+        }
+
+        Ok(())
+    }
+
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("Failure while creating a ZIP archive of the test results at '{path}' : {cause}")]
+    ResultZipping { path: PathBuf, cause: anyhow::Error },
+    #[error("Failure while uploading test results for '{executor_name}': {cause}")]
+    ResultUploading { executor_name: String, cause: Box<result_sink::Error> },
+    #[error("{message}")]
+    Other { message: String },
+}
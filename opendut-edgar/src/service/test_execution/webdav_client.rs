@@ -2,23 +2,25 @@ use reqwest::{Method, RequestBuilder, Url, Body, Response, header};
 use tracing::error;
 
 pub struct WebdavClient {
-    bearer_token: String,
+    bearer_token: Option<String>,
     client: reqwest::Client,
 }
 
 impl WebdavClient {
 
-    pub fn new(bearer_token: String) -> Self {
-        Self { 
+    pub fn new(client: reqwest::Client, bearer_token: Option<String>) -> Self {
+        Self {
             bearer_token,
-            client: reqwest::Client::new()
+            client,
         }
     }
 
     fn start_request(&self, method: Method, path: Url) -> RequestBuilder {
-        self.client
-            .request(method, path)
-            .bearer_auth(self.bearer_token.clone())
+        let request = self.client.request(method, path);
+        match &self.bearer_token {
+            Some(bearer_token) => request.bearer_auth(bearer_token),
+            None => request,
+        }
     }
 
     fn custom_header(&self, name: &str, value: &str) -> header::HeaderMap {
@@ -32,16 +34,27 @@ impl WebdavClient {
     /// It can be any type of file as long as it is transformed to a vector of bytes (Vec<u8>).
     /// This can be achieved with **std::fs::File** or **zip-rs** for sending zip files.
     ///
-    /// Use absolute path to the webdav server folder location
-    pub async fn put<B: Into<Body>>(&self, body: B, path: Url) -> Result<Response, Error> {
+    /// Use absolute path to the webdav server folder location.
+    /// The `digest` is sent as `Digest` header (RFC 3230), e.g. `sha-256=<base64>`, so that the server can verify the content.
+    pub async fn put<B: Into<Body>>(&self, body: B, path: Url, digest: &str) -> Result<Response, Error> {
+        let mut headers = self.custom_header("content-type", "application/octet-stream");
+        headers.extend(self.custom_header("digest", digest));
+
         self.start_request(Method::PUT, path)
-            .headers(self.custom_header("content-type", "application/octet-stream"))
+            .headers(headers)
             .body(body)
             .send()
             .await
             .map_err(|cause| Error::Request { method: String::from("PUT"), cause } )
     }
 
+    pub async fn get(&self, path: Url) -> Result<Response, Error> {
+        self.start_request(Method::GET, path)
+            .send()
+            .await
+            .map_err(|cause| Error::Request { method: String::from("GET"), cause } )
+    }
+
     pub async fn mkcol(&self, path: Url) -> Result<Response, Error> {
         self.start_request(Method::from_bytes(b"MKCOL").unwrap(), path)
             .send()
@@ -32,6 +32,13 @@ pub fn default_checksum_os_cert_store_ca_certificate_file() -> PathBuf {
     PathBuf::from("/usr/local/share/ca-certificates/.opendut-ca.crt.checksum")
 }
 
+pub fn default_client_certificate_path() -> PathBuf {
+    PathBuf::from("/etc/opendut/tls/edgar.pem")
+}
+pub fn default_client_key_path() -> PathBuf {
+    PathBuf::from("/etc/opendut/tls/edgar.key")
+}
+
 pub fn default_config_merge_suggestion_file_path() -> PathBuf {
     PathBuf::from("/etc/opendut/edgar-merge-suggestion.toml")
 }
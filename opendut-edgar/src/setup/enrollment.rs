@@ -0,0 +1,35 @@
+use anyhow::{anyhow, Context};
+use tracing::info;
+use url::Url;
+
+use opendut_types::peer::enrollment::{EnrollmentRequest, EnrollmentResponse, EnrollmentToken};
+use opendut_types::peer::PeerId;
+use opendut_types::util::net::Certificate;
+
+/// Redeems the single-use enrollment token from the setup string at CARL, which returns the credentials of this peer.
+pub async fn redeem(carl: &Url, ca: &Certificate, peer_id: PeerId, token: EnrollmentToken) -> anyhow::Result<EnrollmentResponse> {
+    let ca = reqwest::Certificate::from_pem(ca.encode_as_string().as_bytes())
+        .context("Failed to load CA certificate from Setup-String.")?;
+    let client = reqwest::Client::builder()
+        .add_root_certificate(ca)
+        .build()
+        .context("Failed to create HTTP client for enrollment.")?;
+
+    let url = carl.join("/api/edgar/enrollment")?;
+    info!("Redeeming enrollment token at: {url}");
+
+    let response = client.post(url)
+        .json(&EnrollmentRequest { peer_id, token })
+        .send().await
+        .context("Failed to send enrollment request to CARL.")?;
+
+    let status = response.status();
+    if status.is_success() {
+        let enrollment_response = response.json::<EnrollmentResponse>().await
+            .context("Failed to parse credentials returned by CARL.")?;
+        Ok(enrollment_response)
+    } else {
+        let message = response.text().await.unwrap_or_default();
+        Err(anyhow!("CARL rejected the enrollment token with status {status}: {message}\nPlease generate a new Setup-String."))
+    }
+}
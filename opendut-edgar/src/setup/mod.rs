@@ -3,6 +3,7 @@ use std::net::Ipv4Addr;
 pub use crate::setup::runner::RunMode;
 
 mod constants;
+mod enrollment;
 mod runner;
 pub mod start;
 mod task;
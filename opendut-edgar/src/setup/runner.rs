@@ -18,6 +18,16 @@ const DRY_RUN_BANNER: &str = r"
         ";
 
 pub async fn run(run_mode: RunMode, no_confirm: bool, tasks: &[Box<dyn Task>]) -> anyhow::Result<()> {
+    if let Some(run_mode) = confirm(run_mode, no_confirm)? {
+        run_confirmed(run_mode, tasks);
+    }
+    println!();
+    Ok(())
+}
+
+/// Determines the effective run mode, requests sudo privileges and asks the user for confirmation.
+/// Returns `None`, if the user aborted the setup.
+pub fn confirm(run_mode: RunMode, no_confirm: bool) -> anyhow::Result<Option<RunMode>> {
     let run_mode = if project::is_running_in_development() {
         println!("{DRY_RUN_BANNER}");
         info!("{DRY_RUN_BANNER}");
@@ -31,10 +41,15 @@ pub async fn run(run_mode: RunMode, no_confirm: bool, tasks: &[Box<dyn Task>]) -
             .expect("Failed to request sudo privileges.");
     }
     if no_confirm || user_confirmation(run_mode)? {
-        run_tasks(tasks, run_mode);
+        Ok(Some(run_mode))
+    } else {
+        Ok(None)
     }
-    println!();
-    Ok(())
+}
+
+/// Runs the tasks without asking for confirmation. Expects the run mode returned by [`confirm`].
+pub fn run_confirmed(run_mode: RunMode, tasks: &[Box<dyn Task>]) {
+    run_tasks(tasks, run_mode);
 }
 
 fn user_confirmation(run_mode: RunMode) -> anyhow::Result<bool> {
@@ -6,15 +6,16 @@ use anyhow::Context;
 use tracing::info;
 use url::Url;
 
+use opendut_types::peer::enrollment::{EnrollmentResponse, MutualTlsConfig, PeerEnrollment};
 use opendut_types::peer::PeerSetup;
-use opendut_types::util::net::NetworkInterfaceName;
+use opendut_types::util::net::{AuthConfig, NetworkInterfaceName};
 use opendut_types::vpn::netbird::SetupKey;
 use opendut_types::vpn::VpnPeerConfiguration;
 use opendut_util::telemetry;
 use opendut_util::telemetry::opentelemetry_types::Opentelemetry;
 
 use crate::service::network_interface::manager::NetworkInterfaceManager;
-use crate::setup::{Leader, runner, tasks, User};
+use crate::setup::{enrollment, Leader, runner, tasks, User};
 use crate::setup::runner::RunMode;
 use crate::setup::task::Task;
 use crate::setup::tasks::write_configuration;
@@ -32,14 +33,47 @@ pub async fn managed(run_mode: RunMode, no_confirm: bool, setup_string: String,
     println!("Using PeerId: {}", peer_setup.id);
     println!("Will connect to CARL at: {}", peer_setup.carl);
 
+    //Ask for confirmation before redeeming, as the enrollment token can only be redeemed once.
+    let Some(run_mode) = runner::confirm(run_mode, no_confirm)? else {
+        println!();
+        return Ok(());
+    };
+
+    let EnrollmentResponse { auth_config, mutual_tls } = match (peer_setup.enrollment, run_mode) {
+        (PeerEnrollment::Disabled, _) => EnrollmentResponse { auth_config: AuthConfig::Disabled, mutual_tls: None },
+        (PeerEnrollment::Token(_), RunMode::DryRun) => {
+            println!("Dry-run: Not redeeming the enrollment token, so that the Setup-String remains valid.");
+            EnrollmentResponse { auth_config: AuthConfig::Disabled, mutual_tls: None }
+        }
+        (PeerEnrollment::Token(token), RunMode::Normal) => {
+            enrollment::redeem(&peer_setup.carl, &peer_setup.ca, peer_setup.id, token).await
+                .context("Failed to redeem enrollment token from Setup-String.")?
+        }
+    };
+
     let mut tasks: Vec<Box<dyn Task>> = vec![
         Box::new(tasks::WriteCaCertificate::with_certificate(peer_setup.ca)),
+    ];
+
+    let client_certificate = mutual_tls.map(|MutualTlsConfig { identity, carl_port }| {
+        let write_client_certificate = tasks::WriteClientCertificate::with_identity(identity);
+        let client_certificate = write_configuration::ClientCertificateOverride {
+            certificate_path: Clone::clone(&write_client_certificate.certificate_path),
+            key_path: Clone::clone(&write_client_certificate.key_path),
+            carl_port,
+        };
+        tasks.push(Box::new(write_client_certificate));
+        client_certificate
+    });
+
+    tasks.append(&mut vec![
         Box::new(tasks::CheckCommandLinePrograms),
         Box::new(tasks::WriteConfiguration::with_override(
             write_configuration::ConfigOverride {
                 peer_id: peer_setup.id,
                 carl_url: peer_setup.carl,
-                auth_config: peer_setup.auth_config,
+                auth_config,
+                client_certificate,
             }),
         ),
         Box::new(tasks::CheckCarlReachable),
@@ -47,7 +81,7 @@ pub async fn managed(run_mode: RunMode, no_confirm: bool, setup_string: String,
         Box::new(tasks::copy_rperf::CopyRperf),
 
         Box::new(tasks::LoadKernelModules::default()),
-    ];
+    ]);
 
     if !running_in_docker() {
         tasks.push(Box::new(tasks::CreateKernelModuleLoadRule))
@@ -84,7 +118,9 @@ pub async fn managed(run_mode: RunMode, no_confirm: bool, setup_string: String,
         Box::new(tasks::RestartService),
     ]);
 
-    runner::run(run_mode, no_confirm, &tasks).await
+    runner::run_confirmed(run_mode, &tasks);
+    println!();
+    Ok(())
 }
 
 #[allow(clippy::box_default, clippy::too_many_arguments)]
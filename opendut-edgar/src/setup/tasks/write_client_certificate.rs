@@ -0,0 +1,101 @@
+use crate::fs;
+use std::os::unix::fs::PermissionsExt;
+use std::path::{Path, PathBuf};
+
+use anyhow::Context;
+
+use opendut_types::util::net::ClientIdentity;
+
+use crate::setup::constants;
+use crate::setup::task::{Success, Task, TaskFulfilled};
+
+/// Writes the client certificate and key, which CARL issued on enrollment.
+pub struct WriteClientCertificate {
+    pub identity: ClientIdentity,
+    pub certificate_path: PathBuf,
+    pub key_path: PathBuf,
+}
+
+impl Task for WriteClientCertificate {
+    fn description(&self) -> String {
+        String::from("Write Client Certificate")
+    }
+    fn check_fulfilled(&self) -> anyhow::Result<TaskFulfilled> {
+        let certificate = self.identity.certificate.encode_as_string();
+        let key = self.identity.private_key.encode_as_string();
+
+        let installed = |path: &Path, expected: &str| {
+            fs::read_to_string(path).is_ok_and(|content| content == expected)
+        };
+
+        if installed(&self.certificate_path, &certificate) && installed(&self.key_path, &key) {
+            Ok(TaskFulfilled::Yes)
+        } else {
+            Ok(TaskFulfilled::No)
+        }
+    }
+    fn execute(&self) -> anyhow::Result<Success> {
+        write_file(&self.certificate_path, &self.identity.certificate.encode_as_string(), 0o644)?;
+        write_file(&self.key_path, &self.identity.private_key.encode_as_string(), 0o600)?;
+        Ok(Success::default())
+    }
+}
+
+impl WriteClientCertificate {
+    pub fn with_identity(identity: ClientIdentity) -> Self {
+        Self {
+            identity,
+            certificate_path: constants::default_client_certificate_path(),
+            key_path: constants::default_client_key_path(),
+        }
+    }
+}
+
+fn write_file(path: &Path, content: &str, mode: u32) -> anyhow::Result<()> {
+    let parent_dir = path.parent().unwrap();
+    fs::create_dir_all(parent_dir)
+        .context(format!("Unable to create path {:?}", parent_dir))?;
+
+    fs::write(path, content)
+        .context(format!("Writing client certificate to '{}' was not successful.", path.display()))?;
+    fs::set_permissions(path, std::fs::Permissions::from_mode(mode))
+        .context(format!("Restricting permissions of '{}' was not successful.", path.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use assert_fs::prelude::*;
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+    use pem::Pem;
+
+    use opendut_types::util::net::{Certificate, PrivateKey};
+
+    use super::*;
+
+    #[test]
+    fn should_write_the_key_only_readable_for_its_owner() -> anyhow::Result<()> {
+        let temp = TempDir::new()?;
+        let certificate_path = temp.child("tls/edgar.pem");
+        let key_path = temp.child("tls/edgar.key");
+
+        let task = WriteClientCertificate {
+            identity: ClientIdentity {
+                certificate: Certificate(Pem::new("CERTIFICATE", vec![1, 2, 3])),
+                private_key: PrivateKey(Pem::new("PRIVATE KEY", vec![4, 5, 6])),
+            },
+            certificate_path: certificate_path.to_path_buf(),
+            key_path: key_path.to_path_buf(),
+        };
+        assert_that!(task.check_fulfilled()?, eq(TaskFulfilled::No));
+
+        task.execute()?;
+        assert_that!(task.check_fulfilled()?, eq(TaskFulfilled::Yes));
+
+        assert!(fs::read_to_string(certificate_path.path())?.starts_with("-----BEGIN CERTIFICATE-----"));
+        assert_that!(fs::metadata(key_path.path())?.permissions().mode() & 0o777, eq(0o600));
+
+        Ok(())
+    }
+}
@@ -18,6 +18,14 @@ pub struct ConfigOverride {
     pub peer_id: PeerId,
     pub carl_url: Url,
     pub auth_config: AuthConfig,
+    pub client_certificate: Option<ClientCertificateOverride>,
+}
+
+/// Client certificate, with which EDGAR authenticates at the dedicated port of CARL.
+pub struct ClientCertificateOverride {
+    pub certificate_path: PathBuf,
+    pub key_path: PathBuf,
+    pub carl_port: u16,
 }
 
 pub struct WriteConfiguration {
@@ -45,7 +53,10 @@ impl Task for WriteConfiguration {
 
             let peer_id = self.config_override.peer_id.to_string();
             let carl_host = self.config_override.carl_url.host_str().expect("Host name should be defined in CARL URL.");
-            let carl_port = self.config_override.carl_url.port().unwrap_or(443);
+            let carl_port = match &self.config_override.client_certificate {
+                Some(client_certificate) => client_certificate.carl_port,
+                None => self.config_override.carl_url.port().unwrap_or(443),
+            };
 
             if new_settings.get("peer").is_none() {
                 new_settings["peer"] = toml_edit::table();
@@ -94,6 +105,18 @@ impl Task for WriteConfiguration {
                 }
             };
 
+            if let Some(client_certificate) = &self.config_override.client_certificate {
+                if new_settings.get("network").and_then(|network| network.get("tls")).is_none() {
+                    new_settings["network"]["tls"] = toml_edit::table();
+                    new_settings["network"]["tls"].as_table_mut().unwrap().set_implicit(true);
+                }
+                if new_settings["network"]["tls"].get("client").is_none() {
+                    new_settings["network"]["tls"]["client"] = toml_edit::table();
+                }
+                new_settings["network"]["tls"]["client"]["certificate"] = toml_edit::value(client_certificate.certificate_path.display().to_string());
+                new_settings["network"]["tls"]["client"]["key"] = toml_edit::value(client_certificate.key_path.display().to_string());
+            }
+
             new_settings.to_string()
         };
 
@@ -254,6 +277,41 @@ mod tests {
         Ok(())
     }
 
+    #[rstest]
+    fn should_write_a_fresh_configuration_with_client_certificate(
+        mut write_configuration_auth_disabled: WriteConfiguration,
+    ) -> anyhow::Result<()> {
+
+        write_configuration_auth_disabled.config_override.client_certificate = Some(ClientCertificateOverride {
+            certificate_path: PathBuf::from("/etc/opendut/tls/edgar.pem"),
+            key_path: PathBuf::from("/etc/opendut/tls/edgar.key"),
+            carl_port: 8443,
+        });
+        let path = write_configuration_auth_disabled.config_file_to_write_to.clone();
+
+        runner::test::unchecked(write_configuration_auth_disabled)?;
+
+        let file_content = fs::read_to_string(&path)?;
+
+        assert_that!(file_content, eq(indoc!(r#"
+            [peer]
+            id = "dc72f6d9-d700-455f-8c31-9f15438e7503"
+
+            [network]
+            carl.host = "example.com"
+            carl.port = 8443
+
+            [network.oidc]
+            enabled = false
+
+            [network.tls.client]
+            certificate = "/etc/opendut/tls/edgar.pem"
+            key = "/etc/opendut/tls/edgar.key"
+        "#)));
+
+        Ok(())
+    }
+
     #[rstest]
     fn should_provide_an_merge_suggestion_for_an_already_existing_configuration_but_should_not_delete_existing_unknown_keys(
         write_configuration_auth_enabled: WriteConfiguration,
@@ -380,6 +438,7 @@ mod tests {
                     client_id: ClientId::from(CLIENT_ID),
                     scopes: vec![OAuthScope("test".to_string())],
                 },
+                client_certificate: None,
             },
         }
     }
@@ -395,6 +454,7 @@ mod tests {
                 peer_id: fixture.peer_id,
                 carl_url: Url::parse("https://example.com:1234").unwrap(),
                 auth_config: AuthConfig::Disabled,
+                client_certificate: None,
             },
         }
     }
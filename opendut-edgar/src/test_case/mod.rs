@@ -0,0 +1,105 @@
+use std::ops::Not;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use anyhow::{anyhow, Context};
+
+use opendut_types::test_case::{TestCase, TestCaseReport, TestParameters, TestVerdict};
+
+use crate::fs;
+
+pub mod runner;
+
+/// Environment variable, which EDGAR sets for executable executors. The verdict is written into this directory by default.
+const RESULTS_DIRECTORY_ENV: &str = "OPENDUT_RESULTS_DIR";
+const VERDICT_FILE_NAME: &str = "verdict.json";
+
+pub struct TestCaseOptions {
+    /// Path of the test case, either in the text form (`.csa`) or in the JSON form (`.json`).
+    pub file: PathBuf,
+    /// Values for the `{name}` placeholders, which take precedence over environment variables and defaults.
+    pub parameters: Vec<(String, String)>,
+    /// File to write the verdict to. Defaults to `verdict.json` in the results directory, if EDGAR runs the test case as executor.
+    pub verdict_file: Option<PathBuf>,
+    pub short_step_timeout: Duration,
+}
+
+/// Loads and runs a test case, writes the verdict as JSON and prints a summary.
+pub async fn run(options: TestCaseOptions) -> anyhow::Result<TestVerdict> {
+    let test_case = load(&options.file)?;
+
+    let mut parameters = TestParameters::new(std::env::vars().collect());
+    for (name, value) in options.parameters {
+        parameters.set(name, value);
+    }
+
+    let working_directory = match options.file.parent() {
+        Some(parent) if parent.as_os_str().is_empty().not() => parent.to_path_buf(),
+        _ => std::env::current_dir()?,
+    };
+
+    println!("Running test case '{}'.", test_case.name);
+
+    let runner = runner::TestCaseRunner {
+        working_directory,
+        short_step_timeout: options.short_step_timeout,
+    };
+    let report = runner.run(&test_case, parameters).await;
+
+    let verdict_file = options.verdict_file
+        .or_else(|| std::env::var_os(RESULTS_DIRECTORY_ENV).map(|results_dir| PathBuf::from(results_dir).join(VERDICT_FILE_NAME)));
+    if let Some(verdict_file) = verdict_file {
+        write_report(&report, &verdict_file)?;
+        println!("Wrote verdict to '{}'.", verdict_file.display());
+    }
+
+    println!("Test case '{}' finished with verdict: {}", report.name, report.verdict);
+    Ok(report.verdict)
+}
+
+/// Exit code of `opendut-edgar test-case`, so executors and CI pipelines can react to the verdict.
+/// A test case, of which no step was executed, is not reported as success.
+pub fn exit_code(verdict: TestVerdict) -> i32 {
+    match verdict {
+        TestVerdict::Passed => 0,
+        TestVerdict::Failed => 1,
+        TestVerdict::Error => 2,
+        TestVerdict::Skipped => 3,
+    }
+}
+
+fn load(file: &Path) -> anyhow::Result<TestCase> {
+    let source = fs::read_to_string(file)?;
+
+    let name = file.file_stem()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default();
+
+    let test_case = match file.extension().and_then(|extension| extension.to_str()) {
+        Some("csa") => TestCase::parse_csa(name, &source)?,
+        Some("json") => TestCase::parse_json(&source)?,
+        _ => return Err(anyhow!("Unknown format of test case '{}'. Expected a '.csa' or '.json' file.", file.display())),
+    };
+    Ok(test_case)
+}
+
+fn write_report(report: &TestCaseReport, file: &Path) -> anyhow::Result<()> {
+    let json = serde_json::to_string_pretty(report)?;
+    fs::write(file, json)
+        .context("Failed to write verdict of test case.")
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_only_exit_successfully_for_passed_test_cases() {
+        assert_that!(exit_code(TestVerdict::Passed), eq(0));
+        assert_that!(exit_code(TestVerdict::Skipped), not(eq(0)));
+        assert_that!(exit_code(TestVerdict::Failed), not(eq(0)));
+        assert_that!(exit_code(TestVerdict::Error), not(eq(0)));
+    }
+}
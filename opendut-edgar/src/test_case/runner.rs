@@ -0,0 +1,322 @@
+use std::path::{Path, PathBuf};
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use tokio::process::Command;
+
+use opendut_types::test_case::{Evaluation, ExecuteStep, TestCase, TestCaseReport, TestParameters, TestSection, TestStep, TestStepDuration, TestStepKind, TestStepReport, TestVerdict};
+
+/// Executes the steps of a [`TestCase`] one after another and collects their verdicts.
+///
+/// - A step, which cannot be executed or exits unsuccessfully, results in an `Error` and skips the remaining steps
+///   of its section. An `Error` in the pre-conditions also skips the actions.
+/// - An `evaluate` step, which exits unsuccessfully, results in `Failed`, but the test case continues.
+/// - An `evaluateabort` step, which exits unsuccessfully, results in `Failed` and skips the remaining actions.
+/// - The post-conditions are always executed, so they can clean up after the test case.
+pub struct TestCaseRunner {
+    /// Directory, which the tools are run in and which relative tool paths are resolved against.
+    pub working_directory: PathBuf,
+    /// Time after which steps with a short duration are cancelled.
+    pub short_step_timeout: Duration,
+}
+
+enum StepOutcome {
+    Continue,
+    Abort,
+}
+
+impl TestCaseRunner {
+
+    pub async fn run(&self, test_case: &TestCase, mut parameters: TestParameters) -> TestCaseReport {
+        let mut reports = Vec::new();
+        let mut skipping = false;
+        let mut previous_section = None;
+
+        for (section, step) in test_case.steps() {
+            if previous_section != Some(section) && section == TestSection::PostConditions {
+                skipping = false;
+            }
+            previous_section = Some(section);
+
+            if skipping {
+                println!("[{section}] {}: Skipped", step.label);
+                reports.push(skipped(section, step));
+                continue;
+            }
+
+            let (report, outcome) = self.run_step(section, step, &mut parameters).await;
+
+            match &report.message {
+                Some(message) => println!("[{section}] {}: {} ({message})", step.label, report.verdict),
+                None => println!("[{section}] {}: {}", step.label, report.verdict),
+            }
+            reports.push(report);
+
+            if let StepOutcome::Abort = outcome {
+                skipping = true;
+            }
+        }
+
+        TestCaseReport::new(Clone::clone(&test_case.name), reports)
+    }
+
+    async fn run_step(&self, section: TestSection, step: &TestStep, parameters: &mut TestParameters) -> (TestStepReport, StepOutcome) {
+        let mut report = TestStepReport {
+            section,
+            label: Clone::clone(&step.label),
+            verdict: TestVerdict::Passed,
+            command: None,
+            exit_code: None,
+            duration_ms: 0,
+            message: None,
+        };
+
+        match &step.kind {
+            TestStepKind::Variable { value, default } => {
+                let resolved = match (parameters.substitute(value), default) {
+                    (Ok(value), _) => Ok(value),
+                    (Err(_), Some(default)) => parameters.substitute(default),
+                    (Err(cause), None) => Err(cause),
+                };
+                match resolved {
+                    Ok(value) => {
+                        report.message = Some(format!("{} = {value}", step.label));
+                        parameters.set(Clone::clone(&step.label), value);
+                        (report, StepOutcome::Continue)
+                    }
+                    Err(cause) => {
+                        report.verdict = TestVerdict::Error;
+                        report.message = Some(cause.to_string());
+                        (report, StepOutcome::Abort)
+                    }
+                }
+            }
+            TestStepKind::Execute(execute) => {
+                let command = match substitute_command(execute, parameters) {
+                    Ok(command) => command,
+                    Err(message) => {
+                        report.verdict = TestVerdict::Error;
+                        report.message = Some(message);
+                        return (report, StepOutcome::Abort);
+                    }
+                };
+                report.command = Some(Clone::clone(&command));
+
+                let start = Instant::now();
+                let result = self.execute(&command, execute.duration).await;
+                report.duration_ms = u64::try_from(start.elapsed().as_millis()).unwrap_or(u64::MAX);
+
+                match result {
+                    Ok(Some(0)) => {
+                        report.exit_code = Some(0);
+                        (report, StepOutcome::Continue)
+                    }
+                    Ok(exit_code) => {
+                        report.exit_code = exit_code;
+                        report.message = Some(match exit_code {
+                            Some(exit_code) => format!("Exited with code {exit_code}."),
+                            None => String::from("Terminated by a signal."),
+                        });
+                        match execute.evaluation() {
+                            Some(Evaluation::Evaluate) => {
+                                report.verdict = TestVerdict::Failed;
+                                (report, StepOutcome::Continue)
+                            }
+                            Some(Evaluation::EvaluateAbort) => {
+                                report.verdict = TestVerdict::Failed;
+                                (report, StepOutcome::Abort)
+                            }
+                            None => {
+                                report.verdict = TestVerdict::Error;
+                                (report, StepOutcome::Abort)
+                            }
+                        }
+                    }
+                    Err(message) => {
+                        report.verdict = TestVerdict::Error;
+                        report.message = Some(message);
+                        (report, StepOutcome::Abort)
+                    }
+                }
+            }
+        }
+    }
+
+    /// Runs the command and returns its exit code, which is `None`, if the tool was terminated by a signal.
+    async fn execute(&self, command: &[String], duration: TestStepDuration) -> Result<Option<i32>, String> {
+        let (tool, args) = command.split_first()
+            .ok_or_else(|| String::from("No tool specified."))?;
+
+        let mut cmd = Command::new(resolve_tool(tool, &self.working_directory));
+        cmd.args(args);
+        cmd.current_dir(&self.working_directory);
+        cmd.stdin(Stdio::null());
+        cmd.kill_on_drop(true);
+
+        let mut child = cmd.spawn()
+            .map_err(|cause| format!("Failed to start '{tool}': {cause}"))?;
+
+        let exit_status = match duration {
+            TestStepDuration::Short => {
+                match tokio::time::timeout(self.short_step_timeout, child.wait()).await {
+                    Ok(exit_status) => exit_status,
+                    Err(_) => {
+                        let _ = child.kill().await;
+                        return Err(format!("Cancelled after {} seconds. Use 'duration:long' for long-running steps.", self.short_step_timeout.as_secs()));
+                    }
+                }
+            }
+            TestStepDuration::Long => child.wait().await,
+        }
+        .map_err(|cause| format!("Failed to wait for '{tool}': {cause}"))?;
+
+        Ok(exit_status.code())
+    }
+}
+
+fn skipped(section: TestSection, step: &TestStep) -> TestStepReport {
+    TestStepReport {
+        section,
+        label: Clone::clone(&step.label),
+        verdict: TestVerdict::Skipped,
+        command: None,
+        exit_code: None,
+        duration_ms: 0,
+        message: None,
+    }
+}
+
+fn substitute_command(execute: &ExecuteStep, parameters: &TestParameters) -> Result<Vec<String>, String> {
+    std::iter::once(&execute.tool)
+        .chain(execute.params.iter())
+        .map(|template| parameters.substitute(template).map_err(|cause| cause.to_string()))
+        .collect()
+}
+
+/// Tools with a relative path, like `programs/wait.py`, are resolved against the working directory.
+/// Tools without a path, like `bash`, are looked up in the `PATH`.
+fn resolve_tool(tool: &str, working_directory: &Path) -> PathBuf {
+    let path = Path::new(tool);
+    if path.is_relative() && path.components().count() > 1 {
+        working_directory.join(path)
+    } else {
+        path.to_path_buf()
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use std::fs;
+    use std::os::unix::fs::PermissionsExt;
+
+    use assert_fs::TempDir;
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn runner(working_directory: &TempDir) -> TestCaseRunner {
+        TestCaseRunner {
+            working_directory: working_directory.path().to_path_buf(),
+            short_step_timeout: Duration::from_secs(1),
+        }
+    }
+
+    /// Writes an executable tool, which succeeds, if its third and fourth parameter are equal, e.g. `evaluate == 75 75`.
+    fn write_compare_tool(working_directory: &TempDir) -> anyhow::Result<()> {
+        let programs = working_directory.path().join("programs");
+        fs::create_dir(&programs)?;
+        let tool = programs.join("compare.sh");
+        fs::write(&tool, "#!/bin/sh\n[ \"$3\" = \"$4\" ]\n")?;
+        fs::set_permissions(&tool, fs::Permissions::from_mode(0o755))?;
+        Ok(())
+    }
+
+    fn verdicts(report: &TestCaseReport) -> Vec<(String, TestVerdict)> {
+        report.steps.iter()
+            .map(|step| (Clone::clone(&step.label), step.verdict))
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn should_substitute_parameters_and_defaults() -> anyhow::Result<()> {
+        let working_directory = TempDir::new()?;
+        let test_case = TestCase::parse_csa("Substitution", r#"
+            PreConditions:
+            Actions:
+                File_Path: "{File_Path}" default "out.txt"
+                canIF: "{canIF}" default "can0"
+                Write: execute(tool:"sh", params:["-c", "echo {canIF} > {File_Path}"])
+            PostConditions:
+        "#)?;
+
+        let mut parameters = TestParameters::default();
+        parameters.set("canIF", "vcan0");
+
+        let report = runner(&working_directory).run(&test_case, parameters).await;
+
+        assert_that!(report.verdict, eq(TestVerdict::Passed));
+        assert_that!(fs::read_to_string(working_directory.path().join("out.txt"))?, eq("vcan0\n"));
+        assert_that!(report.steps[2].command, some(eq(vec![String::from("sh"), String::from("-c"), String::from("echo vcan0 > out.txt")])));
+        assert_that!(report.steps[2].exit_code, some(eq(0)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_continue_after_failed_evaluations_and_abort_after_failed_evaluateabort() -> anyhow::Result<()> {
+        let working_directory = TempDir::new()?;
+        write_compare_tool(&working_directory)?;
+        let test_case = TestCase::parse_csa("Evaluations", r#"
+            PreConditions:
+            Actions:
+                Speed: execute(tool:"programs/compare.sh", params:["evaluate", "==", "75", "80"])
+                State: execute(tool:"programs/compare.sh", params:["evaluateabort", "==", "on", "off"])
+                Signal: execute(tool:"programs/compare.sh", params:["evaluate", "==", "75", "75"])
+            PostConditions:
+                Reset: execute(tool:"programs/compare.sh", params:["set", "=", "off", "off"])
+        "#)?;
+
+        let report = runner(&working_directory).run(&test_case, TestParameters::default()).await;
+
+        assert_that!(report.verdict, eq(TestVerdict::Failed));
+        assert_that!(verdicts(&report), eq(vec![
+            (String::from("Speed"), TestVerdict::Failed),
+            (String::from("State"), TestVerdict::Failed),
+            (String::from("Signal"), TestVerdict::Skipped),
+            (String::from("Reset"), TestVerdict::Passed),
+        ]));
+        assert_that!(report.steps[0].exit_code, some(eq(1)));
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn should_skip_actions_after_errors_in_pre_conditions() -> anyhow::Result<()> {
+        let working_directory = TempDir::new()?;
+        let test_case = TestCase::parse_csa("Errors", r#"
+            PreConditions:
+                Unresolved: execute(tool:"sh", params:["-c", "exit 0", "{unknown}"])
+                Prepare: execute(tool:"sh", params:["-c", "exit 0"])
+            Actions:
+                Act: execute(tool:"sh", params:["-c", "exit 0"])
+            PostConditions:
+                Slow: execute(tool:"sleep", params:["10"])
+                Cleanup: execute(tool:"sh", params:["-c", "exit 0"])
+        "#)?;
+
+        let report = runner(&working_directory).run(&test_case, TestParameters::default()).await;
+
+        assert_that!(report.verdict, eq(TestVerdict::Error));
+        assert_that!(verdicts(&report), eq(vec![
+            (String::from("Unresolved"), TestVerdict::Error),
+            (String::from("Prepare"), TestVerdict::Skipped),
+            (String::from("Act"), TestVerdict::Skipped),
+            (String::from("Slow"), TestVerdict::Error),
+            (String::from("Cleanup"), TestVerdict::Skipped),
+        ]));
+        assert_that!(report.steps[0].message, some(eq("No value for placeholder '{unknown}'.")));
+
+        Ok(())
+    }
+}
@@ -10,6 +10,7 @@ use crate::clusters::configurator::types::UserClusterConfiguration;
 use crate::clusters::overview::IsDeployed;
 use crate::components::{ButtonColor, ButtonSize, ButtonState, ConfirmationButton, FontAwesomeIcon, IconButton, Toast, use_toaster};
 use crate::routing::{navigate_to, WellKnownRoutes};
+use crate::util::failure_message;
 
 #[component]
 pub fn Controls(cluster_configuration: ReadSignal<UserClusterConfiguration>, deployed_signal: RwSignal<IsDeployed>) -> impl IntoView {
@@ -59,7 +60,7 @@ fn SaveClusterButton(cluster_configuration: ReadSignal<UserClusterConfiguration>
                         Err(cause) => {
                             error!("Failed to store cluster <{}>, due to error: {:?}", "id", cause);
                             toaster.toast(Toast::builder()
-                                .simple("Failed to store cluster configuration!")
+                                .simple(failure_message("Failed to store cluster configuration!", &cause))
                                 .error()
                             );
                         }
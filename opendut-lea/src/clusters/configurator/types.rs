@@ -1,4 +1,4 @@
-use opendut_types::cluster::{ClusterConfiguration, ClusterId, ClusterName};
+use opendut_types::cluster::{CanCaptureConfiguration, ClusterConfiguration, ClusterId, ClusterName};
 
 use crate::clusters::configurator::components::{DeviceSelection, LeaderSelection};
 use crate::components::UserInputValue;
@@ -22,6 +22,7 @@ pub struct UserClusterConfiguration {
     pub name: UserInputValue,
     pub devices: DeviceSelection,
     pub leader: LeaderSelection,
+    pub can_capture: Option<CanCaptureConfiguration>,
 }
 
 impl UserClusterConfiguration {
@@ -49,6 +50,7 @@ impl TryFrom<UserClusterConfiguration> for ClusterConfiguration {
             name,
             leader,
             devices,
+            can_capture: configuration.can_capture,
         })
     }
 }
@@ -9,6 +9,7 @@ use crate::app::{ExpectGlobals, use_app_globals};
 use crate::clusters::components::CreateClusterButton;
 use crate::components::{BasePageContainer, Breadcrumb, ButtonColor, ButtonSize, ButtonState, FontAwesomeIcon, health, IconButton, Initialized, Toast, use_toaster};
 use crate::components::health::Health;
+use crate::util::failure_message;
 
 #[component]
 pub fn ClustersOverview() -> impl IntoView {
@@ -46,9 +47,9 @@ pub fn ClustersOverview() -> impl IntoView {
                             .success()
                         );
                     }
-                    Err(_) => {
+                    Err(cause) => {
                         toaster.toast(Toast::builder()
-                            .simple("Failed to store cluster deployment!")
+                            .simple(failure_message("Failed to store cluster deployment!", &cause))
                             .error()
                         );
                     }
@@ -69,9 +70,9 @@ pub fn ClustersOverview() -> impl IntoView {
                             .success()
                         );
                     }
-                    Err(_) => {
+                    Err(cause) => {
                         toaster.toast(Toast::builder()
-                            .simple("Failed to delete cluster deployment!")
+                            .simple(failure_message("Failed to delete cluster deployment!", &cause))
                             .error()
                         );
                     }
@@ -70,6 +70,10 @@ pub fn Navbar() -> impl IntoView {
                                         <i class="fa-solid fa-microchip fa-lg pr-1" />
                                         <span class="ml-2 is-size-6">"Peers"</span>
                                     </a>
+                                    <a class="dut-nav-flyout-item" href="/results">
+                                        <i class="fa-solid fa-box-archive fa-lg pr-1" />
+                                        <span class="ml-2 is-size-6">"Results"</span>
+                                    </a>
                                     <a class="dut-nav-flyout-item" href="/cleo">
                                         <i class="fa-solid fa-terminal fa-lg pr-1" />
                                         <span class="ml-2 is-size-6">"CLEO"</span>
@@ -12,6 +12,7 @@ use crate::components::{
 };
 use crate::peers::configurator::types::UserPeerConfiguration;
 use crate::routing::{navigate_to, WellKnownRoutes};
+use crate::util::failure_message;
 
 #[component]
 pub fn Controls(
@@ -62,7 +63,7 @@ fn SavePeerButton(
                         }
                         Err(cause) => {
                             error!("Failed to create peer <{peer_id}>, due to error: {cause:?}");
-                            toaster.toast(Toast::builder().simple("Failed to store peer!").error());
+                            toaster.toast(Toast::builder().simple(failure_message("Failed to store peer!", &cause)).error());
                         }
                     }
                 }
@@ -100,18 +101,23 @@ fn SavePeerButton(
 #[component]
 fn DeletePeerButton(configuration: ReadSignal<UserPeerConfiguration>) -> impl IntoView {
     let globals = use_app_globals();
+    let toaster = use_toaster();
 
-    let delete_action = create_action(move |_: &PeerId| async move {
-        let mut carl = globals.expect_client();
-        let peer_id = configuration.get_untracked().id;
-        let result = carl.peers.delete_peer_descriptor(peer_id).await;
-        match result {
-            Ok(_) => {
-                info!("Successfully deleted peer: {}", peer_id);
-                navigate_to(WellKnownRoutes::PeersOverview);
-            }
-            Err(cause) => {
-                error!("Failed to delete peer <{peer_id}>, due to error: {cause:?}");
+    let delete_action = create_action(move |_: &PeerId| {
+        let toaster = Rc::clone(&toaster);
+        async move {
+            let mut carl = globals.expect_client();
+            let peer_id = configuration.get_untracked().id;
+            let result = carl.peers.delete_peer_descriptor(peer_id).await;
+            match result {
+                Ok(_) => {
+                    info!("Successfully deleted peer: {}", peer_id);
+                    navigate_to(WellKnownRoutes::PeersOverview);
+                }
+                Err(cause) => {
+                    error!("Failed to delete peer <{peer_id}>, due to error: {cause:?}");
+                    toaster.toast(Toast::builder().simple(failure_message("Failed to delete peer!", &cause)).error());
+                }
             }
         }
     });
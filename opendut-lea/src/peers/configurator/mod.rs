@@ -101,12 +101,23 @@ pub fn PeerConfigurator() -> impl IntoView {
                                     engine,
                                     name,
                                     image,
+                                    pull_policy,
+                                    digest,
+                                    registry_credential,
                                     volumes,
                                     devices,
                                     envs,
                                     ports,
                                     command,
                                     args,
+                                    can_databases,
+                                    cpus,
+                                    memory,
+                                    pids_limit,
+                                    network_mode,
+                                    cap_add,
+                                    cap_drop,
+                                    read_only_rootfs,
                                 } = kind {
                                     let volumes = volumes.into_iter()
                                         .map(|volume| {
@@ -137,17 +148,38 @@ pub fn PeerConfigurator() -> impl IntoView {
                                             create_rw_signal(UserInputValue::Right(arg.to_string()))
                                         })
                                         .collect::<Vec<_>>();
+                                    let cap_add = cap_add.into_iter()
+                                        .map(|capability| {
+                                            create_rw_signal(UserInputValue::Right(capability.to_string()))
+                                        })
+                                        .collect::<Vec<_>>();
+                                    let cap_drop = cap_drop.into_iter()
+                                        .map(|capability| {
+                                            create_rw_signal(UserInputValue::Right(capability.to_string()))
+                                        })
+                                        .collect::<Vec<_>>();
                                     user_configuration.executors.push(
                                         create_rw_signal(UserPeerExecutor::Container {
                                             engine,
                                             name: UserInputValue::Right(name.into()),
                                             image: UserInputValue::Right(image.to_string()),
+                                            pull_policy,
+                                            digest: UserInputValue::Right(digest.map(String::from).unwrap_or_default()),
+                                            registry_credential: UserInputValue::Right(registry_credential.map(String::from).unwrap_or_default()),
                                             volumes,
                                             devices,
                                             envs,
                                             ports,
                                             command: UserInputValue::Right(command.into()),
                                             args,
+                                            can_databases,
+                                            cpus: UserInputValue::Right(cpus.map(|cpus| cpus.to_string()).unwrap_or_default()),
+                                            memory: UserInputValue::Right(memory.map(|memory| memory.to_string()).unwrap_or_default()),
+                                            pids_limit: UserInputValue::Right(pids_limit.map(|pids_limit| pids_limit.to_string()).unwrap_or_default()),
+                                            network_mode,
+                                            cap_add,
+                                            cap_drop,
+                                            read_only_rootfs,
                                             results_url: UserInputValue::Right(results_url.map(|s| s.to_string()).unwrap_or(String::new())),
                                             is_collapsed: true
                                         }));
@@ -174,22 +206,36 @@ pub fn PeerConfigurator() -> impl IntoView {
                                 UserPeerExecutor::Container {
                                     name, 
                                     image, 
+                                    digest,
+                                    registry_credential,
                                     volumes, 
                                     devices, 
                                     envs, 
                                     ports, 
                                     command, 
                                     args, 
+                                    cpus,
+                                    memory,
+                                    pids_limit,
+                                    cap_add,
+                                    cap_drop,
                                     ..
                                 } => {
                                     name.is_right() 
                                         && image.is_right() 
+                                        && digest.is_right()
+                                        && registry_credential.is_right()
                                         && volumes.iter().all(|volume| volume.with(|volume| volume.is_right()))
                                         && devices.iter().all(|device| device.with(|device| device.is_right()))
                                         && envs.iter().all(|env| env.with(|env| env.name.is_right()))
                                         && ports.iter().all(|port| port.with(|port| port.is_right()))
                                         && command.is_right()
                                         && args.iter().all(|arg| arg.with(|arg| arg.is_right()))
+                                        && cpus.is_right()
+                                        && memory.is_right()
+                                        && pids_limit.is_right()
+                                        && cap_add.iter().all(|capability| capability.with(|capability| capability.is_right()))
+                                        && cap_drop.iter().all(|capability| capability.with(|capability| capability.is_right()))
                                 }
                             }
                         })
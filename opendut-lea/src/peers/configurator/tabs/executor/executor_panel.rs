@@ -1,7 +1,10 @@
-use leptos::{component, create_read_slice, create_rw_signal, create_slice, event_target_value, IntoView, RwSignal,  SignalGet, SignalGetUntracked, SignalUpdate, SignalWith, SignalWithUntracked, view};
-use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine, IllegalContainerImage}, ResultsUrl};
+use chrono::{DateTime, Local};
+use leptos::{component, create_local_resource, create_read_slice, create_rw_signal, create_slice, event_target_checked, event_target_value, IntoView, RwSignal, Signal, SignalGet, SignalGetUntracked, SignalUpdate, SignalWith, SignalWithUntracked, view};
+use opendut_types::peer::executor::run::{ExecutorLogLine, ExecutorRun, ExecutorRunState};
+use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerImage, ContainerImageDigest, ContainerImagePullPolicy, ContainerName, ContainerPortSpec, ContainerRegistryCredentialName, ContainerVolume, ContainerCpuLimit, ContainerMemoryLimit, ContainerPidsLimit, ContainerNetworkMode, ContainerCapability, Engine, IllegalContainerImage}, ResultsUrl};
 use strum::IntoEnumIterator;
 
+use crate::app::{ExpectGlobals, use_app_globals};
 use crate::components::{ButtonColor, ButtonSize, ButtonState, ConfirmationButton, FontAwesomeIcon, IconButton, Toggled, UserInput, UserInputValue, VectorUserInput};
 use crate::peers::configurator::types::{EMPTY_CONTAINER_IMAGE_ERROR_MESSAGE, UserContainerEnv, UserPeerExecutor};
 use crate::util::NON_BREAKING_SPACE;
@@ -9,6 +12,7 @@ use crate::util::NON_BREAKING_SPACE;
 #[component]
 pub fn ExecutorPanel<OnDeleteFn>(
     executor: RwSignal<UserPeerExecutor>,
+    latest_run: Signal<Option<ExecutorRun>>,
     on_delete: OnDeleteFn
 ) -> impl IntoView
 where
@@ -22,19 +26,30 @@ where
     
     view! {
         <div class="panel is-light">
-            <ExecutorPanelHeading executor on_delete/>
+            <ExecutorPanelHeading executor latest_run on_delete/>
             <div class="panel-block" class=("is-hidden", is_collapsed)>
                 <div class="container">
                     <ExecutorEngineInput executor />
                     <ExecutorContainerNameInput executor />
                     <ExecutorContainerImageInput executor />
+                    <ExecutorContainerPullPolicyInput executor />
+                    <ExecutorContainerImageDigestInput executor />
+                    <ExecutorContainerRegistryCredentialInput executor />
                     <ExecutorContainerVolumesInput executor />
                     <ExecutorContainerDevicesInput executor />
                     <ExecutorContainerEnvsInput executor />
                     <ExecutorContainerPortsInput executor />
                     <ExecutorContainerCommandInput executor />
                     <ExecutorContainerArgsInput executor />
+                    <ExecutorContainerCpusInput executor />
+                    <ExecutorContainerMemoryInput executor />
+                    <ExecutorContainerPidsLimitInput executor />
+                    <ExecutorContainerNetworkModeInput executor />
+                    <ExecutorContainerCapabilitiesInput executor add=true />
+                    <ExecutorContainerCapabilitiesInput executor add=false />
+                    <ExecutorContainerReadOnlyRootfsInput executor />
                     <ExecutorContainerResultsUrlInput executor />
+                    <ExecutorLogViewer latest_run />
                 </div>
             </div>
         </div>
@@ -44,6 +59,7 @@ where
 #[component]
 fn ExecutorPanelHeading<OnDeleteFn>(
     executor: RwSignal<UserPeerExecutor>,
+    latest_run: Signal<Option<ExecutorRun>>,
     on_delete: OnDeleteFn
 ) -> impl IntoView
 where
@@ -103,7 +119,8 @@ where
                         />
                     </span>
                     <span class="is-size-5 has-text-weight-bold pr-3">"Container:"</span>
-                    <span class="is-size-5 has-text-weight-bold">{ executor_heading }</span>
+                    <span class="is-size-5 has-text-weight-bold pr-3">{ executor_heading }</span>
+                    <ExecutorRunStateTag latest_run />
                 </div>
                 <div>
                     <ConfirmationButton
@@ -131,6 +148,100 @@ where
     }
 }
 
+#[component]
+fn ExecutorRunStateTag(
+    latest_run: Signal<Option<ExecutorRun>>,
+) -> impl IntoView {
+
+    let tag = move || {
+        latest_run.get().map(|run| {
+            let color = match run.state {
+                ExecutorRunState::Pending => "is-light",
+                ExecutorRunState::Running => "is-info",
+                ExecutorRunState::Succeeded => "is-success",
+                ExecutorRunState::Failed => "is-danger",
+                ExecutorRunState::Cancelled => "is-warning",
+            };
+            let text = match run.exit_code {
+                Some(exit_code) => format!("{} (Exit Code {})", run.state, exit_code),
+                None => run.state.to_string(),
+            };
+            view! {
+                <span class=format!("tag {color}") title="State of the last run">{ text }</span>
+            }
+        })
+    };
+
+    view! {
+        { tag }
+    }
+}
+
+#[component]
+fn ExecutorLogViewer(
+    latest_run: Signal<Option<ExecutorRun>>,
+) -> impl IntoView {
+
+    let globals = use_app_globals();
+
+    let refresh_counter = RwSignal::new(0_u32);
+
+    let log_source = move || {
+        latest_run.with(|run| run.as_ref().map(|run| (run.peer_id, Clone::clone(&run.executor_name))))
+    };
+
+    let log = create_local_resource(move || (log_source(), refresh_counter.get()), move |(log_source, _)| {
+        let mut carl = globals.expect_client();
+        async move {
+            let (peer_id, executor_name) = log_source?;
+            let result: Result<Vec<ExecutorLogLine>, String> = async {
+                let mut receiver = carl.broker.stream_executor_logs(peer_id, executor_name, false).await
+                    .map_err(|cause| cause.to_string())?;
+                let mut lines = Vec::new();
+                while let Some(batch) = receiver.next().await.map_err(|cause| cause.to_string())? {
+                    lines.extend(batch);
+                }
+                Ok(lines)
+            }.await;
+            Some(result)
+        }
+    });
+
+    let log_text = move || {
+        match log.get().flatten() {
+            Some(Ok(lines)) if lines.is_empty() => String::from("The last run did not produce any output."),
+            Some(Ok(lines)) => {
+                lines.iter()
+                    .map(|line| {
+                        let timestamp = DateTime::<Local>::from(line.timestamp).format("%Y-%m-%d %H:%M:%S%.3f");
+                        format!("{timestamp} [{}] {}", line.stream, line.line)
+                    })
+                    .collect::<Vec<_>>()
+                    .join("\n")
+            }
+            Some(Err(cause)) => format!("Failed to load the log of the last run: {cause}"),
+            None => String::new(),
+        }
+    };
+
+    move || latest_run.with(Option::is_some).then(|| view! {
+        <div class="field">
+            <div class="is-flex is-justify-content-space-between is-align-items-center">
+                <label class="label mb-0">Log of the last run</label>
+                <IconButton
+                    icon=FontAwesomeIcon::ArrowsRotate
+                    color=ButtonColor::Light
+                    size=ButtonSize::Small
+                    state=ButtonState::Enabled
+                    label="Reload Log"
+                    on_action=move || refresh_counter.update(|counter| *counter += 1)
+                />
+            </div>
+            <pre class="is-size-7 mt-2" style="max-height: 24rem; overflow-y: auto;">{ log_text }</pre>
+        </div>
+    })
+}
+
 #[component]
 fn ExecutorEngineInput<>(
     executor: RwSignal<UserPeerExecutor>
@@ -277,6 +388,148 @@ fn ExecutorContainerImageInput(
     }
 }
 
+#[component]
+fn ExecutorContainerPullPolicyInput(
+    executor: RwSignal<UserPeerExecutor>
+) -> impl IntoView {
+
+    let (getter, setter) = create_slice(executor,
+        move |executor| {
+            match executor {
+                UserPeerExecutor::Container { pull_policy, .. } => { *pull_policy }
+            }
+        },
+        move |executor, value| {
+            match executor {
+                UserPeerExecutor::Container { pull_policy, .. } => { *pull_policy = value; }
+            }
+        }
+    );
+
+    let value = getter.get_untracked();
+
+    let dropdown_options = move || {
+            ContainerImagePullPolicy::iter()
+                .map(|pull_policy| {
+                    let pull_policy_value = pull_policy.to_string();
+                    if pull_policy == value {
+                        view! {
+                            <option selected>{pull_policy_value}</option>
+                        }
+                    } else {
+                        view! {
+                            <option>{pull_policy_value}</option>
+                        }
+                    }
+                })
+                .collect::<Vec<_>>()
+    };
+
+    view! {
+        <div class="field pb-3">
+            <label class="label">Pull Policy</label>
+            <div class="control">
+                <div class="select"
+                    on:change=move |ev| {
+                        if let Ok(pull_policy) = event_target_value(&ev).parse::<ContainerImagePullPolicy>() {
+                            setter.set(pull_policy);
+                        }
+                    }>
+                    <select>
+                        { dropdown_options }
+                    </select>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn ExecutorContainerImageDigestInput(
+    executor: RwSignal<UserPeerExecutor>,
+) -> impl IntoView {
+
+    let (getter, setter) = create_slice(executor,
+        move |executor| {
+            match executor {
+                UserPeerExecutor::Container { digest, .. } => { Clone::clone(digest) }
+            }
+        },
+        move |executor, value| {
+            match executor {
+                UserPeerExecutor::Container { digest, .. } => { *digest = value; }
+            }
+        }
+    );
+
+    let validator = |input: String| {
+        if input.is_empty() {
+            return UserInputValue::Right(input);
+        }
+        match ContainerImageDigest::try_from(input.clone()) {
+            Ok(_) => {
+                UserInputValue::Right(input)
+            }
+            Err(cause) => {
+                UserInputValue::Both(cause.to_string(), input)
+            }
+        }
+    };
+
+    view! {
+        <UserInput
+            getter
+            setter
+            label="Image Digest"
+            placeholder="sha256:..."
+            validator
+        />
+    }
+}
+
+#[component]
+fn ExecutorContainerRegistryCredentialInput(
+    executor: RwSignal<UserPeerExecutor>,
+) -> impl IntoView {
+
+    let (getter, setter) = create_slice(executor,
+        move |executor| {
+            match executor {
+                UserPeerExecutor::Container { registry_credential, .. } => { Clone::clone(registry_credential) }
+            }
+        },
+        move |executor, value| {
+            match executor {
+                UserPeerExecutor::Container { registry_credential, .. } => { *registry_credential = value; }
+            }
+        }
+    );
+
+    let validator = |input: String| {
+        if input.is_empty() {
+            return UserInputValue::Right(input);
+        }
+        match ContainerRegistryCredentialName::try_from(input.clone()) {
+            Ok(_) => {
+                UserInputValue::Right(input)
+            }
+            Err(cause) => {
+                UserInputValue::Both(cause.to_string(), input)
+            }
+        }
+    };
+
+    view! {
+        <UserInput
+            getter
+            setter
+            label="Registry Credential"
+            placeholder=""
+            validator
+        />
+    }
+}
+
 #[component]
 fn ExecutorContainerVolumesInput(
     executor: RwSignal<UserPeerExecutor>,
@@ -730,6 +983,291 @@ fn ExecutorContainerEnvsInput(
     }
 }
 
+#[component]
+fn ExecutorContainerCpusInput(
+    executor: RwSignal<UserPeerExecutor>,
+) -> impl IntoView {
+
+    let (getter, setter) = create_slice(executor,
+        move |executor| {
+            match executor {
+                UserPeerExecutor::Container { cpus, .. } => { Clone::clone(cpus) }
+            }
+        },
+        move |executor, value| {
+            match executor {
+                UserPeerExecutor::Container { cpus, .. } => { *cpus = value; }
+            }
+        }
+    );
+
+    let validator = |input: String| {
+        if input.is_empty() {
+            return UserInputValue::Right(input);
+        }
+        match ContainerCpuLimit::try_from(input.clone()) {
+            Ok(_) => {
+                UserInputValue::Right(input)
+            }
+            Err(cause) => {
+                UserInputValue::Both(cause.to_string(), input)
+            }
+        }
+    };
+
+    view! {
+        <UserInput
+            getter
+            setter
+            label="CPU Limit"
+            placeholder="e.g. 1.5"
+            validator
+        />
+    }
+}
+
+#[component]
+fn ExecutorContainerMemoryInput(
+    executor: RwSignal<UserPeerExecutor>,
+) -> impl IntoView {
+
+    let (getter, setter) = create_slice(executor,
+        move |executor| {
+            match executor {
+                UserPeerExecutor::Container { memory, .. } => { Clone::clone(memory) }
+            }
+        },
+        move |executor, value| {
+            match executor {
+                UserPeerExecutor::Container { memory, .. } => { *memory = value; }
+            }
+        }
+    );
+
+    let validator = |input: String| {
+        if input.is_empty() {
+            return UserInputValue::Right(input);
+        }
+        match ContainerMemoryLimit::try_from(input.clone()) {
+            Ok(_) => {
+                UserInputValue::Right(input)
+            }
+            Err(cause) => {
+                UserInputValue::Both(cause.to_string(), input)
+            }
+        }
+    };
+
+    view! {
+        <UserInput
+            getter
+            setter
+            label="Memory Limit"
+            placeholder="e.g. 512m"
+            validator
+        />
+    }
+}
+
+#[component]
+fn ExecutorContainerPidsLimitInput(
+    executor: RwSignal<UserPeerExecutor>,
+) -> impl IntoView {
+
+    let (getter, setter) = create_slice(executor,
+        move |executor| {
+            match executor {
+                UserPeerExecutor::Container { pids_limit, .. } => { Clone::clone(pids_limit) }
+            }
+        },
+        move |executor, value| {
+            match executor {
+                UserPeerExecutor::Container { pids_limit, .. } => { *pids_limit = value; }
+            }
+        }
+    );
+
+    let validator = |input: String| {
+        if input.is_empty() {
+            return UserInputValue::Right(input);
+        }
+        match input.parse::<ContainerPidsLimit>() {
+            Ok(_) => {
+                UserInputValue::Right(input)
+            }
+            Err(cause) => {
+                UserInputValue::Both(cause.to_string(), input)
+            }
+        }
+    };
+
+    view! {
+        <UserInput
+            getter
+            setter
+            label="PIDs Limit"
+            placeholder=""
+            validator
+        />
+    }
+}
+
+#[component]
+fn ExecutorContainerNetworkModeInput(
+    executor: RwSignal<UserPeerExecutor>
+) -> impl IntoView {
+
+    let (getter, setter) = create_slice(executor,
+        move |executor| {
+            match executor {
+                UserPeerExecutor::Container { network_mode, .. } => { *network_mode }
+            }
+        },
+        move |executor, value| {
+            match executor {
+                UserPeerExecutor::Container { network_mode, .. } => { *network_mode = value; }
+            }
+        }
+    );
+
+    let value = getter.get_untracked();
+
+    let dropdown_options = move || {
+            ContainerNetworkMode::iter()
+                .map(|network_mode| {
+                    let network_mode_value = network_mode.to_string();
+                    if network_mode == value {
+                        view! {
+                            <option selected>{network_mode_value}</option>
+                        }
+                    } else {
+                        view! {
+                            <option>{network_mode_value}</option>
+                        }
+                    }
+                })
+                .collect::<Vec<_>>()
+    };
+
+    view! {
+        <div class="field pb-3">
+            <label class="label">Network</label>
+            <div class="control">
+                <div class="select"
+                    on:change=move |ev| {
+                        if let Ok(network_mode) = event_target_value(&ev).parse::<ContainerNetworkMode>() {
+                            setter.set(network_mode);
+                        }
+                    }>
+                    <select>
+                        { dropdown_options }
+                    </select>
+                </div>
+            </div>
+        </div>
+    }
+}
+
+#[component]
+fn ExecutorContainerCapabilitiesInput(
+    executor: RwSignal<UserPeerExecutor>,
+    /// Whether the capabilities are added to or dropped from the container.
+    add: bool,
+) -> impl IntoView {
+
+    let (getter, setter) = create_slice(executor,
+        move |executor| {
+            match executor {
+                UserPeerExecutor::Container { cap_add, cap_drop, .. } => {
+                    if add { Clone::clone(cap_add) } else { Clone::clone(cap_drop) }
+                }
+            }
+        },
+        move |executor, value| {
+            match executor {
+                UserPeerExecutor::Container { cap_add, cap_drop, .. } => {
+                    if add { *cap_add = value; } else { *cap_drop = value; }
+                }
+            }
+        }
+    );
+
+    let validator = |input: String| {
+        match ContainerCapability::try_from(input.clone()) {
+            Ok(_) => {
+                UserInputValue::Right(input)
+            }
+            Err(cause) => {
+                UserInputValue::Both(cause.to_string(), input)
+            }
+        }
+    };
+
+    let on_add_capability = move || {
+        executor.update(|executor| {
+            let capability = create_rw_signal(
+                UserInputValue::Left(String::from("Container capability must not be empty."))
+            );
+            match executor {
+                UserPeerExecutor::Container{ cap_add, cap_drop, .. } => {
+                    if add { cap_add.push(capability); } else { cap_drop.push(capability); }
+                }
+            }
+        })
+    };
+
+    let (label, delete_label) = if add {
+        ("Add Capabilities", "Delete Added Capability?")
+    } else {
+        ("Drop Capabilities", "Delete Dropped Capability?")
+    };
+
+    view! {
+        <VectorUserInput
+            getter
+            setter
+            label
+            placeholder="e.g. NET_ADMIN"
+            validator
+            on_add=on_add_capability
+            delete_label
+        />
+    }
+}
+
+#[component]
+fn ExecutorContainerReadOnlyRootfsInput(
+    executor: RwSignal<UserPeerExecutor>,
+) -> impl IntoView {
+
+    let (getter, setter) = create_slice(executor,
+        move |executor| {
+            match executor {
+                UserPeerExecutor::Container { read_only_rootfs, .. } => { *read_only_rootfs }
+            }
+        },
+        move |executor, value| {
+            match executor {
+                UserPeerExecutor::Container { read_only_rootfs, .. } => { *read_only_rootfs = value; }
+            }
+        }
+    );
+
+    view! {
+        <div class="field pb-3">
+            <label class="checkbox">
+                <input
+                    type="checkbox"
+                    class="mr-2"
+                    prop:checked=move || getter.get()
+                    on:change=move |ev| setter.set(event_target_checked(&ev))
+                />
+                "Read-only root filesystem"
+            </label>
+        </div>
+    }
+}
+
 #[component]
 fn ExecutorContainerResultsUrlInput(
     executor: RwSignal<UserPeerExecutor>,
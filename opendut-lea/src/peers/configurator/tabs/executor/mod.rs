@@ -1,6 +1,10 @@
-use leptos::{component, create_memo, create_rw_signal, create_slice, IntoView, RwSignal, SignalUpdate, SignalWith, SignalWithUntracked, view};
+use leptos::{component, create_local_resource, create_memo, create_rw_signal, create_slice, IntoView, RwSignal, Signal, SignalGet, SignalUpdate, SignalWith, SignalWithUntracked, view};
+use opendut_types::peer::configuration::ParameterValue;
 use opendut_types::peer::executor::container::Engine;
+use opendut_types::peer::executor::ExecutorDescriptor;
+use opendut_types::peer::executor::run::ExecutorRun;
 
+use crate::app::{ExpectGlobals, use_app_globals};
 use crate::components::UserInputValue;
 use crate::peers::configurator::tabs::executor::executor_panel::ExecutorPanel;
 use crate::peers::configurator::types::{EMPTY_CONTAINER_IMAGE_ERROR_MESSAGE, UserPeerConfiguration, UserPeerExecutor};
@@ -55,13 +59,36 @@ fn ExecutorTable(peer_configuration: RwSignal<UserPeerConfiguration>) -> impl In
 
     
 
+    let globals = use_app_globals();
+    let peer_id = peer_configuration.with_untracked(|peer_configuration| peer_configuration.id);
+
+    let executor_runs = create_local_resource(|| {}, move |_| {
+        let mut carl = globals.expect_client();
+        async move {
+            carl.peers.list_executor_runs(Some(peer_id)).await
+                .expect("Failed to request the list of executor runs.")
+        }
+    });
+
+    let latest_run_of = move |executor: RwSignal<UserPeerExecutor>| {
+        Signal::derive(move || {
+            let executor_id = ExecutorDescriptor::try_from(executor.get()).ok()?
+                .parameter_identifier();
+            executor_runs.get()?
+                .into_iter()
+                .filter(|run| run.executor_id == executor_id)
+                .max_by_key(|run| run.created_at)
+        })
+    };
+
     let panels = create_memo(move |_| {
         executors.with(|executors| {
             executors.iter()
                 .cloned()
                 .map(|executor| {
+                    let latest_run: Signal<Option<ExecutorRun>> = latest_run_of(executor);
                     view! {
-                        <ExecutorPanel executor on_delete=on_executor_delete  />
+                        <ExecutorPanel executor latest_run on_delete=on_executor_delete  />
                     }
                 })
                 .collect::<Vec<_>>()
@@ -83,12 +110,23 @@ fn ExecutorTable(peer_configuration: RwSignal<UserPeerConfiguration>) -> impl In
                                     engine: Engine::Podman,
                                     name: UserInputValue::Right(String::from("")),
                                     image: UserInputValue::Left(String::from(EMPTY_CONTAINER_IMAGE_ERROR_MESSAGE)),
+                                    pull_policy: Default::default(),
+                                    digest: UserInputValue::Right(String::from("")),
+                                    registry_credential: UserInputValue::Right(String::from("")),
                                     volumes: vec![],
                                     devices: vec![],
                                     envs: vec![],
                                     ports: vec![],
                                     command: UserInputValue::Right(String::from("")),
                                     args: vec![],
+                                    can_databases: vec![],
+                                    cpus: UserInputValue::Right(String::from("")),
+                                    memory: UserInputValue::Right(String::from("")),
+                                    pids_limit: UserInputValue::Right(String::from("")),
+                                    network_mode: Default::default(),
+                                    cap_add: vec![],
+                                    cap_drop: vec![],
+                                    read_only_rootfs: false,
                                     results_url: UserInputValue::Right(String::from("")),
                                     is_collapsed: false
                                 }
@@ -1,7 +1,7 @@
 use std::ops::Not;
 use leptos::*;
 
-use opendut_types::util::net::{CanSamplePoint, NetworkInterfaceConfiguration, NetworkInterfaceName, NetworkInterfaceNameError};
+use opendut_types::util::net::{CanFrameFilter, CanSamplePoint, NetworkInterfaceConfiguration, NetworkInterfaceName, NetworkInterfaceNameError};
 
 use crate::components::{ButtonColor, ButtonSize, ButtonState, FontAwesomeIcon, IconButton, UserInput, UserInputValue};
 use crate::peers::configurator::types::{UserNetworkInterface};
@@ -123,6 +123,8 @@ where A: Fn(NetworkInterfaceName, UserNetworkInterfaceConfiguration) + 'static {
                                             fd: true,
                                             data_bitrate: 2000000,
                                             data_sample_point: CanSamplePoint::try_from(0.7).unwrap(),
+                                            filter: CanFrameFilter::default(),
+                                            rate_limit: None,
                                         }
                                     }
                                 };
@@ -1,8 +1,9 @@
 use leptos::{RwSignal, SignalGetUntracked};
 
+use opendut_types::can::CanDatabaseId;
 use opendut_types::peer::executor::ExecutorDescriptor;
 use opendut_types::peer::{PeerDescriptor, PeerId, PeerLocation, PeerName, PeerNetworkDescriptor};
-use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerName, ContainerPortSpec, ContainerVolume, Engine}, ExecutorKind, ExecutorDescriptors, ResultsUrl};
+use opendut_types::peer::executor::{container::{ContainerCommand, ContainerCommandArgument, ContainerDevice, ContainerEnvironmentVariable, ContainerImage, ContainerImageDigest, ContainerImagePullPolicy, ContainerName, ContainerPortSpec, ContainerRegistryCredentialName, ContainerVolume, ContainerCpuLimit, ContainerMemoryLimit, ContainerPidsLimit, ContainerNetworkMode, ContainerCapability, Engine}, ExecutorKind, ExecutorDescriptors, ResultsUrl};
 use opendut_types::topology::{DeviceDescription, DeviceDescriptor, DeviceId, DeviceName, Topology};
 use opendut_types::util::net::{NetworkInterfaceDescriptor, NetworkInterfaceName};
 
@@ -55,12 +56,23 @@ pub enum UserPeerExecutor {
         engine: Engine,
         name: UserInputValue,
         image: UserInputValue,
+        pull_policy: ContainerImagePullPolicy,
+        digest: UserInputValue,
+        registry_credential: UserInputValue,
         volumes: Vec<RwSignal<UserInputValue>>,
         devices: Vec<RwSignal<UserInputValue>>,
         envs: Vec<RwSignal<UserContainerEnv>>,
         ports: Vec<RwSignal<UserInputValue>>,
         command: UserInputValue,
         args: Vec<RwSignal<UserInputValue>>,
+        can_databases: Vec<CanDatabaseId>,
+        cpus: UserInputValue,
+        memory: UserInputValue,
+        pids_limit: UserInputValue,
+        network_mode: ContainerNetworkMode,
+        cap_add: Vec<RwSignal<UserInputValue>>,
+        cap_drop: Vec<RwSignal<UserInputValue>>,
+        read_only_rootfs: bool,
         results_url: UserInputValue,
         is_collapsed: bool,
     }
@@ -214,12 +226,23 @@ impl TryFrom<UserPeerExecutor> for ExecutorDescriptor {
                 engine,
                 name,
                 image,
+                pull_policy,
+                digest,
+                registry_credential,
                 volumes,
                 devices,
                 envs,
                 ports,
                 command,
                 args,
+                can_databases,
+                cpus,
+                memory,
+                pids_limit,
+                network_mode,
+                cap_add,
+                cap_drop,
+                read_only_rootfs,
                 results_url,
                 ..
             } => {
@@ -235,6 +258,8 @@ impl TryFrom<UserPeerExecutor> for ExecutorDescriptor {
                         ContainerImage::try_from(description)
                             .map_err(|_| PeerMisconfigurationError::InvalidPeerExecutor)
                     })?;
+                let digest = optional_user_input(digest, ContainerImageDigest::try_from)?;
+                let registry_credential = optional_user_input(registry_credential, ContainerRegistryCredentialName::try_from)?;
                 let command = command
                     .right_ok_or(PeerMisconfigurationError::InvalidPeerExecutor)
                     .and_then(|command| {
@@ -283,6 +308,25 @@ impl TryFrom<UserPeerExecutor> for ExecutorDescriptor {
                                     .map_err(|_| PeerMisconfigurationError::InvalidPeerExecutor)))
                     })
                     .collect::<Result<Vec<_>, _>>()?;
+                let cpus = optional_user_input(cpus, ContainerCpuLimit::try_from)?;
+                let memory = optional_user_input(memory, ContainerMemoryLimit::try_from)?;
+                let pids_limit = optional_user_input(pids_limit, |pids_limit| pids_limit.parse::<ContainerPidsLimit>())?;
+                let cap_add = cap_add
+                    .into_iter()
+                    .map(|signal| signal.get_untracked())
+                    .map(|capability| {
+                        capability.right_ok_or(PeerMisconfigurationError::InvalidPeerExecutor)
+                            .and_then(|capability| ContainerCapability::try_from(capability).map_err(|_| PeerMisconfigurationError::InvalidPeerExecutor))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
+                let cap_drop = cap_drop
+                    .into_iter()
+                    .map(|signal| signal.get_untracked())
+                    .map(|capability| {
+                        capability.right_ok_or(PeerMisconfigurationError::InvalidPeerExecutor)
+                            .and_then(|capability| ContainerCapability::try_from(capability).map_err(|_| PeerMisconfigurationError::InvalidPeerExecutor))
+                    })
+                    .collect::<Result<Vec<_>, _>>()?;
                 let results_url = results_url
                     .right_ok_or(PeerMisconfigurationError::InvalidPeerExecutor)
                     .and_then(|results_url| {
@@ -299,12 +343,23 @@ impl TryFrom<UserPeerExecutor> for ExecutorDescriptor {
                         engine,
                         name,
                         image,
+                        pull_policy,
+                        digest,
+                        registry_credential,
                         volumes,
                         devices,
                         envs,
                         ports,
                         command,
                         args,
+                        can_databases,
+                        cpus,
+                        memory,
+                        pids_limit,
+                        network_mode,
+                        cap_add,
+                        cap_drop,
+                        read_only_rootfs,
                     },
                     results_url,
                 })
@@ -314,3 +369,16 @@ impl TryFrom<UserPeerExecutor> for ExecutorDescriptor {
     }
 }
 
+/// Parses an optional input of the executor configuration, which is unset, if left empty.
+fn optional_user_input<T, E>(input: UserInputValue, parse: impl FnOnce(String) -> Result<T, E>) -> Result<Option<T>, PeerMisconfigurationError> {
+    input
+        .right_ok_or(PeerMisconfigurationError::InvalidPeerExecutor)
+        .and_then(|input| {
+            if input.is_empty() {
+                Ok(None)
+            } else {
+                parse(input).map(Some)
+                    .map_err(|_| PeerMisconfigurationError::InvalidPeerExecutor)
+            }
+        })
+}
@@ -0,0 +1,287 @@
+use chrono::{DateTime, Local};
+use leptos::*;
+use leptos_router::{use_location, use_navigate};
+
+use opendut_types::cluster::{ClusterConfiguration, ClusterId};
+use opendut_types::peer::{PeerDescriptor, PeerId};
+use opendut_types::peer::executor::run::{ExecutorRunId, ResultArtifact};
+
+use crate::app::{ExpectGlobals, use_app_globals};
+use crate::components::{BasePageContainer, Breadcrumb, ButtonColor, ButtonSize, ButtonState, FontAwesomeIcon, IconButton, Initialized};
+
+const CLUSTER_PARAMETER: &str = "cluster";
+const PEER_PARAMETER: &str = "peer";
+const RUN_PARAMETER: &str = "run";
+
+/// Filter of the listed result artifacts, taken from the query parameters `cluster`, `peer` and `run`.
+#[derive(Clone, Debug, Default, PartialEq)]
+struct ResultsFilter {
+    cluster_id: Option<ClusterId>,
+    peer_id: Option<PeerId>,
+    run_id: Option<ExecutorRunId>,
+}
+
+impl ResultsFilter {
+    fn href(&self) -> String {
+        let parameters = [
+            self.cluster_id.map(|cluster_id| format!("{CLUSTER_PARAMETER}={cluster_id}")),
+            self.peer_id.map(|peer_id| format!("{PEER_PARAMETER}={peer_id}")),
+            self.run_id.map(|run_id| format!("{RUN_PARAMETER}={run_id}")),
+        ].into_iter().flatten().collect::<Vec<_>>();
+
+        if parameters.is_empty() {
+            String::from("/results")
+        } else {
+            format!("/results?{}", parameters.join("&"))
+        }
+    }
+}
+
+#[component(transparent)]
+pub fn ResultsOverview() -> impl IntoView {
+
+    #[component]
+    fn inner() -> impl IntoView {
+
+        let globals = use_app_globals();
+        let location = use_location();
+        let navigate = use_navigate();
+
+        let filter = create_memo(move |_| {
+            location.query.with(|query| ResultsFilter {
+                cluster_id: query.get(CLUSTER_PARAMETER).and_then(|value| ClusterId::try_from(value.as_str()).ok()),
+                peer_id: query.get(PEER_PARAMETER).and_then(|value| PeerId::try_from(value.as_str()).ok()),
+                run_id: query.get(RUN_PARAMETER).and_then(|value| ExecutorRunId::try_from(value.as_str()).ok()),
+            })
+        });
+
+        let artifacts: Resource<ResultsFilter, Vec<ResultArtifact>> = create_local_resource(move || filter.get(), move |filter| {
+            let mut carl = globals.expect_client();
+            async move {
+                carl.peers.list_result_artifacts(filter.peer_id, filter.cluster_id, filter.run_id).await
+                    .expect("Failed to request the list of result artifacts.")
+            }
+        });
+
+        let clusters: Resource<(), Vec<ClusterConfiguration>> = create_local_resource(|| {}, move |_| {
+            let mut carl = globals.expect_client();
+            async move {
+                carl.cluster.list_cluster_configurations().await
+                    .expect("Failed to request the list of clusters.")
+            }
+        });
+
+        let peers: Resource<(), Vec<PeerDescriptor>> = create_local_resource(|| {}, move |_| {
+            let mut carl = globals.expect_client();
+            async move {
+                carl.peers.list_peer_descriptors().await
+                    .expect("Failed to request the list of peers.")
+            }
+        });
+
+        let cluster_name = move |cluster_id: Option<ClusterId>| {
+            cluster_id
+                .map(|cluster_id| {
+                    clusters.get().unwrap_or_default().into_iter()
+                        .find(|cluster| cluster.id == cluster_id)
+                        .map(|cluster| cluster.name.to_string())
+                        .unwrap_or_else(|| cluster_id.to_string())
+                })
+                .unwrap_or_else(|| String::from("-"))
+        };
+
+        let peer_name = move |peer_id: PeerId| {
+            peers.get().unwrap_or_default().into_iter()
+                .find(|peer| peer.id == peer_id)
+                .map(|peer| peer.name.to_string())
+                .unwrap_or_else(|| peer_id.to_string())
+        };
+
+        let cluster_options = move || {
+            let selected = filter.get().cluster_id;
+            clusters.get().unwrap_or_default().into_iter()
+                .map(|cluster| {
+                    let is_selected = selected == Some(cluster.id);
+                    view! {
+                        <option value=cluster.id.to_string() selected=is_selected>{ cluster.name.to_string() }</option>
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let peer_options = move || {
+            let selected = filter.get().peer_id;
+            peers.get().unwrap_or_default().into_iter()
+                .map(|peer| {
+                    let is_selected = selected == Some(peer.id);
+                    view! {
+                        <option value=peer.id.to_string() selected=is_selected>{ peer.name.to_string() }</option>
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let on_cluster_change = {
+            let navigate = Clone::clone(&navigate);
+            move |ev: ev::Event| {
+                let cluster_id = ClusterId::try_from(event_target_value(&ev).as_str()).ok();
+                navigate(&ResultsFilter { cluster_id, ..filter.get() }.href(), Default::default());
+            }
+        };
+
+        let on_peer_change = {
+            let navigate = Clone::clone(&navigate);
+            move |ev: ev::Event| {
+                let peer_id = PeerId::try_from(event_target_value(&ev).as_str()).ok();
+                navigate(&ResultsFilter { peer_id, ..filter.get() }.href(), Default::default());
+            }
+        };
+
+        let run_filter = move || {
+            filter.get().run_id.map(|run_id| {
+                let href = ResultsFilter { run_id: None, ..filter.get() }.href();
+                view! {
+                    <div class="control">
+                        <div class="tags has-addons">
+                            <span class="tag is-medium">{ format!("Run {run_id}") }</span>
+                            <a class="tag is-medium is-delete" href=href aria-label="Remove run filter"></a>
+                        </div>
+                    </div>
+                }
+            })
+        };
+
+        let artifacts_table_rows = move || {
+            artifacts.get().unwrap_or_default().into_iter().rev()
+                .map(|artifact| {
+                    let run_href = ResultsFilter { run_id: Some(artifact.run_id), ..filter.get() }.href();
+                    let peer_href = format!("/peers/{}/configure/general", artifact.peer_id);
+                    view! {
+                        <tr>
+                            <td class="is-vcentered">{ format_timestamp(&artifact) }</td>
+                            <td class="is-vcentered">{ cluster_name(artifact.cluster_id) }</td>
+                            <td class="is-vcentered"><a href=peer_href>{ peer_name(artifact.peer_id) }</a></td>
+                            <td class="is-vcentered">{ Clone::clone(&artifact.executor_name) }</td>
+                            <td class="is-vcentered"><a href=run_href>{ artifact.run_id.to_string() }</a></td>
+                            <td class="is-vcentered has-text-right">{ format_size(artifact.size) }</td>
+                            <td class="is-vcentered">{ location_view(&artifact) }</td>
+                        </tr>
+                    }
+                })
+                .collect::<Vec<_>>()
+        };
+
+        let breadcrumbs = vec![
+            Breadcrumb::new("Dashboard", "/"),
+            Breadcrumb::new("Results", "/results")
+        ];
+
+        view! {
+            <BasePageContainer
+                title="Results"
+                breadcrumbs=breadcrumbs
+                controls=view! {
+                    <div class="buttons">
+                        <IconButton
+                            icon=FontAwesomeIcon::ArrowsRotate
+                            color=ButtonColor::Light
+                            size=ButtonSize::Normal
+                            state=ButtonState::Enabled
+                            label="Refresh table of results"
+                            on_action=move || artifacts.refetch()
+                        />
+                    </div>
+                }
+            >
+                <div class="field is-grouped is-grouped-multiline mt-4">
+                    <div class="control">
+                        <div class="select" on:change=on_cluster_change>
+                            <select>
+                                <option value="" selected=move || filter.get().cluster_id.is_none()>"All clusters"</option>
+                                { cluster_options }
+                            </select>
+                        </div>
+                    </div>
+                    <div class="control">
+                        <div class="select" on:change=on_peer_change>
+                            <select>
+                                <option value="" selected=move || filter.get().peer_id.is_none()>"All peers"</option>
+                                { peer_options }
+                            </select>
+                        </div>
+                    </div>
+                    { run_filter }
+                </div>
+                <div class="mt-4">
+                    <Transition
+                        fallback=move || view! { <p>"Loading..."</p> }
+                    >
+                        <table class="table is-hoverable is-fullwidth">
+                            <thead>
+                                <tr>
+                                    <th>"Uploaded"</th>
+                                    <th>"Cluster"</th>
+                                    <th>"Peer"</th>
+                                    <th>"Executor"</th>
+                                    <th>"Run"</th>
+                                    <th class="has-text-right">"Size"</th>
+                                    <th>"Location"</th>
+                                </tr>
+                            </thead>
+                            <tbody>
+                                { artifacts_table_rows }
+                            </tbody>
+                        </table>
+                    </Transition>
+                </div>
+            </BasePageContainer>
+        }
+    }
+
+    view! {
+        <Initialized>
+            <Inner />
+        </Initialized>
+    }
+}
+
+/// Links artifacts, which can be downloaded by the browser. Other locations, e.g. in an S3 bucket, are only displayed.
+fn location_view(artifact: &ResultArtifact) -> impl IntoView {
+    let location = artifact.location.to_string();
+    if ["http", "https"].contains(&artifact.location.scheme()) {
+        view! {
+            <a href=Clone::clone(&location) target="_blank" title=format!("SHA-256: {}", artifact.checksum)>
+                <span class="icon"><i class="fa-solid fa-download"></i></span>
+                <span>{ location }</span>
+            </a>
+        }.into_view()
+    } else {
+        view! {
+            <span title=format!("SHA-256: {}", artifact.checksum)>{ location }</span>
+        }.into_view()
+    }
+}
+
+fn format_timestamp(artifact: &ResultArtifact) -> String {
+    DateTime::<Local>::from(artifact.uploaded_at)
+        .format("%Y-%m-%d %H:%M:%S")
+        .to_string()
+}
+
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next_unit in &UNITS[1..] {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next_unit;
+    }
+    format!("{size:.1} {unit}")
+}
@@ -0,0 +1,11 @@
+use std::fmt::Display;
+
+use opendut_carl_api::carl::ClientError;
+
+/// Message of a toast about a failed request to CARL, which tells users, when they lack the permission for the request.
+pub fn failure_message<E: Display>(message: &str, cause: &ClientError<E>) -> String {
+    match cause {
+        ClientError::PermissionDenied(reason) => format!("{message} {reason}"),
+        _ => String::from(message),
+    }
+}
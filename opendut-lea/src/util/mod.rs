@@ -1,7 +1,9 @@
+pub use client_error::failure_message;
 pub use ior::Ior;
 pub use tick::Tick;
 
 pub mod url;
+mod client_error;
 mod ior;
 mod tick;
 pub mod net;
@@ -9,18 +9,26 @@ fn main() -> Result<()> {
         "proto/opendut/types/peer/configuration.proto",
         "proto/opendut/types/peer/executor/executor.proto",
         "proto/opendut/types/peer/executor/container.proto",
+        "proto/opendut/types/peer/executor/executable.proto",
+        "proto/opendut/types/peer/executor/run.proto",
         "proto/opendut/types/topology/device.proto",
         "proto/opendut/types/topology/topology.proto",
         "proto/opendut/types/util/metadata.proto",
         "proto/opendut/types/util/net.proto",
         "proto/opendut/types/util/uuid.proto",
         "proto/opendut/types/vpn/vpn.proto",
-        "proto/opendut/types/cleo/cleo.proto"
+        "proto/opendut/types/cleo/cleo.proto",
+        "proto/opendut/types/can/can.proto",
+        "proto/opendut/types/audit/audit.proto",
+        "proto/opendut/types/secret/secret.proto",
+        "proto/opendut/types/service_account/service_account.proto",
     ];
 
     let includes = [
         "proto/"
     ];
 
-    prost_build::compile_protos(&protos, &includes)
+    prost_build::Config::new()
+        .type_attribute(".opendut.types.peer.executor.ExecutorDescriptor.kind", "#[allow(clippy::large_enum_variant)]") // the container executor is held inline, like in the native type
+        .compile_protos(&protos, &includes)
 }
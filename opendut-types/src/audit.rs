@@ -0,0 +1,129 @@
+use std::fmt;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::resources::Id;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct AuditEventId(pub Uuid);
+
+impl AuditEventId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for AuditEventId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for AuditEventId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Record of a mutating operation, which a user requested from CARL.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct AuditEvent {
+    pub id: AuditEventId,
+    pub timestamp: SystemTime,
+    /// Name of the authenticated user. Not known, if authentication is disabled.
+    pub user: Option<String>,
+    /// Name of the operation, e.g. `delete_cluster_deployment`.
+    pub operation: String,
+    /// ID of the peer, cluster or other resource, which the operation affected.
+    pub resource_id: Option<Id>,
+    /// Summary of the resource before the operation, if it existed.
+    pub before: Option<String>,
+    /// Summary of the resource after the operation, if it still exists.
+    pub after: Option<String>,
+    pub outcome: AuditOutcome,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum AuditOutcome {
+    Success,
+    Failure { message: String },
+}
+
+impl AuditOutcome {
+    pub fn of<T, E: fmt::Display>(result: &Result<T, E>) -> Self {
+        match result {
+            Ok(_) => AuditOutcome::Success,
+            Err(error) => AuditOutcome::Failure { message: error.to_string() },
+        }
+    }
+}
+
+impl fmt::Display for AuditOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AuditOutcome::Success => write!(f, "Success"),
+            AuditOutcome::Failure { message } => write!(f, "Failure: {message}"),
+        }
+    }
+}
+
+/// Criteria, which audit events have to match. Criteria, which are not set, match all events.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct AuditEventFilter {
+    pub user: Option<String>,
+    pub operation: Option<String>,
+    pub resource_id: Option<Id>,
+    pub since: Option<SystemTime>,
+    pub until: Option<SystemTime>,
+    /// Maximum number of events, of which the most recent are returned.
+    pub limit: Option<u32>,
+}
+
+impl AuditEventFilter {
+    pub fn matches(&self, event: &AuditEvent) -> bool {
+        self.user.as_ref().map_or(true, |user| event.user.as_ref() == Some(user))
+            && self.operation.as_ref().map_or(true, |operation| event.operation == *operation)
+            && self.resource_id.map_or(true, |resource_id| event.resource_id == Some(resource_id))
+            && self.since.map_or(true, |since| event.timestamp >= since)
+            && self.until.map_or(true, |until| event.timestamp <= until)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::*;
+
+    #[test]
+    fn should_match_events_by_all_set_criteria() {
+        let resource_id = Id::random();
+        let event = AuditEvent {
+            id: AuditEventId::random(),
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_secs(1_000),
+            user: Some(String::from("alice")),
+            operation: String::from("delete_cluster_deployment"),
+            resource_id: Some(resource_id),
+            before: Some(String::from("cluster <1>")),
+            after: None,
+            outcome: AuditOutcome::Success,
+        };
+
+        assert!(AuditEventFilter::default().matches(&event));
+        assert!(AuditEventFilter {
+            user: Some(String::from("alice")),
+            operation: Some(String::from("delete_cluster_deployment")),
+            resource_id: Some(resource_id),
+            since: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_000)),
+            until: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(2_000)),
+            limit: Some(1),
+        }.matches(&event));
+
+        assert!(!AuditEventFilter { user: Some(String::from("bob")), ..Default::default() }.matches(&event));
+        assert!(!AuditEventFilter { resource_id: Some(Id::random()), ..Default::default() }.matches(&event));
+        assert!(!AuditEventFilter { since: Some(SystemTime::UNIX_EPOCH + Duration::from_secs(1_001)), ..Default::default() }.matches(&event));
+    }
+}
@@ -0,0 +1,445 @@
+use std::fmt;
+use std::fmt::Write;
+use std::ops::Not;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CanDatabaseId(pub Uuid);
+
+impl CanDatabaseId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for CanDatabaseId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal CanDatabaseId: {value}")]
+pub struct IllegalCanDatabaseId {
+    pub value: String,
+}
+
+impl TryFrom<&str> for CanDatabaseId {
+    type Error = IllegalCanDatabaseId;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(value)
+            .map(Self)
+            .map_err(|_| IllegalCanDatabaseId { value: String::from(value) })
+    }
+}
+
+impl TryFrom<String> for CanDatabaseId {
+    type Error = IllegalCanDatabaseId;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        CanDatabaseId::try_from(value.as_str())
+    }
+}
+
+impl FromStr for CanDatabaseId {
+    type Err = IllegalCanDatabaseId;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        CanDatabaseId::try_from(value)
+    }
+}
+
+impl fmt::Display for CanDatabaseId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct CanDatabaseName(pub(crate) String);
+
+impl CanDatabaseName {
+
+    pub const MIN_LENGTH: usize = 4;
+    pub const MAX_LENGTH: usize = 64;
+
+    pub fn value(self) -> String {
+        self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalCanDatabaseName {
+    #[error("CAN database name '{value}' is too short. Expected at least {expected} characters, got {actual}.")]
+    TooShort { value: String, expected: usize, actual: usize },
+    #[error("CAN database name '{value}' is too long. Expected at most {expected} characters, got {actual}.")]
+    TooLong { value: String, expected: usize, actual: usize },
+    #[error("CAN database name '{value}' contains invalid characters.")]
+    InvalidCharacter { value: String },
+    #[error("CAN database name '{value}' contains invalid start or end characters.")]
+    InvalidStartEndCharacter { value: String },
+}
+
+impl From<CanDatabaseName> for String {
+    fn from(value: CanDatabaseName) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<String> for CanDatabaseName {
+    type Error = IllegalCanDatabaseName;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let length = value.len();
+        if length < Self::MIN_LENGTH {
+            Err(IllegalCanDatabaseName::TooShort {
+                value,
+                expected: Self::MIN_LENGTH,
+                actual: length,
+            })
+        }
+        else if length > Self::MAX_LENGTH {
+            Err(IllegalCanDatabaseName::TooLong {
+                value,
+                expected: Self::MAX_LENGTH,
+                actual: length,
+            })
+        }
+        else if crate::util::invalid_start_and_end_of_a_name(&value) {
+            Err(IllegalCanDatabaseName::InvalidStartEndCharacter { value })
+        }
+        else if value.chars().any(|c| crate::util::valid_characters_in_name(&c).not()) {
+            Err(IllegalCanDatabaseName::InvalidCharacter { value })
+        }
+        else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for CanDatabaseName {
+    type Error = IllegalCanDatabaseName;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        CanDatabaseName::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for CanDatabaseName {
+    type Err = IllegalCanDatabaseName;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        CanDatabaseName::try_from(value)
+    }
+}
+
+impl fmt::Display for CanDatabaseName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct CanDatabaseVersion(pub(crate) String);
+
+impl CanDatabaseVersion {
+
+    pub const MAX_LENGTH: usize = 64;
+
+    pub fn value(self) -> String {
+        self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalCanDatabaseVersion {
+    #[error("CAN database version must not be empty.")]
+    Empty,
+    #[error("CAN database version '{value}' is too long. Expected at most {expected} characters, got {actual}.")]
+    TooLong { value: String, expected: usize, actual: usize },
+    #[error("CAN database version '{value}' contains invalid characters. Only alphanumeric characters, '.', '-' and '_' are allowed.")]
+    InvalidCharacter { value: String },
+    #[error("CAN database version '{value}' must not start or end with '.', '-' or '_', nor contain '..'.")]
+    InvalidDotsOrSeparators { value: String },
+}
+
+impl From<CanDatabaseVersion> for String {
+    fn from(value: CanDatabaseVersion) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<String> for CanDatabaseVersion {
+    type Error = IllegalCanDatabaseVersion;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let length = value.len();
+        if value.is_empty() {
+            Err(IllegalCanDatabaseVersion::Empty)
+        }
+        else if length > Self::MAX_LENGTH {
+            Err(IllegalCanDatabaseVersion::TooLong {
+                value,
+                expected: Self::MAX_LENGTH,
+                actual: length,
+            })
+        }
+        else if value.chars().any(|c| crate::util::valid_characters_in_name(&c).not() && c != '.') {
+            Err(IllegalCanDatabaseVersion::InvalidCharacter { value })
+        }
+        else if crate::util::invalid_start_and_end_of_a_name(&value) || value.starts_with('.') || value.ends_with('.') || value.contains("..") {
+            Err(IllegalCanDatabaseVersion::InvalidDotsOrSeparators { value })
+        }
+        else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for CanDatabaseVersion {
+    type Error = IllegalCanDatabaseVersion;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        CanDatabaseVersion::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for CanDatabaseVersion {
+    type Err = IllegalCanDatabaseVersion;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        CanDatabaseVersion::try_from(value)
+    }
+}
+
+impl fmt::Display for CanDatabaseVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum CanDatabaseFormat {
+    Arxml,
+    Dbc,
+}
+
+impl CanDatabaseFormat {
+    pub fn file_extension(&self) -> &'static str {
+        match self {
+            CanDatabaseFormat::Arxml => "arxml",
+            CanDatabaseFormat::Dbc => "dbc",
+        }
+    }
+
+    pub fn from_file_extension(extension: &str) -> Option<Self> {
+        match extension.to_ascii_lowercase().as_str() {
+            "arxml" | "xml" => Some(CanDatabaseFormat::Arxml),
+            "dbc" => Some(CanDatabaseFormat::Dbc),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for CanDatabaseFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CanDatabaseFormat::Arxml => write!(f, "ARXML"),
+            CanDatabaseFormat::Dbc => write!(f, "DBC"),
+        }
+    }
+}
+
+/// Hex-encoded SHA-256 checksum of the content of a CAN database.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CanDatabaseHash(String);
+
+impl CanDatabaseHash {
+
+    pub const LENGTH: usize = 64;
+
+    pub fn compute(content: &[u8]) -> Self {
+        let digest = Sha256::digest(content);
+        let hex = digest.iter()
+            .fold(String::with_capacity(Self::LENGTH), |mut hex, byte| {
+                let _ = write!(hex, "{byte:02x}");
+                hex
+            });
+        Self(hex)
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal CanDatabaseHash '{value}'. Expected a hex-encoded SHA-256 checksum with {} characters.", CanDatabaseHash::LENGTH)]
+pub struct IllegalCanDatabaseHash {
+    pub value: String,
+}
+
+impl TryFrom<String> for CanDatabaseHash {
+    type Error = IllegalCanDatabaseHash;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.len() == Self::LENGTH && value.chars().all(|c| c.is_ascii_hexdigit()) {
+            Ok(Self(value.to_ascii_lowercase()))
+        } else {
+            Err(IllegalCanDatabaseHash { value })
+        }
+    }
+}
+
+impl TryFrom<&str> for CanDatabaseHash {
+    type Error = IllegalCanDatabaseHash;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        CanDatabaseHash::try_from(value.to_owned())
+    }
+}
+
+impl From<CanDatabaseHash> for String {
+    fn from(value: CanDatabaseHash) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for CanDatabaseHash {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Metadata of a CAN database (communication matrix), without its content.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct CanDatabaseDescriptor {
+    pub id: CanDatabaseId,
+    pub name: CanDatabaseName,
+    pub version: CanDatabaseVersion,
+    pub format: CanDatabaseFormat,
+    pub hash: CanDatabaseHash,
+}
+
+impl CanDatabaseDescriptor {
+    /// Name of the file under which the database is provided to executors, e.g. `powertrain-1.2.dbc`.
+    pub fn file_name(&self) -> String {
+        format!("{}-{}.{}", self.name, self.version, self.format.file_extension())
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash)]
+pub struct CanDatabase {
+    pub descriptor: CanDatabaseDescriptor,
+    pub content: Vec<u8>,
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalCanDatabase {
+    #[error("CAN database '{name}' <{id}> must not be empty.")]
+    EmptyContent { id: CanDatabaseId, name: CanDatabaseName },
+    #[error("Content of CAN database '{name}' <{id}> does not match its checksum. Expected '{expected}', got '{actual}'.")]
+    HashMismatch { id: CanDatabaseId, name: CanDatabaseName, expected: CanDatabaseHash, actual: CanDatabaseHash },
+}
+
+impl CanDatabase {
+    pub fn new(
+        id: CanDatabaseId,
+        name: CanDatabaseName,
+        version: CanDatabaseVersion,
+        format: CanDatabaseFormat,
+        content: Vec<u8>,
+    ) -> Result<Self, IllegalCanDatabase> {
+        if content.is_empty() {
+            return Err(IllegalCanDatabase::EmptyContent { id, name });
+        }
+        let hash = CanDatabaseHash::compute(&content);
+        Ok(Self {
+            descriptor: CanDatabaseDescriptor { id, name, version, format, hash },
+            content,
+        })
+    }
+
+    /// Checks that the content matches the checksum stored in the descriptor.
+    pub fn verify(&self) -> Result<(), IllegalCanDatabase> {
+        let actual = CanDatabaseHash::compute(&self.content);
+        if actual == self.descriptor.hash {
+            Ok(())
+        } else {
+            Err(IllegalCanDatabase::HashMismatch {
+                id: self.descriptor.id,
+                name: Clone::clone(&self.descriptor.name),
+                expected: Clone::clone(&self.descriptor.hash),
+                actual,
+            })
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_compute_sha256_hash_of_content() -> Result<()> {
+        let hash = CanDatabaseHash::compute(b"abc");
+        assert_that!(hash.value(), eq("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"));
+        Ok(())
+    }
+
+    #[test]
+    fn should_detect_modified_content() -> Result<()> {
+        let mut database = CanDatabase::new(
+            CanDatabaseId::random(),
+            CanDatabaseName::try_from("powertrain")?,
+            CanDatabaseVersion::try_from("1.2")?,
+            CanDatabaseFormat::Dbc,
+            b"VERSION \"\"".to_vec(),
+        )?;
+        assert_that!(database.verify().is_ok(), eq(true));
+
+        database.content.push(b'\n');
+        assert_that!(database.verify().is_err(), eq(true));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_empty_content() -> Result<()> {
+        let result = CanDatabase::new(
+            CanDatabaseId::random(),
+            CanDatabaseName::try_from("powertrain")?,
+            CanDatabaseVersion::try_from("1.2")?,
+            CanDatabaseFormat::Arxml,
+            vec![],
+        );
+        assert_that!(result.is_err(), eq(true));
+        Ok(())
+    }
+
+    #[test]
+    fn should_only_accept_versions_usable_in_file_names() -> Result<()> {
+        assert_that!(CanDatabaseVersion::try_from("1.2.0-rc_1").map(String::from)?, eq("1.2.0-rc_1"));
+
+        for version in ["1/../x", "../1", "1..2", ".1", "1.", "-1", "1 2", "1\\2"] {
+            assert!(CanDatabaseVersion::try_from(version).is_err(), "version '{version}' should be rejected");
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_malformed_hash() -> Result<()> {
+        assert_that!(CanDatabaseHash::try_from("xyz").is_err(), eq(true));
+        assert_that!(CanDatabaseHash::try_from("BA7816BF8F01CFEA414140DE5DAE2223B00361A396177A9CB410FF61F20015AD").map(String::from)?, eq("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"));
+        Ok(())
+    }
+}
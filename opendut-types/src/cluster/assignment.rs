@@ -1,5 +1,5 @@
 use std::net::IpAddr;
-use crate::cluster::ClusterId;
+use crate::cluster::{CanCaptureConfiguration, ClusterId};
 use crate::peer::PeerId;
 use crate::util::net::NetworkInterfaceDescriptor;
 use crate::util::Port;
@@ -10,6 +10,7 @@ pub struct ClusterAssignment {
     pub id: ClusterId,
     pub leader: PeerId,
     pub assignments: Vec<PeerClusterAssignment>,
+    pub can_capture: Option<CanCaptureConfiguration>,
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -0,0 +1,51 @@
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+
+use crate::peer::executor::ResultsUrl;
+use crate::util::net::NetworkInterfaceName;
+
+/// Configures the recording of CAN frames on the peers of a cluster.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CanCaptureConfiguration {
+    pub source: CanCaptureSource,
+    pub rotation: CanCaptureRotation,
+    /// WebDAV collection, which rotated log files are uploaded to.
+    /// If not set, the log files are only kept on the peer.
+    pub results_url: Option<ResultsUrl>,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum CanCaptureSource {
+    /// Capture all frames crossing the openDuT CAN bridge of the peer.
+    Bridge,
+    /// Capture only the frames on the given device interfaces.
+    /// Interfaces which are not present on a peer are skipped.
+    DeviceInterfaces(Vec<NetworkInterfaceName>),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CanCaptureRotation {
+    /// Start a new log file once the current one reaches this size in bytes. `0` disables size-based rotation.
+    pub max_file_size: u64,
+    /// Start a new log file once the current one is open for this long. A zero duration disables time-based rotation.
+    pub max_file_duration: Duration,
+    /// Number of rotated log files to keep on the peer. `0` keeps all of them.
+    pub max_files: u32,
+}
+
+impl CanCaptureRotation {
+    pub const DEFAULT_MAX_FILE_SIZE: u64 = 10 * 1024 * 1024;
+    pub const DEFAULT_MAX_FILE_DURATION: Duration = Duration::from_secs(10 * 60);
+    pub const DEFAULT_MAX_FILES: u32 = 10;
+}
+
+impl Default for CanCaptureRotation {
+    fn default() -> Self {
+        Self {
+            max_file_size: Self::DEFAULT_MAX_FILE_SIZE,
+            max_file_duration: Self::DEFAULT_MAX_FILE_DURATION,
+            max_files: Self::DEFAULT_MAX_FILES,
+        }
+    }
+}
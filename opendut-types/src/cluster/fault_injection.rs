@@ -0,0 +1,149 @@
+use std::fmt;
+use std::time::Duration;
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::cluster::ClusterId;
+use crate::util::net::CanIdFilter;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct CanFaultInjectionRuleId(pub Uuid);
+
+impl CanFaultInjectionRuleId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for CanFaultInjectionRuleId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal CanFaultInjectionRuleId: {value}")]
+pub struct IllegalCanFaultInjectionRuleId {
+    pub value: String,
+}
+
+impl TryFrom<&str> for CanFaultInjectionRuleId {
+    type Error = IllegalCanFaultInjectionRuleId;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(value)
+            .map(Self)
+            .map_err(|_| IllegalCanFaultInjectionRuleId { value: String::from(value) })
+    }
+}
+
+impl fmt::Display for CanFaultInjectionRuleId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Manipulates the CAN frames, which are forwarded from the cluster to the device interfaces of its peers.
+/// Rules can be enabled and disabled while the cluster is deployed.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CanFaultInjectionRule {
+    pub id: CanFaultInjectionRuleId,
+    pub cluster_id: ClusterId,
+    /// Frames, which the fault is injected into.
+    pub filter: CanIdFilter,
+    pub fault: CanFault,
+    pub enabled: bool,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub enum CanFault {
+    /// Drops the given percentage of frames at random.
+    Drop { percentage: u8 },
+    /// Inverts the bits set in `mask` within the data byte at index `byte`.
+    FlipBits { byte: u8, mask: u8 },
+    /// Overwrites the data length code of classic CAN frames.
+    SetDlc { dlc: u8 },
+    /// Delays the frames.
+    Delay { delay: Duration },
+}
+
+impl CanFault {
+    pub const MAX_DATA_BYTE: u8 = 63;
+    pub const MAX_DLC: u8 = 8;
+    pub const MAX_DELAY: Duration = Duration::from_secs(10);
+
+    pub fn verify(&self) -> Result<(), IllegalCanFault> {
+        match *self {
+            CanFault::Drop { percentage } => {
+                if percentage == 0 || percentage > 100 {
+                    return Err(IllegalCanFault::DropPercentageOutOfRange { percentage });
+                }
+            }
+            CanFault::FlipBits { byte, mask } => {
+                if byte > Self::MAX_DATA_BYTE {
+                    return Err(IllegalCanFault::DataByteOutOfRange { byte, max: Self::MAX_DATA_BYTE });
+                }
+                if mask == 0 {
+                    return Err(IllegalCanFault::EmptyBitMask);
+                }
+            }
+            CanFault::SetDlc { dlc } => {
+                if dlc > Self::MAX_DLC {
+                    return Err(IllegalCanFault::DlcOutOfRange { dlc, max: Self::MAX_DLC });
+                }
+            }
+            CanFault::Delay { delay } => {
+                if delay.is_zero() || delay > Self::MAX_DELAY {
+                    return Err(IllegalCanFault::DelayOutOfRange { delay, max: Self::MAX_DELAY });
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl fmt::Display for CanFault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CanFault::Drop { percentage } => write!(f, "drop {percentage}%"),
+            CanFault::FlipBits { byte, mask } => write!(f, "flip bits {mask:#04X} of byte {byte}"),
+            CanFault::SetDlc { dlc } => write!(f, "set DLC to {dlc}"),
+            CanFault::Delay { delay } => write!(f, "delay by {} ms", delay.as_millis()),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug, Eq, PartialEq)]
+pub enum IllegalCanFault {
+    #[error("Drop percentage must be between 1 and 100, but was {percentage}.")]
+    DropPercentageOutOfRange { percentage: u8 },
+    #[error("Data byte index must be at most {max}, but was {byte}.")]
+    DataByteOutOfRange { byte: u8, max: u8 },
+    #[error("Bit mask must not be zero.")]
+    EmptyBitMask,
+    #[error("DLC must be at most {max}, but was {dlc}.")]
+    DlcOutOfRange { dlc: u8, max: u8 },
+    #[error("Delay must be greater than zero and at most {} ms, but was {} ms.", max.as_millis(), delay.as_millis())]
+    DelayOutOfRange { delay: Duration, max: Duration },
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_verify_can_faults() {
+        assert_that!(CanFault::Drop { percentage: 100 }.verify(), ok(anything()));
+        assert_that!(CanFault::Drop { percentage: 0 }.verify(), err(eq(IllegalCanFault::DropPercentageOutOfRange { percentage: 0 })));
+        assert_that!(CanFault::FlipBits { byte: 7, mask: 0x01 }.verify(), ok(anything()));
+        assert_that!(CanFault::FlipBits { byte: 64, mask: 0x01 }.verify(), err(anything()));
+        assert_that!(CanFault::FlipBits { byte: 0, mask: 0 }.verify(), err(eq(IllegalCanFault::EmptyBitMask)));
+        assert_that!(CanFault::SetDlc { dlc: 9 }.verify(), err(anything()));
+        assert_that!(CanFault::Delay { delay: Duration::from_millis(20) }.verify(), ok(anything()));
+        assert_that!(CanFault::Delay { delay: Duration::ZERO }.verify(), err(anything()));
+    }
+}
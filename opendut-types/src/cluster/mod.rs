@@ -6,11 +6,15 @@ use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
 pub use assignment::*;
+pub use can_capture::*;
+pub use fault_injection::*;
 
 use crate::peer::PeerId;
 use crate::topology::DeviceId;
 
 mod assignment;
+mod can_capture;
+mod fault_injection;
 pub mod state;
 
 
@@ -149,6 +153,8 @@ pub struct ClusterConfiguration {
     pub name: ClusterName,
     pub leader: PeerId,
     pub devices: HashSet<DeviceId>,
+    #[serde(default)]
+    pub can_capture: Option<CanCaptureConfiguration>,
 }
 
 #[derive(thiserror::Error, Clone, Debug)]
@@ -8,6 +8,11 @@ pub mod vpn;
 pub mod util;
 pub mod resources;
 pub mod cleo;
+pub mod can;
+pub mod audit;
+pub mod secret;
+pub mod service_account;
+pub mod test_case;
 
 pub trait ShortName {
     fn short_name(&self) -> &'static str;
@@ -1,12 +1,16 @@
 use std::any::Any;
+use std::fmt;
 use std::hash::{DefaultHasher, Hash, Hasher};
 
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 
+use crate::can::CanDatabase;
 use crate::cluster::ClusterAssignment;
 use crate::OPENDUT_UUID_NAMESPACE;
 use crate::peer::executor::{ExecutorDescriptor, ExecutorKind};
+use crate::peer::executor::container::ContainerRegistryCredential;
+use crate::secret::Secret;
 use crate::util::net::NetworkInterfaceName;
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -24,6 +28,10 @@ pub struct PeerNetworkConfiguration {
 #[derive(Clone, Debug, Default, PartialEq, Eq)]
 pub struct PeerConfiguration2 {
     pub executors: Vec<Parameter<ExecutorDescriptor>>,
+    pub can_databases: Vec<Parameter<CanDatabase>>,
+    pub registry_credentials: Vec<Parameter<ContainerRegistryCredential>>,
+    /// Secrets referenced by the executors. CARL only adds them when sending the configuration to the peer.
+    pub secrets: Vec<Parameter<Secret>>,
     //TODO migrate more parameters
 }
 impl PeerConfiguration2 {
@@ -37,6 +45,39 @@ impl PeerConfiguration2 {
 
         self.executors.push(parameter);
     }
+
+    pub fn insert_can_database(&mut self, value: CanDatabase, target: ParameterTarget) {
+        let parameter = Parameter {
+            id: value.parameter_identifier(),
+            dependencies: vec![],
+            target,
+            value,
+        };
+
+        self.can_databases.push(parameter);
+    }
+
+    pub fn insert_registry_credential(&mut self, value: ContainerRegistryCredential, target: ParameterTarget) {
+        let parameter = Parameter {
+            id: value.parameter_identifier(),
+            dependencies: vec![],
+            target,
+            value,
+        };
+
+        self.registry_credentials.push(parameter);
+    }
+
+    pub fn insert_secret(&mut self, value: Secret, target: ParameterTarget) {
+        let parameter = Parameter {
+            id: value.parameter_identifier(),
+            dependencies: vec![],
+            target,
+            value,
+        };
+
+        self.secrets.push(parameter);
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -47,9 +88,16 @@ pub struct Parameter<V: ParameterValue> {
     pub value: V,
 }
 
-#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct ParameterId(pub Uuid);
 
+impl fmt::Display for ParameterId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ParameterTarget {
     Present,
@@ -86,7 +134,7 @@ impl ParameterValue for ExecutorDescriptor {
     fn parameter_identifier(&self) -> ParameterId {
         let mut hasher = DefaultHasher::new(); //ID not stable across Rust releases
         match &self.kind {
-            ExecutorKind::Executable => self.kind.hash(&mut hasher),
+            ExecutorKind::Executable { name, .. } => name.hash(&mut hasher),
             ExecutorKind::Container { name, .. } => name.hash(&mut hasher),
         }
         self.results_url.hash(&mut hasher);
@@ -96,9 +144,42 @@ impl ParameterValue for ExecutorDescriptor {
         ParameterId(id)
     }
 }
+impl ParameterValue for CanDatabase {
+    fn parameter_identifier(&self) -> ParameterId {
+        let mut hasher = DefaultHasher::new(); //ID not stable across Rust releases
+        self.descriptor.id.hash(&mut hasher);
+        self.descriptor.hash.hash(&mut hasher);
+        let id = hasher.finish();
+
+        let id = Uuid::new_v5(&OPENDUT_UUID_NAMESPACE, &id.to_le_bytes());
+        ParameterId(id)
+    }
+}
+impl ParameterValue for ContainerRegistryCredential {
+    fn parameter_identifier(&self) -> ParameterId {
+        let mut hasher = DefaultHasher::new(); //ID not stable across Rust releases
+        self.hash(&mut hasher);
+        let id = hasher.finish();
+
+        let id = Uuid::new_v5(&OPENDUT_UUID_NAMESPACE, &id.to_le_bytes());
+        ParameterId(id)
+    }
+}
+impl ParameterValue for Secret {
+    fn parameter_identifier(&self) -> ParameterId {
+        let mut hasher = DefaultHasher::new(); //ID not stable across Rust releases
+        self.hash(&mut hasher);
+        let id = hasher.finish();
+
+        let id = Uuid::new_v5(&OPENDUT_UUID_NAMESPACE, &id.to_le_bytes());
+        ParameterId(id)
+    }
+}
 
 #[cfg(test)]
 mod tests {
+    use crate::peer::executor::executable::{ExecutableName, ExecutableProgram};
+
     use super::*;
 
     #[test]
@@ -106,7 +187,14 @@ mod tests {
         let mut peer_configuration = PeerConfiguration2::default();
 
         let value = ExecutorDescriptor{
-            kind: ExecutorKind::Executable,
+            kind: ExecutorKind::Executable {
+                name: ExecutableName::try_from("MyExecutable").unwrap(),
+                program: ExecutableProgram::try_from("/usr/bin/true").unwrap(),
+                args: vec![],
+                envs: vec![],
+                working_directory: None,
+                user: None,
+            },
             results_url: None
         };
         let target = ParameterTarget::Present;
@@ -0,0 +1,84 @@
+use std::fmt;
+use std::fmt::{Debug, Formatter};
+
+use serde::{Deserialize, Serialize};
+use uuid::Uuid;
+
+use crate::peer::PeerId;
+use crate::util::net::{AuthConfig, ClientIdentity};
+
+/// Short-lived, single-use secret, which EDGAR redeems at CARL to obtain its OIDC client credentials.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct EnrollmentToken(String);
+
+impl EnrollmentToken {
+    pub fn random() -> Self {
+        Self(format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple()))
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+impl Debug for EnrollmentToken {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("EnrollmentToken([redacted])")
+    }
+}
+
+impl From<String> for EnrollmentToken {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for EnrollmentToken {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
+/// How a peer obtains its credentials during setup.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum PeerEnrollment {
+    Token(EnrollmentToken),
+    Disabled,
+}
+
+/// Body of the request, with which EDGAR redeems its enrollment token at CARL.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EnrollmentRequest {
+    pub peer_id: PeerId,
+    pub token: EnrollmentToken,
+}
+
+/// Credentials, which CARL returns to EDGAR in exchange for its enrollment token.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct EnrollmentResponse {
+    pub auth_config: AuthConfig,
+    /// Set, if CARL authenticates peers via mutual TLS instead of OIDC.
+    pub mutual_tls: Option<MutualTlsConfig>,
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MutualTlsConfig {
+    pub identity: ClientIdentity,
+    /// Port, at which CARL accepts connections authenticated with the client certificate.
+    pub carl_port: u16,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_generate_distinct_tokens() {
+        let token = EnrollmentToken::random();
+
+        assert_eq!(token.value().len(), 64);
+        assert_ne!(token, EnrollmentToken::random());
+        assert_eq!(format!("{token:?}"), "EnrollmentToken([redacted])");
+    }
+}
@@ -6,6 +6,8 @@ use std::ops::Not;
 use serde::{Deserialize, Serialize};
 use strum::EnumIter;
 
+use crate::secret::{self, ResolveSecretReferenceError, Secret, SecretName, SECRET_REFERENCE_PREFIX, SECRET_REFERENCE_SUFFIX};
+
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
 #[serde(rename_all = "kebab-case")]
 pub enum Engine {
@@ -136,6 +138,14 @@ impl ContainerEnvironmentVariable {
             Ok(Self{name, value: value.into()})
         }
     }
+
+    /// Copy of this variable, in whose value the references to secrets are replaced with the values of the secrets.
+    pub fn resolve_secret_references(&self, secrets: &HashMap<SecretName, Secret>) -> Result<Self, ResolveSecretReferenceError> {
+        Ok(Self {
+            name: Clone::clone(&self.name),
+            value: secret::resolve_secret_references(&self.value, secrets)?,
+        })
+    }
     
     pub fn name(&self) -> &str {
         self.name.as_str()
@@ -231,6 +241,522 @@ impl fmt::Display for ContainerImage {
     }
 }
 
+/// Determines, when EDGAR pulls the image of a container executor from its registry.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
+#[serde(rename_all = "kebab-case")]
+pub enum ContainerImagePullPolicy {
+    /// Pulls the image each time, before the container is started.
+    Always,
+    /// Pulls the image only, if it is not yet available on the peer.
+    #[default]
+    IfNotPresent,
+    /// Never pulls the image. Starting the container fails, if the image is not available on the peer.
+    Never,
+}
+
+impl fmt::Display for ContainerImagePullPolicy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContainerImagePullPolicy::Always => write!(f, "always"),
+            ContainerImagePullPolicy::IfNotPresent => write!(f, "if-not-present"),
+            ContainerImagePullPolicy::Never => write!(f, "never"),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Unknown image pull policy '{value}'. Expected one of: always, if-not-present, never.")]
+pub struct IllegalContainerImagePullPolicy {
+    pub value: String,
+}
+
+impl FromStr for ContainerImagePullPolicy {
+    type Err = IllegalContainerImagePullPolicy;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "always" => Ok(ContainerImagePullPolicy::Always),
+            "if-not-present" => Ok(ContainerImagePullPolicy::IfNotPresent),
+            "never" => Ok(ContainerImagePullPolicy::Never),
+            _ => Err(IllegalContainerImagePullPolicy { value: value.to_owned() }),
+        }
+    }
+}
+
+/// Content digest of a container image, e.g. `sha256:4f53…`, to which a container executor is pinned.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ContainerImageDigest(String);
+
+impl ContainerImageDigest {
+    pub const ALGORITHM: &'static str = "sha256";
+    pub const HEX_LENGTH: usize = 64;
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalContainerImageDigest {
+    #[error("Container image digest '{value}' does not use the algorithm '{expected}'.")]
+    UnsupportedAlgorithm { value: String, expected: &'static str },
+    #[error("Container image digest '{value}' is invalid. Expected {expected} lowercase hexadecimal characters after the algorithm.")]
+    InvalidHash { value: String, expected: usize },
+}
+
+impl TryFrom<String> for ContainerImageDigest {
+    type Error = IllegalContainerImageDigest;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let Some(hash) = value.strip_prefix(Self::ALGORITHM).and_then(|rest| rest.strip_prefix(':')) else {
+            return Err(IllegalContainerImageDigest::UnsupportedAlgorithm { value, expected: Self::ALGORITHM });
+        };
+        let is_valid_hash = hash.len() == Self::HEX_LENGTH
+            && hash.chars().all(|char| char.is_ascii_digit() || ('a'..='f').contains(&char));
+        if is_valid_hash {
+            Ok(Self(value))
+        } else {
+            Err(IllegalContainerImageDigest::InvalidHash { value, expected: Self::HEX_LENGTH })
+        }
+    }
+}
+
+impl TryFrom<&str> for ContainerImageDigest {
+    type Error = IllegalContainerImageDigest;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ContainerImageDigest::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ContainerImageDigest {
+    type Err = IllegalContainerImageDigest;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ContainerImageDigest::try_from(value)
+    }
+}
+
+impl From<ContainerImageDigest> for String {
+    fn from(value: ContainerImageDigest) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for ContainerImageDigest {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Name of a registry credential, which is managed by CARL and referenced by container executors.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ContainerRegistryCredentialName(String);
+
+impl ContainerRegistryCredentialName {
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalContainerRegistryCredentialName {
+    #[error("Registry credential name must not be empty.")]
+    Empty,
+    #[error("Registry credential name '{value}' contains invalid characters. Only alphanumeric characters, '-' and '_' are allowed.")]
+    InvalidCharacter { value: String },
+}
+
+impl TryFrom<String> for ContainerRegistryCredentialName {
+    type Error = IllegalContainerRegistryCredentialName;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            Err(IllegalContainerRegistryCredentialName::Empty)
+        } else if value.chars().any(|char| !(char.is_ascii_alphanumeric() || char == '-' || char == '_')) {
+            Err(IllegalContainerRegistryCredentialName::InvalidCharacter { value })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for ContainerRegistryCredentialName {
+    type Error = IllegalContainerRegistryCredentialName;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ContainerRegistryCredentialName::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ContainerRegistryCredentialName {
+    type Err = IllegalContainerRegistryCredentialName;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ContainerRegistryCredentialName::try_from(value)
+    }
+}
+
+impl From<ContainerRegistryCredentialName> for String {
+    fn from(value: ContainerRegistryCredentialName) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for ContainerRegistryCredentialName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Credential for pulling images from a private registry. CARL resolves it by its name and passes it to the peer,
+/// which uses the executor referencing it.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct ContainerRegistryCredential {
+    pub name: ContainerRegistryCredentialName,
+    /// Address of the registry, e.g. `registry.example.com:5000`.
+    pub server: String,
+    pub username: String,
+    pub password: String,
+}
+
+impl fmt::Debug for ContainerRegistryCredential {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ContainerRegistryCredential")
+            .field("name", &self.name)
+            .field("server", &self.server)
+            .field("username", &self.username)
+            .field("password", &"<redacted>")
+            .finish()
+    }
+}
+
+/// Number of CPUs a container may use, e.g. `1.5`. Stored in billionths of a CPU, as expected by the container engine.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ContainerCpuLimit(u64);
+
+impl ContainerCpuLimit {
+    pub const NANO_CPUS_PER_CPU: u64 = 1_000_000_000;
+    /// Smallest limit the container engines accept.
+    pub const MIN_NANO_CPUS: u64 = Self::NANO_CPUS_PER_CPU / 100;
+
+    pub fn from_nano_cpus(nano_cpus: u64) -> Result<Self, IllegalContainerCpuLimit> {
+        if nano_cpus < Self::MIN_NANO_CPUS {
+            Err(IllegalContainerCpuLimit::TooSmall { value: Self(nano_cpus).to_string() })
+        } else {
+            Ok(Self(nano_cpus))
+        }
+    }
+
+    pub fn nano_cpus(&self) -> u64 {
+        self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalContainerCpuLimit {
+    #[error("CPU limit '{value}' is not a decimal number of CPUs, e.g. '1.5'.")]
+    InvalidFormat { value: String },
+    #[error("CPU limit '{value}' is too small. At least 0.01 CPUs are required.")]
+    TooSmall { value: String },
+}
+
+impl TryFrom<String> for ContainerCpuLimit {
+    type Error = IllegalContainerCpuLimit;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let (integer, fraction) = value.split_once('.').unwrap_or((&value, ""));
+        let is_valid = !integer.is_empty()
+            && fraction.len() <= 9
+            && integer.chars().chain(fraction.chars()).all(|char| char.is_ascii_digit());
+        if !is_valid {
+            return Err(IllegalContainerCpuLimit::InvalidFormat { value });
+        }
+        let nano_cpus = integer.parse::<u64>().ok()
+            .and_then(|integer| integer.checked_mul(Self::NANO_CPUS_PER_CPU))
+            .and_then(|nano_cpus| nano_cpus.checked_add(format!("{fraction:0<9}").parse::<u64>().unwrap_or_default()));
+        match nano_cpus {
+            None => Err(IllegalContainerCpuLimit::InvalidFormat { value }),
+            Some(nano_cpus) => Self::from_nano_cpus(nano_cpus),
+        }
+    }
+}
+
+impl TryFrom<&str> for ContainerCpuLimit {
+    type Error = IllegalContainerCpuLimit;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ContainerCpuLimit::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ContainerCpuLimit {
+    type Err = IllegalContainerCpuLimit;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ContainerCpuLimit::try_from(value)
+    }
+}
+
+impl From<ContainerCpuLimit> for String {
+    fn from(value: ContainerCpuLimit) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for ContainerCpuLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let integer = self.0 / Self::NANO_CPUS_PER_CPU;
+        let fraction = self.0 % Self::NANO_CPUS_PER_CPU;
+        if fraction == 0 {
+            write!(f, "{integer}")
+        } else {
+            write!(f, "{integer}.{}", format!("{fraction:09}").trim_end_matches('0'))
+        }
+    }
+}
+
+/// Memory a container may use, e.g. `512m` or `2g`. Stored in bytes.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ContainerMemoryLimit(u64);
+
+impl ContainerMemoryLimit {
+    /// Smallest limit the container engines accept.
+    pub const MIN_BYTES: u64 = 6 * 1024 * 1024;
+    const UNITS: [(char, u64); 4] = [('g', 1024 * 1024 * 1024), ('m', 1024 * 1024), ('k', 1024), ('b', 1)];
+
+    pub fn from_bytes(bytes: u64) -> Result<Self, IllegalContainerMemoryLimit> {
+        if bytes < Self::MIN_BYTES {
+            Err(IllegalContainerMemoryLimit::TooSmall { value: Self(bytes).to_string() })
+        } else {
+            Ok(Self(bytes))
+        }
+    }
+
+    pub fn bytes(&self) -> u64 {
+        self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalContainerMemoryLimit {
+    #[error("Memory limit '{value}' is not a number of bytes with an optional unit (b, k, m, g), e.g. '512m'.")]
+    InvalidFormat { value: String },
+    #[error("Memory limit '{value}' is too small. At least 6m are required.")]
+    TooSmall { value: String },
+}
+
+impl TryFrom<String> for ContainerMemoryLimit {
+    type Error = IllegalContainerMemoryLimit;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let lowercase = value.to_lowercase();
+        let (number, factor) = Self::UNITS.iter()
+            .find_map(|(unit, factor)| lowercase.strip_suffix(*unit).map(|number| (number, *factor)))
+            .unwrap_or((&lowercase, 1));
+        let bytes = (!number.is_empty() && number.chars().all(|char| char.is_ascii_digit()))
+            .then(|| number.parse::<u64>().ok())
+            .flatten()
+            .and_then(|number| number.checked_mul(factor));
+        match bytes {
+            None => Err(IllegalContainerMemoryLimit::InvalidFormat { value }),
+            Some(bytes) => Self::from_bytes(bytes),
+        }
+    }
+}
+
+impl TryFrom<&str> for ContainerMemoryLimit {
+    type Error = IllegalContainerMemoryLimit;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ContainerMemoryLimit::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ContainerMemoryLimit {
+    type Err = IllegalContainerMemoryLimit;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ContainerMemoryLimit::try_from(value)
+    }
+}
+
+impl From<ContainerMemoryLimit> for String {
+    fn from(value: ContainerMemoryLimit) -> Self {
+        value.to_string()
+    }
+}
+
+impl fmt::Display for ContainerMemoryLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (unit, factor) = Self::UNITS.iter()
+            .find(|(_, factor)| self.0 % factor == 0)
+            .expect("a factor of 1 should divide every number of bytes");
+        write!(f, "{}{unit}", self.0 / factor)
+    }
+}
+
+/// Maximum number of processes in a container, which protects the peer against fork bombs.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "u64", into = "u64")]
+pub struct ContainerPidsLimit(u64);
+
+impl ContainerPidsLimit {
+    pub fn value(&self) -> u64 {
+        self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalContainerPidsLimit {
+    #[error("PIDs limit must be greater than zero.")]
+    Zero,
+    #[error("PIDs limit '{value}' is not a positive number.")]
+    InvalidFormat { value: String },
+}
+
+impl TryFrom<u64> for ContainerPidsLimit {
+    type Error = IllegalContainerPidsLimit;
+
+    fn try_from(value: u64) -> Result<Self, Self::Error> {
+        if value == 0 {
+            Err(IllegalContainerPidsLimit::Zero)
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl FromStr for ContainerPidsLimit {
+    type Err = IllegalContainerPidsLimit;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let value = value.parse::<u64>()
+            .map_err(|_| IllegalContainerPidsLimit::InvalidFormat { value: value.to_owned() })?;
+        ContainerPidsLimit::try_from(value)
+    }
+}
+
+impl From<ContainerPidsLimit> for u64 {
+    fn from(value: ContainerPidsLimit) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for ContainerPidsLimit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Network, which a container executor is attached to.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash, Serialize, Deserialize, EnumIter)]
+#[serde(rename_all = "kebab-case")]
+pub enum ContainerNetworkMode {
+    /// Shares the network stack of the peer.
+    #[default]
+    Host,
+    /// Attaches the container to the default bridge network of the container engine, isolated from the peer.
+    Bridge,
+    /// Attaches the container to the bridge of the openDuT cluster, so it can reach the devices of the cluster.
+    Cluster,
+}
+
+impl fmt::Display for ContainerNetworkMode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ContainerNetworkMode::Host => write!(f, "host"),
+            ContainerNetworkMode::Bridge => write!(f, "bridge"),
+            ContainerNetworkMode::Cluster => write!(f, "cluster"),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Unknown network mode '{value}'. Expected one of: host, bridge, cluster.")]
+pub struct IllegalContainerNetworkMode {
+    pub value: String,
+}
+
+impl FromStr for ContainerNetworkMode {
+    type Err = IllegalContainerNetworkMode;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value {
+            "host" => Ok(ContainerNetworkMode::Host),
+            "bridge" => Ok(ContainerNetworkMode::Bridge),
+            "cluster" => Ok(ContainerNetworkMode::Cluster),
+            _ => Err(IllegalContainerNetworkMode { value: value.to_owned() }),
+        }
+    }
+}
+
+/// Linux capability, which is added to or dropped from a container, e.g. `NET_ADMIN` or `ALL`.
+/// The prefix `CAP_` is optional and the name is stored in uppercase without it.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(try_from = "String", into = "String")]
+pub struct ContainerCapability(String);
+
+impl ContainerCapability {
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalContainerCapability {
+    #[error("Container capability must not be empty.")]
+    Empty,
+    #[error("Container capability '{value}' contains invalid characters. Only letters, digits and '_' are allowed.")]
+    InvalidCharacter { value: String },
+}
+
+impl TryFrom<String> for ContainerCapability {
+    type Error = IllegalContainerCapability;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let uppercase = value.to_uppercase();
+        let name = uppercase.strip_prefix("CAP_").unwrap_or(&uppercase);
+        if name.is_empty() {
+            Err(IllegalContainerCapability::Empty)
+        } else if name.chars().any(|char| !(char.is_ascii_alphanumeric() || char == '_')) {
+            Err(IllegalContainerCapability::InvalidCharacter { value })
+        } else {
+            Ok(Self(name.to_owned()))
+        }
+    }
+}
+
+impl TryFrom<&str> for ContainerCapability {
+    type Error = IllegalContainerCapability;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ContainerCapability::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ContainerCapability {
+    type Err = IllegalContainerCapability;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ContainerCapability::try_from(value)
+    }
+}
+
+impl From<ContainerCapability> for String {
+    fn from(value: ContainerCapability) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for ContainerCapability {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 pub struct ContainerVolume(String);
 
@@ -238,6 +764,21 @@ impl ContainerVolume {
     pub fn value(&self) -> &str {
         &self.0
     }
+
+    /// Secret, which is mounted into the container as a file, if the source of the volume references it,
+    /// e.g. `${secret:ca-cert}:/etc/ssl/ca.pem:ro`. Returns the name of the secret and the remainder of the volume.
+    pub fn secret_source(&self) -> Option<(SecretName, &str)> {
+        let rest = self.0.strip_prefix(SECRET_REFERENCE_PREFIX)?;
+        let (name, remainder) = rest.split_once(SECRET_REFERENCE_SUFFIX)?;
+        let remainder = remainder.strip_prefix(':')?;
+        let name = SecretName::try_from(name).ok()?;
+        Some((name, remainder))
+    }
+
+    /// Copy of this volume, in which the references to secrets are replaced with the values of the secrets.
+    pub fn resolve_secret_references(&self, secrets: &HashMap<SecretName, Secret>) -> Result<Self, ResolveSecretReferenceError> {
+        Ok(Self(secret::resolve_secret_references(&self.0, secrets)?))
+    }
 }
 
 #[derive(thiserror::Error, Clone, Debug)]
@@ -516,3 +1057,78 @@ impl fmt::Display for ContainerCommandArgument {
 
 #[derive(thiserror::Error, Clone, Debug)]
 pub enum IllegalContainerConfiguration {}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_accept_sha256_image_digests() -> Result<()> {
+        let digest = format!("sha256:{}", "0123456789abcdef".repeat(4));
+
+        assert_that!(ContainerImageDigest::try_from(Clone::clone(&digest))?.value(), eq(digest.as_str()));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_malformed_image_digests() {
+        assert!(matches!(ContainerImageDigest::try_from("md5:0123456789abcdef"), Err(IllegalContainerImageDigest::UnsupportedAlgorithm { .. })));
+        assert!(matches!(ContainerImageDigest::try_from("sha256:0123"), Err(IllegalContainerImageDigest::InvalidHash { .. })));
+        assert!(matches!(ContainerImageDigest::try_from(format!("sha256:{}", "0123456789ABCDEF".repeat(4))), Err(IllegalContainerImageDigest::InvalidHash { .. })));
+    }
+
+    #[test]
+    fn should_parse_cpu_limits() -> Result<()> {
+        assert_that!(ContainerCpuLimit::try_from("1.5")?.nano_cpus(), eq(1_500_000_000));
+        assert_that!(ContainerCpuLimit::try_from("2")?.nano_cpus(), eq(2_000_000_000));
+        assert_that!(ContainerCpuLimit::try_from("0.25")?.to_string(), eq("0.25"));
+        assert!(matches!(ContainerCpuLimit::try_from("0.001"), Err(IllegalContainerCpuLimit::TooSmall { .. })));
+        assert!(matches!(ContainerCpuLimit::try_from("-1"), Err(IllegalContainerCpuLimit::InvalidFormat { .. })));
+        assert!(matches!(ContainerCpuLimit::try_from(".5"), Err(IllegalContainerCpuLimit::InvalidFormat { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_memory_limits() -> Result<()> {
+        assert_that!(ContainerMemoryLimit::try_from("512m")?.bytes(), eq(512 * 1024 * 1024));
+        assert_that!(ContainerMemoryLimit::try_from("2G")?.bytes(), eq(2 * 1024 * 1024 * 1024));
+        assert_that!(ContainerMemoryLimit::try_from("1536m")?.to_string(), eq("1536m"));
+        assert!(matches!(ContainerMemoryLimit::try_from("1m"), Err(IllegalContainerMemoryLimit::TooSmall { .. })));
+        assert!(matches!(ContainerMemoryLimit::try_from("lots"), Err(IllegalContainerMemoryLimit::InvalidFormat { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn should_normalize_container_capabilities() -> Result<()> {
+        assert_that!(ContainerCapability::try_from("cap_net_admin")?.value(), eq("NET_ADMIN"));
+        assert_that!(ContainerCapability::try_from("ALL")?.value(), eq("ALL"));
+        assert!(matches!(ContainerCapability::try_from("CAP_"), Err(IllegalContainerCapability::Empty)));
+        assert!(matches!(ContainerCapability::try_from("NET ADMIN"), Err(IllegalContainerCapability::InvalidCharacter { .. })));
+        Ok(())
+    }
+
+    #[test]
+    fn should_not_reveal_the_password_of_registry_credentials() -> Result<()> {
+        let credential = ContainerRegistryCredential {
+            name: ContainerRegistryCredentialName::try_from("ci-registry")?,
+            server: String::from("registry.example.com"),
+            username: String::from("ci"),
+            password: String::from("hunter2"),
+        };
+
+        assert_that!(format!("{credential:?}"), not(contains_substring("hunter2")));
+        Ok(())
+    }
+
+    #[test]
+    fn should_recognize_volumes_with_a_secret_as_source() -> Result<()> {
+        let volume = ContainerVolume::try_from("${secret:ca-cert}:/etc/ssl/ca.pem:ro")?;
+        assert_that!(volume.secret_source(), eq(Some((SecretName::try_from("ca-cert")?, "/etc/ssl/ca.pem:ro"))));
+
+        assert_that!(ContainerVolume::try_from("/data:/data")?.secret_source(), none());
+        assert_that!(ContainerVolume::try_from("/data/${secret:ca-cert}:/data")?.secret_source(), none());
+        Ok(())
+    }
+}
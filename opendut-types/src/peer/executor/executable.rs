@@ -0,0 +1,364 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::ops::Not;
+use std::path::Path;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+use crate::secret::{self, ResolveSecretReferenceError, Secret, SecretName};
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ExecutableName(String);
+
+impl ExecutableName {
+    pub const MIN_LENGTH: usize = 2;
+    pub const MAX_LENGTH: usize = 60;
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalExecutableName {
+    #[error("Executable name '{value}' has an invalid length. Expected between {min} and {max} characters, got {actual}.")]
+    InvalidLength { value: String, min: usize, max: usize, actual: usize },
+    #[error("Executable name '{value}' contains invalid characters.")]
+    InvalidCharacter { value: String },
+}
+
+impl TryFrom<String> for ExecutableName {
+    type Error = IllegalExecutableName;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let length = value.len();
+        if (Self::MIN_LENGTH..=Self::MAX_LENGTH).contains(&length).not() {
+            Err(IllegalExecutableName::InvalidLength {
+                value,
+                min: Self::MIN_LENGTH,
+                max: Self::MAX_LENGTH,
+                actual: length,
+            })
+        } else if value.chars().any(|c| crate::util::valid_characters_in_name(&c).not()) {
+            Err(IllegalExecutableName::InvalidCharacter { value })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for ExecutableName {
+    type Error = IllegalExecutableName;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ExecutableName::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ExecutableName {
+    type Err = IllegalExecutableName;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ExecutableName::try_from(value)
+    }
+}
+
+impl From<ExecutableName> for String {
+    fn from(value: ExecutableName) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for ExecutableName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Path to the program or name of a program in the `PATH` of EDGAR.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ExecutableProgram(String);
+
+impl ExecutableProgram {
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalExecutableProgram {
+    #[error("Executable program must not be empty.")]
+    Empty,
+}
+
+impl TryFrom<String> for ExecutableProgram {
+    type Error = IllegalExecutableProgram;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            Err(IllegalExecutableProgram::Empty)
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for ExecutableProgram {
+    type Error = IllegalExecutableProgram;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ExecutableProgram::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ExecutableProgram {
+    type Err = IllegalExecutableProgram;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ExecutableProgram::try_from(value)
+    }
+}
+
+impl From<ExecutableProgram> for String {
+    fn from(value: ExecutableProgram) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for ExecutableProgram {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ExecutableArgument(String);
+
+impl ExecutableArgument {
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalExecutableArgument {
+    #[error("Executable argument must not be empty.")]
+    Empty,
+}
+
+impl TryFrom<String> for ExecutableArgument {
+    type Error = IllegalExecutableArgument;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            Err(IllegalExecutableArgument::Empty)
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for ExecutableArgument {
+    type Error = IllegalExecutableArgument;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ExecutableArgument::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ExecutableArgument {
+    type Err = IllegalExecutableArgument;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ExecutableArgument::try_from(value)
+    }
+}
+
+impl From<ExecutableArgument> for String {
+    fn from(value: ExecutableArgument) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for ExecutableArgument {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ExecutableEnvironmentVariable {
+    name: String,
+    value: String,
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalExecutableEnvironmentVariable {
+    #[error("Executable env name must not be empty.")]
+    EmptyName,
+    #[error("Executable env name '{name}' must not contain '=' or null characters.")]
+    InvalidName { name: String },
+}
+
+impl ExecutableEnvironmentVariable {
+    pub fn new(name: impl Into<String>, value: impl Into<String>) -> Result<Self, IllegalExecutableEnvironmentVariable> {
+        let name = name.into();
+        if name.is_empty() {
+            Err(IllegalExecutableEnvironmentVariable::EmptyName)
+        } else if name.contains(['=', '\0']) {
+            Err(IllegalExecutableEnvironmentVariable::InvalidName { name })
+        } else {
+            Ok(Self { name, value: value.into() })
+        }
+    }
+
+    /// Copy of this variable, in whose value the references to secrets are replaced with the values of the secrets.
+    pub fn resolve_secret_references(&self, secrets: &HashMap<SecretName, Secret>) -> Result<Self, ResolveSecretReferenceError> {
+        Ok(Self {
+            name: Clone::clone(&self.name),
+            value: secret::resolve_secret_references(&self.value, secrets)?,
+        })
+    }
+
+    pub fn name(&self) -> &str {
+        self.name.as_str()
+    }
+
+    pub fn value(&self) -> &str {
+        self.value.as_str()
+    }
+}
+
+impl From<ExecutableEnvironmentVariable> for (String, String) {
+    fn from(value: ExecutableEnvironmentVariable) -> Self {
+        (value.name, value.value)
+    }
+}
+
+pub fn deserialize_executable_environment_variable_vec<'de, D>(deserializer: D) -> Result<Vec<ExecutableEnvironmentVariable>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let map: HashMap<String, String> = HashMap::deserialize(deserializer)?;
+    map.into_iter()
+        .map(|(name, value)| ExecutableEnvironmentVariable::new(name, value))
+        .collect::<Result<_, _>>()
+        .map_err(serde::de::Error::custom)
+}
+
+/// Absolute path of the directory, which the executable is started in.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ExecutableWorkingDirectory(String);
+
+impl ExecutableWorkingDirectory {
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalExecutableWorkingDirectory {
+    #[error("Executable working directory '{value}' must be an absolute path.")]
+    NotAbsolute { value: String },
+}
+
+impl TryFrom<String> for ExecutableWorkingDirectory {
+    type Error = IllegalExecutableWorkingDirectory;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if Path::new(&value).is_absolute() {
+            Ok(Self(value))
+        } else {
+            Err(IllegalExecutableWorkingDirectory::NotAbsolute { value })
+        }
+    }
+}
+
+impl TryFrom<&str> for ExecutableWorkingDirectory {
+    type Error = IllegalExecutableWorkingDirectory;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ExecutableWorkingDirectory::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ExecutableWorkingDirectory {
+    type Err = IllegalExecutableWorkingDirectory;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ExecutableWorkingDirectory::try_from(value)
+    }
+}
+
+impl From<ExecutableWorkingDirectory> for String {
+    fn from(value: ExecutableWorkingDirectory) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for ExecutableWorkingDirectory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Name of the system user on the peer, which the executable is run as.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ExecutableUser(String);
+
+impl ExecutableUser {
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalExecutableUser {
+    #[error("Executable user must not be empty.")]
+    Empty,
+    #[error("Executable user '{value}' contains invalid characters.")]
+    InvalidCharacter { value: String },
+}
+
+impl TryFrom<String> for ExecutableUser {
+    type Error = IllegalExecutableUser;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            Err(IllegalExecutableUser::Empty)
+        } else if value.chars().any(|c| (c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.').not()) {
+            Err(IllegalExecutableUser::InvalidCharacter { value })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for ExecutableUser {
+    type Error = IllegalExecutableUser;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ExecutableUser::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ExecutableUser {
+    type Err = IllegalExecutableUser;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ExecutableUser::try_from(value)
+    }
+}
+
+impl From<ExecutableUser> for String {
+    fn from(value: ExecutableUser) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for ExecutableUser {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
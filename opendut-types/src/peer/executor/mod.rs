@@ -4,9 +4,14 @@ use std::str::FromStr;
 use serde::{Deserialize, Serialize};
 use url::Url;
 
-use crate::peer::executor::container::{Engine, ContainerName, ContainerImage, ContainerVolume, ContainerDevice, ContainerEnvironmentVariable, ContainerPortSpec, ContainerCommand, ContainerCommandArgument, deserialize_container_environment_variable_vec};
+use crate::can::CanDatabaseId;
+use crate::peer::executor::container::{Engine, ContainerName, ContainerImage, ContainerImagePullPolicy, ContainerImageDigest, ContainerRegistryCredentialName, ContainerVolume, ContainerDevice, ContainerEnvironmentVariable, ContainerPortSpec, ContainerCommand, ContainerCommandArgument, ContainerCpuLimit, ContainerMemoryLimit, ContainerPidsLimit, ContainerNetworkMode, ContainerCapability, deserialize_container_environment_variable_vec};
+use crate::peer::executor::executable::{ExecutableName, ExecutableProgram, ExecutableArgument, ExecutableEnvironmentVariable, ExecutableWorkingDirectory, ExecutableUser, deserialize_executable_environment_variable_vec};
+use crate::secret::{self, IllegalSecretReference, SecretName};
 
 pub mod container;
+pub mod executable;
+pub mod run;
 
 #[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
 pub struct ExecutorDescriptors {
@@ -23,14 +28,37 @@ pub struct ExecutorDescriptor {
 
 #[derive(Clone, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "kebab-case", deny_unknown_fields)]
+#[allow(clippy::large_enum_variant)] // executors are only held in configurations, so boxing the container variant would not save memory
 pub enum ExecutorKind {
-    Executable,
+    /// A program, which EDGAR runs directly on the peer as a supervised child process.
+    #[serde(rename_all = "kebab-case")]
+    Executable {
+        name: ExecutableName,
+        program: ExecutableProgram,
+        #[serde(default)]
+        args: Vec<ExecutableArgument>,
+        #[serde(default, deserialize_with = "deserialize_executable_environment_variable_vec")]
+        envs: Vec<ExecutableEnvironmentVariable>,
+        #[serde(default)]
+        working_directory: Option<ExecutableWorkingDirectory>,
+        /// System user to run the program as. Defaults to the user EDGAR runs as.
+        #[serde(default)]
+        user: Option<ExecutableUser>,
+    },
     #[serde(rename_all = "kebab-case")]
     Container {
         engine: Engine,
         #[serde(default)]
         name: ContainerName,
         image: ContainerImage,
+        #[serde(default)]
+        pull_policy: ContainerImagePullPolicy,
+        /// Digest, which the image has to match. Starting the container fails otherwise.
+        #[serde(default)]
+        digest: Option<ContainerImageDigest>,
+        /// Name of the credential managed by CARL, which is used to pull the image from a private registry.
+        #[serde(default)]
+        registry_credential: Option<ContainerRegistryCredentialName>,
         volumes: Vec<ContainerVolume>,
         #[serde(default)]
         devices: Vec<ContainerDevice>,
@@ -42,9 +70,85 @@ pub enum ExecutorKind {
         command: ContainerCommand,
         #[serde(default)]
         args: Vec<ContainerCommandArgument>,
+        /// CAN databases, which are provided to the container as read-only files, e.g. for a restbus simulation.
+        #[serde(default)]
+        can_databases: Vec<CanDatabaseId>,
+        /// Number of CPUs the container may use. Unlimited, if not set.
+        #[serde(default)]
+        cpus: Option<ContainerCpuLimit>,
+        /// Memory the container may use. Unlimited, if not set.
+        #[serde(default)]
+        memory: Option<ContainerMemoryLimit>,
+        /// Maximum number of processes in the container. Unlimited, if not set.
+        #[serde(default)]
+        pids_limit: Option<ContainerPidsLimit>,
+        #[serde(default)]
+        network_mode: ContainerNetworkMode,
+        #[serde(default)]
+        cap_add: Vec<ContainerCapability>,
+        #[serde(default)]
+        cap_drop: Vec<ContainerCapability>,
+        /// Mounts the root filesystem of the container read-only. The results directory stays writable.
+        #[serde(default)]
+        read_only_rootfs: bool,
     }
 }
 
+impl ExecutorDescriptor {
+    /// Name, by which the executor is addressed on its peer, i.e. the name of the executable or container.
+    /// Containers without a name are addressed by their image.
+    pub fn name(&self) -> String {
+        match &self.kind {
+            ExecutorKind::Executable { name, .. } => name.to_string(),
+            ExecutorKind::Container { name: ContainerName::Value(name), .. } => Clone::clone(name),
+            ExecutorKind::Container { name: ContainerName::Empty, image, .. } => image.to_string(),
+        }
+    }
+
+    /// Names of the secrets, which are referenced in the environment variables or volumes of the executor.
+    pub fn secret_references(&self) -> Result<Vec<SecretName>, IllegalSecretReference> {
+        let values = match &self.kind {
+            ExecutorKind::Executable { envs, .. } => envs.iter()
+                .map(ExecutableEnvironmentVariable::value)
+                .collect::<Vec<_>>(),
+            ExecutorKind::Container { envs, volumes, .. } => envs.iter()
+                .map(ContainerEnvironmentVariable::value)
+                .chain(volumes.iter().map(ContainerVolume::value))
+                .collect::<Vec<_>>(),
+        };
+
+        let mut names = Vec::new();
+        for value in values {
+            names.extend(secret::secret_references(value)?);
+        }
+        Ok(names)
+    }
+}
+
+/// Command to control an executor on a peer at runtime, without changing the peer configuration.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExecutorCommand {
+    /// Starts the executor, unless it is already running.
+    Start,
+    /// Terminates the executor, if it is running.
+    Stop,
+    /// Terminates the executor, if it is running, and starts it again.
+    Restart,
+    /// Runs the executor once as a job, which keeps running when the peer configuration is applied again.
+    RunJob,
+}
+
+impl fmt::Display for ExecutorCommand {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecutorCommand::Start => write!(f, "start"),
+            ExecutorCommand::Stop => write!(f, "stop"),
+            ExecutorCommand::Restart => write!(f, "restart"),
+            ExecutorCommand::RunJob => write!(f, "run-job"),
+        }
+    }
+}
 
 
 #[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
@@ -100,3 +204,193 @@ impl fmt::Display for ResultsUrl {
         write!(f, "{}", self.0)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_deserialize_executable_executor_descriptor() -> Result<()> {
+        let json = r#"{
+            "executable": {
+                "name": "bench-tool",
+                "program": "/opt/bench/run.py",
+                "args": ["--verbose"],
+                "envs": { "BENCH": "1" },
+                "working-directory": "/opt/bench"
+            },
+            "results-url": "http://nginx-webdav/"
+        }"#;
+
+        let descriptor: ExecutorDescriptor = serde_json::from_str(json)?;
+
+        assert_that!(descriptor, eq(ExecutorDescriptor {
+            kind: ExecutorKind::Executable {
+                name: ExecutableName::try_from("bench-tool")?,
+                program: ExecutableProgram::try_from("/opt/bench/run.py")?,
+                args: vec![ExecutableArgument::try_from("--verbose")?],
+                envs: vec![ExecutableEnvironmentVariable::new("BENCH", "1")?],
+                working_directory: Some(ExecutableWorkingDirectory::try_from("/opt/bench")?),
+                user: None,
+            },
+            results_url: Some(ResultsUrl::try_from("http://nginx-webdav/")?),
+        }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_deserialize_container_executor_descriptor_with_pinned_image() -> Result<()> {
+        let digest = format!("sha256:{}", "0123456789abcdef".repeat(4));
+        let json = format!(r#"{{
+            "container": {{
+                "engine": "podman",
+                "image": "registry.example.com/testenv-nmap:1.0",
+                "pull-policy": "always",
+                "digest": "{digest}",
+                "registry-credential": "ci-registry",
+                "volumes": []
+            }},
+            "results-url": null
+        }}"#);
+
+        let descriptor: ExecutorDescriptor = serde_json::from_str(&json)?;
+
+        assert_that!(descriptor, eq(ExecutorDescriptor {
+            kind: ExecutorKind::Container {
+                engine: Engine::Podman,
+                name: ContainerName::Empty,
+                image: ContainerImage::try_from("registry.example.com/testenv-nmap:1.0")?,
+                pull_policy: ContainerImagePullPolicy::Always,
+                digest: Some(ContainerImageDigest::try_from(digest)?),
+                registry_credential: Some(ContainerRegistryCredentialName::try_from("ci-registry")?),
+                volumes: vec![],
+                devices: vec![],
+                envs: vec![],
+                ports: vec![],
+                command: ContainerCommand::Default,
+                args: vec![],
+                can_databases: vec![],
+                cpus: None,
+                memory: None,
+                pids_limit: None,
+                network_mode: Default::default(),
+                cap_add: vec![],
+                cap_drop: vec![],
+                read_only_rootfs: false,
+            },
+            results_url: None,
+        }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_deserialize_container_executor_descriptor_with_resource_limits() -> Result<()> {
+        let json = r#"{
+            "container": {
+                "engine": "docker",
+                "image": "testenv-nmap",
+                "volumes": [],
+                "cpus": "1.5",
+                "memory": "512m",
+                "pids-limit": 256,
+                "network-mode": "cluster",
+                "cap-add": ["NET_ADMIN", "cap_net_raw"],
+                "cap-drop": ["ALL"],
+                "read-only-rootfs": true
+            },
+            "results-url": null
+        }"#;
+
+        let descriptor: ExecutorDescriptor = serde_json::from_str(json)?;
+
+        let ExecutorKind::Container { cpus, memory, pids_limit, network_mode, cap_add, cap_drop, read_only_rootfs, .. } = descriptor.kind else {
+            panic!("Expected a container executor.");
+        };
+        assert_that!(cpus, eq(Some(ContainerCpuLimit::try_from("1.5")?)));
+        assert_that!(memory, eq(Some(ContainerMemoryLimit::try_from("512m")?)));
+        assert_that!(pids_limit, eq(Some(ContainerPidsLimit::try_from(256)?)));
+        assert_that!(network_mode, eq(ContainerNetworkMode::Cluster));
+        assert_that!(cap_add, eq(vec![ContainerCapability::try_from("NET_ADMIN")?, ContainerCapability::try_from("NET_RAW")?]));
+        assert_that!(cap_drop, eq(vec![ContainerCapability::try_from("ALL")?]));
+        assert_that!(read_only_rootfs, eq(true));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_invalid_resource_limits() {
+        let json = r#"{
+            "container": {
+                "engine": "docker",
+                "image": "testenv-nmap",
+                "volumes": [],
+                "memory": "1k"
+            },
+            "results-url": null
+        }"#;
+
+        assert!(serde_json::from_str::<ExecutorDescriptor>(json).is_err());
+    }
+
+    #[test]
+    fn should_address_unnamed_containers_by_their_image() -> Result<()> {
+        let container = |name: ContainerName| ExecutorDescriptor {
+            kind: ExecutorKind::Container {
+                engine: Engine::Docker,
+                name,
+                image: ContainerImage::try_from("testenv-nmap").unwrap(),
+                pull_policy: ContainerImagePullPolicy::IfNotPresent,
+                digest: None,
+                registry_credential: None,
+                volumes: vec![],
+                devices: vec![],
+                envs: vec![],
+                ports: vec![],
+                command: ContainerCommand::Default,
+                args: vec![],
+                can_databases: vec![],
+                cpus: None,
+                memory: None,
+                pids_limit: None,
+                network_mode: Default::default(),
+                cap_add: vec![],
+                cap_drop: vec![],
+                read_only_rootfs: false,
+            },
+            results_url: None,
+        };
+
+        assert_that!(container(ContainerName::Value(String::from("nmap-scan"))).name(), eq("nmap-scan"));
+        assert_that!(container(ContainerName::Empty).name(), eq("testenv-nmap"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_collect_the_secrets_referenced_in_envs_and_volumes() -> Result<()> {
+        let json = r#"{
+            "container": {
+                "engine": "podman",
+                "image": "testenv-db-client",
+                "volumes": ["${secret:ca-cert}:/etc/ssl/ca.pem:ro", "/data:/data"],
+                "envs": { "DB_URL": "postgres://app:${secret:db-password}@db/app", "LOG_LEVEL": "debug" }
+            },
+            "results-url": null
+        }"#;
+        let descriptor = serde_json::from_str::<ExecutorDescriptor>(json)?;
+
+        let mut names = descriptor.secret_references()?;
+        names.sort();
+
+        assert_that!(names, elements_are![
+            eq(&SecretName::try_from("ca-cert")?),
+            eq(&SecretName::try_from("db-password")?),
+        ]);
+
+        Ok(())
+    }
+}
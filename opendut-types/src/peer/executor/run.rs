@@ -0,0 +1,186 @@
+use std::fmt;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use url::Url;
+use uuid::Uuid;
+
+use crate::cluster::ClusterId;
+use crate::peer::configuration::ParameterId;
+use crate::peer::executor::container::ContainerImageDigest;
+use crate::peer::PeerId;
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ExecutorRunId(pub Uuid);
+
+impl ExecutorRunId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for ExecutorRunId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal ExecutorRunId: {value}")]
+pub struct IllegalExecutorRunId {
+    pub value: String,
+}
+
+impl TryFrom<&str> for ExecutorRunId {
+    type Error = IllegalExecutorRunId;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(value)
+            .map(Self)
+            .map_err(|_| IllegalExecutorRunId { value: String::from(value) })
+    }
+}
+
+impl fmt::Display for ExecutorRunId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A single execution of an executor on a peer, as reported by EDGAR.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ExecutorRun {
+    pub id: ExecutorRunId,
+    pub peer_id: PeerId,
+    /// Identifier of the executor within the peer configuration, see [`crate::peer::configuration::ParameterValue`].
+    pub executor_id: ParameterId,
+    /// Human-readable name of the executor, e.g. the container name or image.
+    pub executor_name: String,
+    pub state: ExecutorRunState,
+    pub exit_code: Option<i32>,
+    pub created_at: SystemTime,
+    pub started_at: Option<SystemTime>,
+    pub finished_at: Option<SystemTime>,
+    /// URL of the uploaded results, if results were uploaded.
+    pub results_location: Option<Url>,
+    /// Digest of the image, from which the container was created. Only set for container executors.
+    pub image_digest: Option<ContainerImageDigest>,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExecutorRunState {
+    /// The run has been created, but the executor has not been started yet.
+    Pending,
+    Running,
+    /// The executor exited with exit code 0.
+    Succeeded,
+    /// The executor could not be started, failed while running or exited with a non-zero exit code.
+    Failed,
+    /// The executor was stopped, e.g. because the cluster was undeployed.
+    Cancelled,
+}
+
+impl ExecutorRunState {
+    pub fn is_finished(&self) -> bool {
+        match self {
+            ExecutorRunState::Pending | ExecutorRunState::Running => false,
+            ExecutorRunState::Succeeded | ExecutorRunState::Failed | ExecutorRunState::Cancelled => true,
+        }
+    }
+}
+
+impl fmt::Display for ExecutorRunState {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecutorRunState::Pending => write!(f, "Pending"),
+            ExecutorRunState::Running => write!(f, "Running"),
+            ExecutorRunState::Succeeded => write!(f, "Succeeded"),
+            ExecutorRunState::Failed => write!(f, "Failed"),
+            ExecutorRunState::Cancelled => write!(f, "Cancelled"),
+        }
+    }
+}
+
+/// A line of output of an executor run.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ExecutorLogLine {
+    pub run_id: ExecutorRunId,
+    pub stream: ExecutorLogStream,
+    pub timestamp: SystemTime,
+    pub line: String,
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ExecutorLogStream {
+    Stdout,
+    Stderr,
+}
+
+impl fmt::Display for ExecutorLogStream {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ExecutorLogStream::Stdout => write!(f, "stdout"),
+            ExecutorLogStream::Stderr => write!(f, "stderr"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ResultArtifactId(pub Uuid);
+
+impl ResultArtifactId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for ResultArtifactId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+#[error("Illegal ResultArtifactId: {value}")]
+pub struct IllegalResultArtifactId {
+    pub value: String,
+}
+
+impl TryFrom<&str> for ResultArtifactId {
+    type Error = IllegalResultArtifactId;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        Uuid::parse_str(value)
+            .map(Self)
+            .map_err(|_| IllegalResultArtifactId { value: String::from(value) })
+    }
+}
+
+impl fmt::Display for ResultArtifactId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// A file with the results of an executor run, which EDGAR uploaded to the results URL of the executor.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ResultArtifact {
+    pub id: ResultArtifactId,
+    pub peer_id: PeerId,
+    /// Cluster, which the peer was assigned to when the results were uploaded. Determined by CARL.
+    pub cluster_id: Option<ClusterId>,
+    pub run_id: ExecutorRunId,
+    pub executor_id: ParameterId,
+    pub executor_name: String,
+    pub uploaded_at: SystemTime,
+    /// Size of the uploaded file in bytes.
+    pub size: u64,
+    /// URL, under which the uploaded file is available.
+    pub location: Url,
+    /// Hex-encoded SHA-256 checksum of the uploaded file.
+    pub checksum: String,
+}
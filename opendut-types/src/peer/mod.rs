@@ -9,12 +9,14 @@ use uuid::Uuid;
 
 use crate::peer::executor::ExecutorDescriptors;
 use crate::topology::Topology;
-use crate::util::net::{Certificate, NetworkInterfaceDescriptor, AuthConfig, NetworkInterfaceName};
+use crate::peer::enrollment::PeerEnrollment;
+use crate::util::net::{Certificate, NetworkInterfaceDescriptor, NetworkInterfaceName};
 use crate::vpn::VpnPeerConfiguration;
 
 pub mod state;
 pub mod executor;
 pub mod configuration;
+pub mod enrollment;
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
@@ -244,7 +246,7 @@ pub struct PeerSetup {
     pub id: PeerId,
     pub carl: Url,
     pub ca: Certificate,
-    pub auth_config: AuthConfig,
+    pub enrollment: PeerEnrollment,
     pub vpn: VpnPeerConfiguration,
 }
 
@@ -314,7 +316,7 @@ mod tests {
     use crate::vpn::netbird::SetupKey;
 
     use super::*;
-    use crate::util::net::{ClientId, ClientSecret, OAuthScope};
+    use crate::peer::enrollment::EnrollmentToken;
 
     #[test]
     fn A_PeerSetup_should_be_encodable() -> Result<()> {
@@ -322,12 +324,7 @@ mod tests {
             id: PeerId::try_from("01bf3f8c-cc7c-4114-9520-91bce71dcead").unwrap(),
             carl: Url::parse("https://carl.opendut.local")?,
             ca: Certificate(Pem::new("Test Tag".to_string(), vec![])),
-            auth_config: AuthConfig::Enabled {
-                client_id: ClientId::from("client_id"),
-                client_secret: ClientSecret::from("my-secure!-random-string-with-at-least-x-chars%"),
-                scopes: vec![OAuthScope::from("manage-realm")],
-                issuer_url: Url::parse("https://keycloak/realms/opendut/").unwrap(),
-            },
+            enrollment: PeerEnrollment::Token(EnrollmentToken::from("4f7a0c2e9b1d48e6a3c5f0e2d7b9a1c34e8f6a2b0d9c47e1b5a3f8c6d2e0b7a9")),
             vpn: VpnPeerConfiguration::Netbird {
                 management_url: Url::parse("https://netbird.opendut.local/api")?,
                 setup_key: SetupKey::from(Uuid::parse_str("d79c202f-bbbf-4997-844e-678f27606e1c")?),
@@ -335,7 +332,7 @@ mod tests {
         };
 
         let encoded = setup.encode()?;
-        assert_that!(encoded, eq("F8sBABwHzrk8VikIvG691CJ7W1Stsq0oZqoIe8UvyoH_PRFpnb4ullM3wV7g8UFoTRxt-svCaEwMLLSAA7DMXeWhPNSyiLPbPh7iH2lyQsi01GXIkGU-g5HSILFKIJFpVniZZ0XMabK49sS7TWmybMacHztb1vGBJFsITgY9fIGLFdBn_YygcXHdu6CJx17_kdGTXHZEVbbx5_p3wJh2xZP1U-w47d2wcUQUC-fE_u3xqINxDkbE8MD44QUiPdzFwtIZcYdvd7jxsT3zrsw9ho2cozsAmNJrznkEXI_FHmRUPZj8BvjFuCsrVnFuCovbd3TFQ-Q-yZRQJVKBGx9MkngEYwo4H0rlnXCFzKgC"));
+        assert_that!(encoded, eq("F2UBIBwHzrk8NjEKvG69VCJ7i2OqfH1WYOMnMTjiQ-qw9JntyIcDsf02WUBxFmjSDSy2eIDdZTcrD-VFElmm--y5Dx3FESJZtsHA3BlKjAVmTQFmJNYehRXFhfF1QTlgBIpFwyHfz-LU5uuEA8x2gbcXWuIeePVa3QCwyJPTRW9rX656-zh99MQbinUMnHRSlDK0YeZqQZN0mhg5Fx3WMFGQyaVrpIoTVZokDdRxuvPzuDnd9EPHl0TbXZzwnc3LMu34OIoC6gbO-u-QPjmFZEBEvlDm7DBKUWh9WOotNI3sfg=="));
 
         let decoded = PeerSetup::decode(&encoded)?;
         assert_that!(decoded, eq(setup));
@@ -0,0 +1,149 @@
+use crate::proto::{ConversionError, ConversionErrorBuilder, from_unix_millis, unix_millis};
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.audit.rs"));
+
+impl From<crate::audit::AuditEventId> for AuditEventId {
+    fn from(value: crate::audit::AuditEventId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<AuditEventId> for crate::audit::AuditEventId {
+    type Error = ConversionError;
+
+    fn try_from(value: AuditEventId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<AuditEventId, crate::audit::AuditEventId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::audit::AuditEvent> for AuditEvent {
+    fn from(value: crate::audit::AuditEvent) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            timestamp_millis: unix_millis(value.timestamp),
+            user: value.user,
+            operation: value.operation,
+            resource_id: value.resource_id.map(|resource_id| resource_id.value().into()),
+            before: value.before,
+            after: value.after,
+            outcome: Some(value.outcome.into()),
+        }
+    }
+}
+
+impl TryFrom<AuditEvent> for crate::audit::AuditEvent {
+    type Error = ConversionError;
+
+    fn try_from(value: AuditEvent) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<AuditEvent, crate::audit::AuditEvent>;
+
+        let id = value.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+
+        let outcome = value.outcome
+            .ok_or(ErrorBuilder::field_not_set("outcome"))?
+            .try_into()?;
+
+        Ok(Self {
+            id,
+            timestamp: from_unix_millis(value.timestamp_millis),
+            user: value.user,
+            operation: value.operation,
+            resource_id: value.resource_id.map(|resource_id| uuid::Uuid::from(resource_id).into()),
+            before: value.before,
+            after: value.after,
+            outcome,
+        })
+    }
+}
+
+impl From<crate::audit::AuditOutcome> for AuditOutcome {
+    fn from(value: crate::audit::AuditOutcome) -> Self {
+        let inner = match value {
+            crate::audit::AuditOutcome::Success => audit_outcome::Inner::Success(AuditOutcomeSuccess {}),
+            crate::audit::AuditOutcome::Failure { message } => audit_outcome::Inner::Failure(AuditOutcomeFailure { message }),
+        };
+        Self {
+            inner: Some(inner)
+        }
+    }
+}
+
+impl TryFrom<AuditOutcome> for crate::audit::AuditOutcome {
+    type Error = ConversionError;
+
+    fn try_from(value: AuditOutcome) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<AuditOutcome, crate::audit::AuditOutcome>;
+
+        let outcome = match value.inner.ok_or(ErrorBuilder::field_not_set("inner"))? {
+            audit_outcome::Inner::Success(_) => crate::audit::AuditOutcome::Success,
+            audit_outcome::Inner::Failure(AuditOutcomeFailure { message }) => crate::audit::AuditOutcome::Failure { message },
+        };
+        Ok(outcome)
+    }
+}
+
+impl From<crate::audit::AuditEventFilter> for AuditEventFilter {
+    fn from(value: crate::audit::AuditEventFilter) -> Self {
+        Self {
+            user: value.user,
+            operation: value.operation,
+            resource_id: value.resource_id.map(|resource_id| resource_id.value().into()),
+            since_millis: value.since.map(unix_millis),
+            until_millis: value.until.map(unix_millis),
+            limit: value.limit,
+        }
+    }
+}
+
+impl From<AuditEventFilter> for crate::audit::AuditEventFilter {
+    fn from(value: AuditEventFilter) -> Self {
+        Self {
+            user: value.user,
+            operation: value.operation,
+            resource_id: value.resource_id.map(|resource_id| uuid::Uuid::from(resource_id).into()),
+            since: value.since_millis.map(from_unix_millis),
+            until: value.until_millis.map(from_unix_millis),
+            limit: value.limit,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use googletest::prelude::*;
+
+    use crate::audit::AuditOutcome as NativeAuditOutcome;
+    use crate::resources::Id;
+
+    use super::*;
+
+    #[test]
+    fn should_convert_audit_events_back_and_forth() -> Result<()> {
+        let native = crate::audit::AuditEvent {
+            id: crate::audit::AuditEventId::random(),
+            timestamp: SystemTime::UNIX_EPOCH + Duration::from_millis(1_700_000_000_123),
+            user: Some(String::from("alice")),
+            operation: String::from("store_peer_descriptor"),
+            resource_id: Some(Id::random()),
+            before: None,
+            after: Some(String::from("peer 'Test'")),
+            outcome: NativeAuditOutcome::Failure { message: String::from("Denied") },
+        };
+
+        let proto = AuditEvent::from(Clone::clone(&native));
+        let result = crate::audit::AuditEvent::try_from(proto)?;
+
+        assert_that!(result, eq(native));
+        Ok(())
+    }
+}
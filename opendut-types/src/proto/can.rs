@@ -0,0 +1,185 @@
+use crate::proto::{ConversionError, ConversionErrorBuilder};
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.can.rs"));
+
+impl From<crate::can::CanDatabaseId> for CanDatabaseId {
+    fn from(value: crate::can::CanDatabaseId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<CanDatabaseId> for crate::can::CanDatabaseId {
+    type Error = ConversionError;
+
+    fn try_from(value: CanDatabaseId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<CanDatabaseId, crate::can::CanDatabaseId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::can::CanDatabaseName> for CanDatabaseName {
+    fn from(value: crate::can::CanDatabaseName) -> Self {
+        Self {
+            value: value.0
+        }
+    }
+}
+
+impl TryFrom<CanDatabaseName> for crate::can::CanDatabaseName {
+    type Error = ConversionError;
+
+    fn try_from(value: CanDatabaseName) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<CanDatabaseName, crate::can::CanDatabaseName>;
+
+        crate::can::CanDatabaseName::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::can::CanDatabaseVersion> for CanDatabaseVersion {
+    fn from(value: crate::can::CanDatabaseVersion) -> Self {
+        Self {
+            value: value.0
+        }
+    }
+}
+
+impl TryFrom<CanDatabaseVersion> for crate::can::CanDatabaseVersion {
+    type Error = ConversionError;
+
+    fn try_from(value: CanDatabaseVersion) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<CanDatabaseVersion, crate::can::CanDatabaseVersion>;
+
+        crate::can::CanDatabaseVersion::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::can::CanDatabaseFormat> for CanDatabaseFormat {
+    fn from(value: crate::can::CanDatabaseFormat) -> Self {
+        let inner = match value {
+            crate::can::CanDatabaseFormat::Arxml => can_database_format::Inner::Arxml(CanDatabaseFormatArxml {}),
+            crate::can::CanDatabaseFormat::Dbc => can_database_format::Inner::Dbc(CanDatabaseFormatDbc {}),
+        };
+        Self {
+            inner: Some(inner)
+        }
+    }
+}
+
+impl TryFrom<CanDatabaseFormat> for crate::can::CanDatabaseFormat {
+    type Error = ConversionError;
+
+    fn try_from(value: CanDatabaseFormat) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<CanDatabaseFormat, crate::can::CanDatabaseFormat>;
+
+        let inner = value.inner
+            .ok_or(ErrorBuilder::field_not_set("inner"))?;
+
+        let result = match inner {
+            can_database_format::Inner::Arxml(_) => crate::can::CanDatabaseFormat::Arxml,
+            can_database_format::Inner::Dbc(_) => crate::can::CanDatabaseFormat::Dbc,
+        };
+
+        Ok(result)
+    }
+}
+
+impl From<crate::can::CanDatabaseHash> for CanDatabaseHash {
+    fn from(value: crate::can::CanDatabaseHash) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<CanDatabaseHash> for crate::can::CanDatabaseHash {
+    type Error = ConversionError;
+
+    fn try_from(value: CanDatabaseHash) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<CanDatabaseHash, crate::can::CanDatabaseHash>;
+
+        crate::can::CanDatabaseHash::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::can::CanDatabaseDescriptor> for CanDatabaseDescriptor {
+    fn from(value: crate::can::CanDatabaseDescriptor) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            name: Some(value.name.into()),
+            version: Some(value.version.into()),
+            format: Some(value.format.into()),
+            hash: Some(value.hash.into()),
+        }
+    }
+}
+
+impl TryFrom<CanDatabaseDescriptor> for crate::can::CanDatabaseDescriptor {
+    type Error = ConversionError;
+
+    fn try_from(value: CanDatabaseDescriptor) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<CanDatabaseDescriptor, crate::can::CanDatabaseDescriptor>;
+
+        let id = value.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+        let name = value.name
+            .ok_or(ErrorBuilder::field_not_set("name"))?
+            .try_into()?;
+        let version = value.version
+            .ok_or(ErrorBuilder::field_not_set("version"))?
+            .try_into()?;
+        let format = value.format
+            .ok_or(ErrorBuilder::field_not_set("format"))?
+            .try_into()?;
+        let hash = value.hash
+            .ok_or(ErrorBuilder::field_not_set("hash"))?
+            .try_into()?;
+
+        Ok(Self {
+            id,
+            name,
+            version,
+            format,
+            hash,
+        })
+    }
+}
+
+impl From<crate::can::CanDatabase> for CanDatabase {
+    fn from(value: crate::can::CanDatabase) -> Self {
+        Self {
+            descriptor: Some(value.descriptor.into()),
+            content: value.content,
+        }
+    }
+}
+
+impl TryFrom<CanDatabase> for crate::can::CanDatabase {
+    type Error = ConversionError;
+
+    fn try_from(value: CanDatabase) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<CanDatabase, crate::can::CanDatabase>;
+
+        let descriptor = value.descriptor
+            .ok_or(ErrorBuilder::field_not_set("descriptor"))?
+            .try_into()?;
+
+        let database = Self {
+            descriptor,
+            content: value.content,
+        };
+
+        database.verify()
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))?;
+
+        Ok(database)
+    }
+}
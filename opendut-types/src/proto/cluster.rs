@@ -59,6 +59,7 @@ impl From<crate::cluster::ClusterConfiguration> for ClusterConfiguration {
             devices: configuration.devices.into_iter()
                         .map(DeviceId::from)
                         .collect(),
+            can_capture: configuration.can_capture.map(Into::into),
         }
     }
 }
@@ -88,10 +89,234 @@ impl TryFrom<ClusterConfiguration> for crate::cluster::ClusterConfiguration {
             devices: configuration.devices.into_iter()
                         .map(DeviceId::try_into)
                         .collect::<Result<_, _>>()?,
+            can_capture: configuration.can_capture
+                .map(TryInto::try_into)
+                .transpose()?,
         })
     }
 }
 
+impl From<crate::cluster::CanCaptureConfiguration> for CanCaptureConfiguration {
+    fn from(configuration: crate::cluster::CanCaptureConfiguration) -> Self {
+        Self {
+            source: Some(configuration.source.into()),
+            rotation: Some(configuration.rotation.into()),
+            results_url: configuration.results_url.map(Into::into),
+        }
+    }
+}
+
+impl TryFrom<CanCaptureConfiguration> for crate::cluster::CanCaptureConfiguration {
+    type Error = ConversionError;
+
+    fn try_from(configuration: CanCaptureConfiguration) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<CanCaptureConfiguration, crate::cluster::CanCaptureConfiguration>;
+
+        let source: crate::cluster::CanCaptureSource = configuration.source
+            .ok_or(ErrorBuilder::field_not_set("source"))?
+            .try_into()?;
+
+        let rotation: crate::cluster::CanCaptureRotation = configuration.rotation
+            .ok_or(ErrorBuilder::field_not_set("rotation"))?
+            .into();
+
+        let results_url: Option<crate::peer::executor::ResultsUrl> = configuration.results_url
+            .map(TryInto::try_into)
+            .transpose()?;
+
+        Ok(Self {
+            source,
+            rotation,
+            results_url,
+        })
+    }
+}
+
+impl From<crate::cluster::CanCaptureSource> for CanCaptureSource {
+    fn from(source: crate::cluster::CanCaptureSource) -> Self {
+        let inner = match source {
+            crate::cluster::CanCaptureSource::Bridge => {
+                can_capture_source::Inner::Bridge(CanCaptureSourceBridge {})
+            }
+            crate::cluster::CanCaptureSource::DeviceInterfaces(interfaces) => {
+                can_capture_source::Inner::DeviceInterfaces(CanCaptureSourceDeviceInterfaces {
+                    interfaces: interfaces.into_iter()
+                        .map(Into::into)
+                        .collect(),
+                })
+            }
+        };
+        Self {
+            inner: Some(inner)
+        }
+    }
+}
+
+impl TryFrom<CanCaptureSource> for crate::cluster::CanCaptureSource {
+    type Error = ConversionError;
+
+    fn try_from(source: CanCaptureSource) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<CanCaptureSource, crate::cluster::CanCaptureSource>;
+
+        let inner = source.inner
+            .ok_or(ErrorBuilder::field_not_set("inner"))?;
+
+        match inner {
+            can_capture_source::Inner::Bridge(_) => {
+                Ok(crate::cluster::CanCaptureSource::Bridge)
+            }
+            can_capture_source::Inner::DeviceInterfaces(CanCaptureSourceDeviceInterfaces { interfaces }) => {
+                let interfaces = interfaces.into_iter()
+                    .map(TryInto::try_into)
+                    .collect::<Result<_, _>>()?;
+                Ok(crate::cluster::CanCaptureSource::DeviceInterfaces(interfaces))
+            }
+        }
+    }
+}
+
+impl From<crate::cluster::CanCaptureRotation> for CanCaptureRotation {
+    fn from(rotation: crate::cluster::CanCaptureRotation) -> Self {
+        Self {
+            max_file_size: rotation.max_file_size,
+            max_file_duration_millis: u64::try_from(rotation.max_file_duration.as_millis()).unwrap_or(u64::MAX),
+            max_files: rotation.max_files,
+        }
+    }
+}
+
+impl From<CanCaptureRotation> for crate::cluster::CanCaptureRotation {
+    fn from(rotation: CanCaptureRotation) -> Self {
+        Self {
+            max_file_size: rotation.max_file_size,
+            max_file_duration: std::time::Duration::from_millis(rotation.max_file_duration_millis),
+            max_files: rotation.max_files,
+        }
+    }
+}
+
+impl From<crate::cluster::CanFaultInjectionRuleId> for CanFaultInjectionRuleId {
+    fn from(value: crate::cluster::CanFaultInjectionRuleId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<CanFaultInjectionRuleId> for crate::cluster::CanFaultInjectionRuleId {
+    type Error = ConversionError;
+
+    fn try_from(value: CanFaultInjectionRuleId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<CanFaultInjectionRuleId, crate::cluster::CanFaultInjectionRuleId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::cluster::CanFaultInjectionRule> for CanFaultInjectionRule {
+    fn from(rule: crate::cluster::CanFaultInjectionRule) -> Self {
+        Self {
+            id: Some(rule.id.into()),
+            cluster_id: Some(rule.cluster_id.into()),
+            filter: Some(rule.filter.into()),
+            fault: Some(rule.fault.into()),
+            enabled: rule.enabled,
+        }
+    }
+}
+
+impl TryFrom<CanFaultInjectionRule> for crate::cluster::CanFaultInjectionRule {
+    type Error = ConversionError;
+
+    fn try_from(rule: CanFaultInjectionRule) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<CanFaultInjectionRule, crate::cluster::CanFaultInjectionRule>;
+
+        let id: crate::cluster::CanFaultInjectionRuleId = rule.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+
+        let cluster_id: crate::cluster::ClusterId = rule.cluster_id
+            .ok_or(ErrorBuilder::field_not_set("cluster_id"))?
+            .try_into()?;
+
+        let filter: crate::util::net::CanIdFilter = rule.filter
+            .ok_or(ErrorBuilder::field_not_set("filter"))?
+            .try_into()?;
+
+        let fault: crate::cluster::CanFault = rule.fault
+            .ok_or(ErrorBuilder::field_not_set("fault"))?
+            .try_into()?;
+
+        Ok(Self {
+            id,
+            cluster_id,
+            filter,
+            fault,
+            enabled: rule.enabled,
+        })
+    }
+}
+
+impl From<crate::cluster::CanFault> for CanFault {
+    fn from(fault: crate::cluster::CanFault) -> Self {
+        let inner = match fault {
+            crate::cluster::CanFault::Drop { percentage } => {
+                can_fault::Inner::Drop(CanFaultDrop { percentage: u32::from(percentage) })
+            }
+            crate::cluster::CanFault::FlipBits { byte, mask } => {
+                can_fault::Inner::FlipBits(CanFaultFlipBits { byte: u32::from(byte), mask: u32::from(mask) })
+            }
+            crate::cluster::CanFault::SetDlc { dlc } => {
+                can_fault::Inner::SetDlc(CanFaultSetDlc { dlc: u32::from(dlc) })
+            }
+            crate::cluster::CanFault::Delay { delay } => {
+                can_fault::Inner::Delay(CanFaultDelay { delay_millis: u64::try_from(delay.as_millis()).unwrap_or(u64::MAX) })
+            }
+        };
+        Self {
+            inner: Some(inner)
+        }
+    }
+}
+
+impl TryFrom<CanFault> for crate::cluster::CanFault {
+    type Error = ConversionError;
+
+    fn try_from(fault: CanFault) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<CanFault, crate::cluster::CanFault>;
+
+        fn narrow(value: u32, field: &'static str) -> Result<u8, ConversionError> {
+            u8::try_from(value)
+                .map_err(|_| ErrorBuilder::message(format!("Field '{field}' is out of range: {value}")))
+        }
+
+        let inner = fault.inner
+            .ok_or(ErrorBuilder::field_not_set("inner"))?;
+
+        let fault = match inner {
+            can_fault::Inner::Drop(CanFaultDrop { percentage }) => {
+                crate::cluster::CanFault::Drop { percentage: narrow(percentage, "percentage")? }
+            }
+            can_fault::Inner::FlipBits(CanFaultFlipBits { byte, mask }) => {
+                crate::cluster::CanFault::FlipBits { byte: narrow(byte, "byte")?, mask: narrow(mask, "mask")? }
+            }
+            can_fault::Inner::SetDlc(CanFaultSetDlc { dlc }) => {
+                crate::cluster::CanFault::SetDlc { dlc: narrow(dlc, "dlc")? }
+            }
+            can_fault::Inner::Delay(CanFaultDelay { delay_millis }) => {
+                crate::cluster::CanFault::Delay { delay: std::time::Duration::from_millis(delay_millis) }
+            }
+        };
+
+        fault.verify()
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))?;
+
+        Ok(fault)
+    }
+}
+
 impl From<crate::cluster::ClusterDeployment> for ClusterDeployment {
     fn from(deployment: crate::cluster::ClusterDeployment) -> Self {
         Self {
@@ -190,6 +415,7 @@ impl From<crate::cluster::ClusterAssignment> for ClusterAssignment {
             id: Some(value.id.into()),
             leader: Some(value.leader.into()),
             assignments: value.assignments.into_iter().map(Into::into).collect(),
+            can_capture: value.can_capture.map(Into::into),
         }
     }
 }
@@ -212,10 +438,15 @@ impl TryFrom<ClusterAssignment> for crate::cluster::ClusterAssignment {
             .map(TryInto::try_into)
             .collect::<Result<_, _>>()?;
 
+        let can_capture: Option<crate::cluster::CanCaptureConfiguration> = value.can_capture
+            .map(TryInto::try_into)
+            .transpose()?;
+
         Ok(Self {
             id: cluster_id,
             leader,
             assignments,
+            can_capture,
         })
     }
 }
@@ -318,4 +549,74 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    fn A_CanFaultInjectionRule_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+
+        let faults = vec![
+            crate::cluster::CanFault::Drop { percentage: 25 },
+            crate::cluster::CanFault::FlipBits { byte: 3, mask: 0x81 },
+            crate::cluster::CanFault::SetDlc { dlc: 2 },
+            crate::cluster::CanFault::Delay { delay: std::time::Duration::from_millis(50) },
+        ];
+
+        for fault in faults {
+            let native = crate::cluster::CanFaultInjectionRule {
+                id: crate::cluster::CanFaultInjectionRuleId::random(),
+                cluster_id: crate::cluster::ClusterId::random(),
+                filter: crate::util::net::CanIdFilter::exact(0x123, false).unwrap(),
+                fault,
+                enabled: true,
+            };
+            let proto: CanFaultInjectionRule = Clone::clone(&native).into();
+
+            assert_that!(
+                crate::cluster::CanFaultInjectionRule::try_from(Clone::clone(&proto)),
+                ok(eq(native))
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn A_CanCaptureConfiguration_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+
+        { // Bridge
+            let native = crate::cluster::CanCaptureConfiguration {
+                source: crate::cluster::CanCaptureSource::Bridge,
+                rotation: crate::cluster::CanCaptureRotation::default(),
+                results_url: None,
+            };
+            let proto: CanCaptureConfiguration = Clone::clone(&native).into();
+
+            assert_that!(
+                crate::cluster::CanCaptureConfiguration::try_from(Clone::clone(&proto)),
+                ok(eq(native))
+            );
+        }
+
+        { // DeviceInterfaces
+            let native = crate::cluster::CanCaptureConfiguration {
+                source: crate::cluster::CanCaptureSource::DeviceInterfaces(vec![
+                    crate::util::net::NetworkInterfaceName::try_from("can0").unwrap(),
+                    crate::util::net::NetworkInterfaceName::try_from("vcan1").unwrap(),
+                ]),
+                rotation: crate::cluster::CanCaptureRotation {
+                    max_file_size: 1024,
+                    max_file_duration: std::time::Duration::from_millis(1500),
+                    max_files: 0,
+                },
+                results_url: Some(crate::peer::executor::ResultsUrl::try_from("https://example.com/captures/").unwrap()),
+            };
+            let proto: CanCaptureConfiguration = Clone::clone(&native).into();
+
+            assert_that!(
+                crate::cluster::CanCaptureConfiguration::try_from(Clone::clone(&proto)),
+                ok(eq(native))
+            );
+        }
+
+        Ok(())
+    }
 }
@@ -4,6 +4,10 @@ pub mod topology;
 pub mod util;
 pub mod vpn;
 pub mod cleo;
+pub mod can;
+pub mod audit;
+pub mod secret;
+pub mod service_account;
 
 use std::marker::PhantomData;
 
@@ -40,3 +44,13 @@ impl<From, To> ConversionErrorBuilder<From, To> {
         ConversionError::new::<From, To>(details)
     }
 }
+
+pub(crate) fn unix_millis(time: std::time::SystemTime) -> u64 {
+    time.duration_since(std::time::UNIX_EPOCH)
+        .map(|duration| u64::try_from(duration.as_millis()).unwrap_or(u64::MAX))
+        .unwrap_or_default()
+}
+
+pub(crate) fn from_unix_millis(millis: u64) -> std::time::SystemTime {
+    std::time::UNIX_EPOCH + std::time::Duration::from_millis(millis)
+}
@@ -60,6 +60,9 @@ impl From<crate::peer::configuration::PeerConfiguration2> for PeerConfiguration2
     fn from(value: crate::peer::configuration::PeerConfiguration2) -> Self {
         Self {
             executors: value.executors.into_iter().map(PeerConfigurationParameterExecutor::from).collect(),
+            can_databases: value.can_databases.into_iter().map(PeerConfigurationParameterCanDatabase::from).collect(),
+            registry_credentials: value.registry_credentials.into_iter().map(PeerConfigurationParameterContainerRegistryCredential::from).collect(),
+            secrets: value.secrets.into_iter().map(PeerConfigurationParameterSecret::from).collect(),
         }
     }
 }
@@ -69,6 +72,9 @@ impl TryFrom<PeerConfiguration2> for crate::peer::configuration::PeerConfigurati
     fn try_from(value: PeerConfiguration2) -> Result<Self, Self::Error> {
         Ok(crate::peer::configuration::PeerConfiguration2 {
             executors: value.executors.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            can_databases: value.can_databases.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            registry_credentials: value.registry_credentials.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            secrets: value.secrets.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
         })
     }
 }
@@ -107,6 +113,108 @@ impl TryFrom<PeerConfigurationParameterExecutor> for crate::peer::configuration:
     }
 }
 
+impl From<crate::peer::configuration::Parameter<crate::can::CanDatabase>> for PeerConfigurationParameterCanDatabase {
+    fn from(value: crate::peer::configuration::Parameter<crate::can::CanDatabase>) -> Self {
+
+        let can_database: crate::proto::can::CanDatabase = value.value.clone().into();
+        let parameter = PeerConfigurationParameter::from(value);
+
+        Self {
+            parameter: Some(parameter),
+            value: Some(can_database),
+        }
+    }
+}
+impl TryFrom<PeerConfigurationParameterCanDatabase> for crate::peer::configuration::Parameter<crate::can::CanDatabase> {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerConfigurationParameterCanDatabase) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerConfigurationParameterCanDatabase, crate::peer::configuration::Parameter<crate::can::CanDatabase>>;
+
+        let parameter = value.parameter
+            .ok_or(ErrorBuilder::field_not_set("parameter"))?;
+
+        let can_database: crate::can::CanDatabase = value.value
+            .ok_or(ErrorBuilder::field_not_set("can_database"))?
+            .try_into()?;
+
+        Ok(Self {
+            id: parameter.id.ok_or(ErrorBuilder::field_not_set("id"))?.try_into()?,
+            dependencies: parameter.dependencies.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            target: parameter.target.ok_or(ErrorBuilder::field_not_set("target"))?.into(),
+            value: can_database,
+        })
+    }
+}
+
+impl From<crate::peer::configuration::Parameter<crate::peer::executor::container::ContainerRegistryCredential>> for PeerConfigurationParameterContainerRegistryCredential {
+    fn from(value: crate::peer::configuration::Parameter<crate::peer::executor::container::ContainerRegistryCredential>) -> Self {
+
+        let registry_credential: crate::proto::peer::executor::ContainerRegistryCredential = value.value.clone().into();
+        let parameter = PeerConfigurationParameter::from(value);
+
+        Self {
+            parameter: Some(parameter),
+            value: Some(registry_credential),
+        }
+    }
+}
+impl TryFrom<PeerConfigurationParameterContainerRegistryCredential> for crate::peer::configuration::Parameter<crate::peer::executor::container::ContainerRegistryCredential> {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerConfigurationParameterContainerRegistryCredential) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerConfigurationParameterContainerRegistryCredential, crate::peer::configuration::Parameter<crate::peer::executor::container::ContainerRegistryCredential>>;
+
+        let parameter = value.parameter
+            .ok_or(ErrorBuilder::field_not_set("parameter"))?;
+
+        let registry_credential: crate::peer::executor::container::ContainerRegistryCredential = value.value
+            .ok_or(ErrorBuilder::field_not_set("registry_credential"))?
+            .try_into()?;
+
+        Ok(Self {
+            id: parameter.id.ok_or(ErrorBuilder::field_not_set("id"))?.try_into()?,
+            dependencies: parameter.dependencies.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            target: parameter.target.ok_or(ErrorBuilder::field_not_set("target"))?.into(),
+            value: registry_credential,
+        })
+    }
+}
+
+impl From<crate::peer::configuration::Parameter<crate::secret::Secret>> for PeerConfigurationParameterSecret {
+    fn from(value: crate::peer::configuration::Parameter<crate::secret::Secret>) -> Self {
+
+        let secret: crate::proto::secret::Secret = value.value.clone().into();
+        let parameter = PeerConfigurationParameter::from(value);
+
+        Self {
+            parameter: Some(parameter),
+            value: Some(secret),
+        }
+    }
+}
+impl TryFrom<PeerConfigurationParameterSecret> for crate::peer::configuration::Parameter<crate::secret::Secret> {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerConfigurationParameterSecret) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerConfigurationParameterSecret, crate::peer::configuration::Parameter<crate::secret::Secret>>;
+
+        let parameter = value.parameter
+            .ok_or(ErrorBuilder::field_not_set("parameter"))?;
+
+        let secret: crate::secret::Secret = value.value
+            .ok_or(ErrorBuilder::field_not_set("secret"))?
+            .try_into()?;
+
+        Ok(Self {
+            id: parameter.id.ok_or(ErrorBuilder::field_not_set("id"))?.try_into()?,
+            dependencies: parameter.dependencies.into_iter().map(TryInto::try_into).collect::<Result<_, _>>()?,
+            target: parameter.target.ok_or(ErrorBuilder::field_not_set("target"))?.into(),
+            value: secret,
+        })
+    }
+}
+
 impl<V: crate::peer::configuration::ParameterValue> From<crate::peer::configuration::Parameter<V>> for PeerConfigurationParameter {
     fn from(value: crate::peer::configuration::Parameter<V>) -> Self {
         Self {
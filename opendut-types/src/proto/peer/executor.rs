@@ -1,4 +1,4 @@
-use crate::proto::{ConversionError, ConversionErrorBuilder};
+use crate::proto::{ConversionError, ConversionErrorBuilder, from_unix_millis, unix_millis};
 
 include!(concat!(env!("OUT_DIR"), "/opendut.types.peer.executor.rs"));
 
@@ -30,9 +30,23 @@ impl TryFrom<ExecutorDescriptors> for crate::peer::executor::ExecutorDescriptors
 impl From<crate::peer::executor::ExecutorDescriptor> for ExecutorDescriptor {
     fn from(value: crate::peer::executor::ExecutorDescriptor) -> Self {
         let executor_kind = match value.kind {
-            crate::peer::executor::ExecutorKind::Executable => {
+            crate::peer::executor::ExecutorKind::Executable {
+                name,
+                program,
+                args,
+                envs,
+                working_directory,
+                user,
+            } => {
                 Some(executor_descriptor::Kind::Executable(
-                       Executable {}
+                        Executable {
+                            name: Some(name.into()),
+                            program: Some(program.into()),
+                            args: args.into_iter().map(|arg| arg.into()).collect(),
+                            envs: envs.into_iter().map(|env| env.into()).collect(),
+                            working_directory: working_directory.map(|working_directory| working_directory.into()),
+                            user: user.map(|user| user.into()),
+                        }
                     )
                 )
             }
@@ -40,24 +54,46 @@ impl From<crate::peer::executor::ExecutorDescriptor> for ExecutorDescriptor {
                 engine,
                 name, 
                 image, 
+                pull_policy,
+                digest,
+                registry_credential,
                 volumes, 
                 devices, 
                 envs,
                 ports,
                 command,
                 args,
+                can_databases,
+                cpus,
+                memory,
+                pids_limit,
+                network_mode,
+                cap_add,
+                cap_drop,
+                read_only_rootfs,
             } => {
                 Some(executor_descriptor::Kind::Container(
                         Container {
                             engine: Some(engine.into()),
                             name: Some(name.into()),
                             image: Some(image.into()),
+                            pull_policy: Some(pull_policy.into()),
+                            digest: digest.map(|digest| digest.into()),
+                            registry_credential: registry_credential.map(|registry_credential| registry_credential.into()),
                             volumes: volumes.into_iter().map(|volume| volume.into()).collect(),
                             devices: devices.into_iter().map(|device| device.into()).collect(),
                             envs: envs.into_iter().map(|env| env.into()).collect(),
                             ports: ports.into_iter().map(|port| port.into()).collect(),
                             command: Some(command.into()),
                             args: args.into_iter().map(|arg| arg.into()).collect(),
+                            can_databases: can_databases.into_iter().map(|can_database| can_database.into()).collect(),
+                            cpus: cpus.map(|cpus| cpus.into()),
+                            memory: memory.map(|memory| memory.into()),
+                            pids_limit: pids_limit.map(|pids_limit| pids_limit.into()),
+                            network_mode: Some(network_mode.into()),
+                            cap_add: cap_add.into_iter().map(|capability| capability.into()).collect(),
+                            cap_drop: cap_drop.into_iter().map(|capability| capability.into()).collect(),
+                            read_only_rootfs,
                         }
                     )
                 )
@@ -83,8 +119,44 @@ impl TryFrom<ExecutorDescriptor> for crate::peer::executor::ExecutorDescriptor {
         let results_url = value.results_url.map(TryFrom::try_from).transpose()?;
 
         let result_kind = match kind {
-            executor_descriptor::Kind::Executable(_) => {
-                crate::peer::executor::ExecutorKind::Executable
+            executor_descriptor::Kind::Executable(descriptor) => {
+                let Executable {
+                    name,
+                    program,
+                    args,
+                    envs,
+                    working_directory,
+                    user,
+                } = descriptor;
+                let name = name
+                    .ok_or(ErrorBuilder::field_not_set("name"))?
+                    .try_into()?;
+                let program = program
+                    .ok_or(ErrorBuilder::field_not_set("program"))?
+                    .try_into()?;
+                let args = args
+                    .into_iter()
+                    .map(TryFrom::try_from)
+                    .collect::<Result<_, _>>()?;
+                let envs = envs
+                    .into_iter()
+                    .map(TryFrom::try_from)
+                    .collect::<Result<_, _>>()?;
+                let working_directory = working_directory
+                    .map(TryFrom::try_from)
+                    .transpose()?;
+                let user = user
+                    .map(TryFrom::try_from)
+                    .transpose()?;
+
+                crate::peer::executor::ExecutorKind::Executable {
+                    name,
+                    program,
+                    args,
+                    envs,
+                    working_directory,
+                    user,
+                }
             },
             executor_descriptor::Kind::Container(descriptor) => {
                 let Container {
@@ -97,6 +169,17 @@ impl TryFrom<ExecutorDescriptor> for crate::peer::executor::ExecutorDescriptor {
                     ports,
                     command,
                     args,
+                    can_databases,
+                    pull_policy,
+                    digest,
+                    registry_credential,
+                    cpus,
+                    memory,
+                    pids_limit,
+                    network_mode,
+                    cap_add,
+                    cap_drop,
+                    read_only_rootfs,
                 } = descriptor;
                 let engine = engine
                     .ok_or(ErrorBuilder::field_not_set("engine"))?
@@ -107,6 +190,15 @@ impl TryFrom<ExecutorDescriptor> for crate::peer::executor::ExecutorDescriptor {
                 let image = image
                     .ok_or(ErrorBuilder::field_not_set("image"))?
                     .try_into()?;
+                let pull_policy = pull_policy
+                    .ok_or(ErrorBuilder::field_not_set("pull_policy"))?
+                    .try_into()?;
+                let digest = digest
+                    .map(TryFrom::try_from)
+                    .transpose()?;
+                let registry_credential = registry_credential
+                    .map(TryFrom::try_from)
+                    .transpose()?;
                 let volumes = volumes
                     .into_iter()
                     .map(TryFrom::try_from)
@@ -130,17 +222,52 @@ impl TryFrom<ExecutorDescriptor> for crate::peer::executor::ExecutorDescriptor {
                     .into_iter()
                     .map(TryFrom::try_from)
                     .collect::<Result<_, _>>()?;
+                let can_databases = can_databases
+                    .into_iter()
+                    .map(TryFrom::try_from)
+                    .collect::<Result<_, _>>()?;
+                let cpus = cpus
+                    .map(TryFrom::try_from)
+                    .transpose()?;
+                let memory = memory
+                    .map(TryFrom::try_from)
+                    .transpose()?;
+                let pids_limit = pids_limit
+                    .map(TryFrom::try_from)
+                    .transpose()?;
+                let network_mode = network_mode
+                    .ok_or(ErrorBuilder::field_not_set("network_mode"))?
+                    .try_into()?;
+                let cap_add = cap_add
+                    .into_iter()
+                    .map(TryFrom::try_from)
+                    .collect::<Result<_, _>>()?;
+                let cap_drop = cap_drop
+                    .into_iter()
+                    .map(TryFrom::try_from)
+                    .collect::<Result<_, _>>()?;
                 
                 crate::peer::executor::ExecutorKind::Container {
                     engine,
                     name,
                     image,
+                    pull_policy,
+                    digest,
+                    registry_credential,
                     volumes,
                     devices,
                     envs,
                     ports,
                     command,
                     args,
+                    can_databases,
+                    cpus,
+                    memory,
+                    pids_limit,
+                    network_mode,
+                    cap_add,
+                    cap_drop,
+                    read_only_rootfs,
                 }
             }
         };
@@ -237,6 +364,214 @@ impl TryFrom<ContainerImage> for crate::peer::executor::container::ContainerImag
     }
 }
 
+impl From<crate::peer::executor::container::ContainerImagePullPolicy> for ContainerImagePullPolicy {
+    fn from(value: crate::peer::executor::container::ContainerImagePullPolicy) -> Self {
+        let inner = match value {
+            crate::peer::executor::container::ContainerImagePullPolicy::Always => container_image_pull_policy::Inner::Always(ContainerImagePullPolicyAlways {}),
+            crate::peer::executor::container::ContainerImagePullPolicy::IfNotPresent => container_image_pull_policy::Inner::IfNotPresent(ContainerImagePullPolicyIfNotPresent {}),
+            crate::peer::executor::container::ContainerImagePullPolicy::Never => container_image_pull_policy::Inner::Never(ContainerImagePullPolicyNever {}),
+        };
+        Self {
+            inner: Some(inner)
+        }
+    }
+}
+
+impl TryFrom<ContainerImagePullPolicy> for crate::peer::executor::container::ContainerImagePullPolicy {
+    type Error = ConversionError;
+
+    fn try_from(value: ContainerImagePullPolicy) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ContainerImagePullPolicy, crate::peer::executor::container::ContainerImagePullPolicy>;
+
+        let inner = value.inner
+            .ok_or(ErrorBuilder::field_not_set("inner"))?;
+
+        let result = match inner {
+            container_image_pull_policy::Inner::Always(_) => crate::peer::executor::container::ContainerImagePullPolicy::Always,
+            container_image_pull_policy::Inner::IfNotPresent(_) => crate::peer::executor::container::ContainerImagePullPolicy::IfNotPresent,
+            container_image_pull_policy::Inner::Never(_) => crate::peer::executor::container::ContainerImagePullPolicy::Never,
+        };
+
+        Ok(result)
+    }
+}
+
+impl From<crate::peer::executor::container::ContainerImageDigest> for ContainerImageDigest {
+    fn from(value: crate::peer::executor::container::ContainerImageDigest) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<ContainerImageDigest> for crate::peer::executor::container::ContainerImageDigest {
+    type Error = ConversionError;
+
+    fn try_from(value: ContainerImageDigest) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ContainerImageDigest, crate::peer::executor::container::ContainerImageDigest>;
+
+        crate::peer::executor::container::ContainerImageDigest::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::executor::container::ContainerRegistryCredentialName> for ContainerRegistryCredentialName {
+    fn from(value: crate::peer::executor::container::ContainerRegistryCredentialName) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<ContainerRegistryCredentialName> for crate::peer::executor::container::ContainerRegistryCredentialName {
+    type Error = ConversionError;
+
+    fn try_from(value: ContainerRegistryCredentialName) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ContainerRegistryCredentialName, crate::peer::executor::container::ContainerRegistryCredentialName>;
+
+        crate::peer::executor::container::ContainerRegistryCredentialName::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::executor::container::ContainerRegistryCredential> for ContainerRegistryCredential {
+    fn from(value: crate::peer::executor::container::ContainerRegistryCredential) -> Self {
+        Self {
+            name: Some(value.name.into()),
+            server: value.server,
+            username: value.username,
+            password: value.password,
+        }
+    }
+}
+
+impl TryFrom<ContainerRegistryCredential> for crate::peer::executor::container::ContainerRegistryCredential {
+    type Error = ConversionError;
+
+    fn try_from(value: ContainerRegistryCredential) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ContainerRegistryCredential, crate::peer::executor::container::ContainerRegistryCredential>;
+
+        let name = value.name
+            .ok_or(ErrorBuilder::field_not_set("name"))?
+            .try_into()?;
+
+        Ok(Self {
+            name,
+            server: value.server,
+            username: value.username,
+            password: value.password,
+        })
+    }
+}
+
+impl From<crate::peer::executor::container::ContainerCpuLimit> for ContainerCpuLimit {
+    fn from(value: crate::peer::executor::container::ContainerCpuLimit) -> Self {
+        Self {
+            nano_cpus: value.nano_cpus()
+        }
+    }
+}
+
+impl TryFrom<ContainerCpuLimit> for crate::peer::executor::container::ContainerCpuLimit {
+    type Error = ConversionError;
+
+    fn try_from(value: ContainerCpuLimit) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ContainerCpuLimit, crate::peer::executor::container::ContainerCpuLimit>;
+
+        crate::peer::executor::container::ContainerCpuLimit::from_nano_cpus(value.nano_cpus)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::executor::container::ContainerMemoryLimit> for ContainerMemoryLimit {
+    fn from(value: crate::peer::executor::container::ContainerMemoryLimit) -> Self {
+        Self {
+            bytes: value.bytes()
+        }
+    }
+}
+
+impl TryFrom<ContainerMemoryLimit> for crate::peer::executor::container::ContainerMemoryLimit {
+    type Error = ConversionError;
+
+    fn try_from(value: ContainerMemoryLimit) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ContainerMemoryLimit, crate::peer::executor::container::ContainerMemoryLimit>;
+
+        crate::peer::executor::container::ContainerMemoryLimit::from_bytes(value.bytes)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::executor::container::ContainerPidsLimit> for ContainerPidsLimit {
+    fn from(value: crate::peer::executor::container::ContainerPidsLimit) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<ContainerPidsLimit> for crate::peer::executor::container::ContainerPidsLimit {
+    type Error = ConversionError;
+
+    fn try_from(value: ContainerPidsLimit) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ContainerPidsLimit, crate::peer::executor::container::ContainerPidsLimit>;
+
+        crate::peer::executor::container::ContainerPidsLimit::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::executor::container::ContainerNetworkMode> for ContainerNetworkMode {
+    fn from(value: crate::peer::executor::container::ContainerNetworkMode) -> Self {
+        let inner = match value {
+            crate::peer::executor::container::ContainerNetworkMode::Host => container_network_mode::Inner::Host(ContainerNetworkModeHost {}),
+            crate::peer::executor::container::ContainerNetworkMode::Bridge => container_network_mode::Inner::Bridge(ContainerNetworkModeBridge {}),
+            crate::peer::executor::container::ContainerNetworkMode::Cluster => container_network_mode::Inner::Cluster(ContainerNetworkModeCluster {}),
+        };
+        Self {
+            inner: Some(inner)
+        }
+    }
+}
+
+impl TryFrom<ContainerNetworkMode> for crate::peer::executor::container::ContainerNetworkMode {
+    type Error = ConversionError;
+
+    fn try_from(value: ContainerNetworkMode) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ContainerNetworkMode, crate::peer::executor::container::ContainerNetworkMode>;
+
+        let inner = value.inner
+            .ok_or(ErrorBuilder::field_not_set("inner"))?;
+
+        let result = match inner {
+            container_network_mode::Inner::Host(_) => crate::peer::executor::container::ContainerNetworkMode::Host,
+            container_network_mode::Inner::Bridge(_) => crate::peer::executor::container::ContainerNetworkMode::Bridge,
+            container_network_mode::Inner::Cluster(_) => crate::peer::executor::container::ContainerNetworkMode::Cluster,
+        };
+
+        Ok(result)
+    }
+}
+
+impl From<crate::peer::executor::container::ContainerCapability> for ContainerCapability {
+    fn from(value: crate::peer::executor::container::ContainerCapability) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<ContainerCapability> for crate::peer::executor::container::ContainerCapability {
+    type Error = ConversionError;
+
+    fn try_from(value: ContainerCapability) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ContainerCapability, crate::peer::executor::container::ContainerCapability>;
+
+        crate::peer::executor::container::ContainerCapability::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
 impl From<crate::peer::executor::container::ContainerVolume> for ContainerVolume {
     fn from(value: crate::peer::executor::container::ContainerVolume) -> Self {
         Self {
@@ -353,6 +688,122 @@ impl TryFrom<ContainerCommandArgument> for crate::peer::executor::container::Con
     }
 }
 
+impl From<crate::peer::executor::executable::ExecutableName> for ExecutableName {
+    fn from(value: crate::peer::executor::executable::ExecutableName) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<ExecutableName> for crate::peer::executor::executable::ExecutableName {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutableName) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutableName, crate::peer::executor::executable::ExecutableName>;
+
+        crate::peer::executor::executable::ExecutableName::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::executor::executable::ExecutableProgram> for ExecutableProgram {
+    fn from(value: crate::peer::executor::executable::ExecutableProgram) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<ExecutableProgram> for crate::peer::executor::executable::ExecutableProgram {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutableProgram) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutableProgram, crate::peer::executor::executable::ExecutableProgram>;
+
+        crate::peer::executor::executable::ExecutableProgram::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::executor::executable::ExecutableArgument> for ExecutableArgument {
+    fn from(value: crate::peer::executor::executable::ExecutableArgument) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<ExecutableArgument> for crate::peer::executor::executable::ExecutableArgument {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutableArgument) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutableArgument, crate::peer::executor::executable::ExecutableArgument>;
+
+        crate::peer::executor::executable::ExecutableArgument::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::executor::executable::ExecutableEnvironmentVariable> for ExecutableEnvironmentVariable {
+    fn from(value: crate::peer::executor::executable::ExecutableEnvironmentVariable) -> Self {
+        let (name, value) = value.into();
+        Self {
+            name,
+            value
+        }
+    }
+}
+
+impl TryFrom<ExecutableEnvironmentVariable> for crate::peer::executor::executable::ExecutableEnvironmentVariable {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutableEnvironmentVariable) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutableEnvironmentVariable, crate::peer::executor::executable::ExecutableEnvironmentVariable>;
+
+        crate::peer::executor::executable::ExecutableEnvironmentVariable::new(value.name, value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::executor::executable::ExecutableWorkingDirectory> for ExecutableWorkingDirectory {
+    fn from(value: crate::peer::executor::executable::ExecutableWorkingDirectory) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<ExecutableWorkingDirectory> for crate::peer::executor::executable::ExecutableWorkingDirectory {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutableWorkingDirectory) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutableWorkingDirectory, crate::peer::executor::executable::ExecutableWorkingDirectory>;
+
+        crate::peer::executor::executable::ExecutableWorkingDirectory::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::peer::executor::executable::ExecutableUser> for ExecutableUser {
+    fn from(value: crate::peer::executor::executable::ExecutableUser) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<ExecutableUser> for crate::peer::executor::executable::ExecutableUser {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutableUser) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutableUser, crate::peer::executor::executable::ExecutableUser>;
+
+        crate::peer::executor::executable::ExecutableUser::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
 impl From<crate::peer::executor::ResultsUrl> for ResultsUrl {
     fn from(value: crate::peer::executor::ResultsUrl) -> Self {
         Self {
@@ -370,4 +821,515 @@ impl TryFrom<ResultsUrl> for crate::peer::executor::ResultsUrl{
         crate::peer::executor::ResultsUrl::try_from(value.value)
             .map_err(|cause| ErrorBuilder::message(cause.to_string()))
     }
-}
\ No newline at end of file
+}
+impl From<crate::peer::executor::ExecutorCommand> for ExecutorCommand {
+    fn from(value: crate::peer::executor::ExecutorCommand) -> Self {
+        let inner = match value {
+            crate::peer::executor::ExecutorCommand::Start => executor_command::Inner::Start(ExecutorCommandStart {}),
+            crate::peer::executor::ExecutorCommand::Stop => executor_command::Inner::Stop(ExecutorCommandStop {}),
+            crate::peer::executor::ExecutorCommand::Restart => executor_command::Inner::Restart(ExecutorCommandRestart {}),
+            crate::peer::executor::ExecutorCommand::RunJob => executor_command::Inner::RunJob(ExecutorCommandRunJob {}),
+        };
+        Self {
+            inner: Some(inner)
+        }
+    }
+}
+
+impl TryFrom<ExecutorCommand> for crate::peer::executor::ExecutorCommand {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutorCommand) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutorCommand, crate::peer::executor::ExecutorCommand>;
+
+        let inner = value.inner
+            .ok_or(ErrorBuilder::field_not_set("inner"))?;
+
+        let result = match inner {
+            executor_command::Inner::Start(_) => crate::peer::executor::ExecutorCommand::Start,
+            executor_command::Inner::Stop(_) => crate::peer::executor::ExecutorCommand::Stop,
+            executor_command::Inner::Restart(_) => crate::peer::executor::ExecutorCommand::Restart,
+            executor_command::Inner::RunJob(_) => crate::peer::executor::ExecutorCommand::RunJob,
+        };
+
+        Ok(result)
+    }
+}
+
+impl From<crate::peer::executor::run::ExecutorRunId> for ExecutorRunId {
+    fn from(value: crate::peer::executor::run::ExecutorRunId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<ExecutorRunId> for crate::peer::executor::run::ExecutorRunId {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutorRunId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutorRunId, crate::peer::executor::run::ExecutorRunId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::peer::executor::run::ExecutorRun> for ExecutorRun {
+    fn from(value: crate::peer::executor::run::ExecutorRun) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            peer_id: Some(value.peer_id.into()),
+            executor_id: Some(value.executor_id.into()),
+            executor_name: value.executor_name,
+            state: Some(value.state.into()),
+            exit_code: value.exit_code,
+            created_at_millis: unix_millis(value.created_at),
+            started_at_millis: value.started_at.map(unix_millis),
+            finished_at_millis: value.finished_at.map(unix_millis),
+            results_location: value.results_location.map(String::from),
+            image_digest: value.image_digest.map(String::from),
+        }
+    }
+}
+
+impl TryFrom<ExecutorRun> for crate::peer::executor::run::ExecutorRun {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutorRun) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutorRun, crate::peer::executor::run::ExecutorRun>;
+
+        let id = value.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+        let peer_id = value.peer_id
+            .ok_or(ErrorBuilder::field_not_set("peer_id"))?
+            .try_into()?;
+        let executor_id = value.executor_id
+            .ok_or(ErrorBuilder::field_not_set("executor_id"))?
+            .try_into()?;
+        let state = value.state
+            .ok_or(ErrorBuilder::field_not_set("state"))?
+            .try_into()?;
+        let results_location = value.results_location
+            .map(|results_location| url::Url::parse(&results_location))
+            .transpose()
+            .map_err(|cause| ErrorBuilder::message(format!("Results location is not a valid URL: {cause}")))?;
+        let image_digest = value.image_digest
+            .map(crate::peer::executor::container::ContainerImageDigest::try_from)
+            .transpose()
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))?;
+
+        Ok(Self {
+            id,
+            peer_id,
+            executor_id,
+            executor_name: value.executor_name,
+            state,
+            exit_code: value.exit_code,
+            created_at: from_unix_millis(value.created_at_millis),
+            started_at: value.started_at_millis.map(from_unix_millis),
+            finished_at: value.finished_at_millis.map(from_unix_millis),
+            results_location,
+            image_digest,
+        })
+    }
+}
+
+impl From<crate::peer::executor::run::ResultArtifactId> for ResultArtifactId {
+    fn from(value: crate::peer::executor::run::ResultArtifactId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<ResultArtifactId> for crate::peer::executor::run::ResultArtifactId {
+    type Error = ConversionError;
+
+    fn try_from(value: ResultArtifactId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ResultArtifactId, crate::peer::executor::run::ResultArtifactId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::peer::executor::run::ResultArtifact> for ResultArtifact {
+    fn from(value: crate::peer::executor::run::ResultArtifact) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            peer_id: Some(value.peer_id.into()),
+            cluster_id: value.cluster_id.map(Into::into),
+            run_id: Some(value.run_id.into()),
+            executor_id: Some(value.executor_id.into()),
+            executor_name: value.executor_name,
+            uploaded_at_millis: unix_millis(value.uploaded_at),
+            size: value.size,
+            location: String::from(value.location),
+            checksum: value.checksum,
+        }
+    }
+}
+
+impl TryFrom<ResultArtifact> for crate::peer::executor::run::ResultArtifact {
+    type Error = ConversionError;
+
+    fn try_from(value: ResultArtifact) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ResultArtifact, crate::peer::executor::run::ResultArtifact>;
+
+        let id = value.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+        let peer_id = value.peer_id
+            .ok_or(ErrorBuilder::field_not_set("peer_id"))?
+            .try_into()?;
+        let cluster_id = value.cluster_id
+            .map(TryInto::try_into)
+            .transpose()?;
+        let run_id = value.run_id
+            .ok_or(ErrorBuilder::field_not_set("run_id"))?
+            .try_into()?;
+        let executor_id = value.executor_id
+            .ok_or(ErrorBuilder::field_not_set("executor_id"))?
+            .try_into()?;
+        let location = url::Url::parse(&value.location)
+            .map_err(|cause| ErrorBuilder::message(format!("Location is not a valid URL: {cause}")))?;
+
+        Ok(Self {
+            id,
+            peer_id,
+            cluster_id,
+            run_id,
+            executor_id,
+            executor_name: value.executor_name,
+            uploaded_at: from_unix_millis(value.uploaded_at_millis),
+            size: value.size,
+            location,
+            checksum: value.checksum,
+        })
+    }
+}
+
+impl From<crate::peer::executor::run::ExecutorRunState> for ExecutorRunState {
+    fn from(value: crate::peer::executor::run::ExecutorRunState) -> Self {
+        let inner = match value {
+            crate::peer::executor::run::ExecutorRunState::Pending => executor_run_state::Inner::Pending(ExecutorRunStatePending {}),
+            crate::peer::executor::run::ExecutorRunState::Running => executor_run_state::Inner::Running(ExecutorRunStateRunning {}),
+            crate::peer::executor::run::ExecutorRunState::Succeeded => executor_run_state::Inner::Succeeded(ExecutorRunStateSucceeded {}),
+            crate::peer::executor::run::ExecutorRunState::Failed => executor_run_state::Inner::Failed(ExecutorRunStateFailed {}),
+            crate::peer::executor::run::ExecutorRunState::Cancelled => executor_run_state::Inner::Cancelled(ExecutorRunStateCancelled {}),
+        };
+        Self {
+            inner: Some(inner)
+        }
+    }
+}
+
+impl TryFrom<ExecutorRunState> for crate::peer::executor::run::ExecutorRunState {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutorRunState) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutorRunState, crate::peer::executor::run::ExecutorRunState>;
+
+        let inner = value.inner
+            .ok_or(ErrorBuilder::field_not_set("inner"))?;
+
+        let result = match inner {
+            executor_run_state::Inner::Pending(_) => crate::peer::executor::run::ExecutorRunState::Pending,
+            executor_run_state::Inner::Running(_) => crate::peer::executor::run::ExecutorRunState::Running,
+            executor_run_state::Inner::Succeeded(_) => crate::peer::executor::run::ExecutorRunState::Succeeded,
+            executor_run_state::Inner::Failed(_) => crate::peer::executor::run::ExecutorRunState::Failed,
+            executor_run_state::Inner::Cancelled(_) => crate::peer::executor::run::ExecutorRunState::Cancelled,
+        };
+
+        Ok(result)
+    }
+}
+
+impl From<crate::peer::executor::run::ExecutorLogLine> for ExecutorLogLine {
+    fn from(value: crate::peer::executor::run::ExecutorLogLine) -> Self {
+        Self {
+            run_id: Some(value.run_id.into()),
+            stream: Some(value.stream.into()),
+            timestamp_millis: unix_millis(value.timestamp),
+            line: value.line,
+        }
+    }
+}
+
+impl TryFrom<ExecutorLogLine> for crate::peer::executor::run::ExecutorLogLine {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutorLogLine) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutorLogLine, crate::peer::executor::run::ExecutorLogLine>;
+
+        let run_id = value.run_id
+            .ok_or(ErrorBuilder::field_not_set("run_id"))?
+            .try_into()?;
+        let stream = value.stream
+            .ok_or(ErrorBuilder::field_not_set("stream"))?
+            .try_into()?;
+
+        Ok(Self {
+            run_id,
+            stream,
+            timestamp: from_unix_millis(value.timestamp_millis),
+            line: value.line,
+        })
+    }
+}
+
+impl From<crate::peer::executor::run::ExecutorLogStream> for ExecutorLogStream {
+    fn from(value: crate::peer::executor::run::ExecutorLogStream) -> Self {
+        let inner = match value {
+            crate::peer::executor::run::ExecutorLogStream::Stdout => executor_log_stream::Inner::Stdout(ExecutorLogStreamStdout {}),
+            crate::peer::executor::run::ExecutorLogStream::Stderr => executor_log_stream::Inner::Stderr(ExecutorLogStreamStderr {}),
+        };
+        Self {
+            inner: Some(inner)
+        }
+    }
+}
+
+impl TryFrom<ExecutorLogStream> for crate::peer::executor::run::ExecutorLogStream {
+    type Error = ConversionError;
+
+    fn try_from(value: ExecutorLogStream) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ExecutorLogStream, crate::peer::executor::run::ExecutorLogStream>;
+
+        let inner = value.inner
+            .ok_or(ErrorBuilder::field_not_set("inner"))?;
+
+        let result = match inner {
+            executor_log_stream::Inner::Stdout(_) => crate::peer::executor::run::ExecutorLogStream::Stdout,
+            executor_log_stream::Inner::Stderr(_) => crate::peer::executor::run::ExecutorLogStream::Stderr,
+        };
+
+        Ok(result)
+    }
+}
+
+#[cfg(test)]
+#[allow(non_snake_case)]
+mod tests {
+    use googletest::prelude::*;
+
+    use crate::peer::executor::{container, executable};
+
+    use super::*;
+
+    #[test]
+    fn An_Executable_ExecutorDescriptor_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+
+        let native = crate::peer::executor::ExecutorDescriptor {
+            kind: crate::peer::executor::ExecutorKind::Executable {
+                name: executable::ExecutableName::try_from("bench-tool")?,
+                program: executable::ExecutableProgram::try_from("/opt/bench/run.py")?,
+                args: vec![executable::ExecutableArgument::try_from("--verbose")?],
+                envs: vec![executable::ExecutableEnvironmentVariable::new("BENCH", "1")?],
+                working_directory: Some(executable::ExecutableWorkingDirectory::try_from("/opt/bench")?),
+                user: Some(executable::ExecutableUser::try_from("bench")?),
+            },
+            results_url: Some(crate::peer::executor::ResultsUrl::try_from("http://nginx-webdav/")?),
+        };
+        let proto: ExecutorDescriptor = Clone::clone(&native).into();
+
+        assert_that!(
+            crate::peer::executor::ExecutorDescriptor::try_from(proto),
+            ok(eq(native))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn A_Container_ExecutorDescriptor_with_a_pinned_image_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+
+        let native = crate::peer::executor::ExecutorDescriptor {
+            kind: crate::peer::executor::ExecutorKind::Container {
+                engine: container::Engine::Podman,
+                name: container::ContainerName::try_from("nmap-scan")?,
+                image: container::ContainerImage::try_from("registry.example.com/testenv-nmap:1.0")?,
+                pull_policy: container::ContainerImagePullPolicy::Never,
+                digest: Some(container::ContainerImageDigest::try_from(format!("sha256:{}", "0123456789abcdef".repeat(4)))?),
+                registry_credential: Some(container::ContainerRegistryCredentialName::try_from("ci-registry")?),
+                volumes: vec![],
+                devices: vec![],
+                envs: vec![],
+                ports: vec![],
+                command: container::ContainerCommand::Default,
+                args: vec![],
+                can_databases: vec![],
+                cpus: None,
+                memory: None,
+                pids_limit: None,
+                network_mode: Default::default(),
+                cap_add: vec![],
+                cap_drop: vec![],
+                read_only_rootfs: false,
+            },
+            results_url: None,
+        };
+        let proto: ExecutorDescriptor = Clone::clone(&native).into();
+
+        assert_that!(
+            crate::peer::executor::ExecutorDescriptor::try_from(proto),
+            ok(eq(native))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn A_Container_ExecutorDescriptor_with_resource_limits_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+
+        let native = crate::peer::executor::ExecutorDescriptor {
+            kind: crate::peer::executor::ExecutorKind::Container {
+                engine: container::Engine::Docker,
+                name: container::ContainerName::try_from("restbus")?,
+                image: container::ContainerImage::try_from("testenv-restbus")?,
+                pull_policy: container::ContainerImagePullPolicy::IfNotPresent,
+                digest: None,
+                registry_credential: None,
+                volumes: vec![],
+                devices: vec![],
+                envs: vec![],
+                ports: vec![],
+                command: container::ContainerCommand::Default,
+                args: vec![],
+                can_databases: vec![],
+                cpus: Some(container::ContainerCpuLimit::try_from("0.5")?),
+                memory: Some(container::ContainerMemoryLimit::try_from("256m")?),
+                pids_limit: Some(container::ContainerPidsLimit::try_from(128)?),
+                network_mode: container::ContainerNetworkMode::Cluster,
+                cap_add: vec![container::ContainerCapability::try_from("NET_ADMIN")?],
+                cap_drop: vec![container::ContainerCapability::try_from("ALL")?],
+                read_only_rootfs: true,
+            },
+            results_url: None,
+        };
+        let proto: ExecutorDescriptor = Clone::clone(&native).into();
+
+        assert_that!(
+            crate::peer::executor::ExecutorDescriptor::try_from(proto),
+            ok(eq(native))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn An_ExecutorRun_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+
+        let created_at = std::time::UNIX_EPOCH + std::time::Duration::from_millis(1_700_000_000_000);
+
+        let native = crate::peer::executor::run::ExecutorRun {
+            id: crate::peer::executor::run::ExecutorRunId::random(),
+            peer_id: crate::peer::PeerId::random(),
+            executor_id: crate::peer::configuration::ParameterId(uuid::Uuid::new_v4()),
+            executor_name: String::from("nmap-test"),
+            state: crate::peer::executor::run::ExecutorRunState::Failed,
+            exit_code: Some(2),
+            created_at,
+            started_at: Some(created_at + std::time::Duration::from_millis(1500)),
+            finished_at: Some(created_at + std::time::Duration::from_secs(60)),
+            results_location: Some(url::Url::parse("http://nginx-webdav/2024-01-01_12-00-00_nmap-test.zip")?),
+            image_digest: Some(crate::peer::executor::container::ContainerImageDigest::try_from(format!("sha256:{}", "0123456789abcdef".repeat(4)))?),
+        };
+        let proto: ExecutorRun = Clone::clone(&native).into();
+
+        assert_that!(
+            crate::peer::executor::run::ExecutorRun::try_from(proto),
+            ok(eq(native))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn A_ResultArtifact_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+
+        let native = crate::peer::executor::run::ResultArtifact {
+            id: crate::peer::executor::run::ResultArtifactId::random(),
+            peer_id: crate::peer::PeerId::random(),
+            cluster_id: Some(crate::cluster::ClusterId::random()),
+            run_id: crate::peer::executor::run::ExecutorRunId::random(),
+            executor_id: crate::peer::configuration::ParameterId(uuid::Uuid::new_v4()),
+            executor_name: String::from("nmap-test"),
+            uploaded_at: std::time::UNIX_EPOCH + std::time::Duration::from_millis(1_700_000_060_000),
+            size: 4096,
+            location: url::Url::parse("http://nginx-webdav/2024-01-01_12-00-00_nmap-test.zip")?,
+            checksum: String::from("e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"),
+        };
+        let proto: ResultArtifact = Clone::clone(&native).into();
+
+        assert_that!(
+            crate::peer::executor::run::ResultArtifact::try_from(proto),
+            ok(eq(native))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn An_ExecutorLogLine_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+
+        let native = crate::peer::executor::run::ExecutorLogLine {
+            run_id: crate::peer::executor::run::ExecutorRunId::random(),
+            stream: crate::peer::executor::run::ExecutorLogStream::Stderr,
+            timestamp: std::time::UNIX_EPOCH + std::time::Duration::from_millis(1_700_000_000_123),
+            line: String::from("Starting Nmap 7.94"),
+        };
+        let proto: ExecutorLogLine = Clone::clone(&native).into();
+
+        assert_that!(
+            crate::peer::executor::run::ExecutorLogLine::try_from(proto),
+            ok(eq(native))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn An_ExecutorCommand_should_be_convertable_to_its_proto_and_vice_versa() -> Result<()> {
+
+        for native in [
+            crate::peer::executor::ExecutorCommand::Start,
+            crate::peer::executor::ExecutorCommand::Stop,
+            crate::peer::executor::ExecutorCommand::Restart,
+            crate::peer::executor::ExecutorCommand::RunJob,
+        ] {
+            let proto: ExecutorCommand = native.into();
+
+            assert_that!(
+                crate::peer::executor::ExecutorCommand::try_from(proto),
+                ok(eq(native))
+            );
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn An_Executable_without_program_should_not_be_convertable_from_its_proto() -> Result<()> {
+
+        let proto = ExecutorDescriptor {
+            kind: Some(executor_descriptor::Kind::Executable(Executable {
+                name: Some(ExecutableName { value: String::from("bench-tool") }),
+                program: None,
+                args: vec![],
+                envs: vec![],
+                working_directory: None,
+                user: None,
+            })),
+            results_url: None,
+        };
+
+        assert!(crate::peer::executor::ExecutorDescriptor::try_from(proto).is_err());
+
+        Ok(())
+    }
+}
@@ -174,7 +174,7 @@ impl From<crate::peer::PeerSetup> for PeerSetup {
             carl: Some(value.carl.into()),
             ca: Some(value.ca.into()),
             vpn: Some(value.vpn.into()),
-            auth_config: Some(value.auth_config.into()),
+            enrollment: Some(value.enrollment.into()),
         }
     }
 }
@@ -202,20 +202,54 @@ impl TryFrom<PeerSetup> for crate::peer::PeerSetup {
             .ok_or(ErrorBuilder::field_not_set("vpn"))
             .and_then(VpnPeerConfig::try_into)?;
 
-        let auth_config = value.auth_config
-            .ok_or(ErrorBuilder::field_not_set("auth_config"))?
+        let enrollment = value.enrollment
+            .ok_or(ErrorBuilder::field_not_set("enrollment"))?
             .try_into()?;
 
         Ok(Self {
             id,
             carl,
             ca,
-            auth_config,
+            enrollment,
             vpn,
         })
     }
 }
 
+impl From<crate::peer::enrollment::PeerEnrollment> for PeerEnrollment {
+    fn from(value: crate::peer::enrollment::PeerEnrollment) -> Self {
+        let inner = match value {
+            crate::peer::enrollment::PeerEnrollment::Token(token) => peer_enrollment::Inner::Token(EnrollmentToken {
+                value: token.value().to_owned(),
+            }),
+            crate::peer::enrollment::PeerEnrollment::Disabled => peer_enrollment::Inner::Disabled(PeerEnrollmentDisabled {}),
+        };
+        Self {
+            inner: Some(inner),
+        }
+    }
+}
+
+impl TryFrom<PeerEnrollment> for crate::peer::enrollment::PeerEnrollment {
+    type Error = ConversionError;
+
+    fn try_from(value: PeerEnrollment) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<PeerEnrollment, crate::peer::enrollment::PeerEnrollment>;
+
+        let enrollment = match value.inner
+            .ok_or(ErrorBuilder::field_not_set("inner"))? {
+            peer_enrollment::Inner::Token(token) => {
+                if token.value.is_empty() {
+                    return Err(ErrorBuilder::message("Enrollment token must not be empty"));
+                }
+                crate::peer::enrollment::PeerEnrollment::Token(crate::peer::enrollment::EnrollmentToken::from(token.value))
+            }
+            peer_enrollment::Inner::Disabled(_) => crate::peer::enrollment::PeerEnrollment::Disabled,
+        };
+        Ok(enrollment)
+    }
+}
+
 impl From<crate::peer::state::PeerState> for PeerState {
     fn from(state: crate::peer::state::PeerState) -> Self {
         match state {
@@ -0,0 +1,76 @@
+use crate::proto::{ConversionError, ConversionErrorBuilder};
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.secret.rs"));
+
+impl From<crate::secret::SecretName> for SecretName {
+    fn from(value: crate::secret::SecretName) -> Self {
+        Self {
+            value: value.into()
+        }
+    }
+}
+
+impl TryFrom<SecretName> for crate::secret::SecretName {
+    type Error = ConversionError;
+
+    fn try_from(value: SecretName) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<SecretName, crate::secret::SecretName>;
+
+        crate::secret::SecretName::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::secret::Secret> for Secret {
+    fn from(value: crate::secret::Secret) -> Self {
+        Self {
+            name: Some(value.name.into()),
+            value: value.value,
+        }
+    }
+}
+
+impl TryFrom<Secret> for crate::secret::Secret {
+    type Error = ConversionError;
+
+    fn try_from(value: Secret) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<Secret, crate::secret::Secret>;
+
+        let name = value.name
+            .ok_or(ErrorBuilder::field_not_set("name"))?
+            .try_into()?;
+
+        Ok(Self {
+            name,
+            value: value.value,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_convert_secrets_back_and_forth() -> Result<()> {
+        let native = crate::secret::Secret {
+            name: crate::secret::SecretName::try_from("db-password")?,
+            value: String::from("hunter2"),
+        };
+
+        let proto = Secret::from(Clone::clone(&native));
+        let result = crate::secret::Secret::try_from(proto)?;
+
+        assert_that!(result, eq(native));
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_invalid_secret_names() {
+        let proto = SecretName { value: String::from("db password") };
+
+        assert!(crate::secret::SecretName::try_from(proto).is_err());
+    }
+}
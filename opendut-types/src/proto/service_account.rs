@@ -0,0 +1,221 @@
+use crate::proto::{ConversionError, ConversionErrorBuilder, from_unix_millis, unix_millis};
+
+include!(concat!(env!("OUT_DIR"), "/opendut.types.service_account.rs"));
+
+impl From<crate::service_account::ServiceAccountId> for ServiceAccountId {
+    fn from(value: crate::service_account::ServiceAccountId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<ServiceAccountId> for crate::service_account::ServiceAccountId {
+    type Error = ConversionError;
+
+    fn try_from(value: ServiceAccountId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ServiceAccountId, crate::service_account::ServiceAccountId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::service_account::ServiceAccountName> for ServiceAccountName {
+    fn from(value: crate::service_account::ServiceAccountName) -> Self {
+        Self {
+            value: value.0
+        }
+    }
+}
+
+impl TryFrom<ServiceAccountName> for crate::service_account::ServiceAccountName {
+    type Error = ConversionError;
+
+    fn try_from(value: ServiceAccountName) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ServiceAccountName, crate::service_account::ServiceAccountName>;
+
+        crate::service_account::ServiceAccountName::try_from(value.value)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::service_account::ServiceAccountRole> for ServiceAccountRole {
+    fn from(value: crate::service_account::ServiceAccountRole) -> Self {
+        let inner = match value {
+            crate::service_account::ServiceAccountRole::Viewer => service_account_role::Inner::Viewer(ServiceAccountRoleViewer {}),
+            crate::service_account::ServiceAccountRole::Operator => service_account_role::Inner::Operator(ServiceAccountRoleOperator {}),
+            crate::service_account::ServiceAccountRole::Admin => service_account_role::Inner::Admin(ServiceAccountRoleAdmin {}),
+        };
+        Self {
+            inner: Some(inner)
+        }
+    }
+}
+
+impl TryFrom<ServiceAccountRole> for crate::service_account::ServiceAccountRole {
+    type Error = ConversionError;
+
+    fn try_from(value: ServiceAccountRole) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ServiceAccountRole, crate::service_account::ServiceAccountRole>;
+
+        let inner = value.inner
+            .ok_or(ErrorBuilder::field_not_set("inner"))?;
+
+        let result = match inner {
+            service_account_role::Inner::Viewer(_) => crate::service_account::ServiceAccountRole::Viewer,
+            service_account_role::Inner::Operator(_) => crate::service_account::ServiceAccountRole::Operator,
+            service_account_role::Inner::Admin(_) => crate::service_account::ServiceAccountRole::Admin,
+        };
+
+        Ok(result)
+    }
+}
+
+impl From<crate::service_account::ServiceAccountDescriptor> for ServiceAccountDescriptor {
+    fn from(value: crate::service_account::ServiceAccountDescriptor) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            name: Some(value.name.into()),
+            role: Some(value.role.into()),
+            group: value.group,
+        }
+    }
+}
+
+impl TryFrom<ServiceAccountDescriptor> for crate::service_account::ServiceAccountDescriptor {
+    type Error = ConversionError;
+
+    fn try_from(value: ServiceAccountDescriptor) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ServiceAccountDescriptor, crate::service_account::ServiceAccountDescriptor>;
+
+        let id = value.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+
+        let name = value.name
+            .ok_or(ErrorBuilder::field_not_set("name"))?
+            .try_into()?;
+
+        let role = value.role
+            .ok_or(ErrorBuilder::field_not_set("role"))?
+            .try_into()?;
+
+        Ok(Self {
+            id,
+            name,
+            role,
+            group: value.group,
+        })
+    }
+}
+
+impl From<crate::service_account::ApiTokenId> for ApiTokenId {
+    fn from(value: crate::service_account::ApiTokenId) -> Self {
+        Self {
+            uuid: Some(value.0.into())
+        }
+    }
+}
+
+impl TryFrom<ApiTokenId> for crate::service_account::ApiTokenId {
+    type Error = ConversionError;
+
+    fn try_from(value: ApiTokenId) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ApiTokenId, crate::service_account::ApiTokenId>;
+
+        value.uuid
+            .ok_or(ErrorBuilder::field_not_set("uuid"))
+            .map(|uuid| Self(uuid.into()))
+    }
+}
+
+impl From<crate::service_account::ApiTokenDescriptor> for ApiTokenDescriptor {
+    fn from(value: crate::service_account::ApiTokenDescriptor) -> Self {
+        Self {
+            id: Some(value.id.into()),
+            service_account_id: Some(value.service_account_id.into()),
+            created_at_millis: unix_millis(value.created_at),
+            expires_at_millis: unix_millis(value.expires_at),
+        }
+    }
+}
+
+impl TryFrom<ApiTokenDescriptor> for crate::service_account::ApiTokenDescriptor {
+    type Error = ConversionError;
+
+    fn try_from(value: ApiTokenDescriptor) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<ApiTokenDescriptor, crate::service_account::ApiTokenDescriptor>;
+
+        let id = value.id
+            .ok_or(ErrorBuilder::field_not_set("id"))?
+            .try_into()?;
+
+        let service_account_id = value.service_account_id
+            .ok_or(ErrorBuilder::field_not_set("service_account_id"))?
+            .try_into()?;
+
+        Ok(Self {
+            id,
+            service_account_id,
+            created_at: from_unix_millis(value.created_at_millis),
+            expires_at: from_unix_millis(value.expires_at_millis),
+        })
+    }
+}
+
+impl From<crate::service_account::ApiToken> for ApiToken {
+    fn from(value: crate::service_account::ApiToken) -> Self {
+        Self {
+            value: value.0
+        }
+    }
+}
+
+impl From<ApiToken> for crate::service_account::ApiToken {
+    fn from(value: ApiToken) -> Self {
+        Self(value.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, SystemTime};
+
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_convert_service_account_descriptors_back_and_forth() -> Result<()> {
+        let native = crate::service_account::ServiceAccountDescriptor {
+            id: crate::service_account::ServiceAccountId::random(),
+            name: crate::service_account::ServiceAccountName::try_from("ci-pipeline")?,
+            role: crate::service_account::ServiceAccountRole::Operator,
+            group: Some(String::from("/team-a")),
+        };
+
+        let proto = ServiceAccountDescriptor::from(Clone::clone(&native));
+        let result = crate::service_account::ServiceAccountDescriptor::try_from(proto)?;
+
+        assert_that!(result, eq(native));
+        Ok(())
+    }
+
+    #[test]
+    fn should_convert_api_token_descriptors_back_and_forth() -> Result<()> {
+        let native = crate::service_account::ApiTokenDescriptor {
+            id: crate::service_account::ApiTokenId::random(),
+            service_account_id: crate::service_account::ServiceAccountId::random(),
+            created_at: SystemTime::UNIX_EPOCH + Duration::from_millis(1_700_000_000_000),
+            expires_at: SystemTime::UNIX_EPOCH + Duration::from_millis(1_702_592_000_000),
+        };
+
+        let proto = ApiTokenDescriptor::from(Clone::clone(&native));
+        let result = crate::service_account::ApiTokenDescriptor::try_from(proto)?;
+
+        assert_that!(result, eq(native));
+        Ok(())
+    }
+}
@@ -222,13 +222,17 @@ impl From<crate::util::net::NetworkInterfaceDescriptor> for NetworkInterfaceDesc
                 sample_point, 
                 fd: flexible_data_rate, 
                 data_bitrate, 
-                data_sample_point } => network_interface_descriptor::Configuration::Can({
+                data_sample_point,
+                filter,
+                rate_limit } => network_interface_descriptor::Configuration::Can({
                     CanInterfaceConfiguration { 
                         bitrate,
                         sample_point: sample_point.sample_point_times_1000(),
                         flexible_data_rate,
                         data_bitrate,
-                        data_sample_point: data_sample_point.sample_point_times_1000()
+                        data_sample_point: data_sample_point.sample_point_times_1000(),
+                        filter: Some(filter.into()),
+                        rate_limit: rate_limit.map(Into::into),
                     }
                 }),
         };
@@ -261,6 +265,11 @@ impl TryFrom<NetworkInterfaceDescriptor> for crate::util::net::NetworkInterfaceD
                     data_bitrate: can_config.data_bitrate, 
                     data_sample_point: can_config.data_sample_point.try_into()
                         .map_err(|cause| ErrorBuilder::message(format!("Sample point could not be converted: {}", cause)))?,
+                    filter: can_config.filter
+                        .map(TryInto::try_into)
+                        .transpose()?
+                        .unwrap_or_default(),
+                    rate_limit: can_config.rate_limit.map(Into::into),
                 },
             };
 
@@ -271,6 +280,69 @@ impl TryFrom<NetworkInterfaceDescriptor> for crate::util::net::NetworkInterfaceD
     }
 }
 
+impl From<crate::util::net::CanIdFilter> for CanIdFilter {
+    fn from(value: crate::util::net::CanIdFilter) -> Self {
+        Self {
+            id: value.id(),
+            mask: value.mask(),
+            extended: value.is_extended(),
+        }
+    }
+}
+
+impl TryFrom<CanIdFilter> for crate::util::net::CanIdFilter {
+    type Error = ConversionError;
+
+    fn try_from(value: CanIdFilter) -> Result<Self, Self::Error> {
+        type ErrorBuilder = ConversionErrorBuilder<CanIdFilter, crate::util::net::CanIdFilter>;
+
+        crate::util::net::CanIdFilter::new(value.id, value.mask, value.extended)
+            .map_err(|cause| ErrorBuilder::message(cause.to_string()))
+    }
+}
+
+impl From<crate::util::net::CanFrameFilter> for CanFrameFilter {
+    fn from(value: crate::util::net::CanFrameFilter) -> Self {
+        Self {
+            accept: value.accept.into_iter().map(Into::into).collect(),
+            reject: value.reject.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TryFrom<CanFrameFilter> for crate::util::net::CanFrameFilter {
+    type Error = ConversionError;
+
+    fn try_from(value: CanFrameFilter) -> Result<Self, Self::Error> {
+        Ok(Self {
+            accept: value.accept.into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+            reject: value.reject.into_iter()
+                .map(TryInto::try_into)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+impl From<crate::util::net::CanRateLimit> for CanRateLimit {
+    fn from(value: crate::util::net::CanRateLimit) -> Self {
+        Self {
+            frames_per_second: value.frames_per_second,
+            burst: value.burst,
+        }
+    }
+}
+
+impl From<CanRateLimit> for crate::util::net::CanRateLimit {
+    fn from(value: CanRateLimit) -> Self {
+        Self {
+            frames_per_second: value.frames_per_second,
+            burst: value.burst,
+        }
+    }
+}
+
 
 impl From<crate::util::net::ClientSecret> for ClientSecret {
     fn from(value: crate::util::net::ClientSecret) -> Self {
@@ -0,0 +1,215 @@
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use serde::{Deserialize, Serialize};
+
+/// Start of a reference to a secret in the value of an environment variable or in a volume, e.g. `${secret:db-password}`.
+pub const SECRET_REFERENCE_PREFIX: &str = "${secret:";
+pub const SECRET_REFERENCE_SUFFIX: char = '}';
+
+/// Name of a secret, which is managed by CARL and referenced by executors.
+#[derive(Clone, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+pub struct SecretName(String);
+
+impl SecretName {
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+
+    /// Reference to this secret, which is replaced with its value on the peer.
+    pub fn reference(&self) -> String {
+        format!("{SECRET_REFERENCE_PREFIX}{}{SECRET_REFERENCE_SUFFIX}", self.0)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalSecretName {
+    #[error("Secret name must not be empty.")]
+    Empty,
+    #[error("Secret name '{value}' contains invalid characters. Only alphanumeric characters, '-' and '_' are allowed.")]
+    InvalidCharacter { value: String },
+}
+
+impl TryFrom<String> for SecretName {
+    type Error = IllegalSecretName;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        if value.is_empty() {
+            Err(IllegalSecretName::Empty)
+        } else if value.chars().any(|char| !(char.is_ascii_alphanumeric() || char == '-' || char == '_')) {
+            Err(IllegalSecretName::InvalidCharacter { value })
+        } else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for SecretName {
+    type Error = IllegalSecretName;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        SecretName::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for SecretName {
+    type Err = IllegalSecretName;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        SecretName::try_from(value)
+    }
+}
+
+impl From<SecretName> for String {
+    fn from(value: SecretName) -> Self {
+        value.0
+    }
+}
+
+impl fmt::Display for SecretName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Secret with its value in plain text. CARL only sends it to the peers, whose executors reference it.
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct Secret {
+    pub name: SecretName,
+    pub value: String,
+}
+
+impl fmt::Debug for Secret {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Secret")
+            .field("name", &self.name)
+            .field("value", &"<redacted>")
+            .finish()
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalSecretReference {
+    #[error("Reference to a secret in '{value}' is missing the closing '}}'.")]
+    Unterminated { value: String },
+    #[error("Reference to a secret in '{value}' is invalid: {cause}")]
+    InvalidName { value: String, cause: IllegalSecretName },
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum ResolveSecretReferenceError {
+    #[error("{0}")]
+    IllegalReference(#[from] IllegalSecretReference),
+    #[error("Secret '{name}' is referenced, but not available.")]
+    SecretNotFound { name: SecretName },
+}
+
+enum Segment<'a> {
+    Text(&'a str),
+    Reference(SecretName),
+}
+
+fn segments(value: &str) -> Result<Vec<Segment<'_>>, IllegalSecretReference> {
+    let mut segments = Vec::new();
+    let mut rest = value;
+    while let Some(start) = rest.find(SECRET_REFERENCE_PREFIX) {
+        let (text, reference) = rest.split_at(start);
+        let reference = &reference[SECRET_REFERENCE_PREFIX.len()..];
+        let end = reference.find(SECRET_REFERENCE_SUFFIX)
+            .ok_or_else(|| IllegalSecretReference::Unterminated { value: value.to_owned() })?;
+        let name = SecretName::try_from(&reference[..end])
+            .map_err(|cause| IllegalSecretReference::InvalidName { value: value.to_owned(), cause })?;
+
+        if !text.is_empty() {
+            segments.push(Segment::Text(text));
+        }
+        segments.push(Segment::Reference(name));
+        rest = &reference[end + SECRET_REFERENCE_SUFFIX.len_utf8()..];
+    }
+    if !rest.is_empty() {
+        segments.push(Segment::Text(rest));
+    }
+    Ok(segments)
+}
+
+/// Names of the secrets, which are referenced in the value via `${secret:<name>}`.
+pub fn secret_references(value: &str) -> Result<Vec<SecretName>, IllegalSecretReference> {
+    Ok(segments(value)?.into_iter()
+        .filter_map(|segment| match segment {
+            Segment::Text(_) => None,
+            Segment::Reference(name) => Some(name),
+        })
+        .collect())
+}
+
+/// Replaces the references to secrets in the value with the values of the secrets.
+pub fn resolve_secret_references(value: &str, secrets: &HashMap<SecretName, Secret>) -> Result<String, ResolveSecretReferenceError> {
+    segments(value)?.into_iter()
+        .map(|segment| match segment {
+            Segment::Text(text) => Ok(text),
+            Segment::Reference(name) => secrets.get(&name)
+                .map(|secret| secret.value.as_str())
+                .ok_or(ResolveSecretReferenceError::SecretNotFound { name }),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    fn secret(name: &str, value: &str) -> Secret {
+        Secret {
+            name: SecretName::try_from(name).unwrap(),
+            value: value.to_owned(),
+        }
+    }
+
+    #[test]
+    fn should_find_the_referenced_secrets() -> Result<()> {
+        let names = secret_references("postgres://app:${secret:db-password}@db/${secret:db_name}")?;
+
+        assert_that!(names, elements_are![
+            eq(&SecretName::try_from("db-password")?),
+            eq(&SecretName::try_from("db_name")?),
+        ]);
+        assert!(secret_references("no secrets in here, not even $ or {}")?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_illegal_references() {
+        assert!(matches!(secret_references("${secret:db-password"), Err(IllegalSecretReference::Unterminated { .. })));
+        assert!(matches!(secret_references("${secret:}"), Err(IllegalSecretReference::InvalidName { .. })));
+        assert!(matches!(secret_references("${secret:db password}"), Err(IllegalSecretReference::InvalidName { .. })));
+    }
+
+    #[test]
+    fn should_replace_the_references_with_the_values_of_the_secrets() -> Result<()> {
+        let secrets = HashMap::from([
+            (SecretName::try_from("db-password")?, secret("db-password", "hunter2")),
+            (SecretName::try_from("token")?, secret("token", "abc")),
+        ]);
+
+        assert_that!(resolve_secret_references("${secret:token}", &secrets)?, eq("abc"));
+        assert_that!(resolve_secret_references("app:${secret:db-password}@db?token=${secret:token}", &secrets)?, eq("app:hunter2@db?token=abc"));
+        assert_that!(resolve_secret_references("plain", &secrets)?, eq("plain"));
+
+        let result = resolve_secret_references("${secret:unknown}", &secrets);
+        assert!(matches!(result, Err(ResolveSecretReferenceError::SecretNotFound { name }) if name.value() == "unknown"));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_not_show_the_value_of_a_secret_in_debug_output() {
+        let debug = format!("{:?}", secret("db-password", "hunter2"));
+
+        assert_that!(debug, not(contains_substring("hunter2")));
+        assert_that!(debug, contains_substring("db-password"));
+    }
+}
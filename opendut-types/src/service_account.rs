@@ -0,0 +1,261 @@
+use std::fmt;
+use std::fmt::Write;
+use std::ops::Not;
+use std::str::FromStr;
+use std::time::SystemTime;
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use uuid::Uuid;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ServiceAccountId(pub Uuid);
+
+impl ServiceAccountId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for ServiceAccountId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for ServiceAccountId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+pub struct ServiceAccountName(pub(crate) String);
+
+impl ServiceAccountName {
+
+    pub const MIN_LENGTH: usize = 4;
+    pub const MAX_LENGTH: usize = 64;
+
+    pub fn value(self) -> String {
+        self.0
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalServiceAccountName {
+    #[error("Service account name '{value}' is too short. Expected at least {expected} characters, got {actual}.")]
+    TooShort { value: String, expected: usize, actual: usize },
+    #[error("Service account name '{value}' is too long. Expected at most {expected} characters, got {actual}.")]
+    TooLong { value: String, expected: usize, actual: usize },
+    #[error("Service account name '{value}' contains invalid characters.")]
+    InvalidCharacter { value: String },
+    #[error("Service account name '{value}' contains invalid start or end characters.")]
+    InvalidStartEndCharacter { value: String },
+}
+
+impl From<ServiceAccountName> for String {
+    fn from(value: ServiceAccountName) -> Self {
+        value.0
+    }
+}
+
+impl TryFrom<String> for ServiceAccountName {
+    type Error = IllegalServiceAccountName;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let length = value.len();
+        if length < Self::MIN_LENGTH {
+            Err(IllegalServiceAccountName::TooShort {
+                value,
+                expected: Self::MIN_LENGTH,
+                actual: length,
+            })
+        }
+        else if length > Self::MAX_LENGTH {
+            Err(IllegalServiceAccountName::TooLong {
+                value,
+                expected: Self::MAX_LENGTH,
+                actual: length,
+            })
+        }
+        else if crate::util::invalid_start_and_end_of_a_name(&value) {
+            Err(IllegalServiceAccountName::InvalidStartEndCharacter { value })
+        }
+        else if value.chars().any(|c| crate::util::valid_characters_in_name(&c).not()) {
+            Err(IllegalServiceAccountName::InvalidCharacter { value })
+        }
+        else {
+            Ok(Self(value))
+        }
+    }
+}
+
+impl TryFrom<&str> for ServiceAccountName {
+    type Error = IllegalServiceAccountName;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        ServiceAccountName::try_from(value.to_owned())
+    }
+}
+
+impl FromStr for ServiceAccountName {
+    type Err = IllegalServiceAccountName;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        ServiceAccountName::try_from(value)
+    }
+}
+
+impl fmt::Display for ServiceAccountName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Permissions of a service account, which correspond to the roles of users.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ServiceAccountRole {
+    Viewer,
+    Operator,
+    Admin,
+}
+
+impl fmt::Display for ServiceAccountRole {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ServiceAccountRole::Viewer => write!(f, "viewer"),
+            ServiceAccountRole::Operator => write!(f, "operator"),
+            ServiceAccountRole::Admin => write!(f, "admin"),
+        }
+    }
+}
+
+/// Non-human user, e.g. of a CI pipeline, which authenticates with API tokens instead of OIDC.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ServiceAccountDescriptor {
+    pub id: ServiceAccountId,
+    pub name: ServiceAccountName,
+    pub role: ServiceAccountRole,
+    /// Group, e.g. team, of the account. Resources created by the account belong to it, and the account may access the resources of the group.
+    #[serde(default)]
+    pub group: Option<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ApiTokenId(pub Uuid);
+
+impl ApiTokenId {
+    pub fn random() -> Self {
+        Self(Uuid::new_v4())
+    }
+}
+
+impl From<Uuid> for ApiTokenId {
+    fn from(value: Uuid) -> Self {
+        Self(value)
+    }
+}
+
+impl fmt::Display for ApiTokenId {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// Metadata of an API token. The token itself is only returned once, when it is created.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApiTokenDescriptor {
+    pub id: ApiTokenId,
+    pub service_account_id: ServiceAccountId,
+    pub created_at: SystemTime,
+    pub expires_at: SystemTime,
+}
+
+impl ApiTokenDescriptor {
+    pub fn is_expired(&self, now: SystemTime) -> bool {
+        now >= self.expires_at
+    }
+}
+
+/// Secret, with which a service account authenticates as a bearer token.
+/// Consists of a prefix, the ID of the token, so it can be looked up, and a random part.
+#[derive(Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct ApiToken(pub(crate) String);
+
+impl ApiToken {
+    pub const PREFIX: &'static str = "opendut_";
+
+    pub fn generate(id: ApiTokenId) -> Self {
+        Self(format!("{}{}_{}{}", Self::PREFIX, id.0.simple(), Uuid::new_v4().simple(), Uuid::new_v4().simple()))
+    }
+
+    pub fn value(&self) -> &str {
+        &self.0
+    }
+
+    /// Whether the bearer token is an API token rather than an OIDC access token.
+    pub fn is_api_token(bearer_token: &str) -> bool {
+        bearer_token.starts_with(Self::PREFIX)
+    }
+
+    /// ID of the token, if the token is well-formed.
+    pub fn id(&self) -> Option<ApiTokenId> {
+        let (id, _) = self.0.strip_prefix(Self::PREFIX)?
+            .split_once('_')?;
+        Uuid::try_parse(id).ok().map(ApiTokenId)
+    }
+
+    /// Hex-encoded SHA-256 digest of the token, which CARL stores instead of the token itself.
+    pub fn digest(&self) -> String {
+        Sha256::digest(self.0.as_bytes()).iter()
+            .fold(String::with_capacity(64), |mut hex, byte| {
+                let _ = write!(hex, "{byte:02x}");
+                hex
+            })
+    }
+}
+
+impl fmt::Debug for ApiToken {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("ApiToken([redacted])")
+    }
+}
+
+impl From<String> for ApiToken {
+    fn from(value: String) -> Self {
+        Self(value)
+    }
+}
+
+impl From<&str> for ApiToken {
+    fn from(value: &str) -> Self {
+        Self(value.to_owned())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_contain_the_id_of_a_generated_api_token() -> Result<()> {
+        let id = ApiTokenId::random();
+        let token = ApiToken::generate(id);
+
+        assert!(ApiToken::is_api_token(token.value()));
+        assert_that!(token.id(), some(eq(id)));
+        assert_that!(token.digest(), not(eq(ApiToken::generate(id).digest())));
+        assert_that!(format!("{token:?}"), not(contains_substring(token.value())));
+
+        assert_that!(ApiToken::from("opendut_not-a-uuid_secret").id(), none());
+        assert_that!(ApiToken::from("eyJhbGciOiJSUzI1NiJ9").id(), none());
+        Ok(())
+    }
+}
@@ -0,0 +1,422 @@
+use std::iter::Peekable;
+use std::str::Chars;
+
+use crate::test_case::{ExecuteStep, TestCase, TestCaseParseError, TestSection, TestStep, TestStepDuration, TestStepKind};
+
+/// Parses the line-based text form of a test case:
+/// - `PreConditions:`, `Actions:` and `PostConditions:` start a section,
+/// - `<label>: "<value>" [default "<default>"]` defines a variable,
+/// - `<label>: execute(tool:"<tool>", params:["<param>", ...], duration:short|long)` runs a tool,
+/// - `//` starts a comment, which spans until the end of the line.
+pub(super) fn parse(name: String, source: &str) -> Result<TestCase, TestCaseParseError> {
+    let mut test_case = TestCase {
+        name,
+        pre_conditions: Vec::new(),
+        actions: Vec::new(),
+        post_conditions: Vec::new(),
+    };
+    let mut current_section = None;
+
+    for (index, line) in source.lines().enumerate() {
+        let line_number = index + 1;
+        let syntax_error = |message: String| TestCaseParseError::Syntax { line: line_number, message };
+
+        let line = strip_comment(line).trim();
+        if line.is_empty() {
+            continue;
+        }
+
+        let (label, expression) = line.split_once(':')
+            .ok_or_else(|| syntax_error(String::from("Expected a section header or a step in the form '<label>: <expression>'.")))?;
+        let label = label.trim();
+        let expression = expression.trim();
+
+        if label.is_empty() {
+            return Err(syntax_error(String::from("Missing label before ':'.")));
+        }
+
+        if expression.is_empty() {
+            let section = match label {
+                "PreConditions" => TestSection::PreConditions,
+                "Actions" => TestSection::Actions,
+                "PostConditions" => TestSection::PostConditions,
+                _ => return Err(syntax_error(format!("Unknown section '{label}'. Expected 'PreConditions', 'Actions' or 'PostConditions'."))),
+            };
+            current_section = Some(section);
+            continue;
+        }
+
+        let section = current_section
+            .ok_or_else(|| syntax_error(format!("Step '{label}' is not part of a section.")))?;
+
+        let kind = parse_expression(expression)
+            .map_err(|message| syntax_error(format!("Invalid step '{label}': {message}")))?;
+
+        let step = TestStep { label: label.to_owned(), kind };
+        match section {
+            TestSection::PreConditions => test_case.pre_conditions.push(step),
+            TestSection::Actions => test_case.actions.push(step),
+            TestSection::PostConditions => test_case.post_conditions.push(step),
+        }
+    }
+
+    Ok(test_case)
+}
+
+fn strip_comment(line: &str) -> &str {
+    let mut in_string = false;
+    let mut escaped = false;
+    let mut previous = None;
+
+    for (index, char) in line.char_indices() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if char == '\\' {
+                escaped = true;
+            } else if char == '"' {
+                in_string = false;
+            }
+        } else if char == '"' {
+            in_string = true;
+        } else if char == '/' && previous == Some('/') {
+            return &line[..index - 1];
+        }
+        previous = Some(char);
+    }
+    line
+}
+
+fn parse_expression(expression: &str) -> Result<TestStepKind, String> {
+    let mut tokens = Tokens::new(expression);
+
+    let kind = match tokens.next()? {
+        Some(Token::String(value)) => {
+            let default = match tokens.next()? {
+                None => None,
+                Some(Token::Identifier(keyword)) if keyword == "default" => {
+                    Some(tokens.expect_string()?)
+                }
+                Some(token) => return Err(format!("Expected 'default', but found {token}.")),
+            };
+            TestStepKind::Variable { value, default }
+        }
+        Some(Token::Identifier(keyword)) if keyword == "execute" => {
+            TestStepKind::Execute(parse_execute(&mut tokens)?)
+        }
+        Some(token) => return Err(format!("Expected a string or 'execute(...)', but found {token}.")),
+        None => return Err(String::from("Missing expression.")),
+    };
+
+    match tokens.next()? {
+        None => Ok(kind),
+        Some(token) => Err(format!("Unexpected {token} at the end of the step.")),
+    }
+}
+
+fn parse_execute(tokens: &mut Tokens) -> Result<ExecuteStep, String> {
+    tokens.expect(Token::OpenParenthesis)?;
+
+    let mut tool = None;
+    let mut params = Vec::new();
+    let mut duration = TestStepDuration::default();
+
+    loop {
+        let argument = match tokens.next()? {
+            Some(Token::CloseParenthesis) => break,
+            Some(Token::Identifier(argument)) => argument,
+            Some(token) => return Err(format!("Expected an argument of 'execute', but found {token}.")),
+            None => return Err(String::from("Missing ')' after the arguments of 'execute'.")),
+        };
+        tokens.expect(Token::Colon)?;
+
+        match argument.as_str() {
+            "tool" => tool = Some(tokens.expect_string()?),
+            "params" => params = parse_list(tokens)?,
+            "duration" => {
+                let value = match tokens.next()? {
+                    Some(Token::Identifier(value) | Token::String(value)) => value,
+                    Some(token) => return Err(format!("Expected 'short' or 'long', but found {token}.")),
+                    None => return Err(String::from("Missing value for argument 'duration'.")),
+                };
+                duration = TestStepDuration::try_from(value.as_str())?;
+            }
+            _ => return Err(format!("Unknown argument '{argument}' of 'execute'. Expected 'tool', 'params' or 'duration'.")),
+        }
+
+        match tokens.next()? {
+            Some(Token::Comma) => continue,
+            Some(Token::CloseParenthesis) => break,
+            Some(token) => return Err(format!("Expected ',' or ')', but found {token}.")),
+            None => return Err(String::from("Missing ')' after the arguments of 'execute'.")),
+        }
+    }
+
+    let tool = tool.ok_or_else(|| String::from("Missing argument 'tool' of 'execute'."))?;
+    Ok(ExecuteStep { tool, params, duration })
+}
+
+fn parse_list(tokens: &mut Tokens) -> Result<Vec<String>, String> {
+    tokens.expect(Token::OpenBracket)?;
+
+    let mut values = Vec::new();
+    loop {
+        match tokens.next()? {
+            Some(Token::CloseBracket) if values.is_empty() => break,
+            Some(Token::String(value)) => values.push(value),
+            Some(token) => return Err(format!("Expected a string, but found {token}.")),
+            None => return Err(String::from("Missing ']' at the end of the list.")),
+        }
+        match tokens.next()? {
+            Some(Token::Comma) => continue,
+            Some(Token::CloseBracket) => break,
+            Some(token) => return Err(format!("Expected ',' or ']', but found {token}.")),
+            None => return Err(String::from("Missing ']' at the end of the list.")),
+        }
+    }
+    Ok(values)
+}
+
+#[derive(Debug, PartialEq)]
+enum Token {
+    String(String),
+    Identifier(String),
+    Colon,
+    Comma,
+    OpenParenthesis,
+    CloseParenthesis,
+    OpenBracket,
+    CloseBracket,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::String(value) => write!(f, "string \"{value}\""),
+            Token::Identifier(value) => write!(f, "'{value}'"),
+            Token::Colon => write!(f, "':'"),
+            Token::Comma => write!(f, "','"),
+            Token::OpenParenthesis => write!(f, "'('"),
+            Token::CloseParenthesis => write!(f, "')'"),
+            Token::OpenBracket => write!(f, "'['"),
+            Token::CloseBracket => write!(f, "']'"),
+        }
+    }
+}
+
+struct Tokens<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl<'a> Tokens<'a> {
+    fn new(expression: &'a str) -> Self {
+        Self { chars: expression.chars().peekable() }
+    }
+
+    fn next(&mut self) -> Result<Option<Token>, String> {
+        while self.chars.next_if(|char| char.is_whitespace()).is_some() {}
+
+        let Some(char) = self.chars.next() else {
+            return Ok(None);
+        };
+
+        let token = match char {
+            ':' => Token::Colon,
+            ',' => Token::Comma,
+            '(' => Token::OpenParenthesis,
+            ')' => Token::CloseParenthesis,
+            '[' => Token::OpenBracket,
+            ']' => Token::CloseBracket,
+            '"' => Token::String(self.string()?),
+            char if char.is_alphanumeric() || char == '_' => {
+                let mut identifier = String::from(char);
+                while let Some(char) = self.chars.next_if(|char| char.is_alphanumeric() || *char == '_') {
+                    identifier.push(char);
+                }
+                Token::Identifier(identifier)
+            }
+            char => return Err(format!("Unexpected character '{char}'.")),
+        };
+        Ok(Some(token))
+    }
+
+    fn string(&mut self) -> Result<String, String> {
+        let mut value = String::new();
+        loop {
+            match self.chars.next() {
+                Some('"') => return Ok(value),
+                Some('\\') => match self.chars.next() {
+                    Some('n') => value.push('\n'),
+                    Some('t') => value.push('\t'),
+                    Some(escaped @ ('"' | '\\')) => value.push(escaped),
+                    Some(escaped) => return Err(format!("Unknown escape sequence '\\{escaped}'.")),
+                    None => return Err(String::from("Unterminated string.")),
+                },
+                Some(char) => value.push(char),
+                None => return Err(String::from("Unterminated string.")),
+            }
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), String> {
+        match self.next()? {
+            Some(token) if token == expected => Ok(()),
+            Some(token) => Err(format!("Expected {expected}, but found {token}.")),
+            None => Err(format!("Expected {expected}, but the step ended.")),
+        }
+    }
+
+    fn expect_string(&mut self) -> Result<String, String> {
+        match self.next()? {
+            Some(Token::String(value)) => Ok(value),
+            Some(token) => Err(format!("Expected a string, but found {token}.")),
+            None => Err(String::from("Expected a string, but the step ended.")),
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use crate::test_case::Evaluation;
+
+    use super::*;
+
+    fn execute(tool: &str, params: &[&str], duration: TestStepDuration) -> TestStepKind {
+        TestStepKind::Execute(ExecuteStep {
+            tool: tool.to_owned(),
+            params: params.iter().map(|param| param.to_string()).collect(),
+            duration,
+        })
+    }
+
+    #[test]
+    fn should_parse_variables_and_execute_steps() -> Result<()> {
+        let source = r#"
+PreConditions:
+
+Actions:
+    File_Path: "{File_Path}" default "/home/kali/CyberSec/Tools/CANtools/CC/cc_discovery.sh"
+    canIF: "{canIF}" default "can0"
+
+    CanDiscoverySH: execute(tool:"bash", params:["{File_Path}", "-i", "{canIF}"], duration:long)
+
+PostConditions:"#;
+
+        let test_case = TestCase::parse_csa("CC_Discovery", source)?;
+
+        assert_that!(test_case, eq(TestCase {
+            name: String::from("CC_Discovery"),
+            pre_conditions: vec![],
+            actions: vec![
+                TestStep {
+                    label: String::from("File_Path"),
+                    kind: TestStepKind::Variable {
+                        value: String::from("{File_Path}"),
+                        default: Some(String::from("/home/kali/CyberSec/Tools/CANtools/CC/cc_discovery.sh")),
+                    },
+                },
+                TestStep {
+                    label: String::from("canIF"),
+                    kind: TestStepKind::Variable {
+                        value: String::from("{canIF}"),
+                        default: Some(String::from("can0")),
+                    },
+                },
+                TestStep {
+                    label: String::from("CanDiscoverySH"),
+                    kind: execute("bash", &["{File_Path}", "-i", "{canIF}"], TestStepDuration::Long),
+                },
+            ],
+            post_conditions: vec![],
+        }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_comments_labels_with_spaces_and_evaluations() -> Result<()> {
+        let source = r#"
+PreConditions:
+Cl15: execute(tool:"/programs/cl15.py", params:["set", "=", "on"])
+
+Actions:
+//Action
+ACC set button: execute(tool:"/programs/accsetbutton.py", params:["set", "=", "short pressed"]) // pressed for 500ms
+Vehiclespeed: execute(tool:"/programs/vehiclespeed.py", params:["evaluateabort", "==", "75.0", "kmh"])
+BusSignal: execute(tool:"/programs/bussignal.py", params:["evaluate", "==", "FCAN::ACC_01::ACC_state", "http://not-a-comment"])
+
+PostConditions:
+Reset: execute(params:[], tool:"programs/reset.py", duration:short)
+"#;
+
+        let test_case = TestCase::parse_csa("SKyBT_ExampleTestcase_2", source)?;
+
+        assert_that!(test_case.pre_conditions, eq(vec![
+            TestStep { label: String::from("Cl15"), kind: execute("/programs/cl15.py", &["set", "=", "on"], TestStepDuration::Short) },
+        ]));
+        assert_that!(test_case.actions, eq(vec![
+            TestStep { label: String::from("ACC set button"), kind: execute("/programs/accsetbutton.py", &["set", "=", "short pressed"], TestStepDuration::Short) },
+            TestStep { label: String::from("Vehiclespeed"), kind: execute("/programs/vehiclespeed.py", &["evaluateabort", "==", "75.0", "kmh"], TestStepDuration::Short) },
+            TestStep { label: String::from("BusSignal"), kind: execute("/programs/bussignal.py", &["evaluate", "==", "FCAN::ACC_01::ACC_state", "http://not-a-comment"], TestStepDuration::Short) },
+        ]));
+        assert_that!(test_case.post_conditions, eq(vec![
+            TestStep { label: String::from("Reset"), kind: execute("programs/reset.py", &[], TestStepDuration::Short) },
+        ]));
+
+        let evaluations = test_case.actions.iter()
+            .map(|step| match &step.kind {
+                TestStepKind::Execute(execute) => execute.evaluation(),
+                TestStepKind::Variable { .. } => None,
+            })
+            .collect::<Vec<_>>();
+        assert_that!(evaluations, eq(vec![None, Some(Evaluation::EvaluateAbort), Some(Evaluation::Evaluate)]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_the_skybt_example() -> Result<()> {
+        let source = include_str!("../../../opendut-skybt/concepts/SKyBT_ExampleTestcase_2.csa");
+
+        let test_case = TestCase::parse_csa("SKyBT_ExampleTestcase_2", source)?;
+
+        assert!(test_case.pre_conditions.is_empty());
+        assert!(test_case.post_conditions.is_empty());
+        assert_that!(test_case.actions.len(), eq(12));
+        assert_that!(test_case.actions[0], eq(&TestStep {
+            label: String::from("Cl15"),
+            kind: execute("/programs/cl15.py", &["set", "=", "on"], TestStepDuration::Short),
+        }));
+        assert_that!(test_case.actions[6], eq(&TestStep {
+            label: String::from("ACC set button"),
+            kind: execute("/programs/accsetbutton.py", &["set", "=", "short pressed"], TestStepDuration::Short),
+        }));
+
+        let evaluations = test_case.actions.iter()
+            .filter_map(|step| match &step.kind {
+                TestStepKind::Execute(execute) => execute.evaluation(),
+                TestStepKind::Variable { .. } => None,
+            })
+            .collect::<Vec<_>>();
+        assert_that!(evaluations, eq(vec![Evaluation::EvaluateAbort, Evaluation::Evaluate, Evaluation::Evaluate, Evaluation::Evaluate]));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_report_the_line_of_syntax_errors() -> Result<()> {
+        let error = |source: &str| TestCase::parse_csa("Invalid", source).unwrap_err();
+
+        assert_that!(error("Step: \"value\""), eq(TestCaseParseError::Syntax { line: 1, message: String::from("Step 'Step' is not part of a section.") }));
+        assert_that!(error("Actions:\n\nStep: execute(params:[\"-i\"])"), eq(TestCaseParseError::Syntax { line: 3, message: String::from("Invalid step 'Step': Missing argument 'tool' of 'execute'.") }));
+        assert_that!(error("Actions:\nStep: execute(tool:\"bash\", environment:\"x\")"), eq(TestCaseParseError::Syntax { line: 2, message: String::from("Invalid step 'Step': Unknown argument 'environment' of 'execute'. Expected 'tool', 'params' or 'duration'.") }));
+        assert_that!(error("Actions:\nStep: execute(tool:\"bash\", duration:forever)"), eq(TestCaseParseError::Syntax { line: 2, message: String::from("Invalid step 'Step': Unknown duration 'forever'. Expected 'short' or 'long'.") }));
+        assert_that!(error("Actions:\nStep: \"unterminated"), eq(TestCaseParseError::Syntax { line: 2, message: String::from("Invalid step 'Step': Unterminated string.") }));
+        assert_that!(error("Cleanup:"), eq(TestCaseParseError::Syntax { line: 1, message: String::from("Unknown section 'Cleanup'. Expected 'PreConditions', 'Actions' or 'PostConditions'.") }));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,156 @@
+use serde::Deserialize;
+
+use crate::test_case::{ExecuteStep, TestCase, TestCaseParseError, TestStep, TestStepDuration, TestStepKind};
+
+/// Parses the JSON form of a test case, as generated from the text form by the ALIA tooling.
+/// Fields, which are not needed for execution (e.g. `DSLStep` or `environment`), are ignored.
+pub(super) fn parse(source: &str) -> Result<TestCase, TestCaseParseError> {
+    let test_case = serde_json::from_str::<JsonTestCase>(source)
+        .map_err(|cause| TestCaseParseError::Json { message: cause.to_string() })?;
+
+    Ok(TestCase {
+        name: test_case.name,
+        pre_conditions: convert_steps(test_case.preconditions)?,
+        actions: convert_steps(test_case.actions)?,
+        post_conditions: convert_steps(test_case.postconditions)?,
+    })
+}
+
+#[derive(Deserialize)]
+struct JsonTestCase {
+    name: String,
+    #[serde(default)]
+    preconditions: Vec<JsonStep>,
+    #[serde(default)]
+    actions: Vec<JsonStep>,
+    #[serde(default)]
+    postconditions: Vec<JsonStep>,
+}
+
+#[derive(Deserialize)]
+struct JsonStep {
+    identifier: String,
+    value: Option<String>,
+    default: Option<String>,
+    #[serde(default)]
+    commands: Vec<JsonCommand>,
+}
+
+#[derive(Deserialize)]
+struct JsonCommand {
+    name: Option<String>,
+    tool: String,
+    #[serde(default)]
+    parameters: Vec<String>,
+    duration: Option<String>,
+}
+
+fn convert_steps(steps: Vec<JsonStep>) -> Result<Vec<TestStep>, TestCaseParseError> {
+    let mut result = Vec::new();
+
+    for step in steps {
+        if let Some(value) = step.value {
+            result.push(TestStep {
+                label: step.identifier,
+                kind: TestStepKind::Variable { value, default: step.default },
+            });
+            continue;
+        }
+
+        if step.commands.is_empty() {
+            return Err(TestCaseParseError::Json {
+                message: format!("Step '{}' has neither a 'value' nor 'commands'.", step.identifier),
+            });
+        }
+
+        for command in step.commands {
+            let duration = match command.duration {
+                Some(duration) => TestStepDuration::try_from(duration.as_str())
+                    .map_err(|message| TestCaseParseError::Json { message: format!("Step '{}': {message}", step.identifier) })?,
+                None => TestStepDuration::default(),
+            };
+
+            result.push(TestStep {
+                label: command.name.unwrap_or_else(|| Clone::clone(&step.identifier)),
+                kind: TestStepKind::Execute(ExecuteStep {
+                    tool: command.tool,
+                    params: command.parameters,
+                    duration,
+                }),
+            });
+        }
+    }
+
+    Ok(result)
+}
+
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_parse_the_json_form_like_the_text_form() -> Result<()> {
+        let json = r#"{
+           "name": "CC_Discovery",
+           "preconditions":[
+           ],
+           "actions":[
+           {"identifier":"File_Path", "result":"File_Path", "DSLStep":"File_Path: \"{File_Path}\" default \"/home/kali/CyberSec/Tools/CANtools/CC/cc_discovery.sh\"" , "value":"{File_Path}", "default":"/home/kali/CyberSec/Tools/CANtools/CC/cc_discovery.sh"},
+           {"identifier":"canIF", "result":"canIF", "DSLStep":"canIF: \"{canIF}\" default \"can0\"" , "value":"{canIF}", "default":"can0"},
+           {"identifier":"CanDiscoverySH", "result":"CanDiscoverySH", "DSLStep":"CanDiscoverySH: execute(tool:\"bash\", params:[\"{File_Path}\", \"-i\", \"{canIF}\"], duration:long)" , "commands":[{
+                        "name":"CanDiscoverySH",
+                        "result":"CanDiscoverySH"
+                        , "environment":"{ENVIRONMENT}"
+                        , "tool":"bash"
+                        , "parameters":["{File_Path}", "-i", "{canIF}"]
+                        , "duration":"long"
+                    }]}
+           ],
+           "postconditions":[
+           ]
+        }"#;
+
+        let csa = r#"
+            PreConditions:
+
+            Actions:
+                File_Path: "{File_Path}" default "/home/kali/CyberSec/Tools/CANtools/CC/cc_discovery.sh"
+                canIF: "{canIF}" default "can0"
+
+                CanDiscoverySH: execute(tool:"bash", params:["{File_Path}", "-i", "{canIF}"], duration:long)
+
+            PostConditions:
+        "#;
+
+        assert_that!(TestCase::parse_json(json)?, eq(TestCase::parse_csa("CC_Discovery", csa)?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_parse_the_alia_example_like_its_text_form() -> Result<()> {
+        let json = include_str!("../../../opendut-alia/dsl-example/CC_Discovery.json");
+        let csa = include_str!("../../../opendut-alia/dsl-example/CC_Discovery.csa");
+
+        let test_case = TestCase::parse_json(json)?;
+
+        assert_that!(test_case.name, eq("CC_Discovery.json"));
+        assert_that!(test_case, eq(TestCase::parse_csa("CC_Discovery.json", csa)?));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_reject_steps_without_value_or_commands() -> Result<()> {
+        let json = r#"{ "name": "Invalid", "actions": [{ "identifier": "Empty" }] }"#;
+
+        assert_that!(TestCase::parse_json(json), err(eq(TestCaseParseError::Json {
+            message: String::from("Step 'Empty' has neither a 'value' nor 'commands'."),
+        })));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,294 @@
+use std::collections::HashMap;
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
+mod csa;
+mod json;
+
+/// A test case of the ALIA/SKyBT test case DSL, consisting of steps, which are executed section by section.
+///
+/// Test cases are written either in the text form (`.csa` files):
+/// ```text
+/// PreConditions:
+///
+/// Actions:
+///     canIF: "{canIF}" default "can0"
+///     CanDiscovery: execute(tool:"bash", params:["cc_discovery.sh", "-i", "{canIF}"], duration:long)
+///
+/// PostConditions:
+/// ```
+/// or in the equivalent JSON form, see [`TestCase::parse_csa`] and [`TestCase::parse_json`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TestCase {
+    pub name: String,
+    pub pre_conditions: Vec<TestStep>,
+    pub actions: Vec<TestStep>,
+    pub post_conditions: Vec<TestStep>,
+}
+
+impl TestCase {
+    /// Parses the text form of a test case, as used in `.csa` files.
+    pub fn parse_csa(name: impl Into<String>, source: &str) -> Result<Self, TestCaseParseError> {
+        csa::parse(name.into(), source)
+    }
+
+    /// Parses the JSON form of a test case.
+    pub fn parse_json(source: &str) -> Result<Self, TestCaseParseError> {
+        json::parse(source)
+    }
+
+    /// All steps in the order of execution, together with their section.
+    pub fn steps(&self) -> impl Iterator<Item=(TestSection, &TestStep)> {
+        self.pre_conditions.iter().map(|step| (TestSection::PreConditions, step))
+            .chain(self.actions.iter().map(|step| (TestSection::Actions, step)))
+            .chain(self.post_conditions.iter().map(|step| (TestSection::PostConditions, step)))
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TestSection {
+    PreConditions,
+    Actions,
+    PostConditions,
+}
+
+impl fmt::Display for TestSection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TestSection::PreConditions => write!(f, "PreConditions"),
+            TestSection::Actions => write!(f, "Actions"),
+            TestSection::PostConditions => write!(f, "PostConditions"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TestStep {
+    pub label: String,
+    pub kind: TestStepKind,
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TestStepKind {
+    /// Defines the variable named by the label of the step, e.g. `canIF: "{canIF}" default "can0"`.
+    /// Placeholders in `value`, which cannot be resolved, fall back to `default`.
+    Variable {
+        value: String,
+        default: Option<String>,
+    },
+    /// Runs a tool, e.g. `execute(tool:"bash", params:["run.sh"], duration:long)`.
+    Execute(ExecuteStep),
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct ExecuteStep {
+    pub tool: String,
+    pub params: Vec<String>,
+    pub duration: TestStepDuration,
+}
+
+impl ExecuteStep {
+    /// Steps, whose first parameter is `evaluate` or `evaluateabort`, check an expected result instead of performing an action.
+    pub fn evaluation(&self) -> Option<Evaluation> {
+        let first_param = self.params.first()?;
+        if first_param.eq_ignore_ascii_case("evaluate") {
+            Some(Evaluation::Evaluate)
+        } else if first_param.eq_ignore_ascii_case("evaluateabort") {
+            Some(Evaluation::EvaluateAbort)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Evaluation {
+    /// A failed evaluation fails the test case, but the remaining steps are still executed.
+    Evaluate,
+    /// A failed evaluation fails the test case and aborts the remaining actions.
+    EvaluateAbort,
+}
+
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TestStepDuration {
+    /// The step is expected to finish quickly and is cancelled after a timeout.
+    #[default]
+    Short,
+    /// The step may run for a long time and is not cancelled.
+    Long,
+}
+
+impl TryFrom<&str> for TestStepDuration {
+    type Error = String;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        match value {
+            "short" => Ok(TestStepDuration::Short),
+            "long" => Ok(TestStepDuration::Long),
+            _ => Err(format!("Unknown duration '{value}'. Expected 'short' or 'long'.")),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug, Eq, PartialEq)]
+pub enum TestCaseParseError {
+    #[error("Line {line}: {message}")]
+    Syntax {
+        line: usize,
+        message: String,
+    },
+    #[error("Invalid JSON test case: {message}")]
+    Json {
+        message: String,
+    },
+}
+
+/// Values of the variables of a test case, which replace the `{name}` placeholders in the steps.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct TestParameters {
+    values: HashMap<String, String>,
+}
+
+impl TestParameters {
+    pub fn new(values: HashMap<String, String>) -> Self {
+        Self { values }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.values.get(name).map(String::as_str)
+    }
+
+    pub fn set(&mut self, name: impl Into<String>, value: impl Into<String>) {
+        self.values.insert(name.into(), value.into());
+    }
+
+    /// Replaces all `{name}` placeholders in `template` by the value of the respective parameter.
+    pub fn substitute(&self, template: &str) -> Result<String, UnresolvedPlaceholder> {
+        let mut result = String::with_capacity(template.len());
+        let mut rest = template;
+
+        while let Some(start) = rest.find('{') {
+            let Some(length) = rest[start..].find('}') else {
+                break;
+            };
+            let name = &rest[start + 1..start + length];
+            let value = self.get(name)
+                .ok_or_else(|| UnresolvedPlaceholder { name: name.to_owned() })?;
+
+            result.push_str(&rest[..start]);
+            result.push_str(value);
+            rest = &rest[start + length + 1..];
+        }
+        result.push_str(rest);
+
+        Ok(result)
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug, Eq, PartialEq)]
+#[error("No value for placeholder '{{{name}}}'.")]
+pub struct UnresolvedPlaceholder {
+    pub name: String,
+}
+
+/// Verdict of a test step or test case. The verdict of a test case is the most severe verdict of its steps,
+/// so a test case is only `Skipped`, if none of its steps were executed.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum TestVerdict {
+    /// The step was not executed, because the test case was aborted before.
+    Skipped,
+    Passed,
+    /// An evaluation did not find the expected result.
+    Failed,
+    /// A step could not be executed or did not succeed, so the test case is inconclusive.
+    Error,
+}
+
+impl fmt::Display for TestVerdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TestVerdict::Skipped => write!(f, "Skipped"),
+            TestVerdict::Passed => write!(f, "Passed"),
+            TestVerdict::Failed => write!(f, "Failed"),
+            TestVerdict::Error => write!(f, "Error"),
+        }
+    }
+}
+
+/// Structured result of executing a [`TestCase`].
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TestCaseReport {
+    pub name: String,
+    pub verdict: TestVerdict,
+    pub steps: Vec<TestStepReport>,
+}
+
+impl TestCaseReport {
+    pub fn new(name: String, steps: Vec<TestStepReport>) -> Self {
+        let verdict = steps.iter()
+            .map(|step| step.verdict)
+            .max()
+            .unwrap_or(TestVerdict::Skipped);
+
+        Self { name, verdict, steps }
+    }
+}
+
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct TestStepReport {
+    pub section: TestSection,
+    pub label: String,
+    pub verdict: TestVerdict,
+    /// The command line after substituting the parameters. Only set for executed steps.
+    pub command: Option<Vec<String>>,
+    pub exit_code: Option<i32>,
+    pub duration_ms: u64,
+    pub message: Option<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use googletest::prelude::*;
+
+    use super::*;
+
+    #[test]
+    fn should_substitute_placeholders() -> Result<()> {
+        let mut parameters = TestParameters::default();
+        parameters.set("canIF", "vcan0");
+        parameters.set("File_Path", "/opt/cc_discovery.sh");
+
+        assert_that!(parameters.substitute("{File_Path} -i {canIF}"), ok(eq("/opt/cc_discovery.sh -i vcan0")));
+        assert_that!(parameters.substitute("no placeholders"), ok(eq("no placeholders")));
+        assert_that!(parameters.substitute("unclosed {brace"), ok(eq("unclosed {brace")));
+        assert_that!(parameters.substitute("{unknown}"), err(eq(UnresolvedPlaceholder { name: String::from("unknown") })));
+
+        Ok(())
+    }
+
+    #[test]
+    fn should_determine_the_most_severe_verdict() -> Result<()> {
+        let step = |verdict| TestStepReport {
+            section: TestSection::Actions,
+            label: String::from("Step"),
+            verdict,
+            command: None,
+            exit_code: None,
+            duration_ms: 0,
+            message: None,
+        };
+
+        assert_that!(TestCaseReport::new(String::from("Empty"), vec![]).verdict, eq(TestVerdict::Skipped));
+        assert_that!(TestCaseReport::new(String::from("Skipped"), vec![step(TestVerdict::Skipped), step(TestVerdict::Skipped)]).verdict, eq(TestVerdict::Skipped));
+        assert_that!(TestCaseReport::new(String::from("Passed"), vec![step(TestVerdict::Passed), step(TestVerdict::Skipped)]).verdict, eq(TestVerdict::Passed));
+        assert_that!(TestCaseReport::new(String::from("Failed"), vec![step(TestVerdict::Passed), step(TestVerdict::Failed), step(TestVerdict::Skipped)]).verdict, eq(TestVerdict::Failed));
+        assert_that!(TestCaseReport::new(String::from("Error"), vec![step(TestVerdict::Failed), step(TestVerdict::Error)]).verdict, eq(TestVerdict::Error));
+
+        Ok(())
+    }
+}
@@ -4,6 +4,7 @@ use std::ops::Not;
 use std::str::FromStr;
 use serde::{Deserialize, Serialize};
 use url::Url;
+use uuid::Uuid;
 
 #[derive(Clone, Debug, Hash, Eq, PartialEq, Serialize, Deserialize)]
 #[serde(transparent)]
@@ -76,6 +77,30 @@ impl FromStr for Certificate {
     }
 }
 
+#[derive(Clone, PartialEq, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct PrivateKey(pub pem::Pem);
+impl PrivateKey {
+    pub fn encode_as_string(&self) -> String {
+        let encode_config = pem::EncodeConfig::default()
+            .set_line_ending(pem::LineEnding::LF);
+
+        pem::encode_config(&self.0, encode_config)
+    }
+}
+impl Debug for PrivateKey {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.write_str("PrivateKey([redacted])")
+    }
+}
+
+/// Client certificate and its private key, with which a client authenticates via mutual TLS.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ClientIdentity {
+    pub certificate: Certificate,
+    pub private_key: PrivateKey,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 pub struct CanSamplePoint {
     sample_point_times_1000: u32
@@ -125,6 +150,111 @@ pub enum CanSamplePointError {
     OutOfRangeInt { value: String },
 }
 
+/// Matches CAN frames, whose ID equals `id` in all bits set in `mask`.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub struct CanIdFilter {
+    id: u32,
+    mask: u32,
+    extended: bool,
+}
+impl CanIdFilter {
+    pub const STANDARD_ID_MASK: u32 = 0x7FF;
+    pub const EXTENDED_ID_MASK: u32 = 0x1FFF_FFFF;
+
+    pub fn new(id: u32, mask: u32, extended: bool) -> Result<Self, IllegalCanIdFilter> {
+        let max = if extended { Self::EXTENDED_ID_MASK } else { Self::STANDARD_ID_MASK };
+        if id > max {
+            Err(IllegalCanIdFilter::IdOutOfRange { id, max })
+        } else if mask > max {
+            Err(IllegalCanIdFilter::MaskOutOfRange { mask, max })
+        } else {
+            Ok(Self { id, mask, extended })
+        }
+    }
+
+    /// Matches exactly one standard (11 bit) or extended (29 bit) CAN ID.
+    pub fn exact(id: u32, extended: bool) -> Result<Self, IllegalCanIdFilter> {
+        let mask = if extended { Self::EXTENDED_ID_MASK } else { Self::STANDARD_ID_MASK };
+        Self::new(id, mask, extended)
+    }
+
+    pub fn id(&self) -> u32 {
+        self.id
+    }
+
+    pub fn mask(&self) -> u32 {
+        self.mask
+    }
+
+    pub fn is_extended(&self) -> bool {
+        self.extended
+    }
+}
+
+impl fmt::Display for CanIdFilter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.extended {
+            write!(f, "{:08X}:{:08X}", self.id, self.mask)
+        } else {
+            write!(f, "{:03X}:{:03X}", self.id, self.mask)
+        }
+    }
+}
+
+/// Parses filters in the notation of `candump`, i.e. `<id>:<mask>` in hexadecimal.
+/// IDs with more than three digits denote extended CAN IDs. If the mask is omitted, the ID has to match exactly.
+impl FromStr for CanIdFilter {
+    type Err = IllegalCanIdFilter;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let (id, mask) = match value.split_once(':') {
+            Some((id, mask)) => (id, Some(mask)),
+            None => (value, None),
+        };
+        let parse = |hex: &str| u32::from_str_radix(hex, 16)
+            .map_err(|_| IllegalCanIdFilter::InvalidNotation { value: String::from(value) });
+
+        let extended = id.len() > 3;
+        let id = parse(id)?;
+        match mask {
+            Some(mask) => Self::new(id, parse(mask)?, extended),
+            None => Self::exact(id, extended),
+        }
+    }
+}
+
+#[derive(thiserror::Error, Clone, Debug)]
+pub enum IllegalCanIdFilter {
+    #[error("CAN ID filter '{value}' is invalid. Expected the format '<id>:<mask>' with hexadecimal numbers.")]
+    InvalidNotation { value: String },
+    #[error("CAN ID {id:#X} exceeds the maximum {max:#X}.")]
+    IdOutOfRange { id: u32, max: u32 },
+    #[error("CAN ID mask {mask:#X} exceeds the maximum {max:#X}.")]
+    MaskOutOfRange { mask: u32, max: u32 },
+}
+
+/// Selects which CAN frames received on a device interface are routed into the cluster.
+/// A frame is routed, if it matches any of the `accept` filters (or `accept` is empty) and none of the `reject` filters.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub struct CanFrameFilter {
+    pub accept: Vec<CanIdFilter>,
+    pub reject: Vec<CanIdFilter>,
+}
+
+impl CanFrameFilter {
+    pub fn is_empty(&self) -> bool {
+        self.accept.is_empty() && self.reject.is_empty()
+    }
+}
+
+/// Limits the number of CAN frames received on a device interface, which are routed into the cluster.
+#[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
+pub struct CanRateLimit {
+    pub frames_per_second: u32,
+    /// Number of frames, which may exceed the rate for a short time.
+    pub burst: u32,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize, Hash)]
 pub enum NetworkInterfaceConfiguration {
     Ethernet,
@@ -134,6 +264,10 @@ pub enum NetworkInterfaceConfiguration {
         fd: bool,
         data_bitrate: u32,
         data_sample_point: CanSamplePoint,
+        #[serde(default)]
+        filter: CanFrameFilter,
+        #[serde(default)]
+        rate_limit: Option<CanRateLimit>,
     },
 }
 
@@ -146,8 +280,22 @@ impl fmt::Display for NetworkInterfaceConfiguration {
                 sample_point, 
                 fd, 
                 data_bitrate, 
-                data_sample_point 
-            } => write!(f, "CAN [bitrate: {bitrate}, sample point: {sample_point}, fd: {fd}, data bitrate: {data_bitrate}, data sample point: {data_sample_point}]"),
+                data_sample_point,
+                filter,
+                rate_limit,
+            } => {
+                write!(f, "CAN [bitrate: {bitrate}, sample point: {sample_point}, fd: {fd}, data bitrate: {data_bitrate}, data sample point: {data_sample_point}")?;
+                if filter.accept.is_empty().not() {
+                    write!(f, ", accept: {}", filter.accept.iter().map(ToString::to_string).collect::<Vec<_>>().join(" "))?;
+                }
+                if filter.reject.is_empty().not() {
+                    write!(f, ", reject: {}", filter.reject.iter().map(ToString::to_string).collect::<Vec<_>>().join(" "))?;
+                }
+                if let Some(CanRateLimit { frames_per_second, burst }) = rate_limit {
+                    write!(f, ", rate limit: {frames_per_second} frames/s (burst: {burst})")?;
+                }
+                write!(f, "]")
+            }
         }
         
     }
@@ -190,6 +338,11 @@ impl Debug for ClientSecret {
 impl ClientSecret {
     pub const MIN_LENGTH: usize = 20;
     pub const MAX_LENGTH: usize = 512;
+
+    pub fn random() -> Self {
+        Self(format!("{}{}", Uuid::new_v4().simple(), Uuid::new_v4().simple()))
+    }
+
     pub fn value(self) -> String {
         self.0
     }
@@ -421,11 +574,13 @@ impl AuthConfig {
 
 #[cfg(test)]
 mod tests {
+    use std::str::FromStr;
+
     use googletest::assert_that;
-    use googletest::matchers::eq;
+    use googletest::matchers::{eq, err, ok};
     use url::Url;
 
-    use crate::util::net::{AuthConfig, ClientCredentials, ClientId, ClientSecret, OAuthScope};
+    use crate::util::net::{AuthConfig, CanIdFilter, ClientCredentials, ClientId, ClientSecret, OAuthScope};
 
     #[test]
     pub fn test_create_auth_config() {
@@ -444,4 +599,16 @@ mod tests {
             
         }));
     }
+
+    #[test]
+    pub fn test_parse_can_id_filter() -> googletest::Result<()> {
+        assert_that!(CanIdFilter::from_str("123:7F0"), ok(eq(CanIdFilter::new(0x123, 0x7F0, false)?)));
+        assert_that!(CanIdFilter::from_str("123"), ok(eq(CanIdFilter::new(0x123, CanIdFilter::STANDARD_ID_MASK, false)?)));
+        assert_that!(CanIdFilter::from_str("00012345:1FFFFF00"), ok(eq(CanIdFilter::new(0x12345, 0x1FFF_FF00, true)?)));
+        assert_that!(CanIdFilter::from_str("0123:7FF")?.to_string(), eq("00000123:000007FF"));
+
+        assert_that!(CanIdFilter::from_str("800:7FF"), err(googletest::matchers::anything()));
+        assert_that!(CanIdFilter::from_str("12G"), err(googletest::matchers::anything()));
+        Ok(())
+    }
 }
@@ -94,6 +94,25 @@ mod auth_tests {
         assert!(filtered_client_list.is_empty());
     }
 
+    #[rstest]
+    #[tokio::test]
+    #[ignore]
+    async fn test_rotate_oidc_client_secret(#[future] registration_client: RegistrationClientRef) {
+        /*
+         * This test is ignored because it requires a running keycloak server from the test environment.
+         * To run this test, execute the following command: cargo test -- --include-ignored
+         */
+        let client: RegistrationClientRef = registration_client.await;
+        let resource_id = Id::random();
+        let credentials = client.register_new_client_for_user(resource_id, String::from("rotateTest")).await.unwrap();
+
+        let rotated_credentials = client.rotate_client_secret_by_resource_id(resource_id).await.unwrap();
+        assert_that!(rotated_credentials.client_id, eq(credentials.client_id));
+        assert_ne!(rotated_credentials.client_secret, credentials.client_secret);
+
+        client.delete_client_by_resource_id(resource_id).await.unwrap();
+    }
+
     /*
     use opendut_auth::registration::resources::ResourceHomeUrl;
  
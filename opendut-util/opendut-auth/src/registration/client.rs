@@ -7,7 +7,7 @@ use oauth2::{HttpRequest, HttpResponse};
 use openidconnect::{ClientName, ClientUrl};
 use openidconnect::core::{CoreClientRegistrationRequest, CoreGrantType};
 use openidconnect::registration::EmptyAdditionalClientMetadata;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use tracing::error;
 use url::Url;
 use opendut_types::resources::Id;
@@ -192,6 +192,65 @@ impl RegistrationClient {
             .map_err(|error| RegistrationClientError::RequestError { error: error.to_string(), cause: error.into() })
     }
 
+    /// Generates a new secret for the client belonging to the resource. The previous secret becomes invalid immediately.
+    pub async fn rotate_client_secret_by_resource_id(&self, resource_id: Id) -> Result<ClientCredentials, RegistrationClientError> {
+        if self.config.peer_credentials.is_some() {
+            return Err(RegistrationClientError::InvalidConfiguration { error: String::from("Client secrets cannot be rotated, while fixed peer credentials are configured.") });
+        }
+
+        let client = self.find_client_by_resource_id(resource_id).await?;
+
+        let client_secret_uri = format!("clients/{}/client-secret", client.id);
+        let client_secret_url = self.config.issuer_admin_url.join(&client_secret_uri)
+            .map_err(|cause| RegistrationClientError::InvalidConfiguration { error: format!("Invalid admin api endpoint for issuer. {}", cause) })?;
+
+        let request = self.create_http_request_with_auth_token(&client_secret_url, http::Method::POST).await?;
+
+        let response = self.inner.reqwest_client.async_http_client(request).await
+            .map_err(|error| RegistrationClientError::RequestError { error: "OIDC client secret rotation request failed!".to_string(), cause: Box::new(error) })?;
+        if response.status_code.is_success().not() {
+            return Err(RegistrationClientError::InvalidConfiguration { error: format!("Rotating the secret of client '{}' failed with status {}.", client.client_id, response.status_code) });
+        }
+
+        let credential: ClientCredentialRepresentation = serde_json::from_slice(&response.body)
+            .map_err(|cause| RegistrationClientError::InvalidConfiguration { error: format!("Could not deserialize response body. {}", cause) })?;
+
+        Ok(ClientCredentials {
+            client_id: ClientId(client.client_id),
+            client_secret: ClientSecret(credential.value),
+        })
+    }
+
+    /// Replaces the secret of the client with the given one. The previous secret becomes invalid immediately.
+    pub async fn set_client_secret(&self, client: &Client, client_secret: ClientSecret) -> Result<(), RegistrationClientError> {
+        if self.config.peer_credentials.is_some() {
+            return Err(RegistrationClientError::InvalidConfiguration { error: String::from("Client secrets cannot be rotated, while fixed peer credentials are configured.") });
+        }
+
+        let client_uri = format!("clients/{}", client.id);
+        let client_url = self.config.issuer_admin_url.join(&client_uri)
+            .map_err(|cause| RegistrationClientError::InvalidConfiguration { error: format!("Invalid admin api endpoint for issuer. {}", cause) })?;
+
+        let mut request = self.create_http_request_with_auth_token(&client_url, http::Method::PUT).await?;
+        request.headers.insert(http::header::CONTENT_TYPE, HeaderValue::from_static("application/json"));
+        request.body = serde_json::to_vec(&ClientSecretRepresentation { secret: client_secret.value() })
+            .map_err(|cause| RegistrationClientError::InvalidConfiguration { error: format!("Could not serialize request body. {}", cause) })?;
+
+        let response = self.inner.reqwest_client.async_http_client(request).await
+            .map_err(|error| RegistrationClientError::RequestError { error: "OIDC client secret update request failed!".to_string(), cause: Box::new(error) })?;
+        if response.status_code.is_success().not() {
+            return Err(RegistrationClientError::InvalidConfiguration { error: format!("Setting the secret of client '{}' failed with status {}.", client.client_id, response.status_code) });
+        }
+        Ok(())
+    }
+
+    pub async fn find_client_by_resource_id(&self, resource_id: Id) -> Result<Client, RegistrationClientError> {
+        let clients = self.list_clients().await?;
+        clients.filter_clients_by_resource_id(resource_id)
+            .into_iter().next()
+            .ok_or(RegistrationClientError::ClientNotFound)
+    }
+
     async fn create_http_request_with_auth_token(&self, issuer_remote_url: &Url, http_method: http::Method) -> Result<HttpRequest, RegistrationClientError> {
         let mut headers = HeaderMap::new();
         let access_token = self.inner.get_token().await
@@ -236,6 +295,18 @@ impl Clients {
 #[derive(Deserialize, Clone)]
 #[serde(rename_all = "camelCase")]
 pub struct Client {
+    /// Internal ID, which the identity provider uses to address the client in its admin API.
+    pub id: String,
     pub client_id: String,
     base_url: Option<String>,
 }
+
+#[derive(Deserialize)]
+struct ClientCredentialRepresentation {
+    value: String,
+}
+
+#[derive(Serialize)]
+struct ClientSecretRepresentation {
+    secret: String,
+}